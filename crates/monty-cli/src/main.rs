@@ -6,8 +6,9 @@ use std::{
 
 use clap::Parser;
 use monty::{
-    LimitedTracker, MontyObject, MontyRepl, MontyRun, NameLookupResult, NoLimitTracker, PrintWriter,
-    ReplContinuationMode, ResourceLimits, ResourceTracker, RunProgress, detect_repl_continuation_mode,
+    ExcType, LimitedTracker, MontyException, MontyObject, MontyRepl, MontyRun, MountFs, NameLookupResult,
+    NoLimitTracker, PrintWriter, ReplContinuationMode, ReplProgress, ReplStartError, ResourceLimits, ResourceTracker,
+    RunProgress, detect_repl_continuation_mode,
 };
 use rustyline::{DefaultEditor, error::ReadlineError};
 // disabled due to format failing on https://github.com/pydantic/monty/pull/75 where CI and local wanted imports ordered differently
@@ -30,7 +31,7 @@ const ARROW: &str = "❯";
 /// Monty — a sandboxed Python interpreter written in Rust.
 ///
 /// - `monty` starts an empty interactive REPL
-/// - `monty <file>` runs the file in script mode
+/// - `monty <file> [args...]` runs the file in script mode, with `sys.argv` set to `[file, args...]`
 /// - `monty -c <cmd>` executes `<cmd>` as a Python program
 /// - `monty -i` starts an empty interactive REPL
 /// - `monty -i <file>` seeds the REPL with file contents
@@ -52,6 +53,10 @@ struct Cli {
     /// Python file to execute.
     file: Option<String>,
 
+    /// Arguments passed to the script in `sys.argv`.
+    #[arg(trailing_var_arg = true, allow_hyphen_values = true)]
+    args: Vec<String>,
+
     /// Maximum number of heap allocations before execution is terminated.
     #[arg(long)]
     max_allocations: Option<usize>,
@@ -71,6 +76,18 @@ struct Cli {
     /// Maximum call-stack depth (defaults to 1000 when any limit is set).
     #[arg(long)]
     max_recursion_depth: Option<usize>,
+
+    /// Expose a host directory to the sandbox, read-only with a `:ro` suffix (repeatable).
+    #[arg(long = "mount", value_name = "HOST_DIR:/SANDBOX_PATH[:ro]", value_parser = parse_mount)]
+    mounts: Vec<MountSpec>,
+}
+
+/// A `--mount` argument: a host directory and where it appears in the sandbox.
+#[derive(Debug, Clone)]
+struct MountSpec {
+    host_dir: String,
+    sandbox_path: String,
+    read_only: bool,
 }
 
 impl Cli {
@@ -106,6 +123,23 @@ impl Cli {
         }
         Some(limits)
     }
+
+    /// Builds a `MountFs` from the `--mount` arguments.
+    ///
+    /// Returns `Ok(None)` when nothing was mounted, so OS calls stay unsupported,
+    /// or an error message if a host directory can't be mounted.
+    fn mount_fs(&self) -> Result<Option<MountFs>, String> {
+        if self.mounts.is_empty() {
+            return Ok(None);
+        }
+        let mut mount_fs = MountFs::new();
+        for spec in &self.mounts {
+            mount_fs = mount_fs
+                .mount(&spec.host_dir, &spec.sandbox_path, spec.read_only)
+                .map_err(|err| format!("cannot mount {}: {err}", spec.host_dir))?;
+        }
+        Ok(Some(mount_fs))
+    }
 }
 
 const EXT_FUNCTIONS: bool = false;
@@ -115,6 +149,13 @@ fn main() -> ExitCode {
 
     let type_check_enabled = cli.type_check;
     let limits = cli.resource_limits();
+    let mount_fs = match cli.mount_fs() {
+        Ok(mount_fs) => mount_fs,
+        Err(err) => {
+            eprintln!("{BOLD_RED}error{RESET}: {err}");
+            return ExitCode::FAILURE;
+        }
+    };
 
     if let Some(cmd) = cli.command {
         if cli.file.is_some() {
@@ -122,9 +163,10 @@ fn main() -> ExitCode {
             return ExitCode::FAILURE;
         }
         return if cli.interactive {
-            dispatch_repl("<string>", &cmd, limits)
+            dispatch_repl("<string>", &cmd, limits, mount_fs)
         } else {
-            dispatch_script("<string>", cmd, type_check_enabled, limits)
+            let argv = vec!["-c".to_owned()];
+            dispatch_script("<string>", cmd, argv, type_check_enabled, limits, mount_fs)
         };
     }
 
@@ -137,13 +179,14 @@ fn main() -> ExitCode {
             }
         };
         return if cli.interactive {
-            dispatch_repl(file_path, &code, limits)
+            dispatch_repl(file_path, &code, limits, mount_fs)
         } else {
-            dispatch_script(file_path, code, type_check_enabled, limits)
+            let argv = std::iter::once(file_path.to_owned()).chain(cli.args).collect();
+            dispatch_script(file_path, code, argv, type_check_enabled, limits, mount_fs)
         };
     }
 
-    dispatch_repl("repl.py", "", limits)
+    dispatch_repl("repl.py", "", limits, mount_fs)
 }
 
 /// Dispatches script execution with either `LimitedTracker` or `NoLimitTracker`.
@@ -153,22 +196,31 @@ fn main() -> ExitCode {
 fn dispatch_script(
     file_path: &str,
     code: String,
+    argv: Vec<String>,
     type_check_enabled: bool,
     limits: Option<ResourceLimits>,
+    mount_fs: Option<MountFs>,
 ) -> ExitCode {
     if let Some(limits) = limits {
-        run_script(file_path, code, type_check_enabled, LimitedTracker::new(limits))
+        run_script(
+            file_path,
+            code,
+            argv,
+            type_check_enabled,
+            LimitedTracker::new(limits),
+            mount_fs,
+        )
     } else {
-        run_script(file_path, code, type_check_enabled, NoLimitTracker)
+        run_script(file_path, code, argv, type_check_enabled, NoLimitTracker, mount_fs)
     }
 }
 
 /// Dispatches REPL startup with either `LimitedTracker` or `NoLimitTracker`.
-fn dispatch_repl(file_path: &str, code: &str, limits: Option<ResourceLimits>) -> ExitCode {
+fn dispatch_repl(file_path: &str, code: &str, limits: Option<ResourceLimits>, mount_fs: Option<MountFs>) -> ExitCode {
     if let Some(limits) = limits {
-        run_repl(file_path, code, LimitedTracker::new(limits))
+        run_repl(file_path, code, LimitedTracker::new(limits), mount_fs)
     } else {
-        run_repl(file_path, code, NoLimitTracker)
+        run_repl(file_path, code, NoLimitTracker, mount_fs)
     }
 }
 
@@ -177,11 +229,18 @@ fn dispatch_repl(file_path: &str, code: &str, limits: Option<ResourceLimits>) ->
 /// This path keeps the existing CLI behavior: run type-checking for visibility,
 /// compile the file as a full module, and execute it either through direct
 /// execution or through the suspendable progress loop when external functions
-/// are enabled.
+/// are enabled or directories are mounted.
 ///
-/// Returns `ExitCode::SUCCESS` for successful execution and
-/// `ExitCode::FAILURE` for parse/type/runtime failures.
-fn run_script(file_path: &str, code: String, type_check_enabled: bool, tracker: impl ResourceTracker) -> ExitCode {
+/// Returns `ExitCode::SUCCESS` for successful execution, `ExitCode::FAILURE` for
+/// parse/type/runtime failures, and the script's exit code when it raises `SystemExit`.
+fn run_script(
+    file_path: &str,
+    code: String,
+    argv: Vec<String>,
+    type_check_enabled: bool,
+    tracker: impl ResourceTracker,
+    mut mount_fs: Option<MountFs>,
+) -> ExitCode {
     if type_check_enabled {
         let start = Instant::now();
         if let Some(failure) = type_check(&SourceFile::new(&code, file_path), None).unwrap() {
@@ -202,29 +261,23 @@ fn run_script(file_path: &str, code: String, type_check_enabled: bool, tracker:
     let input_names = vec![];
     let inputs = vec![];
 
-    let runner = match MontyRun::new(code, file_path, input_names) {
+    let mut runner = match MontyRun::new(code, file_path, input_names) {
         Ok(ex) => ex,
         Err(err) => {
             eprintln!("{BOLD_RED}error{RESET}:\n{err}");
             return ExitCode::FAILURE;
         }
     };
+    runner.set_argv(argv);
 
-    if EXT_FUNCTIONS {
+    if EXT_FUNCTIONS || mount_fs.is_some() {
         let start = Instant::now();
         let progress = match runner.start(inputs, tracker, PrintWriter::Stdout) {
             Ok(p) => p,
-            Err(err) => {
-                let elapsed = start.elapsed();
-                eprintln!(
-                    "{DIM}{}{RESET} {BOLD_CYAN}{ARROW}{RESET} {BOLD_RED}error{RESET}: {err}",
-                    FormattedDuration(elapsed)
-                );
-                return ExitCode::FAILURE;
-            }
+            Err(err) => return script_failure(&err, start.elapsed()),
         };
 
-        match run_until_complete(progress) {
+        match run_until_complete(progress, mount_fs.as_mut()) {
             Ok(value) => {
                 let elapsed = start.elapsed();
                 eprintln!(
@@ -233,27 +286,13 @@ fn run_script(file_path: &str, code: String, type_check_enabled: bool, tracker:
                 );
                 ExitCode::SUCCESS
             }
-            Err(err) => {
-                let elapsed = start.elapsed();
-                eprintln!(
-                    "{DIM}{}{RESET} {BOLD_CYAN}{ARROW}{RESET} {BOLD_RED}error{RESET}: {err}",
-                    FormattedDuration(elapsed)
-                );
-                ExitCode::FAILURE
-            }
+            Err(err) => script_failure(&err, start.elapsed()),
         }
     } else {
         let start = Instant::now();
         let value = match runner.run(inputs, tracker, PrintWriter::Stdout) {
             Ok(p) => p,
-            Err(err) => {
-                let elapsed = start.elapsed();
-                eprintln!(
-                    "{DIM}{}{RESET} {BOLD_CYAN}{ARROW}{RESET} {BOLD_RED}error{RESET}: {err}",
-                    FormattedDuration(elapsed)
-                );
-                return ExitCode::FAILURE;
-            }
+            Err(err) => return script_failure(&err, start.elapsed()),
         };
         let elapsed = start.elapsed();
         eprintln!(
@@ -264,6 +303,31 @@ fn run_script(file_path: &str, code: String, type_check_enabled: bool, tracker:
     }
 }
 
+/// Reports the exception that ended a script, returning the process exit code.
+fn script_failure(err: &MontyException, elapsed: Duration) -> ExitCode {
+    if let Some(exit_code) = system_exit(err) {
+        return exit_code;
+    }
+    eprintln!(
+        "{DIM}{}{RESET} {BOLD_CYAN}{ARROW}{RESET} {BOLD_RED}error{RESET}: {err}",
+        FormattedDuration(elapsed)
+    );
+    ExitCode::FAILURE
+}
+
+/// Handles an uncaught `SystemExit` (e.g. from `sys.exit()`), a clean exit rather than an error.
+///
+/// Prints the message unless it is an integer code, and returns the code to exit the process
+/// with. Returns `None` for every other exception.
+fn system_exit(err: &MontyException) -> Option<ExitCode> {
+    let code = err.exit_code()?;
+    if let Some(message) = err.exit_message() {
+        eprintln!("{message}");
+    }
+    // as for a process's exit status, only the low byte is kept
+    Some(ExitCode::from(u8::try_from(code & 0xff).expect("masked to a byte")))
+}
+
 /// Starts an interactive line-by-line REPL session.
 ///
 /// Initializes `MontyRepl` once and incrementally feeds entered snippets without
@@ -272,13 +336,16 @@ fn run_script(file_path: &str, code: String, type_check_enabled: bool, tracker:
 /// - `❯ ` for a new statement
 /// - `… ` for continuation lines
 ///
-/// Returns `ExitCode::SUCCESS` on EOF or `exit`, and `ExitCode::FAILURE` on
-/// initialization or I/O errors.
-fn run_repl(file_path: &str, code: &str, tracker: impl ResourceTracker) -> ExitCode {
+/// Returns `ExitCode::SUCCESS` on EOF or `exit`, the exit code of a snippet raising
+/// `SystemExit`, and `ExitCode::FAILURE` on initialization or I/O errors.
+fn run_repl(file_path: &str, code: &str, tracker: impl ResourceTracker, mut mount_fs: Option<MountFs>) -> ExitCode {
     let mut repl = MontyRepl::new(file_path, tracker);
 
     if !code.is_empty() {
-        execute_repl_snippet(&mut repl, code);
+        repl = match execute_repl_snippet(repl, code, mount_fs.as_mut()) {
+            Ok(repl) => repl,
+            Err(exit_code) => return exit_code,
+        };
     }
 
     eprintln!("Monty v{} REPL. Type `exit` to exit.", env!("CARGO_PKG_VERSION"));
@@ -329,7 +396,10 @@ fn run_repl(file_path: &str, code: &str, tracker: impl ResourceTracker) -> ExitC
 
         if continuation_mode == ReplContinuationMode::IncompleteBlock && snippet.is_empty() {
             let _ = rl.add_history_entry(pending_snippet.trim_end());
-            execute_repl_snippet(&mut repl, &pending_snippet);
+            repl = match execute_repl_snippet(repl, &pending_snippet, mount_fs.as_mut()) {
+                Ok(repl) => repl,
+                Err(exit_code) => return exit_code,
+            };
             pending_snippet.clear();
             continuation_mode = ReplContinuationMode::Complete;
             continue;
@@ -342,7 +412,10 @@ fn run_repl(file_path: &str, code: &str, tracker: impl ResourceTracker) -> ExitC
                     continue;
                 }
                 let _ = rl.add_history_entry(pending_snippet.trim_end());
-                execute_repl_snippet(&mut repl, &pending_snippet);
+                repl = match execute_repl_snippet(repl, &pending_snippet, mount_fs.as_mut()) {
+                    Ok(repl) => repl,
+                    Err(exit_code) => return exit_code,
+                };
                 pending_snippet.clear();
                 continuation_mode = ReplContinuationMode::Complete;
             }
@@ -357,17 +430,66 @@ fn run_repl(file_path: &str, code: &str, tracker: impl ResourceTracker) -> ExitC
 }
 
 /// Executes one collected REPL snippet, printing the result or error.
-fn execute_repl_snippet(repl: &mut MontyRepl<impl ResourceTracker>, snippet: &str) {
-    match repl.feed_run(snippet, vec![], PrintWriter::Stdout) {
+///
+/// OS calls are resolved against `mount_fs` when directories are mounted.
+/// Returns the REPL to feed the next snippet to, or the exit code to end the session
+/// with when the snippet raised `SystemExit`.
+fn execute_repl_snippet<T: ResourceTracker>(
+    mut repl: MontyRepl<T>,
+    snippet: &str,
+    mount_fs: Option<&mut MountFs>,
+) -> Result<MontyRepl<T>, ExitCode> {
+    let result = if let Some(mount_fs) = mount_fs {
+        let (next_repl, result) = feed_with_mounts(repl, snippet, mount_fs);
+        repl = next_repl;
+        result
+    } else {
+        repl.feed_run(snippet, vec![], PrintWriter::Stdout)
+    };
+    match result {
         Ok(output) => {
             if output != MontyObject::None {
                 println!("{output}");
             }
         }
         Err(err) => {
+            if let Some(exit_code) = system_exit(&err) {
+                return Err(exit_code);
+            }
             eprintln!("{BOLD_RED}error{RESET}: {err}");
         }
     }
+    Ok(repl)
+}
+
+/// Runs a REPL snippet to completion, resolving OS calls against the mounted directories.
+///
+/// Returns the REPL alongside the snippet's value, or the exception it raised; unsupported
+/// suspend points (external functions or async futures) are a `RuntimeError`.
+fn feed_with_mounts<T: ResourceTracker>(
+    repl: MontyRepl<T>,
+    snippet: &str,
+    mount_fs: &mut MountFs,
+) -> (MontyRepl<T>, Result<MontyObject, MontyException>) {
+    let mut result = repl.feed_start(snippet, vec![], PrintWriter::Stdout);
+    loop {
+        match result {
+            Ok(ReplProgress::Complete { repl, value }) => return (repl, Ok(value)),
+            Ok(ReplProgress::OsCall(call)) => result = call.resume_with(mount_fs, PrintWriter::Stdout),
+            Ok(other) => {
+                return (
+                    other.into_repl(),
+                    Err(cli_error(
+                        "external functions and async futures not supported in the REPL".to_owned(),
+                    )),
+                );
+            }
+            Err(err) => {
+                let ReplStartError { repl, error } = *err;
+                return (repl, Err(error));
+            }
+        }
+    }
 }
 
 /// Drives suspendable execution until completion.
@@ -376,23 +498,26 @@ fn execute_repl_snippet(repl: &mut MontyRepl<impl ResourceTracker>, snippet: &st
 /// external calls and returns the final value when execution reaches
 /// `RunProgress::Complete`.
 ///
-/// Returns an error string for unsupported suspend points (OS calls or async
-/// futures) or invalid external-function dispatch.
-fn run_until_complete(mut progress: RunProgress<impl ResourceTracker>) -> Result<MontyObject, String> {
+/// OS calls are resolved against `mount_fs` when directories are mounted.
+///
+/// Returns the exception that ended execution, or a `RuntimeError` for unsupported
+/// suspend points (OS calls without mounts or async futures) or invalid external-function dispatch.
+fn run_until_complete(
+    mut progress: RunProgress<impl ResourceTracker>,
+    mut mount_fs: Option<&mut MountFs>,
+) -> Result<MontyObject, MontyException> {
     loop {
         match progress {
             RunProgress::Complete(value) => return Ok(value),
             RunProgress::FunctionCall(call) => {
-                let return_value = resolve_external_call(&call.function_name, &call.args)?;
-                progress = call
-                    .resume(return_value, PrintWriter::Stdout)
-                    .map_err(|err| format!("{err}"))?;
+                let return_value = resolve_external_call(&call.function_name, &call.args).map_err(cli_error)?;
+                progress = call.resume(return_value, PrintWriter::Stdout)?;
             }
             RunProgress::ResolveFutures(state) => {
-                return Err(format!(
+                return Err(cli_error(format!(
                     "async futures not supported in CLI: {:?}",
                     state.pending_call_ids()
-                ));
+                )));
             }
             RunProgress::NameLookup(lookup) => {
                 let result = if lookup.name == "add_ints" {
@@ -403,20 +528,26 @@ fn run_until_complete(mut progress: RunProgress<impl ResourceTracker>) -> Result
                 } else {
                     NameLookupResult::Undefined
                 };
-                progress = lookup
-                    .resume(result, PrintWriter::Stdout)
-                    .map_err(|err| format!("{err}"))?;
+                progress = lookup.resume(result, PrintWriter::Stdout)?;
             }
             RunProgress::OsCall(call) => {
-                return Err(format!(
-                    "OS calls not supported in CLI: {:?}({:?})",
-                    call.function, call.args
-                ));
+                let Some(mount_fs) = mount_fs.as_deref_mut() else {
+                    return Err(cli_error(format!(
+                        "OS calls not supported in CLI without --mount: {:?}({:?})",
+                        call.function, call.args
+                    )));
+                };
+                progress = call.resume_with(mount_fs, PrintWriter::Stdout)?;
             }
         }
     }
 }
 
+/// Wraps a failure of the CLI's own handling of a suspend point in a `RuntimeError`.
+fn cli_error(message: String) -> MontyException {
+    MontyException::new(ExcType::RuntimeError, Some(message))
+}
+
 /// Resolves supported CLI external function calls.
 ///
 /// The CLI currently supports only `add_ints(int, int)`, which makes it
@@ -509,6 +640,29 @@ fn sig_digits_after_decimal(value: f64) -> usize {
     5usize.saturating_sub(before)
 }
 
+/// Parses a `--mount` argument of the form `host_dir:/sandbox_path` or `host_dir:/sandbox_path:ro`.
+///
+/// The sandbox path is taken from after the last `:`, so host paths may contain colons.
+fn parse_mount(s: &str) -> Result<MountSpec, String> {
+    let (spec, read_only) = match s.strip_suffix(":ro") {
+        Some(spec) => (spec, true),
+        None => (s, false),
+    };
+    let Some((host_dir, sandbox_path)) = spec.rsplit_once(':') else {
+        return Err(format!("invalid mount '{s}': expected HOST_DIR:/SANDBOX_PATH[:ro]"));
+    };
+    if host_dir.is_empty() || !sandbox_path.starts_with('/') {
+        return Err(format!(
+            "invalid mount '{s}': expected a host directory and an absolute sandbox path"
+        ));
+    }
+    Ok(MountSpec {
+        host_dir: host_dir.to_owned(),
+        sandbox_path: sandbox_path.to_owned(),
+        read_only,
+    })
+}
+
 /// Parses a memory size string with optional unit suffix.
 ///
 /// Accepts plain byte counts (`1024`) or values with a case-insensitive suffix:
@@ -227,3 +227,22 @@ test('with snapshot dump load', (t) => {
   t.is((result as MontyComplete).output, null)
   t.is(output.join(''), '42\n')
 })
+
+test('stderr', (t) => {
+  const code = `
+import sys
+print('out')
+print('err', file=sys.stderr)
+sys.stderr.write('more\\n')
+`
+  const m = new Monty(code)
+  const output: [string, string][] = []
+  m.run({ printCallback: (stream: string, text: string) => output.push([stream, text]) })
+  t.deepEqual(output, [
+    ['stdout', 'out'],
+    ['stdout', '\n'],
+    ['stderr', 'err'],
+    ['stderr', '\n'],
+    ['stderr', 'more\n'],
+  ])
+})
@@ -1116,6 +1116,20 @@ impl PrintWriterCallback for CallbackStringPrint<'_> {
             .map_err(exc_js_to_monty)?;
         Ok(())
     }
+
+    fn stderr_write(&mut self, output: Cow<'_, str>) -> std::result::Result<(), MontyException> {
+        self.0
+            .call(("stderr", output.as_ref().to_owned()).into())
+            .map_err(exc_js_to_monty)?;
+        Ok(())
+    }
+
+    fn stderr_push(&mut self, end: char) -> std::result::Result<(), MontyException> {
+        self.0
+            .call(("stderr", end.to_string()).into())
+            .map_err(exc_js_to_monty)?;
+        Ok(())
+    }
 }
 
 // =============================================================================
@@ -599,7 +599,7 @@ export interface RunMontyAsyncOptions {
   externalFunctions?: Record<string, (...args: unknown[]) => unknown>
   /** Resource limits. */
   limits?: ResourceLimits
-  /** Callback invoked on each print() call. The first argument is the stream name (always "stdout"), the second is the printed text. */
+  /** Callback invoked on each print() call. The first argument is the stream name ("stdout" or "stderr"), the second is the printed text. */
   printCallback?: (stream: string, text: string) => void
 }
 
@@ -63,7 +63,7 @@ async def run_monty_async(
     inputs: dict[str, Any] | None = None,
     external_functions: dict[str, Callable[..., Any]] | None = None,
     limits: ResourceLimits | None = None,
-    print_callback: Callable[[Literal['stdout'], str], None] | None = None,
+    print_callback: Callable[[Literal['stdout', 'stderr'], str], None] | None = None,
     os: AbstractOS | None = None,
 ) -> Any:
     """Run a Monty script with async external functions and optional OS access.
@@ -96,7 +96,7 @@ async def run_repl_async(
     *,
     inputs: dict[str, Any] | None = None,
     external_functions: dict[str, Callable[..., Any]] | None = None,
-    print_callback: Callable[[Literal['stdout'], str], None] | None = None,
+    print_callback: Callable[[Literal['stdout', 'stderr'], str], None] | None = None,
     os: AbstractOS | None = None,
 ) -> Any:
     """Feed a snippet to a REPL session with async external function support.
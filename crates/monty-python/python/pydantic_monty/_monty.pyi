@@ -85,7 +85,7 @@ class Monty:
         inputs: dict[str, Any] | None = None,
         limits: ResourceLimits | None = None,
         external_functions: dict[str, Callable[..., Any]] | None = None,
-        print_callback: Callable[[Literal['stdout'], str], None] | None = None,
+        print_callback: Callable[[Literal['stdout', 'stderr'], str], None] | None = None,
         os: Callable[[OsFunction, tuple[Any, ...]], Any] | None = None,
     ) -> Any:
         """
@@ -97,7 +97,8 @@ class Monty:
             inputs: Dict of input variable values (must match names from __init__)
             limits: Optional resource limits configuration
             external_functions: Dict of external function callbacks
-            print_callback: Optional callback for print output
+            print_callback: Optional callback for print output, called with the stream
+                ('stdout' or 'stderr') and the text
             os: Optional callback for OS calls.
                 Called with (function_name, args) where function_name is like 'Path.exists'
                 and args is a tuple of arguments. Must return the appropriate value for the
@@ -115,7 +116,7 @@ class Monty:
         *,
         inputs: dict[str, Any] | None = None,
         limits: ResourceLimits | None = None,
-        print_callback: Callable[[Literal['stdout'], str], None] | None = None,
+        print_callback: Callable[[Literal['stdout', 'stderr'], str], None] | None = None,
     ) -> FunctionSnapshot | NameLookupSnapshot | FutureSnapshot | MontyComplete:
         """
         Start the code execution and return a progress object, or completion.
@@ -127,7 +128,8 @@ class Monty:
         Arguments:
             inputs: Dict of input variable values (must match names from __init__)
             limits: Optional resource limits configuration
-            print_callback: Optional callback for print output
+            print_callback: Optional callback for print output, called with the stream
+                ('stdout' or 'stderr') and the text
 
         Returns:
             FunctionSnapshot if an external function call is pending,
@@ -228,7 +230,7 @@ class MontyRepl:
         *,
         inputs: dict[str, Any] | None = None,
         external_functions: dict[str, Callable[..., Any]] | None = None,
-        print_callback: Callable[[Literal['stdout'], str], None] | None = None,
+        print_callback: Callable[[Literal['stdout', 'stderr'], str], None] | None = None,
         os: Callable[[str, tuple[Any, ...], dict[str, Any]], Any] | None = None,
     ) -> Any:
         """
@@ -247,7 +249,7 @@ class MontyRepl:
         code: str,
         *,
         inputs: dict[str, Any] | None = None,
-        print_callback: Callable[[Literal['stdout'], str], None] | None = None,
+        print_callback: Callable[[Literal['stdout', 'stderr'], str], None] | None = None,
     ) -> FunctionSnapshot | NameLookupSnapshot | FutureSnapshot | MontyComplete:
         """
         Start executing an incremental snippet, yielding snapshots for external calls.
@@ -631,7 +633,7 @@ class Frame:
 def load_snapshot(
     data: bytes,
     *,
-    print_callback: Callable[[Literal['stdout'], str], None] | None = None,
+    print_callback: Callable[[Literal['stdout', 'stderr'], str], None] | None = None,
     dataclass_registry: list[type] | None = None,
 ) -> FunctionSnapshot | NameLookupSnapshot | FutureSnapshot:
     """Load a non-REPL snapshot from serialized bytes.
@@ -641,7 +643,8 @@ def load_snapshot(
 
     Arguments:
         data: Serialized snapshot bytes from `.dump()`
-        print_callback: Optional callback for print output
+        print_callback: Optional callback for print output, called with the stream
+            ('stdout' or 'stderr') and the text
         dataclass_registry: Optional list of dataclass types to register
 
     Returns:
@@ -655,7 +658,7 @@ def load_snapshot(
 def load_repl_snapshot(
     data: bytes,
     *,
-    print_callback: Callable[[Literal['stdout'], str], None] | None = None,
+    print_callback: Callable[[Literal['stdout', 'stderr'], str], None] | None = None,
     dataclass_registry: list[type] | None = None,
 ) -> tuple[FunctionSnapshot | NameLookupSnapshot | FutureSnapshot, MontyRepl]:
     """Load a REPL snapshot from serialized bytes.
@@ -666,7 +669,8 @@ def load_repl_snapshot(
 
     Arguments:
         data: Serialized snapshot bytes from `.dump()` on a REPL snapshot
-        print_callback: Optional callback for print output
+        print_callback: Optional callback for print output, called with the stream
+            ('stdout' or 'stderr') and the text
         dataclass_registry: Optional list of dataclass types to register
 
     Returns:
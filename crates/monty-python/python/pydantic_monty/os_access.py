@@ -1,8 +1,22 @@
 from __future__ import annotations
 
+import fnmatch
+import io
+import os
 from abc import ABC, abstractmethod
 from pathlib import PurePosixPath
-from typing import TYPE_CHECKING, Any, Callable, Literal, NamedTuple, Protocol, Sequence, TypeAlias, TypeGuard
+from typing import (
+    TYPE_CHECKING,
+    Any,
+    Callable,
+    Iterator,
+    Literal,
+    NamedTuple,
+    Protocol,
+    Sequence,
+    TypeAlias,
+    TypeGuard,
+)
 
 if TYPE_CHECKING:
     # Self is 3.11+, hence this
@@ -27,8 +41,25 @@ OsFunction = Literal[
     'Path.rename',
     'Path.resolve',
     'Path.absolute',
+    'Path.glob',
+    'Path.rglob',
+    'Path.touch',
+    'Path.samefile',
     'os.getenv',
     'os.environ',
+    'os.urandom',
+    'os.walk',
+    'shutil.copy',
+    'shutil.copytree',
+    'shutil.rmtree',
+    'open',
+    'file.read',
+    'file.readline',
+    'file.write',
+    'file.seek',
+    'file.tell',
+    'file.close',
+    'input',
 ]
 
 
@@ -134,7 +165,11 @@ class AbstractOS(ABC):
         kwargs = kwargs or {}
         match function_name:
             case 'Path.exists':
-                return self.path_exists(*args)
+                (path,) = args
+                if not kwargs.get('follow_symlinks', True) and self.path_is_symlink(path):
+                    # a dangling symlink exists when it isn't followed
+                    return True
+                return self.path_exists(path)
             case 'Path.is_file':
                 return self.path_is_file(*args)
             case 'Path.is_dir':
@@ -168,10 +203,44 @@ class AbstractOS(ABC):
                 return self.path_resolve(*args)
             case 'Path.absolute':
                 return self.path_absolute(*args)
+            case 'Path.glob':
+                return self.path_glob(*args)
+            case 'Path.rglob':
+                return self.path_rglob(*args)
+            case 'Path.touch':
+                return self.path_touch(args[0], exist_ok=kwargs.get('exist_ok', True))
+            case 'Path.samefile':
+                return self.path_samefile(args[0], PurePosixPath(args[1]))
             case 'os.getenv':
                 return self.getenv(*args)
             case 'os.environ':
                 return self.get_environ()
+            case 'os.urandom':
+                return self.urandom(*args)
+            case 'os.walk':
+                return self.walk(args[0], topdown=kwargs.get('topdown', True))
+            case 'shutil.copy':
+                return self.shutil_copy(*args)
+            case 'shutil.copytree':
+                return self.shutil_copytree(*args, dirs_exist_ok=kwargs.get('dirs_exist_ok', False))
+            case 'shutil.rmtree':
+                return self.shutil_rmtree(args[0], ignore_errors=kwargs.get('ignore_errors', False))
+            case 'open':
+                return self.file_open(*args)
+            case 'file.read':
+                return self.file_read(*args)
+            case 'file.readline':
+                return self.file_readline(*args)
+            case 'file.write':
+                return self.file_write(*args)
+            case 'file.seek':
+                return self.file_seek(*args)
+            case 'file.tell':
+                return self.file_tell(*args)
+            case 'file.close':
+                return self.file_close(*args)
+            case 'input':
+                return self.input(*args)
 
     @abstractmethod
     def path_exists(self, path: PurePosixPath) -> bool:
@@ -422,6 +491,282 @@ class AbstractOS(ABC):
         """
         raise NotImplementedError
 
+    def urandom(self, size: int) -> bytes:
+        """Get random bytes, used by `os.urandom()` and `uuid.uuid4()`.
+
+        Defaults to the host's `os.urandom()`; override to control the entropy source,
+        e.g. to make runs reproducible.
+
+        Args:
+            size: The number of bytes to return.
+
+        Returns:
+            `size` random bytes.
+        """
+        return os.urandom(size)
+
+    def input(self, prompt: str) -> str:
+        """Read a line of input, used by `input()`.
+
+        Sandboxed code has no stdin, so by default there is never any input;
+        override to answer from a queue of lines, a chat UI or the host's own `input()`.
+        The prompt is passed here rather than printed.
+
+        Args:
+            prompt: The prompt passed to `input()`, converted with `str()`.
+
+        Returns:
+            The line read; a trailing newline is stripped.
+
+        Raises:
+            EOFError: When there is no more input.
+        """
+        raise EOFError('EOF when reading a line')
+
+    def path_glob(self, path: PurePosixPath, pattern: str) -> list[PurePosixPath]:
+        """List the paths below a directory matching a glob pattern, used by `Path.glob()`.
+
+        The default implementation walks the tree with `path_iterdir()` and `path_is_dir()`,
+        supporting `*`, `?` and `[...]` within a component and `**` for any number of directories.
+
+        Args:
+            path: The directory to search.
+            pattern: The pattern to match, relative to `path`.
+
+        Returns:
+            The matching paths, or an empty list if `path` is not a directory.
+        """
+        parts = [part for part in pattern.split('/') if part and part != '.']
+        return list(self._glob(PurePosixPath(path), parts))
+
+    def path_rglob(self, path: PurePosixPath, pattern: str) -> list[PurePosixPath]:
+        """List the paths matching a glob pattern in a directory and all its subdirectories, used by `Path.rglob()`.
+
+        The default implementation calls `path_glob()` with `**/` prepended to the pattern.
+        """
+        return self.path_glob(path, f'**/{pattern}')
+
+    def path_touch(self, path: PurePosixPath, exist_ok: bool = True) -> None:
+        """Create an empty file if it doesn't exist, used by `Path.touch()`.
+
+        The default implementation writes an empty file with `path_write_bytes()`.
+
+        Raises:
+            FileExistsError: If `exist_ok` is False and the path exists.
+            FileNotFoundError: If the parent directory does not exist.
+        """
+        if self.path_exists(path):
+            if not exist_ok:
+                raise FileExistsError(17, 'File exists', str(path))
+        else:
+            self.path_write_bytes(path, b'')
+
+    def path_samefile(self, path: PurePosixPath, other: PurePosixPath) -> bool:
+        """Check if two paths point to the same file, used by `Path.samefile()`.
+
+        The default implementation compares the paths returned by `path_resolve()`.
+
+        Raises:
+            FileNotFoundError: If either path does not exist.
+        """
+        for p in (path, other):
+            if not self.path_exists(p):
+                raise FileNotFoundError(2, 'No such file or directory', str(p))
+        return self.path_resolve(path) == self.path_resolve(other)
+
+    def walk(self, top: PurePosixPath, topdown: bool = True) -> list[tuple[str, list[str], list[str]]]:
+        """Walk a directory tree, used by `os.walk()`.
+
+        The default implementation lists directories with `path_iterdir()` and `path_is_dir()`.
+
+        Args:
+            top: The directory to walk.
+            topdown: If True, each directory comes before its subdirectories, otherwise after them.
+
+        Returns:
+            A `(dirpath, dirnames, filenames)` tuple for each directory, or an empty list if
+            `top` is not a directory.
+        """
+        result: list[tuple[str, list[str], list[str]]] = []
+        if self.path_is_dir(top):
+            self._walk(PurePosixPath(top), topdown, result)
+        return result
+
+    def shutil_copy(self, src: PurePosixPath, dst: PurePosixPath) -> str:
+        """Copy a file's contents to a file or into a directory, used by `shutil.copy()`.
+
+        The default implementation uses `path_read_bytes()` and `path_write_bytes()`.
+
+        Returns:
+            The path of the new file.
+
+        Raises:
+            FileNotFoundError: If `src` or the destination's parent directory does not exist.
+            IsADirectoryError: If `src` is a directory.
+        """
+        if self.path_is_dir(dst):
+            dst = PurePosixPath(dst) / PurePosixPath(src).name
+        self.path_write_bytes(dst, self.path_read_bytes(src))
+        return str(dst)
+
+    def shutil_copytree(self, src: PurePosixPath, dst: PurePosixPath, dirs_exist_ok: bool = False) -> str:
+        """Recursively copy a directory, used by `shutil.copytree()`.
+
+        The default implementation uses `walk()`, `path_mkdir()` and `shutil_copy()`.
+
+        Returns:
+            The destination path.
+
+        Raises:
+            FileNotFoundError: If `src` does not exist.
+            FileExistsError: If `dst` exists and `dirs_exist_ok` is False.
+        """
+        if not self.path_is_dir(src):
+            raise FileNotFoundError(2, 'No such file or directory', str(src))
+        src, dst = PurePosixPath(src), PurePosixPath(dst)
+        self.path_mkdir(dst, parents=True, exist_ok=dirs_exist_ok)
+        for dirpath, dirnames, filenames in self.walk(src):
+            target = dst / PurePosixPath(dirpath).relative_to(src)
+            for name in dirnames:
+                self.path_mkdir(target / name, parents=False, exist_ok=True)
+            for name in filenames:
+                self.shutil_copy(PurePosixPath(dirpath) / name, target / name)
+        return str(dst)
+
+    def shutil_rmtree(self, path: PurePosixPath, ignore_errors: bool = False) -> None:
+        """Recursively delete a directory, used by `shutil.rmtree()`.
+
+        The default implementation uses `walk()`, `path_unlink()` and `path_rmdir()`.
+
+        Raises:
+            FileNotFoundError: If the path does not exist, unless `ignore_errors` is True.
+            NotADirectoryError: If the path is a file, unless `ignore_errors` is True.
+        """
+        if not self.path_is_dir(path):
+            if ignore_errors:
+                return
+            if self.path_exists(path):
+                raise NotADirectoryError(20, 'Not a directory', str(path))
+            raise FileNotFoundError(2, 'No such file or directory', str(path))
+        for dirpath, _, filenames in self.walk(path, topdown=False):
+            for name in filenames:
+                self.path_unlink(PurePosixPath(dirpath) / name)
+            self.path_rmdir(PurePosixPath(dirpath))
+
+    def _glob(self, path: PurePosixPath, parts: list[str]) -> Iterator[PurePosixPath]:
+        if not parts:
+            yield path
+            return
+        first, rest = parts[0], parts[1:]
+        if first == '**':
+            yield from self._glob(path, rest)
+            children = self.path_iterdir(path) if self.path_is_dir(path) else []
+            for child in children:
+                if self.path_is_dir(child):
+                    yield from self._glob(child, parts)
+        elif self.path_is_dir(path):
+            for child in self.path_iterdir(path):
+                if fnmatch.fnmatchcase(child.name, first) and (not rest or self.path_is_dir(child)):
+                    yield from self._glob(child, rest)
+
+    def _walk(self, path: PurePosixPath, topdown: bool, result: list[tuple[str, list[str], list[str]]]) -> None:
+        dirnames: list[str] = []
+        filenames: list[str] = []
+        for child in self.path_iterdir(path):
+            (dirnames if self.path_is_dir(child) else filenames).append(child.name)
+        entry = (str(path), dirnames, filenames)
+        if topdown:
+            result.append(entry)
+        for name in dirnames:
+            self._walk(path / name, topdown, result)
+        if not topdown:
+            result.append(entry)
+
+    def file_open(self, path: PurePosixPath, mode: str) -> int:
+        """Open a file for `open()` or `Path.open()`, returning a handle for the other `file_*` methods.
+
+        The default implementation keeps the file's contents in memory while it is open, reading
+        them with `path_read_bytes()` and writing them back with `path_write_bytes()` on close.
+        Override all the `file_*` methods together to stream from real files instead.
+
+        Args:
+            path: The path to open.
+            mode: One of `r`, `w`, `a` or `x`, optionally followed by `+` and then `b` for binary files.
+
+        Returns:
+            An integer handle identifying the open file.
+
+        Raises:
+            FileNotFoundError: If the file does not exist and the mode doesn't create it.
+            FileExistsError: If the mode is `x` and the file already exists.
+        """
+        access = mode[0]
+        if access == 'r':
+            data = self.path_read_bytes(path)
+        elif access == 'a' and self.path_exists(path):
+            data = self.path_read_bytes(path)
+        elif access == 'x' and self.path_exists(path):
+            raise FileExistsError(17, 'File exists', str(path))
+        else:
+            data = b''
+            # like a real open(), creating or truncating happens straight away
+            self.path_write_bytes(path, data)
+        buffer = io.BytesIO(data)
+        if access == 'a':
+            buffer.seek(0, io.SEEK_END)
+        file = buffer if 'b' in mode else io.TextIOWrapper(buffer, encoding='utf-8', write_through=True)
+        handles = self._file_handles()
+        handle = max(handles, default=2) + 1
+        handles[handle] = _HostFile(path, file, buffer, writable=access != 'r' or '+' in mode)
+        return handle
+
+    def file_read(self, handle: int, size: int) -> str | bytes:
+        """Read up to `size` characters (text) or bytes (binary) from an open file, or everything if `size` is -1."""
+        return self._file_handles()[handle].file.read(size)
+
+    def file_readline(self, handle: int, size: int) -> str | bytes:
+        """Read one line from an open file, stopping after `size` characters or bytes unless `size` is -1.
+
+        Returns an empty string or bytes at the end of the file.
+        """
+        return self._file_handles()[handle].file.readline(size)
+
+    def file_write(self, handle: int, data: str | bytes) -> int:
+        """Write `str` (text) or `bytes` (binary) to an open file, returning the number written."""
+        return self._file_handles()[handle].file.write(data)
+
+    def file_seek(self, handle: int, offset: int, whence: int) -> int:
+        """Move an open file's position by `offset` relative to `whence` (0, 1 or 2), returning the new position."""
+        return self._file_handles()[handle].file.seek(offset, whence)
+
+    def file_tell(self, handle: int) -> int:
+        """Get an open file's current position."""
+        return self._file_handles()[handle].file.tell()
+
+    def file_close(self, handle: int) -> None:
+        """Close an open file, writing its contents back with `path_write_bytes()` if it was writable."""
+        host_file = self._file_handles().pop(handle)
+        if host_file.writable:
+            host_file.file.flush()
+            self.path_write_bytes(host_file.path, host_file.buffer.getvalue())
+
+    def _file_handles(self) -> dict[int, _HostFile]:
+        # created lazily, so subclasses don't need to call `super().__init__()`
+        try:
+            return self._open_files
+        except AttributeError:
+            self._open_files: dict[int, _HostFile] = {}
+            return self._open_files
+
+
+class _HostFile(NamedTuple):
+    """A file opened by the default `AbstractOS.file_open()`."""
+
+    path: PurePosixPath
+    file: io.BytesIO | io.TextIOWrapper
+    buffer: io.BytesIO
+    writable: bool
+
 
 class AbstractFile(Protocol):
     """Protocol defining the interface for files used with OSAccess.
@@ -113,6 +113,9 @@ pub fn py_to_monty(obj: &Bound<'_, PyAny>, dc_registry: &DcRegistry) -> PyResult
         // Handle pathlib.PurePosixPath and thereby pathlib.PosixPath objects
         let path_str: String = obj.str()?.extract()?;
         Ok(MontyObject::Path(path_str))
+    } else if obj.is_instance(get_uuid(obj.py())?)? {
+        let value: u128 = obj.getattr(intern!(obj.py(), "int"))?.extract()?;
+        Ok(MontyObject::Uuid(value))
     } else if obj.is_callable() {
         // Callable check is last since many Python types (classes, etc.) are technically callable,
         // and we want to match more specific types first (e.g. dataclasses).
@@ -230,6 +233,13 @@ pub fn monty_to_py(py: Python<'_>, obj: &MontyObject, dc_registry: &DcRegistry)
             let path_obj = pure_posix_path.call1((p,))?;
             Ok(path_obj.into_any().unbind())
         }
+        // Uuid - convert to Python uuid.UUID
+        MontyObject::Uuid(value) => {
+            let kwargs = PyDict::new(py);
+            kwargs.set_item(intern!(py, "int"), *value)?;
+            let uuid_obj = get_uuid(py)?.call((), Some(&kwargs))?;
+            Ok(uuid_obj.unbind())
+        }
         // Output-only types - convert to string representation
         MontyObject::Repr(s) => Ok(PyString::new(py, s).into_any().unbind()),
         MontyObject::Cycle(_, placeholder) => Ok(PyString::new(py, placeholder).into_any().unbind()),
@@ -259,6 +269,13 @@ fn get_pure_posix_path(py: Python<'_>) -> PyResult<&Bound<'_, PyAny>> {
     PUREPOSIX.import(py, "pathlib", "PurePosixPath")
 }
 
+/// Cached import of `uuid.UUID` class.
+fn get_uuid(py: Python<'_>) -> PyResult<&Bound<'_, PyAny>> {
+    static UUID: PyOnceLock<Py<PyAny>> = PyOnceLock::new();
+
+    UUID.import(py, "uuid", "UUID")
+}
+
 pub fn get_name(f: &Bound<'_, PyAny>) -> String {
     f.getattr(intern!(f.py(), "__name__"))
         .and_then(|n| n.extract::<String>())
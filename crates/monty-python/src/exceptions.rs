@@ -383,6 +383,7 @@ pub fn exc_monty_to_py(py: Python<'_>, exc: MontyException) -> PyErr {
         ExcType::NotImplementedError => exceptions::PyNotImplementedError::new_err(msg),
         ExcType::RecursionError => exceptions::PyRecursionError::new_err(msg),
         ExcType::AssertionError => exceptions::PyAssertionError::new_err(msg),
+        ExcType::EOFError => exceptions::PyEOFError::new_err(msg),
         ExcType::AttributeError => exceptions::PyAttributeError::new_err(msg),
         ExcType::FrozenInstanceError => {
             if let Ok(exc_cls) = get_frozen_instance_error(py)
@@ -409,6 +410,7 @@ pub fn exc_monty_to_py(py: Python<'_>, exc: MontyException) -> PyErr {
         ExcType::FileExistsError => exceptions::PyFileExistsError::new_err(msg),
         ExcType::IsADirectoryError => exceptions::PyIsADirectoryError::new_err(msg),
         ExcType::NotADirectoryError => exceptions::PyNotADirectoryError::new_err(msg),
+        ExcType::PermissionError => exceptions::PyPermissionError::new_err(msg),
         ExcType::RePatternError => {
             if let Ok(re_pattern_error) = get_re_pattern_error(py)
                 && let Ok(exc_instance) = re_pattern_error.call1((PyString::new(py, &msg),))
@@ -418,6 +420,69 @@ pub fn exc_monty_to_py(py: Python<'_>, exc: MontyException) -> PyErr {
                 exceptions::PyRuntimeError::new_err(msg)
             }
         }
+        ExcType::BinasciiError => {
+            if let Ok(binascii_error) = get_binascii_error(py)
+                && let Ok(exc_instance) = binascii_error.call1((PyString::new(py, &msg),))
+            {
+                PyErr::from_value(exc_instance)
+            } else {
+                exceptions::PyValueError::new_err(msg)
+            }
+        }
+        ExcType::CsvError => {
+            if let Ok(csv_error) = get_csv_error(py)
+                && let Ok(exc_instance) = csv_error.call1((PyString::new(py, &msg),))
+            {
+                PyErr::from_value(exc_instance)
+            } else {
+                exceptions::PyException::new_err(msg)
+            }
+        }
+        ExcType::StatisticsError => {
+            if let Ok(statistics_error) = get_statistics_error(py)
+                && let Ok(exc_instance) = statistics_error.call1((PyString::new(py, &msg),))
+            {
+                PyErr::from_value(exc_instance)
+            } else {
+                exceptions::PyValueError::new_err(msg)
+            }
+        }
+        ExcType::StructError => {
+            if let Ok(struct_error) = get_struct_error(py)
+                && let Ok(exc_instance) = struct_error.call1((PyString::new(py, &msg),))
+            {
+                PyErr::from_value(exc_instance)
+            } else {
+                exceptions::PyException::new_err(msg)
+            }
+        }
+        ExcType::ZlibError => {
+            if let Ok(zlib_error) = get_zlib_error(py)
+                && let Ok(exc_instance) = zlib_error.call1((PyString::new(py, &msg),))
+            {
+                PyErr::from_value(exc_instance)
+            } else {
+                exceptions::PyException::new_err(msg)
+            }
+        }
+        ExcType::BadGzipFile => {
+            if let Ok(bad_gzip_file) = get_bad_gzip_file(py)
+                && let Ok(exc_instance) = bad_gzip_file.call1((PyString::new(py, &msg),))
+            {
+                PyErr::from_value(exc_instance)
+            } else {
+                exceptions::PyOSError::new_err(msg)
+            }
+        }
+        ExcType::UnsupportedOperation => {
+            if let Ok(unsupported_operation) = get_unsupported_operation(py)
+                && let Ok(exc_instance) = unsupported_operation.call1((PyString::new(py, &msg),))
+            {
+                PyErr::from_value(exc_instance)
+            } else {
+                exceptions::PyOSError::new_err(msg)
+            }
+        }
     }
 }
 
@@ -454,6 +519,12 @@ fn py_err_to_exc_type(exc: &Bound<'_, exceptions::PyBaseException>) -> ExcType {
         } else if exceptions::PyValueError::type_check(exc) {
             if exceptions::PyUnicodeDecodeError::type_check(exc) {
                 ExcType::UnicodeDecodeError
+            } else if is_binascii_error(exc) {
+                ExcType::BinasciiError
+            } else if is_statistics_error(exc) {
+                ExcType::StatisticsError
+            } else if is_unsupported_operation(exc) {
+                ExcType::UnsupportedOperation
             } else {
                 ExcType::ValueError
             }
@@ -512,6 +583,10 @@ fn py_err_to_exc_type(exc: &Bound<'_, exceptions::PyBaseException>) -> ExcType {
                 ExcType::IsADirectoryError
             } else if exceptions::PyNotADirectoryError::type_check(exc) {
                 ExcType::NotADirectoryError
+            } else if exceptions::PyPermissionError::type_check(exc) {
+                ExcType::PermissionError
+            } else if is_bad_gzip_file(exc) {
+                ExcType::BadGzipFile
             } else {
                 ExcType::OSError
             }
@@ -520,6 +595,14 @@ fn py_err_to_exc_type(exc: &Bound<'_, exceptions::PyBaseException>) -> ExcType {
             ExcType::TimeoutError
         } else if exceptions::PyMemoryError::type_check(exc) {
             ExcType::MemoryError
+        } else if is_csv_error(exc) {
+            ExcType::CsvError
+        } else if is_struct_error(exc) {
+            ExcType::StructError
+        } else if is_zlib_error(exc) {
+            ExcType::ZlibError
+        } else if exceptions::PyEOFError::type_check(exc) {
+            ExcType::EOFError
         } else {
             ExcType::Exception
         }
@@ -555,3 +638,108 @@ fn get_re_pattern_error(py: Python<'_>) -> PyResult<&Bound<'_, PyAny>> {
         RE_PATTERN_ERROR.import(py, "re", "error")
     }
 }
+
+fn get_binascii_error(py: Python<'_>) -> PyResult<&Bound<'_, PyAny>> {
+    static BINASCII_ERROR: PyOnceLock<Py<PyAny>> = PyOnceLock::new();
+
+    BINASCII_ERROR.import(py, "binascii", "Error")
+}
+
+/// Checks if an exception is an instance of `binascii.Error`.
+fn is_binascii_error(exc: &Bound<'_, exceptions::PyBaseException>) -> bool {
+    if let Ok(binascii_error) = get_binascii_error(exc.py()) {
+        exc.is_instance(binascii_error).unwrap_or(false)
+    } else {
+        false
+    }
+}
+
+fn get_csv_error(py: Python<'_>) -> PyResult<&Bound<'_, PyAny>> {
+    static CSV_ERROR: PyOnceLock<Py<PyAny>> = PyOnceLock::new();
+
+    CSV_ERROR.import(py, "csv", "Error")
+}
+
+/// Checks if an exception is an instance of `csv.Error`.
+fn is_csv_error(exc: &Bound<'_, exceptions::PyBaseException>) -> bool {
+    if let Ok(csv_error) = get_csv_error(exc.py()) {
+        exc.is_instance(csv_error).unwrap_or(false)
+    } else {
+        false
+    }
+}
+
+fn get_statistics_error(py: Python<'_>) -> PyResult<&Bound<'_, PyAny>> {
+    static STATISTICS_ERROR: PyOnceLock<Py<PyAny>> = PyOnceLock::new();
+
+    STATISTICS_ERROR.import(py, "statistics", "StatisticsError")
+}
+
+/// Checks if an exception is an instance of `statistics.StatisticsError`.
+fn is_statistics_error(exc: &Bound<'_, exceptions::PyBaseException>) -> bool {
+    if let Ok(statistics_error) = get_statistics_error(exc.py()) {
+        exc.is_instance(statistics_error).unwrap_or(false)
+    } else {
+        false
+    }
+}
+
+fn get_struct_error(py: Python<'_>) -> PyResult<&Bound<'_, PyAny>> {
+    static STRUCT_ERROR: PyOnceLock<Py<PyAny>> = PyOnceLock::new();
+
+    STRUCT_ERROR.import(py, "struct", "error")
+}
+
+/// Checks if an exception is an instance of `struct.error`.
+fn is_struct_error(exc: &Bound<'_, exceptions::PyBaseException>) -> bool {
+    if let Ok(struct_error) = get_struct_error(exc.py()) {
+        exc.is_instance(struct_error).unwrap_or(false)
+    } else {
+        false
+    }
+}
+
+fn get_zlib_error(py: Python<'_>) -> PyResult<&Bound<'_, PyAny>> {
+    static ZLIB_ERROR: PyOnceLock<Py<PyAny>> = PyOnceLock::new();
+
+    ZLIB_ERROR.import(py, "zlib", "error")
+}
+
+/// Checks if an exception is an instance of `zlib.error`.
+fn is_zlib_error(exc: &Bound<'_, exceptions::PyBaseException>) -> bool {
+    if let Ok(zlib_error) = get_zlib_error(exc.py()) {
+        exc.is_instance(zlib_error).unwrap_or(false)
+    } else {
+        false
+    }
+}
+
+fn get_bad_gzip_file(py: Python<'_>) -> PyResult<&Bound<'_, PyAny>> {
+    static BAD_GZIP_FILE: PyOnceLock<Py<PyAny>> = PyOnceLock::new();
+
+    BAD_GZIP_FILE.import(py, "gzip", "BadGzipFile")
+}
+
+/// Checks if an exception is an instance of `gzip.BadGzipFile`.
+fn is_bad_gzip_file(exc: &Bound<'_, exceptions::PyBaseException>) -> bool {
+    if let Ok(bad_gzip_file) = get_bad_gzip_file(exc.py()) {
+        exc.is_instance(bad_gzip_file).unwrap_or(false)
+    } else {
+        false
+    }
+}
+
+fn get_unsupported_operation(py: Python<'_>) -> PyResult<&Bound<'_, PyAny>> {
+    static UNSUPPORTED_OPERATION: PyOnceLock<Py<PyAny>> = PyOnceLock::new();
+
+    UNSUPPORTED_OPERATION.import(py, "io", "UnsupportedOperation")
+}
+
+/// Checks if an exception is an instance of `io.UnsupportedOperation`.
+fn is_unsupported_operation(exc: &Bound<'_, exceptions::PyBaseException>) -> bool {
+    if let Ok(unsupported_operation) = get_unsupported_operation(exc.py()) {
+        exc.is_instance(unsupported_operation).unwrap_or(false)
+    } else {
+        false
+    }
+}
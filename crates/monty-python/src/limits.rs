@@ -139,4 +139,8 @@ impl<T: ResourceTracker> ResourceTracker for PySignalTracker<T> {
     fn check_large_result(&self, estimated_bytes: usize) -> Result<(), ResourceError> {
         self.inner.check_large_result(estimated_bytes)
     }
+
+    fn time_usage(&self) -> Option<(Duration, Duration)> {
+        self.inner.time_usage()
+    }
 }
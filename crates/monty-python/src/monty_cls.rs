@@ -1570,6 +1570,22 @@ impl PrintWriterCallback for CallbackStringPrint {
         })
         .map_err(|e| Python::attach(|py| exc_py_to_monty(py, &e)))
     }
+
+    fn stderr_write(&mut self, output: Cow<'_, str>) -> Result<(), MontyException> {
+        Python::attach(|py| {
+            self.0.bind(py).call1(("stderr", output.as_ref()))?;
+            Ok::<_, PyErr>(())
+        })
+        .map_err(|e| Python::attach(|py| exc_py_to_monty(py, &e)))
+    }
+
+    fn stderr_push(&mut self, end: char) -> Result<(), MontyException> {
+        Python::attach(|py| {
+            self.0.bind(py).call1(("stderr", end.to_string()))?;
+            Ok::<_, PyErr>(())
+        })
+        .map_err(|e| Python::attach(|py| exc_py_to_monty(py, &e)))
+    }
 }
 
 /// Recursively checks whether a `MontyObject` contains a dataclass, including
@@ -283,6 +283,48 @@ def test_write_text_to_directory_via_monty():
     assert str(exc_info.value) == snapshot("IsADirectoryError: [Errno 21] Is a directory: '/test/subdir'")
 
 
+def test_open_write_and_read_via_monty():
+    """open() writes through a host file handle and reads it back line by line."""
+    fs = OSAccess([MemoryFile('/test/existing.txt', content='existing')])
+
+    code = """
+with open('/test/new.txt', 'w') as f:
+    f.write('one\\n')
+    f.write('two\\n')
+
+with open('/test/new.txt') as f:
+    first = f.readline()
+    rest = [line for line in f]
+(first, rest)
+"""
+    result = Monty(code).run(os=fs)
+    assert result == snapshot(('one\n', ['two\n']))
+    assert fs.path_read_text(P('/test/new.txt')) == 'one\ntwo\n'
+
+
+def test_glob_walk_and_shutil_via_monty():
+    """Path.glob(), os.walk() and shutil use the AbstractOS defaults built on the basic operations."""
+    fs = OSAccess([MemoryFile('/test/a.txt', content='a'), MemoryFile('/test/sub/b.txt', content='b')])
+
+    code = """
+import os
+import shutil
+from pathlib import Path
+
+txt = sorted(str(p) for p in Path('/test').rglob('*.txt'))
+shutil.copytree('/test/sub', '/test/copy')
+shutil.copy('/test/a.txt', '/test/copy')
+walked = [(d, sorted(ds), sorted(fs)) for d, ds, fs in os.walk('/test/copy')]
+shutil.rmtree('/test/sub')
+(txt, walked, Path('/test/sub').exists())
+"""
+    result = Monty(code).run(os=fs)
+    assert result == snapshot(
+        (['/test/a.txt', '/test/sub/b.txt'], [('/test/copy', [], ['a.txt', 'b.txt'])], False)
+    )
+    assert fs.path_read_text(P('/test/copy/a.txt')) == 'a'
+
+
 # =============================================================================
 # Writing Files (via direct API)
 # =============================================================================
@@ -817,3 +817,105 @@ Path('/tmp/mydir/file.txt').read_text()
             ('Path.read_text', (PurePosixPath('/tmp/mydir/file.txt'),)),
         ]
     )
+
+
+# =============================================================================
+# os.urandom() and uuid.uuid4() tests
+# =============================================================================
+
+
+def test_os_urandom_yields_oscall():
+    """os.urandom() yields an OS call with the requested size."""
+    m = pydantic_monty.Monty('import os; os.urandom(8)')
+    result = m.start()
+
+    assert isinstance(result, pydantic_monty.FunctionSnapshot)
+    assert result.is_os_function is True
+    assert result.function_name == snapshot('os.urandom')
+    assert result.args == snapshot((8,))
+
+
+def test_uuid4_uses_host_entropy():
+    """uuid.uuid4() builds its UUID from the bytes returned for os.urandom."""
+
+    def os_handler(function_name: str, args: tuple[Any, ...], kwargs: dict[str, Any] | None = None) -> bytes:
+        assert function_name == 'os.urandom'
+        assert args == (16,)
+        return b'\xff' * 16
+
+    m = pydantic_monty.Monty('import uuid; str(uuid.uuid4())')
+    result = m.run(os=os_handler)
+    assert result == snapshot('ffffffff-ffff-4fff-bfff-ffffffffffff')
+
+
+def test_uuid4_wrong_size():
+    """uuid.uuid4() raises if the host returns the wrong number of bytes."""
+
+    def os_handler(function_name: str, args: tuple[Any, ...], kwargs: dict[str, Any] | None = None) -> bytes:
+        return b'\x00' * 4
+
+    m = pydantic_monty.Monty('import uuid; uuid.uuid4()')
+    with pytest.raises(pydantic_monty.MontyRuntimeError) as exc_info:
+        m.run(os=os_handler)
+    assert str(exc_info.value) == snapshot('ValueError: os.urandom(16) returned 4 bytes')
+
+
+# =============================================================================
+# input() tests
+# =============================================================================
+
+
+def test_input_yields_oscall():
+    """input() yields an OS call with the prompt converted to a str."""
+    m = pydantic_monty.Monty('input(42)')
+    result = m.start()
+
+    assert isinstance(result, pydantic_monty.FunctionSnapshot)
+    assert result.is_os_function is True
+    assert result.function_name == snapshot('input')
+    assert result.args == snapshot(('42',))
+
+
+def test_input_resume_after_dump_load():
+    """A snapshot paused at input() can be dumped, loaded and resumed with the line."""
+    m = pydantic_monty.Monty("input('name? ').upper()")
+    result = m.start()
+    assert isinstance(result, pydantic_monty.FunctionSnapshot)
+
+    loaded = pydantic_monty.load_snapshot(result.dump())
+    assert isinstance(loaded, pydantic_monty.FunctionSnapshot)
+    assert loaded.args == snapshot(('name? ',))
+    complete = loaded.resume(return_value='monty\n')
+    assert isinstance(complete, pydantic_monty.MontyComplete)
+    assert complete.output == snapshot('MONTY')
+
+
+def test_input_eof_error():
+    """The host raising EOFError ends the input."""
+
+    def os_handler(function_name: str, args: tuple[Any, ...], kwargs: dict[str, Any] | None = None) -> str:
+        raise EOFError('no more input')
+
+    code = """
+try:
+    input()
+except EOFError as e:
+    result = repr(e)
+result
+"""
+    m = pydantic_monty.Monty(code)
+    assert m.run(os=os_handler) == snapshot("EOFError('no more input')")
+
+
+def test_input_os_access():
+    """OSAccess has no input by default, and subclasses can answer input() themselves."""
+
+    class Answers(pydantic_monty.OSAccess):
+        def input(self, prompt: str) -> str:
+            return f'{prompt}yes'
+
+    code = "input('ok? ')"
+    assert pydantic_monty.Monty(code).run(os=Answers()) == snapshot('ok? yes')
+    with pytest.raises(pydantic_monty.MontyRuntimeError) as exc_info:
+        pydantic_monty.Monty(code).run(os=pydantic_monty.OSAccess())
+    assert str(exc_info.value) == snapshot('EOFError: EOF when reading a line')
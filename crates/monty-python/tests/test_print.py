@@ -5,14 +5,14 @@ from inline_snapshot import snapshot
 
 import pydantic_monty
 
-PrintCallback = Callable[[Literal['stdout'], str], None]
+PrintCallback = Callable[[Literal['stdout', 'stderr'], str], None]
 
 
 def make_print_collector() -> tuple[list[str], PrintCallback]:
     """Create a print callback that collects output into a list."""
     output: list[str] = []
 
-    def callback(stream: Literal['stdout'], text: str) -> None:
+    def callback(stream: Literal['stdout', 'stderr'], text: str) -> None:
         assert stream == 'stdout'
         output.append(text)
 
@@ -110,7 +110,7 @@ def test_print_mixed_types() -> None:
 def make_error_callback(error: Exception) -> PrintCallback:
     """Create a print callback that raises an exception."""
 
-    def callback(stream: Literal['stdout'], text: str) -> None:
+    def callback(stream: Literal['stdout', 'stderr'], text: str) -> None:
         raise error
 
     return callback
@@ -183,7 +183,7 @@ for i in range(5):
     m = pydantic_monty.Monty(code)
     call_count = 0
 
-    def callback(stream: Literal['stdout'], text: str) -> None:
+    def callback(stream: Literal['stdout', 'stderr'], text: str) -> None:
         nonlocal call_count
         call_count += 1
         if call_count >= 3:
@@ -206,3 +206,33 @@ list(map(print, [1, 2, 3]))
     output, callback = make_print_collector()
     m.run(print_callback=callback)
     assert ''.join(output) == snapshot('1\n2\n3\n')
+
+
+def test_print_stderr() -> None:
+    """Output written to sys.stderr is passed to the callback as the 'stderr' stream."""
+    code = """
+import sys
+print('out')
+print('err', 1, file=sys.stderr)
+sys.stderr.write('more\\n')
+sys.stdout.write('done\\n')
+"""
+    m = pydantic_monty.Monty(code)
+    output: list[tuple[str, str]] = []
+
+    def callback(stream: Literal['stdout', 'stderr'], text: str) -> None:
+        output.append((stream, text))
+
+    m.run(print_callback=callback)
+    assert output == snapshot(
+        [
+            ('stdout', 'out'),
+            ('stdout', '\n'),
+            ('stderr', 'err'),
+            ('stderr', ' '),
+            ('stderr', '1'),
+            ('stderr', '\n'),
+            ('stderr', 'more\n'),
+            ('stdout', 'done\n'),
+        ]
+    )
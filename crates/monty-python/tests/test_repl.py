@@ -5,14 +5,14 @@ from inline_snapshot import snapshot
 
 import pydantic_monty
 
-PrintCallback = Callable[[Literal['stdout'], str], None]
+PrintCallback = Callable[[Literal['stdout', 'stderr'], str], None]
 
 
 def make_print_collector() -> tuple[list[str], PrintCallback]:
     """Create a print callback that collects output into a list."""
     output: list[str] = []
 
-    def callback(stream: Literal['stdout'], text: str) -> None:
+    def callback(stream: Literal['stdout', 'stderr'], text: str) -> None:
         assert stream == 'stdout'
         output.append(text)
 
@@ -551,3 +551,28 @@ def test_namedtuple_custom_missing_attr_error():
         m.run(inputs={'s': Simple(value=42)})
     # Monty uses the full qualified name (module.ClassName) for the type
     assert "AttributeError: 'test_types.Simple' object has no attribute 'nonexistent'" in str(exc_info.value)
+
+
+def test_uuid_input():
+    import uuid
+
+    m = pydantic_monty.Monty('(x.version, x.hex)', inputs=['x'])
+    value = uuid.UUID('12345678-1234-4678-9234-567812345678')
+    assert m.run(inputs={'x': value}) == snapshot((4, '12345678123446789234567812345678'))
+
+
+def test_uuid_output():
+    import uuid
+
+    m = pydantic_monty.Monty("import uuid; uuid.uuid5(uuid.NAMESPACE_DNS, 'python.org')")
+    result = m.run()
+    assert isinstance(result, uuid.UUID)
+    assert result == uuid.uuid5(uuid.NAMESPACE_DNS, 'python.org')
+
+
+def test_uuid_roundtrip():
+    import uuid
+
+    m = pydantic_monty.Monty('x', inputs=['x'])
+    value = uuid.UUID(int=2**128 - 1)
+    assert m.run(inputs={'x': value}) == value
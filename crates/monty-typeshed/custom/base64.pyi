@@ -0,0 +1,25 @@
+from _typeshed import ReadableBuffer
+
+__all__ = [
+    'b64encode',
+    'b64decode',
+    'b32encode',
+    'b32decode',
+    'b16encode',
+    'b16decode',
+    'standard_b64encode',
+    'standard_b64decode',
+    'urlsafe_b64encode',
+    'urlsafe_b64decode',
+]
+
+def b64encode(s: ReadableBuffer, altchars: ReadableBuffer | None = None) -> bytes: ...
+def b64decode(s: str | ReadableBuffer, altchars: str | ReadableBuffer | None = None, validate: bool = False) -> bytes: ...
+def standard_b64encode(s: ReadableBuffer) -> bytes: ...
+def standard_b64decode(s: str | ReadableBuffer) -> bytes: ...
+def urlsafe_b64encode(s: ReadableBuffer) -> bytes: ...
+def urlsafe_b64decode(s: str | ReadableBuffer) -> bytes: ...
+def b32encode(s: ReadableBuffer) -> bytes: ...
+def b32decode(s: str | ReadableBuffer, casefold: bool = False, map01: str | ReadableBuffer | None = None) -> bytes: ...
+def b16encode(s: ReadableBuffer) -> bytes: ...
+def b16decode(s: str | ReadableBuffer, casefold: bool = False) -> bytes: ...
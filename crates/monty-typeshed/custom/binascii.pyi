@@ -0,0 +1,16 @@
+from _typeshed import ReadableBuffer
+from typing_extensions import TypeAlias
+
+# Many functions in binascii accept buffer objects
+# or ASCII-only strings.
+_AsciiBuffer: TypeAlias = str | ReadableBuffer
+
+def a2b_base64(data: _AsciiBuffer, /, *, strict_mode: bool = False) -> bytes: ...
+def b2a_base64(data: ReadableBuffer, /, *, newline: bool = True) -> bytes: ...
+def crc32(data: ReadableBuffer, crc: int = 0, /) -> int: ...
+def b2a_hex(data: ReadableBuffer, /) -> bytes: ...
+def hexlify(data: ReadableBuffer, /) -> bytes: ...
+def a2b_hex(hexstr: _AsciiBuffer, /) -> bytes: ...
+def unhexlify(hexstr: _AsciiBuffer, /) -> bytes: ...
+
+class Error(ValueError): ...
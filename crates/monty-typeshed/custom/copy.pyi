@@ -0,0 +1,8 @@
+from typing import Any, TypeVar
+
+__all__ = ['copy', 'deepcopy']
+
+_T = TypeVar('_T')
+
+def copy(x: _T) -> _T: ...
+def deepcopy(x: _T, memo: dict[int, Any] | None = None) -> _T: ...
@@ -0,0 +1,136 @@
+from collections.abc import Iterable, Iterator, Mapping, Sequence
+from io import StringIO
+from typing import Any, Final, Literal
+
+__all__ = [
+    'QUOTE_MINIMAL',
+    'QUOTE_ALL',
+    'QUOTE_NONNUMERIC',
+    'QUOTE_NONE',
+    'Error',
+    'Dialect',
+    'excel',
+    'excel_tab',
+    'unix_dialect',
+    'get_dialect',
+    'list_dialects',
+    'Sniffer',
+    'reader',
+    'writer',
+    'DictReader',
+    'DictWriter',
+]
+
+QUOTE_MINIMAL: Final = 0
+QUOTE_ALL: Final = 1
+QUOTE_NONNUMERIC: Final = 2
+QUOTE_NONE: Final = 3
+QUOTE_STRINGS: Final = 4
+QUOTE_NOTNULL: Final = 5
+
+class Error(Exception): ...
+
+class Dialect:
+    @property
+    def delimiter(self) -> str: ...
+    @property
+    def quotechar(self) -> str | None: ...
+    @property
+    def escapechar(self) -> str | None: ...
+    @property
+    def doublequote(self) -> bool: ...
+    @property
+    def skipinitialspace(self) -> bool: ...
+    @property
+    def lineterminator(self) -> str: ...
+    @property
+    def quoting(self) -> int: ...
+    @property
+    def strict(self) -> bool: ...
+
+excel: Dialect
+excel_tab: Dialect
+unix_dialect: Dialect
+
+_DialectLike = str | Dialect
+
+def get_dialect(name: str) -> Dialect: ...
+def list_dialects() -> list[str]: ...
+def reader(
+    csvfile: Iterable[str],
+    dialect: _DialectLike = 'excel',
+    *,
+    delimiter: str = ',',
+    quotechar: str | None = '"',
+    escapechar: str | None = None,
+    doublequote: bool = True,
+    skipinitialspace: bool = False,
+    lineterminator: str = '\r\n',
+    quoting: int = 0,
+    strict: bool = False,
+) -> Iterator[list[str]]: ...
+
+class _writer:
+    @property
+    def dialect(self) -> Dialect: ...
+    def writerow(self, row: Iterable[Any], /) -> int: ...
+    def writerows(self, rows: Iterable[Iterable[Any]], /) -> None: ...
+
+def writer(
+    csvfile: StringIO,
+    dialect: _DialectLike = 'excel',
+    *,
+    delimiter: str = ',',
+    quotechar: str | None = '"',
+    escapechar: str | None = None,
+    doublequote: bool = True,
+    skipinitialspace: bool = False,
+    lineterminator: str = '\r\n',
+    quoting: int = 0,
+    strict: bool = False,
+) -> _writer: ...
+def DictReader(
+    f: Iterable[str],
+    fieldnames: Sequence[str] | None = None,
+    restkey: str | None = None,
+    restval: str | None = None,
+    dialect: _DialectLike = 'excel',
+    *,
+    delimiter: str = ',',
+    quotechar: str | None = '"',
+    escapechar: str | None = None,
+    doublequote: bool = True,
+    skipinitialspace: bool = False,
+    lineterminator: str = '\r\n',
+    quoting: int = 0,
+    strict: bool = False,
+) -> Iterator[dict[str | Any, str | Any]]: ...
+
+class DictWriter:
+    def __init__(
+        self,
+        f: StringIO,
+        fieldnames: Sequence[str],
+        restval: Any = '',
+        extrasaction: Literal['raise', 'ignore'] = 'raise',
+        dialect: _DialectLike = 'excel',
+        *,
+        delimiter: str = ',',
+        quotechar: str | None = '"',
+        escapechar: str | None = None,
+        doublequote: bool = True,
+        skipinitialspace: bool = False,
+        lineterminator: str = '\r\n',
+        quoting: int = 0,
+        strict: bool = False,
+    ) -> None: ...
+    @property
+    def dialect(self) -> Dialect: ...
+    def writeheader(self) -> int: ...
+    def writerow(self, rowdict: Mapping[str, Any]) -> int: ...
+    def writerows(self, rowdicts: Iterable[Mapping[str, Any]]) -> None: ...
+
+class Sniffer:
+    def __init__(self) -> None: ...
+    def sniff(self, sample: str, delimiters: str | None = None) -> Dialect: ...
+    def has_header(self, sample: str) -> bool: ...
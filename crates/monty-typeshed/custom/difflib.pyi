@@ -0,0 +1,39 @@
+from collections.abc import Callable, Sequence
+from typing import Literal, NamedTuple
+
+__all__ = ['get_close_matches', 'SequenceMatcher', 'unified_diff', 'Match']
+
+class Match(NamedTuple):
+    a: int
+    b: int
+    size: int
+
+class SequenceMatcher:
+    def __init__(
+        self,
+        isjunk: Callable[[str], bool] | None = None,
+        a: Sequence[str] = '',
+        b: Sequence[str] = '',
+        autojunk: bool = True,
+    ) -> None: ...
+    def find_longest_match(self, alo: int = 0, ahi: int | None = None, blo: int = 0, bhi: int | None = None) -> Match: ...
+    def get_matching_blocks(self) -> list[Match]: ...
+    def get_opcodes(self) -> list[tuple[Literal['replace', 'delete', 'insert', 'equal'], int, int, int, int]]: ...
+    def get_grouped_opcodes(
+        self, n: int = 3
+    ) -> list[list[tuple[Literal['replace', 'delete', 'insert', 'equal'], int, int, int, int]]]: ...
+    def ratio(self) -> float: ...
+    def quick_ratio(self) -> float: ...
+    def real_quick_ratio(self) -> float: ...
+
+def get_close_matches(word: Sequence[str], possibilities: Sequence[Sequence[str]], n: int = 3, cutoff: float = 0.6) -> list[str]: ...
+def unified_diff(
+    a: Sequence[str],
+    b: Sequence[str],
+    fromfile: str = '',
+    tofile: str = '',
+    fromfiledate: str = '',
+    tofiledate: str = '',
+    n: int = 3,
+    lineterm: str = '\n',
+) -> list[str]: ...
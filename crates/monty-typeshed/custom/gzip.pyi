@@ -0,0 +1,6 @@
+__all__ = ['BadGzipFile', 'compress', 'decompress']
+
+class BadGzipFile(OSError): ...
+
+def compress(data: bytes, compresslevel: int = 9, *, mtime: int | None = None) -> bytes: ...
+def decompress(data: bytes) -> bytes: ...
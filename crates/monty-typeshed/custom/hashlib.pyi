@@ -0,0 +1,45 @@
+from _typeshed import ReadableBuffer
+from typing import final
+from typing_extensions import Self
+
+__all__ = (
+    'md5',
+    'sha1',
+    'sha224',
+    'sha256',
+    'sha384',
+    'sha512',
+    'blake2b',
+    'new',
+)
+
+class HASH:
+    @property
+    def digest_size(self) -> int: ...
+    @property
+    def block_size(self) -> int: ...
+    @property
+    def name(self) -> str: ...
+    def copy(self) -> Self: ...
+    def digest(self) -> bytes: ...
+    def hexdigest(self) -> str: ...
+    def update(self, obj: ReadableBuffer, /) -> None: ...
+
+@final
+class blake2b(HASH):
+    def __new__(
+        cls,
+        data: ReadableBuffer = b'',
+        *,
+        digest_size: int = 64,
+        key: ReadableBuffer = b'',
+        usedforsecurity: bool = True,
+    ) -> Self: ...
+
+def new(name: str, data: ReadableBuffer = b'', *, usedforsecurity: bool = True) -> HASH: ...
+def md5(data: ReadableBuffer = b'', *, usedforsecurity: bool = True) -> HASH: ...
+def sha1(data: ReadableBuffer = b'', *, usedforsecurity: bool = True) -> HASH: ...
+def sha224(data: ReadableBuffer = b'', *, usedforsecurity: bool = True) -> HASH: ...
+def sha256(data: ReadableBuffer = b'', *, usedforsecurity: bool = True) -> HASH: ...
+def sha384(data: ReadableBuffer = b'', *, usedforsecurity: bool = True) -> HASH: ...
+def sha512(data: ReadableBuffer = b'', *, usedforsecurity: bool = True) -> HASH: ...
@@ -0,0 +1,24 @@
+from _typeshed import SupportsRichComparison
+from collections.abc import Callable, Iterable
+from typing import Any, TypeVar
+
+__all__ = ['heappush', 'heappop', 'heapify', 'heapreplace', 'merge', 'nlargest', 'nsmallest', 'heappushpop']
+
+_S = TypeVar('_S')
+
+def heappush(heap: list[_S], item: _S, /) -> None: ...
+def heappop(heap: list[_S], /) -> _S: ...
+def heappushpop(heap: list[_S], item: _S, /) -> _S: ...
+def heapify(heap: list[Any], /) -> None: ...
+def heapreplace(heap: list[_S], item: _S, /) -> _S: ...
+
+# monty returns a list rather than an iterator
+def merge(
+    *iterables: Iterable[_S], key: Callable[[_S], SupportsRichComparison] | None = None, reverse: bool = False
+) -> list[_S]: ...
+def nlargest(
+    n: int, iterable: Iterable[_S], key: Callable[[_S], SupportsRichComparison] | None = None
+) -> list[_S]: ...
+def nsmallest(
+    n: int, iterable: Iterable[_S], key: Callable[[_S], SupportsRichComparison] | None = None
+) -> list[_S]: ...
@@ -0,0 +1,31 @@
+from _typeshed import ReadableBuffer
+from collections.abc import Callable
+from hashlib import HASH
+from typing import overload
+from typing_extensions import TypeAlias
+
+_DigestMod: TypeAlias = str | Callable[[], HASH]
+
+# `digestmod` is required, but may be passed positionally or by keyword
+@overload
+def new(key: bytes | bytearray, msg: ReadableBuffer | None, digestmod: _DigestMod) -> HMAC: ...
+@overload
+def new(key: bytes | bytearray, *, digestmod: _DigestMod) -> HMAC: ...
+
+class HMAC:
+    @property
+    def digest_size(self) -> int: ...
+    @property
+    def block_size(self) -> int: ...
+    @property
+    def name(self) -> str: ...
+    def update(self, msg: ReadableBuffer) -> None: ...
+    def digest(self) -> bytes: ...
+    def hexdigest(self) -> str: ...
+    def copy(self) -> HMAC: ...
+
+def digest(key: ReadableBuffer, msg: ReadableBuffer, digest: _DigestMod) -> bytes: ...
+@overload
+def compare_digest(a: ReadableBuffer, b: ReadableBuffer, /) -> bool: ...
+@overload
+def compare_digest(a: str, b: str, /) -> bool: ...
@@ -0,0 +1,88 @@
+from collections.abc import Iterable, Iterator
+from types import TracebackType
+
+from typing_extensions import Self
+
+__all__ = ['StringIO', 'UnsupportedOperation']
+
+class UnsupportedOperation(OSError, ValueError): ...
+
+class StringIO:
+    def __init__(self, initial_value: str | None = '', newline: str | None = '\n') -> None: ...
+    @property
+    def closed(self) -> bool: ...
+    def close(self) -> None: ...
+    def getvalue(self) -> str: ...
+    def read(self, size: int | None = -1, /) -> str: ...
+    def readline(self, size: int | None = -1, /) -> str: ...
+    def readlines(self, hint: int | None = -1, /) -> list[str]: ...
+    def write(self, s: str, /) -> int: ...
+    def writelines(self, lines: Iterable[str], /) -> None: ...
+    def seek(self, pos: int, whence: int = 0, /) -> int: ...
+    def tell(self) -> int: ...
+    def truncate(self, pos: int | None = None, /) -> int: ...
+    def __enter__(self) -> Self: ...
+    def __exit__(
+        self, exc_type: type[BaseException] | None, exc_val: BaseException | None, exc_tb: TracebackType | None, /
+    ) -> None: ...
+
+# File objects returned by `open()` and `Path.open()`, which can't be constructed directly.
+
+class TextIOWrapper:
+    @property
+    def name(self) -> str: ...
+    @property
+    def mode(self) -> str: ...
+    @property
+    def encoding(self) -> str: ...
+    @property
+    def closed(self) -> bool: ...
+    def close(self) -> None: ...
+    def read(self, size: int | None = -1, /) -> str: ...
+    def readline(self, size: int | None = -1, /) -> str: ...
+    def readlines(self, hint: int = -1, /) -> list[str]: ...
+    def write(self, s: str, /) -> int: ...
+    def writelines(self, lines: Iterable[str], /) -> None: ...
+    def seek(self, cookie: int, whence: int = 0, /) -> int: ...
+    def tell(self) -> int: ...
+    def flush(self) -> None: ...
+    def readable(self) -> bool: ...
+    def writable(self) -> bool: ...
+    def seekable(self) -> bool: ...
+    def __iter__(self) -> Iterator[str]: ...
+    def __enter__(self) -> Self: ...
+    def __exit__(
+        self, exc_type: type[BaseException] | None, exc_val: BaseException | None, exc_tb: TracebackType | None, /
+    ) -> None: ...
+
+class _BufferedFile:
+    @property
+    def name(self) -> str: ...
+    @property
+    def mode(self) -> str: ...
+    @property
+    def closed(self) -> bool: ...
+    def close(self) -> None: ...
+    def read(self, size: int | None = -1, /) -> bytes: ...
+    def readline(self, size: int | None = -1, /) -> bytes: ...
+    def readlines(self, hint: int = -1, /) -> list[bytes]: ...
+    def write(self, buffer: bytes, /) -> int: ...
+    def writelines(self, lines: Iterable[bytes], /) -> None: ...
+    def seek(self, target: int, whence: int = 0, /) -> int: ...
+    def tell(self) -> int: ...
+    def flush(self) -> None: ...
+    def readable(self) -> bool: ...
+    def writable(self) -> bool: ...
+    def seekable(self) -> bool: ...
+    def __iter__(self) -> Iterator[bytes]: ...
+    def __enter__(self) -> Self: ...
+    def __exit__(
+        self, exc_type: type[BaseException] | None, exc_val: BaseException | None, exc_tb: TracebackType | None, /
+    ) -> None: ...
+
+class BufferedReader(_BufferedFile): ...
+class BufferedWriter(_BufferedFile): ...
+class BufferedRandom(_BufferedFile): ...
+
+# Unbuffered files aren't supported, `buffering=0` still gives a buffered file.
+class FileIO(_BufferedFile): ...
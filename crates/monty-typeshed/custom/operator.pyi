@@ -0,0 +1,104 @@
+from _typeshed import SupportsGetItem
+from collections.abc import Container, Iterable, Sequence
+from typing import Any, Generic, SupportsAbs, SupportsIndex, TypeVar, final, overload
+
+__all__ = [
+    'abs',
+    'add',
+    'and_',
+    'attrgetter',
+    'concat',
+    'contains',
+    'countOf',
+    'eq',
+    'floordiv',
+    'ge',
+    'getitem',
+    'gt',
+    'index',
+    'indexOf',
+    'inv',
+    'invert',
+    'is_',
+    'is_not',
+    'itemgetter',
+    'le',
+    'lshift',
+    'lt',
+    'methodcaller',
+    'mod',
+    'mul',
+    'ne',
+    'neg',
+    'not_',
+    'or_',
+    'pos',
+    'pow',
+    'rshift',
+    'sub',
+    'truediv',
+    'truth',
+    'xor',
+]
+
+_T = TypeVar('_T')
+_T_co = TypeVar('_T_co', covariant=True)
+_K = TypeVar('_K')
+_V = TypeVar('_V')
+
+def lt(a: Any, b: Any, /) -> Any: ...
+def le(a: Any, b: Any, /) -> Any: ...
+def eq(a: object, b: object, /) -> Any: ...
+def ne(a: object, b: object, /) -> Any: ...
+def ge(a: Any, b: Any, /) -> Any: ...
+def gt(a: Any, b: Any, /) -> Any: ...
+def not_(a: object, /) -> bool: ...
+def truth(a: object, /) -> bool: ...
+def is_(a: object, b: object, /) -> bool: ...
+def is_not(a: object, b: object, /) -> bool: ...
+def abs(a: SupportsAbs[_T], /) -> _T: ...
+def add(a: Any, b: Any, /) -> Any: ...
+def and_(a: Any, b: Any, /) -> Any: ...
+def floordiv(a: Any, b: Any, /) -> Any: ...
+def index(a: SupportsIndex, /) -> int: ...
+def inv(a: Any, /) -> Any: ...
+def invert(a: Any, /) -> Any: ...
+def lshift(a: Any, b: Any, /) -> Any: ...
+def mod(a: Any, b: Any, /) -> Any: ...
+def mul(a: Any, b: Any, /) -> Any: ...
+def neg(a: Any, /) -> Any: ...
+def or_(a: Any, b: Any, /) -> Any: ...
+def pos(a: Any, /) -> Any: ...
+def pow(a: Any, b: Any, /) -> Any: ...
+def rshift(a: Any, b: Any, /) -> Any: ...
+def sub(a: Any, b: Any, /) -> Any: ...
+def truediv(a: Any, b: Any, /) -> Any: ...
+def xor(a: Any, b: Any, /) -> Any: ...
+def concat(a: Sequence[_T], b: Sequence[_T], /) -> Sequence[_T]: ...
+def contains(a: Container[object], b: object, /) -> bool: ...
+def countOf(a: Iterable[object], b: object, /) -> int: ...
+def indexOf(a: Iterable[_T], b: _T, /) -> int: ...
+@overload
+def getitem(a: Sequence[_T], b: slice, /) -> Sequence[_T]: ...
+@overload
+def getitem(a: SupportsGetItem[_K, _V], b: _K, /) -> _V: ...
+@final
+class attrgetter(Generic[_T_co]):
+    @overload
+    def __new__(cls, attr: str, /) -> attrgetter[Any]: ...
+    @overload
+    def __new__(cls, attr: str, attr2: str, /, *attrs: str) -> attrgetter[tuple[Any, ...]]: ...
+    def __call__(self, obj: Any, /) -> _T_co: ...
+
+@final
+class itemgetter(Generic[_T_co]):
+    @overload
+    def __new__(cls, item: Any, /) -> itemgetter[Any]: ...
+    @overload
+    def __new__(cls, item: Any, item2: Any, /, *items: Any) -> itemgetter[tuple[Any, ...]]: ...
+    def __call__(self, obj: Any, /) -> _T_co: ...
+
+@final
+class methodcaller:
+    def __new__(cls, name: str, /, *args: Any, **kwargs: Any) -> methodcaller: ...
+    def __call__(self, obj: Any) -> Any: ...
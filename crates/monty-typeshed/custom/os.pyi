@@ -1,7 +1,7 @@
 from abc import ABC, abstractmethod
 from typing import Callable, Protocol, TypeAlias, TypeVar, final, overload, runtime_checkable
 
-from _typeshed import AnyStr_co, structseq
+from _typeshed import AnyStr_co, StrPath, structseq
 
 _T = TypeVar('_T')
 environ: dict[str, str]
@@ -10,6 +10,15 @@ environ: dict[str, str]
 def getenv(key: str) -> str | None: ...
 @overload
 def getenv(key: str, default: _T) -> str | _T: ...
+def urandom(size: int, /) -> bytes: ...
+
+# monty returns the whole walk as a list rather than a generator
+def walk(
+    top: StrPath,
+    topdown: bool = True,
+    onerror: Callable[[OSError], object] | None = None,
+    followlinks: bool = False,
+) -> list[tuple[str, list[str], list[str]]]: ...
 @final
 class stat_result(structseq[float], tuple[int, int, int, int, int, int, int, float, float, float]):
     # The constructor of this class takes an iterable of variable length (though it must be at least 10).
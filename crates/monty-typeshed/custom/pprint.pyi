@@ -0,0 +1,39 @@
+from _typeshed import SupportsWrite
+
+__all__ = ['pprint', 'pformat', 'isreadable', 'isrecursive', 'saferepr', 'pp']
+
+def pformat(
+    object: object,
+    indent: int = 1,
+    width: int = 80,
+    depth: int | None = None,
+    *,
+    compact: bool = False,
+    sort_dicts: bool = True,
+    underscore_numbers: bool = False,
+) -> str: ...
+def pp(
+    object: object,
+    stream: SupportsWrite[str] | None = None,
+    indent: int = 1,
+    width: int = 80,
+    depth: int | None = None,
+    *,
+    compact: bool = False,
+    sort_dicts: bool = False,
+    underscore_numbers: bool = False,
+) -> None: ...
+def pprint(
+    object: object,
+    stream: SupportsWrite[str] | None = None,
+    indent: int = 1,
+    width: int = 80,
+    depth: int | None = None,
+    *,
+    compact: bool = False,
+    sort_dicts: bool = True,
+    underscore_numbers: bool = False,
+) -> None: ...
+def isreadable(object: object) -> bool: ...
+def isrecursive(object: object) -> bool: ...
+def saferepr(object: object) -> str: ...
@@ -0,0 +1,3 @@
+__all__ = ['repr']
+
+def repr(x: object) -> str: ...
@@ -0,0 +1,11 @@
+from typing import TypeVar
+
+from _typeshed import StrPath
+
+__all__ = ['copy', 'copytree', 'rmtree']
+
+_StrPathT = TypeVar('_StrPathT', bound=StrPath)
+
+def copy(src: StrPath, dst: _StrPathT, *, follow_symlinks: bool = True) -> _StrPathT | str: ...
+def copytree(src: StrPath, dst: _StrPathT, *, dirs_exist_ok: bool = False) -> _StrPathT: ...
+def rmtree(path: StrPath, ignore_errors: bool = False) -> None: ...
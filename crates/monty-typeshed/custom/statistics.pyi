@@ -0,0 +1,39 @@
+from _typeshed import SupportsRichComparisonT
+from collections.abc import Hashable, Iterable
+from typing import Literal, SupportsFloat, TypeVar
+
+__all__ = [
+    'StatisticsError',
+    'fmean',
+    'mean',
+    'median',
+    'median_high',
+    'median_low',
+    'mode',
+    'multimode',
+    'pstdev',
+    'pvariance',
+    'quantiles',
+    'stdev',
+    'variance',
+]
+
+_NumberT = TypeVar('_NumberT', float, int)
+_HashableT = TypeVar('_HashableT', bound=Hashable)
+
+class StatisticsError(ValueError): ...
+
+def mean(data: Iterable[_NumberT]) -> _NumberT: ...
+def fmean(data: Iterable[SupportsFloat], weights: Iterable[SupportsFloat] | None = None) -> float: ...
+def median(data: Iterable[_NumberT]) -> _NumberT: ...
+def median_low(data: Iterable[SupportsRichComparisonT]) -> SupportsRichComparisonT: ...
+def median_high(data: Iterable[SupportsRichComparisonT]) -> SupportsRichComparisonT: ...
+def mode(data: Iterable[_HashableT]) -> _HashableT: ...
+def multimode(data: Iterable[_HashableT]) -> list[_HashableT]: ...
+def variance(data: Iterable[_NumberT], xbar: _NumberT | None = None) -> _NumberT: ...
+def pvariance(data: Iterable[_NumberT], mu: _NumberT | None = None) -> _NumberT: ...
+def stdev(data: Iterable[_NumberT], xbar: _NumberT | None = None) -> float: ...
+def pstdev(data: Iterable[_NumberT], mu: _NumberT | None = None) -> float: ...
+def quantiles(
+    data: Iterable[_NumberT], *, n: int = 4, method: Literal['inclusive', 'exclusive'] = 'exclusive'
+) -> list[_NumberT]: ...
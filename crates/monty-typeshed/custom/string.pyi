@@ -0,0 +1,31 @@
+from collections.abc import Mapping
+from typing import Final
+
+__all__ = [
+    'ascii_letters',
+    'ascii_lowercase',
+    'ascii_uppercase',
+    'digits',
+    'hexdigits',
+    'octdigits',
+    'printable',
+    'punctuation',
+    'whitespace',
+    'Template',
+]
+
+whitespace: Final = ' \t\n\r\v\f'
+ascii_lowercase: Final = 'abcdefghijklmnopqrstuvwxyz'
+ascii_uppercase: Final = 'ABCDEFGHIJKLMNOPQRSTUVWXYZ'
+ascii_letters: Final = 'abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ'
+digits: Final = '0123456789'
+hexdigits: Final = '0123456789abcdefABCDEF'
+octdigits: Final = '01234567'
+punctuation: Final = r"""!"#$%&'()*+,-./:;<=>?@[\]^_`{|}~"""
+printable: Final[str]
+
+class Template:
+    template: str
+    def __init__(self, template: str) -> None: ...
+    def substitute(self, mapping: Mapping[str, object] = ..., /, **kwds: object) -> str: ...
+    def safe_substitute(self, mapping: Mapping[str, object] = ..., /, **kwds: object) -> str: ...
@@ -0,0 +1,23 @@
+from collections.abc import Iterator
+from typing import Any
+
+__all__ = ['calcsize', 'pack', 'unpack', 'unpack_from', 'iter_unpack', 'Struct', 'error']
+
+class error(Exception): ...
+
+def pack(fmt: str | bytes, /, *v: Any) -> bytes: ...
+def unpack(format: str | bytes, buffer: bytes, /) -> tuple[Any, ...]: ...
+def unpack_from(format: str | bytes, /, buffer: bytes, offset: int = 0) -> tuple[Any, ...]: ...
+def iter_unpack(format: str | bytes, buffer: bytes, /) -> Iterator[tuple[Any, ...]]: ...
+def calcsize(format: str | bytes, /) -> int: ...
+
+class Struct:
+    @property
+    def format(self) -> str: ...
+    @property
+    def size(self) -> int: ...
+    def __init__(self, format: str | bytes) -> None: ...
+    def pack(self, *v: Any) -> bytes: ...
+    def unpack(self, buffer: bytes, /) -> tuple[Any, ...]: ...
+    def unpack_from(self, buffer: bytes, offset: int = 0) -> tuple[Any, ...]: ...
+    def iter_unpack(self, buffer: bytes, /) -> Iterator[tuple[Any, ...]]: ...
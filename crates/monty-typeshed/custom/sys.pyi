@@ -1,8 +1,12 @@
-from typing import Any, Final, Literal, TextIO, final, type_check_only
+from typing import Any, Final, Literal, NoReturn, TextIO, final, type_check_only
 
 from _typeshed import MaybeNone, structseq
 from typing_extensions import TypeAlias
 
+_ExitCode: TypeAlias = str | int | None
+
+argv: list[str]
+
 # stdin: TextIO | MaybeNone
 stdout: TextIO | MaybeNone
 stderr: TextIO | MaybeNone
@@ -31,3 +35,5 @@ class _version_info(_UninstantiableStructseq, tuple[int, int, int, _ReleaseLevel
     def serial(self) -> int: ...
 
 version_info: _version_info
+
+def exit(status: _ExitCode = None, /) -> NoReturn: ...
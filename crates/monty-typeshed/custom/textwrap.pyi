@@ -0,0 +1,53 @@
+from collections.abc import Callable
+
+__all__ = ['wrap', 'fill', 'shorten', 'dedent', 'indent']
+
+def wrap(
+    text: str,
+    width: int = 70,
+    *,
+    initial_indent: str = '',
+    subsequent_indent: str = '',
+    expand_tabs: bool = True,
+    tabsize: int = 8,
+    replace_whitespace: bool = True,
+    fix_sentence_endings: bool = False,
+    break_long_words: bool = True,
+    break_on_hyphens: bool = True,
+    drop_whitespace: bool = True,
+    max_lines: int | None = None,
+    placeholder: str = ' [...]',
+) -> list[str]: ...
+def fill(
+    text: str,
+    width: int = 70,
+    *,
+    initial_indent: str = '',
+    subsequent_indent: str = '',
+    expand_tabs: bool = True,
+    tabsize: int = 8,
+    replace_whitespace: bool = True,
+    fix_sentence_endings: bool = False,
+    break_long_words: bool = True,
+    break_on_hyphens: bool = True,
+    drop_whitespace: bool = True,
+    max_lines: int | None = None,
+    placeholder: str = ' [...]',
+) -> str: ...
+def shorten(
+    text: str,
+    width: int,
+    *,
+    initial_indent: str = '',
+    subsequent_indent: str = '',
+    expand_tabs: bool = True,
+    tabsize: int = 8,
+    replace_whitespace: bool = True,
+    fix_sentence_endings: bool = False,
+    break_long_words: bool = True,
+    break_on_hyphens: bool = True,
+    drop_whitespace: bool = True,
+    placeholder: str = ' [...]',
+) -> str: ...
+def dedent(text: str) -> str: ...
+def indent(text: str, prefix: str, predicate: Callable[[str], bool] | None = None) -> str: ...
@@ -0,0 +1,4 @@
+from typing import Literal
+
+def normalize(form: Literal['NFC', 'NFD', 'NFKC', 'NFKD'], unistr: str, /) -> str: ...
+def is_normalized(form: Literal['NFC', 'NFD', 'NFKC', 'NFKD'], unistr: str, /) -> bool: ...
@@ -0,0 +1,80 @@
+from typing import Final
+
+__all__ = [
+    'UUID',
+    'uuid3',
+    'uuid4',
+    'uuid5',
+    'NAMESPACE_DNS',
+    'NAMESPACE_URL',
+    'NAMESPACE_OID',
+    'NAMESPACE_X500',
+    'RESERVED_NCS',
+    'RFC_4122',
+    'RESERVED_MICROSOFT',
+    'RESERVED_FUTURE',
+]
+
+_FieldsType = tuple[int, int, int, int, int, int]
+
+class UUID:
+    def __init__(
+        self,
+        hex: str | None = None,
+        bytes: bytes | None = None,
+        bytes_le: bytes | None = None,
+        fields: _FieldsType | None = None,
+        int: int | None = None,
+        version: int | None = None,
+    ) -> None: ...
+    @property
+    def bytes(self) -> bytes: ...
+    @property
+    def bytes_le(self) -> bytes: ...
+    @property
+    def clock_seq(self) -> int: ...
+    @property
+    def clock_seq_hi_variant(self) -> int: ...
+    @property
+    def clock_seq_low(self) -> int: ...
+    @property
+    def fields(self) -> _FieldsType: ...
+    @property
+    def hex(self) -> str: ...
+    @property
+    def int(self) -> int: ...
+    @property
+    def node(self) -> int: ...
+    @property
+    def time(self) -> int: ...
+    @property
+    def time_hi_version(self) -> int: ...
+    @property
+    def time_low(self) -> int: ...
+    @property
+    def time_mid(self) -> int: ...
+    @property
+    def urn(self) -> str: ...
+    @property
+    def variant(self) -> str: ...
+    @property
+    def version(self) -> int | None: ...
+    def __eq__(self, other: object) -> bool: ...
+    def __lt__(self, other: UUID) -> bool: ...
+    def __le__(self, other: UUID) -> bool: ...
+    def __gt__(self, other: UUID) -> bool: ...
+    def __ge__(self, other: UUID) -> bool: ...
+    def __hash__(self) -> int: ...
+
+def uuid3(namespace: UUID, name: str | bytes) -> UUID: ...
+def uuid4() -> UUID: ...
+def uuid5(namespace: UUID, name: str | bytes) -> UUID: ...
+
+NAMESPACE_DNS: Final[UUID]
+NAMESPACE_URL: Final[UUID]
+NAMESPACE_OID: Final[UUID]
+NAMESPACE_X500: Final[UUID]
+RESERVED_NCS: Final[str]
+RFC_4122: Final[str]
+RESERVED_MICROSOFT: Final[str]
+RESERVED_FUTURE: Final[str]
@@ -0,0 +1,17 @@
+from typing import Final
+
+MAX_WBITS: Final[int]
+DEFLATED: Final[int]
+DEF_BUF_SIZE: Final[int]
+DEF_MEM_LEVEL: Final[int]
+Z_NO_COMPRESSION: Final[int]
+Z_BEST_SPEED: Final[int]
+Z_BEST_COMPRESSION: Final[int]
+Z_DEFAULT_COMPRESSION: Final[int]
+
+class error(Exception): ...
+
+def adler32(data: bytes, value: int = 1, /) -> int: ...
+def crc32(data: bytes, value: int = 0, /) -> int: ...
+def compress(data: bytes, /, level: int = -1, wbits: int = 15) -> bytes: ...
+def decompress(data: bytes, /, wbits: int = 15, bufsize: int = 16384) -> bytes: ...
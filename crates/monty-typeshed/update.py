@@ -19,29 +19,46 @@ from pathlib import Path
 # Whitelisted builtin functions (from crates/monty/src/builtins/)
 ALLOWED_FUNCTIONS = {
     'abs',
+    'aiter',
     'all',
+    'anext',
     'any',
+    'ascii',
     'bin',
+    'callable',
     'chr',
+    'delattr',
+    'dir',
     'divmod',
+    'format',
+    'globals',
+    'hasattr',
     'hash',
     'hex',
     'id',
+    'input',
     'isinstance',
+    'issubclass',
+    'iter',
     'len',
+    'locals',
     'max',
     'min',
     'oct',
+    'open',
     'ord',
     'pow',
     'print',
     'repr',
     'round',
+    'setattr',
     'sorted',
     'sum',
+    'vars',
 }
 
 # Whitelisted builtin classes (from crates/monty/src/types/ and exception_private.rs)
+# `bytearray` is missing as monty has no mutable bytes type, see UNSUPPORTED_BUILTINS.
 ALLOWED_CLASSES = {
     # Core types
     'object',
@@ -84,6 +101,7 @@ ALLOWED_CLASSES = {
     'RecursionError',
     'AttributeError',
     'AssertionError',
+    'EOFError',
     'MemoryError',
     'NameError',
     'SyntaxError',
@@ -94,6 +112,10 @@ ALLOWED_CLASSES = {
     'StopIteration',
 }
 
+# Builtins monty doesn't implement, named in the header of the filtered builtins.pyi
+# so the stub doesn't suggest full coverage of CPython's builtins
+UNSUPPORTED_BUILTINS = ['bytearray']
+
 # Files to copy without filtering
 COPY_FILES = [
     # Core type system
@@ -133,18 +155,40 @@ VERSIONS = """\
 _collections_abc: 3.3-
 _typeshed: 3.0-  # not present at runtime, only for type checking
 asyncio: 3.4-
+base64: 3.0-
+binascii: 3.0-
+bisect: 3.0-
 builtins: 3.0-
 collections: 3.0-
+copy: 3.0-
+csv: 3.0-
 dataclasses: 3.7-
+difflib: 3.0-
+gzip: 3.0-
+hashlib: 3.0-
+heapq: 3.0-
+hmac: 3.0-
+io: 3.0-
 math: 3.0-
+operator: 3.0-
 os: 3.0-
 pathlib: 3.4-
 pathlib.types: 3.14-
+pprint: 3.0-
 re: 3.0-
+reprlib: 3.0-
+shutil: 3.0-
+statistics: 3.0-
+string: 3.0-
+struct: 3.0-
 sys: 3.0-
+textwrap: 3.0-
 typing: 3.5-
 typing_extensions: 3.7-
 types: 3.0-
+unicodedata: 3.0-
+uuid: 3.0-
+zlib: 3.0-
 """
 
 CRATE_DIR = Path(__file__).parent
@@ -263,7 +307,11 @@ def filter_builtins(source: str) -> str:
     tree = ast.parse(source)
     tree.body = filter_statements(tree.body)
     ast.fix_missing_locations(tree)
-    return ast.unparse(tree)
+    header = (
+        '# Filtered by crates/monty-typeshed/update.py to the builtins monty implements.\n'
+        f'# Not supported: {", ".join(UNSUPPORTED_BUILTINS)}.\n'
+    )
+    return header + ast.unparse(tree)
 
 
 def main() -> int:
@@ -194,6 +194,99 @@ impl ArgValues {
         Ok((val1_guard.into_inner(), val2_guard.into_inner()))
     }
 
+    /// Binds positional and keyword arguments to a fixed list of parameter names.
+    ///
+    /// This covers the common stdlib signature shape `func(a, b, c=..., d=...)` where every
+    /// parameter may be passed either by position or by keyword. The first `required`
+    /// parameters must be supplied; the rest are `None` when omitted so callers can apply
+    /// their own defaults.
+    ///
+    /// # Errors
+    /// Returns a `TypeError` (after dropping all values) if too many positional arguments
+    /// are passed, a keyword is unknown or duplicates a positional argument, or a required
+    /// parameter is missing.
+    pub fn bind_params<const N: usize>(
+        self,
+        func_name: &str,
+        params: [&str; N],
+        required: usize,
+        heap: &mut Heap<impl ResourceTracker>,
+        interns: &Interns,
+    ) -> RunResult<[Option<Value>; N]> {
+        self.bind_params_kw_only(func_name, params, N, required, heap, interns)
+    }
+
+    /// Like [`Self::bind_params`], but only the first `positional` parameters may be passed
+    /// by position; the rest are keyword-only, as after a bare `*` in a Python signature.
+    ///
+    /// # Errors
+    /// As for [`Self::bind_params`]; passing a keyword-only parameter by position gives
+    /// CPython's `{name}() takes at most {positional} positional argument(s) ({n} given)`.
+    pub fn bind_params_kw_only<const N: usize>(
+        self,
+        func_name: &str,
+        params: [&str; N],
+        positional: usize,
+        required: usize,
+        heap: &mut Heap<impl ResourceTracker>,
+        interns: &Interns,
+    ) -> RunResult<[Option<Value>; N]> {
+        let (pos, kwargs) = self.into_parts();
+        let pos_count = pos.len();
+        if pos_count > positional {
+            pos.drop_with_heap(heap);
+            kwargs.drop_with_heap(heap);
+            if positional == N {
+                return Err(ExcType::type_error_at_most(func_name, N, pos_count));
+            }
+            let plural = if positional == 1 { "" } else { "s" };
+            return Err(ExcType::type_error(format!(
+                "{func_name}() takes at most {positional} positional argument{plural} ({pos_count} given)"
+            )));
+        }
+
+        let mut bound: [Option<Value>; N] = std::array::from_fn(|_| None);
+        for (slot, value) in bound.iter_mut().zip(pos) {
+            *slot = Some(value);
+        }
+        let mut bound_guard = HeapGuard::new(bound, heap);
+        {
+            let (bound, heap) = bound_guard.as_parts_mut();
+            let kwargs = kwargs.into_iter();
+            defer_drop_mut!(kwargs, heap);
+            for (key, value) in kwargs {
+                defer_drop!(key, heap);
+                let mut value = HeapGuard::new(value, heap);
+
+                let Some(keyword_name) = key.as_either_str(value.heap()) else {
+                    return Err(ExcType::type_error("keywords must be strings"));
+                };
+                let key_str = keyword_name.as_str(interns);
+                let Some(index) = params.iter().position(|param| *param == key_str) else {
+                    return Err(ExcType::type_error(format!(
+                        "'{key_str}' is an invalid keyword argument for {func_name}()"
+                    )));
+                };
+                if bound[index].is_some() {
+                    return Err(ExcType::type_error(format!(
+                        "{func_name}() got multiple values for argument '{key_str}'"
+                    )));
+                }
+                bound[index] = Some(value.into_inner());
+            }
+        }
+
+        let (bound, _) = bound_guard.as_parts();
+        if let Some(missing) = bound[..required].iter().position(Option::is_none) {
+            return Err(ExcType::type_error(format!(
+                "{func_name}() missing required argument '{}' (pos {})",
+                params[missing],
+                missing + 1
+            )));
+        }
+        Ok(bound_guard.into_inner())
+    }
+
     /// Prepends a value as the first positional argument.
     ///
     /// Used to insert `self` when dispatching dataclass method calls to the host.
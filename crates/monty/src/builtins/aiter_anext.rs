@@ -0,0 +1,38 @@
+//! Implementation of the aiter() and anext() builtin functions.
+//!
+//! Monty has no async iterators: async generators and `async for` aren't supported, and
+//! no type defines `__aiter__` or `__anext__`. So both builtins check their arguments and
+//! then raise the `TypeError` CPython raises for every other type.
+
+use crate::{
+    args::ArgValues,
+    bytecode::VM,
+    exception_private::{ExcType, RunResult},
+    heap::DropWithHeap,
+    resource::ResourceTracker,
+    types::PyTrait,
+    value::Value,
+};
+
+/// Implementation of the aiter() builtin function.
+///
+/// Returns the async iterator of an async iterable, which no monty type is.
+pub fn builtin_aiter(vm: &mut VM<'_, '_, impl ResourceTracker>, args: ArgValues) -> RunResult<Value> {
+    let iterable = args.get_one_arg("aiter", vm.heap)?;
+    let ty = iterable.py_type(vm.heap);
+    iterable.drop_with_heap(vm);
+    Err(ExcType::type_error(format!("'{ty}' object is not an async iterable")))
+}
+
+/// Implementation of the anext() builtin function.
+///
+/// Returns an awaitable for the next item of an async iterator, which no monty type is.
+/// Like CPython, `default` is only checked for by arity, as it's only used once the
+/// iterator raises `StopAsyncIteration`.
+pub fn builtin_anext(vm: &mut VM<'_, '_, impl ResourceTracker>, args: ArgValues) -> RunResult<Value> {
+    let (iterator, default) = args.get_one_two_args("anext", vm.heap)?;
+    let ty = iterator.py_type(vm.heap);
+    iterator.drop_with_heap(vm);
+    default.drop_with_heap(vm);
+    Err(ExcType::type_error(format!("'{ty}' object is not an async iterator")))
+}
@@ -0,0 +1,23 @@
+//! Implementation of the ascii() builtin function.
+
+use crate::{
+    args::ArgValues,
+    bytecode::VM,
+    defer_drop,
+    exception_private::RunResult,
+    fstring::ascii_escape,
+    resource::ResourceTracker,
+    types::{PyTrait, str::allocate_string},
+    value::Value,
+};
+
+/// Implementation of the ascii() builtin function.
+///
+/// Returns the repr of an object with its non-ASCII characters escaped using `\x`, `\u`
+/// or `\U` escapes, as the `!a` conversion does in f-strings.
+pub fn builtin_ascii(vm: &mut VM<'_, '_, impl ResourceTracker>, args: ArgValues) -> RunResult<Value> {
+    let value = args.get_one_arg("ascii", vm.heap)?;
+    defer_drop!(value, vm);
+    let escaped = ascii_escape(&value.py_repr(vm));
+    Ok(allocate_string(escaped, vm.heap)?)
+}
@@ -0,0 +1,16 @@
+//! Implementation of the callable() builtin function.
+
+use crate::{
+    args::ArgValues, bytecode::VM, defer_drop, exception_private::RunResult, resource::ResourceTracker, value::Value,
+};
+
+/// Implementation of the callable() builtin function.
+///
+/// Returns whether `object` can be called: functions (including host functions), builtins,
+/// types and exception classes are callable, while instances of other types are not.
+pub fn builtin_callable(vm: &mut VM<'_, '_, impl ResourceTracker>, args: ArgValues) -> RunResult<Value> {
+    let value = args.get_one_arg("callable", vm.heap)?;
+    defer_drop!(value, vm);
+
+    Ok(Value::Bool(value.is_callable(vm.heap)))
+}
@@ -0,0 +1,33 @@
+//! Implementation of the delattr() builtin function.
+
+use crate::{
+    args::ArgValues,
+    bytecode::VM,
+    defer_drop,
+    exception_private::{ExcType, RunResult},
+    resource::ResourceTracker,
+    types::PyTrait,
+    value::Value,
+};
+
+/// Implementation of the delattr() builtin function.
+///
+/// Deletes the named attribute of `object`. Only dataclass instances support it; deleting
+/// a field of a frozen dataclass raises `FrozenInstanceError`.
+///
+/// Examples:
+/// ```python
+/// delattr(point, 'label')       # Remove point.label
+/// ```
+pub fn builtin_delattr(vm: &mut VM<'_, '_, impl ResourceTracker>, args: ArgValues) -> RunResult<Value> {
+    let (object, name) = args.get_two_args("delattr", vm.heap)?;
+    defer_drop!(object, vm);
+    defer_drop!(name, vm);
+
+    let Some(attr) = name.as_either_str(vm.heap) else {
+        return Err(ExcType::type_error_attribute_name(name.py_type(vm.heap)));
+    };
+
+    object.py_del_attr(&attr, vm)?;
+    Ok(Value::None)
+}
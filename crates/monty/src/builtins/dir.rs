@@ -0,0 +1,66 @@
+//! Implementation of the dir() builtin function.
+
+use crate::{
+    args::ArgValues,
+    bytecode::VM,
+    defer_drop,
+    exception_private::RunResult,
+    heap::{Heap, HeapData},
+    resource::ResourceTracker,
+    types::{Dict, List, PyTrait},
+    value::{EitherStr, Value},
+};
+
+/// Implementation of the dir() builtin function.
+///
+/// Without an argument, returns the sorted names in the current local scope. With an
+/// argument, returns the sorted attribute names of the object: the attributes of
+/// dataclasses and modules, the fields of named tuples, plus the methods the object's
+/// type supports (see `Type::methods()`).
+///
+/// Examples:
+/// ```python
+/// dir()                         # ['f', 'point', 'x']
+/// dir(point)                    # ['x', 'y']
+/// ```
+pub fn builtin_dir(vm: &mut VM<'_, '_, impl ResourceTracker>, args: ArgValues) -> RunResult<Value> {
+    let mut names = Vec::new();
+    if let Some(object) = args.get_zero_one_arg("dir", vm.heap)? {
+        defer_drop!(object, vm);
+        if let Value::Ref(heap_id) = object {
+            match vm.heap.get(*heap_id) {
+                HeapData::Dataclass(dc) => push_keys(&mut names, dc.attrs(), vm.heap),
+                HeapData::Module(module) => push_keys(&mut names, module.attrs(), vm.heap),
+                HeapData::NamedTuple(nt) => names.extend(nt.field_names().iter().cloned()),
+                _ => {}
+            }
+        }
+        let methods = object.py_type(vm.heap).methods();
+        names.extend(methods.iter().map(|&method| EitherStr::from(method)));
+    } else {
+        let locals = vm.locals_dict()?;
+        defer_drop!(locals, vm);
+        let Value::Ref(locals_id) = locals else {
+            unreachable!("locals_dict returns a dict")
+        };
+        let HeapData::Dict(dict) = vm.heap.get(*locals_id) else {
+            unreachable!("locals_dict returns a dict")
+        };
+        push_keys(&mut names, dict, vm.heap);
+    }
+
+    let interns = vm.interns;
+    names.sort_by(|a, b| a.as_str(interns).cmp(b.as_str(interns)));
+    names.dedup_by(|a, b| a.as_str(interns) == b.as_str(interns));
+
+    let mut items = Vec::with_capacity(names.len());
+    for name in &names {
+        items.push(name.to_value(vm.heap)?);
+    }
+    Ok(Value::Ref(vm.heap.allocate(HeapData::List(List::new(items)))?))
+}
+
+/// Adds the str keys of an attribute dict to `names`.
+fn push_keys(names: &mut Vec<EitherStr>, attrs: &Dict, heap: &Heap<impl ResourceTracker>) {
+    names.extend(attrs.iter().filter_map(|(key, _)| key.as_either_str(heap)));
+}
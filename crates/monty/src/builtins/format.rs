@@ -0,0 +1,50 @@
+//! Implementation of the format() builtin function.
+
+use crate::{
+    args::ArgValues,
+    bytecode::VM,
+    defer_drop,
+    exception_private::{ExcType, RunResult},
+    fstring::format_with_spec,
+    resource::{ResourceTracker, check_repeat_size},
+    types::{PyTrait, Type, str::allocate_string},
+    value::Value,
+};
+
+/// Implementation of the format() builtin function.
+///
+/// Formats a value with a format spec, using the same mini-language as f-strings, so
+/// `format(x, spec)` gives the same result as `f'{x:{spec}}'`. Without a spec (or with an
+/// empty one) this is `str(x)`.
+///
+/// Examples:
+/// ```python
+/// format(3.14159, '.2f')  # '3.14'
+/// format(42, '08b')       # '00101010'
+/// format('hi', '^6')      # '  hi  '
+/// ```
+pub fn builtin_format(vm: &mut VM<'_, '_, impl ResourceTracker>, args: ArgValues) -> RunResult<Value> {
+    let (value, spec) = args.get_one_two_args("format", vm.heap)?;
+    defer_drop!(value, vm);
+
+    let Some(spec) = spec else {
+        return Ok(allocate_string(value.py_str(vm).into_owned(), vm.heap)?);
+    };
+    defer_drop!(spec, vm);
+
+    let spec_type = spec.py_type(vm.heap);
+    if spec_type != Type::Str {
+        return Err(ExcType::type_error(format!(
+            "format() argument 2 must be str, not {spec_type}"
+        )));
+    }
+    if spec.py_str(vm).is_empty() {
+        return Ok(allocate_string(value.py_str(vm).into_owned(), vm.heap)?);
+    }
+
+    let spec = vm.get_format_spec(spec, value)?;
+    // Reject huge widths before padding allocates an untracked Rust String
+    check_repeat_size(spec.width, spec.fill.len_utf8(), vm.heap.tracker())?;
+    let formatted = format_with_spec(value, &spec, vm)?;
+    Ok(allocate_string(formatted, vm.heap)?)
+}
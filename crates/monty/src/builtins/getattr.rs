@@ -39,10 +39,7 @@ pub fn builtin_getattr(vm: &mut VM<'_, '_, impl ResourceTracker>, args: ArgValue
     };
 
     let Some(attr) = name.as_either_str(vm.heap) else {
-        let ty = name.py_type(vm.heap);
-        return Err(
-            SimpleException::new_msg(ExcType::TypeError, format!("attribute name must be string, not '{ty}'")).into(),
-        );
+        return Err(ExcType::type_error_attribute_name(name.py_type(vm.heap)));
     };
 
     match object.py_getattr(&attr, vm) {
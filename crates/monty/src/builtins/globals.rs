@@ -0,0 +1,11 @@
+//! Implementation of the globals() builtin function.
+
+use crate::{args::ArgValues, bytecode::VM, exception_private::RunResult, resource::ResourceTracker, value::Value};
+
+/// Implementation of the globals() builtin function.
+///
+/// Returns a dict of the module's bound global names, see `VM::globals_dict()`.
+pub fn builtin_globals(vm: &mut VM<'_, '_, impl ResourceTracker>, args: ArgValues) -> RunResult<Value> {
+    args.check_zero_args("globals", vm.heap)?;
+    vm.globals_dict()
+}
@@ -0,0 +1,50 @@
+//! Implementation of the hasattr() builtin function.
+
+use crate::{
+    args::ArgValues,
+    bytecode::{CallResult, VM},
+    defer_drop,
+    exception_private::{ExcType, RunError, RunResult},
+    heap::DropWithHeap,
+    resource::ResourceTracker,
+    types::PyTrait,
+    value::Value,
+};
+
+/// Implementation of the hasattr() builtin function.
+///
+/// Returns whether `getattr(object, name)` would succeed, or whether `object` has a method
+/// called `name`. Like CPython, only `AttributeError` means "no"; other errors propagate.
+///
+/// Methods of host dataclasses are resolved by the host when called, so `hasattr()` only
+/// sees their fields.
+pub fn builtin_hasattr(vm: &mut VM<'_, '_, impl ResourceTracker>, args: ArgValues) -> RunResult<Value> {
+    let (object, name) = args.get_two_args("hasattr", vm.heap)?;
+    defer_drop!(object, vm);
+    defer_drop!(name, vm);
+
+    let Some(attr) = name.as_either_str(vm.heap) else {
+        return Err(ExcType::type_error_attribute_name(name.py_type(vm.heap)));
+    };
+
+    if let Some(method) = attr.static_string()
+        && object.py_type(vm.heap).methods().contains(&method)
+    {
+        return Ok(Value::Bool(true));
+    }
+
+    match object.py_getattr(&attr, vm) {
+        Ok(result) => {
+            match result {
+                CallResult::Value(value) | CallResult::AwaitValue(value) => value.drop_with_heap(vm),
+                CallResult::External(_, args) | CallResult::OsCall(_, args) | CallResult::MethodCall(_, args) => {
+                    args.drop_with_heap(vm);
+                }
+                CallResult::FramePushed => {}
+            }
+            Ok(Value::Bool(true))
+        }
+        Err(RunError::Exc(exc)) if exc.exc.exc_type() == ExcType::AttributeError => Ok(Value::Bool(false)),
+        Err(e) => Err(e),
+    }
+}
@@ -0,0 +1,52 @@
+//! Implementation of the input() builtin function.
+
+use crate::{
+    args::ArgValues,
+    bytecode::{CallResult, VM},
+    defer_drop,
+    exception_private::{ExcType, RunResult},
+    heap::DropWithHeap,
+    os::{OsFunction, OsResultConversion},
+    resource::ResourceTracker,
+    types::{PyTrait, str::allocate_string},
+    value::Value,
+};
+
+/// Implementation of the input() builtin function.
+///
+/// `input(prompt='')` has no stdin to read, so it yields `OsFunction::Input` with `str(prompt)`
+/// and the host supplies the line, or raises `EOFError` when there is no more input.
+/// The prompt is passed to the host rather than printed, so the host decides how to show it.
+pub fn builtin_input(vm: &mut VM<'_, '_, impl ResourceTracker>, args: ArgValues) -> RunResult<CallResult> {
+    let prompt = match args.get_zero_one_arg("input", vm.heap)? {
+        Some(prompt) => {
+            defer_drop!(prompt, vm);
+            prompt.py_str(vm).into_owned()
+        }
+        None => String::new(),
+    };
+    let prompt = allocate_string(prompt, vm.heap)?;
+    vm.set_os_result_conversion(OsResultConversion::InputLine);
+    Ok(CallResult::OsCall(OsFunction::Input, ArgValues::One(prompt)))
+}
+
+/// Builds the result of `input()` from the line the host returned for `OsFunction::Input`.
+///
+/// Implements `OsResultConversion::InputLine`: the line must be a `str`, and a trailing
+/// newline is stripped so hosts can pass on a line read from a stream unchanged.
+pub(crate) fn input_line_from(value: Value, vm: &mut VM<'_, '_, impl ResourceTracker>) -> RunResult<Value> {
+    let Some(line) = value.as_either_str(vm.heap) else {
+        defer_drop!(value, vm);
+        return Err(ExcType::type_error(format!(
+            "input result must be str, not {}",
+            value.py_type(vm.heap)
+        )));
+    };
+    let line = line.as_str(vm.interns);
+    let Some(stripped) = line.strip_suffix('\n') else {
+        return Ok(value);
+    };
+    let stripped = stripped.strip_suffix('\r').unwrap_or(stripped).to_owned();
+    value.drop_with_heap(vm);
+    allocate_string(stripped, vm.heap)
+}
@@ -1,4 +1,4 @@
-//! Implementation of the isinstance() builtin function.
+//! Implementation of the isinstance() and issubclass() builtin functions.
 
 use super::Builtins;
 use crate::{
@@ -29,7 +29,28 @@ pub fn builtin_isinstance(vm: &mut VM<'_, '_, impl ResourceTracker>, args: ArgVa
     }
 }
 
-/// Recursively checks if obj_type matches classinfo for isinstance().
+/// Implementation of the issubclass() builtin function.
+///
+/// Checks if a class is a subclass of a class or a tuple of classes. Every class is a
+/// subclass of itself and of `object`.
+pub fn builtin_issubclass(vm: &mut VM<'_, '_, impl ResourceTracker>, args: ArgValues) -> RunResult<Value> {
+    let (cls, classinfo) = args.get_two_args("issubclass", vm.heap)?;
+    defer_drop!(cls, vm);
+    defer_drop!(classinfo, vm);
+
+    let cls_type = match cls {
+        Value::Builtin(Builtins::Type(t)) => *t,
+        Value::Builtin(Builtins::ExcType(exc_type)) => Type::Exception(*exc_type),
+        _ => return Err(ExcType::type_error("issubclass() arg 1 must be a class")),
+    };
+
+    match isinstance_check(cls_type, classinfo, vm.heap) {
+        Ok(result) => Ok(Value::Bool(result)),
+        Err(()) => Err(ExcType::issubclass_arg2_error()),
+    }
+}
+
+/// Recursively checks if obj_type matches classinfo, for isinstance() and issubclass().
 ///
 /// Returns `Ok(true)` if the type matches, `Ok(false)` if it doesn't,
 /// or `Err(())` if classinfo is invalid (not a type or tuple of types).
@@ -0,0 +1,11 @@
+//! Implementation of the locals() builtin function.
+
+use crate::{args::ArgValues, bytecode::VM, exception_private::RunResult, resource::ResourceTracker, value::Value};
+
+/// Implementation of the locals() builtin function.
+///
+/// Returns a dict of the current function's bound local names, see `VM::locals_dict()`.
+pub fn builtin_locals(vm: &mut VM<'_, '_, impl ResourceTracker>, args: ArgValues) -> RunResult<Value> {
+    args.check_zero_args("locals", vm.heap)?;
+    vm.locals_dict()
+}
@@ -4,41 +4,57 @@
 //! Each builtin function has its own submodule for organization.
 
 mod abs;
+mod aiter_anext; // aiter and anext share implementation
 mod all;
 mod any;
+mod ascii;
 mod bin;
+mod callable;
 mod chr;
+mod delattr;
+mod dir;
 mod divmod;
 mod enumerate;
 mod filter;
+mod format;
 mod getattr;
+mod globals;
+mod hasattr;
 mod hash;
 mod hex;
 mod id;
-mod isinstance;
+mod input;
+mod isinstance; // isinstance and issubclass share implementation
 mod len;
+mod locals;
 mod map;
 mod min_max; // min and max share implementation
 mod next;
 mod oct;
+mod open;
 mod ord;
 mod pow;
 mod print;
 mod repr;
 mod reversed;
 mod round;
+mod setattr;
 mod sorted;
 mod sum;
 mod type_;
+mod vars;
 mod zip;
 
 use std::{fmt::Write, str::FromStr};
 
 use strum::{Display, EnumString, FromRepr, IntoStaticStr};
 
+pub(crate) use abs::builtin_abs;
+pub(crate) use input::input_line_from;
+
 use crate::{
     args::ArgValues,
-    bytecode::VM,
+    bytecode::{CallResult, VM},
     exception_private::{ExcType, RunResult},
     resource::ResourceTracker,
     types::Type,
@@ -61,11 +77,11 @@ pub(crate) enum Builtins {
 
 impl Builtins {
     /// Calls this builtin with the given arguments.
-    pub fn call(self, vm: &mut VM<'_, '_, impl ResourceTracker>, args: ArgValues) -> RunResult<Value> {
+    pub fn call(self, vm: &mut VM<'_, '_, impl ResourceTracker>, args: ArgValues) -> RunResult<CallResult> {
         match self {
             Self::Function(b) => b.call(vm, args),
-            Self::ExcType(exc) => exc.call(vm, args),
-            Self::Type(t) => t.call(vm, args),
+            Self::ExcType(exc) => exc.call(vm, args).map(CallResult::Value),
+            Self::Type(t) => t.call(vm, args).map(CallResult::Value),
         }
     }
 
@@ -133,47 +149,47 @@ impl FromStr for Builtins {
 #[repr(u8)]
 pub enum BuiltinsFunctions {
     Abs,
-    // Aiter,
+    Aiter,
     All,
-    // Anext,
+    Anext,
     Any,
-    // Ascii,
+    Ascii,
     Bin,
     // bool - handled by Type enum
     // Breakpoint,
-    // bytearray - handled by Type enum
+    // bytearray - not supported, `bytes` is the only binary type
     // bytes - handled by Type enum
-    // Callable,
+    Callable,
     Chr,
     // Classmethod,
     // Compile,
     // complex - handled by Type enum
-    // Delattr,
+    Delattr,
     // dict - handled by Type enum
-    // Dir,
+    Dir,
     Divmod,
     Enumerate,
     // Eval,
     // Exec,
     Filter,
     // float - handled by Type enum
-    // Format,
+    Format,
     // frozenset - handled by Type enum
     Getattr,
-    // Globals,
-    // Hasattr,
+    Globals,
+    Hasattr,
     Hash,
     // Help,
     Hex,
     Id,
-    // Input,
+    Input,
     // int - handled by Type enum
     Isinstance,
-    // Issubclass,
+    Issubclass,
     // Iter - handled by Type enum
     Len,
     // list - handled by Type enum
-    // Locals,
+    Locals,
     Map,
     Max,
     // memoryview - handled by Type enum
@@ -181,7 +197,7 @@ pub enum BuiltinsFunctions {
     Next,
     // object - handled by Type enum
     Oct,
-    // Open,
+    Open,
     Ord,
     Pow,
     Print,
@@ -191,7 +207,7 @@ pub enum BuiltinsFunctions {
     Reversed,
     Round,
     // set - handled by Type enum
-    // Setattr,
+    Setattr,
     // Slice,
     Sorted,
     // Staticmethod,
@@ -200,7 +216,7 @@ pub enum BuiltinsFunctions {
     // Super,
     // tuple - handled by Type enum
     Type,
-    // Vars,
+    Vars,
     Zip,
     // __import__ - not planned
 }
@@ -209,38 +225,55 @@ impl BuiltinsFunctions {
     /// Executes the builtin with the provided arguments.
     ///
     /// All builtins receive the full VM context, which provides access to the heap,
-    /// interned strings, and print output.
-    pub(crate) fn call(self, vm: &mut VM<'_, '_, impl ResourceTracker>, args: ArgValues) -> RunResult<Value> {
-        match self {
+    /// interned strings, and print output. `open()` and `input()` return `CallResult::OsCall`
+    /// as the host opens the file or reads the line; every other builtin returns `CallResult::Value`.
+    pub(crate) fn call(self, vm: &mut VM<'_, '_, impl ResourceTracker>, args: ArgValues) -> RunResult<CallResult> {
+        let value = match self {
             Self::Abs => abs::builtin_abs(vm, args),
+            Self::Aiter => aiter_anext::builtin_aiter(vm, args),
             Self::All => all::builtin_all(vm, args),
+            Self::Anext => aiter_anext::builtin_anext(vm, args),
             Self::Any => any::builtin_any(vm, args),
+            Self::Ascii => ascii::builtin_ascii(vm, args),
             Self::Bin => bin::builtin_bin(vm, args),
+            Self::Callable => callable::builtin_callable(vm, args),
             Self::Chr => chr::builtin_chr(vm, args),
+            Self::Delattr => delattr::builtin_delattr(vm, args),
+            Self::Dir => dir::builtin_dir(vm, args),
             Self::Divmod => divmod::builtin_divmod(vm, args),
             Self::Enumerate => enumerate::builtin_enumerate(vm, args),
             Self::Filter => filter::builtin_filter(vm, args),
+            Self::Format => format::builtin_format(vm, args),
             Self::Getattr => getattr::builtin_getattr(vm, args),
+            Self::Globals => globals::builtin_globals(vm, args),
+            Self::Hasattr => hasattr::builtin_hasattr(vm, args),
             Self::Hash => hash::builtin_hash(vm, args),
             Self::Hex => hex::builtin_hex(vm, args),
             Self::Id => id::builtin_id(vm, args),
+            Self::Input => return input::builtin_input(vm, args),
             Self::Isinstance => isinstance::builtin_isinstance(vm, args),
+            Self::Issubclass => isinstance::builtin_issubclass(vm, args),
             Self::Len => len::builtin_len(vm, args),
+            Self::Locals => locals::builtin_locals(vm, args),
             Self::Map => map::builtin_map(vm, args),
             Self::Max => min_max::builtin_max(vm, args),
             Self::Min => min_max::builtin_min(vm, args),
             Self::Next => next::builtin_next(vm, args),
             Self::Oct => oct::builtin_oct(vm, args),
+            Self::Open => return open::builtin_open(vm, args),
             Self::Ord => ord::builtin_ord(vm, args),
             Self::Pow => pow::builtin_pow(vm, args),
             Self::Print => print::builtin_print(vm, args),
             Self::Repr => repr::builtin_repr(vm, args),
             Self::Reversed => reversed::builtin_reversed(vm, args),
             Self::Round => round::builtin_round(vm, args),
+            Self::Setattr => setattr::builtin_setattr(vm, args),
             Self::Sorted => sorted::builtin_sorted(vm, args),
             Self::Sum => sum::builtin_sum(vm, args),
             Self::Type => type_::builtin_type(vm, args),
+            Self::Vars => vars::builtin_vars(vm, args),
             Self::Zip => zip::builtin_zip(vm, args),
-        }
+        };
+        value.map(CallResult::Value)
     }
 }
@@ -0,0 +1,37 @@
+//! Implementation of the open() builtin function.
+
+use crate::{
+    args::ArgValues,
+    bytecode::{CallResult, VM},
+    defer_drop,
+    exception_private::RunResult,
+    resource::ResourceTracker,
+    types::{Path, file::open_file, path::fspath_arg},
+};
+
+/// Implementation of the open() builtin function.
+///
+/// `open(file, mode='r', buffering=-1, encoding=None, errors=None, newline=None)` where
+/// `file` is a `str` or `Path`. The host opens the file, so this yields `OsFunction::Open`.
+pub fn builtin_open(vm: &mut VM<'_, '_, impl ResourceTracker>, args: ArgValues) -> RunResult<CallResult> {
+    let params = args.bind_params(
+        "open",
+        ["file", "mode", "buffering", "encoding", "errors", "newline"],
+        1,
+        vm.heap,
+        vm.interns,
+    )?;
+    defer_drop!(params, vm);
+    let [Some(file), mode, buffering, encoding, errors, newline] = params else {
+        unreachable!("bind_params requires the file argument");
+    };
+    let name = fspath_arg(file, vm.heap, vm.interns)?;
+    let options = [
+        mode.as_ref(),
+        buffering.as_ref(),
+        encoding.as_ref(),
+        errors.as_ref(),
+        newline.as_ref(),
+    ];
+    open_file(vm, Path::new(name.clone()), name, options)
+}
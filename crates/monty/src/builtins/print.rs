@@ -5,8 +5,9 @@ use crate::{
     bytecode::VM,
     defer_drop,
     exception_private::{ExcType, RunError, RunResult, SimpleException},
-    heap::{Heap, HeapData},
+    heap::{DropWithHeap, Heap, HeapData},
     intern::Interns,
+    io::OutputStream,
     resource::ResourceTracker,
     types::PyTrait,
     value::Value,
@@ -17,16 +18,17 @@ use crate::{
 /// Supports the following keyword arguments:
 /// - `sep`: separator between values (default: " ")
 /// - `end`: string appended after the last value (default: "\n")
+/// - `file`: the stream to write to: `sys.stdout` (the default), `sys.stderr` or an `io.StringIO`
 /// - `flush`: whether to flush the stream (accepted but ignored)
-///
-/// The `file` kwarg is not supported.
 pub fn builtin_print(vm: &mut VM<'_, '_, impl ResourceTracker>, args: ArgValues) -> RunResult<Value> {
     // Split into positional args and kwargs
     let (positional, kwargs) = args.into_parts();
     defer_drop!(positional, vm);
 
     // Extract kwargs first
-    let (sep, end) = extract_print_kwargs(kwargs, vm.heap, vm.interns)?;
+    let (sep, end, file) = extract_print_kwargs(kwargs, vm.heap, vm.interns)?;
+    defer_drop!(file, vm);
+    let stream = OutputStream::from_arg(file.as_ref(), "print", "file", vm.heap)?;
 
     // Print positional args with separator, dropping each value after use
     let mut first = true;
@@ -34,34 +36,35 @@ pub fn builtin_print(vm: &mut VM<'_, '_, impl ResourceTracker>, args: ArgValues)
         if first {
             first = false;
         } else if let Some(sep) = &sep {
-            vm.print_writer.stdout_write(sep.as_str().into())?;
+            stream.write(sep.as_str().into(), vm)?;
         } else {
-            vm.print_writer.stdout_push(' ')?;
+            stream.push(' ', vm)?;
         }
-        vm.print_writer.stdout_write(value.py_str(vm))?;
+        stream.write(value.py_str(vm), vm)?;
     }
 
     // Append end string
     if let Some(end) = end {
-        vm.print_writer.stdout_write(end.into())?;
+        stream.write(end.into(), vm)?;
     } else {
-        vm.print_writer.stdout_push('\n')?;
+        stream.push('\n', vm)?;
     }
 
     Ok(Value::None)
 }
 
-/// Extracts sep and end kwargs from print() arguments.
+/// Extracts sep, end and file kwargs from print() arguments.
 ///
-/// Consumes the kwargs, dropping all values after extraction.
-/// Returns (sep, end, error) where error is Some if a kwarg error occurred.
+/// Consumes the kwargs, dropping all values after extraction except `file`,
+/// which is returned for the caller to drop once printing is done.
 fn extract_print_kwargs(
     kwargs: KwargsValues,
     heap: &mut Heap<impl ResourceTracker>,
     interns: &Interns,
-) -> RunResult<(Option<String>, Option<String>)> {
+) -> RunResult<(Option<String>, Option<String>, Option<Value>)> {
     let mut sep: Option<String> = None;
     let mut end: Option<String> = None;
+    let mut file: Option<Value> = None;
     let mut error: Option<RunError> = None;
 
     for (key, value) in kwargs {
@@ -92,9 +95,8 @@ fn extract_print_kwargs(
             },
             "flush" => {} // Accepted but ignored (we don't buffer output)
             "file" => {
-                error = Some(
-                    SimpleException::new_msg(ExcType::TypeError, "print() 'file' argument is not supported").into(),
-                );
+                let old = file.replace(value.clone_with_heap(heap));
+                old.drop_with_heap(heap);
             }
             _ => {
                 error = Some(ExcType::type_error_unexpected_keyword("print", key_str));
@@ -103,9 +105,10 @@ fn extract_print_kwargs(
     }
 
     if let Some(error) = error {
+        file.drop_with_heap(heap);
         Err(error)
     } else {
-        Ok((sep, end))
+        Ok((sep, end, file))
     }
 }
 
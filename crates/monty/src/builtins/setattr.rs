@@ -0,0 +1,38 @@
+//! Implementation of the setattr() builtin function.
+
+use crate::{
+    args::ArgValues,
+    bytecode::VM,
+    defer_drop,
+    exception_private::{ExcType, RunResult},
+    resource::ResourceTracker,
+    types::PyTrait,
+    value::Value,
+};
+
+/// Implementation of the setattr() builtin function.
+///
+/// Assigns `value` to the named attribute of `object`, as `object.name = value` does,
+/// so only dataclass instances support it. Unlike the statement form, the name may be
+/// built at runtime.
+///
+/// Examples:
+/// ```python
+/// setattr(point, 'x', 10)       # Same as point.x = 10
+/// ```
+pub fn builtin_setattr(vm: &mut VM<'_, '_, impl ResourceTracker>, args: ArgValues) -> RunResult<Value> {
+    let positional = args.into_pos_only("setattr", vm.heap)?;
+    defer_drop!(positional, vm);
+
+    let [object, name, value] = positional.as_slice() else {
+        return Err(ExcType::type_error_arg_count("setattr", 3, positional.len()));
+    };
+
+    let Some(attr) = name.as_either_str(vm.heap) else {
+        return Err(ExcType::type_error_attribute_name(name.py_type(vm.heap)));
+    };
+
+    let value = value.clone_with_heap(vm);
+    object.py_set_attr(&attr, value, vm)?;
+    Ok(Value::None)
+}
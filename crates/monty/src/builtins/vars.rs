@@ -0,0 +1,44 @@
+//! Implementation of the vars() builtin function.
+
+use crate::{
+    args::ArgValues,
+    bytecode::VM,
+    defer_drop,
+    exception_private::{ExcType, RunResult},
+    heap::HeapData,
+    resource::ResourceTracker,
+    types::Dict,
+    value::Value,
+};
+
+/// Implementation of the vars() builtin function.
+///
+/// Without an argument, acts like `locals()`. With an argument, returns a dict of the
+/// attributes of a dataclass instance or module; other objects have no `__dict__`.
+///
+/// The dict is a copy, so changing it doesn't change the object.
+pub fn builtin_vars(vm: &mut VM<'_, '_, impl ResourceTracker>, args: ArgValues) -> RunResult<Value> {
+    let Some(object) = args.get_zero_one_arg("vars", vm.heap)? else {
+        return vm.locals_dict();
+    };
+    defer_drop!(object, vm);
+
+    let attrs = match object {
+        Value::Ref(heap_id) => match vm.heap.get(*heap_id) {
+            HeapData::Dataclass(dc) => Some(dc.attrs()),
+            HeapData::Module(module) => Some(module.attrs()),
+            _ => None,
+        },
+        _ => None,
+    };
+    let Some(attrs) = attrs else {
+        return Err(ExcType::type_error("vars() argument must have __dict__ attribute"));
+    };
+    let pairs: Vec<(Value, Value)> = attrs
+        .iter()
+        .map(|(key, value)| (key.clone_with_heap(vm.heap), value.clone_with_heap(vm.heap)))
+        .collect();
+
+    let dict = Dict::from_pairs(pairs, vm)?;
+    Ok(Value::Ref(vm.heap.allocate(HeapData::Dict(dict))?))
+}
@@ -65,6 +65,12 @@ pub struct CodeBuilder {
     /// Used to determine whether to raise `UnboundLocalError` or `NameError`
     /// when loading an undefined local variable.
     assigned_locals: HashSet<u16>,
+
+    /// The variable each namespace slot holds, for `globals()` and `locals()`.
+    slot_names: Vec<Option<StringId>>,
+
+    /// Module dunders that aren't stored as globals, with their values, for `globals()`.
+    module_dunders: Vec<(StringId, Value)>,
 }
 
 impl CodeBuilder {
@@ -374,6 +380,19 @@ impl CodeBuilder {
         self.assigned_locals.insert(slot);
     }
 
+    /// Sets the variable each namespace slot holds, as worked out by the prepare phase.
+    ///
+    /// Unlike `local_names`, which a function also fills with the names of the globals
+    /// it reads, this only names the code's own namespace.
+    pub fn set_slot_names(&mut self, slot_names: Vec<Option<StringId>>) {
+        self.slot_names = slot_names;
+    }
+
+    /// Sets the module dunders that the prepare phase resolved to literals, for `globals()`.
+    pub fn set_module_dunders(&mut self, module_dunders: Vec<(StringId, Value)>) {
+        self.module_dunders = module_dunders;
+    }
+
     /// Emits a `LoadLocal` instruction, using specialized variants for common slots.
     pub fn emit_load_local(&mut self, slot: u16) {
         match slot {
@@ -485,6 +504,8 @@ impl CodeBuilder {
             self.max_stack_depth,
             local_names,
             self.assigned_locals,
+            self.slot_names,
+            self.module_dunders,
         )
     }
 
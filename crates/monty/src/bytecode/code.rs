@@ -62,6 +62,18 @@ pub struct Code {
     /// Used to determine whether to raise `UnboundLocalError` (slot is assigned somewhere
     /// but accessed before assignment) or `NameError` (name doesn't exist in any scope).
     assigned_locals: HashSet<u16>,
+
+    /// The variable each namespace slot holds, for `globals()` and `locals()`.
+    ///
+    /// `None` for slots that don't hold a single named variable, like comprehension
+    /// variables, which get a slot of their own.
+    slot_names: Vec<Option<StringId>>,
+
+    /// Module dunders (`__name__` and friends) that aren't stored as globals, with their values.
+    ///
+    /// The prepare phase resolves reads of a dunder the code never assigns to its value, so
+    /// these never get a namespace slot; `globals()` adds them from here. Empty for functions.
+    module_dunders: Vec<(StringId, Value)>,
 }
 
 impl Code {
@@ -79,6 +91,8 @@ impl Code {
         stack_size: u16,
         local_names: Vec<StringId>,
         assigned_locals: HashSet<u16>,
+        slot_names: Vec<Option<StringId>>,
+        module_dunders: Vec<(StringId, Value)>,
     ) -> Self {
         Self {
             bytecode,
@@ -89,6 +103,8 @@ impl Code {
             stack_size,
             local_names,
             assigned_locals,
+            slot_names,
+            module_dunders,
         }
     }
 
@@ -121,6 +137,18 @@ impl Code {
         self.assigned_locals.contains(&slot)
     }
 
+    /// Returns the names of the variables held in the namespace slots, indexed by slot.
+    #[must_use]
+    pub fn slot_names(&self) -> &[Option<StringId>] {
+        &self.slot_names
+    }
+
+    /// Returns the module dunders that aren't stored as globals, with their values.
+    #[must_use]
+    pub fn module_dunders(&self) -> &[(StringId, Value)] {
+        &self.module_dunders
+    }
+
     /// Finds the location entry for a given bytecode offset.
     ///
     /// Location entries are recorded at instruction boundaries. This method finds
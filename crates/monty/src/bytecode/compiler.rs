@@ -168,8 +168,10 @@ impl<'a> Compiler<'a> {
         nodes: &[PreparedNode],
         interns: &Interns,
         num_locals: u16,
+        global_names: &[Option<StringId>],
+        module_dunders: &[(StringId, Literal)],
     ) -> Result<CompileResult, CompileError> {
-        Self::compile_module_with_functions(nodes, interns, num_locals, Vec::new())
+        Self::compile_module_with_functions(nodes, interns, num_locals, global_names, module_dunders, Vec::new())
     }
 
     /// Compiles module-level code while preserving an existing function table prefix.
@@ -181,11 +183,20 @@ impl<'a> Compiler<'a> {
         nodes: &[PreparedNode],
         interns: &Interns,
         num_locals: u16,
+        global_names: &[Option<StringId>],
+        module_dunders: &[(StringId, Literal)],
         existing_functions: Vec<Function>,
     ) -> Result<CompileResult, CompileError> {
         let mut compiler = Compiler::new(interns, Vec::new());
         compiler.functions = existing_functions;
         compiler.is_module_scope = true;
+        compiler.code.set_slot_names(global_names.to_vec());
+        compiler.code.set_module_dunders(
+            module_dunders
+                .iter()
+                .map(|&(name, literal)| (name, Value::from(literal)))
+                .collect(),
+        );
         compiler.compile_block(nodes)?;
 
         // Module returns None if no explicit return
@@ -208,11 +219,13 @@ impl<'a> Compiler<'a> {
     /// any nested functions found in the body will be added to it.
     fn compile_function_body(
         body: &[PreparedNode],
+        local_names: &[Option<StringId>],
         interns: &Interns,
         functions: Vec<Function>,
         num_locals: u16,
     ) -> Result<(Code, Vec<Function>), CompileError> {
         let mut compiler = Compiler::new(interns, functions);
+        compiler.code.set_slot_names(local_names.to_vec());
         compiler.compile_block(body)?;
 
         // Implicit return None if no explicit return
@@ -370,7 +383,11 @@ impl<'a> Compiler<'a> {
             }
             Node::FunctionDef(func_def) => self.compile_function_def(func_def)?,
             Node::Try(try_block) => self.compile_try(try_block)?,
-            Node::Import { module_name, binding } => self.compile_import(*module_name, binding),
+            Node::Import {
+                module_name,
+                package,
+                binding,
+            } => self.compile_import(*module_name, *package, binding),
             Node::ImportFrom {
                 module_name,
                 names,
@@ -412,8 +429,13 @@ impl<'a> Compiler<'a> {
         // Take ownership of functions for the recursive compile, then restore
         let functions = std::mem::take(&mut self.functions);
         let namespace_size = u16::try_from(func_def.namespace_size).expect("function namespace size exceeds u16");
-        let (body_code, mut functions) =
-            Self::compile_function_body(&func_def.body, self.interns, functions, namespace_size)?;
+        let (body_code, mut functions) = Self::compile_function_body(
+            &func_def.body,
+            &func_def.local_names,
+            self.interns,
+            functions,
+            namespace_size,
+        )?;
 
         // 2. Create the compiled Function and add to the vector
         let func_id = functions.len();
@@ -491,8 +513,13 @@ impl<'a> Compiler<'a> {
         // 1. Compile the function body recursively
         let functions = std::mem::take(&mut self.functions);
         let namespace_size = u16::try_from(func_def.namespace_size).expect("function namespace size exceeds u16");
-        let (body_code, mut functions) =
-            Self::compile_function_body(&func_def.body, self.interns, functions, namespace_size)?;
+        let (body_code, mut functions) = Self::compile_function_body(
+            &func_def.body,
+            &func_def.local_names,
+            self.interns,
+            functions,
+            namespace_size,
+        )?;
 
         // 2. Create the compiled Function and add to the vector
         let func_id = functions.len();
@@ -546,14 +573,17 @@ impl<'a> Compiler<'a> {
     /// Compiles an import statement.
     ///
     /// Emits `LoadModule` to create the module, then stores it to the binding name.
+    /// For `import a.b` the submodule must exist, but the top-level `package` is what's bound.
     /// If the module is unknown, emits `RaiseImportError` to defer the error to runtime.
     /// This allows imports inside `if TYPE_CHECKING:` blocks to compile successfully.
-    fn compile_import(&mut self, module_name: StringId, binding: &Identifier) {
+    fn compile_import(&mut self, module_name: StringId, package: Option<StringId>, binding: &Identifier) {
         let position = binding.position;
         self.code.set_location(position, None);
 
-        // Look up the module by name
-        if let Some(builtin_module) = BuiltinModule::from_string_id(module_name) {
+        // Look up the module by name, then the package that is bound instead of it
+        let builtin_module = BuiltinModule::from_string_id(module_name)
+            .and_then(|module| package.map_or(Some(module), BuiltinModule::from_string_id));
+        if let Some(builtin_module) = builtin_module {
             // Known module - emit LoadModule
             self.code.emit_u8(Opcode::LoadModule, builtin_module as u8);
             // Store to the binding (respects Local/Global/Cell scope)
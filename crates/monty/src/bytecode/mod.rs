@@ -20,5 +20,6 @@ mod vm;
 
 pub use code::Code;
 pub use compiler::Compiler;
+pub(crate) use op::Opcode;
 pub(crate) use vm::CallResult;
 pub use vm::{FrameExit, VM, VMSnapshot};
@@ -1,16 +1,52 @@
 //! Binary and in-place operation helpers for the VM.
 
+use std::cmp::Ordering;
+
 use super::VM;
 use crate::{
+    bytecode::op::Opcode,
     defer_drop,
-    exception_private::{ExcType, RunError},
+    exception_private::{ExcType, RunError, RunResult},
     heap::{Heap, HeapData, HeapGuard},
     resource::ResourceTracker,
     types::{PyTrait, Set, dict_view::collect_iterable_to_set, set::SetBinaryOp},
-    value::BitwiseOp,
+    value::{BitwiseOp, Value},
 };
 
 impl<T: ResourceTracker> VM<'_, '_, T> {
+    /// Applies a binary or comparison opcode to `lhs` and `rhs` outside the run loop.
+    ///
+    /// Used by the `operator` module so `operator.add(a, b)` and friends behave exactly
+    /// like the corresponding expressions, including their error messages. For
+    /// `CompareIn`, `lhs` is the item and `rhs` the container, as in `lhs in rhs`.
+    pub(crate) fn apply_binary_op(&mut self, op: Opcode, lhs: Value, rhs: Value) -> RunResult<Value> {
+        self.push(lhs);
+        self.push(rhs);
+        match op {
+            Opcode::BinaryAdd => self.binary_add(),
+            Opcode::BinarySub => self.binary_sub(),
+            Opcode::BinaryMul => self.binary_mult(),
+            Opcode::BinaryDiv => self.binary_div(),
+            Opcode::BinaryFloorDiv => self.binary_floordiv(),
+            Opcode::BinaryMod => self.binary_mod(),
+            Opcode::BinaryPow => self.binary_pow(),
+            Opcode::BinaryAnd => self.binary_and(),
+            Opcode::BinaryOr => self.binary_or(),
+            Opcode::BinaryXor => self.binary_xor(),
+            Opcode::BinaryLShift => self.binary_bitwise(BitwiseOp::LShift),
+            Opcode::BinaryRShift => self.binary_bitwise(BitwiseOp::RShift),
+            Opcode::CompareEq => self.compare_eq(),
+            Opcode::CompareNe => self.compare_ne(),
+            Opcode::CompareLt => self.compare_ord(Ordering::is_lt),
+            Opcode::CompareLe => self.compare_ord(Ordering::is_le),
+            Opcode::CompareGt => self.compare_ord(Ordering::is_gt),
+            Opcode::CompareGe => self.compare_ord(Ordering::is_ge),
+            Opcode::CompareIn => self.compare_in(false),
+            _ => unreachable!("apply_binary_op called with non-binary opcode {op:?}"),
+        }?;
+        Ok(self.pop())
+    }
+
     /// Binary addition with proper refcount handling.
     ///
     /// Uses lazy type capture: only calls `py_type()` in error paths to avoid
@@ -14,11 +14,14 @@ use crate::{
     exception_private::{ExcType, RunError},
     heap::{DropWithHeap, Heap, HeapData, HeapGuard, HeapId},
     heap_data::CellValue,
-    intern::{FunctionId, StringId},
-    os::OsFunction,
+    intern::{FunctionId, StaticStrings, StringId},
+    modules::sys::call_stream_method,
+    os::{OsFunction, OsResultConversion},
     resource::ResourceTracker,
-    types::{Dict, PyTrait, Type, bytes::call_bytes_method, str::call_str_method, r#type::call_type_method},
-    value::{EitherStr, Value},
+    types::{
+        Dict, OperatorCallable, PyTrait, Type, bytes::call_bytes_method, str::call_str_method, r#type::call_type_method,
+    },
+    value::{EitherStr, Marker, Value},
 };
 
 /// Result of executing a call or attribute method.
@@ -69,22 +72,29 @@ impl<T: ResourceTracker> VM<'_, '_, T> {
     /// Pops the callable and arguments from the stack, calls the function,
     /// and returns the result.
     pub(super) fn exec_call_function(&mut self, arg_count: usize) -> Result<CallResult, RunError> {
-        let args = self.pop_n_args(arg_count);
-        let callable = self.pop();
-        let this = self;
-        defer_drop!(callable, this);
-        this.call_function(callable, args)
+        self.call_reading_files(arg_count + 1, |this| {
+            let args = this.pop_n_args(arg_count);
+            let callable = this.pop();
+            defer_drop!(callable, this);
+            this.call_function(callable, args)
+        })
     }
 
     /// Executes `CallBuiltinFunction` opcode.
     ///
     /// Calls a builtin function directly without stack manipulation for the callable.
     /// This is an optimization that avoids constant pool lookup and stack manipulation.
-    pub(super) fn exec_call_builtin_function(&mut self, builtin_id: u8, arg_count: usize) -> Result<Value, RunError> {
+    pub(super) fn exec_call_builtin_function(
+        &mut self,
+        builtin_id: u8,
+        arg_count: usize,
+    ) -> Result<CallResult, RunError> {
         // Convert u8 to BuiltinsFunctions via FromRepr
         if let Some(builtin) = BuiltinsFunctions::from_repr(builtin_id) {
-            let args = self.pop_n_args(arg_count);
-            builtin.call(self, args)
+            self.call_reading_files(arg_count, |this| {
+                let args = this.pop_n_args(arg_count);
+                builtin.call(this, args)
+            })
         } else {
             Err(RunError::internal("CallBuiltinFunction: invalid builtin_id"))
         }
@@ -94,11 +104,13 @@ impl<T: ResourceTracker> VM<'_, '_, T> {
     ///
     /// Calls a builtin type constructor directly without stack manipulation for the callable.
     /// This is an optimization for type constructors like `list()`, `int()`, `str()`.
-    pub(super) fn exec_call_builtin_type(&mut self, type_id: u8, arg_count: usize) -> Result<Value, RunError> {
+    pub(super) fn exec_call_builtin_type(&mut self, type_id: u8, arg_count: usize) -> Result<CallResult, RunError> {
         // Convert u8 to Type via callable_from_u8
         if let Some(t) = Type::callable_from_u8(type_id) {
-            let args = self.pop_n_args(arg_count);
-            t.call(self, args)
+            self.call_reading_files(arg_count, |this| {
+                let args = this.pop_n_args(arg_count);
+                t.call(this, args).map(CallResult::Value)
+            })
         } else {
             Err(RunError::internal("CallBuiltinType: invalid type_id"))
         }
@@ -113,6 +125,13 @@ impl<T: ResourceTracker> VM<'_, '_, T> {
         pos_count: usize,
         kwname_ids: Vec<StringId>,
     ) -> Result<CallResult, RunError> {
+        self.call_reading_files(pos_count + kwname_ids.len() + 1, |this| {
+            this.call_function_kw(pos_count, kwname_ids)
+        })
+    }
+
+    /// Pops the operands of `CallFunctionKw` and calls the function.
+    fn call_function_kw(&mut self, pos_count: usize, kwname_ids: Vec<StringId>) -> Result<CallResult, RunError> {
         let kw_count = kwname_ids.len();
 
         // Pop keyword values (TOS is last kwarg value)
@@ -149,9 +168,11 @@ impl<T: ResourceTracker> VM<'_, '_, T> {
     /// Pops the object and arguments from the stack, calls the attribute,
     /// and returns a `CallResult` which may indicate an OS or external call.
     pub(super) fn exec_call_attr(&mut self, name_id: StringId, arg_count: usize) -> Result<CallResult, RunError> {
-        let args = self.pop_n_args(arg_count);
-        let obj = self.pop();
-        self.call_attr(obj, name_id, args)
+        self.call_reading_files(arg_count + 1, |this| {
+            let args = this.pop_n_args(arg_count);
+            let obj = this.pop();
+            this.call_attr(obj, name_id, args)
+        })
     }
 
     /// Executes `CallAttrKw` opcode.
@@ -164,6 +185,18 @@ impl<T: ResourceTracker> VM<'_, '_, T> {
         name_id: StringId,
         pos_count: usize,
         kwname_ids: Vec<StringId>,
+    ) -> Result<CallResult, RunError> {
+        self.call_reading_files(pos_count + kwname_ids.len() + 1, |this| {
+            this.call_attr_kw(name_id, pos_count, kwname_ids)
+        })
+    }
+
+    /// Pops the operands of `CallAttrKw` and calls the attribute.
+    fn call_attr_kw(
+        &mut self,
+        name_id: StringId,
+        pos_count: usize,
+        kwname_ids: Vec<StringId>,
     ) -> Result<CallResult, RunError> {
         let kw_count = kwname_ids.len();
 
@@ -236,6 +269,62 @@ impl<T: ResourceTracker> VM<'_, '_, T> {
     // Internal Call Helpers
     // ========================================================================
 
+    /// Runs `call`, which consumes the top `operands` values of the stack, letting builtins
+    /// iterate files among those values.
+    ///
+    /// Builtins iterate their arguments without yielding, but a file's lines come from the
+    /// host. When `call` fails because it needed to iterate a file (see `MontyIter::new`),
+    /// the operands are put back on the stack and the rest of the file is read with an
+    /// `OsCall`. The call then runs again with an iterator over the lines in place of the
+    /// file, see `OsResultConversion::CallWithFileLines`.
+    fn call_reading_files(
+        &mut self,
+        operands: usize,
+        call: impl FnOnce(&mut Self) -> Result<CallResult, RunError>,
+    ) -> Result<CallResult, RunError> {
+        let start = self.stack.len() - operands;
+        let heap = &*self.heap;
+        let is_file = |value: &Value| matches!(value, Value::Ref(id) if matches!(heap.get(*id), HeapData::File(_)));
+        if !self.stack[start..].iter().any(is_file) {
+            return call(self);
+        }
+
+        let call_ip = self.instruction_ip;
+        let saved: Vec<Value> = self.stack[start..]
+            .iter()
+            .map(|value| value.clone_with_heap(self.heap))
+            .collect();
+        self.file_to_read = None;
+        let result = call(self);
+        let file = self.file_to_read.take().and_then(|id| {
+            saved
+                .iter()
+                .position(|value| matches!(value, Value::Ref(v) if *v == id))
+        });
+        match (result, file) {
+            (Err(_), Some(operand)) => {
+                let Value::Ref(file_id) = saved[operand] else {
+                    unreachable!("position() found a file reference")
+                };
+                let HeapData::File(file) = self.heap.get(file_id) else {
+                    unreachable!("MontyIter::new only reads files")
+                };
+                let args = file.read_rest_args();
+                self.stack.extend(saved);
+                self.set_os_result_conversion(OsResultConversion::CallWithFileLines {
+                    operands,
+                    operand,
+                    call_ip,
+                });
+                Ok(CallResult::OsCall(OsFunction::FileRead, args))
+            }
+            (result, _) => {
+                saved.drop_with_heap(self);
+                result
+            }
+        }
+    }
+
     /// Pops n arguments from the stack and wraps them in `ArgValues`.
     fn pop_n_args(&mut self, n: usize) -> ArgValues {
         match n {
@@ -283,7 +372,11 @@ impl<T: ResourceTracker> VM<'_, '_, T> {
             }
             Value::Builtin(Builtins::Type(t)) => {
                 // Handle classmethods on type objects like dict.fromkeys()
-                call_type_method(t, name_id, args, this).map(CallResult::Value)
+                call_type_method(t, name_id, args, this)
+            }
+            Value::Marker(marker @ Marker(StaticStrings::Stdout | StaticStrings::Stderr)) => {
+                // sys.stdout.write() and sys.stderr.write()
+                call_stream_method(marker, name_id, args, this).map(CallResult::Value)
             }
             _ => {
                 // Non-heap values without method support
@@ -294,6 +387,41 @@ impl<T: ResourceTracker> VM<'_, '_, T> {
         }
     }
 
+    /// Calls method `attr` on `obj` as `obj.attr(*args, **kwargs)` would.
+    ///
+    /// `kwargs` is a dict of keyword arguments, or `None` when there are none. Used by
+    /// `operator.methodcaller`, whose method name may be a heap string.
+    pub(crate) fn call_method(
+        &mut self,
+        obj: Value,
+        attr: &EitherStr,
+        args: Vec<Value>,
+        kwargs: Option<Value>,
+    ) -> Result<CallResult, RunError> {
+        let args = match kwargs {
+            Some(kwargs) => match self.build_args_with_kwargs(args, kwargs) {
+                Ok(args) => args,
+                Err(err) => {
+                    obj.drop_with_heap(self);
+                    return Err(err);
+                }
+            },
+            None => Self::build_args_positional_only(args),
+        };
+        if let EitherStr::Interned(name_id) = attr {
+            return self.call_attr(obj, *name_id, args);
+        }
+        let this = self;
+        if let Value::Ref(heap_id) = obj {
+            defer_drop!(obj, this);
+            return Heap::call_attr(this, heap_id, attr, args);
+        }
+        let type_name = obj.py_type(this.heap);
+        obj.drop_with_heap(this);
+        args.drop_with_heap(this);
+        Err(ExcType::attribute_error(type_name, attr.as_str(this.interns)))
+    }
+
     /// Evaluates a function in a position that doesn't yet support suspending.
     ///
     /// Calls the function and, if it's a user-defined function that pushes a frame,
@@ -309,29 +437,7 @@ impl<T: ResourceTracker> VM<'_, '_, T> {
     ) -> Result<Value, RunError> {
         match self.call_function(callable, args)? {
             CallResult::Value(v) => Ok(v),
-            CallResult::FramePushed => {
-                // A new frame was pushed for a defined function call - we need to run it
-                // to completion.
-                let stack_depth = self.frames.len();
-                // Mark the frame as an exit point from the `run()` loop
-                self.current_frame_mut().should_return = true;
-                match self.run()? {
-                    FrameExit::Return(v) => Ok(v),
-                    FrameExit::ResolveFutures(_)
-                    | FrameExit::ExternalCall { .. }
-                    | FrameExit::OsCall { .. }
-                    | FrameExit::MethodCall { .. }
-                    | FrameExit::NameLookup { .. } => {
-                        // Pop frames off the stack from this failed evaluation
-                        while self.frames.len() > stack_depth {
-                            self.pop_frame();
-                        }
-                        Err(RunError::internal(format!(
-                            "{ctx}: external functions are not yet supported in this context"
-                        )))
-                    }
-                }
-            }
+            CallResult::FramePushed => self.run_pushed_frame(ctx),
             CallResult::External(_, _)
             | CallResult::OsCall(_, _)
             | CallResult::MethodCall(_, _)
@@ -344,6 +450,32 @@ impl<T: ResourceTracker> VM<'_, '_, T> {
         }
     }
 
+    /// Runs the frame `call_function` just pushed for a defined function until it returns.
+    ///
+    /// Returns an error if the function tries to suspend, e.g. to call an external function,
+    /// since the caller can't resume it.
+    pub(crate) fn run_pushed_frame(&mut self, ctx: &'static str) -> Result<Value, RunError> {
+        let stack_depth = self.frames.len();
+        // Mark the frame as an exit point from the `run()` loop
+        self.current_frame_mut().should_return = true;
+        match self.run()? {
+            FrameExit::Return(v) => Ok(v),
+            FrameExit::ResolveFutures(_)
+            | FrameExit::ExternalCall { .. }
+            | FrameExit::OsCall { .. }
+            | FrameExit::MethodCall { .. }
+            | FrameExit::NameLookup { .. } => {
+                // Pop frames off the stack from this failed evaluation
+                while self.frames.len() > stack_depth {
+                    self.pop_frame();
+                }
+                Err(RunError::internal(format!(
+                    "{ctx}: external functions are not yet supported in this context"
+                )))
+            }
+        }
+    }
+
     /// Calls a callable value with the given arguments.
     ///
     /// Dispatches based on the callable type:
@@ -354,10 +486,7 @@ impl<T: ResourceTracker> VM<'_, '_, T> {
     /// - `Value::Ref`: checks for closure/function on heap
     pub(crate) fn call_function(&mut self, callable: &Value, args: ArgValues) -> Result<CallResult, RunError> {
         match callable {
-            Value::Builtin(builtin) => {
-                let result = builtin.call(self, args)?;
-                Ok(CallResult::Value(result))
-            }
+            Value::Builtin(builtin) => builtin.call(self, args),
             Value::ModuleFunction(mf) => mf.call(self, args),
             Value::ExtFunction(name_id) => {
                 // External function - return to caller to execute
@@ -396,6 +525,7 @@ impl<T: ResourceTracker> VM<'_, '_, T> {
                 let name = name.clone();
                 return Ok(CallResult::External(EitherStr::Heap(name), args));
             }
+            HeapData::OperatorCallable(_) => return OperatorCallable::call(self, heap_id, args),
             _ => {
                 args.drop_with_heap(self);
                 return Err(ExcType::type_error("object is not callable"));
@@ -12,6 +12,8 @@ mod compare;
 mod exceptions;
 mod format;
 mod scheduler;
+mod scope;
+mod unary;
 
 use std::cmp::Ordering;
 
@@ -29,10 +31,13 @@ use crate::{
     intern::{FunctionId, Interns, StringId},
     io::PrintWriter,
     modules::BuiltinModule,
-    os::OsFunction,
+    os::{OsFunction, OsResultConversion},
     parse::CodeRange,
     resource::ResourceTracker,
-    types::{LongInt, MontyIter, PyTrait, iter::advance_on_heap},
+    types::{
+        File, LongInt, MontyIter, PyTrait,
+        iter::{advance_on_heap, sentinel_item},
+    },
     value::{BitwiseOp, EitherStr, Value},
 };
 
@@ -509,6 +514,15 @@ pub struct VMSnapshot {
     /// This enables async execution to be paused and resumed across host calls.
     /// None if no async operations have been performed yet.
     scheduler: Option<Scheduler>,
+
+    /// Conversion to apply to the result of the `OsCall` the VM paused on, if any.
+    os_result_conversion: Option<OsResultConversion>,
+
+    /// Whether the files still open when the module returns are closed.
+    close_files_at_exit: bool,
+
+    /// The command line arguments exposed as `sys.argv`.
+    argv: Vec<String>,
 }
 
 // ============================================================================
@@ -551,6 +565,9 @@ pub struct VM<'a, 'p, T: ResourceTracker> {
     /// Print output writer, borrowed so callers retain access to collected output.
     pub(crate) print_writer: PrintWriter<'p>,
 
+    /// The command line arguments exposed as `sys.argv`, given by the host.
+    pub(crate) argv: Vec<String>,
+
     /// Stack of exceptions being handled for nested except blocks.
     ///
     /// Used by bare `raise` to re-raise the current exception.
@@ -590,6 +607,24 @@ pub struct VM<'a, 'p, T: ResourceTracker> {
     /// back to a `NameError`, so the traceback points to the name reference rather than
     /// the call expression.
     ext_function_load_ip: Option<usize>,
+
+    /// Conversion to apply to the host's result for the pending `OsCall`.
+    ///
+    /// Set by functions that yield an `OsCall` for raw data and build their return value
+    /// from it (see `OsResultConversion`); taken when execution resumes.
+    os_result_conversion: Option<OsResultConversion>,
+
+    /// The file a builtin failed to iterate because its lines have to come from the host.
+    ///
+    /// Set by `MontyIter::new` and taken by `call_reading_files`, which reads the file and
+    /// runs the call again.
+    pub(crate) file_to_read: Option<HeapId>,
+
+    /// Whether the files still open when the module returns are closed, as at interpreter exit.
+    ///
+    /// Set for `MontyRun`. A REPL leaves it unset to keep the files its globals hold open for
+    /// later snippets; files dropped without being closed are closed either way.
+    pub(crate) close_files_at_exit: bool,
 }
 
 impl<'a, 'p, T: ResourceTracker> VM<'a, 'p, T> {
@@ -598,6 +633,7 @@ impl<'a, 'p, T: ResourceTracker> VM<'a, 'p, T> {
         globals: Vec<Value>,
         heap: &'a mut Heap<T>,
         interns: &'a Interns,
+        argv: Vec<String>,
         print_writer: PrintWriter<'p>,
     ) -> Self {
         Self {
@@ -607,12 +643,16 @@ impl<'a, 'p, T: ResourceTracker> VM<'a, 'p, T> {
             heap,
             interns,
             print_writer,
+            argv,
             exception_stack: Vec::new(),
             instruction_ip: 0,
             next_call_id: 0,
             scheduler: None,            // Lazy - no allocation for sync code
             ext_function_load_ip: None, // Set by LoadGlobalCallable/LoadLocalCallable
             module_code: None,
+            os_result_conversion: None,
+            file_to_read: None,
+            close_files_at_exit: false,
         }
     }
 
@@ -669,12 +709,16 @@ impl<'a, 'p, T: ResourceTracker> VM<'a, 'p, T> {
             heap,
             interns,
             print_writer,
+            argv: snapshot.argv,
             exception_stack: snapshot.exception_stack,
             instruction_ip: snapshot.instruction_ip,
             next_call_id: snapshot.next_call_id,
             scheduler: snapshot.scheduler,
             module_code: Some(module_code),
             ext_function_load_ip: None,
+            os_result_conversion: snapshot.os_result_conversion,
+            file_to_read: None,
+            close_files_at_exit: snapshot.close_files_at_exit,
         }
     }
     /// Consumes the VM and creates a snapshot for pause/resume.
@@ -696,6 +740,9 @@ impl<'a, 'p, T: ResourceTracker> VM<'a, 'p, T> {
             instruction_ip: self.instruction_ip,
             next_call_id: self.next_call_id,
             scheduler: self.scheduler,
+            os_result_conversion: self.os_result_conversion,
+            close_files_at_exit: self.close_files_at_exit,
+            argv: self.argv,
         }
     }
 
@@ -957,103 +1004,10 @@ impl<'a, 'p, T: ResourceTracker> VM<'a, 'p, T> {
                     try_catch_sync!(self, cached_frame, self.compare_mod_eq(k));
                 }
                 // Unary Operations
-                Opcode::UnaryNot => {
-                    let value = self.pop();
-                    let result = !value.py_bool(self);
-                    value.drop_with_heap(self);
-                    self.push(Value::Bool(result));
-                }
-                Opcode::UnaryNeg => {
-                    // Unary minus - negate numeric value
-                    let value = self.pop();
-                    match value {
-                        Value::Int(n) => {
-                            // Use checked_neg to handle i64::MIN overflow
-                            if let Some(negated) = n.checked_neg() {
-                                self.push(Value::Int(negated));
-                            } else {
-                                // i64::MIN negated overflows to LongInt
-                                let li = -LongInt::from(n);
-                                match li.into_value(self.heap) {
-                                    Ok(v) => self.push(v),
-                                    Err(e) => catch_sync!(self, cached_frame, RunError::from(e)),
-                                }
-                            }
-                        }
-                        Value::Float(f) => self.push(Value::Float(-f)),
-                        Value::Bool(b) => self.push(Value::Int(if b { -1 } else { 0 })),
-                        Value::Ref(id) => {
-                            if let HeapData::LongInt(li) = self.heap.get(id) {
-                                let negated = -LongInt::new(li.inner().clone());
-                                value.drop_with_heap(self);
-                                match negated.into_value(self.heap) {
-                                    Ok(v) => self.push(v),
-                                    Err(e) => catch_sync!(self, cached_frame, RunError::from(e)),
-                                }
-                            } else {
-                                let value_type = value.py_type(self.heap);
-                                value.drop_with_heap(self);
-                                catch_sync!(self, cached_frame, ExcType::unary_type_error("-", value_type));
-                            }
-                        }
-                        _ => {
-                            let value_type = value.py_type(self.heap);
-                            value.drop_with_heap(self);
-                            catch_sync!(self, cached_frame, ExcType::unary_type_error("-", value_type));
-                        }
-                    }
-                }
-                Opcode::UnaryPos => {
-                    // Unary plus - converts bools to int, no-op for other numbers
-                    let value = self.pop();
-                    match value {
-                        Value::Int(_) | Value::Float(_) => self.push(value),
-                        Value::Bool(b) => self.push(Value::Int(i64::from(b))),
-                        Value::Ref(id) => {
-                            if matches!(self.heap.get(id), HeapData::LongInt(_)) {
-                                // LongInt - return as-is (value already has correct refcount)
-                                self.push(value);
-                            } else {
-                                let value_type = value.py_type(self.heap);
-                                value.drop_with_heap(self);
-                                catch_sync!(self, cached_frame, ExcType::unary_type_error("+", value_type));
-                            }
-                        }
-                        _ => {
-                            let value_type = value.py_type(self.heap);
-                            value.drop_with_heap(self);
-                            catch_sync!(self, cached_frame, ExcType::unary_type_error("+", value_type));
-                        }
-                    }
-                }
-                Opcode::UnaryInvert => {
-                    // Bitwise NOT
-                    let value = self.pop();
-                    match value {
-                        Value::Int(n) => self.push(Value::Int(!n)),
-                        Value::Bool(b) => self.push(Value::Int(!i64::from(b))),
-                        Value::Ref(id) => {
-                            if let HeapData::LongInt(li) = self.heap.get(id) {
-                                // LongInt bitwise NOT: ~x = -(x + 1)
-                                let inverted = -(li.inner() + 1i32);
-                                value.drop_with_heap(self);
-                                match LongInt::new(inverted).into_value(self.heap) {
-                                    Ok(v) => self.push(v),
-                                    Err(e) => catch_sync!(self, cached_frame, RunError::from(e)),
-                                }
-                            } else {
-                                let value_type = value.py_type(self.heap);
-                                value.drop_with_heap(self);
-                                catch_sync!(self, cached_frame, ExcType::unary_type_error("~", value_type));
-                            }
-                        }
-                        _ => {
-                            let value_type = value.py_type(self.heap);
-                            value.drop_with_heap(self);
-                            catch_sync!(self, cached_frame, ExcType::unary_type_error("~", value_type));
-                        }
-                    }
-                }
+                Opcode::UnaryNot => self.unary_not(),
+                Opcode::UnaryNeg => try_catch_sync!(self, cached_frame, self.unary_neg()),
+                Opcode::UnaryPos => try_catch_sync!(self, cached_frame, self.unary_pos()),
+                Opcode::UnaryInvert => try_catch_sync!(self, cached_frame, self.unary_invert()),
                 // In-place Operations - route through exception handling
                 Opcode::InplaceAdd => try_catch_sync!(self, cached_frame, self.inplace_add()),
                 // Other in-place ops use the same logic as binary ops for now
@@ -1216,12 +1170,8 @@ impl<'a, 'p, T: ResourceTracker> VM<'a, 'p, T> {
                 // Iteration - route through exception handling
                 Opcode::GetIter => {
                     let value = self.pop();
-                    // Create a MontyIter from the value and store on heap
-                    match MontyIter::new(value, self) {
-                        Ok(iter) => match self.heap.allocate(HeapData::Iter(iter)) {
-                            Ok(heap_id) => self.push(Value::Ref(heap_id)),
-                            Err(e) => catch_sync!(self, cached_frame, e.into()),
-                        },
+                    match self.get_iter(value) {
+                        Ok(iter) => self.push(iter),
                         Err(e) => catch_sync!(self, cached_frame, e),
                     }
                 }
@@ -1232,9 +1182,62 @@ impl<'a, 'p, T: ResourceTracker> VM<'a, 'p, T> {
                         return Err(RunError::internal("ForIter: expected iterator ref on stack"));
                     };
 
-                    // Use advance_iterator which avoids std::mem::replace overhead
-                    // by using a two-phase approach: read state, get value, update index
-                    match advance_on_heap(self.heap, heap_id, self.interns) {
+                    // Files are read a line at a time by the host; the line is pushed (or the
+                    // loop ended) when execution resumes, see `OsResultConversion::ForIterLine`
+                    if let HeapData::File(file) = self.heap.get(heap_id) {
+                        match file.for_iter_args() {
+                            Ok(args) => {
+                                self.set_os_result_conversion(OsResultConversion::ForIterLine { offset });
+                                let call_id = self.allocate_call_id();
+                                // Sync cached IP back to frame before snapshot for resume
+                                self.current_frame_mut().ip = cached_frame.ip;
+                                return Ok(FrameExit::OsCall {
+                                    function: OsFunction::FileReadline,
+                                    args,
+                                    call_id,
+                                });
+                            }
+                            Err(e) => {
+                                let file = self.pop();
+                                file.drop_with_heap(self);
+                                catch_sync!(self, cached_frame, e);
+                            }
+                        }
+                        continue;
+                    }
+
+                    // Sync the IP first, as `iter(callable, sentinel)` runs the callable here
+                    self.current_frame_mut().ip = cached_frame.ip;
+                    let next = if let HeapData::Iter(iter) = self.heap.get(heap_id)
+                        && let Some(callable) = iter.sentinel_callable(self.heap)
+                    {
+                        // The callable may pause for the host (e.g. `iter(f.readline, '')`), in
+                        // which case the item is checked on resume, see `OsResultConversion::ForIterSentinel`
+                        let result = self.call_function(&callable, ArgValues::Empty);
+                        callable.drop_with_heap(self);
+                        match result {
+                            Ok(CallResult::Value(item)) => sentinel_item(self, heap_id, item),
+                            Ok(CallResult::FramePushed) => self
+                                .run_pushed_frame("iter()")
+                                .and_then(|item| sentinel_item(self, heap_id, item)),
+                            Ok(CallResult::AwaitValue(value)) => {
+                                value.drop_with_heap(self);
+                                Err(ExcType::type_error("iter(v, w): v must not return an awaitable"))
+                            }
+                            Ok(result) => {
+                                // An external, OS or method call, which `handle_call_result!` yields
+                                self.set_os_result_conversion(OsResultConversion::ForIterSentinel { offset });
+                                handle_call_result!(self, cached_frame, Ok::<_, RunError>(result));
+                                continue;
+                            }
+                            Err(e) => Err(e),
+                        }
+                    } else {
+                        // Use advance_iterator which avoids std::mem::replace overhead
+                        // by using a two-phase approach: read state, get value, update index.
+                        advance_on_heap(self, heap_id)
+                    };
+                    match next {
                         Ok(Some(value)) => self.push(value),
                         Ok(None) => {
                             // Iterator exhausted - pop it and jump to end
@@ -1268,10 +1271,11 @@ impl<'a, 'p, T: ResourceTracker> VM<'a, 'p, T> {
                     // which pushes frames and runs a nested run() loop)
                     self.current_frame_mut().ip = cached_frame.ip;
 
-                    match self.exec_call_builtin_function(builtin_id, arg_count) {
-                        Ok(result) => self.push(result),
-                        Err(err) => catch_sync!(self, cached_frame, err),
-                    }
+                    handle_call_result!(
+                        self,
+                        cached_frame,
+                        self.exec_call_builtin_function(builtin_id, arg_count)
+                    );
                 }
                 Opcode::CallBuiltinType => {
                     // Fetch operands: type_id (u8) + arg_count (u8)
@@ -1279,8 +1283,13 @@ impl<'a, 'p, T: ResourceTracker> VM<'a, 'p, T> {
                     let arg_count = fetch_u8!(cached_frame) as usize;
 
                     match self.exec_call_builtin_type(type_id, arg_count) {
-                        Ok(result) => self.push(result),
-                        // IP sync deferred to error path (no frame push possible)
+                        Ok(CallResult::Value(result)) => self.push(result),
+                        // IP sync deferred to the other paths (no frame push possible), which
+                        // are a host call to read a file (see `call_reading_files`) or an error
+                        Ok(result) => {
+                            self.current_frame_mut().ip = cached_frame.ip;
+                            handle_call_result!(self, cached_frame, Ok::<_, RunError>(result));
+                        }
                         Err(err) => catch_sync!(self, cached_frame, err),
                     }
                 }
@@ -1459,6 +1468,19 @@ impl<'a, 'p, T: ResourceTracker> VM<'a, 'p, T> {
                         let is_main_task = self.is_main_task();
 
                         if is_main_task {
+                            if let Some(handle) = self.file_to_close_at_exit() {
+                                // Close the file first; execution resumes at this instruction
+                                // again, to close the next one or return.
+                                self.push(value);
+                                self.set_os_result_conversion(OsResultConversion::CloseAtExit);
+                                let call_id = self.allocate_call_id();
+                                self.current_frame_mut().ip = self.instruction_ip;
+                                return Ok(FrameExit::OsCall {
+                                    function: OsFunction::FileClose,
+                                    args: ArgValues::One(Value::Int(handle)),
+                                    call_id,
+                                });
+                            }
                             // Module-level return - we're done
                             return Ok(FrameExit::Return(value));
                         }
@@ -1564,13 +1586,182 @@ impl<'a, 'p, T: ResourceTracker> VM<'a, 'p, T> {
     ///
     /// Pushes the return value onto the stack and continues execution.
     pub fn resume(&mut self, obj: MontyObject) -> Result<FrameExit, RunError> {
+        let conversion = self.os_result_conversion.take();
         let value = obj
             .to_value(self)
             .map_err(|e| SimpleException::new(ExcType::RuntimeError, Some(format!("invalid return type: {e}"))))?;
-        self.push(value);
+        match conversion {
+            Some(OsResultConversion::ForIterLine { offset }) => self.push_file_line(value, offset),
+            Some(OsResultConversion::ForIterSentinel { offset }) => {
+                if let Err(err) = self.push_sentinel_item(value, offset) {
+                    return self.resume_with_exception(err);
+                }
+            }
+            Some(OsResultConversion::CloseAtExit) => value.drop_with_heap(self),
+            Some(OsResultConversion::CallWithFileLines {
+                operands,
+                operand,
+                call_ip,
+            }) => {
+                if let Err(err) = self.replace_file_with_lines(value, operands, operand) {
+                    return self.resume_with_exception(err);
+                }
+                self.current_frame_mut().ip = call_ip;
+            }
+            Some(conversion) => match self.convert_os_result(conversion, value) {
+                Ok(value) => self.push(value),
+                Err(err) => return self.resume_with_exception(err),
+            },
+            None => self.push(value),
+        }
         self.run()
     }
 
+    /// Resumes execution with a pending `ExternalFuture` for the call the VM paused on.
+    ///
+    /// The host resolves the future later via `ResolveFutures`. OS calls whose result
+    /// must be converted (see `OsResultConversion`) cannot be deferred, so they raise
+    /// `RuntimeError` instead.
+    pub fn resume_with_future(&mut self, call_id: CallId) -> Result<FrameExit, RunError> {
+        if self.os_result_conversion.is_some() {
+            let err = SimpleException::new_msg(
+                ExcType::RuntimeError,
+                "the result of this OS call cannot be resolved as a future",
+            );
+            return self.resume_with_exception(err.into());
+        }
+        self.add_pending_call(call_id);
+        self.push(Value::ExternalFuture(call_id));
+        self.run()
+    }
+
+    /// Registers a conversion for the result of the `OsCall` about to be yielded.
+    pub(crate) fn set_os_result_conversion(&mut self, conversion: OsResultConversion) {
+        self.os_result_conversion = Some(conversion);
+    }
+
+    /// Applies `conversion` to the host's result for an `OsCall`.
+    fn convert_os_result(&mut self, conversion: OsResultConversion, value: Value) -> RunResult<Value> {
+        match conversion {
+            OsResultConversion::Uuid4 => crate::modules::uuid::uuid4_from_random(value, self),
+            OsResultConversion::OpenFile { name, mode, encoding } => {
+                File::from_handle(value, name, mode, encoding, self)
+            }
+            OsResultConversion::Readlines => crate::types::file::readlines_from(value, self),
+            OsResultConversion::Discard => {
+                value.drop_with_heap(self);
+                Ok(Value::None)
+            }
+            OsResultConversion::ForIterLine { .. } | OsResultConversion::ForIterSentinel { .. } => {
+                unreachable!("resume() continues the loop itself")
+            }
+            OsResultConversion::CallWithFileLines { .. } => unreachable!("resume() runs the call again itself"),
+            OsResultConversion::CloseAtExit => unreachable!("resume() returns from the module again itself"),
+            OsResultConversion::Listdir => crate::modules::os::listdir_from(value, self),
+            OsResultConversion::Normpath => crate::modules::os_path::normpath_from(value, self),
+            OsResultConversion::StatField { field } => crate::modules::os_path::stat_field_from(value, field, self),
+            OsResultConversion::ToPath => crate::types::path::path_from(value, self),
+            OsResultConversion::ExpandUser { rest } => crate::types::path::expanduser_from(value, &rest, self),
+            OsResultConversion::InputLine => crate::builtins::input_line_from(value, self),
+        }
+    }
+
+    /// Returns the handle of the next file to close before the module returns: first those
+    /// dropped without being closed, then, if `close_files_at_exit` is set, those still open.
+    fn file_to_close_at_exit(&mut self) -> Option<i64> {
+        self.heap.take_dropped_file().or_else(|| {
+            if self.close_files_at_exit {
+                self.heap.close_open_file()
+            } else {
+                None
+            }
+        })
+    }
+
+    /// Continues a `for` loop over a file with the line the host read for `ForIter`.
+    ///
+    /// A non-empty line is pushed for the loop body. An empty one means the end of the file,
+    /// so the file is popped and the frame jumps past the loop, as for an exhausted iterator.
+    fn push_file_line(&mut self, line: Value, offset: i16) {
+        if line.py_bool(self) {
+            self.push(line);
+        } else {
+            line.drop_with_heap(self);
+            let file = self.pop();
+            file.drop_with_heap(self);
+            jump_relative!(self.current_frame_mut().ip, offset);
+        }
+    }
+
+    /// Continues a `for` loop over an `iter(callable, sentinel)` iterator with the item its
+    /// callable returned after pausing for the host.
+    ///
+    /// The item is pushed for the loop body unless it equals the sentinel, which ends the
+    /// loop like `push_file_line` does. If the comparison fails, the iterator is popped and
+    /// the error returned for the caller to raise.
+    fn push_sentinel_item(&mut self, item: Value, offset: i16) -> RunResult<()> {
+        let Value::Ref(iter_id) = *self.peek() else {
+            unreachable!("ForIter leaves the iterator on the stack");
+        };
+        match sentinel_item(self, iter_id, item) {
+            Ok(Some(item)) => self.push(item),
+            Ok(None) => {
+                let iter = self.pop();
+                iter.drop_with_heap(self);
+                jump_relative!(self.current_frame_mut().ip, offset);
+            }
+            Err(err) => {
+                let iter = self.pop();
+                iter.drop_with_heap(self);
+                return Err(err);
+            }
+        }
+        Ok(())
+    }
+
+    /// Replaces the file among the top `operands` values of the stack with an iterator over
+    /// the lines of `data`, the rest of the file, so the call consuming them can run again.
+    fn replace_file_with_lines(&mut self, data: Value, operands: usize, operand: usize) -> RunResult<()> {
+        let lines = crate::types::file::readlines_from(data, self)?;
+        let iter = MontyIter::new(lines, self)?;
+        let iter = Value::Ref(self.heap.allocate(HeapData::Iter(iter))?);
+        let start = self.stack.len() - operands;
+        let Value::Ref(file_id) = self.stack[start + operand] else {
+            unreachable!("call_reading_files saves the position of a file")
+        };
+        // The same file may be passed more than once, e.g. `zip(f, f)`, and shares the iterator
+        for index in start..self.stack.len() {
+            if matches!(self.stack[index], Value::Ref(id) if id == file_id) {
+                let file = std::mem::replace(&mut self.stack[index], iter.clone_with_heap(self.heap));
+                file.drop_with_heap(self);
+            }
+        }
+        iter.drop_with_heap(self);
+        Ok(())
+    }
+
+    /// Creates the iterator `GetIter` pushes for `value`.
+    ///
+    /// Iterators and files are their own iterators (`ForIter` has the host read files a
+    /// line at a time); every other iterable gets a `MontyIter` on the heap.
+    fn get_iter(&mut self, value: Value) -> RunResult<Value> {
+        if let Value::Ref(id) = value {
+            match self.heap.get(id) {
+                HeapData::Iter(_) => return Ok(value),
+                HeapData::File(file) => {
+                    if let Err(err) = file.check_open() {
+                        value.drop_with_heap(self);
+                        return Err(err);
+                    }
+                    return Ok(value);
+                }
+                _ => {}
+            }
+        }
+        let iter = MontyIter::new(value, self)?;
+        Ok(Value::Ref(self.heap.allocate(HeapData::Iter(iter))?))
+    }
+
     /// Sets the instruction IP used for exception table lookup and traceback generation.
     ///
     /// Used by `run()` to restore the IP to the load instruction's position before
@@ -1585,6 +1776,8 @@ impl<'a, 'p, T: ResourceTracker> VM<'a, 'p, T> {
     /// Uses the exception handling mechanism to try to catch the exception.
     /// If caught, continues execution at the handler. If not, propagates the error.
     pub fn resume_with_exception(&mut self, error: RunError) -> Result<FrameExit, RunError> {
+        // The OS call failed, so there is no result to convert
+        self.os_result_conversion = None;
         // Use the normal exception handling mechanism
         // handle_exception returns None if caught, Some(error) if not caught
         if let Some(uncaught_error) = self.handle_exception(error) {
@@ -0,0 +1,80 @@
+//! Namespace snapshots for the `globals()` and `locals()` builtins.
+
+use super::VM;
+use crate::{
+    bytecode::Code,
+    exception_private::RunResult,
+    heap::{Heap, HeapData},
+    intern::Interns,
+    resource::ResourceTracker,
+    types::Dict,
+    value::Value,
+};
+
+impl<T: ResourceTracker> VM<'_, '_, T> {
+    /// Builds the dict returned by `globals()`, mapping each bound module-level name to its value.
+    ///
+    /// The dict is a snapshot: assigning to it doesn't rebind the globals, so code can't
+    /// use it to reach anything it couldn't already name. Module dunders (`__name__`
+    /// and friends) the code never assigns have no global slot, so they come from the
+    /// module code instead.
+    pub(crate) fn globals_dict(&mut self) -> RunResult<Value> {
+        let code = self.module_code.expect("module code is set while running");
+        let mut pairs: Vec<(Value, Value)> = code
+            .module_dunders()
+            .iter()
+            .map(|(name, value)| (Value::InternString(*name), value.clone_with_heap(self.heap)))
+            .collect();
+        pairs.extend(namespace_pairs(code, &self.globals, self.heap, self.interns));
+        let dict = Dict::from_pairs(pairs, self)?;
+        Ok(Value::Ref(self.heap.allocate(HeapData::Dict(dict))?))
+    }
+
+    /// Builds the dict returned by `locals()`, a snapshot of the current function's bound local
+    /// names, including those captured by or from a closure.
+    ///
+    /// At module level this is `globals()`.
+    pub(crate) fn locals_dict(&mut self) -> RunResult<Value> {
+        let frame = self.current_frame();
+        if frame.function_id.is_none() {
+            return self.globals_dict();
+        }
+        let locals = &self.stack[frame.stack_base..frame.stack_base + usize::from(frame.locals_count)];
+        let pairs = namespace_pairs(frame.code, locals, self.heap, self.interns);
+        let dict = Dict::from_pairs(pairs, self)?;
+        Ok(Value::Ref(self.heap.allocate(HeapData::Dict(dict))?))
+    }
+}
+
+/// Pairs each bound, named slot of a namespace with a new reference to its value.
+///
+/// Captured variables are stored in a cell, which isn't a Python object itself, so
+/// cells are looked through. Names starting with `$` are temporaries the parser adds
+/// (e.g. for `with` statements) and are left out.
+fn namespace_pairs(
+    code: &Code,
+    namespace: &[Value],
+    heap: &Heap<impl ResourceTracker>,
+    interns: &Interns,
+) -> Vec<(Value, Value)> {
+    let mut pairs = Vec::new();
+    for (value, name) in namespace.iter().zip(code.slot_names()) {
+        let Some(name) = name else {
+            continue;
+        };
+        if interns.get_str(*name).starts_with('$') {
+            continue;
+        }
+        let value = match value {
+            Value::Ref(id) => match heap.get(*id) {
+                HeapData::Cell(cell) => &cell.0,
+                _ => value,
+            },
+            _ => value,
+        };
+        if !matches!(value, Value::Undefined) {
+            pairs.push((Value::InternString(*name), value.clone_with_heap(heap)));
+        }
+    }
+    pairs
+}
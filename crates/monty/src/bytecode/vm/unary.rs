@@ -0,0 +1,114 @@
+//! Unary operation helpers for the VM.
+
+use super::VM;
+use crate::{
+    bytecode::op::Opcode,
+    exception_private::{ExcType, RunError, RunResult},
+    heap::{DropWithHeap, HeapData},
+    resource::ResourceTracker,
+    types::{LongInt, PyTrait},
+    value::Value,
+};
+
+impl<T: ResourceTracker> VM<'_, '_, T> {
+    /// Applies a unary opcode to `value` outside the run loop.
+    ///
+    /// Used by the `operator` module so `operator.neg(x)` and friends behave exactly
+    /// like the corresponding expressions, including their error messages.
+    pub(crate) fn apply_unary_op(&mut self, op: Opcode, value: Value) -> RunResult<Value> {
+        self.push(value);
+        match op {
+            Opcode::UnaryNot => self.unary_not(),
+            Opcode::UnaryNeg => self.unary_neg()?,
+            Opcode::UnaryPos => self.unary_pos()?,
+            Opcode::UnaryInvert => self.unary_invert()?,
+            _ => unreachable!("apply_unary_op called with non-unary opcode {op:?}"),
+        }
+        Ok(self.pop())
+    }
+
+    /// Logical not.
+    pub(super) fn unary_not(&mut self) {
+        let value = self.pop();
+        let result = !value.py_bool(self);
+        value.drop_with_heap(self);
+        self.push(Value::Bool(result));
+    }
+
+    /// Unary minus - negate numeric value.
+    pub(super) fn unary_neg(&mut self) -> Result<(), RunError> {
+        let value = self.pop();
+        match value {
+            Value::Int(n) => {
+                // Use checked_neg to handle i64::MIN overflow
+                if let Some(negated) = n.checked_neg() {
+                    self.push(Value::Int(negated));
+                } else {
+                    // i64::MIN negated overflows to LongInt
+                    let li = -LongInt::from(n);
+                    self.push(li.into_value(self.heap)?);
+                }
+            }
+            Value::Float(f) => self.push(Value::Float(-f)),
+            Value::Bool(b) => self.push(Value::Int(if b { -1 } else { 0 })),
+            Value::Ref(id) => {
+                if let HeapData::LongInt(li) = self.heap.get(id) {
+                    let negated = -LongInt::new(li.inner().clone());
+                    value.drop_with_heap(self);
+                    self.push(negated.into_value(self.heap)?);
+                } else {
+                    return Err(self.unary_type_error("-", value));
+                }
+            }
+            _ => return Err(self.unary_type_error("-", value)),
+        }
+        Ok(())
+    }
+
+    /// Unary plus - converts bools to int, no-op for other numbers.
+    pub(super) fn unary_pos(&mut self) -> Result<(), RunError> {
+        let value = self.pop();
+        match value {
+            Value::Int(_) | Value::Float(_) => self.push(value),
+            Value::Bool(b) => self.push(Value::Int(i64::from(b))),
+            Value::Ref(id) => {
+                if matches!(self.heap.get(id), HeapData::LongInt(_)) {
+                    // LongInt - return as-is (value already has correct refcount)
+                    self.push(value);
+                } else {
+                    return Err(self.unary_type_error("+", value));
+                }
+            }
+            _ => return Err(self.unary_type_error("+", value)),
+        }
+        Ok(())
+    }
+
+    /// Bitwise NOT.
+    pub(super) fn unary_invert(&mut self) -> Result<(), RunError> {
+        let value = self.pop();
+        match value {
+            Value::Int(n) => self.push(Value::Int(!n)),
+            Value::Bool(b) => self.push(Value::Int(!i64::from(b))),
+            Value::Ref(id) => {
+                if let HeapData::LongInt(li) = self.heap.get(id) {
+                    // LongInt bitwise NOT: ~x = -(x + 1)
+                    let inverted = -(li.inner() + 1i32);
+                    value.drop_with_heap(self);
+                    self.push(LongInt::new(inverted).into_value(self.heap)?);
+                } else {
+                    return Err(self.unary_type_error("~", value));
+                }
+            }
+            _ => return Err(self.unary_type_error("~", value)),
+        }
+        Ok(())
+    }
+
+    /// Builds the `bad operand type for unary` error, dropping the operand.
+    fn unary_type_error(&mut self, op: &str, value: Value) -> RunError {
+        let value_type = value.py_type(self.heap);
+        value.drop_with_heap(self);
+        ExcType::unary_type_error(op, value_type)
+    }
+}
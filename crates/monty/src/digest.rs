@@ -0,0 +1,476 @@
+//! Message digests used by the `hashlib`, `hmac` and `uuid` modules.
+//!
+//! MD5, SHA-1 and the SHA-2 family come from the RustCrypto crates. BLAKE2b (RFC 7693),
+//! including keyed hashing and variable digest sizes, is implemented here: the `blake2`
+//! crate can't serialize a hash state part way through a message, which snapshots need.
+//!
+//! [`Hasher`] is an incremental state which can be cloned (for `hash.copy()` and for computing
+//! a digest without consuming the state) and serialized (so hash objects survive snapshots).
+
+use std::str::FromStr;
+
+use md5::Md5;
+use sha1::Sha1;
+use sha2::{
+    Digest, Sha224, Sha256, Sha384, Sha512,
+    digest::common::hazmat::{SerializableState, SerializedState},
+};
+
+/// Hash algorithms supported by `hashlib.new()` and the named constructors.
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    Hash,
+    strum::Display,
+    strum::EnumString,
+    strum::IntoStaticStr,
+    serde::Serialize,
+    serde::Deserialize,
+)]
+#[strum(serialize_all = "lowercase", ascii_case_insensitive)]
+pub(crate) enum HashAlgorithm {
+    Md5,
+    Sha1,
+    Sha224,
+    Sha256,
+    Sha384,
+    Sha512,
+    Blake2b,
+}
+
+impl HashAlgorithm {
+    /// Looks up an algorithm by the name accepted by `hashlib.new()` (case-insensitive).
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::from_str(name).ok()
+    }
+
+    /// Size of the digest in bytes (the default size for BLAKE2b).
+    pub fn digest_size(self) -> usize {
+        match self {
+            Self::Md5 => 16,
+            Self::Sha1 => 20,
+            Self::Sha224 => 28,
+            Self::Sha256 => 32,
+            Self::Sha384 => 48,
+            Self::Sha512 | Self::Blake2b => 64,
+        }
+    }
+
+    /// Internal block size of the algorithm in bytes.
+    pub fn block_size(self) -> usize {
+        match self {
+            Self::Md5 | Self::Sha1 | Self::Sha224 | Self::Sha256 => 64,
+            Self::Sha384 | Self::Sha512 | Self::Blake2b => 128,
+        }
+    }
+}
+
+/// Incremental hashing state for any [`HashAlgorithm`].
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+#[serde(into = "SerializedHasher", try_from = "SerializedHasher")]
+pub(crate) struct Hasher(HashState);
+
+#[derive(Debug, Clone)]
+enum HashState {
+    Md5(Md5),
+    Sha1(Sha1),
+    Sha224(Sha224),
+    Sha256(Sha256),
+    Sha384(Sha384),
+    Sha512(Sha512),
+    Blake2b(Blake2b),
+}
+
+impl Hasher {
+    /// Creates a fresh hasher for `algorithm` with its default digest size.
+    pub fn new(algorithm: HashAlgorithm) -> Self {
+        Self(match algorithm {
+            HashAlgorithm::Md5 => HashState::Md5(Md5::new()),
+            HashAlgorithm::Sha1 => HashState::Sha1(Sha1::new()),
+            HashAlgorithm::Sha224 => HashState::Sha224(Sha224::new()),
+            HashAlgorithm::Sha256 => HashState::Sha256(Sha256::new()),
+            HashAlgorithm::Sha384 => HashState::Sha384(Sha384::new()),
+            HashAlgorithm::Sha512 => HashState::Sha512(Sha512::new()),
+            HashAlgorithm::Blake2b => HashState::Blake2b(Blake2b::new(64, &[])),
+        })
+    }
+
+    /// Creates a BLAKE2b hasher with a custom digest size (1..=64) and optional key (<= 64 bytes).
+    ///
+    /// The caller is responsible for validating the parameter ranges.
+    pub fn new_blake2b(digest_size: u8, key: &[u8]) -> Self {
+        Self(HashState::Blake2b(Blake2b::new(digest_size, key)))
+    }
+
+    /// The algorithm this hasher implements.
+    pub fn algorithm(&self) -> HashAlgorithm {
+        match &self.0 {
+            HashState::Md5(_) => HashAlgorithm::Md5,
+            HashState::Sha1(_) => HashAlgorithm::Sha1,
+            HashState::Sha224(_) => HashAlgorithm::Sha224,
+            HashState::Sha256(_) => HashAlgorithm::Sha256,
+            HashState::Sha384(_) => HashAlgorithm::Sha384,
+            HashState::Sha512(_) => HashAlgorithm::Sha512,
+            HashState::Blake2b(_) => HashAlgorithm::Blake2b,
+        }
+    }
+
+    /// Size of the digest this hasher will produce, in bytes.
+    pub fn digest_size(&self) -> usize {
+        match &self.0 {
+            HashState::Blake2b(hasher) => usize::from(hasher.digest_size),
+            _ => self.algorithm().digest_size(),
+        }
+    }
+
+    /// Feeds more data into the hash state.
+    pub fn update(&mut self, data: &[u8]) {
+        match &mut self.0 {
+            HashState::Md5(hasher) => hasher.update(data),
+            HashState::Sha1(hasher) => hasher.update(data),
+            HashState::Sha224(hasher) => hasher.update(data),
+            HashState::Sha256(hasher) => hasher.update(data),
+            HashState::Sha384(hasher) => hasher.update(data),
+            HashState::Sha512(hasher) => hasher.update(data),
+            HashState::Blake2b(hasher) => hasher.update(data),
+        }
+    }
+
+    /// Returns the digest of all data fed so far, leaving `self` untouched.
+    pub fn finalize(&self) -> Vec<u8> {
+        match &self.0 {
+            HashState::Md5(hasher) => hasher.clone().finalize().to_vec(),
+            HashState::Sha1(hasher) => hasher.clone().finalize().to_vec(),
+            HashState::Sha224(hasher) => hasher.clone().finalize().to_vec(),
+            HashState::Sha256(hasher) => hasher.clone().finalize().to_vec(),
+            HashState::Sha384(hasher) => hasher.clone().finalize().to_vec(),
+            HashState::Sha512(hasher) => hasher.clone().finalize().to_vec(),
+            HashState::Blake2b(hasher) => hasher.finalize(),
+        }
+    }
+}
+
+/// The serialized form of a [`Hasher`], with the RustCrypto states as opaque bytes.
+#[derive(serde::Serialize, serde::Deserialize)]
+enum SerializedHasher {
+    RustCrypto { algorithm: HashAlgorithm, state: Vec<u8> },
+    Blake2b(Blake2b),
+}
+
+impl From<Hasher> for SerializedHasher {
+    fn from(hasher: Hasher) -> Self {
+        let algorithm = hasher.algorithm();
+        let state = match hasher.0 {
+            HashState::Md5(hasher) => hasher.serialize().to_vec(),
+            HashState::Sha1(hasher) => hasher.serialize().to_vec(),
+            HashState::Sha224(hasher) => hasher.serialize().to_vec(),
+            HashState::Sha256(hasher) => hasher.serialize().to_vec(),
+            HashState::Sha384(hasher) => hasher.serialize().to_vec(),
+            HashState::Sha512(hasher) => hasher.serialize().to_vec(),
+            HashState::Blake2b(hasher) => return Self::Blake2b(hasher),
+        };
+        Self::RustCrypto { algorithm, state }
+    }
+}
+
+impl TryFrom<SerializedHasher> for Hasher {
+    type Error = String;
+
+    fn try_from(serialized: SerializedHasher) -> Result<Self, Self::Error> {
+        let (algorithm, state) = match serialized {
+            SerializedHasher::RustCrypto { algorithm, state } => (algorithm, state),
+            SerializedHasher::Blake2b(hasher) => return Ok(Self(HashState::Blake2b(hasher))),
+        };
+        Ok(Self(match algorithm {
+            HashAlgorithm::Md5 => HashState::Md5(deserialize_state(&state)?),
+            HashAlgorithm::Sha1 => HashState::Sha1(deserialize_state(&state)?),
+            HashAlgorithm::Sha224 => HashState::Sha224(deserialize_state(&state)?),
+            HashAlgorithm::Sha256 => HashState::Sha256(deserialize_state(&state)?),
+            HashAlgorithm::Sha384 => HashState::Sha384(deserialize_state(&state)?),
+            HashAlgorithm::Sha512 => HashState::Sha512(deserialize_state(&state)?),
+            HashAlgorithm::Blake2b => return Err("blake2b hash state isn't a RustCrypto state".to_owned()),
+        }))
+    }
+}
+
+/// Restores a RustCrypto hasher from the bytes of its serialized state.
+fn deserialize_state<H: SerializableState>(bytes: &[u8]) -> Result<H, String> {
+    let state = SerializedState::<H>::try_from(bytes).map_err(|_| "hash state has the wrong length".to_owned())?;
+    H::deserialize(&state).map_err(|_| "invalid hash state".to_owned())
+}
+
+/// Convenience one-shot digest of `data`.
+pub(crate) fn digest(algorithm: HashAlgorithm, data: &[u8]) -> Vec<u8> {
+    let mut hasher = Hasher::new(algorithm);
+    hasher.update(data);
+    hasher.finalize()
+}
+
+/// Formats bytes as lowercase hexadecimal, as returned by `hexdigest()`.
+pub(crate) fn to_hex(bytes: &[u8]) -> String {
+    const HEX: &[u8; 16] = b"0123456789abcdef";
+    let mut out = String::with_capacity(bytes.len() * 2);
+    for b in bytes {
+        out.push(char::from(HEX[usize::from(b >> 4)]));
+        out.push(char::from(HEX[usize::from(b & 0x0f)]));
+    }
+    out
+}
+
+/// Incremental BLAKE2b state.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+struct Blake2b {
+    /// Chaining values of the compression function.
+    h: [u64; 8],
+    digest_size: u8,
+    /// Bytes not yet compressed.
+    ///
+    /// This may hold a full block, since the final block must be compressed with the
+    /// finalization flag set and more data may still follow.
+    buffer: Vec<u8>,
+    /// Total number of bytes compressed so far (excluding `buffer`).
+    length: u128,
+}
+
+impl Blake2b {
+    fn new(digest_size: u8, key: &[u8]) -> Self {
+        debug_assert!((1..=64).contains(&digest_size) && key.len() <= 64);
+        let mut h = BLAKE2B_IV;
+        let key_len = u64::try_from(key.len()).expect("blake2b key length validated by caller");
+        h[0] ^= 0x0101_0000 ^ (key_len << 8) ^ u64::from(digest_size);
+        let mut hasher = Self {
+            h,
+            digest_size,
+            buffer: Vec::with_capacity(BLAKE2B_BLOCK_SIZE),
+            length: 0,
+        };
+        if !key.is_empty() {
+            let mut block = [0u8; BLAKE2B_BLOCK_SIZE];
+            block[..key.len()].copy_from_slice(key);
+            hasher.update(&block);
+        }
+        hasher
+    }
+
+    fn update(&mut self, mut data: &[u8]) {
+        while !data.is_empty() {
+            // A full buffer is only compressed once more data arrives, as it may be the last block.
+            if self.buffer.len() == BLAKE2B_BLOCK_SIZE {
+                self.length += BLAKE2B_BLOCK_SIZE as u128;
+                blake2b_compress(&mut self.h, &self.buffer, self.length, false);
+                self.buffer.clear();
+            }
+            let take = (BLAKE2B_BLOCK_SIZE - self.buffer.len()).min(data.len());
+            self.buffer.extend_from_slice(&data[..take]);
+            data = &data[take..];
+        }
+    }
+
+    fn finalize(&self) -> Vec<u8> {
+        let mut h = self.h;
+        let mut block = self.buffer.clone();
+        let length = self.length + block.len() as u128;
+        block.resize(BLAKE2B_BLOCK_SIZE, 0);
+        blake2b_compress(&mut h, &block, length, true);
+        let mut out: Vec<u8> = h.iter().flat_map(|w| w.to_le_bytes()).collect();
+        out.truncate(usize::from(self.digest_size));
+        out
+    }
+}
+
+// ===== BLAKE2b =====
+
+/// Same as the SHA-512 initial hash values.
+const BLAKE2B_IV: [u64; 8] = [
+    0x6a09_e667_f3bc_c908,
+    0xbb67_ae85_84ca_a73b,
+    0x3c6e_f372_fe94_f82b,
+    0xa54f_f53a_5f1d_36f1,
+    0x510e_527f_ade6_82d1,
+    0x9b05_688c_2b3e_6c1f,
+    0x1f83_d9ab_fb41_bd6b,
+    0x5be0_cd19_137e_2179,
+];
+
+const BLAKE2B_BLOCK_SIZE: usize = 128;
+
+const BLAKE2B_SIGMA: [[usize; 16]; 10] = [
+    [0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15],
+    [14, 10, 4, 8, 9, 15, 13, 6, 1, 12, 0, 2, 11, 7, 5, 3],
+    [11, 8, 12, 0, 5, 2, 15, 13, 10, 14, 3, 6, 7, 1, 9, 4],
+    [7, 9, 3, 1, 13, 12, 11, 14, 2, 6, 5, 10, 4, 0, 15, 8],
+    [9, 0, 5, 7, 2, 4, 10, 15, 14, 1, 11, 12, 6, 8, 3, 13],
+    [2, 12, 6, 10, 0, 11, 8, 3, 4, 13, 7, 5, 15, 14, 1, 9],
+    [12, 5, 1, 15, 14, 13, 4, 10, 0, 7, 6, 3, 9, 2, 8, 11],
+    [13, 11, 7, 14, 12, 1, 3, 9, 5, 0, 15, 4, 8, 6, 2, 10],
+    [6, 15, 14, 9, 11, 3, 0, 8, 12, 2, 13, 7, 1, 4, 10, 5],
+    [10, 2, 8, 4, 7, 6, 1, 5, 15, 11, 9, 14, 3, 12, 13, 0],
+];
+
+/// The BLAKE2b mixing function `G`.
+#[inline]
+#[expect(clippy::many_single_char_names, reason = "uses the names from RFC 7693")]
+fn blake2b_mix(v: &mut [u64; 16], a: usize, b: usize, c: usize, d: usize, x: u64, y: u64) {
+    v[a] = v[a].wrapping_add(v[b]).wrapping_add(x);
+    v[d] = (v[d] ^ v[a]).rotate_right(32);
+    v[c] = v[c].wrapping_add(v[d]);
+    v[b] = (v[b] ^ v[c]).rotate_right(24);
+    v[a] = v[a].wrapping_add(v[b]).wrapping_add(y);
+    v[d] = (v[d] ^ v[a]).rotate_right(16);
+    v[c] = v[c].wrapping_add(v[d]);
+    v[b] = (v[b] ^ v[c]).rotate_right(63);
+}
+
+#[expect(
+    clippy::cast_possible_truncation,
+    reason = "the 128-bit counter is split into two 64-bit words"
+)]
+fn blake2b_compress(h: &mut [u64; 8], block: &[u8], counter: u128, last: bool) {
+    let mut m = [0u64; 16];
+    for (word, chunk) in m.iter_mut().zip(block.chunks_exact(8)) {
+        *word = u64::from_le_bytes(chunk.try_into().expect("chunk is 8 bytes"));
+    }
+    let mut v = [0u64; 16];
+    v[..8].copy_from_slice(h);
+    v[8..].copy_from_slice(&BLAKE2B_IV);
+    v[12] ^= counter as u64;
+    v[13] ^= (counter >> 64) as u64;
+    if last {
+        v[14] = !v[14];
+    }
+    for round in 0..12 {
+        let s = &BLAKE2B_SIGMA[round % 10];
+        blake2b_mix(&mut v, 0, 4, 8, 12, m[s[0]], m[s[1]]);
+        blake2b_mix(&mut v, 1, 5, 9, 13, m[s[2]], m[s[3]]);
+        blake2b_mix(&mut v, 2, 6, 10, 14, m[s[4]], m[s[5]]);
+        blake2b_mix(&mut v, 3, 7, 11, 15, m[s[6]], m[s[7]]);
+        blake2b_mix(&mut v, 0, 5, 10, 15, m[s[8]], m[s[9]]);
+        blake2b_mix(&mut v, 1, 6, 11, 12, m[s[10]], m[s[11]]);
+        blake2b_mix(&mut v, 2, 7, 8, 13, m[s[12]], m[s[13]]);
+        blake2b_mix(&mut v, 3, 4, 9, 14, m[s[14]], m[s[15]]);
+    }
+    for i in 0..8 {
+        h[i] ^= v[i] ^ v[i + 8];
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A message long enough to cover every padding boundary of the 64- and 128-byte block sizes.
+    fn message() -> Vec<u8> {
+        (0..300u32).map(|i| u8::try_from(i % 251).unwrap()).collect()
+    }
+
+    /// Hashes the digests of every prefix of `message()` up to 257 bytes, so that one
+    /// expected value covers the padding at 55, 56, 64, 111, 112 and 128 bytes and the
+    /// multi-block cases. The expected values come from CPython's `hashlib`.
+    fn prefix_digests(new: impl Fn() -> Hasher) -> String {
+        let message = message();
+        let mut outer = Hasher::new(new().algorithm());
+        if let HashState::Blake2b(hasher) = &new().0 {
+            outer = Hasher::new_blake2b(hasher.digest_size, &[]);
+        }
+        for len in 0..=257 {
+            let mut hasher = new();
+            hasher.update(&message[..len]);
+            outer.update(&hasher.finalize());
+        }
+        to_hex(&outer.finalize())
+    }
+
+    #[test]
+    fn prefixes_match_cpython() {
+        let cases = [
+            (HashAlgorithm::Md5, "6bd82239e5dc7ee79a3ccc17be5674fe"),
+            (HashAlgorithm::Sha1, "627f364a8bea653c1c707ea3a8ae64c6941d02b0"),
+            (
+                HashAlgorithm::Sha224,
+                "593594252ee9f36b6ada9585d1046e2e25ae76163ef0a4a69ff5e4ce",
+            ),
+            (
+                HashAlgorithm::Sha256,
+                "4a490bb58296d4673a502d4a21cae7f7b9fb5e6cf0d7344bc3523bf0da0ac6ac",
+            ),
+            (
+                HashAlgorithm::Sha384,
+                "7249c146fa563dc9da12bf3de01b317161baca39e76095a9bd716cdb8b4f1eec1bed456adee394f9ea552695313cb2c8",
+            ),
+            (
+                HashAlgorithm::Sha512,
+                "f01c1641176890a386789559a81f47b7c1e0bdd33b6e5d0e4b1f3daa9e9ebd3b\
+                 8419dee04d6d03399d6f53092163aa372e61da170a6dc743a86bc546f28015ad",
+            ),
+            (
+                HashAlgorithm::Blake2b,
+                "a5c80745c34cae66c54837b244bb9dec6d9b3eaaa0f62a6f7a98ff5e391ccb8e\
+                 96bc176d58cbdff4bb2e27ebc3cb72616e9e23a5b09a915b9b1acd09d97021d6",
+            ),
+        ];
+        for (algorithm, expected) in cases {
+            assert_eq!(prefix_digests(|| Hasher::new(algorithm)), expected, "{algorithm}");
+        }
+    }
+
+    #[test]
+    fn blake2b_keyed_prefixes_match_cpython() {
+        let key: Vec<u8> = (0..64).collect();
+        assert_eq!(
+            prefix_digests(|| Hasher::new_blake2b(32, &key)),
+            "a3d7746ed72c27696eab3d975e20fab454677385f84d6d5a5643c10a597f8e17"
+        );
+    }
+
+    #[test]
+    fn blake2b_rfc7693_vectors() {
+        // RFC 7693 appendix A
+        assert_eq!(
+            to_hex(&digest(HashAlgorithm::Blake2b, b"abc")),
+            "ba80a53f981c4d0d6a2797b69f12f6e94c212f14685ac4b74b12bb6fdbffa2d1\
+             7d87c5392aab792dc252d5de4533cc9518d38aa8dbf1925ab92386edd4009923"
+        );
+        // last keyed vector of the reference implementation's blake2b-kat.txt
+        let key: Vec<u8> = (0..64).collect();
+        let message: Vec<u8> = (0..255).collect();
+        let mut hasher = Hasher::new_blake2b(64, &key);
+        hasher.update(&message);
+        assert_eq!(
+            to_hex(&hasher.finalize()),
+            "142709d62e28fcccd0af97fad0f8465b971e82201dc51070faa0372aa43e9248\
+             4be1c1e73ba10906d5d1853db6a4106e0a7bf9800d373d6dee2d46d62ef2a461"
+        );
+    }
+
+    /// Feeding the message in uneven pieces across block boundaries, with a snapshot
+    /// round trip between pieces, gives the same digest as one `update()`.
+    #[test]
+    fn incremental_updates_and_snapshots() {
+        let message = message();
+        let algorithms = [
+            HashAlgorithm::Md5,
+            HashAlgorithm::Sha1,
+            HashAlgorithm::Sha224,
+            HashAlgorithm::Sha256,
+            HashAlgorithm::Sha384,
+            HashAlgorithm::Sha512,
+            HashAlgorithm::Blake2b,
+        ];
+        for algorithm in algorithms {
+            let mut hasher = Hasher::new(algorithm);
+            let mut rest = message.as_slice();
+            for size in [1, 54, 9, 63, 1, 127, 0, 45] {
+                let (piece, tail) = rest.split_at(size);
+                hasher.update(piece);
+                rest = tail;
+                let bytes = postcard::to_allocvec(&hasher).unwrap();
+                hasher = postcard::from_bytes(&bytes).unwrap();
+            }
+            assert_eq!(rest.len(), 0, "pieces cover the message");
+            assert_eq!(hasher.finalize(), digest(algorithm, &message), "{algorithm}");
+        }
+    }
+}
@@ -97,9 +97,13 @@ pub enum ExcType {
     IsADirectoryError,
     /// Subclass of OSError - for when a path is not a directory but one was expected.
     NotADirectoryError,
+    /// Subclass of OSError - for when an operation lacks the required access rights.
+    PermissionError,
 
     // --- Standalone exception types ---
     AssertionError,
+    /// Raised when input ends before a complete value has been read (e.g. a truncated gzip stream).
+    EOFError,
     MemoryError,
     StopIteration,
     SyntaxError,
@@ -120,6 +124,42 @@ pub enum ExcType {
     /// representations into the required attributes.
     #[strum(serialize = "re.PatternError")]
     RePatternError,
+
+    // --- binascii module ---
+    /// `binascii.Error` - raised for malformed base64/hex input. Subclass of `ValueError`.
+    #[strum(serialize = "binascii.Error")]
+    BinasciiError,
+
+    // --- csv module ---
+    /// `csv.Error` - raised for malformed CSV input or unwritable records. Subclass of `Exception`.
+    #[strum(serialize = "_csv.Error")]
+    CsvError,
+
+    // --- statistics module ---
+    /// `statistics.StatisticsError` - raised for empty or too-small data sets. Subclass of `ValueError`.
+    #[strum(serialize = "statistics.StatisticsError")]
+    StatisticsError,
+
+    // --- struct module ---
+    /// `struct.error` - raised for malformed formats and values that cannot be packed. Subclass of `Exception`.
+    #[strum(serialize = "struct.error")]
+    StructError,
+
+    // --- zlib module ---
+    /// `zlib.error` - raised for invalid compressed data or compression parameters. Subclass of `Exception`.
+    #[strum(serialize = "zlib.error")]
+    ZlibError,
+
+    // --- gzip module ---
+    /// `gzip.BadGzipFile` - raised for invalid gzip headers and trailers. Subclass of `OSError`.
+    #[strum(serialize = "gzip.BadGzipFile")]
+    BadGzipFile,
+
+    // --- io module ---
+    /// `io.UnsupportedOperation` - raised when a file object does not support the requested
+    /// operation (e.g. reading a file opened for writing). Subclass of `OSError` and `ValueError`.
+    #[strum(serialize = "io.UnsupportedOperation")]
+    UnsupportedOperation,
 }
 
 impl ExcType {
@@ -152,14 +192,25 @@ impl ExcType {
             Self::AttributeError => matches!(self, Self::FrozenInstanceError),
             // NameError catches UnboundLocalError
             Self::NameError => matches!(self, Self::UnboundLocalError),
-            // ValueError catches UnicodeDecodeError
-            Self::ValueError => matches!(self, Self::UnicodeDecodeError),
+            // ValueError catches UnicodeDecodeError, binascii.Error, statistics.StatisticsError
+            // and io.UnsupportedOperation
+            Self::ValueError => matches!(
+                self,
+                Self::UnicodeDecodeError | Self::BinasciiError | Self::StatisticsError | Self::UnsupportedOperation
+            ),
             // ImportError catches ModuleNotFoundError
             Self::ImportError => matches!(self, Self::ModuleNotFoundError),
-            // OSError catches FileNotFoundError, FileExistsError, IsADirectoryError, NotADirectoryError
+            // OSError catches FileNotFoundError, FileExistsError, IsADirectoryError, NotADirectoryError,
+            // PermissionError, gzip.BadGzipFile and io.UnsupportedOperation
             Self::OSError => matches!(
                 self,
-                Self::FileNotFoundError | Self::FileExistsError | Self::IsADirectoryError | Self::NotADirectoryError
+                Self::FileNotFoundError
+                    | Self::FileExistsError
+                    | Self::IsADirectoryError
+                    | Self::NotADirectoryError
+                    | Self::PermissionError
+                    | Self::BadGzipFile
+                    | Self::UnsupportedOperation
             ),
             // All other types only match exactly (handled by self == handler_type above)
             _ => false,
@@ -174,6 +225,17 @@ impl ExcType {
     /// The `interns` parameter provides access to interned string content.
     /// Returns a heap-allocated exception value.
     pub(crate) fn call(self, vm: &mut VM<'_, '_, impl ResourceTracker>, args: ArgValues) -> RunResult<Value> {
+        if self == Self::SystemExit {
+            // the exit code may be any object, not just a message
+            let exc = match args.get_zero_one_arg("SystemExit", vm.heap)? {
+                Some(code) => {
+                    defer_drop!(code, vm);
+                    SimpleException::system_exit(code, vm)
+                }
+                None => SimpleException::new_none(self),
+            };
+            return Ok(Value::Ref(vm.heap.allocate(HeapData::Exception(exc))?));
+        }
         defer_drop!(args, vm);
         let exc = match args {
             ArgValues::Empty => Ok(SimpleException::new_none(self)),
@@ -231,6 +293,14 @@ impl ExcType {
         .into()
     }
 
+    /// Creates a TypeError for a non-str attribute name given to `getattr()` and its siblings.
+    ///
+    /// Matches CPython's format: `attribute name must be string, not 'int'`
+    #[must_use]
+    pub(crate) fn type_error_attribute_name(type_: Type) -> RunError {
+        SimpleException::new_msg(Self::TypeError, format!("attribute name must be string, not '{type_}'")).into()
+    }
+
     /// Creates an AttributeError for a missing module attribute.
     ///
     /// Matches CPython's format: `AttributeError: module 'name' has no attribute 'attr'`
@@ -261,6 +331,12 @@ impl ExcType {
         .into()
     }
 
+    /// Creates a FrozenInstanceError for deleting an attribute of a frozen dataclass.
+    #[must_use]
+    pub(crate) fn frozen_instance_error_delete(attr_name: &str) -> RunError {
+        SimpleException::new_msg(Self::FrozenInstanceError, format!("cannot delete field '{attr_name}'")).into()
+    }
+
     #[must_use]
     pub(crate) fn type_error_not_sub(type_: Type) -> RunError {
         SimpleException::new_msg(Self::TypeError, format!("'{type_}' object is not subscriptable")).into()
@@ -676,6 +752,18 @@ impl ExcType {
         .into()
     }
 
+    /// Creates a TypeError for issubclass() arg 2.
+    ///
+    /// Matches CPython's format: `TypeError: issubclass() arg 2 must be a class, a tuple of classes, or a union`
+    #[must_use]
+    pub(crate) fn issubclass_arg2_error() -> RunError {
+        SimpleException::new_msg(
+            Self::TypeError,
+            "issubclass() arg 2 must be a class, a tuple of classes, or a union",
+        )
+        .into()
+    }
+
     /// Creates a TypeError for invalid exception type in except clause.
     ///
     /// Matches CPython's format: `TypeError: catching classes that do not inherit from BaseException is not allowed`
@@ -1149,6 +1237,62 @@ impl ExcType {
     pub(crate) fn re_pattern_error(msg: impl fmt::Display) -> RunError {
         SimpleException::new_msg(Self::RePatternError, msg).into()
     }
+
+    /// Creates a `binascii.Error` for malformed encoded input.
+    ///
+    /// Matches CPython's exception type: `binascii.Error: {message}`
+    #[must_use]
+    pub(crate) fn binascii_error(msg: impl fmt::Display) -> RunError {
+        SimpleException::new_msg(Self::BinasciiError, msg).into()
+    }
+
+    /// Creates a `csv.Error` for malformed CSV input or a record that cannot be written.
+    ///
+    /// Matches CPython's exception type: `_csv.Error: {message}`
+    #[must_use]
+    pub(crate) fn csv_error(msg: impl fmt::Display) -> RunError {
+        SimpleException::new_msg(Self::CsvError, msg).into()
+    }
+
+    /// Creates a `statistics.StatisticsError` for data the requested statistic is undefined for.
+    ///
+    /// Matches CPython's exception type: `statistics.StatisticsError: {message}`
+    #[must_use]
+    pub(crate) fn statistics_error(msg: impl fmt::Display) -> RunError {
+        SimpleException::new_msg(Self::StatisticsError, msg).into()
+    }
+
+    /// Creates a `struct.error` for a malformed format or data that does not match it.
+    ///
+    /// Matches CPython's exception type: `struct.error: {message}`
+    #[must_use]
+    pub(crate) fn struct_error(msg: impl fmt::Display) -> RunError {
+        SimpleException::new_msg(Self::StructError, msg).into()
+    }
+
+    /// Creates a `zlib.error` for invalid compressed data or compression parameters.
+    ///
+    /// Matches CPython's exception type: `zlib.error: {message}`
+    #[must_use]
+    pub(crate) fn zlib_error(msg: impl fmt::Display) -> RunError {
+        SimpleException::new_msg(Self::ZlibError, msg).into()
+    }
+
+    /// Creates a `gzip.BadGzipFile` for an invalid gzip header or trailer.
+    ///
+    /// Matches CPython's exception type: `gzip.BadGzipFile: {message}`
+    #[must_use]
+    pub(crate) fn bad_gzip_file(msg: impl fmt::Display) -> RunError {
+        SimpleException::new_msg(Self::BadGzipFile, msg).into()
+    }
+
+    /// Creates an `io.UnsupportedOperation` for a file operation the file's mode does not allow.
+    ///
+    /// Matches CPython's exception type: `io.UnsupportedOperation: {message}`
+    #[must_use]
+    pub(crate) fn unsupported_operation(msg: impl fmt::Display) -> RunError {
+        SimpleException::new_msg(Self::UnsupportedOperation, msg).into()
+    }
 }
 
 /// Simple lightweight representation of an exception.
@@ -1159,6 +1303,36 @@ impl ExcType {
 pub(crate) struct SimpleException {
     exc_type: ExcType,
     arg: Option<String>,
+    /// The code of a `SystemExit` raised with an `int` or `bool`, see `system_exit()`.
+    int_code: Option<IntExitCode>,
+}
+
+/// The code of a `SystemExit` raised with an `int` or `bool`.
+///
+/// Kept apart from the message so that it can't be confused with a `str` code that
+/// reads as a number, like `sys.exit('3')`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize)]
+pub(crate) enum IntExitCode {
+    Int(i64),
+    Bool(bool),
+}
+
+impl IntExitCode {
+    /// The code as a `Value`, for `SystemExit.code` and `.args`.
+    fn to_value(self) -> Value {
+        match self {
+            Self::Int(code) => Value::Int(code),
+            Self::Bool(code) => Value::Bool(code),
+        }
+    }
+
+    /// The process exit status the code asks for.
+    pub(crate) fn status(self) -> i64 {
+        match self {
+            Self::Int(code) => code,
+            Self::Bool(code) => i64::from(code),
+        }
+    }
 }
 
 impl fmt::Display for SimpleException {
@@ -1170,6 +1344,7 @@ impl From<MontyException> for SimpleException {
     fn from(exc: MontyException) -> Self {
         Self {
             exc_type: exc.exc_type(),
+            int_code: exc.int_code(),
             arg: exc.into_message(),
         }
     }
@@ -1179,7 +1354,11 @@ impl SimpleException {
     /// Creates a new exception with the given type and optional argument message.
     #[must_use]
     pub fn new(exc_type: ExcType, arg: Option<String>) -> Self {
-        Self { exc_type, arg }
+        Self {
+            exc_type,
+            arg,
+            int_code: None,
+        }
     }
 
     /// Creates a new exception with the given type and argument message.
@@ -1188,13 +1367,37 @@ impl SimpleException {
         Self {
             exc_type,
             arg: Some(arg.to_string()),
+            int_code: None,
         }
     }
 
     /// Creates a new exception with the given type and no argument message.
     #[must_use]
     pub fn new_none(exc_type: ExcType) -> Self {
-        Self { exc_type, arg: None }
+        Self {
+            exc_type,
+            arg: None,
+            int_code: None,
+        }
+    }
+
+    /// Creates the `SystemExit` for `sys.exit(code)` or `SystemExit(code)`.
+    ///
+    /// `None` is kept as no message. An `int` or `bool` code is kept in `int_code`, with its
+    /// `str()` as the message, and any other code as just `str(code)`, the message printed
+    /// on exit.
+    pub(crate) fn system_exit(code: &Value, vm: &VM<'_, '_, impl ResourceTracker>) -> Self {
+        let int_code = match code {
+            Value::None => return Self::new_none(ExcType::SystemExit),
+            Value::Bool(b) => Some(IntExitCode::Bool(*b)),
+            Value::Int(i) => Some(IntExitCode::Int(*i)),
+            _ => None,
+        };
+        Self {
+            exc_type: ExcType::SystemExit,
+            arg: Some(code.py_str(vm).into_owned()),
+            int_code,
+        }
     }
 
     #[must_use]
@@ -1227,8 +1430,11 @@ impl SimpleException {
         let type_str: &'static str = self.exc_type.into();
         write!(f, "{type_str}(")?;
 
-        if let Some(arg) = &self.arg {
-            string_repr_fmt(arg, f)?;
+        match (&self.arg, self.int_code) {
+            // an int or bool exit code's message is its repr
+            (Some(arg), Some(_)) => f.write_str(arg)?,
+            (Some(arg), None) => string_repr_fmt(arg, f)?,
+            (None, _) => {}
         }
 
         f.write_char(')')
@@ -1252,8 +1458,8 @@ impl SimpleException {
 
     /// Gets an attribute from this exception.
     ///
-    /// Handles the `.args` attribute by allocating a tuple containing the message.
-    /// Returns `Err(AttributeError)` for all other attributes.
+    /// Handles the `.args` attribute by allocating a tuple containing the message, and
+    /// `SystemExit.code`. Returns `Err(AttributeError)` for all other attributes.
     pub fn py_getattr(
         &self,
         attr: &EitherStr,
@@ -1265,9 +1471,23 @@ impl SimpleException {
             .static_string()
             .map_or_else(|| attr.as_str(interns) == "args", |ss| ss == StaticStrings::Args);
 
+        let is_code = attr
+            .static_string()
+            .map_or_else(|| attr.as_str(interns) == "code", |ss| ss == StaticStrings::Code);
+        if self.exc_type == ExcType::SystemExit && is_code {
+            // see `system_exit()` for how the code is stored
+            let code = match (self.int_code, &self.arg) {
+                (Some(code), _) => code.to_value(),
+                (None, None) => Value::None,
+                (None, Some(arg)) => Value::Ref(heap.allocate(HeapData::Str(Str::from(arg.clone())))?),
+            };
+            return Ok(Some(CallResult::Value(code)));
+        }
         if is_args {
             // Construct tuple with 0 or 1 elements based on whether arg exists
-            let elements = if let Some(arg_str) = &self.arg {
+            let elements = if let Some(code) = self.int_code {
+                smallvec![code.to_value()]
+            } else if let Some(arg_str) = &self.arg {
                 let str_id = heap.allocate(HeapData::Str(Str::from(arg_str.clone())))?;
                 smallvec![Value::Ref(str_id)]
             } else {
@@ -1377,6 +1597,7 @@ impl ExceptionRaise {
             .unwrap_or_default();
 
         MontyException::new_full(self.exc.exc_type(), self.exc.arg().cloned(), traceback)
+            .with_int_code(self.exc.int_code)
     }
 }
 
@@ -1,7 +1,7 @@
 use std::fmt::{self, Write};
 
 use crate::{
-    exception_private::{ExcType, RawStackFrame},
+    exception_private::{ExcType, IntExitCode, RawStackFrame},
     intern::Interns,
     parse::CodeRange,
     types::str::StringRepr,
@@ -16,6 +16,8 @@ pub struct MontyException {
     message: Option<String>,
     /// Stack trace of the exception, first is the outermost frame shown first in the traceback
     traceback: Vec<StackFrame>,
+    /// The code of a `SystemExit` raised with an `int` or `bool`, `None` for every other code
+    int_code: Option<IntExitCode>,
 }
 
 /// Number of identical consecutive frames to show before collapsing.
@@ -81,6 +83,7 @@ impl MontyException {
             exc_type,
             message,
             traceback: vec![],
+            int_code: None,
         }
     }
 
@@ -108,6 +111,40 @@ impl MontyException {
         self.message
     }
 
+    /// The exit status requested by an uncaught `SystemExit`, e.g. from `sys.exit()`.
+    ///
+    /// Returns `None` for every other exception, so hosts can tell a clean exit from an error.
+    /// As in CPython, no code means 0 and an integer code is used as is; any other code means 1,
+    /// and `exit_message()` is the text to print.
+    #[must_use]
+    pub fn exit_code(&self) -> Option<i64> {
+        if self.exc_type != ExcType::SystemExit {
+            return None;
+        }
+        Some(match (self.int_code, &self.message) {
+            (Some(code), _) => code.status(),
+            (None, None) => 0,
+            (None, Some(_)) => 1,
+        })
+    }
+
+    /// The text to print for an uncaught `SystemExit`: `str()` of its code, unless the code is
+    /// an integer or absent.
+    ///
+    /// Returns `None` for every other exception.
+    #[must_use]
+    pub fn exit_message(&self) -> Option<&str> {
+        if self.exc_type != ExcType::SystemExit || self.int_code.is_some() {
+            return None;
+        }
+        self.message.as_deref()
+    }
+
+    /// The code of a `SystemExit` raised with an `int` or `bool`.
+    pub(crate) fn int_code(&self) -> Option<IntExitCode> {
+        self.int_code
+    }
+
     /// Stack trace of the exception, first is the outermost frame shown first in the traceback
     #[must_use]
     pub fn traceback(&self) -> &[StackFrame] {
@@ -134,10 +171,11 @@ impl MontyException {
     #[must_use]
     pub fn py_repr(&self) -> String {
         let type_str: &'static str = self.exc_type.into();
-        if let Some(msg) = &self.message {
-            format!("{}({})", type_str, StringRepr(msg))
-        } else {
-            format!("{type_str}()")
+        match (&self.message, self.int_code) {
+            // an int or bool exit code's message is its repr
+            (Some(msg), Some(_)) => format!("{type_str}({msg})"),
+            (Some(msg), None) => format!("{}({})", type_str, StringRepr(msg)),
+            (None, _) => format!("{type_str}()"),
         }
     }
 
@@ -146,14 +184,23 @@ impl MontyException {
             exc_type,
             message,
             traceback,
+            int_code: None,
         }
     }
 
+    /// Sets the code of a `SystemExit` raised with an `int` or `bool`.
+    #[must_use]
+    pub(crate) fn with_int_code(mut self, int_code: Option<IntExitCode>) -> Self {
+        self.int_code = int_code;
+        self
+    }
+
     pub(crate) fn runtime_error(err: impl fmt::Display) -> Self {
         Self {
             exc_type: ExcType::RuntimeError,
             message: Some(err.to_string()),
             traceback: vec![],
+            int_code: None,
         }
     }
 }
@@ -565,12 +565,15 @@ pub enum Node<F> {
     /// Executes body, catches matching exceptions with handlers, runs else if no exception,
     /// and always runs finally.
     Try(Try<Self>),
-    /// Import statement (e.g., `import sys`, `import sys as s`).
+    /// Import statement (e.g., `import sys`, `import sys as s`, `import os.path`).
     ///
     /// Loads a module and binds it to a name in the current namespace.
     Import {
-        /// The module name to import (e.g., "sys", "typing").
+        /// The module name to import (e.g., "sys", "typing", "os.path").
         module_name: StringId,
+        /// For a dotted import without an alias (e.g. `import os.path`), the top-level
+        /// package (`os`), which is what gets bound rather than the submodule.
+        package: Option<StringId>,
         /// The binding target - contains the name (or alias), position, and namespace slot.
         /// After prepare phase, this includes the resolved namespace slot for storing the module.
         binding: Identifier,
@@ -605,6 +608,8 @@ pub struct PreparedFunctionDef {
     pub body: Vec<Node<Self>>,
     /// Number of local variable slots needed in the namespace.
     pub namespace_size: usize,
+    /// The variable each namespace slot holds, indexed by slot, for `locals()`.
+    pub local_names: Vec<Option<StringId>>,
     /// Enclosing namespace slots for variables captured from enclosing scopes.
     ///
     /// At definition time: look up cell HeapId from enclosing namespace at each slot.
@@ -78,10 +78,12 @@ impl HashState {
                     Self::Unhashable
                 }
             }
-            // Path is immutable and hashable
-            HeapData::Path(_) => Self::Unknown,
+            // Path and UUID are immutable and hashable
+            HeapData::Path(_) | HeapData::Uuid(_) => Self::Unknown,
             // ExtFunction is hashable (by identity, like closures)
             HeapData::ExtFunction(_) => Self::Unknown,
+            // object() instances are hashable by identity
+            HeapData::Object(_) => Self::Unknown,
             // other types are unhashable
             _ => Self::Unhashable,
         }
@@ -157,6 +159,9 @@ pub(crate) struct Heap<T: ResourceTracker> {
     /// Uses `Cell` for interior mutability so that methods with only `&Heap`
     /// (like `py_repr_fmt`) can still increment/decrement the depth counter.
     recursion_depth: Cell<usize>,
+    /// Handles of files that were freed without being closed, for the VM to close when the
+    /// module finishes (see `take_dropped_file`).
+    dropped_files: Vec<i64>,
 }
 
 impl<T: ResourceTracker + serde::Serialize> serde::Serialize for Heap<T> {
@@ -168,6 +173,7 @@ impl<T: ResourceTracker + serde::Serialize> serde::Serialize for Heap<T> {
         state.serialize_field("tracker", &self.tracker)?;
         state.serialize_field("may_have_cycles", &self.may_have_cycles)?;
         state.serialize_field("allocations_since_gc", &self.allocations_since_gc)?;
+        state.serialize_field("dropped_files", &self.dropped_files)?;
         state.end()
     }
 }
@@ -181,6 +187,7 @@ impl<'de, T: ResourceTracker + serde::Deserialize<'de>> serde::Deserialize<'de>
             tracker: T,
             may_have_cycles: bool,
             allocations_since_gc: u32,
+            dropped_files: Vec<i64>,
         }
         let fields = HeapFields::<T>::deserialize(deserializer)?;
         Ok(Self {
@@ -190,6 +197,7 @@ impl<'de, T: ResourceTracker + serde::Deserialize<'de>> serde::Deserialize<'de>
             may_have_cycles: fields.may_have_cycles,
             allocations_since_gc: fields.allocations_since_gc,
             recursion_depth: Cell::new(0),
+            dropped_files: fields.dropped_files,
         })
     }
 }
@@ -238,6 +246,7 @@ impl<T: ResourceTracker> Heap<T> {
             may_have_cycles: false,
             allocations_since_gc: 0,
             recursion_depth: Cell::new(0),
+            dropped_files: Vec::new(),
         };
         // TBC: should the empty tuple contribute to the resource limits?
         // If not, can just place it in `entries` directly without going through `allocate()`.
@@ -447,6 +456,9 @@ impl<T: ResourceTracker> Heap<T> {
                 // Notify tracker of freed memory
                 if let Some(ref data) = value.data {
                     self.tracker.on_free(|| data.py_estimate_size());
+                    if let HeapData::File(file) = data {
+                        self.dropped_files.extend(file.open_handle());
+                    }
                 }
 
                 // Collect child IDs and push onto work stack for iterative processing
@@ -463,6 +475,23 @@ impl<T: ResourceTracker> Heap<T> {
         }
     }
 
+    /// Takes the handle of a file that was freed without being closed.
+    pub(crate) fn take_dropped_file(&mut self) -> Option<i64> {
+        self.dropped_files.pop()
+    }
+
+    /// Marks a file that hasn't been closed as closed and returns its handle, so the VM can
+    /// close the files still open at the end of the program.
+    pub(crate) fn close_open_file(&mut self) -> Option<i64> {
+        self.entries
+            .iter_mut()
+            .flatten()
+            .find_map(|entry| match &mut entry.data {
+                Some(HeapData::File(file)) => file.close(),
+                _ => None,
+            })
+    }
+
     /// Returns an immutable reference to the heap data stored at the given ID.
     ///
     /// # Panics
@@ -519,8 +548,8 @@ impl<T: ResourceTracker> Heap<T> {
             HashState::Unknown => {}
         }
 
-        // Handle Cell specially - uses identity-based hashing (like Python cell objects)
-        if let Some(HeapData::Cell(_)) = &entry.data {
+        // Handle Cell and object() instances specially - they use identity-based hashing
+        if let Some(HeapData::Cell(_) | HeapData::Object(_)) = &entry.data {
             let mut hasher = DefaultHasher::new();
             id.hash(&mut hasher);
             let hash = hasher.finish();
@@ -842,6 +871,9 @@ impl<T: ResourceTracker> Heap<T> {
                 // Notify tracker of freed memory
                 if let Some(ref data) = value.data {
                     self.tracker.on_free(|| data.py_estimate_size());
+                    if let HeapData::File(file) = data {
+                        self.dropped_files.extend(file.open_handle());
+                    }
                 }
 
                 self.free_list.push(HeapId(id));
@@ -1050,6 +1082,23 @@ fn collect_child_ids(data: &HeapData, work_list: &mut Vec<HeapId>) {
                 }
             }
         }
+        HeapData::CsvWriter(writer) => {
+            // Add the target stream and any DictWriter values that are heap references
+            work_list.push(writer.target());
+            for value in writer.values() {
+                if let Value::Ref(id) = value {
+                    work_list.push(*id);
+                }
+            }
+        }
+        HeapData::OperatorCallable(callable) => {
+            // Add stored items and method arguments that are heap references
+            for value in callable.values() {
+                if let Value::Ref(id) = value {
+                    work_list.push(*id);
+                }
+            }
+        }
         // Leaf types with no heap references
         _ => {}
     }
@@ -18,8 +18,10 @@ use crate::{
     heap::{Heap, HeapId},
     intern::{FunctionId, Interns},
     types::{
-        Bytes, Dataclass, Dict, DictItemsView, DictKeysView, DictValuesView, FrozenSet, List, LongInt, Module,
-        MontyIter, NamedTuple, Path, PyTrait, Range, ReMatch, RePattern, Set, Slice, Str, Tuple, Type,
+        Bytes, CsvDialect, CsvSniffer, CsvWriter, Dataclass, Dict, DictItemsView, DictKeysView, DictValuesView, File,
+        FrozenSet, HashObject, List, LongInt, Module, MontyIter, NamedTuple, Object, OperatorCallable, Path, PyTrait,
+        Range, ReMatch, RePattern, SequenceMatcher, Set, Slice, Str, StringIO, Struct, Template, Tuple, Type, Uuid,
+        uuid::uuid_str,
     },
     value::{EitherStr, Value},
 };
@@ -113,6 +115,56 @@ pub(crate) enum HeapData {
     /// Contains the matched text, capture groups, positions, and input string.
     /// Leaf type: no heap references, not GC-tracked.
     ReMatch(ReMatch),
+    /// A hash object from `hashlib` or an HMAC object from `hmac`.
+    ///
+    /// Holds the incremental digest state.
+    /// Leaf type: no heap references, not GC-tracked.
+    Hash(Box<HashObject>),
+    /// A `string.Template` object.
+    ///
+    /// Leaf type: no heap references, not GC-tracked.
+    Template(Template),
+    /// A `difflib.SequenceMatcher` object.
+    ///
+    /// Leaf type: no heap references, not GC-tracked.
+    SequenceMatcher(Box<SequenceMatcher>),
+    /// An in-memory text stream from `io.StringIO`.
+    ///
+    /// Leaf type: no heap references, not GC-tracked.
+    StringIO(StringIO),
+    /// A file from `open()` or `Path.open()`, backed by a host handle.
+    ///
+    /// Leaf type: no heap references, not GC-tracked.
+    File(File),
+    /// A `csv.Dialect` object describing how to read and write CSV data.
+    ///
+    /// Leaf type: no heap references, not GC-tracked.
+    CsvDialect(Box<CsvDialect>),
+    /// A `csv.writer()` or `csv.DictWriter` object.
+    ///
+    /// Holds a reference to the target stream, plus the fieldnames and restval
+    /// of a `DictWriter`, so it is GC-tracked.
+    CsvWriter(Box<CsvWriter>),
+    /// A `csv.Sniffer` object.
+    ///
+    /// Leaf type: no heap references, not GC-tracked.
+    CsvSniffer(CsvSniffer),
+    /// An `operator.itemgetter`, `attrgetter` or `methodcaller` object.
+    ///
+    /// Holds the items, or method arguments, it was created with, so it is GC-tracked.
+    OperatorCallable(OperatorCallable),
+    /// A compiled `struct.Struct` format.
+    ///
+    /// Leaf type: no heap references, not GC-tracked.
+    Struct(Struct),
+    /// A `uuid.UUID` value.
+    ///
+    /// Leaf type: no heap references, not GC-tracked.
+    Uuid(Uuid),
+    /// An `object()` instance, usually a sentinel.
+    ///
+    /// Leaf type: no heap references, not GC-tracked.
+    Object(Object),
     /// Reference to an external function whose name was not found in the intern table.
     ///
     /// Created when the host resolves a `NameLookup` to a callable whose name does not
@@ -152,6 +204,8 @@ impl HeapData {
                 | Self::Module(_)
                 | Self::Coroutine(_)
                 | Self::GatherFuture(_)
+                | Self::CsvWriter(_)
+                | Self::OperatorCallable(_)
         )
     }
 
@@ -191,6 +245,9 @@ impl HeapData {
                         .iter()
                         .any(|r| r.as_ref().is_some_and(|v| matches!(v, Value::Ref(_))))
             }
+            // Writers always reference their target stream
+            Self::CsvWriter(_) => true,
+            Self::OperatorCallable(callable) => callable.has_refs(),
             // Leaf types cannot have refs
             _ => false,
         }
@@ -234,6 +291,18 @@ impl HeapData {
             Self::Path(p) => HeapDataMut::Path(p),
             Self::ReMatch(m) => HeapDataMut::ReMatch(m),
             Self::RePattern(p) => HeapDataMut::RePattern(p),
+            Self::Hash(h) => HeapDataMut::Hash(h),
+            Self::Template(t) => HeapDataMut::Template(t),
+            Self::SequenceMatcher(m) => HeapDataMut::SequenceMatcher(m),
+            Self::StringIO(s) => HeapDataMut::StringIO(s),
+            Self::File(file) => HeapDataMut::File(file),
+            Self::CsvDialect(d) => HeapDataMut::CsvDialect(d),
+            Self::CsvWriter(w) => HeapDataMut::CsvWriter(w),
+            Self::CsvSniffer(s) => HeapDataMut::CsvSniffer(s),
+            Self::OperatorCallable(c) => HeapDataMut::OperatorCallable(c),
+            Self::Struct(s) => HeapDataMut::Struct(s),
+            Self::Uuid(u) => HeapDataMut::Uuid(u),
+            Self::Object(o) => HeapDataMut::Object(o),
             Self::ExtFunction(s) => HeapDataMut::ExtFunction(s),
         }
     }
@@ -324,6 +393,32 @@ pub(crate) enum HeapDataMut<'a> {
     /// Wraps a compiled regex with the original pattern string and flags.
     /// Custom serde serializes only the pattern and flags, recompiling on deserialize.
     RePattern(&'a mut RePattern),
+    /// A hash object from `hashlib` or an HMAC object from `hmac`.
+    ///
+    /// All state is owned (no heap references), so reference counting is trivial.
+    Hash(&'a mut HashObject),
+    /// A `string.Template` object; owns its template string.
+    Template(&'a mut Template),
+    /// A `difflib.SequenceMatcher` object; caches its matching blocks.
+    SequenceMatcher(&'a mut SequenceMatcher),
+    /// An `io.StringIO` stream; owns its buffer.
+    StringIO(&'a mut StringIO),
+    /// A file from `open()`; owns its host handle.
+    File(&'a mut File),
+    /// A `csv.Dialect` object; owns its formatting parameters.
+    CsvDialect(&'a mut CsvDialect),
+    /// A `csv.writer()` or `csv.DictWriter` object; references its target stream.
+    CsvWriter(&'a mut CsvWriter),
+    /// A `csv.Sniffer` object; stateless.
+    CsvSniffer(&'a mut CsvSniffer),
+    /// An `operator.itemgetter`, `attrgetter` or `methodcaller` object; references its arguments.
+    OperatorCallable(&'a mut OperatorCallable),
+    /// A `struct.Struct` object; owns its parsed format.
+    Struct(&'a mut Struct),
+    /// A `uuid.UUID` object; owns its value.
+    Uuid(&'a mut Uuid),
+    /// An `object()` instance; stateless.
+    Object(&'a mut Object),
     /// Reference to an external function where the name was not interned.
     ///
     /// Created when the host resolves a name lookup to a callable whose name
@@ -479,6 +574,13 @@ impl HeapDataMut<'_> {
                 path.as_str().hash(&mut hasher);
                 Ok(Some(hasher.finish()))
             }
+            // UUID is immutable and hashable
+            Self::Uuid(uuid) => {
+                let mut hasher = DefaultHasher::new();
+                discriminant(self).hash(&mut hasher);
+                uuid.value().hash(&mut hasher);
+                Ok(Some(hasher.finish()))
+            }
             // LongInt is immutable and hashable
             Self::LongInt(li) => Ok(Some(li.hash())),
             // ExtFunction is hashable by name
@@ -529,6 +631,18 @@ macro_rules! impl_py_trait_dispatch {
                     Self::Path(p) => p.py_type(heap),
                     Self::ReMatch(m) => m.py_type(heap),
                     Self::RePattern(p) => p.py_type(heap),
+                    Self::Hash(h) => h.py_type(heap),
+                    Self::Template(t) => t.py_type(heap),
+                    Self::SequenceMatcher(m) => m.py_type(heap),
+                    Self::StringIO(s) => s.py_type(heap),
+                    Self::File(file) => file.py_type(heap),
+                    Self::CsvDialect(d) => d.py_type(heap),
+                    Self::CsvWriter(w) => w.py_type(heap),
+                    Self::CsvSniffer(s) => s.py_type(heap),
+                    Self::OperatorCallable(c) => c.py_type(heap),
+                    Self::Struct(s) => s.py_type(heap),
+                    Self::Uuid(u) => u.py_type(heap),
+                    Self::Object(o) => o.py_type(heap),
                 }
             }
 
@@ -567,6 +681,18 @@ macro_rules! impl_py_trait_dispatch {
                     Self::Path(p) => p.py_estimate_size(),
                     Self::ReMatch(m) => m.py_estimate_size(),
                     Self::RePattern(p) => p.py_estimate_size(),
+                    Self::Hash(h) => h.py_estimate_size(),
+                    Self::Template(t) => t.py_estimate_size(),
+                    Self::SequenceMatcher(m) => m.py_estimate_size(),
+                    Self::StringIO(s) => s.py_estimate_size(),
+                    Self::File(file) => file.py_estimate_size(),
+                    Self::CsvDialect(d) => d.py_estimate_size(),
+                    Self::CsvWriter(w) => w.py_estimate_size(),
+                    Self::CsvSniffer(s) => s.py_estimate_size(),
+                    Self::OperatorCallable(c) => c.py_estimate_size(),
+                    Self::Struct(s) => s.py_estimate_size(),
+                    Self::Uuid(u) => u.py_estimate_size(),
+                    Self::Object(o) => o.py_estimate_size(),
                     Self::ExtFunction(s) => std::mem::size_of::<String>() + s.len(),
                 }
             }
@@ -639,18 +765,30 @@ macro_rules! impl_py_trait_dispatch {
                     (Self::Slice(a), Self::Slice(b)) => a.py_eq(b, vm),
                     // Path equality
                     (Self::Path(a), Self::Path(b)) => a.py_eq(b, vm),
+                    (Self::Uuid(a), Self::Uuid(b)) => a.py_eq(b, vm),
                     // ReMatch objects are not comparable
                     (Self::ReMatch(a), Self::ReMatch(b)) => a.py_eq(b, vm),
                     // RePattern equality by pattern string and flags
                     (Self::RePattern(a), Self::RePattern(b)) => a.py_eq(b, vm),
-                    // Cells, Exceptions, Iterators, Modules, and async types compare by identity only
+                    // Cells, Exceptions, Iterators, Modules, hash objects, templates, and async types compare by identity only
                     // (handled at Value level via HeapId comparison)
                     (Self::Cell(_), Self::Cell(_))
                     | (Self::Exception(_), Self::Exception(_))
                     | (Self::Iter(_), Self::Iter(_))
                     | (Self::Module(_), Self::Module(_))
                     | (Self::Coroutine(_), Self::Coroutine(_))
-                    | (Self::GatherFuture(_), Self::GatherFuture(_)) => Ok(false),
+                    | (Self::GatherFuture(_), Self::GatherFuture(_))
+                    | (Self::Hash(_), Self::Hash(_))
+                    | (Self::Template(_), Self::Template(_))
+                    | (Self::SequenceMatcher(_), Self::SequenceMatcher(_))
+                    | (Self::StringIO(_), Self::StringIO(_))
+                    | (Self::File(_), Self::File(_))
+                    | (Self::CsvDialect(_), Self::CsvDialect(_))
+                    | (Self::CsvWriter(_), Self::CsvWriter(_))
+                    | (Self::CsvSniffer(_), Self::CsvSniffer(_))
+                    | (Self::OperatorCallable(_), Self::OperatorCallable(_))
+                    | (Self::Struct(_), Self::Struct(_))
+                    | (Self::Object(_), Self::Object(_)) => Ok(false),
                     _ => Ok(false), // Different types are never equal
                 }
             }
@@ -664,6 +802,8 @@ macro_rules! impl_py_trait_dispatch {
                     (Self::Str(a), Self::Str(b)) => a.py_cmp(b, vm),
                     (Self::Bytes(a), Self::Bytes(b)) => a.py_cmp(b, vm),
                     (Self::Tuple(a), Self::Tuple(b)) => a.py_cmp(b, vm),
+                    (Self::Uuid(a), Self::Uuid(b)) => a.py_cmp(b, vm),
+                    (Self::Path(a), Self::Path(b)) => a.py_cmp(b, vm),
                     _ => Ok(None),
                 }
             }
@@ -717,6 +857,8 @@ macro_rules! impl_py_trait_dispatch {
                             result.py_dec_ref_ids(stack);
                         }
                     }
+                    Self::CsvWriter(w) => w.py_dec_ref_ids(stack),
+                    Self::OperatorCallable(c) => c.py_dec_ref_ids(stack),
                     // other types have no nested heap references
                     _ => {}
                 }
@@ -749,6 +891,18 @@ macro_rules! impl_py_trait_dispatch {
                     Self::Path(p) => p.py_bool(vm),
                     Self::ReMatch(m) => m.py_bool(vm),
                     Self::RePattern(p) => p.py_bool(vm),
+                    Self::Hash(h) => h.py_bool(vm),
+                    Self::Template(t) => t.py_bool(vm),
+                    Self::SequenceMatcher(m) => m.py_bool(vm),
+                    Self::StringIO(s) => s.py_bool(vm),
+                    Self::File(file) => file.py_bool(vm),
+                    Self::CsvDialect(d) => d.py_bool(vm),
+                    Self::CsvWriter(w) => w.py_bool(vm),
+                    Self::CsvSniffer(s) => s.py_bool(vm),
+                    Self::OperatorCallable(c) => c.py_bool(vm),
+                    Self::Struct(s) => s.py_bool(vm),
+                    Self::Uuid(u) => u.py_bool(vm),
+                    Self::Object(o) => o.py_bool(vm),
                 }
             }
 
@@ -793,6 +947,18 @@ macro_rules! impl_py_trait_dispatch {
                     Self::Path(p) => p.py_repr_fmt(f, vm, heap_ids),
                     Self::ReMatch(m) => m.py_repr_fmt(f, vm, heap_ids),
                     Self::RePattern(p) => p.py_repr_fmt(f, vm, heap_ids),
+                    Self::Hash(h) => h.py_repr_fmt(f, vm, heap_ids),
+                    Self::Template(t) => t.py_repr_fmt(f, vm, heap_ids),
+                    Self::SequenceMatcher(m) => m.py_repr_fmt(f, vm, heap_ids),
+                    Self::StringIO(s) => s.py_repr_fmt(f, vm, heap_ids),
+                    Self::File(file) => file.py_repr_fmt(f, vm, heap_ids),
+                    Self::CsvDialect(d) => d.py_repr_fmt(f, vm, heap_ids),
+                    Self::CsvWriter(w) => w.py_repr_fmt(f, vm, heap_ids),
+                    Self::CsvSniffer(s) => s.py_repr_fmt(f, vm, heap_ids),
+                    Self::OperatorCallable(c) => c.py_repr_fmt(f, vm, heap_ids),
+                    Self::Struct(s) => s.py_repr_fmt(f, vm, heap_ids),
+                    Self::Uuid(u) => u.py_repr_fmt(f, vm, heap_ids),
+                    Self::Object(o) => o.py_repr_fmt(f, vm, heap_ids),
                     Self::ExtFunction(name) => write!(f, "<function '{name}' external>"),
                 }
             }
@@ -807,6 +973,8 @@ macro_rules! impl_py_trait_dispatch {
                     Self::Exception(e) => Cow::Owned(e.py_str()),
                     // Paths return the path string without the PosixPath() wrapper
                     Self::Path(p) => Cow::Owned(p.as_str().to_owned()),
+                    // UUIDs return the hyphenated hex form without the UUID() wrapper
+                    Self::Uuid(u) => Cow::Owned(uuid_str(u.value())),
                     // All other types use repr
                     _ => self.py_repr(vm),
                 }
@@ -922,6 +1090,14 @@ macro_rules! impl_py_trait_dispatch {
                     Self::Module(m) => m.py_call_attr(self_id, vm, attr, args),
                     Self::ReMatch(m) => m.py_call_attr(self_id, vm, attr, args),
                     Self::RePattern(p) => p.py_call_attr(self_id, vm, attr, args),
+                    Self::Hash(h) => h.py_call_attr(self_id, vm, attr, args),
+                    Self::Template(t) => t.py_call_attr(self_id, vm, attr, args),
+                    Self::SequenceMatcher(m) => m.py_call_attr(self_id, vm, attr, args),
+                    Self::StringIO(s) => s.py_call_attr(self_id, vm, attr, args),
+                    Self::File(file) => file.py_call_attr(self_id, vm, attr, args),
+                    Self::CsvWriter(w) => w.py_call_attr(self_id, vm, attr, args),
+                    Self::CsvSniffer(s) => s.py_call_attr(self_id, vm, attr, args),
+                    Self::Struct(s) => s.py_call_attr(self_id, vm, attr, args),
                     _ => Err(ExcType::attribute_error(
                         self.py_type(vm.heap),
                         attr.as_str(vm.interns),
@@ -973,6 +1149,14 @@ macro_rules! impl_py_trait_dispatch {
                     Self::Path(p) => p.py_getattr(attr, vm),
                     Self::ReMatch(m) => m.py_getattr(attr, vm),
                     Self::RePattern(p) => p.py_getattr(attr, vm),
+                    Self::Hash(h) => h.py_getattr(attr, vm),
+                    Self::Template(t) => t.py_getattr(attr, vm),
+                    Self::StringIO(s) => s.py_getattr(attr, vm),
+                    Self::File(file) => file.py_getattr(attr, vm),
+                    Self::CsvDialect(d) => d.py_getattr(attr, vm),
+                    Self::CsvWriter(w) => w.py_getattr(attr, vm),
+                    Self::Struct(s) => s.py_getattr(attr, vm),
+                    Self::Uuid(u) => u.py_getattr(attr, vm),
                     // All other types don't support attribute access via py_getattr
                     _ => Ok(None),
                 }
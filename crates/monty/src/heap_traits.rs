@@ -84,7 +84,7 @@ impl<U: DropWithHeap> DropWithHeap for std::vec::Drain<'_, U> {
     }
 }
 
-impl<const N: usize> DropWithHeap for [Value; N] {
+impl<U: DropWithHeap, const N: usize> DropWithHeap for [U; N] {
     fn drop_with_heap<H: ContainsHeap>(self, heap: &mut H) {
         for value in self {
             value.drop_with_heap(heap);
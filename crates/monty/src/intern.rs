@@ -69,7 +69,7 @@ static ASCII_STRS: LazyLock<[&'static str; 128]> = LazyLock::new(|| {
 });
 
 /// Static string values which are known at compile time and don't need to be interned.
-#[repr(u8)]
+#[repr(u16)]
 #[derive(
     Debug, Clone, Copy, FromRepr, EnumString, IntoStaticStr, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize,
 )]
@@ -197,6 +197,8 @@ pub enum StaticStrings {
     Platform,
     Stdout,
     Stderr,
+    Argv,
+    Exit,
     Major,
     Minor,
     Micro,
@@ -289,11 +291,63 @@ pub enum StaticStrings {
     Os,
     Getenv,
     Environ,
+    Urandom,
+    Walk,
     Default,
+    Getcwd,
+    Listdir,
+    Makedirs,
+    /// `os.fspath()`, as opposed to `__fspath__`
+    #[strum(serialize = "fspath")]
+    FspathFunc,
+    Sep,
+    Curdir,
+    Pardir,
+    Linesep,
+    /// `os.name`
+    Posix,
+    /// `os.pardir`
+    #[strum(serialize = "..")]
+    ParentDir,
+    /// `os.path`, the attribute holding the `posixpath` module
+    #[strum(serialize = "path")]
+    PathAttr,
+    Parents,
+    ExistOk,
+    FollowSymlinks,
+
+    // ==========================
+    // os.path module strings
+    /// `import os.path` / `from os.path import ...`
+    #[strum(serialize = "os.path")]
+    OsPath,
+    Posixpath,
+    Basename,
+    Dirname,
+    Splitext,
+    Isabs,
+    Normpath,
+    Normcase,
+    Abspath,
+    Realpath,
+    Lexists,
+    Isfile,
+    Isdir,
+    Islink,
+    Getsize,
+    Getmtime,
+
+    // ==========================
+    // shutil module strings (`copy` reuses `Copy`)
+    Shutil,
+    Copytree,
+    Rmtree,
 
     // ==========================
     // Exception attributes
     Args,
+    /// `SystemExit.code`
+    Code,
 
     // ==========================
     // Type attributes
@@ -305,14 +359,23 @@ pub enum StaticStrings {
     Pathlib,
     #[strum(serialize = "Path")]
     PathClass,
+    #[strum(serialize = "PurePath")]
+    PurePathClass,
+    #[strum(serialize = "PurePosixPath")]
+    PurePosixPathClass,
+    #[strum(serialize = "PosixPath")]
+    PosixPathClass,
 
-    // Path properties (pure - no I/O)
+    // Path properties (pure - no I/O); `parents` reuses `Parents`
     Name,
     Parent,
     Stem,
     Suffix,
     Suffixes,
     Parts,
+    Anchor,
+    Root,
+    Drive,
 
     // Path pure methods (no I/O)
     IsAbsolute,
@@ -323,6 +386,18 @@ pub enum StaticStrings {
     AsPosix,
     #[strum(serialize = "__fspath__")]
     Fspath,
+    RelativeTo,
+    IsRelativeTo,
+    FullMatch,
+    WithSegments,
+    WalkUp,
+    CaseSensitive,
+    // `match` reuses `Match`
+
+    // Path methods and classmethods that need the host's environment
+    Expanduser,
+    Home,
+    Cwd,
 
     // Path filesystem methods (require OsAccess - yield external calls)
     Exists,
@@ -336,6 +411,9 @@ pub enum StaticStrings {
     Iterdir,
     Resolve,
     Absolute,
+    Glob,
+    Rglob,
+    Samefile,
 
     // Path write methods (require OsAccess - yield external calls)
     WriteText,
@@ -344,6 +422,7 @@ pub enum StaticStrings {
     Unlink,
     Rmdir,
     Rename,
+    Touch,
 
     // Slice attributes
     Start,
@@ -508,6 +587,422 @@ pub enum StaticStrings {
     Finditer,
     /// `match.groupdict()` method
     Groupdict,
+    /// `re.subn()` / `pattern.subn()` method
+    Subn,
+    /// `match.expand()` method
+    Expand,
+    /// `match.lastindex`
+    Lastindex,
+    /// `match.lastgroup`
+    Lastgroup,
+    /// `match.regs`
+    Regs,
+    /// `pattern.groupindex`
+    Groupindex,
+    /// `re.VERBOSE` flag
+    #[strum(serialize = "VERBOSE")]
+    VerboseFlag,
+    /// `re.X` flag, alias
+    #[strum(serialize = "X")]
+    X,
+    /// `re.UNICODE` flag
+    #[strum(serialize = "UNICODE")]
+    UnicodeFlag,
+    /// `re.U` flag, alias
+    #[strum(serialize = "U")]
+    U,
+
+    // ==========================
+    // binascii module strings
+    Binascii,
+    Hexlify,
+    Unhexlify,
+    B2aHex,
+    A2bHex,
+    B2aBase64,
+    A2bBase64,
+    Crc32,
+    /// `binascii.Error` exception
+    #[strum(serialize = "Error")]
+    ErrorClass,
+
+    // ==========================
+    // base64 module strings
+    Base64,
+    B64encode,
+    B64decode,
+    StandardB64encode,
+    StandardB64decode,
+    UrlsafeB64encode,
+    UrlsafeB64decode,
+    B32encode,
+    B32decode,
+    B16encode,
+    B16decode,
+
+    // ==========================
+    // hashlib module strings
+    Hashlib,
+    New,
+    Md5,
+    Sha1,
+    Sha224,
+    Sha256,
+    Sha384,
+    Sha512,
+    Blake2b,
+    // hash object methods and attributes (also uses shared: UPDATE, COPY, NAME)
+    Digest,
+    Hexdigest,
+    DigestSize,
+    BlockSize,
+
+    // ==========================
+    // hmac module strings
+    Hmac,
+    CompareDigest,
+    /// `hmac.HMAC` class
+    #[strum(serialize = "HMAC")]
+    HmacClass,
+
+    // ==========================
+    // string module strings
+    // The module name itself is shared with `match.string` (STRING_ATTR)
+    AsciiLetters,
+    AsciiLowercase,
+    AsciiUppercase,
+    Digits,
+    Hexdigits,
+    Octdigits,
+    Punctuation,
+    Printable,
+    Whitespace,
+    #[strum(serialize = "abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ")]
+    AsciiLettersValue,
+    #[strum(serialize = "abcdefghijklmnopqrstuvwxyz")]
+    AsciiLowercaseValue,
+    #[strum(serialize = "ABCDEFGHIJKLMNOPQRSTUVWXYZ")]
+    AsciiUppercaseValue,
+    #[strum(serialize = "0123456789")]
+    DigitsValue,
+    #[strum(serialize = "0123456789abcdefABCDEF")]
+    HexdigitsValue,
+    #[strum(serialize = "01234567")]
+    OctdigitsValue,
+    #[strum(serialize = "!\"#$%&'()*+,-./:;<=>?@[\\]^_`{|}~")]
+    PunctuationValue,
+    #[strum(
+        serialize = "0123456789abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ!\"#$%&'()*+,-./:;<=>?@[\\]^_`{|}~ \t\n\r\x0b\x0c"
+    )]
+    PrintableValue,
+    #[strum(serialize = " \t\n\r\x0b\x0c")]
+    WhitespaceValue,
+    /// `string.Template` class
+    #[strum(serialize = "Template")]
+    TemplateClass,
+    // Template methods and attributes
+    Substitute,
+    SafeSubstitute,
+    Template,
+
+    // ==========================
+    // textwrap module strings
+    Textwrap,
+    Dedent,
+    Indent,
+    Wrap,
+    Fill,
+    Shorten,
+
+    // ==========================
+    // difflib module strings
+    Difflib,
+    UnifiedDiff,
+    GetCloseMatches,
+    /// `difflib.SequenceMatcher` class
+    #[strum(serialize = "SequenceMatcher")]
+    SequenceMatcherClass,
+    // SequenceMatcher methods
+    Ratio,
+    QuickRatio,
+    RealQuickRatio,
+    FindLongestMatch,
+    GetMatchingBlocks,
+    GetOpcodes,
+    GetGroupedOpcodes,
+    /// `Match.size` field of `find_longest_match()` results
+    Size,
+    // opcode tags (`replace` and `insert` are shared with the str/list methods)
+    Delete,
+    Equal,
+
+    // ==========================
+    // unicodedata module strings
+    Unicodedata,
+    Normalize,
+    IsNormalized,
+
+    // ==========================
+    // io module strings
+    Io,
+    /// `io.StringIO` class
+    #[strum(serialize = "StringIO")]
+    StringIOClass,
+    /// `io.UnsupportedOperation` exception
+    #[strum(serialize = "UnsupportedOperation")]
+    UnsupportedOperation,
+    // StringIO methods and attributes
+    Close,
+    Closed,
+    Write,
+    Writelines,
+    Getvalue,
+    Read,
+    Readline,
+    Readlines,
+    Seek,
+    Tell,
+    Truncate,
+    // file object methods and attributes (shared with StringIO where the names overlap)
+    Open,
+    Encoding,
+    Flush,
+    Readable,
+    Writable,
+    Seekable,
+    #[strum(serialize = "__enter__")]
+    DunderEnter,
+    #[strum(serialize = "__exit__")]
+    DunderExit,
+
+    // ==========================
+    // csv module strings
+    Csv,
+    Reader,
+    Writer,
+    /// `csv.DictReader` class
+    #[strum(serialize = "DictReader")]
+    DictReader,
+    /// `csv.DictWriter` class
+    #[strum(serialize = "DictWriter")]
+    DictWriter,
+    /// `csv.Sniffer` class
+    #[strum(serialize = "Sniffer")]
+    Sniffer,
+    GetDialect,
+    ListDialects,
+    #[strum(serialize = "QUOTE_MINIMAL")]
+    QuoteMinimal,
+    #[strum(serialize = "QUOTE_ALL")]
+    QuoteAll,
+    #[strum(serialize = "QUOTE_NONNUMERIC")]
+    QuoteNonnumeric,
+    #[strum(serialize = "QUOTE_NONE")]
+    QuoteNone,
+    #[strum(serialize = "QUOTE_STRINGS")]
+    QuoteStrings,
+    #[strum(serialize = "QUOTE_NOTNULL")]
+    QuoteNotnull,
+    // built-in dialect objects
+    Excel,
+    ExcelTab,
+    UnixDialect,
+    // writer and Sniffer methods (`dialect` is also the writer attribute)
+    Writerow,
+    Writerows,
+    Writeheader,
+    Sniff,
+    HasHeader,
+    Dialect,
+    // Dialect attributes
+    Delimiter,
+    Quotechar,
+    Escapechar,
+    Doublequote,
+    Skipinitialspace,
+    Lineterminator,
+    Quoting,
+    Strict,
+
+    // ==========================
+    // copy module strings (the module name reuses `Copy`)
+    Deepcopy,
+
+    // ==========================
+    // heapq module strings
+    Heapq,
+    Heappush,
+    Heappop,
+    Heapify,
+    Heappushpop,
+    Heapreplace,
+    Nlargest,
+    Nsmallest,
+    Merge,
+
+    // ==========================
+    // bisect module strings
+    Bisect,
+    BisectLeft,
+    BisectRight,
+    Insort,
+    InsortLeft,
+    InsortRight,
+
+    // ==========================
+    // operator module strings (`add`, `sub`, `pow` and `index` are shared with methods)
+    Operator,
+    Itemgetter,
+    Attrgetter,
+    Methodcaller,
+    Mul,
+    Truediv,
+    Floordiv,
+    Mod,
+    Neg,
+    Pos,
+    Abs,
+    Invert,
+    Inv,
+    Lshift,
+    Rshift,
+    Xor,
+    #[strum(serialize = "and_")]
+    OperatorAnd,
+    #[strum(serialize = "or_")]
+    OperatorOr,
+    #[strum(serialize = "not_")]
+    OperatorNot,
+    Truth,
+    #[strum(serialize = "is_")]
+    OperatorIs,
+    IsNot,
+    Eq,
+    Ne,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+    Contains,
+    Getitem,
+    Concat,
+    #[strum(serialize = "countOf")]
+    CountOf,
+    #[strum(serialize = "indexOf")]
+    IndexOf,
+
+    // ==========================
+    // statistics module strings
+    Statistics,
+    /// `statistics.StatisticsError` exception
+    #[strum(serialize = "StatisticsError")]
+    StatisticsError,
+    Mean,
+    Fmean,
+    Median,
+    MedianLow,
+    MedianHigh,
+    Mode,
+    Multimode,
+    Variance,
+    Pvariance,
+    Stdev,
+    Pstdev,
+    Quantiles,
+
+    // ==========================
+    // struct module strings
+    Struct,
+    /// `struct.Struct` class
+    #[strum(serialize = "Struct")]
+    StructClass,
+    Format,
+    Pack,
+    Unpack,
+    UnpackFrom,
+    IterUnpack,
+    Calcsize,
+
+    // ==========================
+    // pprint module strings
+    Pprint,
+    Pformat,
+    Pp,
+    Saferepr,
+    Isreadable,
+    Isrecursive,
+
+    // ==========================
+    // reprlib module strings
+    Reprlib,
+    Repr,
+
+    // ==========================
+    // uuid module strings
+    Uuid,
+    /// `uuid.UUID` class
+    #[strum(serialize = "UUID")]
+    UuidClass,
+    #[strum(serialize = "uuid3")]
+    Uuid3,
+    #[strum(serialize = "uuid4")]
+    Uuid4,
+    #[strum(serialize = "uuid5")]
+    Uuid5,
+    #[strum(serialize = "NAMESPACE_DNS")]
+    NamespaceDns,
+    #[strum(serialize = "NAMESPACE_URL")]
+    NamespaceUrl,
+    #[strum(serialize = "NAMESPACE_OID")]
+    NamespaceOid,
+    #[strum(serialize = "NAMESPACE_X500")]
+    NamespaceX500,
+    #[strum(serialize = "RESERVED_NCS")]
+    ReservedNcs,
+    #[strum(serialize = "RFC_4122")]
+    Rfc4122,
+    #[strum(serialize = "RESERVED_MICROSOFT")]
+    ReservedMicrosoft,
+    #[strum(serialize = "RESERVED_FUTURE")]
+    ReservedFuture,
+    #[strum(serialize = "reserved for NCS compatibility")]
+    ReservedNcsValue,
+    #[strum(serialize = "specified in RFC 4122")]
+    Rfc4122Value,
+    #[strum(serialize = "reserved for Microsoft compatibility")]
+    ReservedMicrosoftValue,
+    #[strum(serialize = "reserved for future definition")]
+    ReservedFutureValue,
+
+    // ==========================
+    // zlib module strings
+    Zlib,
+    Compress,
+    Decompress,
+    Adler32,
+    #[strum(serialize = "MAX_WBITS")]
+    MaxWbits,
+    #[strum(serialize = "DEFLATED")]
+    Deflated,
+    #[strum(serialize = "DEF_BUF_SIZE")]
+    DefBufSize,
+    #[strum(serialize = "DEF_MEM_LEVEL")]
+    DefMemLevel,
+    #[strum(serialize = "Z_NO_COMPRESSION")]
+    ZNoCompression,
+    #[strum(serialize = "Z_BEST_SPEED")]
+    ZBestSpeed,
+    #[strum(serialize = "Z_BEST_COMPRESSION")]
+    ZBestCompression,
+    #[strum(serialize = "Z_DEFAULT_COMPRESSION")]
+    ZDefaultCompression,
+
+    // ==========================
+    // gzip module strings
+    Gzip,
+    /// `gzip.BadGzipFile` exception
+    #[strum(serialize = "BadGzipFile")]
+    BadGzipFile,
 }
 
 impl StaticStrings {
@@ -517,7 +1012,7 @@ impl StaticStrings {
     /// (e.g., it's an ASCII char or a dynamically interned string).
     pub fn from_string_id(id: StringId) -> Option<Self> {
         let enum_id = id.0.checked_sub(STATIC_STRING_ID_OFFSET)?;
-        u8::try_from(enum_id).ok().and_then(Self::from_repr)
+        u16::try_from(enum_id).ok().and_then(Self::from_repr)
     }
 }
 
@@ -693,6 +1188,17 @@ impl InternerBuilder {
         }
     }
 
+    /// Returns the `StringId` of a string interned earlier, without interning it.
+    pub fn lookup(&self, s: &str) -> Option<StringId> {
+        if s.len() == 1 {
+            Some(StringId::from_ascii(s.as_bytes()[0]))
+        } else if let Ok(ss) = StaticStrings::from_str(s) {
+            Some(ss.into())
+        } else {
+            self.string_map.get(s).copied()
+        }
+    }
+
     /// Interns bytes, returning its `BytesId`.
     ///
     /// Unlike interns, bytes are not deduplicated (bytes literals are rare).
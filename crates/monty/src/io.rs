@@ -1,8 +1,17 @@
 use std::borrow::Cow;
 
-use crate::exception_public::MontyException;
+use crate::{
+    bytecode::VM,
+    exception_private::{ExcType, RunResult},
+    exception_public::MontyException,
+    heap::{Heap, HeapData, HeapId},
+    heap_data::HeapDataMut,
+    intern::StaticStrings,
+    resource::ResourceTracker,
+    value::{Marker, Value},
+};
 
-/// Output handler for the `print()` builtin function.
+/// Output handler for the `print()` builtin function and `sys.stdout`/`sys.stderr`.
 ///
 /// Provides common output modes as enum variants to avoid trait object overhead
 /// in the typical cases (stdout, disabled, collect). For custom output handling,
@@ -10,15 +19,15 @@ use crate::exception_public::MontyException;
 ///
 /// # Variants
 /// - `Disabled` - Silently discards all output (useful for benchmarking or suppressing output)
-/// - `Stdout` - Writes to standard output (the default behavior)
-/// - `Collect` - Accumulates output into a target `String` for programmatic access
+/// - `Stdout` - Writes to standard output and standard error (the default behavior)
+/// - `Collect` - Accumulates output from both streams into a target `String` for programmatic access
 /// - `Callback` - Delegates to a user-provided [`PrintWriterCallback`] implementation
 pub enum PrintWriter<'a> {
     /// Silently discard all output.
     Disabled,
-    /// Write to standard output.
+    /// Write to standard output, and `sys.stderr` output to standard error.
     Stdout,
-    /// Collect all output into a string.
+    /// Collect all output into a string, interleaving stdout and stderr as a terminal would.
     Collect(&'a mut String),
     /// Delegate to a custom callback.
     Callback(&'a mut dyn PrintWriterCallback),
@@ -78,6 +87,49 @@ impl PrintWriter<'_> {
             Self::Callback(cb) => cb.stdout_push(end),
         }
     }
+
+    /// Writes text to stderr, e.g. one argument of `print(..., file=sys.stderr)`.
+    ///
+    /// Like [`stdout_write`](Self::stdout_write), separators and terminators are
+    /// emitted separately via [`stderr_push`](Self::stderr_push).
+    pub fn stderr_write(&mut self, output: Cow<'_, str>) -> Result<(), MontyException> {
+        match self {
+            Self::Disabled => Ok(()),
+            Self::Stdout => {
+                eprint!("{output}");
+                Ok(())
+            }
+            Self::Collect(buf) => {
+                buf.push_str(&output);
+                Ok(())
+            }
+            Self::Callback(cb) => cb.stderr_write(output),
+        }
+    }
+
+    /// Appends a single character to stderr.
+    pub fn stderr_push(&mut self, end: char) -> Result<(), MontyException> {
+        match self {
+            Self::Disabled => Ok(()),
+            Self::Stdout => {
+                eprint!("{end}");
+                Ok(())
+            }
+            Self::Collect(buf) => {
+                buf.push(end);
+                Ok(())
+            }
+            Self::Callback(cb) => cb.stderr_push(end),
+        }
+    }
+
+    /// Writes the traceback of an uncaught exception to stderr, as CPython does before exiting.
+    ///
+    /// The interpreter never calls this itself, since the exception is returned to the host;
+    /// hosts that want tracebacks in the stderr stream pass the error here.
+    pub fn stderr_traceback(&mut self, exception: &MontyException) -> Result<(), MontyException> {
+        self.stderr_write(format!("{exception}\n").into())
+    }
 }
 
 /// Trait for custom output handling from the `print()` builtin function.
@@ -103,4 +155,79 @@ pub trait PrintWriterCallback {
     /// # Arguments
     /// * `end` - The character to print after the formatted output.
     fn stdout_push(&mut self, end: char) -> Result<(), MontyException>;
+
+    /// Called for text written to stderr, e.g. by `print(..., file=sys.stderr)`.
+    ///
+    /// Follows the same conventions as [`stdout_write`](Self::stdout_write). The default
+    /// forwards to `stdout_write`, so callbacks that don't distinguish the streams still
+    /// receive all output.
+    fn stderr_write(&mut self, output: Cow<'_, str>) -> Result<(), MontyException> {
+        self.stdout_write(output)
+    }
+
+    /// Add a single character to stderr.
+    ///
+    /// The default forwards to [`stdout_push`](Self::stdout_push).
+    fn stderr_push(&mut self, end: char) -> Result<(), MontyException> {
+        self.stdout_push(end)
+    }
+}
+
+/// A writable text stream passed as the `file` argument of `print()` (or `stream` of `pprint()`).
+#[derive(Debug, Clone, Copy)]
+pub(crate) enum OutputStream {
+    /// `sys.stdout`, also used when the argument is omitted or `None`.
+    Stdout,
+    /// `sys.stderr`.
+    Stderr,
+    /// An `io.StringIO` on the heap.
+    StringIO(HeapId),
+}
+
+impl OutputStream {
+    /// Resolves the `param` argument of `func_name()` to a stream.
+    ///
+    /// # Errors
+    /// Raises `TypeError` for anything other than `None`, `sys.stdout`, `sys.stderr` or an `io.StringIO`.
+    pub(crate) fn from_arg(
+        value: Option<&Value>,
+        func_name: &str,
+        param: &str,
+        heap: &Heap<impl ResourceTracker>,
+    ) -> RunResult<Self> {
+        match value {
+            None | Some(Value::None | Value::Marker(Marker(StaticStrings::Stdout))) => Ok(Self::Stdout),
+            Some(Value::Marker(Marker(StaticStrings::Stderr))) => Ok(Self::Stderr),
+            Some(Value::Ref(id)) if matches!(heap.get(*id), HeapData::StringIO(_)) => Ok(Self::StringIO(*id)),
+            Some(other) => Err(ExcType::type_error(format!(
+                "{func_name}() {param} must be None, sys.stdout, sys.stderr or an io.StringIO, not {}",
+                other.py_type(heap)
+            ))),
+        }
+    }
+
+    /// Writes text to the stream.
+    pub(crate) fn write(self, text: Cow<'_, str>, vm: &mut VM<'_, '_, impl ResourceTracker>) -> RunResult<()> {
+        match self {
+            Self::Stdout => vm.print_writer.stdout_write(text)?,
+            Self::Stderr => vm.print_writer.stderr_write(text)?,
+            Self::StringIO(id) => {
+                Heap::with_entry_mut(vm, id, |vm, data| match data {
+                    HeapDataMut::StringIO(buffer) => buffer.write(&text, vm.heap.tracker()),
+                    _ => unreachable!("checked by from_arg"),
+                })?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Writes a single character (a separator or terminator) to the stream.
+    pub(crate) fn push(self, c: char, vm: &mut VM<'_, '_, impl ResourceTracker>) -> RunResult<()> {
+        match self {
+            Self::Stdout => vm.print_writer.stdout_push(c)?,
+            Self::Stderr => vm.print_writer.stderr_push(c)?,
+            Self::StringIO(_) => self.write(c.to_string().into(), vm)?,
+        }
+        Ok(())
+    }
 }
@@ -6,6 +6,7 @@ mod args;
 mod asyncio;
 mod builtins;
 mod bytecode;
+mod digest;
 mod exception_private;
 mod exception_public;
 mod expressions;
@@ -15,12 +16,16 @@ mod heap;
 mod heap_data;
 mod intern;
 mod io;
+mod memory_fs;
 mod modules;
+mod mount_fs;
 mod namespace;
 mod object;
 mod os;
+mod os_handler;
 mod parse;
 mod prepare;
+mod re_syntax;
 mod repl;
 mod resource;
 mod run;
@@ -36,8 +41,10 @@ pub use crate::{
     exception_private::ExcType,
     exception_public::{CodeLoc, MontyException, StackFrame},
     io::{PrintWriter, PrintWriterCallback},
+    memory_fs::MemoryFs,
+    mount_fs::MountFs,
     object::{DictPairs, InvalidInputError, MontyObject},
-    os::{OsFunction, dir_stat, file_stat, stat_result, symlink_stat},
+    os::{OsFunction, OsHandler, dir_stat, file_stat, stat_result, symlink_stat},
     repl::{
         MontyRepl, ReplContinuationMode, ReplFunctionCall, ReplNameLookup, ReplOsCall, ReplProgress,
         ReplResolveFutures, ReplStartError, detect_repl_continuation_mode,
@@ -0,0 +1,834 @@
+//! An in-memory virtual filesystem that answers `OsCall`s.
+//!
+//! `MemoryFs` implements `OsHandler` for every `OsFunction` against a tree of files and
+//! directories held in memory, so Rust hosts can sandbox filesystem access without
+//! implementing each operation themselves:
+//!
+//! ```
+//! use monty::{MemoryFs, MontyObject, MontyRun, NoLimitTracker, PrintWriter, RunProgress};
+//!
+//! let code = "from pathlib import Path\nPath('/data/out.txt').write_text(Path('/data/in.txt').read_text().upper())";
+//! let runner = MontyRun::new(code.to_owned(), "main.py", vec![]).unwrap();
+//! let mut fs = MemoryFs::new().file("/data/in.txt", "hello");
+//!
+//! let mut progress = runner.start(vec![], NoLimitTracker, PrintWriter::Stdout).unwrap();
+//! while let RunProgress::OsCall(call) = progress {
+//!     progress = call.resume_with(&mut fs, PrintWriter::Stdout).unwrap();
+//! }
+//! assert_eq!(progress.into_complete(), Some(MontyObject::Int(5)));
+//! assert_eq!(fs.read("/data/out.txt"), Some(&b"HELLO"[..]));
+//! ```
+//!
+//! Paths are POSIX style, with relative paths resolved against `/`. There are no symlinks,
+//! files and directories carry Unix permission bits (only the owner bits are checked) and
+//! an optional quota limits the total size of all files. The whole filesystem, including
+//! open files, can be serialized with `dump()` and stored alongside a snapshot.
+
+use std::collections::BTreeMap;
+
+use crate::{
+    ExcType, MontyException, MontyObject,
+    os::{OsFunction, OsHandler, dir_stat, file_stat},
+    os_handler::{
+        FsResult, OpenFile, OpenMode, absolute, bad_argument, bad_file_descriptor, components, count, count_i64,
+        decode, file_exists, glob_match, int_arg, is_a_directory, join, kwarg, kwarg_bool, not_a_directory, not_found,
+        os_error, permission_denied, str_arg,
+    },
+    run_progress::ExtFunctionResult,
+};
+
+/// Permissions of files created by Python code or `MemoryFs::file()`.
+const DEFAULT_FILE_MODE: i64 = 0o644;
+/// Permissions of directories created without an explicit mode.
+const DEFAULT_DIR_MODE: i64 = 0o755;
+
+/// An in-memory filesystem, environment and entropy source implementing `OsHandler`.
+///
+/// Build one with `new()` and the builder methods, resume `OsCall`s with
+/// `OsCall::resume_with()`, then inspect the result with `read()`, `exists()` and `is_dir()`.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct MemoryFs {
+    /// The root directory, `/`.
+    root: Node,
+    /// Variables returned by `os.getenv()` and `os.environ`.
+    environ: BTreeMap<String, String>,
+    /// Maximum total size of all files in bytes, `None` for no limit.
+    max_bytes: Option<usize>,
+    /// Modification time reported by `stat()` for every entry.
+    mtime: f64,
+    /// Files opened with `open()`, by handle.
+    open_files: BTreeMap<i64, OpenFile>,
+    /// The last handle returned by `OsFunction::Open`.
+    last_handle: i64,
+    /// splitmix64 state for `os.urandom()`.
+    random_state: u64,
+}
+
+/// A file or directory in a `MemoryFs`.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+enum Node {
+    File { content: Vec<u8>, mode: i64 },
+    Dir { entries: BTreeMap<String, Node>, mode: i64 },
+}
+
+impl Node {
+    fn empty_dir(mode: i64) -> Self {
+        Self::Dir {
+            entries: BTreeMap::new(),
+            mode,
+        }
+    }
+
+    /// Total size of the files in this node, recursively for directories.
+    fn size(&self) -> usize {
+        match self {
+            Self::File { content, .. } => content.len(),
+            Self::Dir { entries, .. } => entries.values().map(Self::size).sum(),
+        }
+    }
+}
+
+impl Default for MemoryFs {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl MemoryFs {
+    /// Creates an empty filesystem containing only the root directory, with no environment
+    /// variables, no quota and every modification time at the Unix epoch.
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            root: Node::empty_dir(DEFAULT_DIR_MODE),
+            environ: BTreeMap::new(),
+            max_bytes: None,
+            mtime: 0.0,
+            open_files: BTreeMap::new(),
+            last_handle: 2,
+            random_state: 0,
+        }
+    }
+
+    /// Adds a file with permissions `0o644`, creating missing parent directories.
+    ///
+    /// # Panics
+    /// Panics if `path` is `/` or one of its parents is a file.
+    #[must_use]
+    pub fn file(self, path: &str, content: impl Into<Vec<u8>>) -> Self {
+        self.file_with_mode(path, content, DEFAULT_FILE_MODE)
+    }
+
+    /// Adds a file with the given permission bits (e.g. `0o444` for a read-only file),
+    /// creating missing parent directories.
+    ///
+    /// # Panics
+    /// Panics if `path` is `/` or one of its parents is a file.
+    #[must_use]
+    pub fn file_with_mode(mut self, path: &str, content: impl Into<Vec<u8>>, mode: i64) -> Self {
+        let content = content.into();
+        self.insert(path, Node::File { content, mode });
+        self
+    }
+
+    /// Adds an empty directory with permissions `0o755`, creating missing parent directories.
+    ///
+    /// # Panics
+    /// Panics if `path` is `/` or one of its parents is a file.
+    #[must_use]
+    pub fn dir(self, path: &str) -> Self {
+        self.dir_with_mode(path, DEFAULT_DIR_MODE)
+    }
+
+    /// Adds an empty directory with the given permission bits (e.g. `0o555` to stop Python
+    /// code creating or deleting entries in it), creating missing parent directories.
+    ///
+    /// # Panics
+    /// Panics if `path` is `/` or one of its parents is a file.
+    #[must_use]
+    pub fn dir_with_mode(mut self, path: &str, mode: i64) -> Self {
+        self.insert(path, Node::empty_dir(mode));
+        self
+    }
+
+    /// Sets an environment variable visible to `os.getenv()` and `os.environ`.
+    #[must_use]
+    pub fn env(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        self.environ.insert(key.into(), value.into());
+        self
+    }
+
+    /// Limits the total size of all files; writes beyond it raise `OSError` with `ENOSPC`.
+    #[must_use]
+    pub fn max_bytes(mut self, limit: usize) -> Self {
+        self.max_bytes = Some(limit);
+        self
+    }
+
+    /// Sets the modification time `stat()` reports, as a Unix timestamp.
+    #[must_use]
+    pub fn mtime(mut self, mtime: f64) -> Self {
+        self.mtime = mtime;
+        self
+    }
+
+    /// Seeds the bytes `os.urandom()` returns.
+    ///
+    /// These come from a deterministic splitmix64 sequence, which makes runs reproducible but
+    /// means they must not be used where real entropy is needed.
+    #[must_use]
+    pub fn seed(mut self, seed: u64) -> Self {
+        self.random_state = seed;
+        self
+    }
+
+    /// Returns the contents of the file at `path`, or `None` if it isn't a file.
+    #[must_use]
+    pub fn read(&self, path: &str) -> Option<&[u8]> {
+        match self.node(&components(path)) {
+            Some(Node::File { content, .. }) => Some(content),
+            _ => None,
+        }
+    }
+
+    /// Returns whether a file or directory exists at `path`.
+    #[must_use]
+    pub fn exists(&self, path: &str) -> bool {
+        self.node(&components(path)).is_some()
+    }
+
+    /// Returns whether `path` is a directory.
+    #[must_use]
+    pub fn is_dir(&self, path: &str) -> bool {
+        matches!(self.node(&components(path)), Some(Node::Dir { .. }))
+    }
+
+    /// Returns the total size of all files in bytes, the amount counted against `max_bytes()`.
+    ///
+    /// A file open for writing counts at its unsaved size while that's larger than what's
+    /// stored, so several handles written at once can't go over the quota together.
+    #[must_use]
+    pub fn used_bytes(&self) -> usize {
+        let pending: usize = self
+            .open_files
+            .values()
+            .filter(|file| file.writable)
+            .map(|file| file.content.len().saturating_sub(self.stored_len(&file.name)))
+            .sum();
+        self.root.size() + pending
+    }
+
+    /// Returns the stored size of the file at `path`, 0 if there is none.
+    fn stored_len(&self, path: &str) -> usize {
+        self.read(path).map_or(0, <[u8]>::len)
+    }
+
+    /// Serializes the filesystem, including open files, to a binary format.
+    ///
+    /// # Errors
+    /// Returns an error if serialization fails.
+    pub fn dump(&self) -> Result<Vec<u8>, postcard::Error> {
+        postcard::to_allocvec(self)
+    }
+
+    /// Deserializes a filesystem from binary format.
+    ///
+    /// # Arguments
+    /// * `bytes` - The serialized filesystem data from `dump()`
+    ///
+    /// # Errors
+    /// Returns an error if deserialization fails.
+    pub fn load(bytes: &[u8]) -> Result<Self, postcard::Error> {
+        postcard::from_bytes(bytes)
+    }
+
+    /// Inserts a node for the builder methods, creating missing parent directories.
+    fn insert(&mut self, path: &str, node: Node) {
+        let parts = components(path);
+        let Some((name, parents)) = parts.split_last() else {
+            panic!("cannot replace the root directory");
+        };
+        let mut dir = &mut self.root;
+        for part in parents {
+            let Node::Dir { entries, .. } = dir else {
+                panic!("a parent of '{path}' is a file");
+            };
+            dir = entries
+                .entry(part.clone())
+                .or_insert_with(|| Node::empty_dir(DEFAULT_DIR_MODE));
+        }
+        let Node::Dir { entries, .. } = dir else {
+            panic!("a parent of '{path}' is a file");
+        };
+        entries.insert(name.clone(), node);
+    }
+
+    fn node(&self, parts: &[String]) -> Option<&Node> {
+        let mut node = &self.root;
+        for part in parts {
+            let Node::Dir { entries, .. } = node else {
+                return None;
+            };
+            node = entries.get(part)?;
+        }
+        Some(node)
+    }
+
+    fn node_mut(&mut self, parts: &[String]) -> Option<&mut Node> {
+        let mut node = &mut self.root;
+        for part in parts {
+            let Node::Dir { entries, .. } = node else {
+                return None;
+            };
+            node = entries.get_mut(part)?;
+        }
+        Some(node)
+    }
+
+    /// Returns the entries of the directory containing `parts`, checking it's writable if `modify` is set.
+    ///
+    /// `target` is the path to name in errors.
+    fn parent_entries_mut(
+        &mut self,
+        parts: &[String],
+        target: &str,
+        modify: bool,
+    ) -> FsResult<&mut BTreeMap<String, Node>> {
+        let Some((_, parent)) = parts.split_last() else {
+            // the root directory can't be created, replaced or removed
+            return Err(permission_denied(target));
+        };
+        match self.node_mut(parent) {
+            None => Err(not_found(target)),
+            Some(Node::File { .. }) => Err(not_a_directory(target)),
+            Some(Node::Dir { mode, .. }) if modify && *mode & 0o200 == 0 => Err(permission_denied(target)),
+            Some(Node::Dir { entries, .. }) => Ok(entries),
+        }
+    }
+
+    /// Returns the contents of a readable file.
+    fn read_file(&self, path: &str) -> FsResult<&[u8]> {
+        match self.node(&components(path)) {
+            None => Err(not_found(path)),
+            Some(Node::Dir { .. }) => Err(is_a_directory(path)),
+            Some(Node::File { mode, .. }) if mode & 0o400 == 0 => Err(permission_denied(path)),
+            Some(Node::File { content, .. }) => Ok(content),
+        }
+    }
+
+    /// Returns the entries of a readable directory.
+    fn read_dir(&self, path: &str) -> FsResult<&BTreeMap<String, Node>> {
+        match self.node(&components(path)) {
+            None => Err(not_found(path)),
+            Some(Node::File { .. }) => Err(not_a_directory(path)),
+            Some(Node::Dir { mode, .. }) if mode & 0o400 == 0 => Err(permission_denied(path)),
+            Some(Node::Dir { entries, .. }) => Ok(entries),
+        }
+    }
+
+    /// Replaces the contents of a file, creating it if its parent directory exists.
+    ///
+    /// Existing files keep their permissions, new ones get `0o644`.
+    fn write_file(&mut self, path: &str, content: Vec<u8>) -> FsResult<()> {
+        let parts = components(path);
+        let existing = match self.node(&parts) {
+            None => None,
+            Some(Node::Dir { .. }) => return Err(is_a_directory(path)),
+            Some(Node::File { mode, .. }) if mode & 0o200 == 0 => return Err(permission_denied(path)),
+            Some(Node::File { content, mode }) => Some((content.len(), *mode)),
+        };
+        self.check_space(existing.map_or(0, |(len, _)| len), content.len(), path)?;
+        let mode = existing.map_or(DEFAULT_FILE_MODE, |(_, mode)| mode);
+        let entries = self.parent_entries_mut(&parts, path, existing.is_none())?;
+        entries.insert(parts[parts.len() - 1].clone(), Node::File { content, mode });
+        Ok(())
+    }
+
+    /// Checks that replacing `old_len` bytes with `new_len` stays within the quota.
+    fn check_space(&self, old_len: usize, new_len: usize, path: &str) -> FsResult<()> {
+        match self.max_bytes {
+            Some(max) if new_len > old_len && self.used_bytes() - old_len + new_len > max => {
+                Err(os_error(ExcType::OSError, 28, "No space left on device", path))
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Performs an OS function, returning its result or the exception to raise.
+    fn call(
+        &mut self,
+        function: OsFunction,
+        args: &[MontyObject],
+        kwargs: &[(MontyObject, MontyObject)],
+    ) -> FsResult<MontyObject> {
+        match function {
+            OsFunction::Getenv => {
+                let key = str_arg(function, args, 0)?;
+                Ok(self.environ.get(key).map_or_else(
+                    || args.get(1).cloned().unwrap_or(MontyObject::None),
+                    |value| MontyObject::String(value.clone()),
+                ))
+            }
+            OsFunction::GetEnviron => Ok(MontyObject::Dict(
+                self.environ
+                    .iter()
+                    .map(|(key, value)| (MontyObject::String(key.clone()), MontyObject::String(value.clone())))
+                    .collect(),
+            )),
+            OsFunction::Urandom => {
+                let size = usize::try_from(int_arg(function, args, 0)?).map_err(|_| {
+                    MontyException::new(ExcType::ValueError, Some("negative argument not allowed".to_owned()))
+                })?;
+                Ok(MontyObject::Bytes(self.random_bytes(size)))
+            }
+            // there is no stdin to read, so `input()` sees its end straight away
+            OsFunction::Input => Err(MontyException::new(
+                ExcType::EOFError,
+                Some("EOF when reading a line".to_owned()),
+            )),
+            OsFunction::FileRead
+            | OsFunction::FileReadline
+            | OsFunction::FileWrite
+            | OsFunction::FileSeek
+            | OsFunction::FileTell
+            | OsFunction::FileClose => self.file_call(function, args),
+            _ => {
+                let path = str_arg(function, args, 0)?;
+                self.path_call(function, path, args, kwargs)
+            }
+        }
+    }
+
+    /// Performs an OS function whose first argument is a path.
+    fn path_call(
+        &mut self,
+        function: OsFunction,
+        path: &str,
+        args: &[MontyObject],
+        kwargs: &[(MontyObject, MontyObject)],
+    ) -> FsResult<MontyObject> {
+        let parts = components(path);
+        match function {
+            // there are no symlinks, so `follow_symlinks` makes no difference
+            OsFunction::Exists => Ok(MontyObject::Bool(self.node(&parts).is_some())),
+            OsFunction::IsFile => Ok(MontyObject::Bool(matches!(self.node(&parts), Some(Node::File { .. })))),
+            OsFunction::IsDir => Ok(MontyObject::Bool(matches!(self.node(&parts), Some(Node::Dir { .. })))),
+            OsFunction::IsSymlink => Ok(MontyObject::Bool(false)),
+            OsFunction::ReadText => Ok(MontyObject::String(decode(self.read_file(path)?)?.to_owned())),
+            OsFunction::ReadBytes => Ok(MontyObject::Bytes(self.read_file(path)?.to_vec())),
+            OsFunction::WriteText => {
+                let text = str_arg(function, args, 1)?;
+                self.write_file(path, text.as_bytes().to_vec())?;
+                Ok(count(text.chars().count()))
+            }
+            OsFunction::WriteBytes => {
+                let Some(MontyObject::Bytes(data)) = args.get(1) else {
+                    return Err(bad_argument(function, 1, args.get(1)));
+                };
+                self.write_file(path, data.clone())?;
+                Ok(count(data.len()))
+            }
+            OsFunction::Mkdir => {
+                let mode = match kwarg(kwargs, "mode") {
+                    Some(MontyObject::Int(mode)) => mode & 0o777,
+                    _ => DEFAULT_DIR_MODE,
+                };
+                match self.node(&parts) {
+                    Some(Node::Dir { .. }) if kwarg_bool(kwargs, "exist_ok", false) => {}
+                    Some(_) => return Err(file_exists(path)),
+                    None => self.make_dir(&parts, path, kwarg_bool(kwargs, "parents", false), mode)?,
+                }
+                Ok(MontyObject::None)
+            }
+            OsFunction::Unlink => {
+                match self.node(&parts) {
+                    None if kwarg_bool(kwargs, "missing_ok", false) => return Ok(MontyObject::None),
+                    None => return Err(not_found(path)),
+                    Some(Node::Dir { .. }) => return Err(is_a_directory(path)),
+                    Some(Node::File { .. }) => {}
+                }
+                self.parent_entries_mut(&parts, path, true)?
+                    .remove(&parts[parts.len() - 1]);
+                Ok(MontyObject::None)
+            }
+            OsFunction::Rmdir => {
+                match self.node(&parts) {
+                    None => return Err(not_found(path)),
+                    Some(Node::File { .. }) => return Err(not_a_directory(path)),
+                    Some(Node::Dir { entries, .. }) if !entries.is_empty() => {
+                        return Err(os_error(ExcType::OSError, 39, "Directory not empty", path));
+                    }
+                    Some(Node::Dir { .. }) => {}
+                }
+                self.parent_entries_mut(&parts, path, true)?
+                    .remove(&parts[parts.len() - 1]);
+                Ok(MontyObject::None)
+            }
+            OsFunction::Iterdir => {
+                let entries = self.read_dir(path)?;
+                Ok(MontyObject::List(
+                    entries.keys().map(|name| MontyObject::Path(join(path, name))).collect(),
+                ))
+            }
+            OsFunction::Stat => match self.node(&parts) {
+                None => Err(not_found(path)),
+                Some(Node::File { content, mode }) => Ok(file_stat(*mode, count_i64(content.len()), self.mtime)),
+                Some(Node::Dir { mode, .. }) => Ok(dir_stat(*mode, self.mtime)),
+            },
+            OsFunction::Rename => {
+                let target = str_arg(function, args, 1)?;
+                self.rename(path, target)?;
+                Ok(MontyObject::None)
+            }
+            // there are no symlinks, so resolving is just normalizing
+            OsFunction::Resolve | OsFunction::Absolute => Ok(MontyObject::String(absolute(&parts))),
+            OsFunction::Glob | OsFunction::Rglob => {
+                let pattern = str_arg(function, args, 1)?;
+                let pattern = if function == OsFunction::Rglob {
+                    format!("**/{pattern}")
+                } else {
+                    pattern.to_owned()
+                };
+                let pattern: Vec<&str> = pattern
+                    .split('/')
+                    .filter(|part| !part.is_empty() && *part != ".")
+                    .collect();
+                let mut descendants = Vec::new();
+                if let Some(node) = self.node(&parts) {
+                    collect_descendants(node, &[], &mut descendants);
+                }
+                Ok(MontyObject::List(
+                    descendants
+                        .into_iter()
+                        .filter(|relative| glob_match(&pattern, relative))
+                        .map(|relative| MontyObject::Path(join(path, &relative.join("/"))))
+                        .collect(),
+                ))
+            }
+            OsFunction::Touch => {
+                if self.node(&parts).is_some() {
+                    if !kwarg_bool(kwargs, "exist_ok", true) {
+                        return Err(file_exists(path));
+                    }
+                } else {
+                    self.write_file(path, Vec::new())?;
+                }
+                Ok(MontyObject::None)
+            }
+            OsFunction::Samefile => {
+                let other = str_arg(function, args, 1)?;
+                let other_parts = components(other);
+                for (parts, path) in [(&parts, path), (&other_parts, other)] {
+                    if self.node(parts).is_none() {
+                        return Err(not_found(path));
+                    }
+                }
+                Ok(MontyObject::Bool(parts == other_parts))
+            }
+            OsFunction::Walk => {
+                let mut walk = Vec::new();
+                if let Some(node) = self.node(&parts) {
+                    walk_dir(node, path, kwarg_bool(kwargs, "topdown", true), &mut walk);
+                }
+                Ok(MontyObject::List(walk))
+            }
+            OsFunction::Copy => {
+                let target = str_arg(function, args, 1)?;
+                Ok(MontyObject::String(self.copy(path, target)?))
+            }
+            OsFunction::Copytree => {
+                let target = str_arg(function, args, 1)?;
+                self.copytree(path, target, kwarg_bool(kwargs, "dirs_exist_ok", false))?;
+                Ok(MontyObject::String(target.to_owned()))
+            }
+            OsFunction::Rmtree => {
+                let result = match self.node(&parts) {
+                    None => Err(not_found(path)),
+                    Some(Node::File { .. }) => Err(not_a_directory(path)),
+                    Some(Node::Dir { .. }) => self.parent_entries_mut(&parts, path, true).map(|entries| {
+                        entries.remove(&parts[parts.len() - 1]);
+                    }),
+                };
+                match result {
+                    Err(_) if kwarg_bool(kwargs, "ignore_errors", false) => Ok(MontyObject::None),
+                    result => result.map(|()| MontyObject::None),
+                }
+            }
+            OsFunction::Open => {
+                let mode = str_arg(function, args, 1)?;
+                self.open(path, mode)
+            }
+            OsFunction::Getenv
+            | OsFunction::GetEnviron
+            | OsFunction::Urandom
+            | OsFunction::FileRead
+            | OsFunction::FileReadline
+            | OsFunction::FileWrite
+            | OsFunction::FileSeek
+            | OsFunction::FileTell
+            | OsFunction::FileClose
+            | OsFunction::Input => unreachable!("{function} doesn't take a path"),
+        }
+    }
+
+    /// Creates a directory, and its missing parents if `parents` is set.
+    fn make_dir(&mut self, parts: &[String], path: &str, parents: bool, mode: i64) -> FsResult<()> {
+        if parents
+            && let Some((_, parent)) = parts.split_last()
+            && self.node(parent).is_none()
+        {
+            self.make_dir(parent, path, true, DEFAULT_DIR_MODE)?;
+        }
+        self.parent_entries_mut(parts, path, true)?
+            .insert(parts[parts.len() - 1].clone(), Node::empty_dir(mode));
+        Ok(())
+    }
+
+    /// Moves a file or directory, replacing a file or empty directory at `target`.
+    fn rename(&mut self, path: &str, target: &str) -> FsResult<()> {
+        let (from, to) = (components(path), components(target));
+        // errors name both paths, `'src' -> 'dst'`
+        let both = format!("{path}' -> '{target}");
+        let Some(node) = self.node(&from) else {
+            return Err(not_found(&both));
+        };
+        match (node, self.node(&to)) {
+            (Node::File { .. }, Some(Node::Dir { .. })) => return Err(is_a_directory(&both)),
+            (Node::Dir { .. }, Some(Node::File { .. })) => return Err(not_a_directory(&both)),
+            (Node::Dir { .. }, Some(Node::Dir { entries, .. })) if !entries.is_empty() && from != to => {
+                return Err(os_error(ExcType::OSError, 39, "Directory not empty", &both));
+            }
+            (Node::Dir { .. }, _) if to.starts_with(&from) && to != from => {
+                return Err(os_error(ExcType::OSError, 22, "Invalid argument", &both));
+            }
+            _ => {}
+        }
+        if from == to {
+            return Ok(());
+        }
+        self.parent_entries_mut(&to, &both, true)?;
+        let node = self
+            .parent_entries_mut(&from, &both, true)?
+            .remove(&from[from.len() - 1])
+            .expect("source exists");
+        self.parent_entries_mut(&to, &both, true)?
+            .insert(to[to.len() - 1].clone(), node);
+        Ok(())
+    }
+
+    /// Copies a file's contents and permissions to a file or into a directory, returning the destination.
+    fn copy(&mut self, path: &str, target: &str) -> FsResult<String> {
+        let parts = components(path);
+        let Some(Node::File { mode, .. }) = self.node(&parts) else {
+            return Err(if self.node(&parts).is_some() {
+                is_a_directory(path)
+            } else {
+                not_found(path)
+            });
+        };
+        let mode = *mode;
+        let content = self.read_file(path)?.to_vec();
+        let target = if self.is_dir(target) {
+            join(target, &parts[parts.len() - 1])
+        } else {
+            target.to_owned()
+        };
+        self.write_file(&target, content)?;
+        if let Some(Node::File { mode: target_mode, .. }) = self.node_mut(&components(&target)) {
+            *target_mode = mode;
+        }
+        Ok(target)
+    }
+
+    /// Recursively copies a directory, merging into an existing one if `dirs_exist_ok` is set.
+    fn copytree(&mut self, path: &str, target: &str, dirs_exist_ok: bool) -> FsResult<()> {
+        let tree = match self.node(&components(path)) {
+            None => return Err(not_found(path)),
+            Some(Node::File { .. }) => return Err(not_a_directory(path)),
+            Some(tree) => tree.clone(),
+        };
+        let parts = components(target);
+        let (tree, old_size) = match self.node(&parts) {
+            None => (tree, 0),
+            Some(existing @ Node::Dir { .. }) if dirs_exist_ok => {
+                let mut merged = existing.clone();
+                merge_dirs(&mut merged, tree);
+                (merged, existing.size())
+            }
+            Some(_) => return Err(file_exists(target)),
+        };
+        self.check_space(old_size, tree.size(), target)?;
+        if let Some((_, parent)) = parts.split_last()
+            && self.node(parent).is_none()
+        {
+            self.make_dir(parent, target, true, DEFAULT_DIR_MODE)?;
+        }
+        self.parent_entries_mut(&parts, target, true)?
+            .insert(parts[parts.len() - 1].clone(), tree);
+        Ok(())
+    }
+
+    /// Opens a file for `OsFunction::Open`, returning its handle.
+    ///
+    /// `mode` is the normalized mode Monty passes, e.g. `r`, `w+` or `ab`.
+    fn open(&mut self, path: &str, mode: &str) -> FsResult<MontyObject> {
+        let open_mode = OpenMode::parse(mode);
+        let content = match self.node(&components(path)) {
+            Some(Node::Dir { .. }) => return Err(is_a_directory(path)),
+            None if open_mode.access == b'r' => return Err(not_found(path)),
+            Some(Node::File { .. }) if open_mode.access == b'x' => return Err(file_exists(path)),
+            Some(Node::File { mode, .. })
+                if (open_mode.readable() && mode & 0o400 == 0) || (open_mode.writable() && mode & 0o200 == 0) =>
+            {
+                return Err(permission_denied(path));
+            }
+            Some(Node::File { content, .. }) if open_mode.keeps_contents() => content.clone(),
+            _ => {
+                // like a real open(), creating or truncating happens straight away
+                self.write_file(path, Vec::new())?;
+                Vec::new()
+            }
+        };
+        self.last_handle += 1;
+        self.open_files
+            .insert(self.last_handle, OpenFile::new(path.to_owned(), content, mode));
+        Ok(MontyObject::Int(self.last_handle))
+    }
+
+    /// Performs one of the `file.*` OS functions, which operate on a handle from `open()`.
+    fn file_call(&mut self, function: OsFunction, args: &[MontyObject]) -> FsResult<MontyObject> {
+        let handle = int_arg(function, args, 0)?;
+        let file = self.open_files.get(&handle).ok_or_else(bad_file_descriptor)?;
+        match function {
+            OsFunction::FileClose => {
+                let file = self.open_files.remove(&handle).expect("handle is open");
+                if file.writable {
+                    self.store_closed_file(file);
+                }
+                Ok(MontyObject::None)
+            }
+            OsFunction::FileWrite => {
+                let (_, new_len) = file.pending_write(args)?;
+                // the file counts at the larger of its stored and unsaved sizes
+                let stored_len = self.stored_len(&file.name);
+                self.check_space(stored_len.max(file.content.len()), stored_len.max(new_len), &file.name)?;
+                let file = self.open_files.get_mut(&handle).expect("handle is open");
+                file.call(function, args)
+            }
+            _ => {
+                let file = self.open_files.get_mut(&handle).expect("handle is open");
+                file.call(function, args)
+            }
+        }
+    }
+
+    /// Stores the contents of a closed file, unless its directory was removed while it was open.
+    fn store_closed_file(&mut self, file: OpenFile) {
+        let parts = components(&file.name);
+        if let Some(Node::File { content, .. }) = self.node_mut(&parts) {
+            *content = file.content;
+        } else if let Ok(entries) = self.parent_entries_mut(&parts, &file.name, false) {
+            let node = Node::File {
+                content: file.content,
+                mode: DEFAULT_FILE_MODE,
+            };
+            entries.insert(parts[parts.len() - 1].clone(), node);
+        }
+    }
+
+    /// Returns the next `size` bytes of the splitmix64 sequence.
+    fn random_bytes(&mut self, size: usize) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(size);
+        while bytes.len() < size {
+            self.random_state = self.random_state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+            let mut z = self.random_state;
+            z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+            z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+            z ^= z >> 31;
+            bytes.extend(z.to_le_bytes().into_iter().take(size - bytes.len()));
+        }
+        bytes
+    }
+}
+
+impl OsHandler for MemoryFs {
+    fn handle_os_call(
+        &mut self,
+        function: OsFunction,
+        args: &[MontyObject],
+        kwargs: &[(MontyObject, MontyObject)],
+    ) -> ExtFunctionResult {
+        match self.call(function, args, kwargs) {
+            Ok(value) => value.into(),
+            Err(exc) => exc.into(),
+        }
+    }
+}
+
+/// Appends the path of every entry below `node`, relative to it, parents before their children.
+///
+/// Directories without read permission are listed but not descended into.
+fn collect_descendants(node: &Node, prefix: &[String], out: &mut Vec<Vec<String>>) {
+    let Node::Dir { entries, mode } = node else {
+        return;
+    };
+    if mode & 0o400 == 0 {
+        return;
+    }
+    for (name, child) in entries {
+        let mut relative = prefix.to_vec();
+        relative.push(name.clone());
+        out.push(relative.clone());
+        collect_descendants(child, &relative, out);
+    }
+}
+
+/// Appends the `(dirpath, dirnames, filenames)` tuples `os.walk()` yields for a directory.
+///
+/// Like `os.walk()`, unreadable directories are skipped rather than raising.
+fn walk_dir(node: &Node, dirpath: &str, topdown: bool, walk: &mut Vec<MontyObject>) {
+    let Node::Dir { entries, mode } = node else {
+        return;
+    };
+    if mode & 0o400 == 0 {
+        return;
+    }
+    let (dirs, files): (Vec<_>, Vec<_>) = entries.iter().partition(|(_, child)| matches!(child, Node::Dir { .. }));
+    let names = |entries: &[(&String, &Node)]| {
+        MontyObject::List(
+            entries
+                .iter()
+                .map(|(name, _)| MontyObject::String((*name).clone()))
+                .collect(),
+        )
+    };
+    let tuple = MontyObject::Tuple(vec![
+        MontyObject::String(dirpath.to_owned()),
+        names(&dirs),
+        names(&files),
+    ]);
+    if topdown {
+        walk.push(tuple.clone());
+    }
+    for (name, child) in dirs {
+        walk_dir(child, &join(dirpath, name), topdown, walk);
+    }
+    if !topdown {
+        walk.push(tuple);
+    }
+}
+
+/// Copies the entries of the `from` directory into the `into` directory, merging subdirectories.
+fn merge_dirs(into: &mut Node, from: Node) {
+    let (Node::Dir { entries: into, .. }, Node::Dir { entries: from, .. }) = (into, from) else {
+        return;
+    };
+    for (name, child) in from {
+        match (into.get_mut(&name), child) {
+            (Some(existing @ Node::Dir { .. }), child @ Node::Dir { .. }) => merge_dirs(existing, child),
+            (_, child) => {
+                into.insert(name, child);
+            }
+        }
+    }
+}
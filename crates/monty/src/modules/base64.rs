@@ -0,0 +1,320 @@
+//! Implementation of Python's `base64` module.
+//!
+//! Provides the RFC 4648 base64, base32 and base16 codecs. Base64 and hex
+//! conversions reuse the shared routines in the `binascii` module, mirroring
+//! CPython where `base64` is a thin wrapper over `binascii`.
+//!
+//! ## Implemented functions
+//!
+//! - `b64encode(s, altchars=None)` / `b64decode(s, altchars=None, validate=False)`
+//! - `standard_b64encode(s)` / `standard_b64decode(s)`
+//! - `urlsafe_b64encode(s)` / `urlsafe_b64decode(s)`
+//! - `b32encode(s)` / `b32decode(s, casefold=False, map01=None)`
+//! - `b16encode(s)` / `b16decode(s, casefold=False)`
+//!
+//! Encoders require `bytes`; decoders also accept ASCII-only `str`.
+//! Malformed input raises `binascii.Error`.
+
+use crate::{
+    args::ArgValues,
+    bytecode::VM,
+    defer_drop,
+    exception_private::{ExcType, RunResult, SimpleException},
+    heap::{HeapData, HeapId},
+    intern::StaticStrings,
+    modules::{
+        ModuleFunctions,
+        binascii::{
+            BASE64_ALPHABET, allocate_bytes, ascii_arg, base64_decode, base64_encode, base64_encoded_len, bytes_arg,
+            hex_decode,
+        },
+    },
+    resource::{ResourceError, ResourceTracker, check_estimated_size},
+    types::{Module, PyTrait},
+    value::Value,
+};
+
+/// The URL- and filesystem-safe base64 alphabet (RFC 4648 section 5).
+const URLSAFE_BASE64_ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789-_";
+
+/// The standard base32 alphabet (RFC 4648 section 6).
+const BASE32_ALPHABET: &[u8; 32] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZ234567";
+
+/// Uppercase hex digits used by `b16encode`.
+const BASE16_ALPHABET: &[u8; 16] = b"0123456789ABCDEF";
+
+/// Error message used by `base64` decoders for non-string, non-bytes input.
+const BASE64_ASCII_TYPE_ERROR: &str = "argument should be a bytes-like object or ASCII string";
+
+/// Functions exposed by the `base64` module.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, strum::Display, serde::Serialize, serde::Deserialize)]
+#[strum(serialize_all = "snake_case")]
+pub(crate) enum Base64Functions {
+    B64encode,
+    B64decode,
+    StandardB64encode,
+    StandardB64decode,
+    UrlsafeB64encode,
+    UrlsafeB64decode,
+    B32encode,
+    B32decode,
+    B16encode,
+    B16decode,
+}
+
+/// Creates the `base64` module and allocates it on the heap.
+///
+/// # Returns
+/// A `HeapId` pointing to the newly allocated module.
+///
+/// # Panics
+/// Panics if the required strings have not been pre-interned during prepare phase.
+pub fn create_module(vm: &mut VM<'_, '_, impl ResourceTracker>) -> Result<HeapId, ResourceError> {
+    let mut module = Module::new(StaticStrings::Base64);
+
+    for (name, func) in BASE64_FUNCTIONS {
+        module.set_attr(*name, Value::ModuleFunction(ModuleFunctions::Base64(*func)), vm);
+    }
+
+    vm.heap.allocate(HeapData::Module(module))
+}
+
+/// Static mapping of attribute names to base64 functions for module creation.
+const BASE64_FUNCTIONS: &[(StaticStrings, Base64Functions)] = &[
+    (StaticStrings::B64encode, Base64Functions::B64encode),
+    (StaticStrings::B64decode, Base64Functions::B64decode),
+    (StaticStrings::StandardB64encode, Base64Functions::StandardB64encode),
+    (StaticStrings::StandardB64decode, Base64Functions::StandardB64decode),
+    (StaticStrings::UrlsafeB64encode, Base64Functions::UrlsafeB64encode),
+    (StaticStrings::UrlsafeB64decode, Base64Functions::UrlsafeB64decode),
+    (StaticStrings::B32encode, Base64Functions::B32encode),
+    (StaticStrings::B32decode, Base64Functions::B32decode),
+    (StaticStrings::B16encode, Base64Functions::B16encode),
+    (StaticStrings::B16decode, Base64Functions::B16decode),
+];
+
+/// Dispatches a call to a base64 module function.
+///
+/// All functions are pure computations and return `Value` directly.
+pub(super) fn call(
+    vm: &mut VM<'_, '_, impl ResourceTracker>,
+    function: Base64Functions,
+    args: ArgValues,
+) -> RunResult<Value> {
+    match function {
+        Base64Functions::B64encode => {
+            let params = args.bind_params("b64encode", ["s", "altchars"], 1, vm.heap, vm.interns)?;
+            defer_drop!(params, vm);
+            let [Some(data), altchars] = params else {
+                unreachable!("required parameter is always bound")
+            };
+            let alphabet = match altchars {
+                Some(altchars) if !matches!(altchars, Value::None) => {
+                    let altchars = bytes_arg(altchars, vm.heap, vm.interns)?;
+                    alt_alphabet(altchars)?
+                }
+                _ => *BASE64_ALPHABET,
+            };
+            let data = bytes_arg(data, vm.heap, vm.interns)?;
+            let encoded = encode_base64(data, &alphabet, vm.heap.tracker())?;
+            allocate_bytes(encoded, vm.heap)
+        }
+        Base64Functions::B64decode => {
+            let params = args.bind_params("b64decode", ["s", "altchars", "validate"], 1, vm.heap, vm.interns)?;
+            defer_drop!(params, vm);
+            let [Some(data), altchars, validate] = params else {
+                unreachable!("required parameter is always bound")
+            };
+            let validate = validate.as_ref().is_some_and(|v| v.py_bool(vm));
+            let mut data = ascii_arg(data, vm.heap, vm.interns, BASE64_ASCII_TYPE_ERROR)?;
+            if let Some(altchars) = altchars
+                && !matches!(altchars, Value::None)
+            {
+                let altchars = ascii_arg(altchars, vm.heap, vm.interns, BASE64_ASCII_TYPE_ERROR)?;
+                let [plus, slash] = altchars[..] else {
+                    return Err(SimpleException::new_none(ExcType::AssertionError).into());
+                };
+                translate(&mut data, plus, slash, b'+', b'/');
+            }
+            let decoded = base64_decode(&data, validate).map_err(ExcType::binascii_error)?;
+            allocate_bytes(decoded, vm.heap)
+        }
+        Base64Functions::StandardB64encode => {
+            let data = args.get_one_arg("standard_b64encode", vm.heap)?;
+            defer_drop!(data, vm);
+            let data = bytes_arg(data, vm.heap, vm.interns)?;
+            let encoded = encode_base64(data, BASE64_ALPHABET, vm.heap.tracker())?;
+            allocate_bytes(encoded, vm.heap)
+        }
+        Base64Functions::StandardB64decode => {
+            let data = args.get_one_arg("standard_b64decode", vm.heap)?;
+            defer_drop!(data, vm);
+            let data = ascii_arg(data, vm.heap, vm.interns, BASE64_ASCII_TYPE_ERROR)?;
+            let decoded = base64_decode(&data, false).map_err(ExcType::binascii_error)?;
+            allocate_bytes(decoded, vm.heap)
+        }
+        Base64Functions::UrlsafeB64encode => {
+            let data = args.get_one_arg("urlsafe_b64encode", vm.heap)?;
+            defer_drop!(data, vm);
+            let data = bytes_arg(data, vm.heap, vm.interns)?;
+            let encoded = encode_base64(data, URLSAFE_BASE64_ALPHABET, vm.heap.tracker())?;
+            allocate_bytes(encoded, vm.heap)
+        }
+        Base64Functions::UrlsafeB64decode => {
+            let data = args.get_one_arg("urlsafe_b64decode", vm.heap)?;
+            defer_drop!(data, vm);
+            let mut data = ascii_arg(data, vm.heap, vm.interns, BASE64_ASCII_TYPE_ERROR)?;
+            translate(&mut data, b'-', b'_', b'+', b'/');
+            let decoded = base64_decode(&data, false).map_err(ExcType::binascii_error)?;
+            allocate_bytes(decoded, vm.heap)
+        }
+        Base64Functions::B32encode => {
+            let data = args.get_one_arg("b32encode", vm.heap)?;
+            defer_drop!(data, vm);
+            let data = bytes_arg(data, vm.heap, vm.interns)?;
+            check_estimated_size(data.len().div_ceil(5).saturating_mul(8), vm.heap.tracker())?;
+            allocate_bytes(base32_encode(data), vm.heap)
+        }
+        Base64Functions::B32decode => {
+            let params = args.bind_params("b32decode", ["s", "casefold", "map01"], 1, vm.heap, vm.interns)?;
+            defer_drop!(params, vm);
+            let [Some(data), casefold, map01] = params else {
+                unreachable!("required parameter is always bound")
+            };
+            let casefold = casefold.as_ref().is_some_and(|v| v.py_bool(vm));
+            let mut data = ascii_arg(data, vm.heap, vm.interns, BASE64_ASCII_TYPE_ERROR)?;
+            if let Some(map01) = map01
+                && !matches!(map01, Value::None)
+            {
+                let map01 = ascii_arg(map01, vm.heap, vm.interns, BASE64_ASCII_TYPE_ERROR)?;
+                let [one] = map01[..] else {
+                    return Err(SimpleException::new_none(ExcType::AssertionError).into());
+                };
+                translate(&mut data, b'0', b'1', b'O', one);
+            }
+            if casefold {
+                data.make_ascii_uppercase();
+            }
+            let decoded = base32_decode(&data).map_err(ExcType::binascii_error)?;
+            allocate_bytes(decoded, vm.heap)
+        }
+        Base64Functions::B16encode => {
+            let data = args.get_one_arg("b16encode", vm.heap)?;
+            defer_drop!(data, vm);
+            let data = bytes_arg(data, vm.heap, vm.interns)?;
+            check_estimated_size(data.len().saturating_mul(2), vm.heap.tracker())?;
+            let encoded = data
+                .iter()
+                .flat_map(|&byte| {
+                    [
+                        BASE16_ALPHABET[usize::from(byte >> 4)],
+                        BASE16_ALPHABET[usize::from(byte & 0x0f)],
+                    ]
+                })
+                .collect();
+            allocate_bytes(encoded, vm.heap)
+        }
+        Base64Functions::B16decode => {
+            let params = args.bind_params("b16decode", ["s", "casefold"], 1, vm.heap, vm.interns)?;
+            defer_drop!(params, vm);
+            let [Some(data), casefold] = params else {
+                unreachable!("required parameter is always bound")
+            };
+            let casefold = casefold.as_ref().is_some_and(|v| v.py_bool(vm));
+            let mut data = ascii_arg(data, vm.heap, vm.interns, BASE64_ASCII_TYPE_ERROR)?;
+            if casefold {
+                data.make_ascii_uppercase();
+            }
+            if !data.iter().all(|c| BASE16_ALPHABET.contains(c)) {
+                return Err(ExcType::binascii_error("Non-base16 digit found"));
+            }
+            let decoded = hex_decode(&data).map_err(ExcType::binascii_error)?;
+            allocate_bytes(decoded, vm.heap)
+        }
+    }
+}
+
+/// Base64-encodes `data` with `alphabet`, checking the output size against the resource limits.
+fn encode_base64(data: &[u8], alphabet: &[u8; 64], tracker: &impl ResourceTracker) -> RunResult<Vec<u8>> {
+    check_estimated_size(base64_encoded_len(data.len()), tracker)?;
+    Ok(base64_encode(data, alphabet))
+}
+
+/// Builds a base64 alphabet with `+` and `/` replaced by the two `altchars`.
+fn alt_alphabet(altchars: &[u8]) -> RunResult<[u8; 64]> {
+    let [plus, slash] = altchars[..] else {
+        return Err(SimpleException::new_none(ExcType::AssertionError).into());
+    };
+    let mut alphabet = *BASE64_ALPHABET;
+    alphabet[62] = plus;
+    alphabet[63] = slash;
+    Ok(alphabet)
+}
+
+/// Replaces every `from_a` with `to_a` and every `from_b` with `to_b`, like `bytes.translate`.
+fn translate(data: &mut [u8], from_a: u8, from_b: u8, to_a: u8, to_b: u8) {
+    for c in data {
+        if *c == from_a {
+            *c = to_a;
+        } else if *c == from_b {
+            *c = to_b;
+        }
+    }
+}
+
+/// Encodes bytes as padded base32.
+fn base32_encode(data: &[u8]) -> Vec<u8> {
+    let mut out = Vec::with_capacity(data.len().div_ceil(5) * 8);
+    for chunk in data.chunks(5) {
+        let mut quantum = [0u8; 8];
+        quantum[3..3 + chunk.len()].copy_from_slice(chunk);
+        let acc = u64::from_be_bytes(quantum);
+        // Each input byte contributes 8 bits, so n bytes need ceil(8n / 5) output characters.
+        let chars = (chunk.len() * 8).div_ceil(5);
+        for i in 0..8 {
+            if i < chars {
+                let index = (acc >> (35 - 5 * i)) & 0x1f;
+                out.push(BASE32_ALPHABET[usize::try_from(index).expect("5-bit index")]);
+            } else {
+                out.push(b'=');
+            }
+        }
+    }
+    out
+}
+
+/// Decodes padded base32, following CPython's `base64._b32decode`.
+fn base32_decode(data: &[u8]) -> Result<Vec<u8>, &'static str> {
+    if data.len() % 8 != 0 {
+        return Err("Incorrect padding");
+    }
+    let stripped_len = data.iter().rposition(|&c| c != b'=').map_or(0, |i| i + 1);
+    let pad_chars = data.len() - stripped_len;
+
+    let mut decoded = Vec::with_capacity(stripped_len / 8 * 5 + 5);
+    let mut acc = 0u64;
+    for quantum in data[..stripped_len].chunks(8) {
+        acc = 0;
+        for &c in quantum {
+            let value = BASE32_ALPHABET
+                .iter()
+                .position(|&a| a == c)
+                .ok_or("Non-base32 digit found")?;
+            acc = (acc << 5) | u64::try_from(value).expect("alphabet index fits in u64");
+        }
+        decoded.extend_from_slice(&acc.to_be_bytes()[3..]);
+    }
+
+    if !matches!(pad_chars, 0 | 1 | 3 | 4 | 6) {
+        return Err("Incorrect padding");
+    }
+    if pad_chars > 0 && !decoded.is_empty() {
+        acc <<= 5 * pad_chars;
+        let last = acc.to_be_bytes();
+        let leftover = (43 - 5 * pad_chars) / 8;
+        let start = decoded.len() - 5;
+        decoded.truncate(start);
+        decoded.extend_from_slice(&last[3..3 + leftover]);
+    }
+    Ok(decoded)
+}
@@ -0,0 +1,390 @@
+//! Implementation of Python's `binascii` module.
+//!
+//! Provides conversions between binary data and ASCII encodings (hex and base64),
+//! plus `crc32`. The encoding and decoding routines here are shared with the
+//! `base64` module, which is a thin layer over them in CPython as well.
+//!
+//! ## Implemented functions
+//!
+//! - `hexlify(data)` / `b2a_hex(data)` → `bytes`
+//! - `unhexlify(hexstr)` / `a2b_hex(hexstr)` → `bytes`
+//! - `b2a_base64(data, *, newline=True)` → `bytes`
+//! - `a2b_base64(string, *, strict_mode=False)` → `bytes`
+//! - `crc32(data, value=0)` → `int`
+//!
+//! Malformed input raises `binascii.Error`, a subclass of `ValueError`.
+
+use crate::{
+    args::ArgValues,
+    builtins::Builtins,
+    bytecode::VM,
+    defer_drop,
+    exception_private::{ExcType, RunResult, SimpleException},
+    heap::{Heap, HeapData, HeapId},
+    intern::{Interns, StaticStrings},
+    modules::ModuleFunctions,
+    resource::{ResourceError, ResourceTracker, check_estimated_size},
+    types::{Bytes, Module, PyTrait},
+    value::Value,
+};
+
+/// The standard base64 alphabet (RFC 4648 section 4).
+pub(super) const BASE64_ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+/// The padding character shared by base64 and base32.
+const PAD: u8 = b'=';
+
+/// Lowercase hex digits used by `hexlify`.
+const HEX_DIGITS: &[u8; 16] = b"0123456789abcdef";
+
+/// Functions exposed by the `binascii` module.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, strum::Display, serde::Serialize, serde::Deserialize)]
+#[strum(serialize_all = "snake_case")]
+pub(crate) enum BinasciiFunctions {
+    Hexlify,
+    Unhexlify,
+    B2aHex,
+    A2bHex,
+    B2aBase64,
+    A2bBase64,
+    Crc32,
+}
+
+/// Creates the `binascii` module and allocates it on the heap.
+///
+/// # Returns
+/// A `HeapId` pointing to the newly allocated module.
+///
+/// # Panics
+/// Panics if the required strings have not been pre-interned during prepare phase.
+pub fn create_module(vm: &mut VM<'_, '_, impl ResourceTracker>) -> Result<HeapId, ResourceError> {
+    let mut module = Module::new(StaticStrings::Binascii);
+
+    for (name, func) in BINASCII_FUNCTIONS {
+        module.set_attr(*name, Value::ModuleFunction(ModuleFunctions::Binascii(*func)), vm);
+    }
+
+    module.set_attr(
+        StaticStrings::ErrorClass,
+        Value::Builtin(Builtins::ExcType(ExcType::BinasciiError)),
+        vm,
+    );
+
+    vm.heap.allocate(HeapData::Module(module))
+}
+
+/// Static mapping of attribute names to binascii functions for module creation.
+const BINASCII_FUNCTIONS: &[(StaticStrings, BinasciiFunctions)] = &[
+    (StaticStrings::Hexlify, BinasciiFunctions::Hexlify),
+    (StaticStrings::Unhexlify, BinasciiFunctions::Unhexlify),
+    (StaticStrings::B2aHex, BinasciiFunctions::B2aHex),
+    (StaticStrings::A2bHex, BinasciiFunctions::A2bHex),
+    (StaticStrings::B2aBase64, BinasciiFunctions::B2aBase64),
+    (StaticStrings::A2bBase64, BinasciiFunctions::A2bBase64),
+    (StaticStrings::Crc32, BinasciiFunctions::Crc32),
+];
+
+/// Dispatches a call to a binascii module function.
+///
+/// All functions are pure computations and return `Value` directly.
+pub(super) fn call(
+    vm: &mut VM<'_, '_, impl ResourceTracker>,
+    function: BinasciiFunctions,
+    args: ArgValues,
+) -> RunResult<Value> {
+    match function {
+        BinasciiFunctions::Hexlify | BinasciiFunctions::B2aHex => {
+            let data = args.get_one_arg(&format!("binascii.{function}"), vm.heap)?;
+            defer_drop!(data, vm);
+            let data = bytes_arg(data, vm.heap, vm.interns)?;
+            check_estimated_size(data.len().saturating_mul(2), vm.heap.tracker())?;
+            allocate_bytes(hex_encode(data), vm.heap)
+        }
+        BinasciiFunctions::Unhexlify | BinasciiFunctions::A2bHex => {
+            let data = args.get_one_arg(&format!("binascii.{function}"), vm.heap)?;
+            defer_drop!(data, vm);
+            let data = ascii_arg(data, vm.heap, vm.interns, BINASCII_ASCII_TYPE_ERROR)?;
+            let decoded = hex_decode(&data).map_err(ExcType::binascii_error)?;
+            allocate_bytes(decoded, vm.heap)
+        }
+        BinasciiFunctions::B2aBase64 => {
+            let params = args.bind_params("b2a_base64", ["data", "newline"], 1, vm.heap, vm.interns)?;
+            defer_drop!(params, vm);
+            let [Some(data), newline] = params else {
+                unreachable!("required parameter is always bound")
+            };
+            let newline = newline.as_ref().is_none_or(|v| v.py_bool(vm));
+            let data = bytes_arg(data, vm.heap, vm.interns)?;
+            check_estimated_size(base64_encoded_len(data.len()), vm.heap.tracker())?;
+            let mut encoded = base64_encode(data, BASE64_ALPHABET);
+            if newline {
+                encoded.push(b'\n');
+            }
+            allocate_bytes(encoded, vm.heap)
+        }
+        BinasciiFunctions::A2bBase64 => {
+            let params = args.bind_params("a2b_base64", ["data", "strict_mode"], 1, vm.heap, vm.interns)?;
+            defer_drop!(params, vm);
+            let [Some(data), strict_mode] = params else {
+                unreachable!("required parameter is always bound")
+            };
+            let strict_mode = strict_mode.as_ref().is_some_and(|v| v.py_bool(vm));
+            let data = ascii_arg(data, vm.heap, vm.interns, BINASCII_ASCII_TYPE_ERROR)?;
+            let decoded = base64_decode(&data, strict_mode).map_err(ExcType::binascii_error)?;
+            allocate_bytes(decoded, vm.heap)
+        }
+        BinasciiFunctions::Crc32 => {
+            let (data, value) = args.get_one_two_args("binascii.crc32", vm.heap)?;
+            defer_drop!(data, vm);
+            let initial = match value {
+                Some(value) => {
+                    let initial = value.as_int(vm.heap);
+                    value.drop_with_heap(vm);
+                    // CPython masks the starting value to 32 bits
+                    u32::try_from(initial?.rem_euclid(1 << 32)).expect("value reduced modulo 2**32")
+                }
+                None => 0,
+            };
+            let data = bytes_arg(data, vm.heap, vm.interns)?;
+            Ok(Value::Int(i64::from(crc32(data, initial))))
+        }
+    }
+}
+
+/// Error message used by binascii's `a2b_*` functions for non-string, non-bytes input.
+const BINASCII_ASCII_TYPE_ERROR: &str = "argument should be bytes, buffer or ASCII string";
+
+/// Allocates a new `bytes` object on the heap.
+pub(super) fn allocate_bytes(data: Vec<u8>, heap: &mut Heap<impl ResourceTracker>) -> RunResult<Value> {
+    Ok(Value::Ref(heap.allocate(HeapData::Bytes(Bytes::new(data)))?))
+}
+
+/// Extracts the contents of a bytes-like argument.
+///
+/// Matches CPython's `TypeError: a bytes-like object is required, not 'str'` for anything
+/// that isn't `bytes`.
+pub(super) fn bytes_arg<'a>(
+    value: &Value,
+    heap: &'a Heap<impl ResourceTracker>,
+    interns: &'a Interns,
+) -> RunResult<&'a [u8]> {
+    match value {
+        Value::InternBytes(id) => Ok(interns.get_bytes(*id)),
+        Value::Ref(id) => match heap.get(*id) {
+            HeapData::Bytes(b) => Ok(b.as_slice()),
+            _ => Err(ExcType::type_error(format!(
+                "a bytes-like object is required, not '{}'",
+                value.py_type(heap)
+            ))),
+        },
+        _ => Err(ExcType::type_error(format!(
+            "a bytes-like object is required, not '{}'",
+            value.py_type(heap)
+        ))),
+    }
+}
+
+/// Extracts the contents of a decoder argument, which may be `bytes` or an ASCII-only `str`.
+///
+/// Non-ASCII strings raise `ValueError`; other types raise `TypeError` using `type_error_prefix`
+/// followed by the offending type name (the wording differs between `binascii` and `base64`).
+pub(super) fn ascii_arg(
+    value: &Value,
+    heap: &Heap<impl ResourceTracker>,
+    interns: &Interns,
+    type_error_prefix: &str,
+) -> RunResult<Vec<u8>> {
+    let s = match value {
+        Value::InternBytes(id) => return Ok(interns.get_bytes(*id).to_vec()),
+        Value::InternString(id) => interns.get_str(*id),
+        Value::Ref(id) => match heap.get(*id) {
+            HeapData::Bytes(b) => return Ok(b.as_slice().to_vec()),
+            HeapData::Str(s) => s.as_str(),
+            _ => {
+                return Err(ExcType::type_error(format!(
+                    "{type_error_prefix}, not '{}'",
+                    value.py_type(heap)
+                )));
+            }
+        },
+        _ => {
+            return Err(ExcType::type_error(format!(
+                "{type_error_prefix}, not '{}'",
+                value.py_type(heap)
+            )));
+        }
+    };
+    if s.is_ascii() {
+        Ok(s.as_bytes().to_vec())
+    } else {
+        Err(SimpleException::new_msg(
+            ExcType::ValueError,
+            "string argument should contain only ASCII characters",
+        )
+        .into())
+    }
+}
+
+/// Encodes bytes as lowercase hex digits.
+pub(super) fn hex_encode(data: &[u8]) -> Vec<u8> {
+    let mut out = Vec::with_capacity(data.len() * 2);
+    for &byte in data {
+        out.push(HEX_DIGITS[usize::from(byte >> 4)]);
+        out.push(HEX_DIGITS[usize::from(byte & 0x0f)]);
+    }
+    out
+}
+
+/// Decodes a string of hex digit pairs (either case) back into bytes.
+pub(super) fn hex_decode(data: &[u8]) -> Result<Vec<u8>, &'static str> {
+    if data.len() % 2 != 0 {
+        return Err("Odd-length string");
+    }
+    data.chunks_exact(2)
+        .map(|pair| match (hex_value(pair[0]), hex_value(pair[1])) {
+            (Some(high), Some(low)) => Ok((high << 4) | low),
+            _ => Err("Non-hexadecimal digit found"),
+        })
+        .collect()
+}
+
+/// Returns the value of a single ASCII hex digit.
+fn hex_value(c: u8) -> Option<u8> {
+    match c {
+        b'0'..=b'9' => Some(c - b'0'),
+        b'a'..=b'f' => Some(c - b'a' + 10),
+        b'A'..=b'F' => Some(c - b'A' + 10),
+        _ => None,
+    }
+}
+
+/// Returns the length of the base64 encoding of `len` bytes (including padding).
+pub(super) fn base64_encoded_len(len: usize) -> usize {
+    len.div_ceil(3).saturating_mul(4)
+}
+
+/// Encodes bytes as padded base64 using the given 64-character alphabet.
+pub(super) fn base64_encode(data: &[u8], alphabet: &[u8; 64]) -> Vec<u8> {
+    let mut out = Vec::with_capacity(base64_encoded_len(data.len()));
+    for chunk in data.chunks(3) {
+        let b0 = chunk[0];
+        let b1 = chunk.get(1).copied().unwrap_or(0);
+        let b2 = chunk.get(2).copied().unwrap_or(0);
+        out.push(alphabet[usize::from(b0 >> 2)]);
+        out.push(alphabet[usize::from(((b0 & 0x03) << 4) | (b1 >> 4))]);
+        if chunk.len() > 1 {
+            out.push(alphabet[usize::from(((b1 & 0x0f) << 2) | (b2 >> 6))]);
+        } else {
+            out.push(PAD);
+        }
+        if chunk.len() > 2 {
+            out.push(alphabet[usize::from(b2 & 0x3f)]);
+        } else {
+            out.push(PAD);
+        }
+    }
+    out
+}
+
+/// Returns the 6-bit value of a standard base64 alphabet character.
+fn base64_value(c: u8) -> Option<u8> {
+    match c {
+        b'A'..=b'Z' => Some(c - b'A'),
+        b'a'..=b'z' => Some(c - b'a' + 26),
+        b'0'..=b'9' => Some(c - b'0' + 52),
+        b'+' => Some(62),
+        b'/' => Some(63),
+        _ => None,
+    }
+}
+
+/// Decodes standard-alphabet base64, following CPython's `binascii.a2b_base64`.
+///
+/// In the default (non-strict) mode, characters outside the alphabet are skipped and
+/// decoding stops at the first complete padding sequence. In strict mode any
+/// non-alphabet character or malformed padding is an error.
+pub(super) fn base64_decode(data: &[u8], strict_mode: bool) -> Result<Vec<u8>, String> {
+    if strict_mode && data.first() == Some(&PAD) {
+        return Err("Leading padding not allowed".to_owned());
+    }
+
+    let mut out = Vec::with_capacity(data.len() / 4 * 3);
+    let mut quad_pos = 0;
+    let mut left_char = 0u8;
+    let mut pads = 0;
+    let mut padding_started = false;
+
+    for (i, &c) in data.iter().enumerate() {
+        if c == PAD {
+            padding_started = true;
+            if strict_mode && quad_pos == 0 {
+                return Err("Excess padding not allowed".to_owned());
+            }
+            if quad_pos >= 2 {
+                pads += 1;
+                if quad_pos + pads >= 4 {
+                    if strict_mode && i + 1 < data.len() {
+                        return Err("Excess data after padding".to_owned());
+                    }
+                    return Ok(out);
+                }
+            }
+            continue;
+        }
+
+        let Some(value) = base64_value(c) else {
+            if strict_mode {
+                return Err("Only base64 data is allowed".to_owned());
+            }
+            continue;
+        };
+        if strict_mode && padding_started {
+            return Err("Discontinuous padding not allowed".to_owned());
+        }
+        pads = 0;
+
+        match quad_pos {
+            0 => {
+                left_char = value;
+                quad_pos = 1;
+            }
+            1 => {
+                out.push((left_char << 2) | (value >> 4));
+                left_char = value & 0x0f;
+                quad_pos = 2;
+            }
+            2 => {
+                out.push((left_char << 4) | (value >> 2));
+                left_char = value & 0x03;
+                quad_pos = 3;
+            }
+            _ => {
+                out.push((left_char << 6) | value);
+                left_char = 0;
+                quad_pos = 0;
+            }
+        }
+    }
+
+    match quad_pos {
+        0 => Ok(out),
+        1 => Err(format!(
+            "Invalid base64-encoded string: number of data characters ({}) cannot be 1 more than a multiple of 4",
+            out.len() / 3 * 4 + 1
+        )),
+        _ => Err("Incorrect padding".to_owned()),
+    }
+}
+
+/// Computes the CRC-32 (IEEE 802.3 polynomial) of `data`, continuing from `crc`.
+pub(super) fn crc32(data: &[u8], crc: u32) -> u32 {
+    let mut crc = !crc;
+    for &byte in data {
+        crc ^= u32::from(byte);
+        for _ in 0..8 {
+            let mask = (crc & 1).wrapping_neg();
+            crc = (crc >> 1) ^ (0xEDB8_8320 & mask);
+        }
+    }
+    !crc
+}
@@ -0,0 +1,176 @@
+//! Implementation of Python's `bisect` module.
+//!
+//! ## Implemented functions
+//!
+//! - `bisect_left(a, x, lo=0, hi=len(a), *, key=None)` and `bisect_right(...)` (alias
+//!   `bisect`) → insertion point for `x` in the sorted sequence `a`
+//! - `insort_left(a, x, lo=0, hi=len(a), *, key=None)` and `insort_right(...)` (alias
+//!   `insort`) → inserts `x` into the sorted list `a`
+//!
+//! Elements are read with `a[i]`, so any indexable sequence can be searched, and
+//! compared with the same `<` ordering as `sorted()` (see [`py_lt`]). As in CPython,
+//! `key` is applied to the elements of `a` but not to `x` when searching, while the
+//! `insort` functions apply it to `x` too. Only lists can be inserted into.
+
+use crate::{
+    args::ArgValues,
+    bytecode::VM,
+    defer_drop,
+    exception_private::{ExcType, RunResult, SimpleException},
+    heap::{Heap, HeapData, HeapId},
+    heap_data::HeapDataMut,
+    intern::StaticStrings,
+    modules::ModuleFunctions,
+    resource::{ResourceError, ResourceTracker},
+    sorting::py_lt,
+    types::{Module, PyTrait},
+    value::Value,
+};
+
+/// Functions exposed by the `bisect` module.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, strum::Display, serde::Serialize, serde::Deserialize)]
+#[strum(serialize_all = "snake_case")]
+pub(crate) enum BisectFunctions {
+    BisectLeft,
+    BisectRight,
+    InsortLeft,
+    InsortRight,
+}
+
+/// Creates the `bisect` module and allocates it on the heap.
+///
+/// # Returns
+/// A `HeapId` pointing to the newly allocated module.
+///
+/// # Panics
+/// Panics if the required strings have not been pre-interned during prepare phase.
+pub fn create_module(vm: &mut VM<'_, '_, impl ResourceTracker>) -> Result<HeapId, ResourceError> {
+    let mut module = Module::new(StaticStrings::Bisect);
+
+    let functions = [
+        (StaticStrings::BisectLeft, BisectFunctions::BisectLeft),
+        (StaticStrings::BisectRight, BisectFunctions::BisectRight),
+        (StaticStrings::Bisect, BisectFunctions::BisectRight),
+        (StaticStrings::InsortLeft, BisectFunctions::InsortLeft),
+        (StaticStrings::InsortRight, BisectFunctions::InsortRight),
+        (StaticStrings::Insort, BisectFunctions::InsortRight),
+    ];
+    for (name, function) in functions {
+        module.set_attr(name, Value::ModuleFunction(ModuleFunctions::Bisect(function)), vm);
+    }
+
+    vm.heap.allocate(HeapData::Module(module))
+}
+
+/// Dispatches a call to a bisect module function.
+pub(super) fn call(
+    vm: &mut VM<'_, '_, impl ResourceTracker>,
+    function: BisectFunctions,
+    args: ArgValues,
+) -> RunResult<Value> {
+    let (right, insert) = match function {
+        BisectFunctions::BisectLeft => (false, false),
+        BisectFunctions::BisectRight => (true, false),
+        BisectFunctions::InsortLeft => (false, true),
+        BisectFunctions::InsortRight => (true, true),
+    };
+    let func_name = function.to_string();
+    let params = args.bind_params(&func_name, ["a", "x", "lo", "hi", "key"], 2, vm.heap, vm.interns)?;
+    defer_drop!(params, vm);
+    let [Some(a), Some(x), lo, hi, key] = params else {
+        unreachable!("required parameters are always bound")
+    };
+    let key = key.as_ref().filter(|key| !matches!(key, Value::None));
+
+    // Only lists support insertion; check before searching like `a.insert` lookup would fail
+    let list_id = match a {
+        Value::Ref(id) if matches!(vm.heap.get(*id), HeapData::List(_)) => Some(*id),
+        _ if insert => return Err(ExcType::attribute_error(a.py_type(vm.heap), "insert")),
+        _ => None,
+    };
+
+    let lo = match lo {
+        Some(lo) => lo.as_int(vm.heap)?,
+        None => 0,
+    };
+    if lo < 0 {
+        return Err(SimpleException::new_msg(ExcType::ValueError, "lo must be non-negative").into());
+    }
+    let hi = match hi.as_ref().filter(|hi| !matches!(hi, Value::None)) {
+        Some(hi) => hi.as_int(vm.heap)?,
+        None => {
+            let Some(len) = a.py_len(vm) else {
+                let type_name = a.py_type(vm.heap);
+                return Err(ExcType::type_error(format!(
+                    "object of type '{type_name}' has no len()"
+                )));
+            };
+            i64::try_from(len).expect("sequence length fits in i64")
+        }
+    };
+
+    if insert && let Some(key) = key {
+        // The insort functions search for the key of the item being inserted
+        let item = x.clone_with_heap(vm);
+        let x_key = vm.evaluate_function("insort() key argument", key, ArgValues::One(item))?;
+        defer_drop!(x_key, vm);
+        let pos = search(a, x_key, lo, hi, Some(key), right, vm)?;
+        insert_at(list_id, pos, x.clone_with_heap(vm), vm);
+        return Ok(Value::None);
+    }
+
+    let pos = search(a, x, lo, hi, key, right, vm)?;
+    if insert {
+        insert_at(list_id, pos, x.clone_with_heap(vm), vm);
+        Ok(Value::None)
+    } else {
+        Ok(Value::Int(pos))
+    }
+}
+
+/// Binary search for `x` in `a[lo:hi]`, returning the leftmost (or, with `right`, the
+/// rightmost) position where it could be inserted while keeping `a` sorted.
+fn search(
+    a: &Value,
+    x: &Value,
+    mut lo: i64,
+    mut hi: i64,
+    key: Option<&Value>,
+    right: bool,
+    vm: &mut VM<'_, '_, impl ResourceTracker>,
+) -> RunResult<i64> {
+    while lo < hi {
+        vm.heap.check_time()?;
+        let mid = lo + (hi - lo) / 2;
+        let item = a.py_getitem(&Value::Int(mid), vm)?;
+        let item = match key {
+            Some(key) => vm.evaluate_function("bisect() key argument", key, ArgValues::One(item))?,
+            None => item,
+        };
+        defer_drop!(item, vm);
+        let go_left = if right {
+            py_lt(x, item, vm)?
+        } else {
+            !py_lt(item, x, vm)?
+        };
+        if go_left {
+            hi = mid;
+        } else {
+            lo = mid + 1;
+        }
+    }
+    Ok(lo)
+}
+
+/// Inserts `item` at `pos` into the list `list_id`, which the insort functions have
+/// already checked is a list.
+fn insert_at(list_id: Option<HeapId>, pos: i64, item: Value, vm: &mut VM<'_, '_, impl ResourceTracker>) {
+    let list_id = list_id.expect("insort target is a list");
+    let pos = usize::try_from(pos).expect("insertion point is non-negative");
+    Heap::with_entry_mut(vm, list_id, |vm, data| {
+        let HeapDataMut::List(list) = data else {
+            unreachable!("insort target is a list")
+        };
+        list.insert(vm.heap, pos, item);
+    });
+}
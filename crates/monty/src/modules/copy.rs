@@ -0,0 +1,430 @@
+//! Implementation of Python's `copy` module.
+//!
+//! ## Implemented functions
+//!
+//! - `copy(x)` → shallow copy of `x`
+//! - `deepcopy(x, memo=None)` → recursive copy of `x`
+//!
+//! Immutable values (numbers, strings, bytes, ranges, functions, compiled patterns, ...)
+//! are returned unchanged, as are tuples, named tuples and frozensets by `copy()` and
+//! tuples and named tuples by `deepcopy()` when none of their items needed copying. Lists,
+//! dicts, sets, dataclasses, paths, exceptions and `StringIO` buffers are copied, as are
+//! frozensets by `deepcopy()`.
+//!
+//! `deepcopy()` keeps a memo from each original heap object to its copy, so an object
+//! reachable along several paths is copied once and reference cycles are reproduced in
+//! the copy, the same way `MontyObject` conversion tracks visited ids to find cycles.
+//! Mutable containers are registered in the memo before their contents are copied,
+//! which is what lets a cycle back to them resolve to the copy. Only `memo=None` is
+//! supported.
+//!
+//! Every copy is allocated through the heap, so it is charged to the `ResourceTracker`;
+//! recursion depth and elapsed time are checked as the copy descends.
+
+use ahash::AHashMap;
+
+use crate::{
+    args::ArgValues,
+    bytecode::VM,
+    defer_drop,
+    exception_private::{ExcType, RunError, RunResult},
+    heap::{DropWithHeap, Heap, HeapData, HeapId},
+    heap_data::HeapDataMut,
+    intern::StaticStrings,
+    modules::ModuleFunctions,
+    resource::{ResourceError, ResourceTracker, check_estimated_size},
+    types::{Dict, FrozenSet, List, Module, PyTrait, Set, Type, allocate_tuple},
+    value::Value,
+};
+
+/// Functions exposed by the `copy` module.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, strum::Display, serde::Serialize, serde::Deserialize)]
+#[strum(serialize_all = "snake_case")]
+pub(crate) enum CopyFunctions {
+    Copy,
+    Deepcopy,
+}
+
+/// Creates the `copy` module and allocates it on the heap.
+///
+/// # Returns
+/// A `HeapId` pointing to the newly allocated module.
+///
+/// # Panics
+/// Panics if the required strings have not been pre-interned during prepare phase.
+pub fn create_module(vm: &mut VM<'_, '_, impl ResourceTracker>) -> Result<HeapId, ResourceError> {
+    let mut module = Module::new(StaticStrings::Copy);
+
+    module.set_attr(
+        StaticStrings::Copy,
+        Value::ModuleFunction(ModuleFunctions::Copy(CopyFunctions::Copy)),
+        vm,
+    );
+    module.set_attr(
+        StaticStrings::Deepcopy,
+        Value::ModuleFunction(ModuleFunctions::Copy(CopyFunctions::Deepcopy)),
+        vm,
+    );
+
+    vm.heap.allocate(HeapData::Module(module))
+}
+
+/// Dispatches a call to a copy module function.
+pub(super) fn call(
+    vm: &mut VM<'_, '_, impl ResourceTracker>,
+    function: CopyFunctions,
+    args: ArgValues,
+) -> RunResult<Value> {
+    match function {
+        CopyFunctions::Copy => {
+            let value = args.get_one_arg("copy", vm.heap)?;
+            defer_drop!(value, vm);
+            shallow_copy(value, vm)
+        }
+        CopyFunctions::Deepcopy => {
+            let (value, memo) = args.get_one_two_args("deepcopy", vm.heap)?;
+            defer_drop!(value, vm);
+            if let Some(memo) = memo {
+                let is_none = matches!(memo, Value::None);
+                memo.drop_with_heap(vm);
+                if !is_none {
+                    return Err(ExcType::type_error("deepcopy() only supports memo=None"));
+                }
+            }
+            deep_copy(value, vm, &mut AHashMap::new())
+        }
+    }
+}
+
+/// Maps each original heap object to its copy during a `deepcopy()`.
+type Memo = AHashMap<HeapId, HeapId>;
+
+/// The contents of a heap object, taken out of the heap so they can be copied while
+/// new objects are allocated.
+///
+/// Item values are owned clones of the originals and must be dropped.
+enum Contents {
+    /// Immutable (or identity-only) objects, returned unchanged.
+    Atomic,
+    List(Vec<Value>),
+    Tuple(Vec<Value>),
+    NamedTuple(Vec<Value>),
+    FrozenSet(Vec<Value>),
+    Set(Vec<Value>),
+    Dict(Vec<(Value, Value)>),
+    Dataclass(Vec<(Value, Value)>),
+    /// A fresh copy of an object without heap references.
+    Leaf(HeapData),
+    /// Objects that cannot be copied.
+    Unsupported(Type),
+}
+
+impl Contents {
+    /// Takes the contents of the object at `id`.
+    ///
+    /// With `deep` false, immutable containers are `Atomic` since a shallow copy returns
+    /// them unchanged.
+    fn of(id: HeapId, deep: bool, heap: &Heap<impl ResourceTracker>) -> Self {
+        let items = |values: &[Value]| values.iter().map(|v| v.clone_with_heap(heap)).collect();
+        let pairs = |dict: &Dict| {
+            dict.iter()
+                .map(|(k, v)| (k.clone_with_heap(heap), v.clone_with_heap(heap)))
+                .collect()
+        };
+        match heap.get(id) {
+            HeapData::List(list) => Self::List(items(list.as_slice())),
+            HeapData::Dict(dict) => Self::Dict(pairs(dict)),
+            HeapData::Set(set) => Self::Set(set.iter().map(|v| v.clone_with_heap(heap)).collect()),
+            HeapData::Dataclass(dc) => Self::Dataclass(pairs(dc.attrs())),
+            HeapData::Tuple(tuple) if deep => Self::Tuple(items(tuple.as_slice())),
+            HeapData::NamedTuple(nt) if deep => Self::NamedTuple(items(nt.as_vec())),
+            HeapData::FrozenSet(fs) if deep => {
+                Self::FrozenSet(fs.storage().iter().map(|v| v.clone_with_heap(heap)).collect())
+            }
+            HeapData::Path(path) => Self::Leaf(HeapData::Path(path.clone())),
+            HeapData::Exception(exc) => Self::Leaf(HeapData::Exception(exc.clone())),
+            HeapData::StringIO(buffer) => Self::Leaf(HeapData::StringIO(buffer.clone())),
+            HeapData::SequenceMatcher(m) => Self::Leaf(HeapData::SequenceMatcher(m.clone())),
+            HeapData::CsvSniffer(s) => Self::Leaf(HeapData::CsvSniffer(s.clone())),
+            HeapData::Iter(_)
+            | HeapData::Module(_)
+            | HeapData::Coroutine(_)
+            | HeapData::GatherFuture(_)
+            | HeapData::DictKeysView(_)
+            | HeapData::DictItemsView(_)
+            | HeapData::DictValuesView(_)
+            | HeapData::Hash(_)
+            | HeapData::CsvWriter(_)
+            | HeapData::File(_)
+            | HeapData::Struct(_) => Self::Unsupported(heap.get(id).py_type(heap)),
+            _ => Self::Atomic,
+        }
+    }
+}
+
+/// `copy.copy(x)`: returns a shallow copy of `value`.
+fn shallow_copy(value: &Value, vm: &mut VM<'_, '_, impl ResourceTracker>) -> RunResult<Value> {
+    let Value::Ref(id) = value else {
+        return Ok(value.clone_with_heap(vm.heap));
+    };
+    let id = *id;
+    let data = match Contents::of(id, false, vm.heap) {
+        Contents::Atomic => return Ok(value.clone_with_heap(vm.heap)),
+        Contents::Unsupported(ty) => return Err(cannot_copy(ty)),
+        Contents::List(items) => HeapData::List(List::new(items)),
+        Contents::Set(items) => HeapData::Set(build_set(items, vm)?),
+        Contents::Dict(pairs) => HeapData::Dict(Dict::from_pairs(pairs, vm)?),
+        Contents::Dataclass(pairs) => {
+            let attrs = Dict::from_pairs(pairs, vm)?;
+            let HeapData::Dataclass(dc) = vm.heap.get(id) else {
+                unreachable!("contents taken from a dataclass")
+            };
+            HeapData::Dataclass(dc.with_attrs(attrs))
+        }
+        Contents::Leaf(data) => data,
+        Contents::Tuple(_) | Contents::NamedTuple(_) | Contents::FrozenSet(_) => {
+            unreachable!("immutable containers are atomic for shallow copies")
+        }
+    };
+    Ok(Value::Ref(vm.heap.allocate(data)?))
+}
+
+/// `copy.deepcopy(x)`: returns a recursive copy of `value`, reusing copies recorded in `memo`.
+fn deep_copy(value: &Value, vm: &mut VM<'_, '_, impl ResourceTracker>, memo: &mut Memo) -> RunResult<Value> {
+    let Value::Ref(id) = value else {
+        return Ok(value.clone_with_heap(vm.heap));
+    };
+    let id = *id;
+    if let Some(&copy_id) = memo.get(&id) {
+        vm.heap.inc_ref(copy_id);
+        return Ok(Value::Ref(copy_id));
+    }
+    let token = vm.heap.incr_recursion_depth()?;
+    defer_drop!(token, vm);
+
+    match Contents::of(id, true, vm.heap) {
+        Contents::Atomic => Ok(value.clone_with_heap(vm.heap)),
+        Contents::Unsupported(ty) => Err(cannot_copy(ty)),
+        Contents::Leaf(data) => {
+            let copy_id = vm.heap.allocate(data)?;
+            memo.insert(id, copy_id);
+            Ok(Value::Ref(copy_id))
+        }
+        Contents::List(items) => {
+            defer_drop!(items, vm);
+            check_estimated_size(items.len() * size_of::<Value>(), vm.heap.tracker())?;
+            // Register the copy before descending so cycles back to this list resolve to it.
+            let copy_id = vm
+                .heap
+                .allocate(HeapData::List(List::new(Vec::with_capacity(items.len()))))?;
+            memo.insert(id, copy_id);
+            let copy = Value::Ref(copy_id);
+            let copies = match deep_copy_all(items, vm, memo) {
+                Ok(copies) => copies,
+                Err(err) => {
+                    copy.drop_with_heap(vm);
+                    return Err(err);
+                }
+            };
+            Heap::with_entry_mut(vm, copy_id, |vm, data| {
+                let HeapDataMut::List(list) = data else {
+                    unreachable!("allocated as a list")
+                };
+                for item in copies {
+                    list.append(vm.heap, item);
+                }
+            });
+            Ok(copy)
+        }
+        Contents::Dict(pairs) => {
+            defer_drop!(pairs, vm);
+            check_estimated_size(pairs.len() * 2 * size_of::<Value>(), vm.heap.tracker())?;
+            let copy_id = vm.heap.allocate(HeapData::Dict(Dict::with_capacity(pairs.len())))?;
+            memo.insert(id, copy_id);
+            let copy = Value::Ref(copy_id);
+            let result = deep_copy_pairs(pairs, vm, memo).and_then(|pairs| {
+                Heap::with_entry_mut(vm, copy_id, |vm, data| {
+                    let HeapDataMut::Dict(dict) = data else {
+                        unreachable!("allocated as a dict")
+                    };
+                    insert_pairs(dict, pairs, vm)
+                })
+            });
+            finish_container(copy, result, vm)
+        }
+        Contents::Dataclass(pairs) => {
+            defer_drop!(pairs, vm);
+            let HeapData::Dataclass(dc) = vm.heap.get(id) else {
+                unreachable!("contents taken from a dataclass")
+            };
+            let empty = dc.with_attrs(Dict::with_capacity(pairs.len()));
+            let copy_id = vm.heap.allocate(HeapData::Dataclass(empty))?;
+            memo.insert(id, copy_id);
+            let copy = Value::Ref(copy_id);
+            let result = deep_copy_pairs(pairs, vm, memo).and_then(|pairs| {
+                Heap::with_entry_mut(vm, copy_id, |vm, data| {
+                    let HeapDataMut::Dataclass(dc) = data else {
+                        unreachable!("allocated as a dataclass")
+                    };
+                    insert_pairs(dc.attrs_mut(), pairs, vm)
+                })
+            });
+            finish_container(copy, result, vm)
+        }
+        Contents::Set(items) => {
+            defer_drop!(items, vm);
+            // Set items are hashable, so they cannot lead back to the set itself.
+            let copies = deep_copy_all(items, vm, memo)?;
+            let set = build_set(copies, vm)?;
+            let copy_id = vm.heap.allocate(HeapData::Set(set))?;
+            memo.insert(id, copy_id);
+            Ok(Value::Ref(copy_id))
+        }
+        Contents::Tuple(items) | Contents::NamedTuple(items) | Contents::FrozenSet(items) => {
+            defer_drop!(items, vm);
+            let copies = deep_copy_all(items, vm, memo)?;
+            // A cycle through a mutable item may already have copied this object.
+            if let Some(&copy_id) = memo.get(&id) {
+                copies.drop_with_heap(vm);
+                vm.heap.inc_ref(copy_id);
+                return Ok(Value::Ref(copy_id));
+            }
+            // Tuples whose items were all returned unchanged are kept; frozensets are
+            // always rebuilt, as CPython does.
+            if !matches!(vm.heap.get(id), HeapData::FrozenSet(_))
+                && items.iter().zip(&copies).all(|(item, copy)| is_same(item, copy))
+            {
+                copies.drop_with_heap(vm);
+                return Ok(value.clone_with_heap(vm.heap));
+            }
+            let copy = match vm.heap.get(id) {
+                HeapData::Tuple(_) => allocate_tuple(copies.into(), vm.heap)?,
+                HeapData::NamedTuple(nt) => {
+                    let nt = nt.with_items(copies);
+                    Value::Ref(vm.heap.allocate(HeapData::NamedTuple(nt))?)
+                }
+                _ => {
+                    let set = build_set(copies, vm)?;
+                    Value::Ref(vm.heap.allocate(HeapData::FrozenSet(FrozenSet::from_set(set)))?)
+                }
+            };
+            if let Value::Ref(copy_id) = copy {
+                memo.insert(id, copy_id);
+            }
+            Ok(copy)
+        }
+    }
+}
+
+/// Deep-copies each of `items`, dropping the copies made so far on failure.
+fn deep_copy_all(items: &[Value], vm: &mut VM<'_, '_, impl ResourceTracker>, memo: &mut Memo) -> RunResult<Vec<Value>> {
+    let mut copies = Vec::with_capacity(items.len());
+    for item in items {
+        let copy = vm
+            .heap
+            .check_time()
+            .map_err(Into::into)
+            .and_then(|()| deep_copy(item, vm, memo));
+        match copy {
+            Ok(copy) => copies.push(copy),
+            Err(err) => {
+                copies.drop_with_heap(vm);
+                return Err(err);
+            }
+        }
+    }
+    Ok(copies)
+}
+
+/// Deep-copies each key and value of `pairs`, dropping the copies made so far on failure.
+fn deep_copy_pairs(
+    pairs: &[(Value, Value)],
+    vm: &mut VM<'_, '_, impl ResourceTracker>,
+    memo: &mut Memo,
+) -> RunResult<Vec<(Value, Value)>> {
+    let mut copies = Vec::with_capacity(pairs.len());
+    for (key, value) in pairs {
+        let key = match deep_copy(key, vm, memo) {
+            Ok(key) => key,
+            Err(err) => {
+                copies.drop_with_heap(vm);
+                return Err(err);
+            }
+        };
+        match deep_copy(value, vm, memo) {
+            Ok(value) => copies.push((key, value)),
+            Err(err) => {
+                key.drop_with_heap(vm);
+                copies.drop_with_heap(vm);
+                return Err(err);
+            }
+        }
+    }
+    Ok(copies)
+}
+
+/// Inserts copied `pairs` into `dict`, dropping the remaining pairs on failure.
+fn insert_pairs(
+    dict: &mut Dict,
+    pairs: Vec<(Value, Value)>,
+    vm: &mut VM<'_, '_, impl ResourceTracker>,
+) -> RunResult<()> {
+    let mut pairs = pairs.into_iter();
+    for (key, value) in pairs.by_ref() {
+        match dict.set(key, value, vm) {
+            Ok(old) => old.drop_with_heap(vm),
+            Err(err) => {
+                pairs.drop_with_heap(vm);
+                return Err(err);
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Returns a container copy whose contents were filled after allocation, or drops it
+/// if filling failed.
+///
+/// Filling bypasses the allocation-time check for references, so the heap is told the
+/// copy may now be part of a cycle.
+fn finish_container(copy: Value, result: RunResult<()>, vm: &mut VM<'_, '_, impl ResourceTracker>) -> RunResult<Value> {
+    match result {
+        Ok(()) => {
+            vm.heap.mark_potential_cycle();
+            Ok(copy)
+        }
+        Err(err) => {
+            copy.drop_with_heap(vm);
+            Err(err)
+        }
+    }
+}
+
+/// Builds a set from owned `items`, dropping them on failure.
+fn build_set(items: Vec<Value>, vm: &mut VM<'_, '_, impl ResourceTracker>) -> RunResult<Set> {
+    let mut set = Set::with_capacity(items.len());
+    let mut items = items.into_iter();
+    for item in items.by_ref() {
+        if let Err(err) = set.add(item, vm) {
+            items.drop_with_heap(vm);
+            set.drop_with_heap(vm);
+            return Err(err);
+        }
+    }
+    Ok(set)
+}
+
+/// Returns whether `copy` is the very object `item` (the copy returned it unchanged).
+fn is_same(item: &Value, copy: &Value) -> bool {
+    match (item, copy) {
+        (Value::Ref(a), Value::Ref(b)) => a == b,
+        (Value::Ref(_), _) | (_, Value::Ref(_)) => false,
+        // Immediate values are immutable and copied as themselves.
+        _ => true,
+    }
+}
+
+/// The `TypeError` raised for objects that cannot be copied, matching CPython's pickling error.
+fn cannot_copy(ty: Type) -> RunError {
+    ExcType::type_error(format!("cannot pickle '{ty}' object"))
+}
@@ -0,0 +1,382 @@
+//! Implementation of Python's `csv` module.
+//!
+//! ## Implemented
+//!
+//! - `reader(csvfile, dialect='excel', **fmtparams)`
+//! - `writer(csvfile, dialect='excel', **fmtparams)` — see [`CsvWriter`]
+//! - `DictReader(f, fieldnames=None, restkey=None, restval=None, dialect='excel', **fmtparams)`
+//! - `DictWriter(f, fieldnames, restval='', extrasaction='raise', dialect='excel', **fmtparams)`
+//! - `Sniffer()` with `sniff()` and `has_header()` — see [`CsvSniffer`](crate::types::CsvSniffer)
+//! - `get_dialect(name)`, `list_dialects()`, the `excel`, `excel_tab` and `unix_dialect`
+//!   dialects, the `QUOTE_*` constants and `Error`
+//!
+//! `csvfile` is an `io.StringIO` or, for readers, any iterable of strings. Readers parse
+//! their whole input when created and return an iterator over the rows, so malformed
+//! input raises `csv.Error` at that point rather than part-way through iteration, and
+//! `line_num` and `DictReader.fieldnames` are not available. Custom dialects cannot be
+//! registered.
+
+use crate::{
+    args::ArgValues,
+    builtins::Builtins,
+    bytecode::VM,
+    defer_drop,
+    exception_private::{ExcType, RunResult},
+    heap::{DropWithHeap, Heap, HeapData, HeapId},
+    heap_data::HeapDataMut,
+    intern::StaticStrings,
+    modules::ModuleFunctions,
+    resource::{ResourceError, ResourceTracker},
+    types::{
+        Dict, List, Module, MontyIter, PyTrait, Type,
+        csv_dialect::{DIALECT_NAMES, Dialect, FMTPARAMS, Field, Quoting, allocate_dialect},
+        csv_writer::CsvWriter,
+        re_pattern::value_to_str,
+        str::allocate_string,
+    },
+    value::Value,
+};
+
+/// Functions exposed by the `csv` module.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, strum::Display, serde::Serialize, serde::Deserialize)]
+#[strum(serialize_all = "snake_case")]
+pub(crate) enum CsvFunctions {
+    Reader,
+    Writer,
+    #[strum(serialize = "DictReader")]
+    DictReader,
+    GetDialect,
+    ListDialects,
+}
+
+/// Creates the `csv` module and allocates it on the heap.
+///
+/// # Returns
+/// A `HeapId` pointing to the newly allocated module.
+///
+/// # Panics
+/// Panics if the required strings have not been pre-interned during prepare phase.
+pub fn create_module(vm: &mut VM<'_, '_, impl ResourceTracker>) -> Result<HeapId, ResourceError> {
+    let mut module = Module::new(StaticStrings::Csv);
+
+    // Functions
+    for (name, function) in [
+        (StaticStrings::Reader, CsvFunctions::Reader),
+        (StaticStrings::Writer, CsvFunctions::Writer),
+        (StaticStrings::DictReader, CsvFunctions::DictReader),
+        (StaticStrings::GetDialect, CsvFunctions::GetDialect),
+        (StaticStrings::ListDialects, CsvFunctions::ListDialects),
+    ] {
+        module.set_attr(name, Value::ModuleFunction(ModuleFunctions::Csv(function)), vm);
+    }
+
+    // Classes
+    module.set_attr(
+        StaticStrings::DictWriter,
+        Value::Builtin(Builtins::Type(Type::CsvDictWriter)),
+        vm,
+    );
+    module.set_attr(
+        StaticStrings::Sniffer,
+        Value::Builtin(Builtins::Type(Type::CsvSniffer)),
+        vm,
+    );
+
+    // Built-in dialects
+    for (name, dialect) in [
+        (StaticStrings::Excel, "excel"),
+        (StaticStrings::ExcelTab, "excel-tab"),
+        (StaticStrings::UnixDialect, "unix"),
+    ] {
+        let dialect = Dialect::builtin(dialect).expect("built-in dialect");
+        let value = Value::Ref(vm.heap.allocate(HeapData::CsvDialect(Box::new(dialect)))?);
+        module.set_attr(name, value, vm);
+    }
+
+    // Quoting constants
+    for (name, quoting) in Quoting::ALL {
+        module.set_attr(name, Value::Int(quoting as i64), vm);
+    }
+
+    // Exception types
+    module.set_attr(
+        StaticStrings::ErrorClass,
+        Value::Builtin(Builtins::ExcType(ExcType::CsvError)),
+        vm,
+    );
+
+    vm.heap.allocate(HeapData::Module(module))
+}
+
+/// Dispatches a call to a csv module function.
+pub(super) fn call(
+    vm: &mut VM<'_, '_, impl ResourceTracker>,
+    function: CsvFunctions,
+    args: ArgValues,
+) -> RunResult<Value> {
+    match function {
+        CsvFunctions::Reader => reader(vm, args),
+        CsvFunctions::Writer => writer(vm, args),
+        CsvFunctions::DictReader => dict_reader(vm, args),
+        CsvFunctions::GetDialect => get_dialect(vm, args),
+        CsvFunctions::ListDialects => list_dialects(vm, args),
+    }
+}
+
+/// `csv.reader(csvfile, dialect='excel', **fmtparams)`: parses `csvfile` and returns
+/// an iterator over its rows as lists of strings.
+fn reader(vm: &mut VM<'_, '_, impl ResourceTracker>, args: ArgValues) -> RunResult<Value> {
+    let (csvfile, dialect) = file_and_dialect("reader", args, vm)?;
+    let lines = read_lines(csvfile, vm)?;
+    let rows = dialect.parse(&lines, vm.heap)?;
+
+    let mut row_values = Vec::with_capacity(rows.len());
+    for row in rows {
+        match fields_to_list(row, vm) {
+            Ok(row) => row_values.push(row),
+            Err(err) => {
+                row_values.drop_with_heap(vm);
+                return Err(err);
+            }
+        }
+    }
+    iter_over(row_values, vm)
+}
+
+/// `csv.writer(csvfile, dialect='excel', **fmtparams)`: returns a writer formatting
+/// rows into `csvfile`.
+fn writer(vm: &mut VM<'_, '_, impl ResourceTracker>, args: ArgValues) -> RunResult<Value> {
+    let (csvfile, dialect) = file_and_dialect("writer", args, vm)?;
+    let writer = CsvWriter::new(csvfile, dialect, vm)?;
+    Ok(Value::Ref(vm.heap.allocate(HeapData::CsvWriter(Box::new(writer)))?))
+}
+
+/// `csv.DictReader(f, fieldnames=None, restkey=None, restval=None, dialect='excel', **fmtparams)`:
+/// returns an iterator over the rows as dicts keyed by `fieldnames`.
+///
+/// Without `fieldnames`, the first row supplies them. Empty rows are skipped, values
+/// beyond the field names are collected in a list under `restkey`, and missing values
+/// are filled with `restval`.
+fn dict_reader(vm: &mut VM<'_, '_, impl ResourceTracker>, args: ArgValues) -> RunResult<Value> {
+    let params = args.bind_params(
+        "DictReader",
+        [
+            "f",
+            "fieldnames",
+            "restkey",
+            "restval",
+            "dialect",
+            FMTPARAMS[0],
+            FMTPARAMS[1],
+            FMTPARAMS[2],
+            FMTPARAMS[3],
+            FMTPARAMS[4],
+            FMTPARAMS[5],
+            FMTPARAMS[6],
+            FMTPARAMS[7],
+        ],
+        1,
+        vm.heap,
+        vm.interns,
+    )?;
+    let [f, fieldnames, restkey, restval, dialect, fmtparams @ ..] = params;
+    let parsed = Dialect::from_params(dialect.as_ref(), &fmtparams, vm);
+    dialect.drop_with_heap(vm);
+    fmtparams.drop_with_heap(vm);
+    let restkey = restkey.unwrap_or(Value::None);
+    defer_drop!(restkey, vm);
+    let restval = restval.unwrap_or(Value::None);
+    defer_drop!(restval, vm);
+    let f = f.expect("required argument");
+    let dialect = match parsed {
+        Ok(dialect) => dialect,
+        Err(err) => {
+            f.drop_with_heap(vm);
+            fieldnames.drop_with_heap(vm);
+            return Err(err);
+        }
+    };
+    let fieldnames: Option<Vec<Value>> = match fieldnames {
+        None | Some(Value::None) => None,
+        Some(fieldnames) => match MontyIter::new(fieldnames, vm).and_then(|iter| iter.collect(vm)) {
+            Ok(fieldnames) => Some(fieldnames),
+            Err(err) => {
+                f.drop_with_heap(vm);
+                return Err(err);
+            }
+        },
+    };
+    defer_drop!(fieldnames, vm);
+
+    let lines = read_lines(f, vm)?;
+    let mut rows = dialect.parse(&lines, vm.heap)?.into_iter();
+    let header = match fieldnames {
+        Some(fieldnames) => fieldnames.iter().map(|name| name.clone_with_heap(vm.heap)).collect(),
+        None => match rows.next() {
+            Some(header) => fields_to_values(header, vm)?,
+            None => return iter_over(Vec::new(), vm),
+        },
+    };
+    defer_drop!(header, vm);
+
+    let mut dicts: Vec<Value> = Vec::new();
+    for row in rows.filter(|row| !row.is_empty()) {
+        let result = row_to_dict(header, row, restkey, restval, vm);
+        match result {
+            Ok(dict) => dicts.push(dict),
+            Err(err) => {
+                dicts.drop_with_heap(vm);
+                return Err(err);
+            }
+        }
+    }
+    iter_over(dicts, vm)
+}
+
+/// Builds one `DictReader` row dict.
+fn row_to_dict(
+    header: &[Value],
+    row: Vec<Field>,
+    restkey: &Value,
+    restval: &Value,
+    vm: &mut VM<'_, '_, impl ResourceTracker>,
+) -> RunResult<Value> {
+    let row_len = row.len();
+    let mut values = fields_to_values(row, vm)?.into_iter();
+    let mut pairs: Vec<(Value, Value)> = header
+        .iter()
+        .zip(values.by_ref())
+        .map(|(key, value)| (key.clone_with_heap(vm.heap), value))
+        .collect();
+    if row_len > header.len() {
+        let rest = match vm.heap.allocate(HeapData::List(List::new(values.collect()))) {
+            Ok(id) => Value::Ref(id),
+            Err(err) => {
+                pairs.drop_with_heap(vm);
+                return Err(err.into());
+            }
+        };
+        pairs.push((restkey.clone_with_heap(vm.heap), rest));
+    } else {
+        for key in &header[row_len..] {
+            pairs.push((key.clone_with_heap(vm.heap), restval.clone_with_heap(vm.heap)));
+        }
+    }
+    let dict = Dict::from_pairs(pairs, vm)?;
+    Ok(Value::Ref(vm.heap.allocate(HeapData::Dict(dict))?))
+}
+
+/// `csv.get_dialect(name)`: returns the built-in dialect called `name`.
+fn get_dialect(vm: &mut VM<'_, '_, impl ResourceTracker>, args: ArgValues) -> RunResult<Value> {
+    let name = args.get_one_arg("get_dialect", vm.heap)?;
+    defer_drop!(name, vm);
+    let name = value_to_str(name, vm.heap, vm.interns)?;
+    let dialect = Dialect::builtin(&name).ok_or_else(|| ExcType::csv_error("unknown dialect"))?;
+    Ok(allocate_dialect(dialect, vm.heap)?)
+}
+
+/// `csv.list_dialects()`: returns the names of the built-in dialects.
+fn list_dialects(vm: &mut VM<'_, '_, impl ResourceTracker>, args: ArgValues) -> RunResult<Value> {
+    args.check_zero_args("list_dialects", vm.heap)?;
+    let mut names = Vec::with_capacity(DIALECT_NAMES.len());
+    for name in DIALECT_NAMES {
+        names.push(allocate_string(name.to_owned(), vm.heap)?);
+    }
+    Ok(Value::Ref(vm.heap.allocate(HeapData::List(List::new(names)))?))
+}
+
+/// Binds `(csvfile, dialect='excel', **fmtparams)` and builds the dialect they describe.
+fn file_and_dialect(
+    name: &str,
+    args: ArgValues,
+    vm: &mut VM<'_, '_, impl ResourceTracker>,
+) -> RunResult<(Value, Dialect)> {
+    let params = args.bind_params(
+        name,
+        [
+            "csvfile",
+            "dialect",
+            FMTPARAMS[0],
+            FMTPARAMS[1],
+            FMTPARAMS[2],
+            FMTPARAMS[3],
+            FMTPARAMS[4],
+            FMTPARAMS[5],
+            FMTPARAMS[6],
+            FMTPARAMS[7],
+        ],
+        1,
+        vm.heap,
+        vm.interns,
+    )?;
+    let [csvfile, dialect, fmtparams @ ..] = params;
+    let result = Dialect::from_params(dialect.as_ref(), &fmtparams, vm);
+    dialect.drop_with_heap(vm);
+    fmtparams.drop_with_heap(vm);
+    let csvfile = csvfile.expect("required argument");
+    match result {
+        Ok(dialect) => Ok((csvfile, dialect)),
+        Err(err) => {
+            csvfile.drop_with_heap(vm);
+            Err(err)
+        }
+    }
+}
+
+/// Reads the lines of a reader's input: the remaining lines of an `io.StringIO`, or the
+/// items of any other iterable, which must be strings.
+fn read_lines(csvfile: Value, vm: &mut VM<'_, '_, impl ResourceTracker>) -> RunResult<Vec<String>> {
+    if let Value::Ref(id) = csvfile
+        && matches!(vm.heap.get(id), HeapData::StringIO(_))
+    {
+        let lines = Heap::with_entry_mut(vm, id, |_, data| match data {
+            HeapDataMut::StringIO(buffer) => buffer.read_lines(),
+            _ => unreachable!("checked above"),
+        });
+        csvfile.drop_with_heap(vm);
+        return lines;
+    }
+
+    let items: Vec<Value> = MontyIter::new(csvfile, vm)?.collect(vm)?;
+    defer_drop!(items, vm);
+    items
+        .iter()
+        .map(|item| {
+            if !item.is_str(vm.heap) {
+                return Err(ExcType::csv_error(format!(
+                    "iterator should return strings, not {} (the file should be opened in text mode)",
+                    item.py_type(vm.heap)
+                )));
+            }
+            Ok(value_to_str(item, vm.heap, vm.interns)?.into_owned())
+        })
+        .collect()
+}
+
+/// Allocates parsed fields as values, dropping any already allocated on failure.
+fn fields_to_values(fields: Vec<Field>, vm: &mut VM<'_, '_, impl ResourceTracker>) -> RunResult<Vec<Value>> {
+    let mut values = Vec::with_capacity(fields.len());
+    for field in fields {
+        match field.into_value(vm.heap) {
+            Ok(value) => values.push(value),
+            Err(err) => {
+                values.drop_with_heap(vm);
+                return Err(err);
+            }
+        }
+    }
+    Ok(values)
+}
+
+/// Allocates a parsed row as a list.
+fn fields_to_list(fields: Vec<Field>, vm: &mut VM<'_, '_, impl ResourceTracker>) -> RunResult<Value> {
+    let values = fields_to_values(fields, vm)?;
+    Ok(Value::Ref(vm.heap.allocate(HeapData::List(List::new(values)))?))
+}
+
+/// Returns an iterator over `items`.
+fn iter_over(items: Vec<Value>, vm: &mut VM<'_, '_, impl ResourceTracker>) -> RunResult<Value> {
+    let list = Value::Ref(vm.heap.allocate(HeapData::List(List::new(items)))?);
+    let iter = MontyIter::new(list, vm)?;
+    Ok(Value::Ref(vm.heap.allocate(HeapData::Iter(iter))?))
+}
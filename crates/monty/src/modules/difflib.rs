@@ -0,0 +1,266 @@
+//! Implementation of Python's `difflib` module.
+//!
+//! ## Implemented
+//!
+//! - `SequenceMatcher(isjunk=None, a='', b='', autojunk=True)` — see
+//!   [`SequenceMatcher`](crate::types::SequenceMatcher)
+//! - `unified_diff(a, b, fromfile='', tofile='', fromfiledate='', tofiledate='', n=3, lineterm='\n')`
+//! - `get_close_matches(word, possibilities, n=3, cutoff=0.6)`
+//!
+//! Sequences are strings or lists/tuples of strings. `unified_diff` returns a list
+//! rather than a generator; iterating over it behaves the same.
+
+use crate::{
+    args::ArgValues,
+    builtins::Builtins,
+    bytecode::VM,
+    defer_drop,
+    exception_private::{ExcType, RunResult, SimpleException},
+    heap::{HeapData, HeapId},
+    intern::StaticStrings,
+    modules::{ModuleFunctions, math::value_to_float},
+    resource::{ResourceError, ResourceTracker, check_estimated_size},
+    types::{
+        List, Module, PyTrait, SequenceMatcher, Type,
+        re_pattern::value_to_str,
+        sequence_matcher::{OpTag, Symbols, sequence_elements},
+        str::allocate_string,
+    },
+    value::Value,
+};
+
+/// Functions exposed by the `difflib` module.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, strum::Display, serde::Serialize, serde::Deserialize)]
+#[strum(serialize_all = "snake_case")]
+pub(crate) enum DifflibFunctions {
+    UnifiedDiff,
+    GetCloseMatches,
+}
+
+/// Creates the `difflib` module and allocates it on the heap.
+///
+/// # Returns
+/// A `HeapId` pointing to the newly allocated module.
+///
+/// # Panics
+/// Panics if the required strings have not been pre-interned during prepare phase.
+pub fn create_module(vm: &mut VM<'_, '_, impl ResourceTracker>) -> Result<HeapId, ResourceError> {
+    let mut module = Module::new(StaticStrings::Difflib);
+
+    module.set_attr(
+        StaticStrings::UnifiedDiff,
+        Value::ModuleFunction(ModuleFunctions::Difflib(DifflibFunctions::UnifiedDiff)),
+        vm,
+    );
+    module.set_attr(
+        StaticStrings::GetCloseMatches,
+        Value::ModuleFunction(ModuleFunctions::Difflib(DifflibFunctions::GetCloseMatches)),
+        vm,
+    );
+    module.set_attr(
+        StaticStrings::SequenceMatcherClass,
+        Value::Builtin(Builtins::Type(Type::SequenceMatcher)),
+        vm,
+    );
+
+    vm.heap.allocate(HeapData::Module(module))
+}
+
+/// Dispatches a call to a difflib module function.
+pub(super) fn call(
+    vm: &mut VM<'_, '_, impl ResourceTracker>,
+    function: DifflibFunctions,
+    args: ArgValues,
+) -> RunResult<Value> {
+    match function {
+        DifflibFunctions::UnifiedDiff => unified_diff(vm, args),
+        DifflibFunctions::GetCloseMatches => get_close_matches(vm, args),
+    }
+}
+
+/// `difflib.unified_diff()`: compares two sequences of lines and returns the delta
+/// as unified diff lines.
+fn unified_diff(vm: &mut VM<'_, '_, impl ResourceTracker>, args: ArgValues) -> RunResult<Value> {
+    let params = args.bind_params(
+        "unified_diff",
+        [
+            "a",
+            "b",
+            "fromfile",
+            "tofile",
+            "fromfiledate",
+            "tofiledate",
+            "n",
+            "lineterm",
+        ],
+        2,
+        vm.heap,
+        vm.interns,
+    )?;
+    defer_drop!(params, vm);
+    let [
+        Some(a),
+        Some(b),
+        fromfile,
+        tofile,
+        fromfiledate,
+        tofiledate,
+        n,
+        lineterm,
+    ] = params
+    else {
+        unreachable!("required parameters are always bound")
+    };
+    let a = sequence_elements(a, vm)?;
+    let b = sequence_elements(b, vm)?;
+    let str_param = |value: &Option<Value>, default: &str| -> RunResult<String> {
+        match value {
+            Some(value) => Ok(value_to_str(value, vm.heap, vm.interns)?.into_owned()),
+            None => Ok(default.to_owned()),
+        }
+    };
+    let fromfile = str_param(fromfile, "")?;
+    let tofile = str_param(tofile, "")?;
+    let fromfiledate = str_param(fromfiledate, "")?;
+    let tofiledate = str_param(tofiledate, "")?;
+    let lineterm = str_param(lineterm, "\n")?;
+    let n = match n {
+        Some(n) => usize::try_from(n.as_int(vm.heap)?).unwrap_or(0),
+        None => 3,
+    };
+
+    let mut symbols = Symbols::default();
+    let a_symbols = symbols.intern_all(&a);
+    let b_symbols = symbols.intern_all(&b);
+    let mut matcher = SequenceMatcher::new(a_symbols, b_symbols, vec![false; symbols.len()], true);
+    let groups = matcher.grouped_opcodes(n, vm.heap)?;
+
+    let mut lines = Vec::new();
+    let mut total_len = 0;
+    let tracker = vm.heap.tracker();
+    let mut push = |line: String| -> Result<(), ResourceError> {
+        total_len += line.len();
+        check_estimated_size(total_len, tracker)?;
+        lines.push(line);
+        Ok(())
+    };
+    for (index, group) in groups.iter().enumerate() {
+        if index == 0 {
+            let fromdate = if fromfiledate.is_empty() {
+                String::new()
+            } else {
+                format!("\t{fromfiledate}")
+            };
+            let todate = if tofiledate.is_empty() {
+                String::new()
+            } else {
+                format!("\t{tofiledate}")
+            };
+            push(format!("--- {fromfile}{fromdate}{lineterm}"))?;
+            push(format!("+++ {tofile}{todate}{lineterm}"))?;
+        }
+        let (first, last) = (group[0], group[group.len() - 1]);
+        let file1_range = format_range_unified(first.1, last.2);
+        let file2_range = format_range_unified(first.3, last.4);
+        push(format!("@@ -{file1_range} +{file2_range} @@{lineterm}"))?;
+        for &(tag, i1, i2, j1, j2) in group {
+            if tag == OpTag::Equal {
+                for line in &a[i1..i2] {
+                    push(format!(" {line}"))?;
+                }
+                continue;
+            }
+            if matches!(tag, OpTag::Replace | OpTag::Delete) {
+                for line in &a[i1..i2] {
+                    push(format!("-{line}"))?;
+                }
+            }
+            if matches!(tag, OpTag::Replace | OpTag::Insert) {
+                for line in &b[j1..j2] {
+                    push(format!("+{line}"))?;
+                }
+            }
+        }
+    }
+
+    let mut items = Vec::with_capacity(lines.len());
+    for line in lines {
+        items.push(allocate_string(line, vm.heap)?);
+    }
+    Ok(Value::Ref(vm.heap.allocate(HeapData::List(List::new(items)))?))
+}
+
+/// Converts a range to the "ed" format used in unified diff hunk headers.
+fn format_range_unified(start: usize, stop: usize) -> String {
+    let beginning = start + 1;
+    match stop - start {
+        1 => beginning.to_string(),
+        0 => format!("{},0", beginning - 1),
+        length => format!("{beginning},{length}"),
+    }
+}
+
+/// `difflib.get_close_matches()`: returns up to `n` of `possibilities` whose similarity
+/// ratio to `word` is at least `cutoff`, best first.
+fn get_close_matches(vm: &mut VM<'_, '_, impl ResourceTracker>, args: ArgValues) -> RunResult<Value> {
+    let params = args.bind_params(
+        "get_close_matches",
+        ["word", "possibilities", "n", "cutoff"],
+        2,
+        vm.heap,
+        vm.interns,
+    )?;
+    defer_drop!(params, vm);
+    let [Some(word), Some(possibilities), n, cutoff_value] = params else {
+        unreachable!("required parameters are always bound")
+    };
+    let n = match n {
+        Some(n) => n.as_int(vm.heap)?,
+        None => 3,
+    };
+    if n <= 0 {
+        return Err(SimpleException::new_msg(ExcType::ValueError, format!("n must be > 0: {n}")).into());
+    }
+    let cutoff = match cutoff_value {
+        Some(cutoff) => value_to_float(cutoff, vm.heap)?,
+        None => 0.6,
+    };
+    if !(0.0..=1.0).contains(&cutoff) {
+        let repr = cutoff_value
+            .as_ref()
+            .map(|v| v.py_repr(vm).into_owned())
+            .unwrap_or_default();
+        return Err(
+            SimpleException::new_msg(ExcType::ValueError, format!("cutoff must be in [0.0, 1.0]: {repr}")).into(),
+        );
+    }
+
+    let word = sequence_elements(word, vm)?;
+    let possibilities = sequence_elements(possibilities, vm)?;
+    let mut scored = Vec::new();
+    for candidate in possibilities {
+        vm.heap.check_time()?;
+        let mut symbols = Symbols::default();
+        let b = symbols.intern_all(&word);
+        let a = candidate
+            .chars()
+            .map(|c| symbols.intern(c.encode_utf8(&mut [0; 4])))
+            .collect();
+        let mut matcher = SequenceMatcher::new(a, b, vec![false; symbols.len()], true);
+        if matcher.real_quick_ratio() >= cutoff && matcher.quick_ratio() >= cutoff {
+            let ratio = matcher.ratio(vm.heap)?;
+            if ratio >= cutoff {
+                scored.push((ratio, candidate));
+            }
+        }
+    }
+    // Same order as `heapq.nlargest` over `(score, candidate)` tuples.
+    scored.sort_by(|x, y| y.0.total_cmp(&x.0).then_with(|| y.1.cmp(&x.1)));
+    scored.truncate(usize::try_from(n).unwrap_or(usize::MAX));
+
+    let mut items = Vec::with_capacity(scored.len());
+    for (_, candidate) in scored {
+        items.push(allocate_string(candidate, vm.heap)?);
+    }
+    Ok(Value::Ref(vm.heap.allocate(HeapData::List(List::new(items)))?))
+}
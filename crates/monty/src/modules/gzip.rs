@@ -0,0 +1,164 @@
+//! Implementation of Python's `gzip` module.
+//!
+//! ## Implemented
+//!
+//! - `compress(data, compresslevel=9, *, mtime=None)` → `bytes`
+//! - `decompress(data)` → `bytes`
+//! - `BadGzipFile`
+//!
+//! The sandbox has no clock, so `compress()` writes an mtime of 0 when `mtime` is `None`
+//! rather than the current time. `decompress()` reads every member in the input, and its
+//! output is charged against the resource tracker like `zlib.decompress()`.
+//! `GzipFile` and `open()` are not supported.
+
+use miniz_oxide::DataFormat;
+
+use crate::{
+    args::ArgValues,
+    builtins::Builtins,
+    bytecode::VM,
+    defer_drop,
+    exception_private::{ExcType, RunError, RunResult, SimpleException},
+    heap::{HeapData, HeapId},
+    intern::StaticStrings,
+    modules::{
+        ModuleFunctions,
+        binascii::{allocate_bytes, bytes_arg, crc32},
+        zlib::{
+            GzipHeader, InflateError, compression_level, gzip_member, gzip_size, inflate, parse_gzip_header,
+            read_u32_le,
+        },
+    },
+    resource::{ResourceError, ResourceTracker},
+    types::{Module, bytes::bytes_repr},
+    value::Value,
+};
+
+/// Functions exposed by the `gzip` module.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, strum::Display, serde::Serialize, serde::Deserialize)]
+#[strum(serialize_all = "snake_case")]
+pub(crate) enum GzipFunctions {
+    Compress,
+    Decompress,
+}
+
+/// `gzip.compress()`'s default compression level.
+const COMPRESS_LEVEL_BEST: i64 = 9;
+
+/// Creates the `gzip` module and allocates it on the heap.
+///
+/// # Returns
+/// A `HeapId` pointing to the newly allocated module.
+///
+/// # Panics
+/// Panics if the required strings have not been pre-interned during prepare phase.
+pub fn create_module(vm: &mut VM<'_, '_, impl ResourceTracker>) -> Result<HeapId, ResourceError> {
+    let mut module = Module::new(StaticStrings::Gzip);
+
+    module.set_attr(
+        StaticStrings::Compress,
+        Value::ModuleFunction(ModuleFunctions::Gzip(GzipFunctions::Compress)),
+        vm,
+    );
+    module.set_attr(
+        StaticStrings::Decompress,
+        Value::ModuleFunction(ModuleFunctions::Gzip(GzipFunctions::Decompress)),
+        vm,
+    );
+    module.set_attr(
+        StaticStrings::BadGzipFile,
+        Value::Builtin(Builtins::ExcType(ExcType::BadGzipFile)),
+        vm,
+    );
+
+    vm.heap.allocate(HeapData::Module(module))
+}
+
+/// Dispatches a call to a gzip module function.
+pub(super) fn call(
+    vm: &mut VM<'_, '_, impl ResourceTracker>,
+    function: GzipFunctions,
+    args: ArgValues,
+) -> RunResult<Value> {
+    match function {
+        GzipFunctions::Compress => {
+            let params = args.bind_params("compress", ["data", "compresslevel", "mtime"], 1, vm.heap, vm.interns)?;
+            defer_drop!(params, vm);
+            let [Some(data), level, mtime] = params else {
+                unreachable!("required parameter is always bound")
+            };
+            let level = match level {
+                Some(level) => level.as_int(vm.heap)?,
+                None => COMPRESS_LEVEL_BEST,
+            };
+            let mtime = match mtime {
+                Some(Value::None) | None => 0,
+                Some(mtime) => mtime.as_int(vm.heap)?,
+            };
+            let data = bytes_arg(data, vm.heap, vm.interns)?;
+            let level = compression_level(level)?;
+            let mtime = u32::try_from(mtime)
+                .map_err(|_| ExcType::struct_error("'L' format requires 0 <= number <= 4294967295"))?;
+            allocate_bytes(gzip_member(data, level, mtime, 255), vm.heap)
+        }
+        GzipFunctions::Decompress => {
+            let data = args.get_one_arg("decompress", vm.heap)?;
+            defer_drop!(data, vm);
+            let data = bytes_arg(data, vm.heap, vm.interns)?;
+            let decompressed = decompress(data, vm.heap.tracker())?;
+            allocate_bytes(decompressed, vm.heap)
+        }
+    }
+}
+
+/// Decompresses every gzip member in `data` and concatenates the results.
+///
+/// Zero bytes between members are skipped, as CPython does for padded files.
+fn decompress(mut data: &[u8], tracker: &impl ResourceTracker) -> RunResult<Vec<u8>> {
+    let mut out = Vec::new();
+    while !data.is_empty() {
+        let header_len = match parse_gzip_header(data) {
+            GzipHeader::Valid(len) => len,
+            GzipHeader::Truncated => return Err(eof_error()),
+            GzipHeader::BadMagic => {
+                let magic = &data[..data.len().min(2)];
+                return Err(ExcType::bad_gzip_file(format!(
+                    "Not a gzipped file ({})",
+                    bytes_repr(magic)
+                )));
+            }
+            GzipHeader::BadMethod => return Err(ExcType::bad_gzip_file("Unknown compression method")),
+        };
+        let (member, consumed) = match inflate(&data[header_len..], DataFormat::Raw, out.len(), tracker) {
+            Ok(result) => result,
+            Err(InflateError::Truncated) => return Err(eof_error()),
+            Err(InflateError::Invalid(msg)) => {
+                return Err(ExcType::zlib_error(format!("Error -3 while decompressing data: {msg}")));
+            }
+            Err(InflateError::Resource(err)) => return Err(err.into()),
+        };
+        let trailer = &data[header_len + consumed..];
+        let (Some(crc), Some(size)) = (read_u32_le(trailer, 0), read_u32_le(trailer, 4)) else {
+            return Err(eof_error());
+        };
+        if crc != crc32(&member, 0) {
+            return Err(ExcType::bad_gzip_file("CRC check failed"));
+        }
+        if size != gzip_size(member.len()) {
+            return Err(ExcType::bad_gzip_file("Incorrect length of data produced"));
+        }
+        out.extend_from_slice(&member);
+        let rest = &trailer[8..];
+        data = &rest[rest.iter().take_while(|&&b| b == 0).count()..];
+    }
+    Ok(out)
+}
+
+/// The `EOFError` raised when the input ends inside a gzip member.
+fn eof_error() -> RunError {
+    SimpleException::new_msg(
+        ExcType::EOFError,
+        "Compressed file ended before the end-of-stream marker was reached",
+    )
+    .into()
+}
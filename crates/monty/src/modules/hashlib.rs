@@ -0,0 +1,183 @@
+//! Implementation of Python's `hashlib` module.
+//!
+//! Provides secure hash and message digest algorithms implemented in pure Rust
+//! (see [`crate::digest`]). Hashing work is charged to the resource tracker: large
+//! inputs are hashed in chunks with a time check between them.
+//!
+//! ## Implemented functions
+//!
+//! - `new(name, data=b'', **kwargs)` → hash object for the named algorithm
+//! - `md5`, `sha1`, `sha224`, `sha256`, `sha384`, `sha512` — `(data=b'', *, usedforsecurity=True)`
+//! - `blake2b(data=b'', *, digest_size=64, key=b'')`
+//!
+//! Hash objects support `update()`, `digest()`, `hexdigest()`, `copy()` and the
+//! `name`, `digest_size` and `block_size` attributes.
+
+use crate::{
+    args::ArgValues,
+    bytecode::VM,
+    defer_drop,
+    digest::{HashAlgorithm, Hasher},
+    exception_private::{ExcType, RunResult, SimpleException},
+    heap::{HeapData, HeapId},
+    intern::StaticStrings,
+    modules::ModuleFunctions,
+    resource::{ResourceError, ResourceTracker},
+    types::{HashObject, Module, PyTrait, hash_object::hash_data_arg, re_pattern::value_to_str},
+    value::Value,
+};
+
+/// Functions exposed by the `hashlib` module.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, strum::Display, serde::Serialize, serde::Deserialize)]
+#[strum(serialize_all = "lowercase")]
+pub(crate) enum HashlibFunctions {
+    New,
+    Md5,
+    Sha1,
+    Sha224,
+    Sha256,
+    Sha384,
+    Sha512,
+    Blake2b,
+}
+
+impl HashlibFunctions {
+    /// Returns the algorithm computed by a named constructor, or `None` for `hashlib.new`.
+    pub fn algorithm(self) -> Option<HashAlgorithm> {
+        match self {
+            Self::New => None,
+            Self::Md5 => Some(HashAlgorithm::Md5),
+            Self::Sha1 => Some(HashAlgorithm::Sha1),
+            Self::Sha224 => Some(HashAlgorithm::Sha224),
+            Self::Sha256 => Some(HashAlgorithm::Sha256),
+            Self::Sha384 => Some(HashAlgorithm::Sha384),
+            Self::Sha512 => Some(HashAlgorithm::Sha512),
+            Self::Blake2b => Some(HashAlgorithm::Blake2b),
+        }
+    }
+}
+
+/// Creates the `hashlib` module and allocates it on the heap.
+///
+/// # Returns
+/// A `HeapId` pointing to the newly allocated module.
+///
+/// # Panics
+/// Panics if the required strings have not been pre-interned during prepare phase.
+pub fn create_module(vm: &mut VM<'_, '_, impl ResourceTracker>) -> Result<HeapId, ResourceError> {
+    let mut module = Module::new(StaticStrings::Hashlib);
+
+    for (name, func) in HASHLIB_FUNCTIONS {
+        module.set_attr(*name, Value::ModuleFunction(ModuleFunctions::Hashlib(*func)), vm);
+    }
+
+    vm.heap.allocate(HeapData::Module(module))
+}
+
+/// Static mapping of attribute names to hashlib functions for module creation.
+const HASHLIB_FUNCTIONS: &[(StaticStrings, HashlibFunctions)] = &[
+    (StaticStrings::New, HashlibFunctions::New),
+    (StaticStrings::Md5, HashlibFunctions::Md5),
+    (StaticStrings::Sha1, HashlibFunctions::Sha1),
+    (StaticStrings::Sha224, HashlibFunctions::Sha224),
+    (StaticStrings::Sha256, HashlibFunctions::Sha256),
+    (StaticStrings::Sha384, HashlibFunctions::Sha384),
+    (StaticStrings::Sha512, HashlibFunctions::Sha512),
+    (StaticStrings::Blake2b, HashlibFunctions::Blake2b),
+];
+
+/// Keyword parameters shared by all constructors (after `name` for `hashlib.new`).
+///
+/// Only `data` may be passed by position. `digest_size` and `key` are only meaningful
+/// for BLAKE2b; `string` is the legacy alias for `data` and `usedforsecurity` is
+/// accepted and ignored.
+const CONSTRUCTOR_PARAMS: [&str; 5] = ["data", "digest_size", "key", "usedforsecurity", "string"];
+
+/// Dispatches a call to a hashlib module function.
+pub(super) fn call(
+    vm: &mut VM<'_, '_, impl ResourceTracker>,
+    function: HashlibFunctions,
+    args: ArgValues,
+) -> RunResult<Value> {
+    let params = if function.algorithm().is_some() {
+        let [data, digest_size, key, usedforsecurity, string] =
+            args.bind_params_kw_only(&function.to_string(), CONSTRUCTOR_PARAMS, 1, 0, vm.heap, vm.interns)?;
+        [None, data, digest_size, key, usedforsecurity, string]
+    } else {
+        args.bind_params_kw_only(
+            "new",
+            ["name", "data", "digest_size", "key", "usedforsecurity", "string"],
+            2,
+            1,
+            vm.heap,
+            vm.interns,
+        )?
+    };
+    defer_drop!(params, vm);
+    let [name, data, digest_size, key, _usedforsecurity, string] = params;
+    let algorithm = match (function.algorithm(), name) {
+        (Some(algorithm), _) => algorithm,
+        (None, Some(name)) => algorithm_from_name(name, vm)?,
+        (None, None) => unreachable!("required parameter is always bound"),
+    };
+
+    let hasher = if algorithm == HashAlgorithm::Blake2b {
+        let digest_size = match digest_size {
+            Some(value) => {
+                let size = value.as_int(vm.heap)?;
+                u8::try_from(size)
+                    .ok()
+                    .filter(|size| (1..=64).contains(size))
+                    .ok_or_else(|| {
+                        SimpleException::new_msg(ExcType::ValueError, "digest_size must be between 1 and 64 bytes")
+                    })?
+            }
+            None => 64,
+        };
+        let key: &[u8] = match key {
+            Some(key) => hash_data_arg(key, vm.heap, vm.interns)?,
+            None => &[],
+        };
+        if key.len() > 64 {
+            return Err(SimpleException::new_msg(ExcType::ValueError, "maximum key length is 64 bytes").into());
+        }
+        Hasher::new_blake2b(digest_size, key)
+    } else {
+        if digest_size.is_some() || key.is_some() {
+            let param = if digest_size.is_some() { "digest_size" } else { "key" };
+            return Err(ExcType::type_error(format!(
+                "'{param}' is an invalid keyword argument for {algorithm}()"
+            )));
+        }
+        Hasher::new(algorithm)
+    };
+
+    let mut hash = HashObject::new(hasher);
+    let data = match (data, string) {
+        (Some(_), Some(_)) => {
+            return Err(ExcType::type_error(
+                "'data' and 'string' are mutually exclusive and support for 'string' keyword parameter is slated for removal in a future version.",
+            ));
+        }
+        (Some(data), None) | (None, Some(data)) => Some(data),
+        (None, None) => None,
+    };
+    if let Some(data) = data {
+        let data = hash_data_arg(data, vm.heap, vm.interns)?;
+        hash.update(data, vm.heap)?;
+    }
+    Ok(Value::Ref(vm.heap.allocate(HeapData::Hash(Box::new(hash)))?))
+}
+
+/// Resolves an algorithm name as accepted by `hashlib.new()` and `hmac.new()`.
+pub(super) fn algorithm_from_name(name: &Value, vm: &VM<'_, '_, impl ResourceTracker>) -> RunResult<HashAlgorithm> {
+    if !name.is_str(vm.heap) {
+        return Err(ExcType::type_error(format!(
+            "new() argument 'name' must be str, not {}",
+            name.py_type(vm.heap)
+        )));
+    }
+    let name = value_to_str(name, vm.heap, vm.interns)?;
+    HashAlgorithm::from_name(&name)
+        .ok_or_else(|| SimpleException::new_msg(ExcType::ValueError, format!("unsupported hash type {name}")).into())
+}
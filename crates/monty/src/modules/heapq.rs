@@ -0,0 +1,376 @@
+//! Implementation of Python's `heapq` module.
+//!
+//! ## Implemented functions
+//!
+//! - `heappush(heap, item)`, `heappop(heap)`, `heappushpop(heap, item)`,
+//!   `heapreplace(heap, item)` and `heapify(x)` → min-heap operations on a list
+//! - `nlargest(n, iterable, key=None)` and `nsmallest(n, iterable, key=None)`
+//! - `merge(*iterables, key=None, reverse=False)` → merges sorted inputs
+//!
+//! The heap operations work in place on the list's items, using the same `<` ordering
+//! as `sorted()` (see [`py_lt`]), and lay the heap out exactly as CPython does, so
+//! `heap[0]` and the list contents match after every operation. The list is detached
+//! from the heap while it is reordered (see [`with_detached_items`]).
+//!
+//! `nlargest()` and `nsmallest()` sort a copy of the input, which gives the documented
+//! `sorted(iterable, key=key)[:n]` result including the order of ties. `merge()` returns
+//! a list rather than an iterator, like `map()`.
+
+use crate::{
+    args::ArgValues,
+    bytecode::VM,
+    defer_drop, defer_drop_mut,
+    exception_private::{ExcType, RunError, RunResult, SimpleException},
+    heap::{DropWithHeap, HeapData, HeapGuard, HeapId},
+    intern::StaticStrings,
+    modules::ModuleFunctions,
+    resource::{ResourceError, ResourceTracker},
+    sorting::{py_lt, sort_values},
+    types::{List, Module, MontyIter, PyTrait, list::with_detached_items},
+    value::Value,
+};
+
+/// Functions exposed by the `heapq` module.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, strum::Display, serde::Serialize, serde::Deserialize)]
+#[strum(serialize_all = "lowercase")]
+pub(crate) enum HeapqFunctions {
+    Heappush,
+    Heappop,
+    Heappushpop,
+    Heapreplace,
+    Heapify,
+    Nlargest,
+    Nsmallest,
+    Merge,
+}
+
+/// Creates the `heapq` module and allocates it on the heap.
+///
+/// # Returns
+/// A `HeapId` pointing to the newly allocated module.
+///
+/// # Panics
+/// Panics if the required strings have not been pre-interned during prepare phase.
+pub fn create_module(vm: &mut VM<'_, '_, impl ResourceTracker>) -> Result<HeapId, ResourceError> {
+    let mut module = Module::new(StaticStrings::Heapq);
+
+    let functions = [
+        (StaticStrings::Heappush, HeapqFunctions::Heappush),
+        (StaticStrings::Heappop, HeapqFunctions::Heappop),
+        (StaticStrings::Heappushpop, HeapqFunctions::Heappushpop),
+        (StaticStrings::Heapreplace, HeapqFunctions::Heapreplace),
+        (StaticStrings::Heapify, HeapqFunctions::Heapify),
+        (StaticStrings::Nlargest, HeapqFunctions::Nlargest),
+        (StaticStrings::Nsmallest, HeapqFunctions::Nsmallest),
+        (StaticStrings::Merge, HeapqFunctions::Merge),
+    ];
+    for (name, function) in functions {
+        module.set_attr(name, Value::ModuleFunction(ModuleFunctions::Heapq(function)), vm);
+    }
+
+    vm.heap.allocate(HeapData::Module(module))
+}
+
+/// Dispatches a call to a heapq module function.
+pub(super) fn call(
+    vm: &mut VM<'_, '_, impl ResourceTracker>,
+    function: HeapqFunctions,
+    args: ArgValues,
+) -> RunResult<Value> {
+    match function {
+        HeapqFunctions::Heappush => {
+            let (heap, item) = args.get_two_args("heappush", vm.heap)?;
+            defer_drop!(heap, vm);
+            let id = match list_id(heap, "heappush() argument 1", vm) {
+                Ok(id) => id,
+                Err(err) => {
+                    item.drop_with_heap(vm);
+                    return Err(err);
+                }
+            };
+            with_detached_items(vm, id, |vm, items| {
+                let pos = items.len();
+                items.push(item);
+                sift_down(items, 0, pos, vm)
+            })?;
+            Ok(Value::None)
+        }
+        HeapqFunctions::Heappop => {
+            let heap = args.get_one_arg("heappop", vm.heap)?;
+            defer_drop!(heap, vm);
+            let id = list_id(heap, "heappop() argument", vm)?;
+            with_detached_items(vm, id, |vm, items| {
+                let Some(mut last) = items.pop() else {
+                    return Err(index_out_of_range());
+                };
+                if let Some(first) = items.first_mut() {
+                    std::mem::swap(first, &mut last);
+                    if let Err(err) = sift_up(items, 0, vm) {
+                        last.drop_with_heap(vm);
+                        return Err(err);
+                    }
+                }
+                Ok(last)
+            })
+        }
+        HeapqFunctions::Heapreplace => {
+            let (heap, mut item) = args.get_two_args("heapreplace", vm.heap)?;
+            defer_drop!(heap, vm);
+            let id = match list_id(heap, "heapreplace() argument 1", vm) {
+                Ok(id) => id,
+                Err(err) => {
+                    item.drop_with_heap(vm);
+                    return Err(err);
+                }
+            };
+            with_detached_items(vm, id, |vm, items| {
+                let Some(first) = items.first_mut() else {
+                    item.drop_with_heap(vm);
+                    return Err(index_out_of_range());
+                };
+                std::mem::swap(first, &mut item);
+                if let Err(err) = sift_up(items, 0, vm) {
+                    item.drop_with_heap(vm);
+                    return Err(err);
+                }
+                Ok(item)
+            })
+        }
+        HeapqFunctions::Heappushpop => {
+            let (heap, item) = args.get_two_args("heappushpop", vm.heap)?;
+            defer_drop!(heap, vm);
+            let id = match list_id(heap, "heappushpop() argument 1", vm) {
+                Ok(id) => id,
+                Err(err) => {
+                    item.drop_with_heap(vm);
+                    return Err(err);
+                }
+            };
+            with_detached_items(vm, id, |vm, items| {
+                let mut item_guard = HeapGuard::new(item, vm);
+                let (item, vm) = item_guard.as_parts_mut();
+                if let Some(first) = items.first_mut()
+                    && py_lt(first, item, vm)?
+                {
+                    std::mem::swap(first, item);
+                    sift_up(items, 0, vm)?;
+                }
+                Ok(item_guard.into_inner())
+            })
+        }
+        HeapqFunctions::Heapify => {
+            let heap = args.get_one_arg("heapify", vm.heap)?;
+            defer_drop!(heap, vm);
+            let id = list_id(heap, "heapify() argument", vm)?;
+            with_detached_items(vm, id, |vm, items| {
+                for pos in (0..items.len() / 2).rev() {
+                    sift_up(items, pos, vm)?;
+                }
+                Ok(Value::None)
+            })
+        }
+        HeapqFunctions::Nlargest => n_extreme(vm, args, "nlargest", true),
+        HeapqFunctions::Nsmallest => n_extreme(vm, args, "nsmallest", false),
+        HeapqFunctions::Merge => merge(vm, args),
+    }
+}
+
+/// Returns the heap id of `value` if it is a list, as the heap functions require.
+///
+/// `arg_name` names the argument in the error, e.g. `"heappush() argument 1"`.
+fn list_id(value: &Value, arg_name: &str, vm: &VM<'_, '_, impl ResourceTracker>) -> RunResult<HeapId> {
+    if let Value::Ref(id) = value
+        && matches!(vm.heap.get(*id), HeapData::List(_))
+    {
+        return Ok(*id);
+    }
+    Err(ExcType::type_error(format!(
+        "{arg_name} must be list, not {}",
+        value.py_type(vm.heap)
+    )))
+}
+
+/// The `IndexError` raised when popping from an empty heap.
+fn index_out_of_range() -> RunError {
+    SimpleException::new_msg(ExcType::IndexError, "index out of range").into()
+}
+
+/// Moves the item at `pos` towards the root until its parent is not greater than it.
+///
+/// This is CPython's `_siftdown`; `start` bounds how far up the item may move.
+fn sift_down(
+    items: &mut [Value],
+    start: usize,
+    mut pos: usize,
+    vm: &mut VM<'_, '_, impl ResourceTracker>,
+) -> RunResult<()> {
+    while pos > start {
+        vm.heap.check_time()?;
+        let parent = (pos - 1) / 2;
+        if !py_lt(&items[pos], &items[parent], vm)? {
+            break;
+        }
+        items.swap(pos, parent);
+        pos = parent;
+    }
+    Ok(())
+}
+
+/// Moves the item at `pos` down to a leaf along the path of smaller children, then
+/// back up to its place.
+///
+/// This is CPython's `_siftup`, which needs fewer comparisons than stopping as soon as
+/// the item is in order, and determines the exact layout the heap ends up with.
+fn sift_up(items: &mut [Value], mut pos: usize, vm: &mut VM<'_, '_, impl ResourceTracker>) -> RunResult<()> {
+    let start = pos;
+    let end = items.len();
+    let mut child = 2 * pos + 1;
+    while child < end {
+        vm.heap.check_time()?;
+        let right = child + 1;
+        if right < end && !py_lt(&items[child], &items[right], vm)? {
+            child = right;
+        }
+        items.swap(pos, child);
+        pos = child;
+        child = 2 * pos + 1;
+    }
+    sift_down(items, start, pos, vm)
+}
+
+/// `nlargest(n, iterable, key=None)` and `nsmallest(n, iterable, key=None)`.
+fn n_extreme(
+    vm: &mut VM<'_, '_, impl ResourceTracker>,
+    args: ArgValues,
+    func_name: &str,
+    largest: bool,
+) -> RunResult<Value> {
+    let params = args.bind_params(func_name, ["n", "iterable", "key"], 2, vm.heap, vm.interns)?;
+    let [Some(n), Some(iterable), key] = params else {
+        unreachable!("required parameters are always bound")
+    };
+    defer_drop!(key, vm);
+    let n_int = n.as_int(vm.heap);
+    n.drop_with_heap(vm);
+    let n = match n_int {
+        Ok(n) => n,
+        Err(err) => {
+            iterable.drop_with_heap(vm);
+            return Err(err);
+        }
+    };
+    let key = key.as_ref().filter(|key| !matches!(key, Value::None));
+
+    let items: Vec<Value> = MontyIter::new(iterable, vm)?.collect(vm)?;
+    let mut items_guard = HeapGuard::new(items, vm);
+    let (items, vm) = items_guard.as_parts_mut();
+    if n > 0 {
+        sort_values(items, key, largest, vm)?;
+    }
+    let keep = usize::try_from(n).unwrap_or(0).min(items.len());
+    let rest = items.split_off(keep);
+    rest.drop_with_heap(vm);
+
+    let (items, vm) = items_guard.into_parts();
+    Ok(Value::Ref(vm.heap.allocate(HeapData::List(List::new(items)))?))
+}
+
+/// `merge(*iterables, key=None, reverse=False)`: merges already-sorted inputs.
+///
+/// Each step takes the smallest (or, with `reverse`, largest) head of the inputs; on ties
+/// the earliest input wins, so the merge is stable.
+fn merge(vm: &mut VM<'_, '_, impl ResourceTracker>, args: ArgValues) -> RunResult<Value> {
+    let (positional, kwargs) = args.into_parts();
+    defer_drop_mut!(positional, vm);
+    let kwargs = kwargs.into_iter();
+    defer_drop_mut!(kwargs, vm);
+
+    let mut key_fn = None;
+    let mut reverse = false;
+    for (name, value) in kwargs.by_ref() {
+        defer_drop!(name, vm);
+        let name_str = name.as_either_str(vm.heap).map(|name| name.into_string(vm.interns));
+        match name_str.as_deref() {
+            Some("key") => {
+                let old = std::mem::replace(&mut key_fn, Some(value).filter(|key| !matches!(key, Value::None)));
+                old.drop_with_heap(vm);
+            }
+            Some("reverse") => {
+                reverse = value.py_bool(vm);
+                value.drop_with_heap(vm);
+            }
+            _ => {
+                value.drop_with_heap(vm);
+                key_fn.drop_with_heap(vm);
+                let name = name_str.unwrap_or_default();
+                return Err(ExcType::type_error(format!(
+                    "merge() got an unexpected keyword argument '{name}'"
+                )));
+            }
+        }
+    }
+    defer_drop!(key_fn, vm);
+
+    // Collect every input along with its keys
+    let mut inputs_guard = HeapGuard::new(Vec::<(Vec<Value>, Vec<Value>)>::new(), vm);
+    let (inputs, vm) = inputs_guard.as_parts_mut();
+    for iterable in positional.by_ref() {
+        let items: Vec<Value> = MontyIter::new(iterable, vm)?.collect(vm)?;
+        let mut keys = Vec::new();
+        if let Some(key_fn) = key_fn {
+            for item in &items {
+                let item = item.clone_with_heap(vm);
+                match vm.evaluate_function("merge() key argument", key_fn, ArgValues::One(item)) {
+                    Ok(key) => keys.push(key),
+                    Err(err) => {
+                        items.drop_with_heap(vm);
+                        keys.drop_with_heap(vm);
+                        return Err(err);
+                    }
+                }
+            }
+        }
+        inputs.push((items, keys));
+    }
+
+    let total = inputs.iter().map(|(items, _)| items.len()).sum();
+    let mut merged_guard = HeapGuard::new(Vec::with_capacity(total), vm);
+    let (merged, vm) = merged_guard.as_parts_mut();
+    let mut heads = vec![0; inputs.len()];
+    for _ in 0..total {
+        vm.heap.check_time()?;
+        let mut best: Option<usize> = None;
+        for (i, input) in inputs.iter().enumerate() {
+            if heads[i] == input.0.len() {
+                continue;
+            }
+            let Some(b) = best else {
+                best = Some(i);
+                continue;
+            };
+            let candidate = merge_key(input, heads[i]);
+            let current = merge_key(&inputs[b], heads[b]);
+            let better = if reverse {
+                py_lt(current, candidate, vm)?
+            } else {
+                py_lt(candidate, current, vm)?
+            };
+            if better {
+                best = Some(i);
+            }
+        }
+        let best = best.expect("an input has items left");
+        merged.push(inputs[best].0[heads[best]].clone_with_heap(vm));
+        heads[best] += 1;
+    }
+
+    let (merged, vm) = merged_guard.into_parts();
+    Ok(Value::Ref(vm.heap.allocate(HeapData::List(List::new(merged)))?))
+}
+
+/// Returns the value `merge()` orders the item at `pos` of an input by: its key if a key
+/// function was given, otherwise the item itself.
+fn merge_key(input: &(Vec<Value>, Vec<Value>), pos: usize) -> &Value {
+    let (items, keys) = input;
+    if keys.is_empty() { &items[pos] } else { &keys[pos] }
+}
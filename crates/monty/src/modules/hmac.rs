@@ -0,0 +1,168 @@
+//! Implementation of Python's `hmac` module.
+//!
+//! Provides keyed-hashing for message authentication (RFC 2104) on top of the
+//! digests used by `hashlib`.
+//!
+//! ## Implemented functions
+//!
+//! - `new(key, msg=None, digestmod)` → `hmac.HMAC` object
+//! - `digest(key, msg, digest)` → `bytes`
+//! - `compare_digest(a, b)` → `bool` (constant-time comparison)
+//!
+//! `digestmod` may be an algorithm name (e.g. `'sha256'`) or a `hashlib`
+//! constructor (e.g. `hashlib.sha256`).
+
+use crate::{
+    args::ArgValues,
+    builtins::Builtins,
+    bytecode::VM,
+    defer_drop,
+    exception_private::{ExcType, RunResult},
+    heap::{Heap, HeapData, HeapId},
+    intern::{Interns, StaticStrings},
+    modules::{ModuleFunctions, binascii::bytes_arg, hashlib::algorithm_from_name},
+    resource::{ResourceError, ResourceTracker},
+    types::{HashObject, Module, PyTrait, Type, hash_object::hash_data_arg, re_pattern::value_to_str},
+    value::Value,
+};
+
+/// Functions exposed by the `hmac` module.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, strum::Display, serde::Serialize, serde::Deserialize)]
+#[strum(serialize_all = "snake_case")]
+pub(crate) enum HmacFunctions {
+    New,
+    Digest,
+    CompareDigest,
+}
+
+/// Creates the `hmac` module and allocates it on the heap.
+///
+/// # Returns
+/// A `HeapId` pointing to the newly allocated module.
+///
+/// # Panics
+/// Panics if the required strings have not been pre-interned during prepare phase.
+pub fn create_module(vm: &mut VM<'_, '_, impl ResourceTracker>) -> Result<HeapId, ResourceError> {
+    let mut module = Module::new(StaticStrings::Hmac);
+
+    module.set_attr(
+        StaticStrings::New,
+        Value::ModuleFunction(ModuleFunctions::Hmac(HmacFunctions::New)),
+        vm,
+    );
+    module.set_attr(
+        StaticStrings::Digest,
+        Value::ModuleFunction(ModuleFunctions::Hmac(HmacFunctions::Digest)),
+        vm,
+    );
+    module.set_attr(
+        StaticStrings::CompareDigest,
+        Value::ModuleFunction(ModuleFunctions::Hmac(HmacFunctions::CompareDigest)),
+        vm,
+    );
+    module.set_attr(StaticStrings::HmacClass, Value::Builtin(Builtins::Type(Type::Hmac)), vm);
+
+    vm.heap.allocate(HeapData::Module(module))
+}
+
+/// Dispatches a call to an hmac module function.
+pub(super) fn call(
+    vm: &mut VM<'_, '_, impl ResourceTracker>,
+    function: HmacFunctions,
+    args: ArgValues,
+) -> RunResult<Value> {
+    match function {
+        HmacFunctions::New => {
+            let params = args.bind_params("new", ["key", "msg", "digestmod"], 1, vm.heap, vm.interns)?;
+            defer_drop!(params, vm);
+            let [Some(key), msg, digestmod] = params else {
+                unreachable!("required parameter is always bound")
+            };
+            let Some(digestmod) = digestmod.as_ref().filter(|v| !matches!(v, Value::None)) else {
+                return Err(ExcType::type_error("Missing required argument 'digestmod'."));
+            };
+            let msg = msg.as_ref().filter(|v| !matches!(v, Value::None));
+            let hmac = new_hmac(key, msg, digestmod, vm)?;
+            Ok(Value::Ref(vm.heap.allocate(HeapData::Hash(Box::new(hmac)))?))
+        }
+        HmacFunctions::Digest => {
+            let params = args.bind_params("digest", ["key", "msg", "digest"], 3, vm.heap, vm.interns)?;
+            defer_drop!(params, vm);
+            let [Some(key), Some(msg), Some(digest)] = params else {
+                unreachable!("required parameters are always bound")
+            };
+            let hmac = new_hmac(key, Some(msg), digest, vm)?;
+            Ok(Value::Ref(vm.heap.allocate(HeapData::Bytes(hmac.digest().into()))?))
+        }
+        HmacFunctions::CompareDigest => {
+            let (a, b) = args.get_two_args("compare_digest", vm.heap)?;
+            defer_drop!(a, vm);
+            defer_drop!(b, vm);
+            compare_digest(a, b, vm.heap, vm.interns).map(Value::Bool)
+        }
+    }
+}
+
+/// Builds an HMAC object from `key`, an optional initial `msg`, and a `digestmod`.
+fn new_hmac(
+    key: &Value,
+    msg: Option<&Value>,
+    digestmod: &Value,
+    vm: &VM<'_, '_, impl ResourceTracker>,
+) -> RunResult<HashObject> {
+    let algorithm = match digestmod {
+        Value::ModuleFunction(ModuleFunctions::Hashlib(constructor)) => constructor.algorithm(),
+        _ if digestmod.is_str(vm.heap) => Some(algorithm_from_name(digestmod, vm)?),
+        _ => None,
+    }
+    .ok_or_else(|| ExcType::type_error("digestmod must be a hash algorithm name or a hashlib constructor"))?;
+
+    if key.py_type(vm.heap) != Type::Bytes {
+        return Err(ExcType::type_error(format!(
+            "key: expected bytes or bytearray, but got '{}'",
+            key.py_type(vm.heap)
+        )));
+    }
+    let key = bytes_arg(key, vm.heap, vm.interns)?;
+    let mut hmac = HashObject::new_hmac(algorithm, key);
+    if let Some(msg) = msg {
+        let msg = hash_data_arg(msg, vm.heap, vm.interns)?;
+        hmac.update(msg, vm.heap)?;
+    }
+    Ok(hmac)
+}
+
+/// Implements `hmac.compare_digest(a, b)`.
+///
+/// Accepts two ASCII strings or two bytes objects. Inputs of different lengths compare
+/// unequal straight away, as in CPython; otherwise every byte is inspected so the duration
+/// doesn't reveal where the inputs first differ.
+fn compare_digest(a: &Value, b: &Value, heap: &Heap<impl ResourceTracker>, interns: &Interns) -> RunResult<bool> {
+    let (a, b) = if a.is_str(heap) && b.is_str(heap) {
+        let a = value_to_str(a, heap, interns)?;
+        let b = value_to_str(b, heap, interns)?;
+        if !a.is_ascii() || !b.is_ascii() {
+            return Err(ExcType::type_error(
+                "comparing strings with non-ASCII characters is not supported",
+            ));
+        }
+        (a.as_bytes().to_vec(), b.as_bytes().to_vec())
+    } else if [a, b].iter().any(|v| v.is_str(heap) || v.py_type(heap) == Type::Bytes) {
+        (
+            bytes_arg(a, heap, interns)?.to_vec(),
+            bytes_arg(b, heap, interns)?.to_vec(),
+        )
+    } else {
+        return Err(ExcType::type_error(format!(
+            "unsupported operand types(s) or combination of types: '{}' and '{}'",
+            a.py_type(heap),
+            b.py_type(heap)
+        )));
+    };
+
+    if a.len() != b.len() {
+        return Ok(false);
+    }
+    let diff = a.iter().zip(&b).fold(0, |diff, (x, y)| diff | (x ^ y));
+    Ok(diff == 0)
+}
@@ -0,0 +1,40 @@
+//! Implementation of Python's `io` module.
+//!
+//! Only the in-memory text buffer `StringIO` is provided, e.g. as the target of
+//! `csv.writer()` or to feed text to `csv.reader()`, along with `UnsupportedOperation`,
+//! which file objects from `open()` raise for operations their mode doesn't allow.
+
+use crate::{
+    builtins::Builtins,
+    bytecode::VM,
+    exception_private::ExcType,
+    heap::{HeapData, HeapId},
+    intern::StaticStrings,
+    resource::{ResourceError, ResourceTracker},
+    types::{Module, Type},
+    value::Value,
+};
+
+/// Creates the `io` module and allocates it on the heap.
+///
+/// # Returns
+/// A `HeapId` pointing to the newly allocated module.
+///
+/// # Panics
+/// Panics if the required strings have not been pre-interned during prepare phase.
+pub fn create_module(vm: &mut VM<'_, '_, impl ResourceTracker>) -> Result<HeapId, ResourceError> {
+    let mut module = Module::new(StaticStrings::Io);
+
+    module.set_attr(
+        StaticStrings::StringIOClass,
+        Value::Builtin(Builtins::Type(Type::StringIO)),
+        vm,
+    );
+    module.set_attr(
+        StaticStrings::UnsupportedOperation,
+        Value::Builtin(Builtins::ExcType(ExcType::UnsupportedOperation)),
+        vm,
+    );
+
+    vm.heap.allocate(HeapData::Module(module))
+}
@@ -17,12 +17,35 @@ use crate::{
 };
 
 pub(crate) mod asyncio;
+pub(crate) mod base64;
+pub(crate) mod binascii;
+pub(crate) mod bisect;
+pub(crate) mod copy;
+pub(crate) mod csv;
+pub(crate) mod difflib;
+pub(crate) mod gzip;
+pub(crate) mod hashlib;
+pub(crate) mod heapq;
+pub(crate) mod hmac;
+pub(crate) mod io;
 pub(crate) mod math;
+pub(crate) mod operator;
 pub(crate) mod os;
+pub(crate) mod os_path;
 pub(crate) mod pathlib;
+pub(crate) mod pprint;
 pub(crate) mod re;
+pub(crate) mod reprlib;
+pub(crate) mod shutil;
+pub(crate) mod statistics;
+pub(crate) mod string;
+pub(crate) mod r#struct;
 pub(crate) mod sys;
+pub(crate) mod textwrap;
 pub(crate) mod typing;
+pub(crate) mod unicodedata;
+pub(crate) mod uuid;
+pub(crate) mod zlib;
 
 /// Built-in modules that can be imported.
 #[repr(u8)]
@@ -36,12 +59,59 @@ pub(crate) enum BuiltinModule {
     Asyncio,
     /// The `pathlib` module providing object-oriented filesystem paths.
     Pathlib,
-    /// The `os` module providing operating system interface (only `getenv()` implemented).
+    /// The `os` module providing operating system interface (environment, `urandom()`, `walk()`,
+    /// `listdir()`, `makedirs()` and other filesystem functions).
     Os,
     /// The `math` module providing mathematical functions and constants.
     Math,
     /// The `re` module providing regular expression matching.
     Re,
+    /// The `binascii` module providing hex/base64 conversions and `crc32`.
+    Binascii,
+    /// The `base64` module providing base64/base32/base16 codecs.
+    Base64,
+    /// The `hashlib` module providing message digests.
+    Hashlib,
+    /// The `hmac` module providing keyed message authentication.
+    Hmac,
+    /// The `string` module providing character constants and `Template`.
+    String,
+    /// The `textwrap` module providing text wrapping, filling and indentation.
+    Textwrap,
+    /// The `difflib` module providing `SequenceMatcher` and `unified_diff()`.
+    Difflib,
+    /// The `unicodedata` module (only `normalize()` and `is_normalized()` implemented).
+    Unicodedata,
+    /// The `io` module (only `StringIO` implemented).
+    Io,
+    /// The `csv` module providing CSV reading, writing and dialect sniffing.
+    Csv,
+    /// The `copy` module providing shallow and deep copies.
+    Copy,
+    /// The `heapq` module providing heap queue operations on lists.
+    Heapq,
+    /// The `bisect` module providing binary search and sorted insertion.
+    Bisect,
+    /// The `operator` module providing operators as functions, plus `itemgetter` and friends.
+    Operator,
+    /// The `statistics` module providing averages and measures of spread.
+    Statistics,
+    /// The `struct` module for packing and unpacking binary data.
+    Struct,
+    /// The `pprint` module providing pretty-printed reprs of nested data.
+    Pprint,
+    /// The `reprlib` module (only `repr()` implemented) providing size-limited reprs.
+    Reprlib,
+    /// The `uuid` module providing `UUID` objects and `uuid3()`/`uuid4()`/`uuid5()`.
+    Uuid,
+    /// The `zlib` module providing deflate compression and checksums.
+    Zlib,
+    /// The `gzip` module providing one-shot gzip `compress()`/`decompress()`.
+    Gzip,
+    /// The `shutil` module (only `copy()`, `copytree()` and `rmtree()` implemented).
+    Shutil,
+    /// The `os.path` module (`posixpath`) providing path manipulation and queries.
+    OsPath,
 }
 
 impl BuiltinModule {
@@ -55,6 +125,29 @@ impl BuiltinModule {
             StaticStrings::Os => Some(Self::Os),
             StaticStrings::Math => Some(Self::Math),
             StaticStrings::Re => Some(Self::Re),
+            StaticStrings::Binascii => Some(Self::Binascii),
+            StaticStrings::Base64 => Some(Self::Base64),
+            StaticStrings::Hashlib => Some(Self::Hashlib),
+            StaticStrings::Hmac => Some(Self::Hmac),
+            StaticStrings::StringAttr => Some(Self::String),
+            StaticStrings::Textwrap => Some(Self::Textwrap),
+            StaticStrings::Difflib => Some(Self::Difflib),
+            StaticStrings::Unicodedata => Some(Self::Unicodedata),
+            StaticStrings::Io => Some(Self::Io),
+            StaticStrings::Csv => Some(Self::Csv),
+            StaticStrings::Copy => Some(Self::Copy),
+            StaticStrings::Heapq => Some(Self::Heapq),
+            StaticStrings::Bisect => Some(Self::Bisect),
+            StaticStrings::Operator => Some(Self::Operator),
+            StaticStrings::Statistics => Some(Self::Statistics),
+            StaticStrings::Struct => Some(Self::Struct),
+            StaticStrings::Pprint => Some(Self::Pprint),
+            StaticStrings::Reprlib => Some(Self::Reprlib),
+            StaticStrings::Uuid => Some(Self::Uuid),
+            StaticStrings::Zlib => Some(Self::Zlib),
+            StaticStrings::Gzip => Some(Self::Gzip),
+            StaticStrings::Shutil => Some(Self::Shutil),
+            StaticStrings::OsPath | StaticStrings::Posixpath => Some(Self::OsPath),
             _ => None,
         }
     }
@@ -75,6 +168,29 @@ impl BuiltinModule {
             Self::Os => os::create_module(vm),
             Self::Math => math::create_module(vm),
             Self::Re => re::create_module(vm),
+            Self::Binascii => binascii::create_module(vm),
+            Self::Base64 => base64::create_module(vm),
+            Self::Hashlib => hashlib::create_module(vm),
+            Self::Hmac => hmac::create_module(vm),
+            Self::String => string::create_module(vm),
+            Self::Textwrap => textwrap::create_module(vm),
+            Self::Difflib => difflib::create_module(vm),
+            Self::Unicodedata => unicodedata::create_module(vm),
+            Self::Io => io::create_module(vm),
+            Self::Csv => csv::create_module(vm),
+            Self::Copy => copy::create_module(vm),
+            Self::Heapq => heapq::create_module(vm),
+            Self::Bisect => bisect::create_module(vm),
+            Self::Operator => operator::create_module(vm),
+            Self::Statistics => statistics::create_module(vm),
+            Self::Struct => r#struct::create_module(vm),
+            Self::Pprint => pprint::create_module(vm),
+            Self::Reprlib => reprlib::create_module(vm),
+            Self::Uuid => uuid::create_module(vm),
+            Self::Zlib => zlib::create_module(vm),
+            Self::Gzip => gzip::create_module(vm),
+            Self::Shutil => shutil::create_module(vm),
+            Self::OsPath => os_path::create_module(vm),
         }
     }
 }
@@ -85,7 +201,29 @@ pub(crate) enum ModuleFunctions {
     Asyncio(asyncio::AsyncioFunctions),
     Math(math::MathFunctions),
     Os(os::OsFunctions),
+    OsPath(os_path::OsPathFunctions),
     Re(re::ReFunctions),
+    Binascii(binascii::BinasciiFunctions),
+    Base64(base64::Base64Functions),
+    Hashlib(hashlib::HashlibFunctions),
+    Hmac(hmac::HmacFunctions),
+    Textwrap(textwrap::TextwrapFunctions),
+    Difflib(difflib::DifflibFunctions),
+    Unicodedata(unicodedata::UnicodedataFunctions),
+    Csv(csv::CsvFunctions),
+    Copy(copy::CopyFunctions),
+    Heapq(heapq::HeapqFunctions),
+    Bisect(bisect::BisectFunctions),
+    Operator(operator::OperatorFunctions),
+    Statistics(statistics::StatisticsFunctions),
+    Struct(r#struct::StructFunctions),
+    Pprint(pprint::PprintFunctions),
+    Reprlib(reprlib::ReprlibFunctions),
+    Uuid(uuid::UuidFunctions),
+    Zlib(zlib::ZlibFunctions),
+    Gzip(gzip::GzipFunctions),
+    Shutil(shutil::ShutilFunctions),
+    Sys(sys::SysFunctions),
 }
 
 impl fmt::Display for ModuleFunctions {
@@ -94,7 +232,29 @@ impl fmt::Display for ModuleFunctions {
             Self::Asyncio(func) => write!(f, "{func}"),
             Self::Math(func) => write!(f, "{func}"),
             Self::Os(func) => write!(f, "{func}"),
+            Self::OsPath(func) => write!(f, "{func}"),
             Self::Re(func) => write!(f, "{func}"),
+            Self::Binascii(func) => write!(f, "{func}"),
+            Self::Base64(func) => write!(f, "{func}"),
+            Self::Hashlib(func) => write!(f, "{func}"),
+            Self::Hmac(func) => write!(f, "{func}"),
+            Self::Textwrap(func) => write!(f, "{func}"),
+            Self::Difflib(func) => write!(f, "{func}"),
+            Self::Unicodedata(func) => write!(f, "{func}"),
+            Self::Csv(func) => write!(f, "{func}"),
+            Self::Copy(func) => write!(f, "{func}"),
+            Self::Heapq(func) => write!(f, "{func}"),
+            Self::Bisect(func) => write!(f, "{func}"),
+            Self::Operator(func) => write!(f, "{func}"),
+            Self::Statistics(func) => write!(f, "{func}"),
+            Self::Struct(func) => write!(f, "{func}"),
+            Self::Pprint(func) => write!(f, "{func}"),
+            Self::Reprlib(func) => write!(f, "{func}"),
+            Self::Uuid(func) => write!(f, "{func}"),
+            Self::Zlib(func) => write!(f, "{func}"),
+            Self::Gzip(func) => write!(f, "{func}"),
+            Self::Shutil(func) => write!(f, "{func}"),
+            Self::Sys(func) => write!(f, "{func}"),
         }
     }
 }
@@ -108,8 +268,30 @@ impl ModuleFunctions {
         match self {
             Self::Asyncio(functions) => asyncio::call(vm.heap, functions, args),
             Self::Math(functions) => math::call(vm, functions, args).map(CallResult::Value),
-            Self::Os(functions) => os::call(vm.heap, functions, args),
+            Self::Os(functions) => os::call(vm, functions, args),
+            Self::OsPath(functions) => os_path::call(vm, functions, args),
             Self::Re(functions) => re::call(vm, functions, args),
+            Self::Binascii(functions) => binascii::call(vm, functions, args).map(CallResult::Value),
+            Self::Base64(functions) => base64::call(vm, functions, args).map(CallResult::Value),
+            Self::Hashlib(functions) => hashlib::call(vm, functions, args).map(CallResult::Value),
+            Self::Hmac(functions) => hmac::call(vm, functions, args).map(CallResult::Value),
+            Self::Textwrap(functions) => textwrap::call(vm, functions, args).map(CallResult::Value),
+            Self::Difflib(functions) => difflib::call(vm, functions, args).map(CallResult::Value),
+            Self::Unicodedata(functions) => unicodedata::call(vm, functions, args).map(CallResult::Value),
+            Self::Csv(functions) => csv::call(vm, functions, args).map(CallResult::Value),
+            Self::Copy(functions) => copy::call(vm, functions, args).map(CallResult::Value),
+            Self::Heapq(functions) => heapq::call(vm, functions, args).map(CallResult::Value),
+            Self::Bisect(functions) => bisect::call(vm, functions, args).map(CallResult::Value),
+            Self::Operator(functions) => operator::call(vm, functions, args).map(CallResult::Value),
+            Self::Statistics(functions) => statistics::call(vm, functions, args).map(CallResult::Value),
+            Self::Struct(functions) => r#struct::call(vm, functions, args).map(CallResult::Value),
+            Self::Pprint(functions) => pprint::call(vm, functions, args).map(CallResult::Value),
+            Self::Reprlib(functions) => reprlib::call(vm, functions, args).map(CallResult::Value),
+            Self::Uuid(functions) => uuid::call(vm, functions, args),
+            Self::Zlib(functions) => zlib::call(vm, functions, args).map(CallResult::Value),
+            Self::Gzip(functions) => gzip::call(vm, functions, args).map(CallResult::Value),
+            Self::Shutil(functions) => shutil::call(vm, functions, args),
+            Self::Sys(functions) => sys::call(vm, functions, args),
         }
     }
 
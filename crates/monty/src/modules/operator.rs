@@ -0,0 +1,267 @@
+//! Implementation of Python's `operator` module.
+//!
+//! ## Implemented functions
+//!
+//! - Arithmetic and bitwise: `add`, `sub`, `mul`, `truediv`, `floordiv`, `mod`, `pow`,
+//!   `neg`, `pos`, `abs`, `invert`/`inv`, `lshift`, `rshift`, `and_`, `or_`, `xor`,
+//!   `index`
+//! - Comparisons and truth: `eq`, `ne`, `lt`, `le`, `gt`, `ge`, `not_`, `truth`, `is_`,
+//!   `is_not`
+//! - Sequences: `contains`, `getitem`, `concat`, `countOf`, `indexOf`
+//! - Callables: `itemgetter`, `attrgetter`, `methodcaller` (see [`OperatorCallable`])
+//!
+//! The operator functions run the same VM helpers as the corresponding expressions (see
+//! [`VM::apply_binary_op`] and [`VM::apply_unary_op`]), so `operator.add(a, b)` behaves
+//! exactly like `a + b`, including its error messages.
+//!
+//! [`OperatorCallable`]: crate::types::OperatorCallable
+
+use crate::{
+    args::ArgValues,
+    builtins::{Builtins, builtin_abs},
+    bytecode::{Opcode, VM},
+    defer_drop, defer_drop_mut,
+    exception_private::{ExcType, RunResult, SimpleException},
+    heap::{HeapData, HeapId},
+    intern::StaticStrings,
+    modules::ModuleFunctions,
+    resource::{ResourceError, ResourceTracker},
+    types::{Module, MontyIter, PyTrait, Type},
+    value::Value,
+};
+
+/// Functions exposed by the `operator` module.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, strum::Display, serde::Serialize, serde::Deserialize)]
+#[strum(serialize_all = "snake_case")]
+pub(crate) enum OperatorFunctions {
+    Add,
+    Sub,
+    Mul,
+    Truediv,
+    Floordiv,
+    Mod,
+    Pow,
+    Neg,
+    Pos,
+    Abs,
+    Invert,
+    Inv,
+    Lshift,
+    Rshift,
+    #[strum(serialize = "and_")]
+    And,
+    #[strum(serialize = "or_")]
+    Or,
+    Xor,
+    Index,
+    Eq,
+    Ne,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+    #[strum(serialize = "not_")]
+    Not,
+    Truth,
+    #[strum(serialize = "is_")]
+    Is,
+    IsNot,
+    Contains,
+    Getitem,
+    Concat,
+    #[strum(serialize = "countOf")]
+    CountOf,
+    #[strum(serialize = "indexOf")]
+    IndexOf,
+}
+
+/// Creates the `operator` module and allocates it on the heap.
+///
+/// # Returns
+/// A `HeapId` pointing to the newly allocated module.
+///
+/// # Panics
+/// Panics if the required strings have not been pre-interned during prepare phase.
+pub fn create_module(vm: &mut VM<'_, '_, impl ResourceTracker>) -> Result<HeapId, ResourceError> {
+    let mut module = Module::new(StaticStrings::Operator);
+
+    let functions = [
+        (StaticStrings::Add, OperatorFunctions::Add),
+        (StaticStrings::Sub, OperatorFunctions::Sub),
+        (StaticStrings::Mul, OperatorFunctions::Mul),
+        (StaticStrings::Truediv, OperatorFunctions::Truediv),
+        (StaticStrings::Floordiv, OperatorFunctions::Floordiv),
+        (StaticStrings::Mod, OperatorFunctions::Mod),
+        (StaticStrings::Pow, OperatorFunctions::Pow),
+        (StaticStrings::Neg, OperatorFunctions::Neg),
+        (StaticStrings::Pos, OperatorFunctions::Pos),
+        (StaticStrings::Abs, OperatorFunctions::Abs),
+        (StaticStrings::Invert, OperatorFunctions::Invert),
+        (StaticStrings::Inv, OperatorFunctions::Inv),
+        (StaticStrings::Lshift, OperatorFunctions::Lshift),
+        (StaticStrings::Rshift, OperatorFunctions::Rshift),
+        (StaticStrings::OperatorAnd, OperatorFunctions::And),
+        (StaticStrings::OperatorOr, OperatorFunctions::Or),
+        (StaticStrings::Xor, OperatorFunctions::Xor),
+        (StaticStrings::Index, OperatorFunctions::Index),
+        (StaticStrings::Eq, OperatorFunctions::Eq),
+        (StaticStrings::Ne, OperatorFunctions::Ne),
+        (StaticStrings::Lt, OperatorFunctions::Lt),
+        (StaticStrings::Le, OperatorFunctions::Le),
+        (StaticStrings::Gt, OperatorFunctions::Gt),
+        (StaticStrings::Ge, OperatorFunctions::Ge),
+        (StaticStrings::OperatorNot, OperatorFunctions::Not),
+        (StaticStrings::Truth, OperatorFunctions::Truth),
+        (StaticStrings::OperatorIs, OperatorFunctions::Is),
+        (StaticStrings::IsNot, OperatorFunctions::IsNot),
+        (StaticStrings::Contains, OperatorFunctions::Contains),
+        (StaticStrings::Getitem, OperatorFunctions::Getitem),
+        (StaticStrings::Concat, OperatorFunctions::Concat),
+        (StaticStrings::CountOf, OperatorFunctions::CountOf),
+        (StaticStrings::IndexOf, OperatorFunctions::IndexOf),
+    ];
+    for (name, function) in functions {
+        module.set_attr(name, Value::ModuleFunction(ModuleFunctions::Operator(function)), vm);
+    }
+
+    let types = [
+        (StaticStrings::Itemgetter, Type::ItemGetter),
+        (StaticStrings::Attrgetter, Type::AttrGetter),
+        (StaticStrings::Methodcaller, Type::MethodCaller),
+    ];
+    for (name, ty) in types {
+        module.set_attr(name, Value::Builtin(Builtins::Type(ty)), vm);
+    }
+
+    vm.heap.allocate(HeapData::Module(module))
+}
+
+/// Dispatches a call to an operator module function.
+pub(super) fn call(
+    vm: &mut VM<'_, '_, impl ResourceTracker>,
+    function: OperatorFunctions,
+    args: ArgValues,
+) -> RunResult<Value> {
+    let binary_op = match function {
+        OperatorFunctions::Add => Some(Opcode::BinaryAdd),
+        OperatorFunctions::Sub => Some(Opcode::BinarySub),
+        OperatorFunctions::Mul => Some(Opcode::BinaryMul),
+        OperatorFunctions::Truediv => Some(Opcode::BinaryDiv),
+        OperatorFunctions::Floordiv => Some(Opcode::BinaryFloorDiv),
+        OperatorFunctions::Mod => Some(Opcode::BinaryMod),
+        OperatorFunctions::Pow => Some(Opcode::BinaryPow),
+        OperatorFunctions::Lshift => Some(Opcode::BinaryLShift),
+        OperatorFunctions::Rshift => Some(Opcode::BinaryRShift),
+        OperatorFunctions::And => Some(Opcode::BinaryAnd),
+        OperatorFunctions::Or => Some(Opcode::BinaryOr),
+        OperatorFunctions::Xor => Some(Opcode::BinaryXor),
+        OperatorFunctions::Eq => Some(Opcode::CompareEq),
+        OperatorFunctions::Ne => Some(Opcode::CompareNe),
+        OperatorFunctions::Lt => Some(Opcode::CompareLt),
+        OperatorFunctions::Le => Some(Opcode::CompareLe),
+        OperatorFunctions::Gt => Some(Opcode::CompareGt),
+        OperatorFunctions::Ge => Some(Opcode::CompareGe),
+        _ => None,
+    };
+    if let Some(op) = binary_op {
+        let (a, b) = args.get_two_args(&function.to_string(), vm.heap)?;
+        return vm.apply_binary_op(op, a, b);
+    }
+
+    let unary_op = match function {
+        OperatorFunctions::Neg => Some(Opcode::UnaryNeg),
+        OperatorFunctions::Pos => Some(Opcode::UnaryPos),
+        OperatorFunctions::Invert | OperatorFunctions::Inv => Some(Opcode::UnaryInvert),
+        OperatorFunctions::Not => Some(Opcode::UnaryNot),
+        _ => None,
+    };
+    if let Some(op) = unary_op {
+        let a = args.get_one_arg(&function.to_string(), vm.heap)?;
+        return vm.apply_unary_op(op, a);
+    }
+
+    match function {
+        OperatorFunctions::Abs => builtin_abs(vm, args),
+        OperatorFunctions::Index => {
+            let a = args.get_one_arg("index", vm.heap)?;
+            defer_drop!(a, vm);
+            match a {
+                Value::Bool(b) => Ok(Value::Int(i64::from(*b))),
+                Value::Int(_) => Ok(a.clone_with_heap(vm)),
+                Value::Ref(id) if matches!(vm.heap.get(*id), HeapData::LongInt(_)) => Ok(a.clone_with_heap(vm)),
+                _ => Err(ExcType::type_error(format!(
+                    "'{}' object cannot be interpreted as an integer",
+                    a.py_type(vm.heap)
+                ))),
+            }
+        }
+        OperatorFunctions::Truth => {
+            let a = args.get_one_arg("truth", vm.heap)?;
+            defer_drop!(a, vm);
+            Ok(Value::Bool(a.py_bool(vm)))
+        }
+        OperatorFunctions::Is | OperatorFunctions::IsNot => {
+            let (a, b) = args.get_two_args(&function.to_string(), vm.heap)?;
+            defer_drop!(a, vm);
+            defer_drop!(b, vm);
+            Ok(Value::Bool(a.is(b) == (function == OperatorFunctions::Is)))
+        }
+        OperatorFunctions::Contains => {
+            // `contains(a, b)` is `b in a`
+            let (a, b) = args.get_two_args("contains", vm.heap)?;
+            vm.apply_binary_op(Opcode::CompareIn, b, a)
+        }
+        OperatorFunctions::Getitem => {
+            let (a, b) = args.get_two_args("getitem", vm.heap)?;
+            defer_drop!(a, vm);
+            defer_drop!(b, vm);
+            a.py_getitem(b, vm)
+        }
+        OperatorFunctions::Concat => {
+            let (a, b) = args.get_two_args("concat", vm.heap)?;
+            if !is_sequence(&a, vm) {
+                let type_name = a.py_type(vm.heap);
+                a.drop_with_heap(vm);
+                b.drop_with_heap(vm);
+                return Err(ExcType::type_error(format!(
+                    "'{type_name}' object can't be concatenated"
+                )));
+            }
+            vm.apply_binary_op(Opcode::BinaryAdd, a, b)
+        }
+        OperatorFunctions::CountOf | OperatorFunctions::IndexOf => {
+            let (a, b) = args.get_two_args(&function.to_string(), vm.heap)?;
+            defer_drop!(b, vm);
+            let iter = MontyIter::new(a, vm)?;
+            defer_drop_mut!(iter, vm);
+            let mut index = 0;
+            let mut count = 0;
+            while let Some(item) = iter.for_next(vm)? {
+                defer_drop!(item, vm);
+                // Identity implies equality, as in CPython's containment checks
+                if item.is(b) || item.py_eq(b, vm)? {
+                    if function == OperatorFunctions::IndexOf {
+                        return Ok(Value::Int(index));
+                    }
+                    count += 1;
+                }
+                index += 1;
+            }
+            if function == OperatorFunctions::IndexOf {
+                return Err(
+                    SimpleException::new_msg(ExcType::ValueError, "sequence.index(x): x not in sequence").into(),
+                );
+            }
+            Ok(Value::Int(count))
+        }
+        _ => unreachable!("binary and unary operator functions are handled above"),
+    }
+}
+
+/// Returns whether `value` is a sequence that `concat()` accepts.
+fn is_sequence(value: &Value, vm: &VM<'_, '_, impl ResourceTracker>) -> bool {
+    matches!(
+        value.py_type(vm.heap),
+        Type::Str | Type::Bytes | Type::List | Type::Tuple | Type::NamedTuple | Type::Range
+    )
+}
@@ -1,23 +1,36 @@
 //! Implementation of the `os` module.
 //!
-//! Provides a minimal implementation of Python's `os` module with:
+//! Provides a partial implementation of Python's `os` module with:
 //! - `getenv(key, default=None)`: Get a single environment variable
 //! - `environ`: Property that returns the entire environment as a dict
+//! - `urandom(size)`: Get `size` random bytes from the host
+//! - `walk(top, topdown=True, onerror=None, followlinks=False)`: Walk a directory tree
+//! - `getcwd()`, `listdir(path='.')`, `stat(path)`
+//! - `mkdir(path, mode=0o777)`, `makedirs(name, mode=0o777, exist_ok=False)`
+//! - `remove(path)`, `unlink(path)`, `rmdir(path)`, `rename(src, dst)`, `replace(src, dst)`
+//! - `fspath(path)`, and the `name`, `sep`, `curdir`, `pardir` and `linesep` constants
+//! - `path`: the `os.path` module (see `os_path`)
 //!
 //! Other os functions are not implemented. OS operations require host involvement
 //! via the `OsFunction` callback mechanism - Monty yields control to the host
-//! which executes the operation and returns the result.
+//! which executes the operation and returns the result. The filesystem functions
+//! yield the same calls as the matching `Path` methods, so hosts need no extra handling.
 
 use crate::{
-    args::ArgValues,
+    args::{ArgValues, KwargsValues},
     bytecode::{CallResult, VM},
-    exception_private::{ExcType, RunResult},
-    heap::{Heap, HeapData, HeapId},
-    intern::StaticStrings,
-    modules::ModuleFunctions,
-    os::OsFunction,
+    defer_drop,
+    exception_private::{ExcType, RunResult, SimpleException},
+    heap::{DropWithHeap, Heap, HeapData, HeapId},
+    intern::{StaticStrings, StringId},
+    modules::{ModuleFunctions, os_path},
+    os::{OsFunction, OsResultConversion},
     resource::{ResourceError, ResourceTracker},
-    types::{Module, Property, PyTrait},
+    types::{
+        List, Module, Property, PyTrait,
+        path::{Path, fspath_arg, path_os_call},
+        str::allocate_string,
+    },
     value::Value,
 };
 
@@ -26,6 +39,19 @@ use crate::{
 #[strum(serialize_all = "lowercase")]
 pub(crate) enum OsFunctions {
     Getenv,
+    Urandom,
+    Walk,
+    Getcwd,
+    Listdir,
+    Stat,
+    Mkdir,
+    Makedirs,
+    Remove,
+    Unlink,
+    Rmdir,
+    Rename,
+    Replace,
+    Fspath,
 }
 
 /// Creates the `os` module and allocates it on the heap.
@@ -33,8 +59,13 @@ pub(crate) enum OsFunctions {
 /// The module provides:
 /// - `getenv(key, default=None)`: Get a single environment variable
 /// - `environ`: Property that returns the entire environment as a dict
+/// - `urandom(size)`: Get `size` random bytes from the host
+/// - `walk(top, topdown=True, onerror=None, followlinks=False)`: Walk a directory tree
+/// - the filesystem functions `getcwd()`, `listdir()`, `stat()`, `mkdir()`, `makedirs()`,
+///   `remove()`, `unlink()`, `rmdir()`, `rename()` and `replace()`
+/// - `fspath()`, the path constants, and `path` (the `os.path` module)
 ///
-/// Both operations yield to the host via `OsFunction` callbacks.
+/// All operations except `fspath()` yield to the host via `OsFunction` callbacks.
 ///
 /// # Returns
 /// A HeapId pointing to the newly allocated module.
@@ -51,6 +82,38 @@ pub fn create_module(vm: &mut VM<'_, '_, impl ResourceTracker>) -> Result<HeapId
         vm,
     );
 
+    // os.urandom - function to get random bytes from the host
+    module.set_attr(
+        StaticStrings::Urandom,
+        Value::ModuleFunction(ModuleFunctions::Os(OsFunctions::Urandom)),
+        vm,
+    );
+
+    // os.walk - function to walk a directory tree on the host
+    module.set_attr(
+        StaticStrings::Walk,
+        Value::ModuleFunction(ModuleFunctions::Os(OsFunctions::Walk)),
+        vm,
+    );
+
+    // filesystem functions, yielding the same calls as the matching `Path` methods
+    let functions = [
+        (StaticStrings::Getcwd, OsFunctions::Getcwd),
+        (StaticStrings::Listdir, OsFunctions::Listdir),
+        (StaticStrings::StatMethod, OsFunctions::Stat),
+        (StaticStrings::Mkdir, OsFunctions::Mkdir),
+        (StaticStrings::Makedirs, OsFunctions::Makedirs),
+        (StaticStrings::Remove, OsFunctions::Remove),
+        (StaticStrings::Unlink, OsFunctions::Unlink),
+        (StaticStrings::Rmdir, OsFunctions::Rmdir),
+        (StaticStrings::Rename, OsFunctions::Rename),
+        (StaticStrings::Replace, OsFunctions::Replace),
+        (StaticStrings::FspathFunc, OsFunctions::Fspath),
+    ];
+    for (name, function) in functions {
+        module.set_attr(name, Value::ModuleFunction(ModuleFunctions::Os(function)), vm);
+    }
+
     // os.environ - property that returns the entire environment as a dict
     module.set_attr(
         StaticStrings::Environ,
@@ -58,6 +121,25 @@ pub fn create_module(vm: &mut VM<'_, '_, impl ResourceTracker>) -> Result<HeapId
         vm,
     );
 
+    // path constants for a POSIX host
+    module.set_attr(StaticStrings::Name, StaticStrings::Posix.into(), vm);
+    module.set_attr(StaticStrings::Sep, Value::InternString(StringId::from_ascii(b'/')), vm);
+    module.set_attr(
+        StaticStrings::Curdir,
+        Value::InternString(StringId::from_ascii(b'.')),
+        vm,
+    );
+    module.set_attr(StaticStrings::Pardir, StaticStrings::ParentDir.into(), vm);
+    module.set_attr(
+        StaticStrings::Linesep,
+        Value::InternString(StringId::from_ascii(b'\n')),
+        vm,
+    );
+
+    // os.path - the posixpath module
+    let path_id = os_path::create_module(vm)?;
+    module.set_attr(StaticStrings::PathAttr, Value::Ref(path_id), vm);
+
     vm.heap.allocate(HeapData::Module(module))
 }
 
@@ -66,12 +148,25 @@ pub fn create_module(vm: &mut VM<'_, '_, impl ResourceTracker>) -> Result<HeapId
 /// Returns `CallResult::OsCall` for functions that need host involvement,
 /// or `CallResult::Value` for functions that can be computed immediately.
 pub(super) fn call(
-    heap: &mut Heap<impl ResourceTracker>,
+    vm: &mut VM<'_, '_, impl ResourceTracker>,
     functions: OsFunctions,
     args: ArgValues,
 ) -> RunResult<CallResult> {
     match functions {
-        OsFunctions::Getenv => getenv(heap, args),
+        OsFunctions::Getenv => getenv(vm.heap, args),
+        OsFunctions::Urandom => urandom(vm.heap, args),
+        OsFunctions::Walk => walk(vm, args),
+        OsFunctions::Getcwd => getcwd(vm, args),
+        OsFunctions::Listdir => listdir(vm, args),
+        OsFunctions::Stat => path_os_call(OsFunction::Stat, "stat", ["path"], args, vm.heap, vm.interns),
+        OsFunctions::Mkdir => path_os_call(OsFunction::Mkdir, "mkdir", ["path"], args, vm.heap, vm.interns),
+        OsFunctions::Makedirs => makedirs(vm, args),
+        OsFunctions::Remove => path_os_call(OsFunction::Unlink, "remove", ["path"], args, vm.heap, vm.interns),
+        OsFunctions::Unlink => path_os_call(OsFunction::Unlink, "unlink", ["path"], args, vm.heap, vm.interns),
+        OsFunctions::Rmdir => path_os_call(OsFunction::Rmdir, "rmdir", ["path"], args, vm.heap, vm.interns),
+        OsFunctions::Rename => rename(vm, "rename", args),
+        OsFunctions::Replace => rename(vm, "replace", args),
+        OsFunctions::Fspath => fspath(vm, args),
     }
 }
 
@@ -114,3 +209,150 @@ fn getenv(heap: &mut Heap<impl ResourceTracker>, args: ArgValues) -> RunResult<C
         Err(ExcType::type_error(format!("str expected, not {type_name}")))
     }
 }
+
+/// Implementation of `os.urandom(size)`.
+///
+/// Yields to the host, which should return `size` bytes from its entropy source.
+///
+/// # Errors
+/// Returns `TypeError` if `size` is not an integer, or `ValueError` if it is negative.
+fn urandom(heap: &mut Heap<impl ResourceTracker>, args: ArgValues) -> RunResult<CallResult> {
+    let size = args.get_one_arg("os.urandom", heap)?;
+    let n = match size {
+        Value::Int(n) => n,
+        Value::Bool(b) => i64::from(b),
+        _ => {
+            let type_name = size.py_type(heap);
+            size.drop_with_heap(heap);
+            return Err(ExcType::type_error(format!(
+                "'{type_name}' object cannot be interpreted as an integer"
+            )));
+        }
+    };
+    if n < 0 {
+        return Err(SimpleException::new_msg(ExcType::ValueError, "negative argument not allowed").into());
+    }
+    Ok(CallResult::OsCall(OsFunction::Urandom, ArgValues::One(Value::Int(n))))
+}
+
+/// Implementation of `os.walk(top, topdown=True, onerror=None, followlinks=False)`.
+///
+/// Yields `OsFunction::Walk` with `top` as a `Path` and any other arguments as given. The host
+/// returns the whole walk as a list of `(dirpath, dirnames, filenames)` tuples rather than a
+/// generator, so pruning `dirnames` in place doesn't stop the walk descending into them.
+fn walk(vm: &mut VM<'_, '_, impl ResourceTracker>, args: ArgValues) -> RunResult<CallResult> {
+    path_os_call(OsFunction::Walk, "walk", ["top"], args, vm.heap, vm.interns)
+}
+
+/// Implementation of `os.getcwd()`.
+///
+/// Yields `OsFunction::Absolute` for `Path('.')`, the same call as `Path.cwd()` would need.
+fn getcwd(vm: &mut VM<'_, '_, impl ResourceTracker>, args: ArgValues) -> RunResult<CallResult> {
+    args.check_zero_args("os.getcwd", vm.heap)?;
+    let cwd = vm.heap.allocate(HeapData::Path(Path::new(".".to_owned())))?;
+    vm.set_os_result_conversion(OsResultConversion::Normpath);
+    Ok(CallResult::OsCall(
+        OsFunction::Absolute,
+        ArgValues::One(Value::Ref(cwd)),
+    ))
+}
+
+/// Implementation of `os.listdir(path='.')`.
+///
+/// Yields `OsFunction::Iterdir`; the VM turns the paths the host returns into names.
+fn listdir(vm: &mut VM<'_, '_, impl ResourceTracker>, args: ArgValues) -> RunResult<CallResult> {
+    let path = match args.get_zero_one_arg("listdir", vm.heap)? {
+        Some(value) => {
+            defer_drop!(value, vm);
+            fspath_arg(value, vm.heap, vm.interns)?
+        }
+        None => ".".to_owned(),
+    };
+    let path = vm.heap.allocate(HeapData::Path(Path::new(path)))?;
+    vm.set_os_result_conversion(OsResultConversion::Listdir);
+    Ok(CallResult::OsCall(
+        OsFunction::Iterdir,
+        ArgValues::One(Value::Ref(path)),
+    ))
+}
+
+/// Builds the result of `os.listdir()` from the list of paths the host returned for
+/// `OsFunction::Iterdir`, keeping just the name of each entry.
+///
+/// Implements `OsResultConversion::Listdir`.
+pub(crate) fn listdir_from(value: Value, vm: &mut VM<'_, '_, impl ResourceTracker>) -> RunResult<Value> {
+    defer_drop!(value, vm);
+    let paths: Option<Result<Vec<String>, _>> = match value {
+        Value::Ref(id) => match vm.heap.get(*id) {
+            HeapData::List(list) => Some(
+                list.as_slice()
+                    .iter()
+                    .map(|path| fspath_arg(path, vm.heap, vm.interns))
+                    .collect(),
+            ),
+            _ => None,
+        },
+        _ => None,
+    };
+    let Some(Ok(paths)) = paths else {
+        return Err(ExcType::type_error(format!(
+            "Path.iterdir() result must be a list of paths, not {}",
+            value.py_type(vm.heap)
+        )));
+    };
+    let mut names = Vec::with_capacity(paths.len());
+    for path in paths {
+        match allocate_string(os_path::split(&path).1.to_owned(), vm.heap) {
+            Ok(name) => names.push(name),
+            Err(err) => {
+                names.drop_with_heap(vm.heap);
+                return Err(err);
+            }
+        }
+    }
+    Ok(Value::Ref(vm.heap.allocate(HeapData::List(List::new(names)))?))
+}
+
+/// Implementation of `os.makedirs(name, mode=0o777, exist_ok=False)`.
+///
+/// Yields `OsFunction::Mkdir` with `parents=True`, as for `Path.mkdir(parents=True)`.
+fn makedirs(vm: &mut VM<'_, '_, impl ResourceTracker>, args: ArgValues) -> RunResult<CallResult> {
+    let params = args.bind_params("makedirs", ["name", "mode", "exist_ok"], 1, vm.heap, vm.interns)?;
+    defer_drop!(params, vm);
+    let [name, mode, exist_ok] = params;
+    let path = fspath_arg(name.as_ref().expect("required argument"), vm.heap, vm.interns)?;
+    let exist_ok = exist_ok.as_ref().is_some_and(|value| value.py_bool(vm));
+
+    let path = Value::Ref(vm.heap.allocate(HeapData::Path(Path::new(path)))?);
+
+    let mut kwargs: Vec<(StringId, Value)> = Vec::with_capacity(3);
+    if let Some(mode) = mode {
+        kwargs.push((StaticStrings::Mode.into(), mode.clone_with_heap(vm.heap)));
+    }
+    kwargs.push((StaticStrings::Parents.into(), Value::Bool(true)));
+    kwargs.push((StaticStrings::ExistOk.into(), Value::Bool(exist_ok)));
+    Ok(CallResult::OsCall(
+        OsFunction::Mkdir,
+        ArgValues::ArgsKargs {
+            args: vec![path],
+            kwargs: KwargsValues::Inline(kwargs),
+        },
+    ))
+}
+
+/// Implementation of `os.rename(src, dst)` and `os.replace(src, dst)`.
+///
+/// Yields `OsFunction::Rename`, discarding the new path the host returns for `Path.rename()`.
+fn rename(vm: &mut VM<'_, '_, impl ResourceTracker>, name: &str, args: ArgValues) -> RunResult<CallResult> {
+    let call = path_os_call(OsFunction::Rename, name, ["src", "dst"], args, vm.heap, vm.interns)?;
+    vm.set_os_result_conversion(OsResultConversion::Discard);
+    Ok(call)
+}
+
+/// Implementation of `os.fspath(path)`, returning the `str` form of a `str` or `Path`.
+fn fspath(vm: &mut VM<'_, '_, impl ResourceTracker>, args: ArgValues) -> RunResult<CallResult> {
+    let value = args.get_one_arg("fspath", vm.heap)?;
+    defer_drop!(value, vm);
+    let path = fspath_arg(value, vm.heap, vm.interns)?;
+    Ok(CallResult::Value(allocate_string(path, vm.heap)?))
+}
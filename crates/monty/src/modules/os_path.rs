@@ -0,0 +1,376 @@
+//! Implementation of the `os.path` module (`posixpath`).
+//!
+//! The string operations are pure and computed here, matching CPython's `posixpath`:
+//! - `join(a, *p)`, `split(p)`, `splitext(p)`, `basename(p)`, `dirname(p)`
+//! - `isabs(s)`, `normpath(path)`, `normcase(s)`, and `abspath(path)` for absolute paths
+//! - `sep`, `curdir` and `pardir` constants
+//!
+//! The filesystem queries yield the same `OsFunction` calls as the matching `Path` methods,
+//! so hosts need no extra handling:
+//! - `exists(path)`, `lexists(path)`, `isfile(path)`, `isdir(path)`, `islink(path)`
+//! - `getsize(filename)`, `getmtime(filename)` via `OsFunction::Stat`
+//! - `abspath(path)` for relative paths via `OsFunction::Absolute`, `realpath(path)` via `OsFunction::Resolve`
+
+use smallvec::smallvec;
+
+use crate::{
+    args::{ArgValues, KwargsValues},
+    bytecode::{CallResult, VM},
+    defer_drop,
+    exception_private::{ExcType, RunResult},
+    heap::{DropWithHeap, HeapData, HeapId},
+    intern::{StaticStrings, StringId},
+    modules::ModuleFunctions,
+    os::{OsFunction, OsResultConversion},
+    resource::{ResourceError, ResourceTracker},
+    types::{
+        Module, PyTrait, allocate_tuple,
+        path::{Path, fspath_arg},
+        str::allocate_string,
+    },
+    value::Value,
+};
+
+/// os.path module functions.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, strum::Display, serde::Serialize, serde::Deserialize)]
+#[strum(serialize_all = "lowercase")]
+pub(crate) enum OsPathFunctions {
+    Join,
+    Split,
+    Splitext,
+    Basename,
+    Dirname,
+    Isabs,
+    Normpath,
+    Normcase,
+    Abspath,
+    Realpath,
+    Exists,
+    Lexists,
+    Isfile,
+    Isdir,
+    Islink,
+    Getsize,
+    Getmtime,
+}
+
+/// Creates the `os.path` module and allocates it on the heap.
+///
+/// Like CPython's, the module is named `posixpath`; `import os.path` and the `path`
+/// attribute of `os` both refer to it.
+///
+/// # Returns
+/// A HeapId pointing to the newly allocated module.
+///
+/// # Panics
+/// Panics if the required strings have not been pre-interned during prepare phase.
+pub fn create_module(vm: &mut VM<'_, '_, impl ResourceTracker>) -> Result<HeapId, ResourceError> {
+    let mut module = Module::new(StaticStrings::Posixpath);
+
+    let functions = [
+        (StaticStrings::Join, OsPathFunctions::Join),
+        (StaticStrings::Split, OsPathFunctions::Split),
+        (StaticStrings::Splitext, OsPathFunctions::Splitext),
+        (StaticStrings::Basename, OsPathFunctions::Basename),
+        (StaticStrings::Dirname, OsPathFunctions::Dirname),
+        (StaticStrings::Isabs, OsPathFunctions::Isabs),
+        (StaticStrings::Normpath, OsPathFunctions::Normpath),
+        (StaticStrings::Normcase, OsPathFunctions::Normcase),
+        (StaticStrings::Abspath, OsPathFunctions::Abspath),
+        (StaticStrings::Realpath, OsPathFunctions::Realpath),
+        (StaticStrings::Exists, OsPathFunctions::Exists),
+        (StaticStrings::Lexists, OsPathFunctions::Lexists),
+        (StaticStrings::Isfile, OsPathFunctions::Isfile),
+        (StaticStrings::Isdir, OsPathFunctions::Isdir),
+        (StaticStrings::Islink, OsPathFunctions::Islink),
+        (StaticStrings::Getsize, OsPathFunctions::Getsize),
+        (StaticStrings::Getmtime, OsPathFunctions::Getmtime),
+    ];
+    for (name, function) in functions {
+        module.set_attr(name, Value::ModuleFunction(ModuleFunctions::OsPath(function)), vm);
+    }
+
+    module.set_attr(StaticStrings::Sep, Value::InternString(StringId::from_ascii(b'/')), vm);
+    module.set_attr(
+        StaticStrings::Curdir,
+        Value::InternString(StringId::from_ascii(b'.')),
+        vm,
+    );
+    module.set_attr(StaticStrings::Pardir, StaticStrings::ParentDir.into(), vm);
+
+    vm.heap.allocate(HeapData::Module(module))
+}
+
+/// Dispatches a call to an os.path module function.
+///
+/// Returns `CallResult::OsCall` for the filesystem queries, `CallResult::Value` otherwise.
+pub(super) fn call(
+    vm: &mut VM<'_, '_, impl ResourceTracker>,
+    function: OsPathFunctions,
+    args: ArgValues,
+) -> RunResult<CallResult> {
+    match function {
+        OsPathFunctions::Join => join_args(vm, args).map(CallResult::Value),
+        OsPathFunctions::Split => {
+            let path = path_arg(vm, "split", args)?;
+            let (head, tail) = split(&path);
+            str_pair(vm, head, tail).map(CallResult::Value)
+        }
+        OsPathFunctions::Splitext => {
+            let path = path_arg(vm, "splitext", args)?;
+            let (root, ext) = splitext(&path);
+            str_pair(vm, root, ext).map(CallResult::Value)
+        }
+        OsPathFunctions::Basename => {
+            let path = path_arg(vm, "basename", args)?;
+            Ok(CallResult::Value(allocate_string(split(&path).1.to_owned(), vm.heap)?))
+        }
+        OsPathFunctions::Dirname => {
+            let path = path_arg(vm, "dirname", args)?;
+            Ok(CallResult::Value(allocate_string(split(&path).0.to_owned(), vm.heap)?))
+        }
+        OsPathFunctions::Isabs => Ok(CallResult::Value(Value::Bool(
+            path_arg(vm, "isabs", args)?.starts_with('/'),
+        ))),
+        OsPathFunctions::Normpath => {
+            let path = path_arg(vm, "normpath", args)?;
+            Ok(CallResult::Value(allocate_string(normpath(&path), vm.heap)?))
+        }
+        // POSIX paths are case-sensitive, so there is nothing to normalize
+        OsPathFunctions::Normcase => {
+            let path = path_arg(vm, "normcase", args)?;
+            Ok(CallResult::Value(allocate_string(path, vm.heap)?))
+        }
+        OsPathFunctions::Abspath => {
+            let path = path_arg(vm, "abspath", args)?;
+            if path.starts_with('/') {
+                return Ok(CallResult::Value(allocate_string(normpath(&path), vm.heap)?));
+            }
+            let path = if path.is_empty() { ".".to_owned() } else { path };
+            converted_path_call(vm, OsFunction::Absolute, path, OsResultConversion::Normpath)
+        }
+        OsPathFunctions::Realpath => {
+            let path = path_arg(vm, "realpath", args)?;
+            converted_path_call(vm, OsFunction::Resolve, path, OsResultConversion::Normpath)
+        }
+        OsPathFunctions::Exists => {
+            let path = path_arg(vm, "exists", args)?;
+            path_call(vm, OsFunction::Exists, path, Vec::new())
+        }
+        OsPathFunctions::Lexists => {
+            let path = path_arg(vm, "lexists", args)?;
+            let kwargs = vec![(StaticStrings::FollowSymlinks.into(), Value::Bool(false))];
+            path_call(vm, OsFunction::Exists, path, kwargs)
+        }
+        OsPathFunctions::Isfile => {
+            let path = path_arg(vm, "isfile", args)?;
+            path_call(vm, OsFunction::IsFile, path, Vec::new())
+        }
+        OsPathFunctions::Isdir => {
+            let path = path_arg(vm, "isdir", args)?;
+            path_call(vm, OsFunction::IsDir, path, Vec::new())
+        }
+        OsPathFunctions::Islink => {
+            let path = path_arg(vm, "islink", args)?;
+            path_call(vm, OsFunction::IsSymlink, path, Vec::new())
+        }
+        OsPathFunctions::Getsize => {
+            let path = path_arg(vm, "getsize", args)?;
+            let conversion = OsResultConversion::StatField {
+                field: StaticStrings::StSize,
+            };
+            converted_path_call(vm, OsFunction::Stat, path, conversion)
+        }
+        OsPathFunctions::Getmtime => {
+            let path = path_arg(vm, "getmtime", args)?;
+            let conversion = OsResultConversion::StatField {
+                field: StaticStrings::StMtime,
+            };
+            converted_path_call(vm, OsFunction::Stat, path, conversion)
+        }
+    }
+}
+
+/// Splits a path into `(head, tail)` where `tail` is everything after the last `/`.
+///
+/// Trailing slashes are stripped from `head` unless it is the root (one or more slashes only).
+pub(crate) fn split(path: &str) -> (&str, &str) {
+    let index = path.rfind('/').map_or(0, |i| i + 1);
+    let (head, tail) = path.split_at(index);
+    let trimmed = head.trim_end_matches('/');
+    if trimmed.is_empty() {
+        (head, tail)
+    } else {
+        (trimmed, tail)
+    }
+}
+
+/// Splits the extension from a path, returning `(root, ext)` with `root + ext == path`.
+///
+/// Leading dots of the final component are not treated as an extension separator.
+pub(crate) fn splitext(path: &str) -> (&str, &str) {
+    let name_start = path.rfind('/').map_or(0, |i| i + 1);
+    if let Some(dot) = path.rfind('.')
+        && dot >= name_start
+        && path[name_start..dot].bytes().any(|b| b != b'.')
+    {
+        return path.split_at(dot);
+    }
+    (path, "")
+}
+
+/// Joins `other` onto `path`, inserting a `/` if needed; an absolute `other` replaces `path`.
+pub(crate) fn join(path: &mut String, other: &str) {
+    if other.starts_with('/') {
+        other.clone_into(path);
+    } else {
+        if !path.is_empty() && !path.ends_with('/') {
+            path.push('/');
+        }
+        path.push_str(other);
+    }
+}
+
+/// Normalizes a path, collapsing redundant separators and resolving `.` and `..` lexically.
+///
+/// As in POSIX, a path starting with exactly two slashes keeps both.
+pub(crate) fn normpath(path: &str) -> String {
+    if path.is_empty() {
+        return ".".to_owned();
+    }
+    let initial_slashes = if path.starts_with("//") && !path.starts_with("///") {
+        2
+    } else {
+        usize::from(path.starts_with('/'))
+    };
+    let mut parts: Vec<&str> = Vec::new();
+    for part in path.split('/') {
+        match part {
+            "" | "." => {}
+            ".." if initial_slashes > 0 && parts.is_empty() => {}
+            ".." if parts.last().is_some_and(|last| *last != "..") => {
+                parts.pop();
+            }
+            _ => parts.push(part),
+        }
+    }
+    let normalized = "/".repeat(initial_slashes) + &parts.join("/");
+    if normalized.is_empty() {
+        ".".to_owned()
+    } else {
+        normalized
+    }
+}
+
+/// Builds the result of `abspath()` or `realpath()` from the host's `OsFunction::Absolute`
+/// or `OsFunction::Resolve` result.
+///
+/// Implements `OsResultConversion::Normpath`; the host may return a `str` or a `Path`.
+pub(crate) fn normpath_from(value: Value, vm: &mut VM<'_, '_, impl ResourceTracker>) -> RunResult<Value> {
+    defer_drop!(value, vm);
+    let Ok(path) = fspath_arg(value, vm.heap, vm.interns) else {
+        return Err(ExcType::type_error(format!(
+            "path result must be str or Path, not {}",
+            value.py_type(vm.heap)
+        )));
+    };
+    allocate_string(normpath(&path), vm.heap)
+}
+
+/// Takes `field` from the `stat_result` the host returned for `OsFunction::Stat`.
+///
+/// Implements `OsResultConversion::StatField` for `getsize()` and `getmtime()`.
+pub(crate) fn stat_field_from(
+    value: Value,
+    field: StaticStrings,
+    vm: &mut VM<'_, '_, impl ResourceTracker>,
+) -> RunResult<Value> {
+    defer_drop!(value, vm);
+    let name: &'static str = field.into();
+    if let Value::Ref(id) = value
+        && let HeapData::NamedTuple(stat) = vm.heap.get(*id)
+        && let Some(item) = stat.get_by_name(name, vm.interns)
+    {
+        return Ok(item.clone_with_heap(vm.heap));
+    }
+    Err(ExcType::type_error(format!(
+        "stat() result must be a stat_result with {name}, not {}",
+        value.py_type(vm.heap)
+    )))
+}
+
+/// Takes the single path argument of an os.path function as a string.
+fn path_arg(vm: &mut VM<'_, '_, impl ResourceTracker>, name: &str, args: ArgValues) -> RunResult<String> {
+    let value = args.get_one_arg(name, vm.heap)?;
+    defer_drop!(value, vm);
+    fspath_arg(value, vm.heap, vm.interns)
+}
+
+/// Builds an OS call on `path`, converted to a `Path` as for the matching `Path` method.
+fn path_call(
+    vm: &mut VM<'_, '_, impl ResourceTracker>,
+    function: OsFunction,
+    path: String,
+    kwargs: Vec<(StringId, Value)>,
+) -> RunResult<CallResult> {
+    let path = Value::Ref(vm.heap.allocate(HeapData::Path(Path::new(path)))?);
+    let args = if kwargs.is_empty() {
+        ArgValues::One(path)
+    } else {
+        ArgValues::ArgsKargs {
+            args: vec![path],
+            kwargs: KwargsValues::Inline(kwargs),
+        }
+    };
+    Ok(CallResult::OsCall(function, args))
+}
+
+/// Builds an OS call on `path` whose result the VM converts with `conversion`.
+fn converted_path_call(
+    vm: &mut VM<'_, '_, impl ResourceTracker>,
+    function: OsFunction,
+    path: String,
+    conversion: OsResultConversion,
+) -> RunResult<CallResult> {
+    let call = path_call(vm, function, path, Vec::new())?;
+    vm.set_os_result_conversion(conversion);
+    Ok(call)
+}
+
+/// Allocates a `(str, str)` tuple, as returned by `split()` and `splitext()`.
+fn str_pair(vm: &mut VM<'_, '_, impl ResourceTracker>, first: &str, second: &str) -> RunResult<Value> {
+    let first = allocate_string(first.to_owned(), vm.heap)?;
+    let second = match allocate_string(second.to_owned(), vm.heap) {
+        Ok(second) => second,
+        Err(err) => {
+            first.drop_with_heap(vm.heap);
+            return Err(err);
+        }
+    };
+    Ok(allocate_tuple(smallvec![first, second], vm.heap)?)
+}
+
+/// Implementation of `os.path.join(a, *p)`.
+fn join_args(vm: &mut VM<'_, '_, impl ResourceTracker>, args: ArgValues) -> RunResult<Value> {
+    let (pos, kwargs) = args.into_parts();
+    let parts: Vec<Value> = pos.collect();
+    defer_drop!(parts, vm);
+    if !kwargs.is_empty() {
+        kwargs.drop_with_heap(vm.heap);
+        return Err(ExcType::type_error("join() takes no keyword arguments"));
+    }
+    if parts.is_empty() {
+        return Err(ExcType::type_error_missing_positional_with_names("join", &["a"]));
+    }
+    let mut path = String::new();
+    for value in parts {
+        let Ok(part) = fspath_arg(value, vm.heap, vm.interns) else {
+            return Err(ExcType::type_error(format!(
+                "join() argument must be str, bytes, or os.PathLike object, not '{}'",
+                value.py_type(vm.heap)
+            )));
+        };
+        join(&mut path, &part);
+    }
+    allocate_string(path, vm.heap)
+}
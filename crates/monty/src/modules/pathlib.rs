@@ -2,6 +2,7 @@
 //!
 //! Provides a minimal implementation of Python's `pathlib` module with:
 //! - `Path`: A class for filesystem path operations
+//! - `PurePath`, `PurePosixPath` and `PosixPath`: aliases of `Path`, as every path is POSIX
 //!
 //! The `Path` class supports both pure methods (no I/O, handled directly) and
 //! filesystem methods (require I/O, yield external function calls for host resolution).
@@ -28,6 +29,14 @@ pub fn create_module(vm: &mut VM<'_, '_, impl ResourceTracker>) -> Result<HeapId
 
     // pathlib.Path - the Path class (callable to create Path instances)
     module.set_attr(StaticStrings::PathClass, Value::Builtin(Builtins::Type(Type::Path)), vm);
+    // POSIX paths are the only flavour, so the pure and concrete classes are all `Path`
+    for name in [
+        StaticStrings::PurePathClass,
+        StaticStrings::PurePosixPathClass,
+        StaticStrings::PosixPathClass,
+    ] {
+        module.set_attr(name, Value::Builtin(Builtins::Type(Type::Path)), vm);
+    }
 
     vm.heap.allocate(HeapData::Module(module))
 }
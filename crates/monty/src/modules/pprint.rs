@@ -0,0 +1,952 @@
+//! Implementation of Python's `pprint` module.
+//!
+//! ## Implemented
+//!
+//! - `pformat(object, indent=1, width=80, depth=None, *, compact=False, sort_dicts=True,
+//!   underscore_numbers=False)`
+//! - `pprint(object, stream=None, ...)` with the same options, and `pp()`, which defaults to
+//!   `sort_dicts=False`
+//! - `saferepr(object)`, `isreadable(object)` and `isrecursive(object)`
+//!
+//! The layout is a port of CPython's `PrettyPrinter`: a value whose one-line repr fits in the
+//! remaining width is written as is, otherwise dicts, lists, tuples, sets, frozensets, strings,
+//! bytes and dataclasses are broken over several lines. One-line reprs of dicts, lists and
+//! tuples are rebuilt here so they honour `depth` and `sort_dicts` and report cycles as
+//! `<Recursion on list with id=...>`; every other value uses its normal `repr()`.
+//!
+//! `stream` may be `None` or `sys.stdout` (print output), `sys.stderr` or an `io.StringIO`.
+//! `PrettyPrinter` itself is not exposed.
+
+use std::cmp::Ordering;
+
+use ahash::AHashSet;
+
+use crate::{
+    args::ArgValues,
+    bytecode::VM,
+    defer_drop, defer_drop_mut,
+    exception_private::{ExcType, RunResult, SimpleException},
+    heap::{Heap, HeapData, HeapId},
+    intern::StaticStrings,
+    io::OutputStream,
+    modules::ModuleFunctions,
+    resource::{ResourceError, ResourceTracker, check_estimated_size},
+    types::{
+        Module, PyTrait,
+        bytes::bytes_repr,
+        str::{allocate_string, string_repr_fmt},
+        template::is_line_break,
+    },
+    value::Value,
+};
+
+/// Functions exposed by the `pprint` module.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, strum::Display, serde::Serialize, serde::Deserialize)]
+#[strum(serialize_all = "snake_case")]
+pub(crate) enum PprintFunctions {
+    Pprint,
+    Pformat,
+    Pp,
+    Saferepr,
+    Isreadable,
+    Isrecursive,
+}
+
+/// Creates the `pprint` module and allocates it on the heap.
+///
+/// # Returns
+/// A `HeapId` pointing to the newly allocated module.
+///
+/// # Panics
+/// Panics if the required strings have not been pre-interned during prepare phase.
+pub fn create_module(vm: &mut VM<'_, '_, impl ResourceTracker>) -> Result<HeapId, ResourceError> {
+    let mut module = Module::new(StaticStrings::Pprint);
+
+    let functions = [
+        (StaticStrings::Pprint, PprintFunctions::Pprint),
+        (StaticStrings::Pformat, PprintFunctions::Pformat),
+        (StaticStrings::Pp, PprintFunctions::Pp),
+        (StaticStrings::Saferepr, PprintFunctions::Saferepr),
+        (StaticStrings::Isreadable, PprintFunctions::Isreadable),
+        (StaticStrings::Isrecursive, PprintFunctions::Isrecursive),
+    ];
+    for (name, function) in functions {
+        module.set_attr(name, Value::ModuleFunction(ModuleFunctions::Pprint(function)), vm);
+    }
+
+    vm.heap.allocate(HeapData::Module(module))
+}
+
+/// Dispatches a call to a pprint module function.
+pub(super) fn call(
+    vm: &mut VM<'_, '_, impl ResourceTracker>,
+    function: PprintFunctions,
+    args: ArgValues,
+) -> RunResult<Value> {
+    match function {
+        PprintFunctions::Pprint => pprint(vm, args, "pprint", true),
+        PprintFunctions::Pp => pprint(vm, args, "pp", false),
+        PprintFunctions::Pformat => pformat(vm, args),
+        PprintFunctions::Saferepr => {
+            let object = args.get_one_arg("saferepr", vm.heap)?;
+            defer_drop!(object, vm);
+            let (rep, _, _) = PrettyPrinter::default().safe_repr(object, &mut AHashSet::new(), 0, vm)?;
+            allocate_string(rep, vm.heap)
+        }
+        PprintFunctions::Isreadable => {
+            let object = args.get_one_arg("isreadable", vm.heap)?;
+            defer_drop!(object, vm);
+            let (_, readable, _) = PrettyPrinter::default().safe_repr(object, &mut AHashSet::new(), 0, vm)?;
+            Ok(Value::Bool(readable))
+        }
+        PprintFunctions::Isrecursive => {
+            let object = args.get_one_arg("isrecursive", vm.heap)?;
+            defer_drop!(object, vm);
+            let (_, _, recursive) = PrettyPrinter::default().safe_repr(object, &mut AHashSet::new(), 0, vm)?;
+            Ok(Value::Bool(recursive))
+        }
+    }
+}
+
+/// Implementation of `pprint.pformat(object, indent=1, width=80, depth=None, ...)`.
+fn pformat(vm: &mut VM<'_, '_, impl ResourceTracker>, args: ArgValues) -> RunResult<Value> {
+    let params = args.bind_params(
+        "pformat",
+        [
+            "object",
+            "indent",
+            "width",
+            "depth",
+            "compact",
+            "sort_dicts",
+            "underscore_numbers",
+        ],
+        1,
+        vm.heap,
+        vm.interns,
+    )?;
+    defer_drop!(params, vm);
+    let [object, indent, width, depth, compact, sort_dicts, underscore_numbers] = params;
+    let options = [indent, width, depth, compact, sort_dicts, underscore_numbers];
+    let printer = PrettyPrinter::new(options, true, vm)?;
+    let text = printer.pformat(object.as_ref().expect("required argument"), vm)?;
+    allocate_string(text, vm.heap)
+}
+
+/// Implementation of `pprint.pprint(object, stream=None, ...)` and `pprint.pp()`, which
+/// differ only in the default of `sort_dicts`.
+fn pprint(
+    vm: &mut VM<'_, '_, impl ResourceTracker>,
+    args: ArgValues,
+    name: &str,
+    sort_dicts_default: bool,
+) -> RunResult<Value> {
+    let params = args.bind_params(
+        name,
+        [
+            "object",
+            "stream",
+            "indent",
+            "width",
+            "depth",
+            "compact",
+            "sort_dicts",
+            "underscore_numbers",
+        ],
+        1,
+        vm.heap,
+        vm.interns,
+    )?;
+    defer_drop!(params, vm);
+    let [
+        object,
+        stream,
+        indent,
+        width,
+        depth,
+        compact,
+        sort_dicts,
+        underscore_numbers,
+    ] = params;
+    let options = [indent, width, depth, compact, sort_dicts, underscore_numbers];
+    let printer = PrettyPrinter::new(options, sort_dicts_default, vm)?;
+    let mut text = printer.pformat(object.as_ref().expect("required argument"), vm)?;
+    text.push('\n');
+
+    OutputStream::from_arg(stream.as_ref(), name, "stream", vm.heap)?.write(text.into(), vm)?;
+    Ok(Value::None)
+}
+
+/// The kinds of value `PrettyPrinter` treats specially.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Kind {
+    /// `float`, `bool` and `None`, whose repr is always readable.
+    Scalar,
+    Int,
+    Str,
+    Bytes,
+    Dict,
+    List,
+    Tuple,
+    Set,
+    FrozenSet,
+    Dataclass,
+    /// Anything else, written with its plain repr.
+    Other,
+}
+
+impl Kind {
+    fn of(value: &Value, heap: &Heap<impl ResourceTracker>) -> Self {
+        match value {
+            Value::Bool(_) | Value::Float(_) | Value::None => Self::Scalar,
+            Value::Int(_) | Value::InternLongInt(_) => Self::Int,
+            Value::InternString(_) => Self::Str,
+            Value::InternBytes(_) => Self::Bytes,
+            Value::Ref(id) => match heap.get(*id) {
+                HeapData::LongInt(_) => Self::Int,
+                HeapData::Str(_) => Self::Str,
+                HeapData::Bytes(_) => Self::Bytes,
+                HeapData::Dict(_) => Self::Dict,
+                HeapData::List(_) => Self::List,
+                HeapData::Tuple(_) => Self::Tuple,
+                HeapData::Set(_) => Self::Set,
+                HeapData::FrozenSet(_) => Self::FrozenSet,
+                HeapData::Dataclass(_) => Self::Dataclass,
+                _ => Self::Other,
+            },
+            _ => Self::Other,
+        }
+    }
+}
+
+/// The separator `_format_items` writes before the next item.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Delimiter {
+    None,
+    Comma,
+    Newline,
+}
+
+/// Options and state of one pretty-printing run, mirroring CPython's `PrettyPrinter`.
+#[derive(Debug)]
+struct PrettyPrinter {
+    indent_per_level: i64,
+    width: i64,
+    /// The nesting level at which containers are elided as `[...]`.
+    depth: Option<usize>,
+    compact: bool,
+    sort_dicts: bool,
+    underscore_numbers: bool,
+    /// The text written so far.
+    out: String,
+}
+
+impl Default for PrettyPrinter {
+    fn default() -> Self {
+        Self {
+            indent_per_level: 1,
+            width: 80,
+            depth: None,
+            compact: false,
+            sort_dicts: true,
+            underscore_numbers: false,
+            out: String::new(),
+        }
+    }
+}
+
+impl PrettyPrinter {
+    /// Builds a printer from the `indent`, `width`, `depth`, `compact`, `sort_dicts` and
+    /// `underscore_numbers` arguments, validating them as `PrettyPrinter()` does.
+    fn new(
+        options: [&Option<Value>; 6],
+        sort_dicts_default: bool,
+        vm: &VM<'_, '_, impl ResourceTracker>,
+    ) -> RunResult<Self> {
+        let [indent, width, depth, compact, sort_dicts, underscore_numbers] = options;
+        let int_option = |value: &Option<Value>, default: i64| match value {
+            Some(value) => value.as_int(vm.heap),
+            None => Ok(default),
+        };
+        let bool_option = |value: &Option<Value>, default: bool| value.as_ref().map_or(default, |v| v.py_bool(vm));
+
+        let indent_per_level = int_option(indent, 1)?;
+        let width = int_option(width, 80)?;
+        let depth = match depth {
+            None | Some(Value::None) => None,
+            Some(depth) => Some(depth.as_int(vm.heap)?),
+        };
+        if indent_per_level < 0 {
+            return Err(SimpleException::new_msg(ExcType::ValueError, "indent must be >= 0").into());
+        }
+        if depth.is_some_and(|depth| depth <= 0) {
+            return Err(SimpleException::new_msg(ExcType::ValueError, "depth must be > 0").into());
+        }
+        if width == 0 {
+            return Err(SimpleException::new_msg(ExcType::ValueError, "width must be != 0").into());
+        }
+        Ok(Self {
+            indent_per_level,
+            width,
+            depth: depth.map(|depth| usize::try_from(depth).unwrap_or(usize::MAX)),
+            compact: bool_option(compact, false),
+            sort_dicts: bool_option(sort_dicts, sort_dicts_default),
+            underscore_numbers: bool_option(underscore_numbers, false),
+            ..Self::default()
+        })
+    }
+
+    /// Formats `object` and returns the text, as `PrettyPrinter.pformat()` does.
+    fn pformat(mut self, object: &Value, vm: &mut VM<'_, '_, impl ResourceTracker>) -> RunResult<String> {
+        self.format(object, 0, 0, &mut AHashSet::new(), 0, vm)?;
+        Ok(self.out)
+    }
+
+    /// Writes `value`, breaking it over several lines if its repr does not fit in the width
+    /// left after `indent` columns and `allowance` trailing characters.
+    ///
+    /// `context` holds the containers currently being written, to detect cycles.
+    fn format(
+        &mut self,
+        value: &Value,
+        indent: i64,
+        allowance: i64,
+        context: &mut AHashSet<HeapId>,
+        level: usize,
+        vm: &mut VM<'_, '_, impl ResourceTracker>,
+    ) -> RunResult<()> {
+        if let Value::Ref(id) = value
+            && context.contains(id)
+        {
+            self.out.push_str(&recursion_repr(value, vm));
+            return Ok(());
+        }
+
+        let rep = self.repr(value, context, level, vm)?;
+        if char_len(&rep) <= self.width - indent - allowance {
+            self.out.push_str(&rep);
+            return Ok(());
+        }
+        vm.heap.check_time()?;
+
+        let kind = Kind::of(value, vm.heap);
+        match (kind, value) {
+            (Kind::Str, _) => self.pprint_str(value, &rep, indent, allowance, level + 1, vm),
+            (Kind::Bytes, _) => self.pprint_bytes(value, &rep, indent, allowance, level + 1, vm),
+            (Kind::Dict | Kind::List | Kind::Tuple | Kind::Set | Kind::FrozenSet | Kind::Dataclass, Value::Ref(id)) => {
+                let id = *id;
+                let token = vm.heap.incr_recursion_depth()?;
+                defer_drop!(token, vm);
+                context.insert(id);
+                let result = match kind {
+                    Kind::Dict => self.pprint_dict(id, indent, allowance, context, level + 1, vm),
+                    Kind::Dataclass => self.pprint_dataclass(id, indent, allowance, context, level + 1, vm),
+                    _ => self.pprint_sequence(id, kind, indent, allowance, context, level + 1, vm),
+                };
+                context.remove(&id);
+                result
+            }
+            _ => {
+                self.out.push_str(&rep);
+                Ok(())
+            }
+        }
+    }
+
+    /// Returns the one-line repr of `value`.
+    fn repr(
+        &self,
+        value: &Value,
+        context: &mut AHashSet<HeapId>,
+        level: usize,
+        vm: &mut VM<'_, '_, impl ResourceTracker>,
+    ) -> RunResult<String> {
+        let (rep, _, _) = self.safe_repr(value, context, level, vm)?;
+        Ok(rep)
+    }
+
+    /// Port of `_safe_repr`: returns the one-line repr of `value` and whether it is readable
+    /// and recursive.
+    ///
+    /// Dicts, lists and tuples nested `depth` levels deep are elided, and a container found
+    /// inside itself is written as `<Recursion on list with id=...>`.
+    fn safe_repr(
+        &self,
+        value: &Value,
+        context: &mut AHashSet<HeapId>,
+        level: usize,
+        vm: &mut VM<'_, '_, impl ResourceTracker>,
+    ) -> RunResult<(String, bool, bool)> {
+        let kind = Kind::of(value, vm.heap);
+        let (open, close) = match kind {
+            Kind::Scalar | Kind::Str | Kind::Bytes => return Ok((value.py_repr(vm).into_owned(), true, false)),
+            Kind::Int if self.underscore_numbers => return Ok((group_digits(&value.py_repr(vm)), true, false)),
+            Kind::Int => return Ok((value.py_repr(vm).into_owned(), true, false)),
+            Kind::Dict => ("{", "}"),
+            Kind::List => ("[", "]"),
+            Kind::Tuple if value.py_len(vm) == Some(1) => ("(", ",)"),
+            Kind::Tuple => ("(", ")"),
+            _ => {
+                let rep = value.py_repr(vm).into_owned();
+                let readable = !rep.is_empty() && !rep.starts_with('<');
+                return Ok((rep, readable, false));
+            }
+        };
+        let Value::Ref(id) = value else {
+            unreachable!("containers live on the heap")
+        };
+        let id = *id;
+        if value.py_len(vm) == Some(0) {
+            return Ok((format!("{open}{close}"), true, false));
+        }
+        if self.depth.is_some_and(|depth| level >= depth) {
+            return Ok((format!("{open}...{close}"), false, context.contains(&id)));
+        }
+        if context.contains(&id) {
+            return Ok((recursion_repr(value, vm), false, true));
+        }
+
+        let token = vm.heap.incr_recursion_depth()?;
+        defer_drop!(token, vm);
+        context.insert(id);
+        let result = self.safe_repr_items(id, kind, context, level + 1, vm);
+        context.remove(&id);
+        let (components, readable, recursive) = result?;
+        Ok((format!("{open}{}{close}", components.join(", ")), readable, recursive))
+    }
+
+    /// Returns the reprs of the items of the dict, list or tuple `id` for [`Self::safe_repr`].
+    fn safe_repr_items(
+        &self,
+        id: HeapId,
+        kind: Kind,
+        context: &mut AHashSet<HeapId>,
+        level: usize,
+        vm: &mut VM<'_, '_, impl ResourceTracker>,
+    ) -> RunResult<(Vec<String>, bool, bool)> {
+        let mut components = Vec::new();
+        let mut readable = true;
+        let mut recursive = false;
+        if kind == Kind::Dict {
+            let pairs = dict_pairs(id, vm.heap);
+            defer_drop_mut!(pairs, vm);
+            if self.sort_dicts {
+                sort_safely(pairs, |(key, _)| key, vm)?;
+            }
+            for (key, value) in pairs.iter() {
+                vm.heap.check_time()?;
+                let (key_rep, key_readable, key_recursive) = self.safe_repr(key, context, level, vm)?;
+                let (value_rep, value_readable, value_recursive) = self.safe_repr(value, context, level, vm)?;
+                components.push(format!("{key_rep}: {value_rep}"));
+                readable &= key_readable && value_readable;
+                recursive |= key_recursive || value_recursive;
+            }
+        } else {
+            let items = sequence_items(id, vm.heap);
+            defer_drop!(items, vm);
+            for item in items {
+                vm.heap.check_time()?;
+                let (rep, item_readable, item_recursive) = self.safe_repr(item, context, level, vm)?;
+                components.push(rep);
+                readable &= item_readable;
+                recursive |= item_recursive;
+            }
+        }
+        Ok((components, readable, recursive))
+    }
+
+    /// Port of `_pprint_dict`.
+    fn pprint_dict(
+        &mut self,
+        id: HeapId,
+        indent: i64,
+        allowance: i64,
+        context: &mut AHashSet<HeapId>,
+        level: usize,
+        vm: &mut VM<'_, '_, impl ResourceTracker>,
+    ) -> RunResult<()> {
+        self.out.push('{');
+        self.write_spaces(self.indent_per_level - 1, vm)?;
+        let pairs = dict_pairs(id, vm.heap);
+        defer_drop_mut!(pairs, vm);
+        if self.sort_dicts {
+            sort_safely(pairs, |(key, _)| key, vm)?;
+        }
+
+        let indent = indent + self.indent_per_level;
+        let allowance = allowance + 1;
+        for (i, (key, value)) in pairs.iter().enumerate() {
+            let last = i + 1 == pairs.len();
+            let rep = self.repr(key, context, level, vm)?;
+            self.out.push_str(&rep);
+            self.out.push_str(": ");
+            let value_indent = indent + char_len(&rep) + 2;
+            let value_allowance = if last { allowance } else { 1 };
+            self.format(value, value_indent, value_allowance, context, level, vm)?;
+            if !last {
+                self.write_delimiter(Delimiter::Newline, indent, vm)?;
+            }
+        }
+        self.out.push('}');
+        Ok(())
+    }
+
+    /// Port of `_pprint_list`, `_pprint_tuple` and `_pprint_set`.
+    #[expect(clippy::too_many_arguments, reason = "mirrors CPython's dispatch functions")]
+    fn pprint_sequence(
+        &mut self,
+        id: HeapId,
+        kind: Kind,
+        mut indent: i64,
+        allowance: i64,
+        context: &mut AHashSet<HeapId>,
+        level: usize,
+        vm: &mut VM<'_, '_, impl ResourceTracker>,
+    ) -> RunResult<()> {
+        let items = sequence_items(id, vm.heap);
+        defer_drop_mut!(items, vm);
+        let end = match kind {
+            Kind::List => {
+                self.out.push('[');
+                "]"
+            }
+            Kind::Tuple => {
+                self.out.push('(');
+                if items.len() == 1 { ",)" } else { ")" }
+            }
+            Kind::Set if items.is_empty() => {
+                self.out.push_str("set()");
+                return Ok(());
+            }
+            Kind::Set => {
+                self.out.push('{');
+                "}"
+            }
+            _ if items.is_empty() => {
+                self.out.push_str("frozenset()");
+                return Ok(());
+            }
+            _ => {
+                self.out.push_str("frozenset({");
+                indent += char_len("frozenset") + 1;
+                "})"
+            }
+        };
+        if matches!(kind, Kind::Set | Kind::FrozenSet) {
+            sort_safely(items, |item| item, vm)?;
+        }
+        self.format_items(items, indent, allowance + char_len(end), context, level, vm)?;
+        self.out.push_str(end);
+        Ok(())
+    }
+
+    /// Port of `_format_items`: writes one item per line, or as many as fit on each line
+    /// when `compact` is set.
+    fn format_items(
+        &mut self,
+        items: &[Value],
+        indent: i64,
+        allowance: i64,
+        context: &mut AHashSet<HeapId>,
+        level: usize,
+        vm: &mut VM<'_, '_, impl ResourceTracker>,
+    ) -> RunResult<()> {
+        let indent = indent + self.indent_per_level;
+        self.write_spaces(self.indent_per_level - 1, vm)?;
+        let mut delimiter = Delimiter::None;
+        let mut width = self.width - indent + 1;
+        let mut max_width = width;
+        for (i, item) in items.iter().enumerate() {
+            vm.heap.check_time()?;
+            let last = i + 1 == items.len();
+            if last {
+                max_width -= allowance;
+                width -= allowance;
+            }
+            if self.compact {
+                let rep = self.repr(item, context, level, vm)?;
+                let needed = char_len(&rep) + 2;
+                if width < needed {
+                    width = max_width;
+                    if delimiter != Delimiter::None {
+                        delimiter = Delimiter::Newline;
+                    }
+                }
+                if width >= needed {
+                    width -= needed;
+                    self.write_delimiter(delimiter, indent, vm)?;
+                    delimiter = Delimiter::Comma;
+                    self.out.push_str(&rep);
+                    continue;
+                }
+            }
+            self.write_delimiter(delimiter, indent, vm)?;
+            delimiter = Delimiter::Newline;
+            self.format(item, indent, if last { allowance } else { 1 }, context, level, vm)?;
+        }
+        Ok(())
+    }
+
+    /// Port of `_pprint_str`: splits the string at line breaks and then at whitespace into
+    /// implicitly concatenated literals, parenthesized at the top level.
+    fn pprint_str(
+        &mut self,
+        value: &Value,
+        rep: &str,
+        mut indent: i64,
+        mut allowance: i64,
+        level: usize,
+        vm: &mut VM<'_, '_, impl ResourceTracker>,
+    ) -> RunResult<()> {
+        let Some(text) = value.as_either_str(vm.heap) else {
+            unreachable!("checked to be a str")
+        };
+        let text = text.as_str(vm.interns);
+        if text.is_empty() {
+            self.out.push_str(rep);
+            return Ok(());
+        }
+        if level == 1 {
+            indent += 1;
+            allowance += 1;
+        }
+
+        let lines = split_lines(text);
+        let max_width = self.width - indent;
+        let mut max_width1 = max_width;
+        let mut chunks = Vec::new();
+        for (i, line) in lines.iter().enumerate() {
+            vm.heap.check_time()?;
+            let last_line = i + 1 == lines.len();
+            let line_rep = str_repr(line);
+            if last_line {
+                max_width1 -= allowance;
+            }
+            if char_len(&line_rep) <= max_width1 {
+                chunks.push(line_rep);
+                continue;
+            }
+            let parts = split_words(line);
+            let mut max_width2 = max_width;
+            let mut current = String::new();
+            for (j, part) in parts.iter().enumerate() {
+                let candidate = format!("{current}{part}");
+                if last_line && j + 1 == parts.len() {
+                    max_width2 -= allowance;
+                }
+                if char_len(&str_repr(&candidate)) > max_width2 {
+                    if !current.is_empty() {
+                        chunks.push(str_repr(&current));
+                    }
+                    (*part).clone_into(&mut current);
+                } else {
+                    current = candidate;
+                }
+            }
+            if !current.is_empty() {
+                chunks.push(str_repr(&current));
+            }
+        }
+        self.write_chunks(&chunks, level == 1, indent, vm)
+    }
+
+    /// Port of `_pprint_bytes`: splits the bytes into literals of whole 4-byte groups,
+    /// parenthesized at the top level.
+    fn pprint_bytes(
+        &mut self,
+        value: &Value,
+        rep: &str,
+        mut indent: i64,
+        mut allowance: i64,
+        level: usize,
+        vm: &mut VM<'_, '_, impl ResourceTracker>,
+    ) -> RunResult<()> {
+        let data = match value {
+            Value::InternBytes(id) => vm.interns.get_bytes(*id),
+            Value::Ref(id) => match vm.heap.get(*id) {
+                HeapData::Bytes(bytes) => bytes.as_slice(),
+                _ => unreachable!("checked to be bytes"),
+            },
+            _ => unreachable!("checked to be bytes"),
+        };
+        if data.len() <= 4 {
+            self.out.push_str(rep);
+            return Ok(());
+        }
+        let parens = level == 1;
+        if parens {
+            indent += 1;
+            allowance += 1;
+        }
+
+        let mut width = self.width - indent;
+        let last = data.len() / 4 * 4;
+        let mut chunks = Vec::new();
+        let mut start = 0;
+        for i in (0..data.len()).step_by(4) {
+            let end = (i + 4).min(data.len());
+            if i == last {
+                width -= allowance;
+            }
+            if char_len(&bytes_repr(&data[start..end])) > width {
+                if start < i {
+                    chunks.push(bytes_repr(&data[start..i]));
+                }
+                start = i;
+            }
+        }
+        if start < data.len() {
+            chunks.push(bytes_repr(&data[start..]));
+        }
+        self.write_chunks(&chunks, parens, indent, vm)
+    }
+
+    /// Port of `_pprint_dataclass`: writes `Name(field=value, ...)` with one field per line.
+    fn pprint_dataclass(
+        &mut self,
+        id: HeapId,
+        indent: i64,
+        allowance: i64,
+        context: &mut AHashSet<HeapId>,
+        level: usize,
+        vm: &mut VM<'_, '_, impl ResourceTracker>,
+    ) -> RunResult<()> {
+        let HeapData::Dataclass(dc) = vm.heap.get(id) else {
+            unreachable!("checked to be a dataclass")
+        };
+        let name = dc.name(vm.interns).to_owned();
+        let mut fields = Vec::new();
+        let mut values = Vec::new();
+        for field in dc.field_names() {
+            if let Some(value) = dc.attrs().get_by_str(field, vm.heap, vm.interns) {
+                fields.push(field.clone());
+                values.push(value.clone_with_heap(vm.heap));
+            }
+        }
+        defer_drop!(values, vm);
+
+        let indent = indent + char_len(&name) + 1;
+        self.out.push_str(&name);
+        self.out.push('(');
+        for (i, (field, value)) in fields.iter().zip(values.iter()).enumerate() {
+            let last = i + 1 == fields.len();
+            self.out.push_str(field);
+            self.out.push('=');
+            if let Value::Ref(value_id) = value
+                && context.contains(value_id)
+            {
+                // Matches the `...` the dataclass repr writes for recursion.
+                self.out.push_str("...");
+            } else {
+                let value_indent = indent + char_len(field) + 1;
+                let value_allowance = if last { allowance } else { 1 };
+                self.format(value, value_indent, value_allowance, context, level, vm)?;
+            }
+            if !last {
+                self.write_delimiter(Delimiter::Newline, indent, vm)?;
+            }
+        }
+        self.out.push(')');
+        Ok(())
+    }
+
+    /// Writes the literals of a split string or bytes object, one per line.
+    fn write_chunks(
+        &mut self,
+        chunks: &[String],
+        parens: bool,
+        indent: i64,
+        vm: &VM<'_, '_, impl ResourceTracker>,
+    ) -> RunResult<()> {
+        if let [chunk] = chunks {
+            self.out.push_str(chunk);
+            return Ok(());
+        }
+        if parens {
+            self.out.push('(');
+        }
+        for (i, chunk) in chunks.iter().enumerate() {
+            if i > 0 {
+                self.out.push('\n');
+                self.write_spaces(indent, vm)?;
+            }
+            self.out.push_str(chunk);
+        }
+        if parens {
+            self.out.push(')');
+        }
+        Ok(())
+    }
+
+    /// Writes the separator before an item, indenting the next line by `indent` columns.
+    fn write_delimiter(
+        &mut self,
+        delimiter: Delimiter,
+        indent: i64,
+        vm: &VM<'_, '_, impl ResourceTracker>,
+    ) -> RunResult<()> {
+        match delimiter {
+            Delimiter::None => Ok(()),
+            Delimiter::Comma => {
+                self.out.push_str(", ");
+                Ok(())
+            }
+            Delimiter::Newline => {
+                self.out.push_str(",\n");
+                self.write_spaces(indent, vm)
+            }
+        }
+    }
+
+    /// Writes `count` spaces (none if `count` is negative), checking the output stays within
+    /// the memory limit since `indent` is user controlled.
+    fn write_spaces(&mut self, count: i64, vm: &VM<'_, '_, impl ResourceTracker>) -> RunResult<()> {
+        let count = usize::try_from(count).unwrap_or(0);
+        check_estimated_size(self.out.len().saturating_add(count), vm.heap.tracker())?;
+        self.out.extend(std::iter::repeat_n(' ', count));
+        Ok(())
+    }
+}
+
+/// Returns the `<Recursion on list with id=...>` marker written for a container found
+/// inside itself.
+fn recursion_repr(value: &Value, vm: &VM<'_, '_, impl ResourceTracker>) -> String {
+    let id = value.id().cast_signed();
+    if let Value::Ref(heap_id) = value
+        && let HeapData::Dataclass(dc) = vm.heap.get(*heap_id)
+    {
+        return format!("<Recursion on {} with id={id}>", dc.name(vm.interns));
+    }
+    format!("<Recursion on {} with id={id}>", value.py_type(vm.heap))
+}
+
+/// Clones the key/value pairs of the dict `id`.
+fn dict_pairs(id: HeapId, heap: &Heap<impl ResourceTracker>) -> Vec<(Value, Value)> {
+    let HeapData::Dict(dict) = heap.get(id) else {
+        unreachable!("checked to be a dict")
+    };
+    dict.iter()
+        .map(|(key, value)| (key.clone_with_heap(heap), value.clone_with_heap(heap)))
+        .collect()
+}
+
+/// Clones the items of the list, tuple, set or frozenset `id`.
+fn sequence_items(id: HeapId, heap: &Heap<impl ResourceTracker>) -> Vec<Value> {
+    match heap.get(id) {
+        HeapData::List(list) => list.as_slice().iter().map(|v| v.clone_with_heap(heap)).collect(),
+        HeapData::Tuple(tuple) => tuple.as_slice().iter().map(|v| v.clone_with_heap(heap)).collect(),
+        HeapData::Set(set) => set.iter().map(|v| v.clone_with_heap(heap)).collect(),
+        HeapData::FrozenSet(set) => set.storage().iter().map(|v| v.clone_with_heap(heap)).collect(),
+        _ => unreachable!("checked to be a sequence or set"),
+    }
+}
+
+/// Sorts `items` by `key` like `sorted(items, key=_safe_key)`: values that cannot be
+/// ordered are sorted by type name and then by `id()` instead of raising.
+fn sort_safely<T>(
+    items: &mut Vec<T>,
+    key: impl Fn(&T) -> &Value,
+    vm: &mut VM<'_, '_, impl ResourceTracker>,
+) -> RunResult<()> {
+    let mut order: Vec<usize> = (0..items.len()).collect();
+    let mut sort_error = None;
+    order.sort_by(|&a, &b| {
+        if sort_error.is_some() {
+            return Ordering::Equal;
+        }
+        safe_cmp(key(&items[a]), key(&items[b]), vm).unwrap_or_else(|err| {
+            sort_error = Some(err);
+            Ordering::Equal
+        })
+    });
+    if let Some(err) = sort_error {
+        return Err(err.into());
+    }
+
+    let mut slots: Vec<Option<T>> = std::mem::take(items).into_iter().map(Some).collect();
+    items.extend(
+        order
+            .into_iter()
+            .map(|i| slots[i].take().expect("each index appears once")),
+    );
+    Ok(())
+}
+
+/// Compares two values the way `_safe_key.__lt__` does.
+fn safe_cmp(a: &Value, b: &Value, vm: &mut VM<'_, '_, impl ResourceTracker>) -> Result<Ordering, ResourceError> {
+    vm.heap.check_time()?;
+    if let Some(ordering) = a.py_cmp(b, vm)? {
+        return Ok(ordering);
+    }
+    let a_type = a.py_type(vm.heap).to_string();
+    let b_type = b.py_type(vm.heap).to_string();
+    Ok(a_type.cmp(&b_type).then_with(|| a.id().cmp(&b.id())))
+}
+
+/// Returns the repr of an int with its digits grouped by underscores, like `f'{n:_d}'`.
+fn group_digits(rep: &str) -> String {
+    let (sign, digits) = rep.split_at(usize::from(rep.starts_with('-')));
+    let mut grouped = String::with_capacity(rep.len() + digits.len() / 3);
+    grouped.push_str(sign);
+    for (i, digit) in digits.chars().enumerate() {
+        if i > 0 && (digits.len() - i) % 3 == 0 {
+            grouped.push('_');
+        }
+        grouped.push(digit);
+    }
+    grouped
+}
+
+/// Splits `text` like `str.splitlines(keepends=True)`.
+fn split_lines(text: &str) -> Vec<&str> {
+    let mut lines = Vec::new();
+    let mut start = 0;
+    let mut chars = text.char_indices().peekable();
+    while let Some((i, c)) = chars.next() {
+        if is_line_break(c) {
+            let mut end = i + c.len_utf8();
+            if c == '\r' && chars.next_if(|&(_, next)| next == '\n').is_some() {
+                end += 1;
+            }
+            lines.push(&text[start..end]);
+            start = end;
+        }
+    }
+    if start < text.len() {
+        lines.push(&text[start..]);
+    }
+    lines
+}
+
+/// Splits `line` into words each followed by their trailing whitespace, like
+/// `re.findall(r'\S*\s*', line)` without the final empty match.
+fn split_words(line: &str) -> Vec<&str> {
+    let mut parts = Vec::new();
+    let mut start = 0;
+    let mut in_space = false;
+    for (i, c) in line.char_indices() {
+        let space = c.is_whitespace();
+        if in_space && !space {
+            parts.push(&line[start..i]);
+            start = i;
+        }
+        in_space = space;
+    }
+    if start < line.len() {
+        parts.push(&line[start..]);
+    }
+    parts
+}
+
+/// Returns the repr of a string.
+fn str_repr(s: &str) -> String {
+    let mut rep = String::with_capacity(s.len() + 2);
+    // Writing to a String never fails
+    string_repr_fmt(s, &mut rep).unwrap();
+    rep
+}
+
+/// Returns the number of characters in `s`, which is how `len()` measures the width of a repr.
+fn char_len(s: &str) -> i64 {
+    i64::try_from(s.chars().count()).unwrap_or(i64::MAX)
+}
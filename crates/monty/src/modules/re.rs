@@ -11,6 +11,7 @@
 //! - `re.fullmatch(pattern, string, flags=0)` → `re.Match` or `None`
 //! - `re.findall(pattern, string, flags=0)` → `list`
 //! - `re.sub(pattern, repl, string, count=0, flags=0)` → `str`
+//! - `re.subn(pattern, repl, string, count=0, flags=0)` → `(str, int)`
 //! - `re.split(pattern, string, maxsplit=0, flags=0)` → `list`
 //! - `re.finditer(pattern, string, flags=0)` → iterator of `re.Match`
 //! - `re.escape(pattern)` → `str`
@@ -21,8 +22,14 @@
 //! - `re.IGNORECASE` / `re.I` — case-insensitive matching (value: 2)
 //! - `re.MULTILINE` / `re.M` — `^`/`$` match at line boundaries (value: 8)
 //! - `re.DOTALL` / `re.S` — `.` matches newlines (value: 16)
+//! - `re.UNICODE` / `re.U` — Unicode matching, the default for `str` patterns (value: 32)
+//! - `re.VERBOSE` / `re.X` — whitespace and `#` comments in patterns are ignored (value: 64)
 //! - `re.ASCII` / `re.A` — ASCII-only matching for `\w`, `\d`, `\s` (value: 256)
 //! - `re.PatternError` / `re.error` — exception type for invalid patterns
+//!
+//! Patterns use Python syntax, including inline flags like `(?i)` and `(?s:...)`,
+//! `(?P<name>...)` groups and `(?P=name)` backreferences. They are translated to
+//! `fancy-regex` syntax by [`crate::re_syntax`].
 
 use std::borrow::Cow;
 
@@ -36,7 +43,10 @@ use crate::{
     intern::StaticStrings,
     modules::ModuleFunctions,
     resource::{ResourceError, ResourceTracker},
-    types::{Module, PyTrait, RePattern, Str, Type, re_pattern::value_to_str},
+    types::{
+        Module, PyTrait, RePattern, Str, Type,
+        re_pattern::{backtrack_budget, extract_count, sub_result, value_to_str},
+    },
     value::Value,
 };
 
@@ -48,6 +58,10 @@ pub(crate) const IGNORECASE: u16 = 2;
 pub(crate) const MULTILINE: u16 = 8;
 /// Python regex flag: `.` matches newlines.
 pub(crate) const DOTALL: u16 = 16;
+/// Python regex flag: Unicode matching (the default for `str` patterns).
+pub(crate) const UNICODE: u16 = 32;
+/// Python regex flag: whitespace and `#` comments in the pattern are ignored.
+pub(crate) const VERBOSE: u16 = 64;
 /// Python regex flag: ASCII-only matching for `\w`, `\b`, `\d`, `\s`.
 pub(crate) const ASCII: u16 = 256;
 
@@ -71,6 +85,8 @@ pub(crate) enum ReFunctions {
     Findall,
     /// `re.sub(pattern, repl, string, count=0, flags=0)` — substitute matches.
     Sub,
+    /// `re.subn(pattern, repl, string, count=0, flags=0)` — substitute matches and count them.
+    Subn,
     /// `re.split(pattern, string, maxsplit=0, flags=0)` — split string by pattern.
     Split,
     /// `re.finditer(pattern, string, flags=0)` — return iterator over all matches.
@@ -123,6 +139,11 @@ pub fn create_module(vm: &mut VM<'_, '_, impl ResourceTracker>) -> Result<HeapId
         Value::ModuleFunction(ModuleFunctions::Re(ReFunctions::Sub)),
         vm,
     );
+    module.set_attr(
+        StaticStrings::Subn,
+        Value::ModuleFunction(ModuleFunctions::Re(ReFunctions::Subn)),
+        vm,
+    );
     module.set_attr(
         StaticStrings::Split,
         Value::ModuleFunction(ModuleFunctions::Re(ReFunctions::Split)),
@@ -147,6 +168,10 @@ pub fn create_module(vm: &mut VM<'_, '_, impl ResourceTracker>) -> Result<HeapId
     module.set_attr(StaticStrings::M, Value::Int(i64::from(MULTILINE)), vm);
     module.set_attr(StaticStrings::DotallFlag, Value::Int(i64::from(DOTALL)), vm);
     module.set_attr(StaticStrings::S, Value::Int(i64::from(DOTALL)), vm);
+    module.set_attr(StaticStrings::UnicodeFlag, Value::Int(i64::from(UNICODE)), vm);
+    module.set_attr(StaticStrings::U, Value::Int(i64::from(UNICODE)), vm);
+    module.set_attr(StaticStrings::VerboseFlag, Value::Int(i64::from(VERBOSE)), vm);
+    module.set_attr(StaticStrings::X, Value::Int(i64::from(VERBOSE)), vm);
     module.set_attr(StaticStrings::AsciiFlag, Value::Int(i64::from(ASCII)), vm);
     module.set_attr(StaticStrings::A, Value::Int(i64::from(ASCII)), vm);
 
@@ -194,7 +219,8 @@ pub(super) fn call(
         ReFunctions::Match => call_match(vm, args).map(CallResult::Value),
         ReFunctions::Fullmatch => call_fullmatch(vm, args).map(CallResult::Value),
         ReFunctions::Findall => call_findall(vm, args).map(CallResult::Value),
-        ReFunctions::Sub => call_sub(vm, args).map(CallResult::Value),
+        ReFunctions::Sub => call_sub(vm, args, "re.sub", false).map(CallResult::Value),
+        ReFunctions::Subn => call_sub(vm, args, "re.subn", true).map(CallResult::Value),
         ReFunctions::Split => call_split(vm, args).map(CallResult::Value),
         ReFunctions::Finditer => call_finditer(vm, args).map(CallResult::Value),
         ReFunctions::Escape => call_escape(vm, args).map(CallResult::Value),
@@ -207,7 +233,7 @@ pub(super) fn call(
 /// The pattern is compiled once and stored, avoiding recompilation overhead.
 fn call_compile(vm: &mut VM<'_, '_, impl ResourceTracker>, args: ArgValues) -> RunResult<Value> {
     let (pattern_val, flags) = extract_pattern_and_flags(args, "re.compile", vm)?;
-    let compiled = RePattern::compile(pattern_val, flags)?;
+    let compiled = RePattern::compile(pattern_val, flags, backtrack_budget(vm.heap.tracker())?)?;
     Ok(Value::Ref(vm.heap.allocate(HeapData::RePattern(Box::new(compiled)))?))
 }
 
@@ -217,7 +243,7 @@ fn call_compile(vm: &mut VM<'_, '_, impl ResourceTracker>, args: ArgValues) -> R
 /// object on success, or `None` if no position in the string matches.
 fn call_search(vm: &mut VM<'_, '_, impl ResourceTracker>, args: ArgValues) -> RunResult<Value> {
     let (pattern, text, flags) = extract_pattern_string_flags(args, "re.search", vm)?;
-    let compiled = RePattern::compile(pattern, flags)?;
+    let mut compiled = RePattern::compile(pattern, flags, backtrack_budget(vm.heap.tracker())?)?;
     compiled.search(&text, vm.heap)
 }
 
@@ -227,7 +253,7 @@ fn call_search(vm: &mut VM<'_, '_, impl ResourceTracker>, args: ArgValues) -> Ru
 /// object if the pattern matches at position 0, or `None` otherwise.
 fn call_match(vm: &mut VM<'_, '_, impl ResourceTracker>, args: ArgValues) -> RunResult<Value> {
     let (pattern, text, flags) = extract_pattern_string_flags(args, "re.match", vm)?;
-    let compiled = RePattern::compile(pattern, flags)?;
+    let mut compiled = RePattern::compile(pattern, flags, backtrack_budget(vm.heap.tracker())?)?;
     compiled.match_start(&text, vm.heap)
 }
 
@@ -237,7 +263,7 @@ fn call_match(vm: &mut VM<'_, '_, impl ResourceTracker>, args: ArgValues) -> Run
 /// object if the pattern matches the whole string, or `None` otherwise.
 fn call_fullmatch(vm: &mut VM<'_, '_, impl ResourceTracker>, args: ArgValues) -> RunResult<Value> {
     let (pattern, text, flags) = extract_pattern_string_flags(args, "re.fullmatch", vm)?;
-    let compiled = RePattern::compile(pattern, flags)?;
+    let mut compiled = RePattern::compile(pattern, flags, backtrack_budget(vm.heap.tracker())?)?;
     compiled.fullmatch(&text, vm.heap)
 }
 
@@ -247,33 +273,46 @@ fn call_fullmatch(vm: &mut VM<'_, '_, impl ResourceTracker>, args: ArgValues) ->
 /// strings or tuples depending on the number of capture groups (matching CPython semantics).
 fn call_findall(vm: &mut VM<'_, '_, impl ResourceTracker>, args: ArgValues) -> RunResult<Value> {
     let (pattern, text, flags) = extract_pattern_string_flags(args, "re.findall", vm)?;
-    let compiled = RePattern::compile(pattern, flags)?;
+    let mut compiled = RePattern::compile(pattern, flags, backtrack_budget(vm.heap.tracker())?)?;
     compiled.findall(&text, vm.heap)
 }
 
 /// `re.sub(pattern, repl, string, count=0, flags=0)` — substitute matches with a replacement.
 ///
 /// Compiles the pattern, then delegates to `RePattern::sub`. Replaces occurrences of the
-/// pattern with the replacement string. When `count` is 0, all matches are replaced.
-/// Supports both positional and keyword arguments for `count` and `flags`.
-fn call_sub(vm: &mut VM<'_, '_, impl ResourceTracker>, args: ArgValues) -> RunResult<Value> {
+/// pattern with the replacement string or the result of calling `repl` with each match.
+/// When `count` is 0, all matches are replaced. Supports both positional and keyword
+/// arguments for `count` and `flags`. With `return_count` this is `re.subn`, which returns
+/// a `(new_string, number_of_subs)` tuple.
+fn call_sub(
+    vm: &mut VM<'_, '_, impl ResourceTracker>,
+    args: ArgValues,
+    func_name: &str,
+    return_count: bool,
+) -> RunResult<Value> {
     let (pos, kwargs) = args.into_parts();
     defer_drop_mut!(pos, vm);
     let kwargs = kwargs.into_iter();
     defer_drop_mut!(kwargs, vm);
 
     let Some(pattern_val) = pos.next() else {
-        return Err(ExcType::type_error("re.sub() missing required argument: 'pattern'"));
+        return Err(ExcType::type_error(format!(
+            "{func_name}() missing required argument: 'pattern'"
+        )));
     };
     defer_drop!(pattern_val, vm);
 
     let Some(repl_val) = pos.next() else {
-        return Err(ExcType::type_error("re.sub() missing required argument: 'repl'"));
+        return Err(ExcType::type_error(format!(
+            "{func_name}() missing required argument: 'repl'"
+        )));
     };
     defer_drop!(repl_val, vm);
 
     let Some(string_val) = pos.next() else {
-        return Err(ExcType::type_error("re.sub() missing required argument: 'string'"));
+        return Err(ExcType::type_error(format!(
+            "{func_name}() missing required argument: 'string'"
+        )));
     };
     defer_drop!(string_val, vm);
 
@@ -283,7 +322,9 @@ fn call_sub(vm: &mut VM<'_, '_, impl ResourceTracker>, args: ArgValues) -> RunRe
 
     if let Some(extra) = pos.next() {
         extra.drop_with_heap(vm);
-        return Err(ExcType::type_error("re.sub() takes at most 5 positional arguments"));
+        return Err(ExcType::type_error(format!(
+            "{func_name}() takes at most 5 positional arguments"
+        )));
     }
 
     // Extract count and flags from kwargs (if not given positionally)
@@ -299,69 +340,43 @@ fn call_sub(vm: &mut VM<'_, '_, impl ResourceTracker>, args: ArgValues) -> RunRe
             "count" => {
                 if pos_count.is_some() {
                     value.drop_with_heap(vm);
-                    return Err(ExcType::type_error("re.sub() got multiple values for argument 'count'"));
+                    return Err(ExcType::type_error(format!(
+                        "{func_name}() got multiple values for argument 'count'"
+                    )));
                 }
                 kw_count.replace(value).drop_with_heap(vm);
             }
             "flags" => {
                 if pos_flags.is_some() {
                     value.drop_with_heap(vm);
-                    return Err(ExcType::type_error("re.sub() got multiple values for argument 'flags'"));
+                    return Err(ExcType::type_error(format!(
+                        "{func_name}() got multiple values for argument 'flags'"
+                    )));
                 }
                 kw_flags.replace(value).drop_with_heap(vm);
             }
             _ => {
                 value.drop_with_heap(vm);
                 return Err(ExcType::type_error(format!(
-                    "'{key_str}' is an invalid keyword argument for re.sub()"
+                    "'{key_str}' is an invalid keyword argument for {func_name}()"
                 )));
             }
         }
     }
 
-    let count_val = pos_count.or(kw_count);
-    let flags_val = pos_flags.or(kw_flags);
-
-    #[expect(
-        clippy::cast_sign_loss,
-        clippy::cast_possible_truncation,
-        reason = "n is checked non-negative above"
-    )]
-    let count = match count_val {
-        Some(Value::Int(n)) if n >= 0 => n as usize,
-        Some(Value::Bool(b)) => usize::from(b),
-        Some(Value::Int(_)) => {
-            // Negative count — return original string unchanged
-            let _flags = extract_flags(flags_val, vm.heap)?;
-            let text = value_to_str(string_val, vm.heap, vm.interns)?.into_owned();
-            let s = Str::new(text);
-            return Ok(Value::Ref(vm.heap.allocate(HeapData::Str(s))?));
-        }
-        Some(other) => {
-            let t = other.py_type(vm.heap);
-            other.drop_with_heap(vm);
-            return Err(ExcType::type_error(format!(
-                "'{t}' object cannot be interpreted as an integer for 'count' argument"
-            )));
-        }
-        None => 0,
-    };
-
-    let flags = extract_flags(flags_val, vm.heap)?;
+    let count = extract_count(pos_count.or(kw_count), vm.heap)?;
+    let flags = extract_flags(pos_flags.or(kw_flags), vm.heap)?;
 
     let pattern = value_to_str(pattern_val, vm.heap, vm.interns)?.into_owned();
-
-    // Check that repl is a string — callable replacement is not supported
-    if !repl_val.is_str(vm.heap) {
-        return Err(ExcType::type_error(
-            "callable replacement is not yet supported in re.sub()",
-        ));
-    }
-    let repl = value_to_str(repl_val, vm.heap, vm.interns)?.into_owned();
     let text = value_to_str(string_val, vm.heap, vm.interns)?.into_owned();
 
-    let compiled = RePattern::compile(pattern, flags)?;
-    compiled.sub(&repl, &text, count, vm.heap)
+    let mut compiled = RePattern::compile(pattern, flags, backtrack_budget(vm.heap.tracker())?)?;
+    let (result, n) = match count {
+        Some(count) => compiled.sub(repl_val, &text, count, vm)?,
+        // Negative count — no substitutions are made
+        None => (text, 0),
+    };
+    sub_result(result, n, return_count, vm.heap)
 }
 
 /// `re.split(pattern, string, maxsplit=0, flags=0)` — split string by pattern occurrences.
@@ -435,7 +450,7 @@ fn call_split(vm: &mut VM<'_, '_, impl ResourceTracker>, args: ArgValues) -> Run
     let pattern = value_to_str(pattern_val, vm.heap, vm.interns)?.into_owned();
     let text = value_to_str(string_val, vm.heap, vm.interns)?.into_owned();
 
-    let compiled = RePattern::compile(pattern, flags)?;
+    let mut compiled = RePattern::compile(pattern, flags, backtrack_budget(vm.heap.tracker())?)?;
     compiled.split(&text, maxsplit, vm.heap)
 }
 
@@ -446,7 +461,7 @@ fn call_split(vm: &mut VM<'_, '_, impl ResourceTracker>, args: ArgValues) -> Run
 /// over the returned list automatically.
 fn call_finditer(vm: &mut VM<'_, '_, impl ResourceTracker>, args: ArgValues) -> RunResult<Value> {
     let (pattern, text, flags) = extract_pattern_string_flags(args, "re.finditer", vm)?;
-    let compiled = RePattern::compile(pattern, flags)?;
+    let mut compiled = RePattern::compile(pattern, flags, backtrack_budget(vm.heap.tracker())?)?;
     compiled.finditer(&text, vm.heap)
 }
 
@@ -477,7 +492,7 @@ fn call_escape(vm: &mut VM<'_, '_, impl ResourceTracker>, args: ArgValues) -> Ru
 /// Returns whether a character should be escaped by `re.escape()`.
 ///
 /// Matches CPython's `_special_chars_map` — only regex metacharacters and whitespace.
-fn should_escape(c: char) -> bool {
+pub(crate) fn should_escape(c: char) -> bool {
     matches!(
         c,
         '\t' | '\n'
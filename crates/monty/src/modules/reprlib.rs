@@ -0,0 +1,270 @@
+//! Implementation of Python's `reprlib` module.
+//!
+//! ## Implemented
+//!
+//! - `repr(obj)` → a repr of `obj` with size limits, like `reprlib.aRepr.repr()`
+//!
+//! Containers show at most 6 items (4 for dicts) followed by `...`, nesting below 6 levels
+//! is elided, and strings longer than 30 characters, ints longer than 40 digits and other
+//! reprs longer than 30 characters keep their start and end around a `...`. Set items and
+//! dict keys are sorted when they can be. The limits are those of CPython's default `Repr`
+//! instance; the `Repr` class and `recursive_repr()` are not supported.
+
+use crate::{
+    args::ArgValues,
+    bytecode::VM,
+    defer_drop,
+    exception_private::{RunError, RunResult},
+    heap::{Heap, HeapData, HeapId},
+    intern::StaticStrings,
+    modules::ModuleFunctions,
+    resource::{ResourceError, ResourceTracker},
+    sorting::sort_indices,
+    types::{
+        Module, PyTrait,
+        str::{allocate_string, string_repr_fmt},
+    },
+    value::Value,
+};
+
+/// Functions exposed by the `reprlib` module.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, strum::Display, serde::Serialize, serde::Deserialize)]
+#[strum(serialize_all = "snake_case")]
+pub(crate) enum ReprlibFunctions {
+    Repr,
+}
+
+/// Nesting depth below which containers are written as `...`.
+const MAX_LEVEL: usize = 6;
+const MAX_TUPLE: usize = 6;
+const MAX_LIST: usize = 6;
+const MAX_DICT: usize = 4;
+const MAX_SET: usize = 6;
+const MAX_FROZENSET: usize = 6;
+const MAX_STRING: usize = 30;
+const MAX_LONG: usize = 40;
+const MAX_OTHER: usize = 30;
+const FILL_VALUE: &str = "...";
+
+/// Creates the `reprlib` module and allocates it on the heap.
+///
+/// # Returns
+/// A `HeapId` pointing to the newly allocated module.
+///
+/// # Panics
+/// Panics if the required strings have not been pre-interned during prepare phase.
+pub fn create_module(vm: &mut VM<'_, '_, impl ResourceTracker>) -> Result<HeapId, ResourceError> {
+    let mut module = Module::new(StaticStrings::Reprlib);
+
+    module.set_attr(
+        StaticStrings::Repr,
+        Value::ModuleFunction(ModuleFunctions::Reprlib(ReprlibFunctions::Repr)),
+        vm,
+    );
+
+    vm.heap.allocate(HeapData::Module(module))
+}
+
+/// Dispatches a call to a reprlib module function.
+pub(super) fn call(
+    vm: &mut VM<'_, '_, impl ResourceTracker>,
+    function: ReprlibFunctions,
+    args: ArgValues,
+) -> RunResult<Value> {
+    match function {
+        ReprlibFunctions::Repr => {
+            let object = args.get_one_arg("repr", vm.heap)?;
+            defer_drop!(object, vm);
+            let rep = repr1(object, MAX_LEVEL, vm)?;
+            allocate_string(rep, vm.heap)
+        }
+    }
+}
+
+/// Port of `Repr.repr1`: dispatches on the type of `value`, with `level` levels of nesting
+/// left to show.
+fn repr1(value: &Value, level: usize, vm: &mut VM<'_, '_, impl ResourceTracker>) -> RunResult<String> {
+    vm.heap.check_time()?;
+    let heap_data = match value {
+        Value::Int(_) | Value::InternLongInt(_) => return Ok(truncate(&value.py_repr(vm), MAX_LONG)),
+        Value::InternString(id) => return Ok(repr_str(vm.interns.get_str(*id))),
+        Value::Ref(id) => vm.heap.get(*id),
+        _ => return Ok(truncate(&value.py_repr(vm), MAX_OTHER)),
+    };
+    match heap_data {
+        HeapData::LongInt(_) => Ok(truncate(&value.py_repr(vm), MAX_LONG)),
+        HeapData::Str(s) => Ok(repr_str(s.as_str())),
+        HeapData::Tuple(tuple) => {
+            let items = clone_all(tuple.as_slice().iter(), vm.heap);
+            defer_drop!(items, vm);
+            repr_iterable(items, None, level, ("(", ")"), MAX_TUPLE, ",", vm)
+        }
+        HeapData::List(list) => {
+            let items = clone_all(list.as_slice().iter(), vm.heap);
+            defer_drop!(items, vm);
+            repr_iterable(items, None, level, ("[", "]"), MAX_LIST, "", vm)
+        }
+        HeapData::Set(set) => {
+            if set.is_empty() {
+                return Ok("set()".to_owned());
+            }
+            let items = clone_all(set.iter(), vm.heap);
+            defer_drop!(items, vm);
+            let order = possibly_sorted(items, vm)?;
+            repr_iterable(items, Some(&order), level, ("{", "}"), MAX_SET, "", vm)
+        }
+        HeapData::FrozenSet(set) => {
+            if set.is_empty() {
+                return Ok("frozenset()".to_owned());
+            }
+            let items = clone_all(set.storage().iter(), vm.heap);
+            defer_drop!(items, vm);
+            let order = possibly_sorted(items, vm)?;
+            repr_iterable(items, Some(&order), level, ("frozenset({", "})"), MAX_FROZENSET, "", vm)
+        }
+        HeapData::Dict(dict) => {
+            let (keys, values): (Vec<Value>, Vec<Value>) = dict
+                .iter()
+                .map(|(key, value)| (key.clone_with_heap(vm.heap), value.clone_with_heap(vm.heap)))
+                .unzip();
+            defer_drop!(keys, vm);
+            defer_drop!(values, vm);
+            repr_dict(keys, values, level, vm)
+        }
+        _ => Ok(truncate(&value.py_repr(vm), MAX_OTHER)),
+    }
+}
+
+/// Port of `Repr._repr_iterable`: writes at most `max_items` items, taken in `order` if
+/// given, between the `left` and `right` delimiters.
+///
+/// `trail` is added before `right` for a single item, to write one-tuples as `(x,)`.
+fn repr_iterable(
+    items: &[Value],
+    order: Option<&[usize]>,
+    level: usize,
+    (left, right): (&str, &str),
+    max_items: usize,
+    trail: &str,
+    vm: &mut VM<'_, '_, impl ResourceTracker>,
+) -> RunResult<String> {
+    let inner = if level == 0 && !items.is_empty() {
+        FILL_VALUE.to_owned()
+    } else {
+        let mut pieces = Vec::new();
+        for i in 0..items.len().min(max_items) {
+            let index = order.map_or(i, |order| order[i]);
+            pieces.push(repr1(&items[index], level - 1, vm)?);
+        }
+        if items.len() > max_items {
+            pieces.push(FILL_VALUE.to_owned());
+        }
+        pieces.join(", ")
+    };
+    let trail = if items.len() == 1 { trail } else { "" };
+    Ok(format!("{left}{inner}{trail}{right}"))
+}
+
+/// Port of `Repr.repr_dict`: writes at most `MAX_DICT` items, sorted by key if possible.
+fn repr_dict(
+    keys: &[Value],
+    values: &[Value],
+    level: usize,
+    vm: &mut VM<'_, '_, impl ResourceTracker>,
+) -> RunResult<String> {
+    if keys.is_empty() {
+        return Ok("{}".to_owned());
+    }
+    if level == 0 {
+        return Ok(format!("{{{FILL_VALUE}}}"));
+    }
+    let order = possibly_sorted(keys, vm)?;
+    let mut pieces = Vec::new();
+    for &index in order.iter().take(MAX_DICT) {
+        let key = repr1(&keys[index], level - 1, vm)?;
+        let value = repr1(&values[index], level - 1, vm)?;
+        pieces.push(format!("{key}: {value}"));
+    }
+    if keys.len() > MAX_DICT {
+        pieces.push(FILL_VALUE.to_owned());
+    }
+    Ok(format!("{{{}}}", pieces.join(", ")))
+}
+
+/// Port of `_possibly_sorted`: returns the order that sorts `items`, or their original order
+/// if they cannot be compared.
+fn possibly_sorted(items: &[Value], vm: &mut VM<'_, '_, impl ResourceTracker>) -> RunResult<Vec<usize>> {
+    let mut order: Vec<usize> = (0..items.len()).collect();
+    match sort_indices(&mut order, items, false, vm) {
+        Ok(()) => Ok(order),
+        Err(RunError::Exc(_)) => Ok((0..items.len()).collect()),
+        Err(err) => Err(err),
+    }
+}
+
+/// Port of `Repr.repr_str`: keeps the start and end of a long string's repr around `...`.
+fn repr_str(s: &str) -> String {
+    let chars: Vec<char> = s.chars().collect();
+    let rep = str_repr(&chars[..chars.len().min(MAX_STRING)]);
+    if rep.chars().count() <= MAX_STRING {
+        return rep;
+    }
+    let (start, end) = split_lengths(MAX_STRING);
+    let kept: Vec<char> = chars[..start.min(chars.len())]
+        .iter()
+        .chain(py_tail(&chars, end))
+        .copied()
+        .collect();
+    let rep: Vec<char> = str_repr(&kept).chars().collect();
+    let mut truncated: String = rep[..start.min(rep.len())].iter().collect();
+    truncated.push_str(FILL_VALUE);
+    truncated.extend(py_tail(&rep, end));
+    truncated
+}
+
+/// Keeps the start and end of `rep` around `...` if it is longer than `max_len` characters,
+/// as `Repr.repr_int` and `Repr.repr_instance` do.
+fn truncate(rep: &str, max_len: usize) -> String {
+    let chars: Vec<char> = rep.chars().collect();
+    if chars.len() <= max_len {
+        return rep.to_owned();
+    }
+    let (start, end) = split_lengths(max_len);
+    let mut truncated: String = chars[..start].iter().collect();
+    truncated.push_str(FILL_VALUE);
+    truncated.extend(&chars[chars.len() - end..]);
+    truncated
+}
+
+/// Returns how many characters to keep before and after the `...` to fit in `max_len`.
+fn split_lengths(max_len: usize) -> (usize, usize) {
+    let start = max_len.saturating_sub(3) / 2;
+    (start, max_len.saturating_sub(3 + start))
+}
+
+/// Returns `chars[len(chars) - n:]` with Python's slice semantics, where a start before
+/// the beginning counts from the end once more before being clamped to zero.
+fn py_tail<T>(chars: &[T], n: usize) -> &[T] {
+    let len = chars.len();
+    let start = if n <= len {
+        len - n
+    } else {
+        // `len - n` is negative, so Python adds `len` again
+        (2 * len).saturating_sub(n)
+    };
+    &chars[start..]
+}
+
+/// Returns the repr of the string made of `chars`.
+fn str_repr(chars: &[char]) -> String {
+    let s: String = chars.iter().collect();
+    let mut rep = String::with_capacity(s.len() + 2);
+    // Writing to a String never fails
+    string_repr_fmt(&s, &mut rep).unwrap();
+    rep
+}
+
+/// Clones `values` so they can be used while the heap is borrowed mutably.
+fn clone_all<'a>(values: impl Iterator<Item = &'a Value>, heap: &Heap<impl ResourceTracker>) -> Vec<Value> {
+    values.map(|value| value.clone_with_heap(heap)).collect()
+}
@@ -0,0 +1,83 @@
+//! Implementation of the `shutil` module.
+//!
+//! Provides the high-level file operations the host performs:
+//! - `copy(src, dst)`: Copy a file's contents to a file or into a directory
+//! - `copytree(src, dst, ..., dirs_exist_ok=False)`: Recursively copy a directory
+//! - `rmtree(path, ignore_errors=False)`: Recursively delete a directory
+//!
+//! Each function yields an `OsFunction` call with its path arguments converted to
+//! `Path` objects; keyword arguments are passed through for the host to interpret.
+
+use crate::{
+    args::ArgValues,
+    bytecode::{CallResult, VM},
+    exception_private::RunResult,
+    heap::{HeapData, HeapId},
+    intern::StaticStrings,
+    modules::ModuleFunctions,
+    os::OsFunction,
+    resource::{ResourceError, ResourceTracker},
+    types::{Module, path::path_os_call},
+    value::Value,
+};
+
+/// Shutil module functions.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, strum::Display, serde::Serialize, serde::Deserialize)]
+#[strum(serialize_all = "lowercase")]
+pub(crate) enum ShutilFunctions {
+    Copy,
+    Copytree,
+    Rmtree,
+}
+
+/// Creates the `shutil` module and allocates it on the heap.
+///
+/// # Returns
+/// A HeapId pointing to the newly allocated module.
+///
+/// # Panics
+/// Panics if the required strings have not been pre-interned during prepare phase.
+pub fn create_module(vm: &mut VM<'_, '_, impl ResourceTracker>) -> Result<HeapId, ResourceError> {
+    let mut module = Module::new(StaticStrings::Shutil);
+
+    module.set_attr(
+        StaticStrings::Copy,
+        Value::ModuleFunction(ModuleFunctions::Shutil(ShutilFunctions::Copy)),
+        vm,
+    );
+    module.set_attr(
+        StaticStrings::Copytree,
+        Value::ModuleFunction(ModuleFunctions::Shutil(ShutilFunctions::Copytree)),
+        vm,
+    );
+    module.set_attr(
+        StaticStrings::Rmtree,
+        Value::ModuleFunction(ModuleFunctions::Shutil(ShutilFunctions::Rmtree)),
+        vm,
+    );
+
+    vm.heap.allocate(HeapData::Module(module))
+}
+
+/// Dispatches a call to a shutil module function.
+///
+/// Every function yields to the host: `copy()` and `copytree()` return the destination
+/// path the host reports, `rmtree()` returns `None`.
+pub(super) fn call(
+    vm: &mut VM<'_, '_, impl ResourceTracker>,
+    function: ShutilFunctions,
+    args: ArgValues,
+) -> RunResult<CallResult> {
+    match function {
+        ShutilFunctions::Copy => path_os_call(OsFunction::Copy, "copy", ["src", "dst"], args, vm.heap, vm.interns),
+        ShutilFunctions::Copytree => path_os_call(
+            OsFunction::Copytree,
+            "copytree",
+            ["src", "dst"],
+            args,
+            vm.heap,
+            vm.interns,
+        ),
+        ShutilFunctions::Rmtree => path_os_call(OsFunction::Rmtree, "rmtree", ["path"], args, vm.heap, vm.interns),
+    }
+}
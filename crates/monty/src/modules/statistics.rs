@@ -0,0 +1,778 @@
+//! Implementation of Python's `statistics` module.
+//!
+//! ## Implemented functions
+//!
+//! - Averages: `mean`, `fmean(data, weights=None)`, `median`, `median_low`, `median_high`
+//! - Modes: `mode`, `multimode`
+//! - Spread: `variance(data, xbar=None)`, `pvariance(data, mu=None)`, `stdev`, `pstdev`
+//! - `quantiles(data, *, n=4, method='exclusive')`
+//! - `StatisticsError`, a `ValueError` subclass raised for empty or too-small data
+//!
+//! As in CPython, `mean` and the variance functions sum the data exactly (every int and
+//! float is a ratio with a power-of-two denominator) and only round the final result, so
+//! they agree with CPython bit for bit: all-int data gives an int when the result is
+//! exact and a float otherwise. `stdev` takes a correctly rounded square root of the
+//! exact variance. `median` and `quantiles` interpolate with ordinary `+`, `*` and `/`,
+//! matching CPython's result types.
+
+use std::cmp::Ordering;
+
+use num_bigint::{BigInt, BigUint};
+use num_integer::Integer;
+use num_traits::{One, Signed, ToPrimitive, Zero};
+
+use crate::{
+    args::ArgValues,
+    builtins::Builtins,
+    bytecode::{Opcode, VM},
+    defer_drop, defer_drop_mut,
+    exception_private::{ExcType, RunError, RunResult, SimpleException},
+    heap::{HeapData, HeapGuard, HeapId},
+    intern::StaticStrings,
+    modules::ModuleFunctions,
+    resource::{ResourceError, ResourceTracker, check_repeat_size},
+    sorting::sort_values,
+    types::{Dict, List, LongInt, Module, MontyIter, PyTrait},
+    value::Value,
+};
+
+/// Functions exposed by the `statistics` module.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, strum::Display, serde::Serialize, serde::Deserialize)]
+#[strum(serialize_all = "snake_case")]
+pub(crate) enum StatisticsFunctions {
+    Mean,
+    Fmean,
+    Median,
+    MedianLow,
+    MedianHigh,
+    Mode,
+    Multimode,
+    Variance,
+    Pvariance,
+    Stdev,
+    Pstdev,
+    Quantiles,
+}
+
+/// Creates the `statistics` module and allocates it on the heap.
+///
+/// # Returns
+/// A `HeapId` pointing to the newly allocated module.
+///
+/// # Panics
+/// Panics if the required strings have not been pre-interned during prepare phase.
+pub fn create_module(vm: &mut VM<'_, '_, impl ResourceTracker>) -> Result<HeapId, ResourceError> {
+    let mut module = Module::new(StaticStrings::Statistics);
+
+    let functions = [
+        (StaticStrings::Mean, StatisticsFunctions::Mean),
+        (StaticStrings::Fmean, StatisticsFunctions::Fmean),
+        (StaticStrings::Median, StatisticsFunctions::Median),
+        (StaticStrings::MedianLow, StatisticsFunctions::MedianLow),
+        (StaticStrings::MedianHigh, StatisticsFunctions::MedianHigh),
+        (StaticStrings::Mode, StatisticsFunctions::Mode),
+        (StaticStrings::Multimode, StatisticsFunctions::Multimode),
+        (StaticStrings::Variance, StatisticsFunctions::Variance),
+        (StaticStrings::Pvariance, StatisticsFunctions::Pvariance),
+        (StaticStrings::Stdev, StatisticsFunctions::Stdev),
+        (StaticStrings::Pstdev, StatisticsFunctions::Pstdev),
+        (StaticStrings::Quantiles, StatisticsFunctions::Quantiles),
+    ];
+    for (name, function) in functions {
+        module.set_attr(name, Value::ModuleFunction(ModuleFunctions::Statistics(function)), vm);
+    }
+
+    module.set_attr(
+        StaticStrings::StatisticsError,
+        Value::Builtin(Builtins::ExcType(ExcType::StatisticsError)),
+        vm,
+    );
+
+    vm.heap.allocate(HeapData::Module(module))
+}
+
+/// Dispatches a call to a statistics module function.
+pub(super) fn call(
+    vm: &mut VM<'_, '_, impl ResourceTracker>,
+    function: StatisticsFunctions,
+    args: ArgValues,
+) -> RunResult<Value> {
+    match function {
+        StatisticsFunctions::Mean => mean(vm, args),
+        StatisticsFunctions::Fmean => fmean(vm, args),
+        StatisticsFunctions::Median | StatisticsFunctions::MedianLow | StatisticsFunctions::MedianHigh => {
+            median(vm, function, args)
+        }
+        StatisticsFunctions::Mode | StatisticsFunctions::Multimode => mode(vm, function, args),
+        StatisticsFunctions::Variance
+        | StatisticsFunctions::Pvariance
+        | StatisticsFunctions::Stdev
+        | StatisticsFunctions::Pstdev => spread(vm, function, args),
+        StatisticsFunctions::Quantiles => quantiles(vm, args),
+    }
+}
+
+/// Implementation of `statistics.mean(data)`.
+fn mean(vm: &mut VM<'_, '_, impl ResourceTracker>, args: ArgValues) -> RunResult<Value> {
+    let data = args.get_one_arg("mean", vm.heap)?;
+    let sums = ExactSums::collect(data, false, vm)?;
+    if sums.count == 0 {
+        return Err(ExcType::statistics_error("mean requires at least one data point"));
+    }
+    if let Some(non_finite) = sums.non_finite {
+        // inf and nan are unchanged by dividing by the count
+        return Ok(Value::Float(non_finite));
+    }
+    let (num, den) = sums.sum.into_ratio();
+    ratio_to_value(&num, &(den * sums.count), !sums.has_float, vm)
+}
+
+/// Implementation of `statistics.fmean(data, weights=None)`.
+///
+/// Unlike `mean`, the data is converted to floats up front and the result is always a
+/// float: `fsum(data) / len(data)`, or `sumprod(data, weights) / fsum(weights)`.
+fn fmean(vm: &mut VM<'_, '_, impl ResourceTracker>, args: ArgValues) -> RunResult<Value> {
+    let params = args.bind_params("fmean", ["data", "weights"], 1, vm.heap, vm.interns)?;
+    defer_drop!(params, vm);
+    let [Some(data), weights] = params else {
+        unreachable!("required parameters are always bound")
+    };
+
+    let Some(weights) = weights.as_ref().filter(|weights| !matches!(weights, Value::None)) else {
+        let mut fsum = FloatSum::default();
+        let mut count = 0_usize;
+        let iter = MontyIter::new(data.clone_with_heap(vm), vm)?;
+        defer_drop_mut!(iter, vm);
+        while let Some(item) = iter.for_next(vm)? {
+            defer_drop!(item, vm);
+            fsum.add(value_to_float(item, vm)?);
+            count += 1;
+        }
+        let total = fsum.total()?;
+        if count == 0 {
+            return Err(ExcType::statistics_error("fmean requires at least one data point"));
+        }
+        return Ok(Value::Float(total / count as f64));
+    };
+
+    let data: Vec<Value> = MontyIter::new(data.clone_with_heap(vm), vm)?.collect(vm)?;
+    defer_drop!(data, vm);
+    let weights: Vec<Value> = MontyIter::new(weights.clone_with_heap(vm), vm)?.collect(vm)?;
+    defer_drop!(weights, vm);
+    if data.len() != weights.len() {
+        return Err(ExcType::statistics_error("data and weights must be the same length"));
+    }
+
+    // `sumprod` is computed exactly and rounded once, like CPython's extended precision sum
+    let mut products = ExactSums::default();
+    for (x, w) in data.iter().zip(weights.iter()) {
+        vm.heap.check_time()?;
+        match (exact_ratio(x, vm)?.0, exact_ratio(w, vm)?.0) {
+            (Exact::Finite(x_num, x_shift), Exact::Finite(w_num, w_shift)) => {
+                products.sum.add(x_num * w_num, x_shift + w_shift);
+            }
+            _ => products.add_non_finite(value_to_float(x, vm)? * value_to_float(w, vm)?),
+        }
+    }
+    let num = match products.non_finite {
+        Some(non_finite) => non_finite,
+        None => {
+            let (num, den) = products.sum.into_ratio();
+            ratio_to_f64(&num, &den).ok_or_else(float_overflow_error)?
+        }
+    };
+
+    let mut fsum = FloatSum::default();
+    for w in weights.iter() {
+        fsum.add(value_to_float(w, vm)?);
+    }
+    let den = fsum.total()?;
+    if den == 0.0 {
+        return Err(ExcType::statistics_error("sum of weights must be non-zero"));
+    }
+    Ok(Value::Float(num / den))
+}
+
+/// Implementation of `statistics.median`, `median_low` and `median_high`.
+fn median(
+    vm: &mut VM<'_, '_, impl ResourceTracker>,
+    function: StatisticsFunctions,
+    args: ArgValues,
+) -> RunResult<Value> {
+    let data = args.get_one_arg(&function.to_string(), vm.heap)?;
+    let items = sorted_data(data, vm)?;
+    defer_drop!(items, vm);
+    let n = items.len();
+    if n == 0 {
+        return Err(ExcType::statistics_error("no median for empty data"));
+    }
+    let index = match function {
+        StatisticsFunctions::MedianLow if n % 2 == 0 => n / 2 - 1,
+        StatisticsFunctions::Median if n % 2 == 0 => {
+            // Interpolate with ordinary arithmetic, so `median([1, 2])` is `1.5`
+            let low = items[n / 2 - 1].clone_with_heap(vm);
+            let high = items[n / 2].clone_with_heap(vm);
+            let total = vm.apply_binary_op(Opcode::BinaryAdd, low, high)?;
+            return vm.apply_binary_op(Opcode::BinaryDiv, total, Value::Int(2));
+        }
+        _ => n / 2,
+    };
+    Ok(items[index].clone_with_heap(vm))
+}
+
+/// Implementation of `statistics.mode` and `statistics.multimode`.
+///
+/// Values are counted like `collections.Counter`: equal values share the count of the
+/// first one seen, and ties are broken by first appearance.
+fn mode(vm: &mut VM<'_, '_, impl ResourceTracker>, function: StatisticsFunctions, args: ArgValues) -> RunResult<Value> {
+    let data = args.get_one_arg(&function.to_string(), vm.heap)?;
+    let iter = MontyIter::new(data, vm)?;
+    defer_drop_mut!(iter, vm);
+
+    // `seen` maps each distinct value to its index in `counts`
+    let mut seen_guard = HeapGuard::new(Dict::new(), vm);
+    let (seen, vm) = seen_guard.as_parts_mut();
+    let mut counts: Vec<usize> = Vec::new();
+    while let Some(item) = iter.for_next(vm)? {
+        let index = match seen.get(&item, vm) {
+            Ok(index) => index.map(|index| match index {
+                Value::Int(index) => usize::try_from(*index).expect("mode indices are non-negative"),
+                _ => unreachable!("mode indices are ints"),
+            }),
+            Err(err) => {
+                item.drop_with_heap(vm);
+                return Err(err);
+            }
+        };
+        if let Some(index) = index {
+            counts[index] += 1;
+            item.drop_with_heap(vm);
+        } else {
+            let index = i64::try_from(counts.len()).expect("count of distinct values fits in i64");
+            seen.set(item, Value::Int(index), vm)?;
+            counts.push(1);
+        }
+    }
+
+    let max_count = counts.iter().copied().max();
+    if function == StatisticsFunctions::Mode {
+        let Some(max_count) = max_count else {
+            return Err(ExcType::statistics_error("no mode for empty data"));
+        };
+        let index = counts
+            .iter()
+            .position(|&count| count == max_count)
+            .expect("the maximum count is in counts");
+        let key = seen.key_at(index).expect("every count has a key");
+        return Ok(key.clone_with_heap(vm));
+    }
+
+    let modes: Vec<Value> = counts
+        .iter()
+        .enumerate()
+        .filter(|&(_, &count)| Some(count) == max_count)
+        .map(|(index, _)| seen.key_at(index).expect("every count has a key").clone_with_heap(vm))
+        .collect();
+    Ok(Value::Ref(vm.heap.allocate(HeapData::List(List::new(modes)))?))
+}
+
+/// Implementation of `statistics.variance`, `pvariance`, `stdev` and `pstdev`.
+fn spread(
+    vm: &mut VM<'_, '_, impl ResourceTracker>,
+    function: StatisticsFunctions,
+    args: ArgValues,
+) -> RunResult<Value> {
+    let (func_name, center_name, sample) = match function {
+        StatisticsFunctions::Variance => ("variance", "xbar", true),
+        StatisticsFunctions::Pvariance => ("pvariance", "mu", false),
+        StatisticsFunctions::Stdev => ("stdev", "xbar", true),
+        StatisticsFunctions::Pstdev => ("pstdev", "mu", false),
+        _ => unreachable!("spread() only handles the variance functions"),
+    };
+    let params = args.bind_params(func_name, ["data", center_name], 1, vm.heap, vm.interns)?;
+    defer_drop!(params, vm);
+    let [Some(data), center] = params else {
+        unreachable!("required parameters are always bound")
+    };
+    let center = center.as_ref().filter(|center| !matches!(center, Value::None));
+
+    let deviations = SquaredDeviations::collect(data.clone_with_heap(vm), center, vm)?;
+    let count = deviations.count;
+    let divisor = if sample { count.saturating_sub(1) } else { count };
+    if divisor == 0 {
+        let requirement = if sample {
+            "at least two data points"
+        } else {
+            "at least one data point"
+        };
+        return Err(ExcType::statistics_error(format!("{func_name} requires {requirement}")));
+    }
+
+    let (num, den) = match deviations.total {
+        Total::Ratio(num, den) => (num, den * divisor),
+        Total::NonFinite(non_finite) => {
+            if matches!(function, StatisticsFunctions::Stdev | StatisticsFunctions::Pstdev) {
+                return Err(SimpleException::new_msg(ExcType::ValueError, "inf or nan encountered in data").into());
+            }
+            // inf and nan are unchanged by dividing by the count
+            return Ok(Value::Float(non_finite));
+        }
+    };
+    match function {
+        StatisticsFunctions::Variance | StatisticsFunctions::Pvariance => {
+            ratio_to_value(&num, &den, !deviations.has_float, vm)
+        }
+        _ => Ok(Value::Float(float_sqrt_of_frac(num, den)?)),
+    }
+}
+
+/// Implementation of `statistics.quantiles(data, *, n=4, method='exclusive')`.
+fn quantiles(vm: &mut VM<'_, '_, impl ResourceTracker>, args: ArgValues) -> RunResult<Value> {
+    let (mut pos, kwargs) = args.into_parts();
+    let pos_count = pos.len();
+    let data = match (pos.next(), pos_count) {
+        (Some(data), 1) => data,
+        (data, _) => {
+            data.drop_with_heap(vm);
+            pos.drop_with_heap(vm);
+            kwargs.drop_with_heap(vm);
+            return Err(if pos_count == 0 {
+                ExcType::type_error_missing_positional_with_names("quantiles", &["data"])
+            } else {
+                ExcType::type_error_too_many_positional("quantiles", 1, pos_count, 0)
+            });
+        }
+    };
+    defer_drop!(data, vm);
+    let (n, method) =
+        ArgValues::Kwargs(kwargs).extract_two_kwargs_only("quantiles", "n", "method", vm.heap, vm.interns)?;
+    defer_drop!(n, vm);
+    defer_drop!(method, vm);
+
+    let n = match n {
+        Some(n) => n.as_int(vm.heap)?,
+        None => 4,
+    };
+    if n < 1 {
+        return Err(ExcType::statistics_error("n must be at least 1"));
+    }
+    let inclusive = match method {
+        None => Some(false),
+        Some(method) => match method.as_either_str(vm.heap) {
+            Some(name) if name.as_str(vm.interns) == "inclusive" => Some(true),
+            Some(name) if name.as_str(vm.interns) == "exclusive" => Some(false),
+            _ => None,
+        },
+    };
+
+    let items = sorted_data(data.clone_with_heap(vm), vm)?;
+    defer_drop!(items, vm);
+    let cut_count = usize::try_from(n - 1).expect("n is at least 1");
+    match items.len() {
+        0 => return Err(ExcType::statistics_error("must have at least one data point")),
+        1 => {
+            // A single data point is every cut point, as in `data * (n - 1)`
+            check_repeat_size(size_of::<Value>(), cut_count, vm.heap.tracker())?;
+            let cuts: Vec<Value> = (0..cut_count).map(|_| items[0].clone_with_heap(vm)).collect();
+            return Ok(Value::Ref(vm.heap.allocate(HeapData::List(List::new(cuts)))?));
+        }
+        _ => {}
+    }
+    let Some(inclusive) = inclusive else {
+        let method = method.as_ref().expect("the default method is known");
+        return Err(
+            SimpleException::new_msg(ExcType::ValueError, format!("Unknown method: {}", method.py_repr(vm))).into(),
+        );
+    };
+
+    let ld = i64::try_from(items.len()).expect("data length fits in i64");
+    let cuts = Vec::with_capacity(cut_count);
+    let mut cuts_guard = HeapGuard::new(cuts, vm);
+    let (cuts, vm) = cuts_guard.as_parts_mut();
+    for i in 1..n {
+        vm.heap.check_time()?;
+        // `lower` and `lower + 1` are the neighbouring data points, weighted by `delta`
+        let (lower, delta) = if inclusive {
+            let m = ld - 1;
+            (i * m / n, i * m % n)
+        } else {
+            let m = ld + 1;
+            let j = (i * m / n).clamp(1, ld - 1);
+            (j - 1, i * m - j * n)
+        };
+        let lower = usize::try_from(lower).expect("cut point index is in range");
+        let low = items[lower].clone_with_heap(vm);
+        let low = vm.apply_binary_op(Opcode::BinaryMul, low, Value::Int(n - delta))?;
+        let high = items[lower + 1].clone_with_heap(vm);
+        let high = match vm.apply_binary_op(Opcode::BinaryMul, high, Value::Int(delta)) {
+            Ok(high) => high,
+            Err(err) => {
+                low.drop_with_heap(vm);
+                return Err(err);
+            }
+        };
+        let total = vm.apply_binary_op(Opcode::BinaryAdd, low, high)?;
+        cuts.push(vm.apply_binary_op(Opcode::BinaryDiv, total, Value::Int(n))?);
+    }
+    let (cuts, vm) = cuts_guard.into_parts();
+    Ok(Value::Ref(vm.heap.allocate(HeapData::List(List::new(cuts)))?))
+}
+
+/// Collects `data` into a vector sorted with the same ordering as `sorted()`.
+fn sorted_data(data: Value, vm: &mut VM<'_, '_, impl ResourceTracker>) -> RunResult<Vec<Value>> {
+    let items: Vec<Value> = MontyIter::new(data, vm)?.collect(vm)?;
+    let mut items_guard = HeapGuard::new(items, vm);
+    let (items, vm) = items_guard.as_parts_mut();
+    sort_values(items, None, false, vm)?;
+    Ok(items_guard.into_inner())
+}
+
+/// A data point converted for exact arithmetic, as by `float.as_integer_ratio()`.
+enum Exact {
+    /// The value `num / 2**shift`.
+    Finite(BigInt, u64),
+    /// An infinite or NaN float, which has no exact ratio.
+    NonFinite(f64),
+}
+
+/// Converts an int, bool or float to an [`Exact`] ratio, also returning whether it was
+/// a float.
+fn exact_ratio(value: &Value, vm: &VM<'_, '_, impl ResourceTracker>) -> RunResult<(Exact, bool)> {
+    if let Value::Ref(id) = value
+        && let HeapData::LongInt(li) = vm.heap.get(*id)
+    {
+        return Ok((Exact::Finite(li.inner().clone(), 0), false));
+    }
+    match value {
+        Value::Bool(b) => Ok((Exact::Finite(BigInt::from(u8::from(*b)), 0), false)),
+        Value::Int(i) => Ok((Exact::Finite(BigInt::from(*i), 0), false)),
+        Value::Float(f) => Ok((float_ratio(*f), true)),
+        _ => Err(ExcType::type_error(format!(
+            "can't convert type '{}' to numerator/denominator",
+            value.py_type(vm.heap)
+        ))),
+    }
+}
+
+/// Splits a float into its exact ratio `num / 2**shift`, with the shift kept minimal.
+fn float_ratio(f: f64) -> Exact {
+    if !f.is_finite() {
+        return Exact::NonFinite(f);
+    }
+    let bits = f.to_bits();
+    let exponent_bits = i64::try_from((bits >> 52) & 0x7ff).expect("float exponent is 11 bits");
+    let fraction = bits & ((1 << 52) - 1);
+    // Subnormals have no implicit leading bit and the minimum exponent
+    let (mantissa, exponent) = if exponent_bits == 0 {
+        (fraction, -1074)
+    } else {
+        (fraction | (1 << 52), exponent_bits - 1075)
+    };
+    if mantissa == 0 {
+        return Exact::Finite(BigInt::zero(), 0);
+    }
+    let num = if f.is_sign_negative() {
+        -BigInt::from(mantissa)
+    } else {
+        BigInt::from(mantissa)
+    };
+    if exponent >= 0 {
+        return Exact::Finite(num << exponent.unsigned_abs(), 0);
+    }
+    let shift = exponent.unsigned_abs();
+    let trailing_zeros = u64::from(mantissa.trailing_zeros()).min(shift);
+    Exact::Finite(num >> trailing_zeros, shift - trailing_zeros)
+}
+
+/// An exact running sum of ratios with power-of-two denominators, `num / 2**shift`.
+#[derive(Default)]
+struct DyadicSum {
+    num: BigInt,
+    shift: u64,
+}
+
+impl DyadicSum {
+    /// Adds `num / 2**shift` to the sum.
+    fn add(&mut self, num: BigInt, shift: u64) {
+        if shift > self.shift {
+            self.num <<= shift - self.shift;
+            self.shift = shift;
+        }
+        self.num += num << (self.shift - shift);
+    }
+
+    /// Returns the sum as a numerator and denominator.
+    fn into_ratio(self) -> (BigInt, BigInt) {
+        (self.num, BigInt::one() << self.shift)
+    }
+}
+
+/// Exact sums over a data set, mirroring CPython's `_sum` and `_ss` helpers.
+#[derive(Default)]
+struct ExactSums {
+    count: usize,
+    /// Whether any data point was a float, which makes the result a float.
+    has_float: bool,
+    /// The float sum of any infinite or NaN data points, which overrides the exact sums.
+    non_finite: Option<f64>,
+    sum: DyadicSum,
+    /// The sum of squares, only maintained when requested.
+    sum_of_squares: DyadicSum,
+}
+
+impl ExactSums {
+    /// Sums the items of the iterable `data`, and their squares if `squares` is set.
+    fn collect(data: Value, squares: bool, vm: &mut VM<'_, '_, impl ResourceTracker>) -> RunResult<Self> {
+        let iter = MontyIter::new(data, vm)?;
+        defer_drop_mut!(iter, vm);
+        let mut sums = Self::default();
+        while let Some(item) = iter.for_next(vm)? {
+            defer_drop!(item, vm);
+            sums.add(item, squares, vm)?;
+        }
+        Ok(sums)
+    }
+
+    /// Adds a single data point.
+    fn add(&mut self, value: &Value, squares: bool, vm: &VM<'_, '_, impl ResourceTracker>) -> RunResult<()> {
+        let (exact, is_float) = exact_ratio(value, vm)?;
+        self.count += 1;
+        self.has_float |= is_float;
+        match exact {
+            Exact::Finite(num, shift) => {
+                if squares {
+                    self.sum_of_squares.add(&num * &num, 2 * shift);
+                }
+                self.sum.add(num, shift);
+            }
+            Exact::NonFinite(f) => self.add_non_finite(f),
+        }
+        Ok(())
+    }
+
+    /// Adds an infinite or NaN value, which the exact sums cannot represent.
+    fn add_non_finite(&mut self, f: f64) {
+        self.non_finite = Some(self.non_finite.unwrap_or(0.0) + f);
+    }
+}
+
+/// An exact total, or the infinite or NaN float it became.
+enum Total {
+    /// The ratio `num / den`.
+    Ratio(BigInt, BigInt),
+    NonFinite(f64),
+}
+
+/// The exact sum of squared deviations from the mean (or a given center) of a data set.
+struct SquaredDeviations {
+    count: usize,
+    has_float: bool,
+    total: Total,
+}
+
+impl SquaredDeviations {
+    /// Computes the squared deviations of `data`, like CPython's `_ss`.
+    ///
+    /// Without a `center` the exact mean is used and the sum is computed exactly as
+    /// `sum(x**2) - sum(x)**2 / n`. A given center is subtracted with ordinary
+    /// arithmetic (`(x - c) * (x - c)`), so the result types follow the center's type.
+    fn collect(data: Value, center: Option<&Value>, vm: &mut VM<'_, '_, impl ResourceTracker>) -> RunResult<Self> {
+        let Some(center) = center else {
+            let sums = ExactSums::collect(data, true, vm)?;
+            let total = if let Some(non_finite) = sums.non_finite {
+                Total::NonFinite(non_finite)
+            } else if sums.count == 0 {
+                Total::Ratio(BigInt::zero(), BigInt::one())
+            } else {
+                // n * sum(x**2) - sum(x)**2 over n, with the sum of squares sharing the
+                // denominator of the squared sum
+                debug_assert_eq!(sums.sum_of_squares.shift, 2 * sums.sum.shift);
+                let count = BigInt::from(sums.count);
+                let sum = sums.sum.num;
+                let num = &count * sums.sum_of_squares.num - &sum * &sum;
+                Total::Ratio(num, count << (2 * sums.sum.shift))
+            };
+            return Ok(Self {
+                count: sums.count,
+                has_float: sums.has_float,
+                total,
+            });
+        };
+
+        let iter = MontyIter::new(data, vm)?;
+        defer_drop_mut!(iter, vm);
+        let mut sums = ExactSums::default();
+        while let Some(item) = iter.for_next(vm)? {
+            let deviation = vm.apply_binary_op(Opcode::BinarySub, item, center.clone_with_heap(vm))?;
+            let copy = deviation.clone_with_heap(vm);
+            let square = vm.apply_binary_op(Opcode::BinaryMul, deviation, copy)?;
+            defer_drop!(square, vm);
+            sums.add(square, false, vm)?;
+        }
+        let total = match sums.non_finite {
+            Some(non_finite) => Total::NonFinite(non_finite),
+            None => {
+                let (num, den) = sums.sum.into_ratio();
+                Total::Ratio(num, den)
+            }
+        };
+        Ok(Self {
+            count: sums.count,
+            has_float: sums.has_float,
+            total,
+        })
+    }
+}
+
+/// Converts the exact ratio `num / den` like CPython's `_convert`: to an int if
+/// `int_result` is set and the division is exact, otherwise to the nearest float.
+fn ratio_to_value(
+    num: &BigInt,
+    den: &BigInt,
+    int_result: bool,
+    vm: &mut VM<'_, '_, impl ResourceTracker>,
+) -> RunResult<Value> {
+    if int_result {
+        let (quotient, remainder) = num.div_rem(den);
+        if remainder.is_zero() {
+            return Ok(LongInt::new(quotient).into_value(vm.heap)?);
+        }
+    }
+    Ok(Value::Float(ratio_to_f64(num, den).ok_or_else(float_overflow_error)?))
+}
+
+/// Divides `num` by the positive `den`, correctly rounded to the nearest float (ties to
+/// even) like Python's `int / int`.
+///
+/// Returns `None` if the result is too large for a float.
+fn ratio_to_f64(num: &BigInt, den: &BigInt) -> Option<f64> {
+    if num.is_zero() {
+        return Some(0.0);
+    }
+    let bit_length = |n: &BigUint| i64::try_from(n.bits()).expect("bit length fits in i64");
+    let (num_abs, den_abs) = (num.magnitude(), den.magnitude());
+
+    // Scale so the integer quotient has 55 or 56 bits: more than a float's 53, so the
+    // discarded low bits and the remainder decide the rounding
+    let shift = bit_length(num_abs) - bit_length(den_abs) - 55;
+    let (quotient, remainder) = if shift >= 0 {
+        num_abs.div_rem(&(den_abs << shift.unsigned_abs()))
+    } else {
+        (num_abs << shift.unsigned_abs()).div_rem(den_abs)
+    };
+
+    // Keep 53 bits, or fewer if the result is subnormal
+    let mut dropped = bit_length(&quotient) - 53;
+    if shift + dropped < -1074 {
+        dropped = -1074 - shift;
+    }
+    let dropped_bits = dropped.unsigned_abs();
+    let mut mantissa = &quotient >> dropped_bits;
+    let rest = &quotient - (&mantissa << dropped_bits);
+    let half = BigUint::one() << (dropped_bits - 1);
+    let round_up = match rest.cmp(&half) {
+        Ordering::Greater => true,
+        Ordering::Less => false,
+        Ordering::Equal => !remainder.is_zero() || mantissa.bit(0),
+    };
+    if round_up {
+        mantissa += 1_u32;
+    }
+
+    let mantissa = mantissa.to_f64().expect("mantissa has at most 54 bits");
+    let exponent = i32::try_from(shift + dropped).unwrap_or(i32::MAX);
+    let result = libm::ldexp(mantissa, exponent);
+    if result.is_infinite() {
+        return None;
+    }
+    Some(if num.is_negative() { -result } else { result })
+}
+
+/// Returns the `OverflowError` Python raises when an exact ratio is too large for a float.
+fn float_overflow_error() -> RunError {
+    SimpleException::new_msg(ExcType::OverflowError, "integer division result too large for a float").into()
+}
+
+/// Number of bits kept when taking square roots, `2 * 53 + 3` as in CPython.
+const SQRT_BIT_WIDTH: i64 = 109;
+
+/// Square root of the non-negative ratio `num / den` as a correctly rounded float.
+///
+/// Follows CPython's `_float_sqrt_of_frac`: the integer square root of the ratio scaled
+/// to `SQRT_BIT_WIDTH` bits, made odd when inexact, rounds exactly like the true root.
+fn float_sqrt_of_frac(num: BigInt, den: BigInt) -> RunResult<f64> {
+    let divisor = num.gcd(&den);
+    let (num, den) = (num / &divisor, den / &divisor);
+    let bit_length = |n: &BigInt| i64::try_from(n.bits()).expect("bit length fits in i64");
+    let q = (bit_length(&num) - bit_length(&den) - SQRT_BIT_WIDTH).div_euclid(2);
+    let (root_num, root_den) = if q >= 0 {
+        let root = isqrt_of_frac_round_to_odd(&num, &(den << (2 * q.unsigned_abs())));
+        (root << q.unsigned_abs(), BigInt::one())
+    } else {
+        let root = isqrt_of_frac_round_to_odd(&(num << (2 * q.unsigned_abs())), &den);
+        (root, BigInt::one() << q.unsigned_abs())
+    };
+    ratio_to_f64(&root_num, &root_den).ok_or_else(float_overflow_error)
+}
+
+/// Integer square root of `num / den`, with the lowest bit set if the root is inexact.
+fn isqrt_of_frac_round_to_odd(num: &BigInt, den: &BigInt) -> BigInt {
+    let root = (num / den).sqrt();
+    if &root * &root * den == *num {
+        root
+    } else {
+        root | BigInt::one()
+    }
+}
+
+/// An exactly rounded float sum, matching `math.fsum`.
+#[derive(Default)]
+struct FloatSum {
+    exact: DyadicSum,
+    has_nan: bool,
+    has_pos_inf: bool,
+    has_neg_inf: bool,
+}
+
+impl FloatSum {
+    /// Adds a float to the sum.
+    fn add(&mut self, f: f64) {
+        match float_ratio(f) {
+            Exact::Finite(num, shift) => self.exact.add(num, shift),
+            Exact::NonFinite(f) if f.is_nan() => self.has_nan = true,
+            Exact::NonFinite(f) if f > 0.0 => self.has_pos_inf = true,
+            Exact::NonFinite(_) => self.has_neg_inf = true,
+        }
+    }
+
+    /// Returns the correctly rounded total, with CPython's errors for `-inf + inf` and
+    /// overflow.
+    fn total(self) -> RunResult<f64> {
+        if self.has_nan {
+            return Ok(f64::NAN);
+        }
+        match (self.has_pos_inf, self.has_neg_inf) {
+            (true, true) => Err(SimpleException::new_msg(ExcType::ValueError, "-inf + inf in fsum").into()),
+            (true, false) => Ok(f64::INFINITY),
+            (false, true) => Ok(f64::NEG_INFINITY),
+            (false, false) => {
+                let (num, den) = self.exact.into_ratio();
+                ratio_to_f64(&num, &den).ok_or_else(|| {
+                    SimpleException::new_msg(ExcType::OverflowError, "intermediate overflow in fsum").into()
+                })
+            }
+        }
+    }
+}
+
+/// Converts a number to a float like `float(x)` does for `fsum`.
+fn value_to_float(value: &Value, vm: &VM<'_, '_, impl ResourceTracker>) -> RunResult<f64> {
+    if let Value::Ref(id) = value
+        && let HeapData::LongInt(li) = vm.heap.get(*id)
+    {
+        return li.to_f64().filter(|f| f.is_finite()).ok_or_else(|| {
+            SimpleException::new_msg(ExcType::OverflowError, "int too large to convert to float").into()
+        });
+    }
+    super::math::value_to_float(value, vm.heap)
+}
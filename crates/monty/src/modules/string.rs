@@ -0,0 +1,54 @@
+//! Implementation of Python's `string` module.
+//!
+//! Provides the character-class constants and the `Template` class:
+//! - `ascii_letters`, `ascii_lowercase`, `ascii_uppercase`
+//! - `digits`, `hexdigits`, `octdigits`
+//! - `punctuation`, `printable`, `whitespace`
+//! - `Template(template)` with `substitute()` / `safe_substitute()`
+//!
+//! The constants are static interned strings, so creating the module allocates
+//! nothing but the module itself.
+
+use crate::{
+    builtins::Builtins,
+    bytecode::VM,
+    heap::{HeapData, HeapId},
+    intern::StaticStrings,
+    resource::{ResourceError, ResourceTracker},
+    types::{Module, Type},
+    value::Value,
+};
+
+/// Creates the `string` module and allocates it on the heap.
+///
+/// # Returns
+/// A `HeapId` pointing to the newly allocated module.
+///
+/// # Panics
+/// Panics if the required strings have not been pre-interned during prepare phase.
+pub fn create_module(vm: &mut VM<'_, '_, impl ResourceTracker>) -> Result<HeapId, ResourceError> {
+    let mut module = Module::new(StaticStrings::StringAttr);
+
+    let constants = [
+        (StaticStrings::AsciiLetters, StaticStrings::AsciiLettersValue),
+        (StaticStrings::AsciiLowercase, StaticStrings::AsciiLowercaseValue),
+        (StaticStrings::AsciiUppercase, StaticStrings::AsciiUppercaseValue),
+        (StaticStrings::Digits, StaticStrings::DigitsValue),
+        (StaticStrings::Hexdigits, StaticStrings::HexdigitsValue),
+        (StaticStrings::Octdigits, StaticStrings::OctdigitsValue),
+        (StaticStrings::Punctuation, StaticStrings::PunctuationValue),
+        (StaticStrings::Printable, StaticStrings::PrintableValue),
+        (StaticStrings::Whitespace, StaticStrings::WhitespaceValue),
+    ];
+    for (name, value) in constants {
+        module.set_attr(name, value.into(), vm);
+    }
+
+    module.set_attr(
+        StaticStrings::TemplateClass,
+        Value::Builtin(Builtins::Type(Type::Template)),
+        vm,
+    );
+
+    vm.heap.allocate(HeapData::Module(module))
+}
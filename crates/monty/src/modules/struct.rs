@@ -0,0 +1,135 @@
+//! Implementation of Python's `struct` module.
+//!
+//! ## Implemented
+//!
+//! - `pack(format, *values)`, `unpack(format, buffer)`, `unpack_from(format, buffer, offset=0)`,
+//!   `iter_unpack(format, buffer)` and `calcsize(format)`
+//! - `Struct(format)` with the same methods plus `format` and `size` — see [`Struct`]
+//! - `error`, raised for malformed formats and values that do not fit them
+//!
+//! Formats may be `str` or `bytes` and support the `@`, `=`, `<`, `>` and `!` byte-order
+//! prefixes and every standard format character, with native sizes and alignment as on a
+//! 64-bit little-endian platform. Buffers must be `bytes`; `pack_into()` is not supported.
+
+use crate::{
+    args::ArgValues,
+    builtins::Builtins,
+    bytecode::VM,
+    defer_drop,
+    exception_private::{ExcType, RunResult},
+    heap::{HeapData, HeapId},
+    intern::StaticStrings,
+    modules::ModuleFunctions,
+    resource::{ResourceError, ResourceTracker},
+    types::{Module, Struct, Type},
+    value::Value,
+};
+
+/// Functions exposed by the `struct` module.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, strum::Display, serde::Serialize, serde::Deserialize)]
+#[strum(serialize_all = "snake_case")]
+pub(crate) enum StructFunctions {
+    Pack,
+    Unpack,
+    UnpackFrom,
+    IterUnpack,
+    Calcsize,
+}
+
+/// Creates the `struct` module and allocates it on the heap.
+///
+/// # Returns
+/// A `HeapId` pointing to the newly allocated module.
+///
+/// # Panics
+/// Panics if the required strings have not been pre-interned during prepare phase.
+pub fn create_module(vm: &mut VM<'_, '_, impl ResourceTracker>) -> Result<HeapId, ResourceError> {
+    let mut module = Module::new(StaticStrings::Struct);
+
+    let functions = [
+        (StaticStrings::Pack, StructFunctions::Pack),
+        (StaticStrings::Unpack, StructFunctions::Unpack),
+        (StaticStrings::UnpackFrom, StructFunctions::UnpackFrom),
+        (StaticStrings::IterUnpack, StructFunctions::IterUnpack),
+        (StaticStrings::Calcsize, StructFunctions::Calcsize),
+    ];
+    for (name, function) in functions {
+        module.set_attr(name, Value::ModuleFunction(ModuleFunctions::Struct(function)), vm);
+    }
+
+    module.set_attr(
+        StaticStrings::StructClass,
+        Value::Builtin(Builtins::Type(Type::Struct)),
+        vm,
+    );
+    module.set_attr(
+        StaticStrings::Error,
+        Value::Builtin(Builtins::ExcType(ExcType::StructError)),
+        vm,
+    );
+
+    vm.heap.allocate(HeapData::Module(module))
+}
+
+/// Dispatches a call to a struct module function.
+pub(super) fn call(
+    vm: &mut VM<'_, '_, impl ResourceTracker>,
+    function: StructFunctions,
+    args: ArgValues,
+) -> RunResult<Value> {
+    match function {
+        StructFunctions::Pack => pack(vm, args),
+        StructFunctions::Unpack => unpack(vm, args),
+        StructFunctions::UnpackFrom => unpack_from(vm, args),
+        StructFunctions::IterUnpack => iter_unpack(vm, args),
+        StructFunctions::Calcsize => calcsize(vm, args),
+    }
+}
+
+/// Implementation of `struct.pack(format, *values)`.
+fn pack(vm: &mut VM<'_, '_, impl ResourceTracker>, args: ArgValues) -> RunResult<Value> {
+    let mut pos = args.into_pos_only("pack", vm.heap)?;
+    let Some(format) = pos.next() else {
+        return Err(ExcType::type_error("missing format argument"));
+    };
+    defer_drop!(format, vm);
+    let values: Vec<Value> = pos.collect();
+    defer_drop!(values, vm);
+    Struct::compile(format, vm.heap, vm.interns)?.pack(values, vm)
+}
+
+/// Implementation of `struct.unpack(format, buffer)`.
+fn unpack(vm: &mut VM<'_, '_, impl ResourceTracker>, args: ArgValues) -> RunResult<Value> {
+    let (format, buffer) = args.get_two_args("unpack", vm.heap)?;
+    defer_drop!(format, vm);
+    defer_drop!(buffer, vm);
+    Struct::compile(format, vm.heap, vm.interns)?.unpack(buffer, vm)
+}
+
+/// Implementation of `struct.unpack_from(format, buffer, offset=0)`.
+fn unpack_from(vm: &mut VM<'_, '_, impl ResourceTracker>, args: ArgValues) -> RunResult<Value> {
+    let params = args.bind_params("unpack_from", ["format", "buffer", "offset"], 2, vm.heap, vm.interns)?;
+    defer_drop!(params, vm);
+    let [format, buffer, offset] = params;
+    let format = format.as_ref().expect("required argument");
+    let buffer = buffer.as_ref().expect("required argument");
+    Struct::compile(format, vm.heap, vm.interns)?.unpack_from(buffer, offset.as_ref(), vm)
+}
+
+/// Implementation of `struct.iter_unpack(format, buffer)`.
+fn iter_unpack(vm: &mut VM<'_, '_, impl ResourceTracker>, args: ArgValues) -> RunResult<Value> {
+    let (format, buffer) = args.get_two_args("iter_unpack", vm.heap)?;
+    defer_drop!(format, vm);
+    defer_drop!(buffer, vm);
+    Struct::compile(format, vm.heap, vm.interns)?.iter_unpack(buffer, vm)
+}
+
+/// Implementation of `struct.calcsize(format)`.
+fn calcsize(vm: &mut VM<'_, '_, impl ResourceTracker>, args: ArgValues) -> RunResult<Value> {
+    let format = args.get_one_arg("calcsize", vm.heap)?;
+    defer_drop!(format, vm);
+    let size = Struct::compile(format, vm.heap, vm.interns)?.size();
+    Ok(Value::Int(
+        i64::try_from(size).expect("struct size is at most isize::MAX"),
+    ))
+}
@@ -4,18 +4,34 @@
 //! - `version`: Python version string (e.g., "3.14.0 (Monty)")
 //! - `version_info`: Named tuple (3, 14, 0, 'final', 0)
 //! - `platform`: Platform identifier ("monty")
-//! - `stdout`: Marker for standard output (no real functionality)
-//! - `stderr`: Marker for standard error (no real functionality)
+//! - `stdout`: Standard output, supporting `write()` and `flush()`
+//! - `stderr`: Standard error, supporting `write()` and `flush()`
+//! - `argv`: Command line arguments, set by the host with `MontyRun::set_argv()`
+//! - `exit()`: Raises `SystemExit`, which hosts see via `MontyException::exit_code()`
+//!
+//! Both streams write through the host's `PrintWriter`, like `print()`.
 
 use crate::{
-    bytecode::VM,
-    heap::{HeapData, HeapId},
-    intern::StaticStrings,
+    args::ArgValues,
+    bytecode::{CallResult, VM},
+    defer_drop,
+    exception_private::{ExcType, RunResult, SimpleException},
+    heap::{DropWithHeap, HeapData, HeapId},
+    intern::{StaticStrings, StringId},
+    io::OutputStream,
+    modules::ModuleFunctions,
     resource::{ResourceError, ResourceTracker},
-    types::{Module, NamedTuple},
+    types::{List, Module, NamedTuple, PyTrait, Str, Type},
     value::{Marker, Value},
 };
 
+/// Sys module functions.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, strum::Display, serde::Serialize, serde::Deserialize)]
+#[strum(serialize_all = "lowercase")]
+pub(crate) enum SysFunctions {
+    Exit,
+}
+
 /// Creates the `sys` module and allocates it on the heap.
 ///
 /// Returns a HeapId pointing to the newly allocated module.
@@ -29,10 +45,25 @@ pub fn create_module(vm: &mut VM<'_, '_, impl ResourceTracker>) -> Result<HeapId
     // sys.platform
     module.set_attr(StaticStrings::Platform, StaticStrings::Monty.into(), vm);
 
-    // sys.stdout / sys.stderr - markers for standard output/error
+    // sys.stdout / sys.stderr - markers for standard output/error, see `call_stream_method`
     module.set_attr(StaticStrings::Stdout, Value::Marker(Marker(StaticStrings::Stdout)), vm);
     module.set_attr(StaticStrings::Stderr, Value::Marker(Marker(StaticStrings::Stderr)), vm);
 
+    // sys.argv - the command line arguments given by the host
+    let mut argv = Vec::with_capacity(vm.argv.len());
+    for arg in &vm.argv {
+        argv.push(Value::Ref(vm.heap.allocate(HeapData::Str(Str::from(arg.clone())))?));
+    }
+    let argv_id = vm.heap.allocate(HeapData::List(List::new(argv)))?;
+    module.set_attr(StaticStrings::Argv, Value::Ref(argv_id), vm);
+
+    // sys.exit - raises SystemExit
+    module.set_attr(
+        StaticStrings::Exit,
+        Value::ModuleFunction(ModuleFunctions::Sys(SysFunctions::Exit)),
+        vm,
+    );
+
     // sys.version
     module.set_attr(StaticStrings::Version, StaticStrings::MontyVersionString.into(), vm);
     // sys.version_info - named tuple (major=3, minor=14, micro=0, releaselevel='final', serial=0)
@@ -58,3 +89,65 @@ pub fn create_module(vm: &mut VM<'_, '_, impl ResourceTracker>) -> Result<HeapId
 
     vm.heap.allocate(HeapData::Module(module))
 }
+
+/// Dispatches a call to a sys module function.
+pub(super) fn call(
+    vm: &mut VM<'_, '_, impl ResourceTracker>,
+    function: SysFunctions,
+    args: ArgValues,
+) -> RunResult<CallResult> {
+    match function {
+        SysFunctions::Exit => exit(vm, args),
+    }
+}
+
+/// Implementation of `sys.exit(arg=None)`.
+///
+/// Always raises `SystemExit` with `arg` as its code, see `SimpleException::system_exit()`.
+fn exit(vm: &mut VM<'_, '_, impl ResourceTracker>, args: ArgValues) -> RunResult<CallResult> {
+    let Some(code) = args.get_zero_one_arg("exit", vm.heap)? else {
+        return Err(SimpleException::new_none(ExcType::SystemExit).into());
+    };
+    defer_drop!(code, vm);
+    Err(SimpleException::system_exit(code, vm).into())
+}
+
+/// Calls a method of `sys.stdout` or `sys.stderr`.
+///
+/// `write(s)` writes `s` to the stream and returns the number of characters written;
+/// `flush()` does nothing, as output is never buffered.
+pub(crate) fn call_stream_method(
+    stream: Marker,
+    method_id: StringId,
+    args: ArgValues,
+    vm: &mut VM<'_, '_, impl ResourceTracker>,
+) -> RunResult<Value> {
+    let output = if stream.0 == StaticStrings::Stderr {
+        OutputStream::Stderr
+    } else {
+        OutputStream::Stdout
+    };
+    if method_id == StaticStrings::Write {
+        let text = args.get_one_arg("write", vm.heap)?;
+        defer_drop!(text, vm);
+        let Some(text) = text.as_either_str(vm.heap) else {
+            return Err(ExcType::type_error(format!(
+                "write() argument must be str, not {}",
+                text.py_type(vm.heap)
+            )));
+        };
+        let text = text.as_str(vm.interns).to_owned();
+        let count = text.chars().count();
+        output.write(text.into(), vm)?;
+        Ok(Value::Int(i64::try_from(count).expect("string length fits in i64")))
+    } else if method_id == StaticStrings::Flush {
+        args.check_zero_args("flush", vm.heap)?;
+        Ok(Value::None)
+    } else {
+        args.drop_with_heap(vm.heap);
+        Err(ExcType::attribute_error(
+            Type::TextIOWrapper,
+            vm.interns.get_str(method_id),
+        ))
+    }
+}
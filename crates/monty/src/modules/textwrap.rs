@@ -0,0 +1,577 @@
+//! Implementation of Python's `textwrap` module.
+//!
+//! ## Implemented functions
+//!
+//! - `wrap(text, width=70, **kwargs)` → `list[str]`
+//! - `fill(text, width=70, **kwargs)` → `str`
+//! - `shorten(text, width, **kwargs)` → `str`
+//! - `dedent(text)` → `str`
+//! - `indent(text, prefix, predicate=None)` → `str`
+//!
+//! `wrap`, `fill` and `shorten` accept the same keyword arguments as CPython's
+//! `TextWrapper` constructor. The `TextWrapper` class itself is not exposed.
+//!
+//! Text is split into chunks exactly as CPython's `TextWrapper.wordsep_re` would,
+//! so lines break in the same places, including after hyphens in compound words.
+
+use crate::{
+    args::ArgValues,
+    bytecode::VM,
+    defer_drop,
+    exception_private::{ExcType, RunError, RunResult, SimpleException},
+    heap::{DropWithHeap, Heap, HeapData, HeapId},
+    intern::StaticStrings,
+    modules::ModuleFunctions,
+    resource::{ResourceError, ResourceTracker, check_estimated_size},
+    types::{List, Module, PyTrait, re_pattern::value_to_str, str::allocate_string, template::is_line_break},
+    value::Value,
+};
+
+/// Functions exposed by the `textwrap` module.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, strum::Display, serde::Serialize, serde::Deserialize)]
+#[strum(serialize_all = "snake_case")]
+pub(crate) enum TextwrapFunctions {
+    Wrap,
+    Fill,
+    Shorten,
+    Dedent,
+    Indent,
+}
+
+/// Creates the `textwrap` module and allocates it on the heap.
+///
+/// # Returns
+/// A `HeapId` pointing to the newly allocated module.
+///
+/// # Panics
+/// Panics if the required strings have not been pre-interned during prepare phase.
+pub fn create_module(vm: &mut VM<'_, '_, impl ResourceTracker>) -> Result<HeapId, ResourceError> {
+    let mut module = Module::new(StaticStrings::Textwrap);
+
+    for (name, func) in TEXTWRAP_FUNCTIONS {
+        module.set_attr(*name, Value::ModuleFunction(ModuleFunctions::Textwrap(*func)), vm);
+    }
+
+    vm.heap.allocate(HeapData::Module(module))
+}
+
+/// Static mapping of attribute names to textwrap functions for module creation.
+const TEXTWRAP_FUNCTIONS: &[(StaticStrings, TextwrapFunctions)] = &[
+    (StaticStrings::Wrap, TextwrapFunctions::Wrap),
+    (StaticStrings::Fill, TextwrapFunctions::Fill),
+    (StaticStrings::Shorten, TextwrapFunctions::Shorten),
+    (StaticStrings::Dedent, TextwrapFunctions::Dedent),
+    (StaticStrings::Indent, TextwrapFunctions::Indent),
+];
+
+/// Parameters shared by `wrap`, `fill` and `shorten`, in `TextWrapper` constructor order.
+const WRAPPER_PARAMS: [&str; 13] = [
+    "text",
+    "width",
+    "initial_indent",
+    "subsequent_indent",
+    "expand_tabs",
+    "replace_whitespace",
+    "fix_sentence_endings",
+    "break_long_words",
+    "drop_whitespace",
+    "break_on_hyphens",
+    "tabsize",
+    "max_lines",
+    "placeholder",
+];
+
+/// Dispatches a call to a textwrap module function.
+pub(super) fn call(
+    vm: &mut VM<'_, '_, impl ResourceTracker>,
+    function: TextwrapFunctions,
+    args: ArgValues,
+) -> RunResult<Value> {
+    match function {
+        TextwrapFunctions::Wrap | TextwrapFunctions::Fill => {
+            let name = if function == TextwrapFunctions::Wrap {
+                "wrap"
+            } else {
+                "fill"
+            };
+            let params = args.bind_params(name, WRAPPER_PARAMS, 1, vm.heap, vm.interns)?;
+            defer_drop!(params, vm);
+            let text = value_to_str(
+                params[0].as_ref().expect("required parameter is always bound"),
+                vm.heap,
+                vm.interns,
+            )?;
+            let wrapper = TextWrapper::from_params(params, vm)?;
+            let lines = wrapper.wrap(&text, vm.heap)?;
+            if function == TextwrapFunctions::Wrap {
+                let mut items = Vec::with_capacity(lines.len());
+                for line in lines {
+                    items.push(allocate_string(line, vm.heap)?);
+                }
+                Ok(Value::Ref(vm.heap.allocate(HeapData::List(List::new(items)))?))
+            } else {
+                allocate_string(lines.join("\n"), vm.heap)
+            }
+        }
+        TextwrapFunctions::Shorten => {
+            let params = args.bind_params("shorten", WRAPPER_PARAMS, 2, vm.heap, vm.interns)?;
+            defer_drop!(params, vm);
+            let text = value_to_str(
+                params[0].as_ref().expect("required parameter is always bound"),
+                vm.heap,
+                vm.interns,
+            )?;
+            let mut wrapper = TextWrapper::from_params(params, vm)?;
+            wrapper.max_lines = Some(1);
+            let text = text.split_whitespace().collect::<Vec<_>>().join(" ");
+            let lines = wrapper.wrap(&text, vm.heap)?;
+            allocate_string(lines.join("\n"), vm.heap)
+        }
+        TextwrapFunctions::Dedent => {
+            let text = args.get_one_arg("textwrap.dedent", vm.heap)?;
+            defer_drop!(text, vm);
+            let text = dedent(&value_to_str(text, vm.heap, vm.interns)?);
+            allocate_string(text, vm.heap)
+        }
+        TextwrapFunctions::Indent => indent(vm, args),
+    }
+}
+
+/// Options controlling line wrapping, mirroring CPython's `TextWrapper` attributes.
+struct TextWrapper {
+    width: i64,
+    initial_indent: String,
+    subsequent_indent: String,
+    expand_tabs: bool,
+    replace_whitespace: bool,
+    fix_sentence_endings: bool,
+    break_long_words: bool,
+    drop_whitespace: bool,
+    break_on_hyphens: bool,
+    tabsize: i64,
+    max_lines: Option<i64>,
+    placeholder: String,
+}
+
+impl TextWrapper {
+    /// Reads the wrapper options from parameters bound with [`WRAPPER_PARAMS`],
+    /// applying CPython's defaults for any that were not given.
+    fn from_params(params: &[Option<Value>; 13], vm: &VM<'_, '_, impl ResourceTracker>) -> RunResult<Self> {
+        let int = |index: usize, default: i64| match &params[index] {
+            Some(value) => value.as_int(vm.heap),
+            None => Ok(default),
+        };
+        let flag = |index: usize, default: bool| params[index].as_ref().map_or(default, |value| value.py_bool(vm));
+        let string = |index: usize, default: &str| -> RunResult<String> {
+            match &params[index] {
+                Some(value) => Ok(value_to_str(value, vm.heap, vm.interns)?.into_owned()),
+                None => Ok(default.to_owned()),
+            }
+        };
+        let max_lines = match &params[11] {
+            None | Some(Value::None) => None,
+            Some(value) => Some(value.as_int(vm.heap)?),
+        };
+        Ok(Self {
+            width: int(1, 70)?,
+            initial_indent: string(2, "")?,
+            subsequent_indent: string(3, "")?,
+            expand_tabs: flag(4, true),
+            replace_whitespace: flag(5, true),
+            fix_sentence_endings: flag(6, false),
+            break_long_words: flag(7, true),
+            drop_whitespace: flag(8, true),
+            break_on_hyphens: flag(9, true),
+            tabsize: int(10, 8)?,
+            max_lines,
+            placeholder: string(12, " [...]")?,
+        })
+    }
+
+    /// Wraps `text` into lines of at most `width` characters.
+    fn wrap(&self, text: &str, heap: &Heap<impl ResourceTracker>) -> RunResult<Vec<String>> {
+        let text = self.munge_whitespace(text);
+        check_estimated_size(text.len(), heap.tracker())?;
+        let mut chunks = split_chunks(&text, self.break_on_hyphens);
+        if self.fix_sentence_endings {
+            fix_sentence_endings(&mut chunks);
+        }
+        self.wrap_chunks(chunks, heap)
+    }
+
+    /// Expands tabs and replaces other whitespace characters with spaces.
+    fn munge_whitespace(&self, text: &str) -> String {
+        let mut out = String::with_capacity(text.len());
+        let mut column = 0;
+        for c in text.chars() {
+            match c {
+                '\t' if self.expand_tabs => {
+                    if self.tabsize > 0 {
+                        let tabsize = usize::try_from(self.tabsize).expect("tabsize is positive");
+                        let spaces = tabsize - column % tabsize;
+                        out.extend(std::iter::repeat_n(' ', spaces));
+                        column += spaces;
+                    }
+                }
+                '\n' | '\r' => {
+                    out.push(if self.replace_whitespace { ' ' } else { c });
+                    column = 0;
+                }
+                '\t' | '\x0b' | '\x0c' if self.replace_whitespace => {
+                    out.push(' ');
+                    column += 1;
+                }
+                _ => {
+                    out.push(c);
+                    column += 1;
+                }
+            }
+        }
+        out
+    }
+
+    /// Port of CPython's `TextWrapper._wrap_chunks`.
+    fn wrap_chunks(&self, mut chunks: Vec<String>, heap: &Heap<impl ResourceTracker>) -> RunResult<Vec<String>> {
+        if self.width <= 0 {
+            return Err(value_error(format!("invalid width {} (must be > 0)", self.width)));
+        }
+        if let Some(max_lines) = self.max_lines {
+            let indent = if max_lines > 1 {
+                &self.subsequent_indent
+            } else {
+                &self.initial_indent
+            };
+            if char_len(indent) + char_len(self.placeholder.trim_start()) > self.width {
+                return Err(value_error("placeholder too large for max width".to_owned()));
+            }
+        }
+
+        let mut lines: Vec<String> = Vec::new();
+        let mut output_len = 0;
+        // Reverse so chunks can be popped from the end.
+        chunks.reverse();
+        while !chunks.is_empty() {
+            heap.check_time()?;
+            let mut cur_line: Vec<String> = Vec::new();
+            let mut cur_len = 0;
+            let indent = if lines.is_empty() {
+                &self.initial_indent
+            } else {
+                &self.subsequent_indent
+            };
+            let width = self.width - char_len(indent);
+
+            // Drop leading whitespace, except at the very beginning of the text.
+            if self.drop_whitespace && !lines.is_empty() && is_blank(chunks.last().expect("chunks is not empty")) {
+                chunks.pop();
+            }
+
+            while let Some(chunk) = chunks.last() {
+                let len = char_len(chunk);
+                if cur_len + len > width {
+                    break;
+                }
+                cur_line.push(chunks.pop().expect("chunk was just inspected"));
+                cur_len += len;
+            }
+
+            // The next chunk is too long to fit on any line.
+            if chunks.last().is_some_and(|chunk| char_len(chunk) > width) {
+                self.handle_long_word(&mut chunks, &mut cur_line, cur_len, width);
+                cur_len = cur_line.iter().map(|chunk| char_len(chunk)).sum();
+            }
+
+            // Drop trailing whitespace.
+            if self.drop_whitespace && cur_line.last().is_some_and(|chunk| is_blank(chunk)) {
+                cur_len -= char_len(&cur_line.pop().expect("line is not empty"));
+            }
+
+            if cur_line.is_empty() {
+                continue;
+            }
+            let fits = match self.max_lines {
+                None => true,
+                Some(max_lines) => {
+                    i64::try_from(lines.len()).expect("line count fits in i64") + 1 < max_lines
+                        || ((chunks.is_empty() || (self.drop_whitespace && chunks.len() == 1 && is_blank(&chunks[0])))
+                            && cur_len <= width)
+                }
+            };
+            if fits {
+                let line = format!("{indent}{}", cur_line.concat());
+                output_len += line.len();
+                check_estimated_size(output_len, heap.tracker())?;
+                lines.push(line);
+                continue;
+            }
+
+            // Truncate: put the placeholder on this line, or on the previous one if nothing fits.
+            let placeholder_len = char_len(&self.placeholder);
+            while let Some(last) = cur_line.last() {
+                if !is_blank(last) && cur_len + placeholder_len <= width {
+                    cur_line.push(self.placeholder.clone());
+                    lines.push(format!("{indent}{}", cur_line.concat()));
+                    return Ok(lines);
+                }
+                cur_len -= char_len(last);
+                cur_line.pop();
+            }
+            if let Some(prev_line) = lines.last_mut() {
+                let trimmed_len = prev_line.trim_end().len();
+                if char_len(&prev_line[..trimmed_len]) + placeholder_len <= self.width {
+                    prev_line.truncate(trimmed_len);
+                    prev_line.push_str(&self.placeholder);
+                    return Ok(lines);
+                }
+            }
+            lines.push(format!("{indent}{}", self.placeholder.trim_start()));
+            return Ok(lines);
+        }
+        Ok(lines)
+    }
+
+    /// Port of CPython's `TextWrapper._handle_long_word`: breaks the chunk at the top of
+    /// `reversed_chunks` so that part of it fills the current line.
+    fn handle_long_word(
+        &self,
+        reversed_chunks: &mut Vec<String>,
+        cur_line: &mut Vec<String>,
+        cur_len: i64,
+        width: i64,
+    ) {
+        let space_left = if width < 1 { 1 } else { width - cur_len };
+        if self.break_long_words && space_left > 0 {
+            let chunk = reversed_chunks.last_mut().expect("caller checked a chunk remains");
+            let chars: Vec<char> = chunk.chars().collect();
+            let space_left = usize::try_from(space_left).expect("space_left is positive");
+            let mut end = space_left;
+            if self.break_on_hyphens && chars.len() > space_left {
+                // Break after the last hyphen, but only if there are non-hyphens before it.
+                if let Some(hyphen) = chars[..space_left].iter().rposition(|c| *c == '-')
+                    && hyphen > 0
+                    && chars[..hyphen].iter().any(|c| *c != '-')
+                {
+                    end = hyphen + 1;
+                }
+            }
+            let end = end.min(chars.len());
+            cur_line.push(chars[..end].iter().collect());
+            *chunk = chars[end..].iter().collect();
+        } else if cur_line.is_empty() {
+            cur_line.push(reversed_chunks.pop().expect("caller checked a chunk remains"));
+        }
+    }
+}
+
+/// Splits text into the chunks CPython's `TextWrapper._split` produces.
+///
+/// Runs of whitespace are always their own chunk. With `break_on_hyphens`, words are
+/// additionally split after hyphens in compound words (`"well-known"` →
+/// `"well-"`, `"known"`) and around em-dashes (`"this--that"`).
+fn split_chunks(text: &str, break_on_hyphens: bool) -> Vec<String> {
+    let chars: Vec<char> = text.chars().collect();
+    let mut chunks = Vec::new();
+    let mut start = 0;
+    while start < chars.len() {
+        let end = if is_wrap_whitespace(chars[start]) {
+            start + chars[start..].iter().take_while(|c| is_wrap_whitespace(**c)).count()
+        } else if break_on_hyphens {
+            word_end(&chars, start)
+        } else {
+            start + chars[start..].iter().take_while(|c| !is_wrap_whitespace(**c)).count()
+        };
+        chunks.push(chars[start..end].iter().collect());
+        start = end;
+    }
+    chunks
+}
+
+/// Returns the end of the word chunk starting at `start`, following the alternatives
+/// of CPython's `wordsep_re` in order.
+fn word_end(chars: &[char], start: usize) -> usize {
+    if let Some(end) = em_dash_end(chars, start) {
+        return end;
+    }
+    let letter_at = |i: usize| chars.get(i).is_some_and(|c| is_letter(*c));
+    let mut end = start + 1;
+    loop {
+        // A hyphen in a compound word: `-(?:(?<=lt{2}-)|(?<=lt-lt-))(?=lt-?lt)`.
+        if chars.get(end) == Some(&'-') {
+            let behind = (end >= 2 && letter_at(end - 2) && letter_at(end - 1))
+                || (end >= 3 && letter_at(end - 3) && chars[end - 2] == '-' && letter_at(end - 1));
+            let ahead =
+                letter_at(end + 1) && (letter_at(end + 2) || (chars.get(end + 2) == Some(&'-') && letter_at(end + 3)));
+            if behind && ahead {
+                return end + 1;
+            }
+        }
+        // End of the word, or the start of an em-dash.
+        if end == chars.len() || is_wrap_whitespace(chars[end]) || em_dash_end(chars, end).is_some() {
+            return end;
+        }
+        end += 1;
+    }
+}
+
+/// Matches an em-dash between words (`(?<=wp)-{2,}(?=\w)`) at `pos`, returning its end.
+fn em_dash_end(chars: &[char], pos: usize) -> Option<usize> {
+    if pos == 0 || !is_word_punct(chars[pos - 1]) {
+        return None;
+    }
+    let hyphens = chars[pos..].iter().take_while(|c| **c == '-').count();
+    let end = pos + hyphens;
+    (hyphens >= 2 && chars.get(end).is_some_and(|c| is_word_char(*c))).then_some(end)
+}
+
+/// Whether `c` is one of the whitespace characters textwrap splits on (`\t\n\x0b\x0c\r `).
+fn is_wrap_whitespace(c: char) -> bool {
+    matches!(c, '\t' | '\n' | '\x0b' | '\x0c' | '\r' | ' ')
+}
+
+/// Regex `\w`: alphanumeric or underscore.
+fn is_word_char(c: char) -> bool {
+    c.is_alphanumeric() || c == '_'
+}
+
+/// Regex `[^\d\W]`: a word character that is not a digit.
+fn is_letter(c: char) -> bool {
+    is_word_char(c) && !c.is_numeric()
+}
+
+/// `wordsep_re`'s `word_punct`: a word character or one of `!"'&.,?`.
+fn is_word_punct(c: char) -> bool {
+    is_word_char(c) || matches!(c, '!' | '"' | '\'' | '&' | '.' | ',' | '?')
+}
+
+/// Ensures sentence-ending punctuation is followed by two spaces.
+///
+/// A chunk ends a sentence if it matches `[a-z][.!?]["']?$`.
+fn fix_sentence_endings(chunks: &mut [String]) {
+    let mut i = 0;
+    while i + 1 < chunks.len() {
+        if chunks[i + 1] == " " && ends_sentence(&chunks[i]) {
+            chunks[i + 1] = "  ".to_owned();
+            i += 2;
+        } else {
+            i += 1;
+        }
+    }
+}
+
+/// Whether `chunk` ends with a lowercase letter, sentence punctuation and an optional quote.
+fn ends_sentence(chunk: &str) -> bool {
+    let mut chars = chunk.chars().rev().peekable();
+    if chars.peek().is_some_and(|c| matches!(c, '"' | '\'')) {
+        chars.next();
+    }
+    chars.next().is_some_and(|c| matches!(c, '.' | '!' | '?')) && chars.next().is_some_and(|c| c.is_ascii_lowercase())
+}
+
+/// Python's `str.strip() == ''`.
+fn is_blank(s: &str) -> bool {
+    s.trim().is_empty()
+}
+
+/// Length in characters, as Python's `len()`, for comparison against widths.
+fn char_len(s: &str) -> i64 {
+    i64::try_from(s.chars().count()).expect("string length fits in i64")
+}
+
+/// Builds a `ValueError` with the given message.
+fn value_error(msg: String) -> RunError {
+    SimpleException::new_msg(ExcType::ValueError, msg).into()
+}
+
+/// Removes any common leading spaces and tabs from every line of `text`.
+///
+/// Lines consisting solely of spaces and tabs are normalized to empty lines and
+/// ignored when computing the margin, as in CPython.
+fn dedent(text: &str) -> String {
+    let lines: Vec<&str> = text
+        .split('\n')
+        .map(|line| {
+            if line.chars().all(|c| matches!(c, ' ' | '\t')) {
+                ""
+            } else {
+                line
+            }
+        })
+        .collect();
+    let mut margin: Option<&str> = None;
+    for line in &lines {
+        if line.is_empty() {
+            continue;
+        }
+        let indent_len = line.len() - line.trim_start_matches([' ', '\t']).len();
+        let indent = &line[..indent_len];
+        margin = Some(match margin {
+            None => indent,
+            Some(margin) => {
+                let common = margin.bytes().zip(indent.bytes()).take_while(|(x, y)| x == y).count();
+                &margin[..common]
+            }
+        });
+    }
+    let margin = margin.unwrap_or("");
+    lines
+        .iter()
+        .map(|line| line.strip_prefix(margin).unwrap_or(line))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// `textwrap.indent(text, prefix, predicate=None)`: adds `prefix` to the selected lines.
+///
+/// Without a predicate, every line that is not entirely whitespace is prefixed.
+fn indent(vm: &mut VM<'_, '_, impl ResourceTracker>, args: ArgValues) -> RunResult<Value> {
+    let params = args.bind_params("indent", ["text", "prefix", "predicate"], 2, vm.heap, vm.interns)?;
+    defer_drop!(params, vm);
+    let [Some(text), Some(prefix), predicate] = params else {
+        unreachable!("required parameters are always bound")
+    };
+    let text = value_to_str(text, vm.heap, vm.interns)?.into_owned();
+    let prefix = value_to_str(prefix, vm.heap, vm.interns)?.into_owned();
+    let predicate = predicate.as_ref().filter(|p| !matches!(p, Value::None));
+
+    let mut result = String::with_capacity(text.len());
+    for line in split_lines_keepends(&text) {
+        let selected = match predicate {
+            Some(predicate) => {
+                let line = allocate_string(line.to_owned(), vm.heap)?;
+                let selected = vm.evaluate_function("textwrap.indent() predicate", predicate, ArgValues::One(line))?;
+                let truthy = selected.py_bool(vm);
+                selected.drop_with_heap(vm);
+                truthy
+            }
+            None => !line.chars().all(char::is_whitespace),
+        };
+        if selected {
+            result.push_str(&prefix);
+        }
+        result.push_str(line);
+        check_estimated_size(result.len(), vm.heap.tracker())?;
+    }
+    allocate_string(result, vm.heap)
+}
+
+/// Splits `text` like `str.splitlines(keepends=True)`.
+fn split_lines_keepends(text: &str) -> Vec<&str> {
+    let mut lines = Vec::new();
+    let mut start = 0;
+    let mut chars = text.char_indices().peekable();
+    while let Some((i, c)) = chars.next() {
+        if is_line_break(c) {
+            let mut end = i + c.len_utf8();
+            if c == '\r' && chars.peek().is_some_and(|(_, next)| *next == '\n') {
+                chars.next();
+                end += 1;
+            }
+            lines.push(&text[start..end]);
+            start = end;
+        }
+    }
+    if start < text.len() {
+        lines.push(&text[start..]);
+    }
+    lines
+}
@@ -0,0 +1,122 @@
+//! Implementation of Python's `unicodedata` module.
+//!
+//! ## Implemented functions
+//!
+//! - `normalize(form, unistr)` → `str`
+//! - `is_normalized(form, unistr)` → `bool`
+//!
+//! `form` is one of `'NFC'`, `'NFKC'`, `'NFD'` or `'NFKD'`. Normalization uses the
+//! `unicode-normalization` crate, whose Unicode version may differ slightly from the
+//! one bundled with CPython.
+
+use unicode_normalization::UnicodeNormalization;
+
+use crate::{
+    args::ArgValues,
+    bytecode::VM,
+    defer_drop,
+    exception_private::{ExcType, RunResult, SimpleException},
+    heap::{HeapData, HeapId},
+    intern::StaticStrings,
+    modules::ModuleFunctions,
+    resource::{ResourceError, ResourceTracker, check_estimated_size},
+    types::{Module, PyTrait, re_pattern::value_to_str, str::allocate_string},
+    value::Value,
+};
+
+/// Functions exposed by the `unicodedata` module.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, strum::Display, serde::Serialize, serde::Deserialize)]
+#[strum(serialize_all = "snake_case")]
+pub(crate) enum UnicodedataFunctions {
+    Normalize,
+    IsNormalized,
+}
+
+/// A Unicode normalization form.
+#[derive(Debug, Clone, Copy)]
+enum Form {
+    Nfc,
+    Nfkc,
+    Nfd,
+    Nfkd,
+}
+
+/// Creates the `unicodedata` module and allocates it on the heap.
+///
+/// # Returns
+/// A `HeapId` pointing to the newly allocated module.
+///
+/// # Panics
+/// Panics if the required strings have not been pre-interned during prepare phase.
+pub fn create_module(vm: &mut VM<'_, '_, impl ResourceTracker>) -> Result<HeapId, ResourceError> {
+    let mut module = Module::new(StaticStrings::Unicodedata);
+
+    module.set_attr(
+        StaticStrings::Normalize,
+        Value::ModuleFunction(ModuleFunctions::Unicodedata(UnicodedataFunctions::Normalize)),
+        vm,
+    );
+    module.set_attr(
+        StaticStrings::IsNormalized,
+        Value::ModuleFunction(ModuleFunctions::Unicodedata(UnicodedataFunctions::IsNormalized)),
+        vm,
+    );
+
+    vm.heap.allocate(HeapData::Module(module))
+}
+
+/// Dispatches a call to a unicodedata module function.
+pub(super) fn call(
+    vm: &mut VM<'_, '_, impl ResourceTracker>,
+    function: UnicodedataFunctions,
+    args: ArgValues,
+) -> RunResult<Value> {
+    let (form, unistr) = args.get_two_args(&function.to_string(), vm.heap)?;
+    defer_drop!(form, vm);
+    defer_drop!(unistr, vm);
+    let form = parse_form(form, function, vm)?;
+    if !unistr.is_str(vm.heap) {
+        return Err(ExcType::type_error(format!(
+            "{function}() argument 2 must be str, not {}",
+            unistr.py_type(vm.heap)
+        )));
+    }
+    let unistr = value_to_str(unistr, vm.heap, vm.interns)?;
+
+    match function {
+        UnicodedataFunctions::Normalize => {
+            // Decomposition expands each character by at most a small constant factor.
+            check_estimated_size(unistr.len().saturating_mul(4), vm.heap.tracker())?;
+            let normalized: String = match form {
+                Form::Nfc => unistr.nfc().collect(),
+                Form::Nfkc => unistr.nfkc().collect(),
+                Form::Nfd => unistr.nfd().collect(),
+                Form::Nfkd => unistr.nfkd().collect(),
+            };
+            allocate_string(normalized, vm.heap)
+        }
+        UnicodedataFunctions::IsNormalized => Ok(Value::Bool(match form {
+            Form::Nfc => unicode_normalization::is_nfc(&unistr),
+            Form::Nfkc => unicode_normalization::is_nfkc(&unistr),
+            Form::Nfd => unicode_normalization::is_nfd(&unistr),
+            Form::Nfkd => unicode_normalization::is_nfkd(&unistr),
+        })),
+    }
+}
+
+/// Parses the normalization form argument, raising CPython's errors for bad values.
+fn parse_form(form: &Value, function: UnicodedataFunctions, vm: &VM<'_, '_, impl ResourceTracker>) -> RunResult<Form> {
+    let Some(name) = form.as_either_str(vm.heap) else {
+        return Err(ExcType::type_error(format!(
+            "{function}() argument 1 must be str, not {}",
+            form.py_type(vm.heap)
+        )));
+    };
+    match name.as_str(vm.interns) {
+        "NFC" => Ok(Form::Nfc),
+        "NFKC" => Ok(Form::Nfkc),
+        "NFD" => Ok(Form::Nfd),
+        "NFKD" => Ok(Form::Nfkd),
+        _ => Err(SimpleException::new_msg(ExcType::ValueError, "invalid normalization form").into()),
+    }
+}
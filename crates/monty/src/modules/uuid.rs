@@ -0,0 +1,181 @@
+//! Implementation of Python's `uuid` module.
+//!
+//! ## Implemented
+//!
+//! - `UUID(hex=None, bytes=None, bytes_le=None, fields=None, int=None, version=None)` class
+//! - `uuid3(namespace, name)` → name-based UUID using MD5
+//! - `uuid4()` → random UUID
+//! - `uuid5(namespace, name)` → name-based UUID using SHA-1
+//! - `NAMESPACE_DNS`, `NAMESPACE_URL`, `NAMESPACE_OID`, `NAMESPACE_X500`
+//! - `RESERVED_NCS`, `RFC_4122`, `RESERVED_MICROSOFT`, `RESERVED_FUTURE`
+//!
+//! `uuid4()` takes its 16 random bytes from the host: it yields `OsFunction::Urandom`
+//! and the VM turns the returned bytes into a `UUID`, so the host controls entropy.
+//! `uuid1()` and the other time/MAC-address based functions are not supported.
+
+use crate::{
+    args::ArgValues,
+    builtins::Builtins,
+    bytecode::{CallResult, VM},
+    defer_drop,
+    digest::{HashAlgorithm, digest},
+    exception_private::{ExcType, RunError, RunResult, SimpleException},
+    heap::{HeapData, HeapId},
+    intern::StaticStrings,
+    modules::ModuleFunctions,
+    os::{OsFunction, OsResultConversion},
+    resource::{ResourceError, ResourceTracker},
+    types::{Module, PyTrait, Type, Uuid},
+    value::Value,
+};
+
+/// Functions exposed by the `uuid` module.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, strum::Display, serde::Serialize, serde::Deserialize)]
+#[strum(serialize_all = "snake_case")]
+pub(crate) enum UuidFunctions {
+    Uuid3,
+    Uuid4,
+    Uuid5,
+}
+
+/// The well-known namespace UUIDs from RFC 4122, appendix C.
+const NAMESPACES: [(StaticStrings, u128); 4] = [
+    (StaticStrings::NamespaceDns, 0x6ba7_b810_9dad_11d1_80b4_00c0_4fd4_30c8),
+    (StaticStrings::NamespaceUrl, 0x6ba7_b811_9dad_11d1_80b4_00c0_4fd4_30c8),
+    (StaticStrings::NamespaceOid, 0x6ba7_b812_9dad_11d1_80b4_00c0_4fd4_30c8),
+    (StaticStrings::NamespaceX500, 0x6ba7_b814_9dad_11d1_80b4_00c0_4fd4_30c8),
+];
+
+/// Creates the `uuid` module and allocates it on the heap.
+///
+/// # Returns
+/// A `HeapId` pointing to the newly allocated module.
+///
+/// # Panics
+/// Panics if the required strings have not been pre-interned during prepare phase.
+pub fn create_module(vm: &mut VM<'_, '_, impl ResourceTracker>) -> Result<HeapId, ResourceError> {
+    let mut module = Module::new(StaticStrings::Uuid);
+
+    let functions = [
+        (StaticStrings::Uuid3, UuidFunctions::Uuid3),
+        (StaticStrings::Uuid4, UuidFunctions::Uuid4),
+        (StaticStrings::Uuid5, UuidFunctions::Uuid5),
+    ];
+    for (name, function) in functions {
+        module.set_attr(name, Value::ModuleFunction(ModuleFunctions::Uuid(function)), vm);
+    }
+
+    module.set_attr(StaticStrings::UuidClass, Value::Builtin(Builtins::Type(Type::Uuid)), vm);
+
+    for (name, value) in NAMESPACES {
+        let id = vm.heap.allocate(HeapData::Uuid(Uuid::new(value)))?;
+        module.set_attr(name, Value::Ref(id), vm);
+    }
+
+    let variants = [
+        (StaticStrings::ReservedNcs, StaticStrings::ReservedNcsValue),
+        (StaticStrings::Rfc4122, StaticStrings::Rfc4122Value),
+        (StaticStrings::ReservedMicrosoft, StaticStrings::ReservedMicrosoftValue),
+        (StaticStrings::ReservedFuture, StaticStrings::ReservedFutureValue),
+    ];
+    for (name, value) in variants {
+        module.set_attr(name, value.into(), vm);
+    }
+
+    vm.heap.allocate(HeapData::Module(module))
+}
+
+/// Dispatches a call to a uuid module function.
+///
+/// Returns `CallResult::OsCall` for `uuid4()`, which needs random bytes from the host,
+/// and `CallResult::Value` for the deterministic functions.
+pub(super) fn call(
+    vm: &mut VM<'_, '_, impl ResourceTracker>,
+    function: UuidFunctions,
+    args: ArgValues,
+) -> RunResult<CallResult> {
+    match function {
+        UuidFunctions::Uuid3 => name_based(vm, args, "uuid3", HashAlgorithm::Md5, 3).map(CallResult::Value),
+        UuidFunctions::Uuid4 => {
+            args.check_zero_args("uuid4", vm.heap)?;
+            vm.set_os_result_conversion(OsResultConversion::Uuid4);
+            Ok(CallResult::OsCall(OsFunction::Urandom, ArgValues::One(Value::Int(16))))
+        }
+        UuidFunctions::Uuid5 => name_based(vm, args, "uuid5", HashAlgorithm::Sha1, 5).map(CallResult::Value),
+    }
+}
+
+/// Implementation of `uuid3(namespace, name)` and `uuid5(namespace, name)`.
+///
+/// Hashes the namespace bytes followed by `name` (UTF-8 encoded if it is a `str`) and
+/// uses the first 16 bytes of the digest as the UUID, with the variant and `version` set.
+fn name_based(
+    vm: &mut VM<'_, '_, impl ResourceTracker>,
+    args: ArgValues,
+    func_name: &str,
+    algorithm: HashAlgorithm,
+    version: u8,
+) -> RunResult<Value> {
+    let (namespace, name) = args.get_two_args(func_name, vm.heap)?;
+    defer_drop!(namespace, vm);
+    defer_drop!(name, vm);
+
+    let mut data = match namespace {
+        Value::Ref(id) => match vm.heap.get(*id) {
+            HeapData::Uuid(uuid) => uuid.to_bytes().to_vec(),
+            _ => return Err(ExcType::attribute_error(namespace.py_type(vm.heap), "bytes")),
+        },
+        _ => return Err(ExcType::attribute_error(namespace.py_type(vm.heap), "bytes")),
+    };
+    let name_bytes = match name {
+        Value::InternString(id) => vm.interns.get_str(*id).as_bytes(),
+        Value::InternBytes(id) => vm.interns.get_bytes(*id),
+        Value::Ref(id) => match vm.heap.get(*id) {
+            HeapData::Str(s) => s.as_str().as_bytes(),
+            HeapData::Bytes(b) => b.as_slice(),
+            _ => return Err(concat_error(name, vm)),
+        },
+        _ => return Err(concat_error(name, vm)),
+    };
+    data.extend_from_slice(name_bytes);
+
+    let hash = digest(algorithm, &data);
+    let bytes: [u8; 16] = hash[..16].try_into().expect("digest is at least 16 bytes");
+    let id = vm.heap.allocate(HeapData::Uuid(Uuid::with_version(bytes, version)))?;
+    Ok(Value::Ref(id))
+}
+
+/// The `TypeError` for a `name` argument that is neither `str` nor `bytes`.
+fn concat_error(name: &Value, vm: &VM<'_, '_, impl ResourceTracker>) -> RunError {
+    ExcType::type_error(format!("can't concat {} to bytes", name.py_type(vm.heap)))
+}
+
+/// Builds the result of `uuid4()` from the host's `OsFunction::Urandom` result.
+///
+/// The host must return exactly 16 bytes; anything else raises an error in the
+/// interpreter at the `uuid4()` call site.
+pub(crate) fn uuid4_from_random(value: Value, vm: &mut VM<'_, '_, impl ResourceTracker>) -> RunResult<Value> {
+    defer_drop!(value, vm);
+    let data = match value {
+        Value::InternBytes(id) => Some(vm.interns.get_bytes(*id)),
+        Value::Ref(id) => match vm.heap.get(*id) {
+            HeapData::Bytes(b) => Some(b.as_slice()),
+            _ => None,
+        },
+        _ => None,
+    };
+    let Some(data) = data else {
+        return Err(ExcType::type_error(format!(
+            "os.urandom() result must be bytes, not {}",
+            value.py_type(vm.heap)
+        )));
+    };
+    let bytes: [u8; 16] = data.try_into().map_err(|_| {
+        SimpleException::new_msg(
+            ExcType::ValueError,
+            format!("os.urandom(16) returned {} bytes", data.len()),
+        )
+    })?;
+    let id = vm.heap.allocate(HeapData::Uuid(Uuid::with_version(bytes, 4)))?;
+    Ok(Value::Ref(id))
+}
@@ -0,0 +1,445 @@
+//! Implementation of Python's `zlib` module.
+//!
+//! ## Implemented
+//!
+//! - `compress(data, /, level=-1, wbits=15)` → `bytes`
+//! - `decompress(data, /, wbits=15, bufsize=16384)` → `bytes`
+//! - `crc32(data, value=0, /)` → `int`
+//! - `adler32(data, value=1, /)` → `int`
+//! - `MAX_WBITS`, `DEFLATED`, `DEF_BUF_SIZE`, `DEF_MEM_LEVEL` and the `Z_*_COMPRESSION` levels
+//! - `error`
+//!
+//! `wbits` selects the container as in CPython: 9 to 15 for a zlib header, -9 to -15 for a
+//! raw deflate stream and 25 to 31 for a gzip header; `decompress()` also accepts 40 to 47
+//! to detect zlib or gzip from the header. Deflate itself comes from `miniz_oxide`, so
+//! compressed output is valid but not always byte-identical to CPython's zlib.
+//!
+//! Decompressed output is charged against the resource tracker as it grows, so a small
+//! "zip bomb" raises `MemoryError` instead of exhausting the host. The streaming
+//! `compressobj()`/`decompressobj()` objects are not supported.
+
+use miniz_oxide::{
+    DataFormat, MZError, MZFlush, MZStatus,
+    deflate::{compress_to_vec, compress_to_vec_zlib},
+    inflate::{
+        TINFLStatus,
+        stream::{InflateState, inflate as inflate_stream},
+    },
+};
+
+use crate::{
+    args::ArgValues,
+    builtins::Builtins,
+    bytecode::VM,
+    defer_drop,
+    exception_private::{ExcType, RunError, RunResult, SimpleException},
+    heap::{HeapData, HeapId},
+    intern::StaticStrings,
+    modules::{
+        ModuleFunctions,
+        binascii::{allocate_bytes, bytes_arg, crc32},
+    },
+    resource::{ResourceError, ResourceTracker, check_estimated_size},
+    types::Module,
+    value::Value,
+};
+
+/// Functions exposed by the `zlib` module.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, strum::Display, serde::Serialize, serde::Deserialize)]
+#[strum(serialize_all = "snake_case")]
+pub(crate) enum ZlibFunctions {
+    Compress,
+    Decompress,
+    Crc32,
+    Adler32,
+}
+
+/// `zlib.MAX_WBITS`: the largest window size, as a base-two logarithm.
+const MAX_WBITS: i64 = 15;
+/// `zlib.DEF_BUF_SIZE`: the default `bufsize` of `decompress()`.
+const DEF_BUF_SIZE: i64 = 16384;
+/// `zlib.Z_DEFAULT_COMPRESSION`, which means level 6.
+const Z_DEFAULT_COMPRESSION: i64 = -1;
+
+/// Size of each chunk of output produced by one step of decompression.
+///
+/// Between chunks the output size is checked against the resource tracker, along with the time limit.
+const INFLATE_CHUNK_SIZE: usize = 64 * 1024;
+
+/// Creates the `zlib` module and allocates it on the heap.
+///
+/// # Returns
+/// A `HeapId` pointing to the newly allocated module.
+///
+/// # Panics
+/// Panics if the required strings have not been pre-interned during prepare phase.
+pub fn create_module(vm: &mut VM<'_, '_, impl ResourceTracker>) -> Result<HeapId, ResourceError> {
+    let mut module = Module::new(StaticStrings::Zlib);
+
+    let functions = [
+        (StaticStrings::Compress, ZlibFunctions::Compress),
+        (StaticStrings::Decompress, ZlibFunctions::Decompress),
+        (StaticStrings::Crc32, ZlibFunctions::Crc32),
+        (StaticStrings::Adler32, ZlibFunctions::Adler32),
+    ];
+    for (name, function) in functions {
+        module.set_attr(name, Value::ModuleFunction(ModuleFunctions::Zlib(function)), vm);
+    }
+
+    let constants = [
+        (StaticStrings::MaxWbits, MAX_WBITS),
+        (StaticStrings::Deflated, 8),
+        (StaticStrings::DefBufSize, DEF_BUF_SIZE),
+        (StaticStrings::DefMemLevel, 8),
+        (StaticStrings::ZNoCompression, 0),
+        (StaticStrings::ZBestSpeed, 1),
+        (StaticStrings::ZBestCompression, 9),
+        (StaticStrings::ZDefaultCompression, Z_DEFAULT_COMPRESSION),
+    ];
+    for (name, value) in constants {
+        module.set_attr(name, Value::Int(value), vm);
+    }
+
+    module.set_attr(
+        StaticStrings::Error,
+        Value::Builtin(Builtins::ExcType(ExcType::ZlibError)),
+        vm,
+    );
+
+    vm.heap.allocate(HeapData::Module(module))
+}
+
+/// Dispatches a call to a zlib module function.
+pub(super) fn call(
+    vm: &mut VM<'_, '_, impl ResourceTracker>,
+    function: ZlibFunctions,
+    args: ArgValues,
+) -> RunResult<Value> {
+    match function {
+        ZlibFunctions::Compress => {
+            let params = args.bind_params("compress", ["data", "level", "wbits"], 1, vm.heap, vm.interns)?;
+            defer_drop!(params, vm);
+            let [Some(data), level, wbits] = params else {
+                unreachable!("required parameter is always bound")
+            };
+            let level = match level {
+                Some(level) => level.as_int(vm.heap)?,
+                None => Z_DEFAULT_COMPRESSION,
+            };
+            let wbits = match wbits {
+                Some(wbits) => wbits.as_int(vm.heap)?,
+                None => MAX_WBITS,
+            };
+            let data = bytes_arg(data, vm.heap, vm.interns)?;
+            let level = compression_level(level)?;
+            let container = match wbits {
+                9..=15 => Container::Zlib,
+                -15..=-9 => Container::Raw,
+                25..=31 => Container::Gzip,
+                _ => return Err(ExcType::zlib_error("Bad compression level")),
+            };
+            allocate_bytes(deflate(data, level, container), vm.heap)
+        }
+        ZlibFunctions::Decompress => {
+            let params = args.bind_params("decompress", ["data", "wbits", "bufsize"], 1, vm.heap, vm.interns)?;
+            defer_drop!(params, vm);
+            let [Some(data), wbits, bufsize] = params else {
+                unreachable!("required parameter is always bound")
+            };
+            let wbits = match wbits {
+                Some(wbits) => wbits.as_int(vm.heap)?,
+                None => MAX_WBITS,
+            };
+            // `bufsize` is only the initial output buffer size, so it is validated but not used
+            if let Some(bufsize) = bufsize
+                && bufsize.as_int(vm.heap)? < 0
+            {
+                return Err(SimpleException::new_msg(ExcType::ValueError, "bufsize must be non-negative").into());
+            }
+            let data = bytes_arg(data, vm.heap, vm.interns)?;
+            let container = match wbits {
+                0 | 8..=15 => Container::Zlib,
+                -15..=-8 => Container::Raw,
+                24..=31 => Container::Gzip,
+                32 | 40..=47 if data.starts_with(&GZIP_MAGIC) => Container::Gzip,
+                32 | 40..=47 => Container::Zlib,
+                _ => {
+                    return Err(ExcType::zlib_error(
+                        "Error -2 while preparing to decompress data: inconsistent stream state",
+                    ));
+                }
+            };
+            let decompressed = decompress(data, container, vm.heap.tracker()).map_err(|err| err.into_zlib_error())?;
+            allocate_bytes(decompressed, vm.heap)
+        }
+        ZlibFunctions::Crc32 | ZlibFunctions::Adler32 => {
+            let (data, value) = args.get_one_two_args(&format!("zlib.{function}"), vm.heap)?;
+            defer_drop!(data, vm);
+            let initial = match value {
+                Some(value) => {
+                    let initial = value.as_int(vm.heap);
+                    value.drop_with_heap(vm);
+                    // CPython masks the starting value to 32 bits
+                    u32::try_from(initial?.rem_euclid(1 << 32)).expect("value reduced modulo 2**32")
+                }
+                None if function == ZlibFunctions::Crc32 => 0,
+                None => 1,
+            };
+            let data = bytes_arg(data, vm.heap, vm.interns)?;
+            let checksum = if function == ZlibFunctions::Crc32 {
+                crc32(data, initial)
+            } else {
+                adler32(data, initial)
+            };
+            Ok(Value::Int(i64::from(checksum)))
+        }
+    }
+}
+
+/// The framing around a deflate stream, selected by `wbits`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Container {
+    /// Two-byte zlib header and Adler-32 trailer.
+    Zlib,
+    /// Bare deflate data with no header or trailer.
+    Raw,
+    /// Gzip header and CRC-32/length trailer.
+    Gzip,
+}
+
+/// The two magic bytes that start every gzip member.
+const GZIP_MAGIC: [u8; 2] = [0x1f, 0x8b];
+
+/// Converts a Python compression level to a deflate level, treating -1 as the default of 6.
+pub(super) fn compression_level(level: i64) -> RunResult<u8> {
+    match level {
+        -1 => Ok(6),
+        0..=9 => Ok(u8::try_from(level).expect("level is in 0..=9")),
+        _ => Err(ExcType::zlib_error("Bad compression level")),
+    }
+}
+
+/// Compresses `data` at `level` and wraps it in `container`.
+fn deflate(data: &[u8], level: u8, container: Container) -> Vec<u8> {
+    match container {
+        Container::Zlib => compress_to_vec_zlib(data, level),
+        Container::Raw => compress_to_vec(data, level),
+        Container::Gzip => gzip_member(data, level, 0, 3),
+    }
+}
+
+/// Builds a single gzip member with a minimal header, as written by zlib.
+///
+/// `os` is the header's OS byte: zlib writes 3 (Unix) while `gzip.compress()` writes 255 (unknown).
+pub(super) fn gzip_member(data: &[u8], level: u8, mtime: u32, os: u8) -> Vec<u8> {
+    let extra_flags = match level {
+        9 => 2,
+        1 => 4,
+        _ => 0,
+    };
+    let mut out = vec![GZIP_MAGIC[0], GZIP_MAGIC[1], 8, 0];
+    out.extend_from_slice(&mtime.to_le_bytes());
+    out.push(extra_flags);
+    out.push(os);
+    out.extend_from_slice(&compress_to_vec(data, level));
+    out.extend_from_slice(&crc32(data, 0).to_le_bytes());
+    out.extend_from_slice(&gzip_size(data.len()).to_le_bytes());
+    out
+}
+
+/// The gzip trailer's size field: the uncompressed length modulo 2**32.
+pub(super) fn gzip_size(len: usize) -> u32 {
+    #[expect(clippy::cast_possible_truncation, reason = "gzip stores the size modulo 2**32")]
+    let size = len as u32;
+    size
+}
+
+/// Why decompressing a deflate stream failed.
+#[derive(Debug)]
+pub(super) enum InflateError {
+    /// The input ended before the end of the stream.
+    Truncated,
+    /// The input is not valid compressed data; holds zlib's description.
+    Invalid(&'static str),
+    /// The output would exceed the resource limits.
+    Resource(ResourceError),
+}
+
+impl InflateError {
+    /// Converts the error to the exception `zlib.decompress()` raises.
+    fn into_zlib_error(self) -> RunError {
+        match self {
+            Self::Truncated => ExcType::zlib_error("Error -5 while decompressing data: incomplete or truncated stream"),
+            Self::Invalid(msg) => ExcType::zlib_error(format!("Error -3 while decompressing data: {msg}")),
+            Self::Resource(err) => err.into(),
+        }
+    }
+}
+
+impl From<ResourceError> for InflateError {
+    fn from(err: ResourceError) -> Self {
+        Self::Resource(err)
+    }
+}
+
+/// Decompresses a complete stream in `container`, as `zlib.decompress()` does.
+///
+/// Any data after the end of the stream is ignored.
+fn decompress(data: &[u8], container: Container, tracker: &impl ResourceTracker) -> Result<Vec<u8>, InflateError> {
+    match container {
+        Container::Zlib => {
+            check_zlib_header(data)?;
+            inflate(data, DataFormat::Zlib, 0, tracker).map(|(out, _)| out)
+        }
+        Container::Raw => inflate(data, DataFormat::Raw, 0, tracker).map(|(out, _)| out),
+        Container::Gzip => {
+            let header_len = match parse_gzip_header(data) {
+                GzipHeader::Valid(len) => len,
+                GzipHeader::Truncated => return Err(InflateError::Truncated),
+                GzipHeader::BadMagic => return Err(InflateError::Invalid("incorrect header check")),
+                GzipHeader::BadMethod => return Err(InflateError::Invalid("unknown compression method")),
+            };
+            let (out, consumed) = inflate(&data[header_len..], DataFormat::Raw, 0, tracker)?;
+            let trailer = &data[header_len + consumed..];
+            let (Some(crc), Some(size)) = (read_u32_le(trailer, 0), read_u32_le(trailer, 4)) else {
+                return Err(InflateError::Truncated);
+            };
+            if crc != crc32(&out, 0) {
+                return Err(InflateError::Invalid("incorrect data check"));
+            }
+            if size != gzip_size(out.len()) {
+                return Err(InflateError::Invalid("incorrect length check"));
+            }
+            Ok(out)
+        }
+    }
+}
+
+/// Validates a zlib header the way zlib does, so errors carry zlib's messages.
+fn check_zlib_header(data: &[u8]) -> Result<(), InflateError> {
+    let [cmf, flg, ..] = *data else {
+        return Err(InflateError::Truncated);
+    };
+    if ((u16::from(cmf) << 8) | u16::from(flg)) % 31 != 0 || cmf & 0x0f != 8 {
+        return Err(InflateError::Invalid("incorrect header check"));
+    }
+    if cmf >> 4 > 7 {
+        return Err(InflateError::Invalid("invalid window size"));
+    }
+    Ok(())
+}
+
+/// Decompresses one deflate stream from the start of `data`.
+///
+/// Output is produced in chunks; before each chunk is kept, the total size (including
+/// `base_len` bytes already produced by the caller) is checked against the resource
+/// tracker, which is what stops decompression bombs.
+///
+/// Returns the decompressed bytes and the number of input bytes the stream used.
+pub(super) fn inflate(
+    data: &[u8],
+    format: DataFormat,
+    base_len: usize,
+    tracker: &impl ResourceTracker,
+) -> Result<(Vec<u8>, usize), InflateError> {
+    let mut state = InflateState::new_boxed(format);
+    let mut chunk = vec![0; INFLATE_CHUNK_SIZE];
+    let mut out = Vec::new();
+    let mut consumed = 0;
+    loop {
+        let result = inflate_stream(&mut state, &data[consumed..], &mut chunk, MZFlush::None);
+        consumed += result.bytes_consumed;
+        check_estimated_size(base_len + out.len() + result.bytes_written, tracker)?;
+        tracker.check_time()?;
+        out.extend_from_slice(&chunk[..result.bytes_written]);
+        match result.status {
+            Ok(MZStatus::StreamEnd) => return Ok((out, consumed)),
+            Ok(MZStatus::Ok) if result.bytes_consumed > 0 || result.bytes_written > 0 => {}
+            Ok(MZStatus::Ok) | Err(MZError::Buf) => return Err(InflateError::Truncated),
+            Ok(MZStatus::NeedDict) => return Err(InflateError::Invalid("need dictionary")),
+            Err(_) if state.last_status() == TINFLStatus::Adler32Mismatch => {
+                return Err(InflateError::Invalid("incorrect data check"));
+            }
+            Err(_) => return Err(InflateError::Invalid("invalid compressed data")),
+        }
+    }
+}
+
+/// The result of parsing a gzip member header.
+pub(super) enum GzipHeader {
+    /// A valid header of the given length in bytes.
+    Valid(usize),
+    /// The input ends inside the header.
+    Truncated,
+    /// The input does not start with both gzip magic bytes.
+    BadMagic,
+    /// The compression method is not deflate.
+    BadMethod,
+}
+
+/// Parses the gzip member header at the start of `data`, skipping the optional fields.
+pub(super) fn parse_gzip_header(data: &[u8]) -> GzipHeader {
+    const FHCRC: u8 = 0x02;
+    const FEXTRA: u8 = 0x04;
+    const FNAME: u8 = 0x08;
+    const FCOMMENT: u8 = 0x10;
+
+    if !data.starts_with(&GZIP_MAGIC) {
+        return GzipHeader::BadMagic;
+    }
+    let [_, _, method, flags, ..] = *data else {
+        return GzipHeader::Truncated;
+    };
+    if method != 8 {
+        return GzipHeader::BadMethod;
+    }
+    let mut pos = 10;
+    if flags & FEXTRA != 0 {
+        let Some(&[lo, hi]) = data.get(pos..pos + 2) else {
+            return GzipHeader::Truncated;
+        };
+        pos += 2 + usize::from(u16::from_le_bytes([lo, hi]));
+    }
+    for flag in [FNAME, FCOMMENT] {
+        if flags & flag != 0 {
+            // Null-terminated string; an unterminated one runs to the end of the input
+            pos = match data.get(pos..).and_then(|rest| rest.iter().position(|&b| b == 0)) {
+                Some(nul) => pos + nul + 1,
+                None => data.len(),
+            };
+        }
+    }
+    if flags & FHCRC != 0 {
+        pos += 2;
+    }
+    if pos > data.len() {
+        GzipHeader::Truncated
+    } else {
+        GzipHeader::Valid(pos)
+    }
+}
+
+/// Reads a little-endian `u32` at `offset`, or `None` if `data` is too short.
+pub(super) fn read_u32_le(data: &[u8], offset: usize) -> Option<u32> {
+    let bytes = data.get(offset..offset + 4)?;
+    Some(u32::from_le_bytes(bytes.try_into().expect("slice has 4 bytes")))
+}
+
+/// Computes the Adler-32 checksum of `data`, continuing from `adler`.
+fn adler32(data: &[u8], adler: u32) -> u32 {
+    // Largest number of bytes that can be summed before `b` could overflow a u32
+    const NMAX: usize = 5552;
+    const MOD: u32 = 65521;
+
+    let mut a = adler & 0xffff;
+    let mut b = adler >> 16;
+    for block in data.chunks(NMAX) {
+        for &byte in block {
+            a += u32::from(byte);
+            b += a;
+        }
+        a %= MOD;
+        b %= MOD;
+    }
+    (b << 16) | a
+}
@@ -0,0 +1,904 @@
+//! An `OsHandler` that exposes real host directories to sandboxed code.
+//!
+//! `MountFs` maps sandbox paths onto host directories mounted at sandbox paths, e.g.
+//! `./data` at `/data`, and performs `OsFunction`s against the real filesystem:
+//!
+//! ```no_run
+//! use monty::{MontyRun, MountFs, NoLimitTracker, OsFunction, PrintWriter, RunProgress};
+//!
+//! let code = "from pathlib import Path\nPath('/out/summary.txt').write_text(Path('/data/in.txt').read_text())";
+//! let runner = MontyRun::new(code.to_owned(), "main.py", vec![]).unwrap();
+//! let mut fs = MountFs::new()
+//!     .mount("./data", "/data", true)
+//!     .unwrap()
+//!     .mount("./out", "/out", false)
+//!     .unwrap()
+//!     .deny([OsFunction::Rmtree])
+//!     .max_bytes_written(1024 * 1024);
+//!
+//! let mut progress = runner.start(vec![], NoLimitTracker, PrintWriter::Stdout).unwrap();
+//! while let RunProgress::OsCall(call) = progress {
+//!     progress = call.resume_with(&mut fs, PrintWriter::Stdout).unwrap();
+//! }
+//! ```
+//!
+//! Every path is checked after resolving `..` and symlinks, so sandboxed code can't reach
+//! anything outside its mounts: escapes raise `PermissionError`, as do writes to read-only
+//! mounts and functions excluded by `allow()` or `deny()`. Paths outside every mount don't
+//! exist. Files opened with `open()` are read and written in place, a call at a time. The
+//! environment is empty, and `os.urandom()` uses the host's secure random source.
+
+use std::{
+    collections::{BTreeMap, HashSet},
+    fs,
+    io::{self, Read, Seek, SeekFrom, Write},
+    path::{Component, Path, PathBuf},
+    time::{Duration, UNIX_EPOCH},
+};
+
+use crate::{
+    ExcType, MontyException, MontyObject,
+    os::{OsFunction, OsHandler, dir_stat, file_stat},
+    os_handler::{
+        FsResult, OpenMode, absolute, bad_argument, bad_file_descriptor, components, count, decode, file_exists,
+        glob_match, int_arg, invalid_seek, is_a_directory, join, kwarg_bool, not_a_directory, not_found, os_error,
+        permission_denied, str_arg, write_data, written,
+    },
+    run_progress::ExtFunctionResult,
+};
+
+/// How many symlinks are followed while checking a path before giving up, like Linux's `ELOOP` limit.
+const MAX_SYMLINKS: usize = 40;
+
+/// How many bytes `file.readline` and sized `file.read` calls read from the host at a time.
+const READ_CHUNK_SIZE: usize = 8192;
+
+/// A host filesystem jail implementing `OsHandler`.
+///
+/// Build one with `new()`, add directories with `mount()` and restrict it further with
+/// `allow()`, `deny()` and `max_bytes_written()`, then resume `OsCall`s with
+/// `OsCall::resume_with()`.
+#[derive(Debug, Default)]
+pub struct MountFs {
+    mounts: Vec<Mount>,
+    /// Functions sandboxed code may call, `None` to allow all but those in `denied`.
+    allowed: Option<HashSet<OsFunction>>,
+    /// Functions sandboxed code may not call.
+    denied: HashSet<OsFunction>,
+    /// Maximum number of bytes written across all calls, `None` for no limit.
+    max_bytes_written: Option<usize>,
+    /// Bytes written so far, counted when data is written rather than when files are closed.
+    bytes_written: usize,
+    /// Files opened with `open()`, by handle.
+    open_files: BTreeMap<i64, HostFile>,
+    /// The last handle returned by `OsFunction::Open`.
+    last_handle: i64,
+}
+
+/// A host directory mounted into the sandbox.
+#[derive(Debug)]
+struct Mount {
+    /// Components of the sandbox path the directory is mounted at.
+    point: Vec<String>,
+    /// The host directory, canonicalized so resolved paths can be compared against it.
+    host_dir: PathBuf,
+    read_only: bool,
+}
+
+impl MountFs {
+    /// Creates a handler with no mounts, where every path is missing.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Mounts `host_dir` at `sandbox_path`, making it read-only if `read_only` is set.
+    ///
+    /// When mounts are nested, the deepest one containing a path handles it.
+    ///
+    /// # Errors
+    /// Returns an error if `host_dir` doesn't exist or isn't a directory.
+    pub fn mount(mut self, host_dir: impl AsRef<Path>, sandbox_path: &str, read_only: bool) -> io::Result<Self> {
+        let host_dir = host_dir.as_ref().canonicalize()?;
+        if !host_dir.is_dir() {
+            return Err(io::Error::new(
+                io::ErrorKind::NotADirectory,
+                format!("{} is not a directory", host_dir.display()),
+            ));
+        }
+        self.mounts.push(Mount {
+            point: components(sandbox_path),
+            host_dir,
+            read_only,
+        });
+        Ok(self)
+    }
+
+    /// Only allows the given functions, on top of any `deny()`.
+    ///
+    /// The `file.*` functions on files returned by `open()` are always allowed,
+    /// so allow or deny `OsFunction::Open` to control access to them.
+    #[must_use]
+    pub fn allow(mut self, functions: impl IntoIterator<Item = OsFunction>) -> Self {
+        self.allowed = Some(functions.into_iter().collect());
+        self
+    }
+
+    /// Denies the given functions, which raise `PermissionError` in the sandbox.
+    #[must_use]
+    pub fn deny(mut self, functions: impl IntoIterator<Item = OsFunction>) -> Self {
+        self.denied.extend(functions);
+        self
+    }
+
+    /// Limits the total number of bytes sandboxed code can write, across all calls.
+    ///
+    /// Writes that would exceed the limit raise `OSError` with `EDQUOT` and write nothing.
+    #[must_use]
+    pub fn max_bytes_written(mut self, limit: usize) -> Self {
+        self.max_bytes_written = Some(limit);
+        self
+    }
+
+    /// Returns the number of bytes written so far.
+    #[must_use]
+    pub fn bytes_written(&self) -> usize {
+        self.bytes_written
+    }
+
+    /// Checks whether sandboxed code may call `function`.
+    fn is_permitted(&self, function: OsFunction) -> bool {
+        let file_function = matches!(
+            function,
+            OsFunction::FileRead
+                | OsFunction::FileReadline
+                | OsFunction::FileWrite
+                | OsFunction::FileSeek
+                | OsFunction::FileTell
+                | OsFunction::FileClose
+        );
+        file_function
+            || (!self.denied.contains(&function)
+                && self.allowed.as_ref().is_none_or(|allowed| allowed.contains(&function)))
+    }
+
+    /// Maps a sandbox path to its mount and host path, checking it stays inside the mount.
+    ///
+    /// With `follow_symlinks` unset, only the path's parent is checked, so operations on
+    /// a symlink itself (e.g. unlinking it) work wherever it points.
+    fn host_path(&self, path: &str, follow_symlinks: bool) -> FsResult<(&Mount, PathBuf)> {
+        let parts = components(path);
+        let mount = self
+            .mounts
+            .iter()
+            .filter(|mount| parts.starts_with(&mount.point))
+            .max_by_key(|mount| mount.point.len())
+            .ok_or_else(|| not_found(path))?;
+        let relative = &parts[mount.point.len()..];
+        let host = relative
+            .iter()
+            .fold(mount.host_dir.clone(), |host, part| host.join(part));
+        let checked = match host.parent() {
+            Some(parent) if !follow_symlinks && !relative.is_empty() => parent,
+            _ => host.as_path(),
+        };
+        if resolve_host(checked).is_some_and(|resolved| resolved.starts_with(&mount.host_dir)) {
+            Ok((mount, host))
+        } else {
+            Err(permission_denied(path))
+        }
+    }
+
+    /// Like `host_path()`, but raises `PermissionError` if the mount is read-only.
+    fn writable_host_path(&self, path: &str, follow_symlinks: bool) -> FsResult<PathBuf> {
+        match self.host_path(path, follow_symlinks)? {
+            (mount, _) if mount.read_only => Err(permission_denied(path)),
+            (_, host) => Ok(host),
+        }
+    }
+
+    /// Counts `len` bytes against the quota, raising `OSError` if they don't fit.
+    fn record_write(&mut self, len: usize, path: &str) -> FsResult<()> {
+        match self.max_bytes_written {
+            Some(max) if self.bytes_written.saturating_add(len) > max => {
+                Err(os_error(ExcType::OSError, 122, "Disk quota exceeded", path))
+            }
+            _ => {
+                self.bytes_written += len;
+                Ok(())
+            }
+        }
+    }
+
+    /// Performs an OS function, returning its result or the exception to raise.
+    fn call(
+        &mut self,
+        function: OsFunction,
+        args: &[MontyObject],
+        kwargs: &[(MontyObject, MontyObject)],
+    ) -> FsResult<MontyObject> {
+        if !self.is_permitted(function) {
+            let target = match args.first() {
+                Some(MontyObject::Path(path) | MontyObject::String(path)) => path.clone(),
+                _ => function.to_string(),
+            };
+            return Err(os_error(
+                ExcType::PermissionError,
+                1,
+                "Operation not permitted",
+                &target,
+            ));
+        }
+        match function {
+            // the host's environment stays private
+            OsFunction::Getenv => Ok(args.get(1).cloned().unwrap_or(MontyObject::None)),
+            OsFunction::GetEnviron => Ok(MontyObject::Dict(Vec::<(MontyObject, MontyObject)>::new().into())),
+            OsFunction::Urandom => {
+                let size = usize::try_from(int_arg(function, args, 0)?).map_err(|_| {
+                    MontyException::new(ExcType::ValueError, Some("negative argument not allowed".to_owned()))
+                })?;
+                let mut bytes = vec![0; size];
+                getrandom::fill(&mut bytes)
+                    .map_err(|err| MontyException::new(ExcType::OSError, Some(err.to_string())))?;
+                Ok(MontyObject::Bytes(bytes))
+            }
+            // there is no stdin to read, so `input()` sees its end straight away
+            OsFunction::Input => Err(MontyException::new(
+                ExcType::EOFError,
+                Some("EOF when reading a line".to_owned()),
+            )),
+            OsFunction::FileRead
+            | OsFunction::FileReadline
+            | OsFunction::FileWrite
+            | OsFunction::FileSeek
+            | OsFunction::FileTell
+            | OsFunction::FileClose => self.file_call(function, args),
+            _ => {
+                let path = str_arg(function, args, 0)?;
+                self.path_call(function, path, args, kwargs)
+            }
+        }
+    }
+
+    /// Performs an OS function whose first argument is a path.
+    fn path_call(
+        &mut self,
+        function: OsFunction,
+        path: &str,
+        args: &[MontyObject],
+        kwargs: &[(MontyObject, MontyObject)],
+    ) -> FsResult<MontyObject> {
+        let io_err = |err: io::Error| io_error(&err, path);
+        match function {
+            // like `os.path.exists()`, paths that can't be reached don't exist
+            OsFunction::Exists => {
+                let follow_symlinks = kwarg_bool(kwargs, "follow_symlinks", true);
+                let exists = self.host_path(path, follow_symlinks).is_ok_and(|(_, host)| {
+                    if follow_symlinks {
+                        host.exists()
+                    } else {
+                        host.symlink_metadata().is_ok()
+                    }
+                });
+                Ok(MontyObject::Bool(exists))
+            }
+            OsFunction::IsFile => Ok(MontyObject::Bool(
+                self.host_path(path, true).is_ok_and(|(_, host)| host.is_file()),
+            )),
+            OsFunction::IsDir => Ok(MontyObject::Bool(
+                self.host_path(path, true).is_ok_and(|(_, host)| host.is_dir()),
+            )),
+            OsFunction::IsSymlink => Ok(MontyObject::Bool(
+                self.host_path(path, false).is_ok_and(|(_, host)| host.is_symlink()),
+            )),
+            OsFunction::ReadText => {
+                let (_, host) = self.host_path(path, true)?;
+                let content = fs::read(host).map_err(io_err)?;
+                Ok(MontyObject::String(decode(&content)?.to_owned()))
+            }
+            OsFunction::ReadBytes => {
+                let (_, host) = self.host_path(path, true)?;
+                Ok(MontyObject::Bytes(fs::read(host).map_err(io_err)?))
+            }
+            OsFunction::WriteText | OsFunction::WriteBytes => {
+                let (data, written) = match args.get(1) {
+                    Some(MontyObject::String(text)) if function == OsFunction::WriteText => {
+                        (text.as_bytes(), text.chars().count())
+                    }
+                    Some(MontyObject::Bytes(bytes)) if function == OsFunction::WriteBytes => {
+                        (bytes.as_slice(), bytes.len())
+                    }
+                    other => return Err(bad_argument(function, 1, other)),
+                };
+                let host = self.writable_host_path(path, true)?;
+                if host.is_dir() {
+                    return Err(is_a_directory(path));
+                }
+                self.record_write(data.len(), path)?;
+                fs::write(host, data).map_err(io_err)?;
+                Ok(count(written))
+            }
+            OsFunction::Mkdir => {
+                let host = self.writable_host_path(path, true)?;
+                if host.symlink_metadata().is_ok() {
+                    return if host.is_dir() && kwarg_bool(kwargs, "exist_ok", false) {
+                        Ok(MontyObject::None)
+                    } else {
+                        Err(file_exists(path))
+                    };
+                }
+                if kwarg_bool(kwargs, "parents", false) {
+                    fs::create_dir_all(host).map_err(io_err)?;
+                } else {
+                    fs::create_dir(host).map_err(io_err)?;
+                }
+                Ok(MontyObject::None)
+            }
+            OsFunction::Unlink => {
+                let host = self.writable_host_path(path, false)?;
+                match fs::remove_file(host) {
+                    Err(err) if err.kind() == io::ErrorKind::NotFound && kwarg_bool(kwargs, "missing_ok", false) => {
+                        Ok(MontyObject::None)
+                    }
+                    result => result.map(|()| MontyObject::None).map_err(io_err),
+                }
+            }
+            OsFunction::Rmdir => {
+                let host = self.writable_host_path(path, false)?;
+                fs::remove_dir(host).map_err(io_err)?;
+                Ok(MontyObject::None)
+            }
+            OsFunction::Iterdir => {
+                let (_, host) = self.host_path(path, true)?;
+                let names = read_dir_names(&host).map_err(io_err)?;
+                Ok(MontyObject::List(
+                    names.iter().map(|name| MontyObject::Path(join(path, name))).collect(),
+                ))
+            }
+            OsFunction::Stat => {
+                let (_, host) = self.host_path(path, true)?;
+                let metadata = fs::metadata(host).map_err(io_err)?;
+                Ok(stat(&metadata))
+            }
+            OsFunction::Rename => {
+                let target = str_arg(function, args, 1)?;
+                let from = self.writable_host_path(path, false)?;
+                let to = self.writable_host_path(target, false)?;
+                fs::rename(from, to).map_err(|err| io_error(&err, &format!("{path}' -> '{target}")))?;
+                Ok(MontyObject::None)
+            }
+            OsFunction::Resolve => {
+                let parts = components(path);
+                let (mount, host) = match self.host_path(path, true) {
+                    Ok(found) => found,
+                    Err(exc) if exc.exc_type() == ExcType::PermissionError => return Err(exc),
+                    // outside every mount there are no symlinks to resolve
+                    Err(_) => return Ok(MontyObject::String(absolute(&parts))),
+                };
+                let Some(relative) = resolve_host(&host)
+                    .and_then(|resolved| resolved.strip_prefix(&mount.host_dir).ok().map(Path::to_path_buf))
+                else {
+                    return Err(permission_denied(path));
+                };
+                let mut parts = mount.point.clone();
+                parts.extend(relative.iter().map(|part| part.to_string_lossy().into_owned()));
+                Ok(MontyObject::String(absolute(&parts)))
+            }
+            OsFunction::Absolute => Ok(MontyObject::String(absolute(&components(path)))),
+            OsFunction::Glob | OsFunction::Rglob => {
+                let pattern = str_arg(function, args, 1)?;
+                let pattern = if function == OsFunction::Rglob {
+                    format!("**/{pattern}")
+                } else {
+                    pattern.to_owned()
+                };
+                let pattern: Vec<&str> = pattern
+                    .split('/')
+                    .filter(|part| !part.is_empty() && *part != ".")
+                    .collect();
+                let mut descendants = Vec::new();
+                if let Ok((_, host)) = self.host_path(path, true) {
+                    collect_descendants(&host, &[], &mut descendants);
+                }
+                Ok(MontyObject::List(
+                    descendants
+                        .into_iter()
+                        .filter(|relative| glob_match(&pattern, relative))
+                        .map(|relative| MontyObject::Path(join(path, &relative.join("/"))))
+                        .collect(),
+                ))
+            }
+            OsFunction::Touch => {
+                let host = self.writable_host_path(path, true)?;
+                if host.exists() {
+                    if !kwarg_bool(kwargs, "exist_ok", true) {
+                        return Err(file_exists(path));
+                    }
+                } else {
+                    fs::OpenOptions::new()
+                        .write(true)
+                        .create_new(true)
+                        .open(host)
+                        .map_err(io_err)?;
+                }
+                Ok(MontyObject::None)
+            }
+            OsFunction::Samefile => {
+                let other = str_arg(function, args, 1)?;
+                let mut resolved = Vec::with_capacity(2);
+                for path in [path, other] {
+                    let (_, host) = self.host_path(path, true)?;
+                    resolved.push(host.canonicalize().map_err(|err| io_error(&err, path))?);
+                }
+                Ok(MontyObject::Bool(resolved[0] == resolved[1]))
+            }
+            OsFunction::Walk => {
+                let mut walk = Vec::new();
+                if let Ok((_, host)) = self.host_path(path, true) {
+                    walk_dir(&host, path, kwarg_bool(kwargs, "topdown", true), &mut walk);
+                }
+                Ok(MontyObject::List(walk))
+            }
+            OsFunction::Copy => {
+                let target = str_arg(function, args, 1)?;
+                Ok(MontyObject::String(self.copy(path, target)?))
+            }
+            OsFunction::Copytree => {
+                let target = str_arg(function, args, 1)?;
+                self.copytree(path, target, kwarg_bool(kwargs, "dirs_exist_ok", false))?;
+                Ok(MontyObject::String(target.to_owned()))
+            }
+            OsFunction::Rmtree => {
+                let result = self.writable_host_path(path, false).and_then(|host| {
+                    match host.symlink_metadata().map_err(io_err)? {
+                        metadata if metadata.is_dir() => fs::remove_dir_all(host).map_err(io_err),
+                        _ => Err(not_a_directory(path)),
+                    }
+                });
+                match result {
+                    Err(_) if kwarg_bool(kwargs, "ignore_errors", false) => Ok(MontyObject::None),
+                    result => result.map(|()| MontyObject::None),
+                }
+            }
+            OsFunction::Open => {
+                let mode = str_arg(function, args, 1)?;
+                self.open(path, mode)
+            }
+            OsFunction::Getenv
+            | OsFunction::GetEnviron
+            | OsFunction::Urandom
+            | OsFunction::FileRead
+            | OsFunction::FileReadline
+            | OsFunction::FileWrite
+            | OsFunction::FileSeek
+            | OsFunction::FileTell
+            | OsFunction::FileClose
+            | OsFunction::Input => unreachable!("{function} doesn't take a path"),
+        }
+    }
+
+    /// Copies a file's contents to a file or into a directory, returning the destination.
+    fn copy(&mut self, path: &str, target: &str) -> FsResult<String> {
+        let (_, from) = self.host_path(path, true)?;
+        if from.is_dir() {
+            return Err(is_a_directory(path));
+        }
+        let content = fs::read(&from).map_err(|err| io_error(&err, path))?;
+        let mut target = target.to_owned();
+        if self.host_path(&target, true).is_ok_and(|(_, host)| host.is_dir()) {
+            let name = components(path).pop().unwrap_or_default();
+            target = join(&target, &name);
+        }
+        let to = self.writable_host_path(&target, true)?;
+        self.record_write(content.len(), &target)?;
+        fs::write(to, content).map_err(|err| io_error(&err, &target))?;
+        Ok(target)
+    }
+
+    /// Recursively copies a directory, merging into an existing one if `dirs_exist_ok` is set.
+    ///
+    /// Symlinks inside the source tree are skipped rather than copied or followed, and
+    /// symlinks already in the destination tree may not lead outside it.
+    fn copytree(&mut self, path: &str, target: &str, dirs_exist_ok: bool) -> FsResult<()> {
+        let (_, from) = self.host_path(path, true)?;
+        let metadata = fs::metadata(&from).map_err(|err| io_error(&err, path))?;
+        if !metadata.is_dir() {
+            return Err(not_a_directory(path));
+        }
+        let to = self.writable_host_path(target, true)?;
+        if to.symlink_metadata().is_ok() && !(dirs_exist_ok && to.is_dir()) {
+            return Err(file_exists(target));
+        }
+        let resolved_to = resolve_host(&to).ok_or_else(|| permission_denied(target))?;
+        if from.canonicalize().is_ok_and(|from| resolved_to.starts_with(from)) {
+            // copying a directory into itself would never finish
+            return Err(os_error(ExcType::OSError, 22, "Invalid argument", target));
+        }
+        self.record_write(tree_size(&from), target)?;
+        copy_dir(&from, &to, &resolved_to).map_err(|err| io_error(&err, target))
+    }
+
+    /// Opens a file for `OsFunction::Open`, returning its handle.
+    ///
+    /// `mode` is the normalized mode Monty passes, e.g. `r`, `w+` or `ab`.
+    fn open(&mut self, path: &str, mode: &str) -> FsResult<MontyObject> {
+        let open_mode = OpenMode::parse(mode);
+        let host = if open_mode.writable() {
+            self.writable_host_path(path, true)?
+        } else {
+            self.host_path(path, true)?.1
+        };
+        if host.is_dir() {
+            return Err(is_a_directory(path));
+        }
+        let file = fs::OpenOptions::new()
+            .read(open_mode.readable())
+            .write(open_mode.writable())
+            .append(open_mode.access == b'a')
+            .create(matches!(open_mode.access, b'w' | b'a'))
+            .create_new(open_mode.access == b'x')
+            .truncate(open_mode.access == b'w')
+            .open(&host)
+            .map_err(|err| io_error(&err, path))?;
+        self.last_handle += 1;
+        self.open_files.insert(
+            self.last_handle,
+            HostFile {
+                name: path.to_owned(),
+                file,
+                binary: open_mode.binary,
+            },
+        );
+        Ok(MontyObject::Int(self.last_handle))
+    }
+
+    /// Performs one of the `file.*` OS functions, which operate on a handle from `open()`.
+    fn file_call(&mut self, function: OsFunction, args: &[MontyObject]) -> FsResult<MontyObject> {
+        let handle = int_arg(function, args, 0)?;
+        let file = self.open_files.get(&handle).ok_or_else(bad_file_descriptor)?;
+        match function {
+            OsFunction::FileClose => {
+                // dropping the host file closes it
+                self.open_files.remove(&handle);
+                Ok(MontyObject::None)
+            }
+            OsFunction::FileWrite => {
+                let name = file.name.clone();
+                self.record_write(write_data(args)?.len(), &name)?;
+                let file = self.open_files.get_mut(&handle).expect("handle is open");
+                file.call(function, args)
+            }
+            _ => {
+                let file = self.open_files.get_mut(&handle).expect("handle is open");
+                file.call(function, args)
+            }
+        }
+    }
+}
+
+/// A host file opened with `OsFunction::Open`.
+#[derive(Debug)]
+struct HostFile {
+    /// The path as passed to `open()`, for error messages.
+    name: String,
+    file: fs::File,
+    binary: bool,
+}
+
+impl HostFile {
+    /// Performs `file.read`, `file.readline`, `file.write`, `file.seek` or `file.tell`.
+    fn call(&mut self, function: OsFunction, args: &[MontyObject]) -> FsResult<MontyObject> {
+        match function {
+            OsFunction::FileRead | OsFunction::FileReadline => {
+                let size = usize::try_from(int_arg(function, args, 1)?).ok();
+                let data = self
+                    .read(size, function == OsFunction::FileReadline)
+                    .map_err(|err| io_error(&err, &self.name))?;
+                if self.binary {
+                    Ok(MontyObject::Bytes(data))
+                } else {
+                    Ok(MontyObject::String(decode(&data)?.to_owned()))
+                }
+            }
+            OsFunction::FileWrite => {
+                self.file
+                    .write_all(write_data(args)?)
+                    .map_err(|err| io_error(&err, &self.name))?;
+                Ok(written(args))
+            }
+            OsFunction::FileSeek => {
+                let offset = int_arg(function, args, 1)?;
+                let position = match int_arg(function, args, 2)? {
+                    0 => SeekFrom::Start(u64::try_from(offset).map_err(|_| invalid_seek())?),
+                    1 => SeekFrom::Current(offset),
+                    _ => SeekFrom::End(offset),
+                };
+                let position = self.file.seek(position).map_err(|err| match err.kind() {
+                    io::ErrorKind::InvalidInput => invalid_seek(),
+                    _ => io_error(&err, &self.name),
+                })?;
+                Ok(position_object(position))
+            }
+            OsFunction::FileTell => {
+                let position = self.file.stream_position().map_err(|err| io_error(&err, &self.name))?;
+                Ok(position_object(position))
+            }
+            _ => unreachable!("not a host file.* function: {function}"),
+        }
+    }
+
+    /// Reads from the current position to the end, or up to `size` characters (bytes for a
+    /// binary file) and, for `file.readline`, the end of the line.
+    ///
+    /// The host is read a chunk at a time, and whatever was read past the end is given back
+    /// by seeking back over it, so the next call starts in the right place.
+    fn read(&mut self, size: Option<usize>, line: bool) -> io::Result<Vec<u8>> {
+        let mut data = Vec::new();
+        if size.is_none() && !line {
+            self.file.read_to_end(&mut data)?;
+            return Ok(data);
+        }
+        let mut chunk = [0; READ_CHUNK_SIZE];
+        loop {
+            let read = self.file.read(&mut chunk)?;
+            data.extend_from_slice(&chunk[..read]);
+            if let Some(end) = self.read_end(&data, size, line) {
+                let extra = i64::try_from(data.len() - end).expect("chunks are small");
+                self.file.seek(SeekFrom::Current(-extra))?;
+                data.truncate(end);
+                return Ok(data);
+            }
+            if read == 0 {
+                return Ok(data);
+            }
+        }
+    }
+
+    /// Returns where a read of `data` stops, or `None` if it needs more data.
+    fn read_end(&self, data: &[u8], size: Option<usize>, line: bool) -> Option<usize> {
+        let line_end = if line {
+            data.iter().position(|&b| b == b'\n').map(|i| i + 1)
+        } else {
+            None
+        };
+        let size_end = size.and_then(|size| {
+            if self.binary {
+                (data.len() >= size).then_some(size)
+            } else {
+                // text sizes count characters, which start at any byte but a UTF-8 continuation byte
+                data.iter()
+                    .enumerate()
+                    .filter(|(_, b)| **b & 0xc0 != 0x80)
+                    .nth(size)
+                    .map(|(i, _)| i)
+            }
+        });
+        line_end.into_iter().chain(size_end).min()
+    }
+}
+
+impl OsHandler for MountFs {
+    fn handle_os_call(
+        &mut self,
+        function: OsFunction,
+        args: &[MontyObject],
+        kwargs: &[(MontyObject, MontyObject)],
+    ) -> ExtFunctionResult {
+        match self.call(function, args, kwargs) {
+            Ok(value) => value.into(),
+            Err(exc) => exc.into(),
+        }
+    }
+}
+
+/// Converts a host file position to the `int` `file.seek` and `file.tell` return.
+fn position_object(position: u64) -> MontyObject {
+    MontyObject::Int(i64::try_from(position).expect("host file positions fit in i64"))
+}
+
+/// Resolves every symlink in an absolute host path, including in parts that don't exist yet.
+///
+/// Dangling symlinks are followed by hand, since something could later be created where they
+/// point. Returns `None` if there are too many levels of symlinks.
+fn resolve_host(path: &Path) -> Option<PathBuf> {
+    let mut path = path.to_path_buf();
+    // names below the deepest existing ancestor, innermost first
+    let mut missing = Vec::new();
+    let mut links = 0;
+    loop {
+        if let Ok(resolved) = path.canonicalize() {
+            return Some(
+                missing
+                    .iter()
+                    .rev()
+                    .fold(resolved, |resolved, name| resolved.join(name)),
+            );
+        }
+        if let Ok(target) = fs::read_link(&path) {
+            links += 1;
+            if links > MAX_SYMLINKS {
+                return None;
+            }
+            let parent = path.parent().map(Path::to_path_buf).unwrap_or_default();
+            path = normalize(&parent.join(target));
+        } else {
+            missing.push(path.file_name()?.to_owned());
+            path.pop();
+        }
+    }
+}
+
+/// Resolves `.` and `..` in a path without touching the filesystem.
+fn normalize(path: &Path) -> PathBuf {
+    let mut normalized = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir => {
+                normalized.pop();
+            }
+            component => normalized.push(component),
+        }
+    }
+    normalized
+}
+
+/// Maps a host I/O error to the exception CPython would raise, keeping the host's errno.
+fn io_error(err: &io::Error, path: &str) -> MontyException {
+    let (exc_type, errno, message) = match err.kind() {
+        io::ErrorKind::NotFound => (ExcType::FileNotFoundError, 2, "No such file or directory"),
+        io::ErrorKind::AlreadyExists => (ExcType::FileExistsError, 17, "File exists"),
+        io::ErrorKind::PermissionDenied => (ExcType::PermissionError, 13, "Permission denied"),
+        io::ErrorKind::IsADirectory => (ExcType::IsADirectoryError, 21, "Is a directory"),
+        io::ErrorKind::NotADirectory => (ExcType::NotADirectoryError, 20, "Not a directory"),
+        io::ErrorKind::DirectoryNotEmpty => (ExcType::OSError, 39, "Directory not empty"),
+        io::ErrorKind::InvalidInput => (ExcType::OSError, 22, "Invalid argument"),
+        _ => return MontyException::new(ExcType::OSError, Some(format!("{err}: '{path}'"))),
+    };
+    os_error(exc_type, err.raw_os_error().unwrap_or(errno), message, path)
+}
+
+/// Builds a `stat_result` from host metadata.
+fn stat(metadata: &fs::Metadata) -> MontyObject {
+    #[cfg(unix)]
+    let mode = {
+        use std::os::unix::fs::PermissionsExt;
+        i64::from(metadata.permissions().mode() & 0o7777)
+    };
+    #[cfg(not(unix))]
+    let mode = match (metadata.is_dir(), metadata.permissions().readonly()) {
+        (true, _) => 0o755,
+        (false, true) => 0o444,
+        (false, false) => 0o644,
+    };
+    let mtime = metadata
+        .modified()
+        .ok()
+        .and_then(|modified| modified.duration_since(UNIX_EPOCH).ok())
+        .map_or(0.0, Duration::as_secs_f64);
+    if metadata.is_dir() {
+        dir_stat(mode, mtime)
+    } else {
+        file_stat(mode, i64::try_from(metadata.len()).unwrap_or(i64::MAX), mtime)
+    }
+}
+
+/// Returns the sorted names of a directory's entries.
+fn read_dir_names(dir: &Path) -> io::Result<Vec<String>> {
+    let mut names = fs::read_dir(dir)?
+        .map(|entry| entry.map(|entry| entry.file_name().to_string_lossy().into_owned()))
+        .collect::<io::Result<Vec<_>>>()?;
+    names.sort();
+    Ok(names)
+}
+
+/// Returns a directory's entries sorted by name, with whether each is a directory.
+///
+/// Symlinks count as files, so walking a tree never follows them out of a mount.
+fn dir_entries(dir: &Path) -> Vec<(String, bool)> {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    let mut entries: Vec<(String, bool)> = entries
+        .filter_map(Result::ok)
+        .map(|entry| {
+            let is_dir = entry.file_type().as_ref().is_ok_and(fs::FileType::is_dir);
+            (entry.file_name().to_string_lossy().into_owned(), is_dir)
+        })
+        .collect();
+    entries.sort();
+    entries
+}
+
+/// Appends the path of every entry below `dir`, relative to it, parents before their children.
+///
+/// Unreadable directories are listed but not descended into.
+fn collect_descendants(dir: &Path, prefix: &[String], out: &mut Vec<Vec<String>>) {
+    for (name, is_dir) in dir_entries(dir) {
+        let mut relative = prefix.to_vec();
+        relative.push(name.clone());
+        out.push(relative.clone());
+        if is_dir {
+            collect_descendants(&dir.join(&name), &relative, out);
+        }
+    }
+}
+
+/// Appends the `(dirpath, dirnames, filenames)` tuples `os.walk()` yields for a directory.
+///
+/// Like `os.walk()`, unreadable directories are skipped rather than raising.
+fn walk_dir(dir: &Path, dirpath: &str, topdown: bool, walk: &mut Vec<MontyObject>) {
+    if !dir.is_dir() {
+        return;
+    }
+    let (dirs, files): (Vec<_>, Vec<_>) = dir_entries(dir).into_iter().partition(|(_, is_dir)| *is_dir);
+    let names = |entries: &[(String, bool)]| {
+        MontyObject::List(
+            entries
+                .iter()
+                .map(|(name, _)| MontyObject::String(name.clone()))
+                .collect(),
+        )
+    };
+    let tuple = MontyObject::Tuple(vec![
+        MontyObject::String(dirpath.to_owned()),
+        names(&dirs),
+        names(&files),
+    ]);
+    if topdown {
+        walk.push(tuple.clone());
+    }
+    for (name, _) in &dirs {
+        walk_dir(&dir.join(name), &join(dirpath, name), topdown, walk);
+    }
+    if !topdown {
+        walk.push(tuple);
+    }
+}
+
+/// Returns the total size of the regular files below a directory.
+fn tree_size(dir: &Path) -> usize {
+    dir_entries(dir)
+        .into_iter()
+        .map(|(name, is_dir)| {
+            let path = dir.join(name);
+            if is_dir {
+                tree_size(&path)
+            } else {
+                path.symlink_metadata()
+                    .ok()
+                    .filter(fs::Metadata::is_file)
+                    .map_or(0, |metadata| usize::try_from(metadata.len()).unwrap_or(usize::MAX))
+            }
+        })
+        .fold(0, usize::saturating_add)
+}
+
+/// Copies the regular files and directories below `from` into `to`, creating it if needed.
+///
+/// `root` is the resolved destination of the whole copy. Creating and copying both follow
+/// symlinks, so each destination is checked to resolve below it first: with `dirs_exist_ok`,
+/// a symlink already in the destination tree could otherwise redirect writes anywhere.
+fn copy_dir(from: &Path, to: &Path, root: &Path) -> io::Result<()> {
+    check_below(to, root)?;
+    fs::create_dir_all(to)?;
+    for (name, is_dir) in dir_entries(from) {
+        let (source, target) = (from.join(&name), to.join(&name));
+        if is_dir {
+            copy_dir(&source, &target, root)?;
+        } else if source.symlink_metadata()?.is_file() {
+            check_below(&target, root)?;
+            fs::copy(&source, &target)?;
+        }
+    }
+    Ok(())
+}
+
+/// Fails with `PermissionDenied` unless `path` resolves to somewhere below `root`.
+fn check_below(path: &Path, root: &Path) -> io::Result<()> {
+    if resolve_host(path).is_some_and(|resolved| resolved.starts_with(root)) {
+        Ok(())
+    } else {
+        Err(io::ErrorKind::PermissionDenied.into())
+    }
+}
@@ -16,12 +16,13 @@ use crate::{
     heap::{HeapData, HeapId},
     resource::{ResourceError, ResourceTracker},
     types::{
-        LongInt, NamedTuple, Path, PyTrait, Type, allocate_tuple,
+        LongInt, NamedTuple, Path, PyTrait, Type, Uuid, allocate_tuple,
         bytes::{Bytes, bytes_repr},
         dict::Dict,
         list::List,
         set::{FrozenSet, Set},
         str::{Str, StringRepr, string_repr_fmt},
+        uuid::uuid_str,
     },
     value::{EitherStr, Value},
 };
@@ -125,6 +126,10 @@ pub enum MontyObject {
     ///
     /// Represents a filesystem path. Can be used both as input (from host) and output.
     Path(String),
+    /// Python `uuid.UUID` object, stored as its 128-bit integer value (`UUID.int`).
+    ///
+    /// Can be used both as input (from host) and output.
+    Uuid(u128),
     /// A dataclass instance with class name, field names, attributes, and mutability.
     ///
     /// Method calls are detected lazily at runtime: when `call_attr` is invoked
@@ -172,6 +177,7 @@ impl fmt::Display for MontyObject {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
             Self::String(s) => f.write_str(s),
+            Self::Uuid(u) => f.write_str(&uuid_str(*u)),
             Self::Cycle(_, placeholder) => f.write_str(placeholder),
             Self::Type(t) => write!(f, "<class '{t}'>"),
             Self::Function { name, .. } => write!(f, "<function '{name}' external>"),
@@ -296,6 +302,7 @@ impl MontyObject {
                 Ok(Value::Ref(vm.heap.allocate(HeapData::Dataclass(dc))?))
             }
             Self::Path(s) => Ok(Value::Ref(vm.heap.allocate(HeapData::Path(Path::new(s)))?)),
+            Self::Uuid(u) => Ok(Value::Ref(vm.heap.allocate(HeapData::Uuid(Uuid::new(u)))?)),
             Self::Type(t) => Ok(Value::Builtin(Builtins::Type(t))),
             Self::BuiltinFunction(f) => Ok(Value::Builtin(Builtins::Function(f))),
             Self::Function { name, .. } => {
@@ -476,7 +483,20 @@ impl MontyObject {
                         Self::Repr(format!("<gather({})>", gather.item_count()))
                     }
                     HeapData::Path(path) => Self::Path(path.as_str().to_owned()),
-                    HeapData::RePattern(_) | HeapData::ReMatch(_) => Self::Repr(object.py_repr(vm).into_owned()),
+                    HeapData::Uuid(uuid) => Self::Uuid(uuid.value()),
+                    HeapData::RePattern(_)
+                    | HeapData::ReMatch(_)
+                    | HeapData::Hash(_)
+                    | HeapData::Template(_)
+                    | HeapData::SequenceMatcher(_)
+                    | HeapData::StringIO(_)
+                    | HeapData::File(_)
+                    | HeapData::CsvDialect(_)
+                    | HeapData::CsvWriter(_)
+                    | HeapData::CsvSniffer(_)
+                    | HeapData::OperatorCallable(_)
+                    | HeapData::Struct(_)
+                    | HeapData::Object(_) => Self::Repr(object.py_repr(vm).into_owned()),
                     HeapData::ExtFunction(name) => Self::Function {
                         name: name.clone(),
                         docstring: None,
@@ -655,6 +675,7 @@ impl MontyObject {
                 f.write_char(')')
             }
             Self::Path(p) => write!(f, "PosixPath('{p}')"),
+            Self::Uuid(u) => write!(f, "UUID('{}')", uuid_str(*u)),
             Self::Type(t) => write!(f, "<class '{t}'>"),
             Self::BuiltinFunction(func) => write!(f, "<built-in function {func}>"),
             Self::Function { name, .. } => write!(f, "<function '{name}' external>"),
@@ -691,6 +712,7 @@ impl MontyObject {
             Self::FrozenSet(fs) => !fs.is_empty(),
             Self::Exception { .. } => true,
             Self::Path(_) => true,          // Path instances are always truthy
+            Self::Uuid(_) => true,          // UUID instances are always truthy
             Self::Dataclass { .. } => true, // Dataclass instances are always truthy
             Self::Type(_) | Self::BuiltinFunction(_) | Self::Function { .. } | Self::Repr(_) | Self::Cycle(_, _) => {
                 true
@@ -719,6 +741,7 @@ impl MontyObject {
             Self::FrozenSet(_) => "frozenset",
             Self::Exception { .. } => "Exception",
             Self::Path(_) => "PosixPath",
+            Self::Uuid(_) => "UUID",
             Self::Dataclass { .. } => "dataclass",
             Self::Type(_) => "type",
             Self::BuiltinFunction(_) => "builtin_function_or_method",
@@ -757,6 +780,7 @@ impl Hash for MontyObject {
             Self::String(string) => string.hash(state),
             Self::Bytes(bytes) => bytes.hash(state),
             Self::Path(path) => path.hash(state),
+            Self::Uuid(u) => u.hash(state),
             Self::Type(t) => t.to_string().hash(state),
             Self::Cycle(_, _) => panic!("cycle values are not hashable"),
             _ => panic!("{} python values are not hashable", self.type_name()),
@@ -832,6 +856,7 @@ impl PartialEq for MontyObject {
                     && a_frozen == b_frozen
             }
             (Self::Path(a), Self::Path(b)) => a == b,
+            (Self::Uuid(a), Self::Uuid(b)) => a == b,
             (
                 Self::Function {
                     name: a_name,
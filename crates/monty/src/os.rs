@@ -10,7 +10,7 @@
 //! I/O, filesystem, or network operations. Instead, the host decides whether to
 //! permit and execute such operations.
 
-use crate::{MontyObject, intern::StaticStrings};
+use crate::{MontyObject, intern::StaticStrings, run_progress::ExtFunctionResult};
 
 /// OS operations that require host system access.
 ///
@@ -27,7 +27,7 @@ use crate::{MontyObject, intern::StaticStrings};
     Debug, Clone, Copy, PartialEq, Eq, Hash, strum::EnumString, strum::Display, serde::Serialize, serde::Deserialize,
 )]
 pub enum OsFunction {
-    /// Check if a path exists
+    /// Check if a path exists, passing on a `follow_symlinks` keyword when given
     #[strum(serialize = "Path.exists")]
     Exists,
     /// Check if path is a file
@@ -75,12 +75,162 @@ pub enum OsFunction {
     /// Get absolute path (without resolving symlinks)
     #[strum(serialize = "Path.absolute")]
     Absolute,
+    /// List the paths matching a glob pattern relative to a directory
+    #[strum(serialize = "Path.glob")]
+    Glob,
+    /// List the paths matching a glob pattern in a directory and all its subdirectories
+    #[strum(serialize = "Path.rglob")]
+    Rglob,
+    /// Create a file if it doesn't exist
+    #[strum(serialize = "Path.touch")]
+    Touch,
+    /// Check if two paths point to the same file
+    #[strum(serialize = "Path.samefile")]
+    Samefile,
     /// Get an environment variable value
     #[strum(serialize = "os.getenv")]
     Getenv,
     /// Get the entire environment as a dictionary
     #[strum(serialize = "os.environ")]
     GetEnviron,
+    /// Get random bytes from the host's entropy source
+    #[strum(serialize = "os.urandom")]
+    Urandom,
+    /// Walk a directory tree, returning a list of `(dirpath, dirnames, filenames)` tuples
+    ///
+    /// The first argument is always a `Path`; `topdown` and `followlinks` are passed on as given.
+    #[strum(serialize = "os.walk")]
+    Walk,
+    /// Copy a file's contents to a file or into a directory, returning the destination
+    #[strum(serialize = "shutil.copy")]
+    Copy,
+    /// Recursively copy a directory, returning the destination
+    #[strum(serialize = "shutil.copytree")]
+    Copytree,
+    /// Recursively delete a directory
+    #[strum(serialize = "shutil.rmtree")]
+    Rmtree,
+    /// Open a file, returning an integer handle the other `file.*` operations refer to.
+    ///
+    /// Arguments are the `Path` and a normalized mode: one of `r`, `w`, `a` or `x`,
+    /// optionally followed by `+` and then `b` for binary files (e.g. `r`, `w+`, `ab`, `r+b`).
+    #[strum(serialize = "open")]
+    Open,
+    /// Read up to `size` characters (text) or bytes (binary) from a handle, everything if `size` is -1
+    #[strum(serialize = "file.read")]
+    FileRead,
+    /// Read one line from a handle, stopping after `size` characters or bytes unless `size` is -1
+    #[strum(serialize = "file.readline")]
+    FileReadline,
+    /// Write `str` (text) or `bytes` (binary) to a handle, returning the number written
+    #[strum(serialize = "file.write")]
+    FileWrite,
+    /// Move a handle's position by `offset` relative to `whence` (0, 1 or 2), returning the new position
+    #[strum(serialize = "file.seek")]
+    FileSeek,
+    /// Get a handle's current position
+    #[strum(serialize = "file.tell")]
+    FileTell,
+    /// Close a handle, flushing anything written to it
+    #[strum(serialize = "file.close")]
+    FileClose,
+    /// Read a line of input for `input()`, given the prompt as a `str`.
+    ///
+    /// The host returns the line as a `str` (a trailing newline is stripped) or raises
+    /// `EOFError` when there is no more input.
+    #[strum(serialize = "input")]
+    Input,
+}
+
+/// A host-side implementation of the `OsFunction`s, used to answer `OsCall`s.
+///
+/// `MemoryFs` implements this against an in-memory tree; hosts can implement it themselves
+/// to route calls to a real filesystem or any other backend, then resume with
+/// `OsCall::resume_with()`.
+pub trait OsHandler {
+    /// Performs `function` with the arguments of an `OsCall`, returning the value or
+    /// exception to resume execution with.
+    fn handle_os_call(
+        &mut self,
+        function: OsFunction,
+        args: &[MontyObject],
+        kwargs: &[(MontyObject, MontyObject)],
+    ) -> ExtFunctionResult;
+}
+
+/// A conversion the VM applies to the host's result for an `OsCall` before pushing it.
+///
+/// Functions that only need raw data from the host (e.g. `uuid.uuid4()` needs random bytes)
+/// yield an ordinary `OsCall` and register one of these with the VM, so hosts answer the
+/// same `OsFunction` whichever Python function asked for it.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub(crate) enum OsResultConversion {
+    /// Builds a version 4 `uuid.UUID` from the 16 bytes returned by `OsFunction::Urandom`.
+    Uuid4,
+    /// Builds a file object from the handle returned by `OsFunction::Open`.
+    OpenFile {
+        /// The `name` attribute of the file, the path as passed to `open()`.
+        name: String,
+        /// The `mode` attribute of the file.
+        mode: String,
+        /// The `encoding` attribute of a text file, `None` for a binary file.
+        encoding: Option<String>,
+    },
+    /// Splits the rest of a file returned by `OsFunction::FileRead` into a list of lines.
+    Readlines,
+    /// Discards the result, returning `None` (e.g. `writelines()` ignores the count written).
+    Discard,
+    /// Continues a `for` loop over a file with the line returned by `OsFunction::FileReadline`.
+    ///
+    /// An empty line ends the loop: the file is popped and execution jumps `offset` bytes
+    /// forward, as `ForIter` does for an exhausted iterator.
+    ForIterLine {
+        /// The `ForIter` jump offset, relative to the instruction after it.
+        offset: i16,
+    },
+    /// Continues a `for` loop over an `iter(callable, sentinel)` iterator with the result of
+    /// the external, OS or method call its callable made.
+    ///
+    /// A result equal to the sentinel ends the loop as `ForIterLine` does for an empty line.
+    ForIterSentinel {
+        /// The `ForIter` jump offset, relative to the instruction after it.
+        offset: i16,
+    },
+    /// Runs a call again with an iterator over the lines of the rest of a file, returned by
+    /// `OsFunction::FileRead`, in place of the file.
+    ///
+    /// The call's operands were put back on the stack when it failed to iterate the file,
+    /// see `VM::call_reading_files`.
+    CallWithFileLines {
+        /// The number of stack values the call consumes.
+        operands: usize,
+        /// The position of the file among the operands.
+        operand: usize,
+        /// The IP of the call instruction, which execution resumes at.
+        call_ip: usize,
+    },
+    /// Ignores the result of closing a file left open when the module returns, which returns
+    /// again without pushing anything (see `Opcode::ReturnValue`).
+    CloseAtExit,
+    /// Turns the paths returned by `OsFunction::Iterdir` into a list of names for `os.listdir()`.
+    Listdir,
+    /// Turns a path returned by `OsFunction::Absolute` or `OsFunction::Resolve` into a normalized `str`.
+    Normpath,
+    /// Takes one field (e.g. `st_size`) of the `stat_result` returned by `OsFunction::Stat`.
+    StatField {
+        /// The name of the field.
+        field: StaticStrings,
+    },
+    /// Turns a path returned by `OsFunction::Absolute` or `OsFunction::Resolve` into a `Path`.
+    ToPath,
+    /// Joins the `HOME` variable returned by `OsFunction::Getenv` with the rest of a `~` path,
+    /// for `Path.expanduser()` and `Path.home()`.
+    ExpandUser {
+        /// The path after the leading `~/`, empty for `~` itself.
+        rest: String,
+    },
+    /// Checks the line returned by `OsFunction::Input` is a `str` and strips its trailing newline.
+    InputLine,
 }
 
 impl TryFrom<StaticStrings> for OsFunction {
@@ -102,6 +252,9 @@ impl TryFrom<StaticStrings> for OsFunction {
             StaticStrings::Iterdir => Ok(Self::Iterdir),
             StaticStrings::Resolve => Ok(Self::Resolve),
             StaticStrings::Absolute => Ok(Self::Absolute),
+            StaticStrings::Glob => Ok(Self::Glob),
+            StaticStrings::Rglob => Ok(Self::Rglob),
+            StaticStrings::Samefile => Ok(Self::Samefile),
             // Write operations
             StaticStrings::WriteText => Ok(Self::WriteText),
             StaticStrings::WriteBytes => Ok(Self::WriteBytes),
@@ -109,6 +262,7 @@ impl TryFrom<StaticStrings> for OsFunction {
             StaticStrings::Unlink => Ok(Self::Unlink),
             StaticStrings::Rmdir => Ok(Self::Rmdir),
             StaticStrings::Rename => Ok(Self::Rename),
+            StaticStrings::Touch => Ok(Self::Touch),
             _ => Err(()),
         }
     }
@@ -0,0 +1,374 @@
+//! Building blocks shared by the `OsHandler` implementations, `MemoryFs` and `MountFs`.
+//!
+//! Both resolve sandbox paths the same way and raise exceptions in CPython's
+//! `[Errno N] message: 'path'` format. `MemoryFs` buffers files opened with `open()` in
+//! memory until they're closed, while `MountFs` reads and writes host files directly.
+
+use crate::{ExcType, MontyException, MontyObject, os::OsFunction};
+
+pub(crate) type FsResult<T> = Result<T, MontyException>;
+
+/// An `open()` mode, which the host receives normalized (e.g. `r`, `w+` or `ab`).
+///
+/// `open()` validates the mode the program passed (see `types::file::parse_mode`), so the
+/// host side only parses modes it built itself.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct OpenMode {
+    /// One of `r`, `w`, `a` or `x`.
+    pub access: u8,
+    /// Whether `+` was given, opening the file for both reading and writing.
+    pub update: bool,
+    /// Whether `b` was given.
+    pub binary: bool,
+}
+
+impl OpenMode {
+    /// Parses a normalized mode, as built by `host_mode()`.
+    pub fn parse(mode: &str) -> Self {
+        Self {
+            access: mode.bytes().next().unwrap_or(b'r'),
+            update: mode.contains('+'),
+            binary: mode.contains('b'),
+        }
+    }
+
+    /// The mode passed to the host: the access character, then `+` and `b` if given.
+    pub fn host_mode(self) -> String {
+        let mut mode = char::from(self.access).to_string();
+        if self.update {
+            mode.push('+');
+        }
+        if self.binary {
+            mode.push('b');
+        }
+        mode
+    }
+
+    pub fn readable(self) -> bool {
+        self.access == b'r' || self.update
+    }
+
+    pub fn writable(self) -> bool {
+        self.access != b'r' || self.update
+    }
+
+    /// Whether opening keeps the file's existing contents rather than creating or truncating it.
+    pub fn keeps_contents(self) -> bool {
+        matches!(self.access, b'r' | b'a')
+    }
+}
+
+/// A `MemoryFs` file opened with `OsFunction::Open`, whose contents are buffered until it's closed.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub(crate) struct OpenFile {
+    /// The path as passed to `open()`, for error messages and storing the contents on close.
+    pub name: String,
+    /// The file's contents, to be stored back on close if `writable` is set.
+    pub content: Vec<u8>,
+    pub writable: bool,
+    position: usize,
+    binary: bool,
+    append: bool,
+}
+
+impl OpenFile {
+    /// Creates an open file from its current contents, empty if the mode creates or truncates it.
+    pub fn new(name: String, content: Vec<u8>, mode: &str) -> Self {
+        let open_mode = OpenMode::parse(mode);
+        let append = open_mode.access == b'a';
+        Self {
+            name,
+            position: if append { content.len() } else { 0 },
+            content,
+            writable: open_mode.writable(),
+            binary: open_mode.binary,
+            append,
+        }
+    }
+
+    /// Returns the bytes a `file.write` call would write and the length of the contents afterwards,
+    /// so handlers can check quotas before calling `call()`.
+    pub fn pending_write<'a>(&self, args: &'a [MontyObject]) -> FsResult<(&'a [u8], usize)> {
+        let data = write_data(args)?;
+        let start = if self.append { self.content.len() } else { self.position };
+        Ok((data, self.content.len().max(start + data.len())))
+    }
+
+    /// Performs `file.read`, `file.readline`, `file.write`, `file.seek` or `file.tell`.
+    pub fn call(&mut self, function: OsFunction, args: &[MontyObject]) -> FsResult<MontyObject> {
+        match function {
+            OsFunction::FileRead | OsFunction::FileReadline => {
+                let size = int_arg(function, args, 1)?;
+                // reading past the end (after a seek) gives nothing
+                let start = self.position.min(self.content.len());
+                let rest = &self.content[start..];
+                let mut end = if function == OsFunction::FileReadline {
+                    rest.iter().position(|&b| b == b'\n').map_or(rest.len(), |i| i + 1)
+                } else {
+                    rest.len()
+                };
+                if let Ok(size) = usize::try_from(size) {
+                    end = if self.binary {
+                        end.min(size)
+                    } else {
+                        // text sizes count characters
+                        decode(&rest[..end])?.char_indices().nth(size).map_or(end, |(i, _)| i)
+                    };
+                }
+                let chunk = &rest[..end];
+                let result = if self.binary {
+                    MontyObject::Bytes(chunk.to_vec())
+                } else {
+                    MontyObject::String(decode(chunk)?.to_owned())
+                };
+                self.position = start + end;
+                Ok(result)
+            }
+            OsFunction::FileWrite => {
+                let (data, new_len) = self.pending_write(args)?;
+                if self.append {
+                    self.position = self.content.len();
+                }
+                if self.content.len() < new_len {
+                    self.content.resize(new_len, 0);
+                }
+                let end = self.position + data.len();
+                self.content[self.position..end].copy_from_slice(data);
+                self.position = end;
+                Ok(written(args))
+            }
+            OsFunction::FileSeek => {
+                let base = match int_arg(function, args, 2)? {
+                    0 => 0,
+                    1 => self.position,
+                    _ => self.content.len(),
+                };
+                let position = count_i64(base).checked_add(int_arg(function, args, 1)?);
+                match position.and_then(|position| usize::try_from(position).ok()) {
+                    Some(position) => {
+                        self.position = position;
+                        Ok(count(position))
+                    }
+                    None => Err(invalid_seek()),
+                }
+            }
+            OsFunction::FileTell => Ok(count(self.position)),
+            _ => unreachable!("not a buffered file.* function: {function}"),
+        }
+    }
+}
+
+/// Splits a path into its components, resolving `.` and `..`.
+///
+/// Relative paths are resolved against `/`, which acts as the working directory.
+pub(crate) fn components(path: &str) -> Vec<String> {
+    let mut parts: Vec<String> = Vec::new();
+    for part in path.split('/') {
+        match part {
+            "" | "." => {}
+            ".." => {
+                parts.pop();
+            }
+            _ => parts.push(part.to_owned()),
+        }
+    }
+    parts
+}
+
+/// Formats components as an absolute path.
+pub(crate) fn absolute(parts: &[String]) -> String {
+    format!("/{}", parts.join("/"))
+}
+
+/// Joins a name onto a path as given, the way `Path.__truediv__` does.
+pub(crate) fn join(path: &str, name: &str) -> String {
+    if name.is_empty() {
+        path.to_owned()
+    } else if path.is_empty() || path == "." {
+        name.to_owned()
+    } else if path.ends_with('/') {
+        format!("{path}{name}")
+    } else {
+        format!("{path}/{name}")
+    }
+}
+
+/// Matches a path's components against a glob pattern's, where `**` matches any number of components.
+pub(crate) fn glob_match(pattern: &[&str], parts: &[String]) -> bool {
+    match pattern.split_first() {
+        None => parts.is_empty(),
+        Some((&"**", rest)) => (0..=parts.len()).any(|skip| glob_match(rest, &parts[skip..])),
+        Some((first, rest)) => parts
+            .split_first()
+            .is_some_and(|(part, parts_rest)| glob_component_match(first, part) && glob_match(rest, parts_rest)),
+    }
+}
+
+/// Matches a single path component against a glob component supporting `*`, `?` and `[...]`
+/// character classes (negated with `[!...]`), as `fnmatch` does.
+pub(crate) fn glob_component_match(pattern: &str, name: &str) -> bool {
+    let pattern: Vec<char> = pattern.chars().collect();
+    let name: Vec<char> = name.chars().collect();
+    glob_chars_match(&pattern, &name)
+}
+
+fn glob_chars_match(pattern: &[char], name: &[char]) -> bool {
+    match (pattern.split_first(), name.split_first()) {
+        (None, _) => name.is_empty(),
+        (Some(('*', rest)), _) => {
+            glob_chars_match(rest, name) || (!name.is_empty() && glob_chars_match(pattern, &name[1..]))
+        }
+        (Some(_), None) => false,
+        (Some(('?', rest)), Some((_, name_rest))) => glob_chars_match(rest, name_rest),
+        (Some(('[', rest)), Some((c, name_rest))) => match glob_class_match(rest, *c) {
+            Some((matched, rest)) => matched && glob_chars_match(rest, name_rest),
+            // an unclosed `[` is matched literally
+            None => *c == '[' && glob_chars_match(rest, name_rest),
+        },
+        (Some((p, rest)), Some((n, name_rest))) => p == n && glob_chars_match(rest, name_rest),
+    }
+}
+
+/// Matches `c` against the character class following a `[`, returning whether it matched
+/// and the pattern after the closing `]`, or `None` if the class is never closed.
+fn glob_class_match(pattern: &[char], c: char) -> Option<(bool, &[char])> {
+    let negated = pattern.first() == Some(&'!');
+    let start = usize::from(negated);
+    let mut matched = false;
+    let mut i = start;
+    while i < pattern.len() {
+        // a `]` straight after the `[` (or `[!`) is a literal member of the class
+        if pattern[i] == ']' && i > start {
+            return Some((matched != negated, &pattern[i + 1..]));
+        }
+        if i + 2 < pattern.len() && pattern[i + 1] == '-' && pattern[i + 2] != ']' {
+            matched |= (pattern[i]..=pattern[i + 2]).contains(&c);
+            i += 3;
+        } else {
+            matched |= pattern[i] == c;
+            i += 1;
+        }
+    }
+    None
+}
+
+/// Decodes UTF-8 file contents, raising `UnicodeDecodeError` like CPython's `utf-8` codec.
+pub(crate) fn decode(bytes: &[u8]) -> FsResult<&str> {
+    std::str::from_utf8(bytes).map_err(|err| {
+        let position = err.valid_up_to();
+        let byte = bytes[position];
+        let reason = match err.error_len() {
+            None => "unexpected end of data",
+            Some(_) if matches!(byte, 0x80..=0xc1 | 0xf5..=0xff) => "invalid start byte",
+            Some(_) => "invalid continuation byte",
+        };
+        MontyException::new(
+            ExcType::UnicodeDecodeError,
+            Some(format!(
+                "'utf-8' codec can't decode byte 0x{byte:02x} in position {position}: {reason}"
+            )),
+        )
+    })
+}
+
+pub(crate) fn count(n: usize) -> MontyObject {
+    MontyObject::Int(count_i64(n))
+}
+
+pub(crate) fn count_i64(n: usize) -> i64 {
+    i64::try_from(n).expect("in-memory sizes fit in i64")
+}
+
+/// Builds an `OSError` subclass exception in CPython's `[Errno N] message: 'path'` format.
+pub(crate) fn os_error(exc_type: ExcType, errno: i32, message: &str, path: &str) -> MontyException {
+    MontyException::new(exc_type, Some(format!("[Errno {errno}] {message}: '{path}'")))
+}
+
+pub(crate) fn not_found(path: &str) -> MontyException {
+    os_error(ExcType::FileNotFoundError, 2, "No such file or directory", path)
+}
+
+pub(crate) fn file_exists(path: &str) -> MontyException {
+    os_error(ExcType::FileExistsError, 17, "File exists", path)
+}
+
+pub(crate) fn is_a_directory(path: &str) -> MontyException {
+    os_error(ExcType::IsADirectoryError, 21, "Is a directory", path)
+}
+
+pub(crate) fn not_a_directory(path: &str) -> MontyException {
+    os_error(ExcType::NotADirectoryError, 20, "Not a directory", path)
+}
+
+pub(crate) fn permission_denied(path: &str) -> MontyException {
+    os_error(ExcType::PermissionError, 13, "Permission denied", path)
+}
+
+/// Error for a `file.*` call with a handle that isn't open.
+pub(crate) fn bad_file_descriptor() -> MontyException {
+    MontyException::new(ExcType::OSError, Some("[Errno 9] Bad file descriptor".to_owned()))
+}
+
+/// Error for a `file.seek` call that would move before the start of the file.
+pub(crate) fn invalid_seek() -> MontyException {
+    MontyException::new(ExcType::OSError, Some("[Errno 22] Invalid argument".to_owned()))
+}
+
+/// Returns the data a `file.write` call writes: the bytes of a binary file's `bytes`, or the
+/// UTF-8 encoding of a text file's `str`.
+pub(crate) fn write_data(args: &[MontyObject]) -> FsResult<&[u8]> {
+    match args.get(1) {
+        Some(MontyObject::Bytes(bytes)) => Ok(bytes.as_slice()),
+        Some(MontyObject::String(text)) => Ok(text.as_bytes()),
+        other => Err(bad_argument(OsFunction::FileWrite, 1, other)),
+    }
+}
+
+/// The result of a `file.write` call that wrote all its data: the number of characters for a
+/// text file, or of bytes for a binary file.
+pub(crate) fn written(args: &[MontyObject]) -> MontyObject {
+    count(match &args[1] {
+        MontyObject::String(text) => text.chars().count(),
+        MontyObject::Bytes(bytes) => bytes.len(),
+        _ => 0,
+    })
+}
+
+/// Error for an argument Monty never passes, e.g. from a host constructing `OsCall`s by hand.
+pub(crate) fn bad_argument(function: OsFunction, index: usize, value: Option<&MontyObject>) -> MontyException {
+    MontyException::new(
+        ExcType::TypeError,
+        Some(format!("{function}: unexpected argument {index}: {value:?}")),
+    )
+}
+
+pub(crate) fn str_arg(function: OsFunction, args: &[MontyObject], index: usize) -> FsResult<&str> {
+    match args.get(index) {
+        Some(MontyObject::Path(s) | MontyObject::String(s)) => Ok(s),
+        other => Err(bad_argument(function, index, other)),
+    }
+}
+
+pub(crate) fn int_arg(function: OsFunction, args: &[MontyObject], index: usize) -> FsResult<i64> {
+    match args.get(index) {
+        Some(MontyObject::Int(n)) => Ok(*n),
+        other => Err(bad_argument(function, index, other)),
+    }
+}
+
+pub(crate) fn kwarg<'a>(kwargs: &'a [(MontyObject, MontyObject)], name: &str) -> Option<&'a MontyObject> {
+    kwargs
+        .iter()
+        .find(|(key, _)| matches!(key, MontyObject::String(key) if key == name))
+        .map(|(_, value)| value)
+}
+
+/// Gets a keyword argument's truthiness, `default` if it wasn't passed.
+pub(crate) fn kwarg_bool(kwargs: &[(MontyObject, MontyObject)], name: &str, default: bool) -> bool {
+    kwarg(kwargs, name).map_or(default, |value| match value {
+        MontyObject::Bool(b) => *b,
+        MontyObject::Int(n) => *n != 0,
+        MontyObject::None => false,
+        _ => true,
+    })
+}
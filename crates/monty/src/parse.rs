@@ -12,6 +12,7 @@ use ruff_text_size::{Ranged, TextRange};
 use crate::{
     StackFrame,
     args::{ArgExprs, CallArg, CallKwarg, Kwarg},
+    builtins::Builtins,
     exception_private::ExcType,
     exception_public::{CodeLoc, MontyException},
     expressions::{
@@ -19,7 +20,8 @@ use crate::{
         SequenceItem, UnpackTarget,
     },
     fstring::{ConversionFlag, FStringPart, FormatSpec},
-    intern::{InternerBuilder, StringId},
+    intern::{InternerBuilder, StaticStrings, StringId},
+    types::Type,
     value::EitherStr,
 };
 
@@ -168,6 +170,8 @@ pub struct Parser<'a> {
     /// Starts at MAX_NESTING_DEPTH and decrements on each nested level.
     /// When it reaches zero, we return a "too many nested parentheses" error.
     depth_remaining: u16,
+    /// Number of `with` statements desugared so far, used to name their hidden variables.
+    with_count: u32,
 }
 
 impl<'a> Parser<'a> {
@@ -186,6 +190,7 @@ impl<'a> Parser<'a> {
             filename_id,
             interner,
             depth_remaining: MAX_NESTING_DEPTH,
+            with_count: 0,
         }
     }
 
@@ -357,17 +362,25 @@ impl<'a> Parser<'a> {
                 let or_else = self.parse_elif_else_clauses(elif_else_clauses)?;
                 Ok(Node::If { test, body, or_else })
             }
-            Stmt::With(ast::StmtWith { is_async, range, .. }) => {
+            Stmt::With(ast::StmtWith {
+                is_async,
+                items,
+                body,
+                range,
+                ..
+            }) => {
                 if is_async {
                     Err(ParseError::not_implemented(
                         "async context managers (async with)",
                         self.convert_range(range),
                     ))
                 } else {
-                    Err(ParseError::not_implemented(
-                        "context managers (with statements)",
-                        self.convert_range(range),
-                    ))
+                    // `with A, B: body` is `with A: with B: body`
+                    let mut body = self.parse_statements(body)?;
+                    for item in items.into_iter().rev() {
+                        body = vec![self.parse_with_item(item, body)?];
+                    }
+                    Ok(body.pop().expect("with statements have at least one item"))
                 }
             }
             Stmt::Match(m) => Err(ParseError::not_implemented(
@@ -429,14 +442,23 @@ impl<'a> Parser<'a> {
                 }
                 let alias_node = &names[0];
                 let module_name = self.interner.intern(&alias_node.name);
+                // `import a.b` binds the top-level package `a`, while `import a.b as c` binds `a.b`
+                let package = match (&alias_node.asname, alias_node.name.split_once('.')) {
+                    (None, Some((package, _))) => Some(self.interner.intern(package)),
+                    _ => None,
+                };
                 // The binding name is the alias if present, otherwise the module name
                 let binding_name = alias_node
                     .asname
                     .as_ref()
-                    .map_or(module_name, |n| self.interner.intern(&n.id));
+                    .map_or(package.unwrap_or(module_name), |n| self.interner.intern(&n.id));
                 // Create an unresolved identifier (namespace slot will be set during prepare)
                 let binding = Identifier::new(binding_name, position);
-                Ok(Node::Import { module_name, binding })
+                Ok(Node::Import {
+                    module_name,
+                    package,
+                    binding,
+                })
             }
             Stmt::ImportFrom(ast::StmtImportFrom {
                 module,
@@ -527,6 +549,12 @@ impl<'a> Parser<'a> {
     /// Handles simple assignments (x = value), subscript assignments (dict[key] = value),
     /// attribute assignments (obj.attr = value), and tuple unpacking (a, b = value)
     fn parse_assignment(&mut self, lhs: AstExpr, rhs: AstExpr) -> Result<ParseNode, ParseError> {
+        let value = self.parse_expression(rhs)?;
+        self.parse_assignment_value(lhs, value)
+    }
+
+    /// Assigns an already parsed `value` to the target `lhs`, see `parse_assignment`.
+    fn parse_assignment_value(&mut self, lhs: AstExpr, value: ExprLoc) -> Result<ParseNode, ParseError> {
         match lhs {
             // Subscript assignment like dict[key] = value
             AstExpr::Subscript(ast::ExprSubscript {
@@ -534,7 +562,7 @@ impl<'a> Parser<'a> {
             }) => Ok(Node::SubscriptAssign {
                 target: self.parse_identifier(*value)?,
                 index: self.parse_expression(*slice)?,
-                value: self.parse_expression(rhs)?,
+                value,
                 target_position: self.convert_range(range),
             }),
             // Attribute assignment like obj.attr = value (supports chained like a.b.c = value)
@@ -542,7 +570,7 @@ impl<'a> Parser<'a> {
                 object: self.parse_expression(*value)?,
                 attr: EitherStr::Interned(self.interner.intern(attr.id())),
                 target_position: self.convert_range(range),
-                value: self.parse_expression(rhs)?,
+                value,
             }),
             // Tuple unpacking like a, b = value or (a, b), c = nested
             AstExpr::Tuple(ast::ExprTuple { elts, range, .. }) => {
@@ -554,7 +582,7 @@ impl<'a> Parser<'a> {
                 Ok(Node::UnpackAssign {
                     targets,
                     targets_position,
-                    object: self.parse_expression(rhs)?,
+                    object: value,
                 })
             }
             // List unpacking like [a, b] = value or [a, *rest] = value
@@ -567,17 +595,131 @@ impl<'a> Parser<'a> {
                 Ok(Node::UnpackAssign {
                     targets,
                     targets_position,
-                    object: self.parse_expression(rhs)?,
+                    object: value,
                 })
             }
             // Simple identifier assignment like x = value
             _ => Ok(Node::Assign {
                 target: self.parse_identifier(lhs)?,
-                object: self.parse_expression(rhs)?,
+                object: value,
             }),
         }
     }
 
+    /// Desugars one item of a `with` statement into a `try` statement around `body`.
+    ///
+    /// The VM has no context manager support of its own, so `with EXPR as VAR: BODY` becomes
+    /// the equivalent of:
+    ///
+    /// ```text
+    /// try:
+    ///     $with0_ok = False
+    ///     $with0_mgr = EXPR
+    ///     VAR = $with0_mgr.__enter__()
+    ///     $with0_ok = True
+    ///     BODY
+    /// except:
+    ///     if not $with0_ok:
+    ///         raise
+    ///     $with0_ok = False
+    ///     if not $with0_mgr.__exit__(type($with0_exc), $with0_exc, None):
+    ///         raise
+    /// finally:
+    ///     if $with0_ok:
+    ///         $with0_mgr.__exit__(None, None, None)
+    ///     $with0_mgr = None
+    /// ```
+    ///
+    /// where the bare `except` binds the exception to `$with0_exc`. The `$` prefix keeps the
+    /// hidden names from clashing with user variables, and `globals()`/`locals()` leave them
+    /// out. Resetting `$with0_mgr` stops the slot keeping the manager alive after the statement.
+    fn parse_with_item(&mut self, item: ast::WithItem, body: Vec<ParseNode>) -> Result<ParseNode, ParseError> {
+        let ast::WithItem {
+            context_expr,
+            optional_vars,
+            range,
+            ..
+        } = item;
+        let position = self.convert_range(range);
+        let index = self.with_count;
+        self.with_count += 1;
+        let mgr = self.interner.intern(&format!("$with{index}_mgr"));
+        let ok = self.interner.intern(&format!("$with{index}_ok"));
+        let exc = self.interner.intern(&format!("$with{index}_exc"));
+
+        let name = |id| ExprLoc::new(position, Expr::Name(Identifier::new(id, position)));
+        let literal = |literal| ExprLoc::new(position, Expr::Literal(literal));
+        let assign = |target, object| Node::Assign {
+            target: Identifier::new(target, position),
+            object,
+        };
+        let call_mgr = |method: StaticStrings, args| {
+            let expr = Expr::AttrCall {
+                object: Box::new(name(mgr)),
+                attr: EitherStr::Interned(method.into()),
+                args: Box::new(args),
+            };
+            ExprLoc::new(position, expr)
+        };
+        let raise_unless = |test| Node::If {
+            test: ExprLoc::new(position, Expr::Not(Box::new(test))),
+            body: vec![Node::Raise(None)],
+            or_else: vec![],
+        };
+
+        let context = self.parse_expression(context_expr)?;
+        let enter = call_mgr(StaticStrings::DunderEnter, ArgExprs::Empty);
+        let enter = match optional_vars {
+            Some(target) => self.parse_assignment_value(*target, enter)?,
+            None => Node::Expr(enter),
+        };
+        let mut try_body = vec![
+            assign(ok, literal(Literal::Bool(false))),
+            assign(mgr, context),
+            enter,
+            assign(ok, literal(Literal::Bool(true))),
+        ];
+        try_body.extend(body);
+
+        let exc_type = Expr::Call {
+            callable: Callable::Builtin(Builtins::Type(Type::Type)),
+            args: Box::new(ArgExprs::One(name(exc))),
+        };
+        let exit_args = ArgExprs::Args(vec![
+            ExprLoc::new(position, exc_type),
+            name(exc),
+            literal(Literal::None),
+        ]);
+        let handler = ExceptHandler {
+            exc_type: None,
+            name: Some(Identifier::new(exc, position)),
+            body: vec![
+                raise_unless(name(ok)),
+                assign(ok, literal(Literal::Bool(false))),
+                raise_unless(call_mgr(StaticStrings::DunderExit, exit_args)),
+            ],
+        };
+        let exit_args = ArgExprs::Args(vec![
+            literal(Literal::None),
+            literal(Literal::None),
+            literal(Literal::None),
+        ]);
+        let finally = vec![
+            Node::If {
+                test: name(ok),
+                body: vec![Node::Expr(call_mgr(StaticStrings::DunderExit, exit_args))],
+                or_else: vec![],
+            },
+            assign(mgr, literal(Literal::None)),
+        ];
+        Ok(Node::Try(Try {
+            body: try_body,
+            handlers: vec![handler],
+            or_else: vec![],
+            finally,
+        }))
+    }
+
     /// Parses an expression from the ruff AST into Monty's ExprLoc representation.
     ///
     /// Includes depth tracking to prevent stack overflow from deeply nested structures.
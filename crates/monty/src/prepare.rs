@@ -32,6 +32,11 @@ pub struct PrepareResult {
     /// - ref-count tests for looking up variables by name
     /// - REPL incremental compilation to preserve stable global slot IDs across snippets
     pub name_map: AHashMap<String, NamespaceId>,
+    /// The variable each module namespace slot holds, indexed by slot, for `globals()`.
+    pub global_names: Vec<Option<StringId>>,
+    /// The module dunders read as literals rather than stored as globals, with their values,
+    /// so `globals()` can still include them.
+    pub module_dunders: Vec<(StringId, Literal)>,
     /// The prepared AST nodes with all names resolved to namespace indices.
     /// Function definitions are inline as `PreparedFunctionDef` variants.
     pub nodes: Vec<PreparedNode>,
@@ -39,13 +44,110 @@ pub struct PrepareResult {
     pub interner: InternerBuilder,
 }
 
+/// Values of the module-level dunder names `__name__`, `__file__` and `__doc__`.
+///
+/// Monty has no module object or `__builtins__` to hold these, so a read of one of them
+/// that isn't bound by the code itself is resolved straight to its value during prepare.
+/// `None` means the name is read like any other global: either it is undefined, raising
+/// `NameError`, or the code assigns it somewhere, see `ModuleDunders::seed_assigned`.
+#[derive(Debug, Clone, Copy)]
+struct ModuleDunders {
+    /// `__name__`
+    name: Option<Literal>,
+    /// `__file__`, undefined in the REPL as in CPython's interactive interpreter
+    file: Option<Literal>,
+    /// `__doc__`, the module docstring or `None`
+    doc: Option<Literal>,
+}
+
+impl ModuleDunders {
+    /// Returns the value of `name` if it is one of the module dunders.
+    fn get(self, name: &str) -> Option<Literal> {
+        match name {
+            "__name__" => self.name,
+            "__file__" => self.file,
+            "__doc__" => self.doc,
+            _ => None,
+        }
+    }
+
+    /// Returns the dunders still resolved to literals, in the order CPython's `globals()` lists them.
+    fn literals(self, interner: &mut InternerBuilder) -> Vec<(StringId, Literal)> {
+        [("__name__", self.name), ("__doc__", self.doc), ("__file__", self.file)]
+            .into_iter()
+            .filter_map(|(name, value)| Some((interner.intern(name), value?)))
+            .collect()
+    }
+
+    /// Turns the dunders that the code binds itself into ordinary globals.
+    ///
+    /// A literal would be wrong for such a name: a function defined before the assignment
+    /// must still see the new value. So any dunder assigned in `nodes`, at module level or
+    /// through a `global` declaration, stops being folded, and gets an assignment of its
+    /// default value prepended to `nodes` so reads before the code's own assignment work.
+    ///
+    /// Names in `existing_names` were bound by earlier REPL snippets and are already globals.
+    fn seed_assigned(
+        &mut self,
+        nodes: &mut Vec<ParseNode>,
+        existing_names: &AHashMap<String, NamespaceId>,
+        interner: &InternerBuilder,
+    ) {
+        let mut assigned = AHashSet::new();
+        collect_module_assigned_names(nodes, &mut assigned, interner);
+        for (name, value) in [
+            ("__name__", &mut self.name),
+            ("__file__", &mut self.file),
+            ("__doc__", &mut self.doc),
+        ] {
+            if existing_names.contains_key(name) {
+                *value = None;
+            } else if assigned.contains(name)
+                && let Some(literal) = value.take()
+                && let Some(name_id) = interner.lookup(name)
+            {
+                let position = CodeRange::default();
+                nodes.insert(
+                    0,
+                    Node::Assign {
+                        target: Identifier::new(name_id, position),
+                        object: ExprLoc::new(position, Expr::Literal(literal)),
+                    },
+                );
+            }
+        }
+    }
+}
+
 /// Prepares parsed nodes for compilation by resolving names and building the initial namespace.
 ///
 /// The namespace will be converted to runtime Objects when execution begins and the heap is available.
 /// At module level, the local namespace IS the global namespace.
-pub(crate) fn prepare(parse_result: ParseResult, input_names: Vec<String>) -> Result<PrepareResult, ParseError> {
-    let ParseResult { nodes, interner } = parse_result;
-    let mut p = Prepare::new_module(input_names, &interner);
+///
+/// The code runs as `__main__`, with `__file__` set to `script_name` and `__doc__` to its docstring.
+pub(crate) fn prepare(
+    parse_result: ParseResult,
+    input_names: Vec<String>,
+    script_name: &str,
+) -> Result<PrepareResult, ParseError> {
+    let ParseResult {
+        mut nodes,
+        mut interner,
+    } = parse_result;
+    let doc = match nodes.first() {
+        Some(Node::Expr(ExprLoc {
+            expr: Expr::Literal(Literal::Str(doc)),
+            ..
+        })) => Some(*doc),
+        _ => None,
+    };
+    let mut dunders = ModuleDunders {
+        name: Some(Literal::Str(interner.intern("__main__"))),
+        file: Some(Literal::Str(interner.intern(script_name))),
+        doc: Some(doc.map_or(Literal::None, Literal::Str)),
+    };
+    dunders.seed_assigned(&mut nodes, &AHashMap::new(), &interner);
+    let mut p = Prepare::new_module(input_names, dunders, &interner);
     let mut prepared_nodes = p.prepare_nodes(nodes)?;
 
     // In the root frame, the last expression is implicitly returned
@@ -59,9 +161,16 @@ pub(crate) fn prepare(parse_result: ParseResult, input_names: Vec<String>) -> Re
         prepared_nodes.push(Node::Return(new_expr_loc));
     }
 
+    let Prepare {
+        namespace_size,
+        name_map,
+        ..
+    } = p;
     Ok(PrepareResult {
-        namespace_size: p.namespace_size,
-        name_map: p.name_map,
+        global_names: global_names(&name_map, namespace_size, &mut interner),
+        module_dunders: dunders.literals(&mut interner),
+        namespace_size,
+        name_map,
         nodes: prepared_nodes,
         interner,
     })
@@ -71,12 +180,25 @@ pub(crate) fn prepare(parse_result: ParseResult, input_names: Vec<String>) -> Re
 ///
 /// Existing bindings keep their original namespace slots; any new names are appended with new slots.
 /// This ensures snippets can be compiled independently while sharing one persistent global namespace.
+///
+/// As in CPython's interactive interpreter, the snippet has no `__file__` and `__doc__` is `None`;
+/// `__name__` is `module_name`.
 pub(crate) fn prepare_with_existing_names(
     parse_result: ParseResult,
     existing_name_map: AHashMap<String, NamespaceId>,
+    module_name: &str,
 ) -> Result<PrepareResult, ParseError> {
-    let ParseResult { nodes, interner } = parse_result;
-    let mut p = Prepare::new_module_with_name_map(existing_name_map, &interner);
+    let ParseResult {
+        mut nodes,
+        mut interner,
+    } = parse_result;
+    let mut dunders = ModuleDunders {
+        name: Some(Literal::Str(interner.intern(module_name))),
+        file: None,
+        doc: Some(Literal::None),
+    };
+    dunders.seed_assigned(&mut nodes, &existing_name_map, &interner);
+    let mut p = Prepare::new_module_with_name_map(existing_name_map, dunders, &interner);
     let mut prepared_nodes = p.prepare_nodes(nodes)?;
 
     // In the root frame, the last expression is implicitly returned to match REPL behavior.
@@ -88,14 +210,36 @@ pub(crate) fn prepare_with_existing_names(
         prepared_nodes.push(Node::Return(new_expr_loc));
     }
 
+    let Prepare {
+        namespace_size,
+        name_map,
+        ..
+    } = p;
     Ok(PrepareResult {
-        namespace_size: p.namespace_size,
-        name_map: p.name_map,
+        global_names: global_names(&name_map, namespace_size, &mut interner),
+        module_dunders: dunders.literals(&mut interner),
+        namespace_size,
+        name_map,
         nodes: prepared_nodes,
         interner,
     })
 }
 
+/// Interns the name of each module namespace slot, returning them indexed by slot.
+///
+/// Input names may not appear in the code, so unlike function locals they may need interning.
+fn global_names(
+    name_map: &AHashMap<String, NamespaceId>,
+    namespace_size: usize,
+    interner: &mut InternerBuilder,
+) -> Vec<Option<StringId>> {
+    let mut names = vec![None; namespace_size];
+    for (name, id) in name_map {
+        names[id.index()] = Some(interner.intern(name));
+    }
+    names
+}
+
 /// State machine for the preparation phase that transforms parsed AST nodes into a prepared form.
 ///
 /// This struct maintains the mapping between variable names and their namespace indices,
@@ -151,6 +295,8 @@ struct Prepare<'i> {
     /// classifying subsequent references as `Local` (like parameters) when the name
     /// appears in `name_map` from a previous `get_id` call.
     unassigned_ref_names: AHashSet<String>,
+    /// Values of `__name__`, `__file__` and `__doc__` for reads that the code doesn't bind itself.
+    module_dunders: ModuleDunders,
 }
 
 impl<'i> Prepare<'i> {
@@ -161,8 +307,9 @@ impl<'i> Prepare<'i> {
     ///
     /// # Arguments
     /// * `input_names` - Names that should be pre-registered in the namespace (e.g., input variables)
+    /// * `module_dunders` - Values of the module dunder names
     /// * `interner` - Reference to the string interner for looking up names
-    fn new_module(input_names: Vec<String>, interner: &'i InternerBuilder) -> Self {
+    fn new_module(input_names: Vec<String>, module_dunders: ModuleDunders, interner: &'i InternerBuilder) -> Self {
         let mut name_map = AHashMap::with_capacity(input_names.len());
         for (index, name) in input_names.into_iter().enumerate() {
             name_map.insert(name, NamespaceId::new(index));
@@ -181,13 +328,18 @@ impl<'i> Prepare<'i> {
             free_var_map: AHashMap::new(),
             cell_var_map: AHashMap::new(),
             unassigned_ref_names: AHashSet::new(),
+            module_dunders,
         }
     }
 
     /// Creates a module-scope Prepare instance from an existing global name map.
     ///
     /// Used by incremental REPL compilation to keep stable slot assignments across snippets.
-    fn new_module_with_name_map(name_map: AHashMap<String, NamespaceId>, interner: &'i InternerBuilder) -> Self {
+    fn new_module_with_name_map(
+        name_map: AHashMap<String, NamespaceId>,
+        module_dunders: ModuleDunders,
+        interner: &'i InternerBuilder,
+    ) -> Self {
         let namespace_size = name_map
             .values()
             .map(|id| id.index())
@@ -207,6 +359,7 @@ impl<'i> Prepare<'i> {
             free_var_map: AHashMap::new(),
             cell_var_map: AHashMap::new(),
             unassigned_ref_names: AHashSet::new(),
+            module_dunders,
         }
     }
 
@@ -225,6 +378,7 @@ impl<'i> Prepare<'i> {
     /// * `global_name_map` - Copy of the module-level name map for global resolution
     /// * `enclosing_locals` - Names that exist as locals in the enclosing function (for nonlocal resolution)
     /// * `cell_var_names` - Names that are captured by nested functions (must be stored in cells)
+    /// * `module_dunders` - Values of the module dunder names
     /// * `interner` - Reference to the string interner for looking up names
     #[expect(clippy::too_many_arguments)]
     fn new_function(
@@ -237,6 +391,7 @@ impl<'i> Prepare<'i> {
         global_name_map: AHashMap<String, NamespaceId>,
         enclosing_locals: Option<AHashSet<String>>,
         cell_var_names: AHashSet<String>,
+        module_dunders: ModuleDunders,
         interner: &'i InternerBuilder,
     ) -> Self {
         let mut name_map = AHashMap::with_capacity(capacity);
@@ -294,6 +449,7 @@ impl<'i> Prepare<'i> {
             free_var_map,
             cell_var_map,
             unassigned_ref_names: AHashSet::new(),
+            module_dunders,
         }
     }
 
@@ -563,11 +719,16 @@ impl<'i> Prepare<'i> {
                         finally,
                     }));
                 }
-                Node::Import { module_name, binding } => {
+                Node::Import {
+                    module_name,
+                    package,
+                    binding,
+                } => {
                     // Resolve the binding identifier to get the namespace slot
                     let (resolved_binding, _) = self.get_id(binding);
                     new_nodes.push(Node::Import {
                         module_name,
+                        package,
                         binding: resolved_binding,
                     });
                 }
@@ -637,7 +798,7 @@ impl<'i> Prepare<'i> {
         let expr = match expr {
             Expr::Literal(object) => Expr::Literal(object),
             Expr::Builtin(callable) => Expr::Builtin(callable),
-            Expr::Name(name) => self.resolve_name_or_builtin(name),
+            Expr::Name(name) => self.resolve_name(name),
             Expr::Op { left, op, right } => Expr::Op {
                 left: Box::new(self.prepare_expression(*left)?),
                 op,
@@ -823,6 +984,20 @@ impl<'i> Prepare<'i> {
         Ok(ExprLoc { position, expr })
     }
 
+    /// Resolves a name read as a value, which may also be one of the module dunders.
+    ///
+    /// A `__name__`, `__file__` or `__doc__` that the code doesn't bind itself becomes a literal
+    /// of its value, see `ModuleDunders`; any other name goes through `resolve_name_or_builtin`.
+    fn resolve_name(&mut self, name: Identifier) -> Expr {
+        let name_str = self.interner.get_str(name.name_id);
+        if self.is_unbound(name_str)
+            && let Some(literal) = self.module_dunders.get(name_str)
+        {
+            return Expr::Literal(literal);
+        }
+        self.resolve_name_or_builtin(name)
+    }
+
     /// Resolves a name to either `Expr::Builtin` or `Expr::Name` with scope-aware builtin detection.
     ///
     /// Python's name resolution follows LEGB order (Local, Enclosing, Global, Builtin).
@@ -834,7 +1009,29 @@ impl<'i> Prepare<'i> {
     /// `global_name_map` for nested functions, causing incorrect resolution.
     fn resolve_name_or_builtin(&mut self, name: Identifier) -> Expr {
         let name_str = self.interner.get_str(name.name_id);
+        if self.is_unbound(name_str)
+            && let Ok(builtin) = name_str.parse::<Builtins>()
+        {
+            return Expr::Builtin(builtin);
+        }
+
+        Expr::Name(self.get_id(name).0)
+    }
+
+    /// Returns the variable each slot of this function's namespace holds, for `locals()`.
+    ///
+    /// Comprehension variables get slots of their own but no lasting map entry, so stay unnamed.
+    fn local_names(&self) -> Vec<Option<StringId>> {
+        let mut names = vec![None; self.namespace_size];
+        for (name, id) in self.name_map.iter().chain(&self.cell_var_map).chain(&self.free_var_map) {
+            names[id.index()] = self.interner.lookup(name);
+        }
+        names
+    }
 
+    /// Whether a read of `name_str` here finds no binding in any scope the code defines,
+    /// so it falls through to the builtins (or the module dunders).
+    fn is_unbound(&self, name_str: &str) -> bool {
         // Check if the name is assigned in the current scope. If so, it shadows
         // any builtin with the same name.
         let is_locally_assigned = if self.is_module_scope {
@@ -845,25 +1042,21 @@ impl<'i> Prepare<'i> {
             // the name local for the entire function
             self.assigned_names.contains(name_str)
         };
-
-        if !is_locally_assigned {
-            // In function scope, also check if the name is bound by other mechanisms
-            // (global declaration, parameter, closure capture, enclosing/global scope).
-            // Only fall back to builtins if the name is truly unresolved.
-            let is_otherwise_bound = !self.is_module_scope
-                && (self.global_names.contains(name_str)
-                    || self.free_var_map.contains_key(name_str)
-                    || self.cell_var_map.contains_key(name_str)
-                    || self.name_map.contains_key(name_str)
-                    || self.enclosing_locals.as_ref().is_some_and(|l| l.contains(name_str))
-                    || self.global_name_map.as_ref().is_some_and(|m| m.contains_key(name_str)));
-
-            if !is_otherwise_bound && let Ok(builtin) = name_str.parse::<Builtins>() {
-                return Expr::Builtin(builtin);
-            }
-        }
-
-        Expr::Name(self.get_id(name).0)
+        if is_locally_assigned {
+            return false;
+        }
+
+        // In function scope, also check if the name is bound by other mechanisms
+        // (global declaration, parameter, closure capture, enclosing/global scope).
+        // Only fall back to builtins if the name is truly unresolved.
+        let is_otherwise_bound = !self.is_module_scope
+            && (self.global_names.contains(name_str)
+                || self.free_var_map.contains_key(name_str)
+                || self.cell_var_map.contains_key(name_str)
+                || self.name_map.contains_key(name_str)
+                || self.enclosing_locals.as_ref().is_some_and(|l| l.contains(name_str))
+                || self.global_name_map.as_ref().is_some_and(|m| m.contains_key(name_str)));
+        !is_otherwise_bound
     }
 
     /// Prepares a `SequenceItem` by recursively preparing its inner expression.
@@ -1238,6 +1431,7 @@ impl<'i> Prepare<'i> {
             global_name_map,
             Some(enclosing_locals),
             scope_info.cell_var_names,
+            self.module_dunders,
             self.interner,
         );
 
@@ -1291,6 +1485,7 @@ impl<'i> Prepare<'i> {
         // Slots are implicitly params.len()..params.len()+cell_var_count in the namespace layout
         let cell_var_count = inner_prepare.cell_var_map.len();
         let namespace_size = inner_prepare.namespace_size;
+        let local_names = inner_prepare.local_names();
 
         // Build cell_param_indices: maps cell indices to parameter indices for captured parameters.
         // When a parameter is captured by a nested function, we need to copy its value into the cell.
@@ -1369,6 +1564,7 @@ impl<'i> Prepare<'i> {
             signature,
             body: prepared_body,
             namespace_size,
+            local_names,
             free_var_enclosing_slots,
             cell_var_count,
             cell_param_indices,
@@ -1452,6 +1648,7 @@ impl<'i> Prepare<'i> {
             global_name_map,
             Some(enclosing_locals),
             scope_info.cell_var_names,
+            self.module_dunders,
             self.interner,
         );
 
@@ -1494,6 +1691,7 @@ impl<'i> Prepare<'i> {
         // Build cell_param_indices
         let cell_var_count = inner_prepare.cell_var_map.len();
         let namespace_size = inner_prepare.namespace_size;
+        let local_names = inner_prepare.local_names();
 
         let cell_param_indices: Vec<Option<usize>> = if cell_var_count == 0 {
             Vec::new()
@@ -1566,6 +1764,7 @@ impl<'i> Prepare<'i> {
             signature,
             body: prepared_body,
             namespace_size,
+            local_names,
             free_var_enclosing_slots,
             cell_var_count,
             cell_param_indices,
@@ -2077,6 +2276,54 @@ fn collect_scope_info_from_node(
     }
 }
 
+/// Collects the global names `nodes` may bind when run as a module.
+///
+/// These are the names assigned at module level plus those declared `global` in any
+/// function, however deeply nested.
+fn collect_module_assigned_names(nodes: &[ParseNode], assigned: &mut AHashSet<String>, interner: &InternerBuilder) {
+    let mut global_names = AHashSet::new();
+    let mut nonlocal_names = AHashSet::new();
+    for node in nodes {
+        collect_scope_info_from_node(node, &mut global_names, &mut nonlocal_names, assigned, interner);
+        collect_global_declarations_from_node(node, assigned, interner);
+    }
+}
+
+/// Helper to collect the names declared `global` in the functions a node defines.
+fn collect_global_declarations_from_node(
+    node: &ParseNode,
+    global_names: &mut AHashSet<String>,
+    interner: &InternerBuilder,
+) {
+    let blocks: Vec<&[ParseNode]> = match node {
+        Node::FunctionDef(RawFunctionDef { body, .. }) => {
+            let mut nonlocal_names = AHashSet::new();
+            let mut assigned_names = AHashSet::new();
+            for n in body {
+                collect_scope_info_from_node(n, global_names, &mut nonlocal_names, &mut assigned_names, interner);
+            }
+            vec![body.as_slice()]
+        }
+        Node::For { body, or_else, .. } | Node::While { body, or_else, .. } | Node::If { body, or_else, .. } => {
+            vec![body.as_slice(), or_else.as_slice()]
+        }
+        Node::Try(Try {
+            body,
+            handlers,
+            or_else,
+            finally,
+        }) => {
+            let mut blocks = vec![body.as_slice(), or_else.as_slice(), finally.as_slice()];
+            blocks.extend(handlers.iter().map(|handler| handler.body.as_slice()));
+            blocks
+        }
+        _ => return,
+    };
+    for n in blocks.into_iter().flatten() {
+        collect_global_declarations_from_node(n, global_names, interner);
+    }
+}
+
 /// Collects names assigned by walrus operators (`:=`) within an expression.
 ///
 /// Per PEP 572, walrus operator targets are assignments in the enclosing scope.
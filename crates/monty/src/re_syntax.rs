@@ -0,0 +1,1349 @@
+//! Translation of Python regular expression syntax into `fancy-regex` syntax.
+//!
+//! Python's `re` dialect and `fancy-regex` agree on most constructs, but differ in enough
+//! places that passing Python patterns straight through gives wrong results or confusing
+//! errors. This module parses Python patterns with a port of CPython's `re._parser` so that
+//! invalid patterns are rejected with CPython's messages and positions
+//! (`re.error: missing ), unterminated subpattern at position 0`), and emits an equivalent
+//! `fancy-regex` pattern.
+//!
+//! Translations performed:
+//!
+//! - `re.VERBOSE` / `(?x)`: whitespace and `#` comments are stripped here
+//! - inline flags: global `(?imsx)` groups are lifted into the returned flags, scoped
+//!   `(?i-s:...)` groups are re-emitted without the flags `fancy-regex` doesn't know
+//! - `(?P<name>...)`: emitted as a plain capture group, names are tracked here
+//! - `(?P=name)` and `\1`: emitted as numeric backreferences
+//! - `(?#...)` comments are dropped
+//! - `\Z` → `\z`, and `$` outside `MULTILINE` → `(?=\n?\z)` (Python's `$` also matches
+//!   before a trailing newline)
+//! - octal, `\x`, `\u` and `\U` escapes become literal characters
+//! - possessive quantifiers (`a*+`) become atomic groups (`(?>a*)`)
+//! - `{,n}` → `{0,n}`, and a `{` which doesn't start a valid quantifier becomes a literal
+//!
+//! Replacement templates (`\1`, `\g<name>`, escapes) are parsed by [`parse_template`].
+
+use std::fmt;
+
+use crate::{
+    exception_private::{ExcType, RunError, SimpleException},
+    modules::re::{ASCII, DOTALL, IGNORECASE, MULTILINE, UNICODE, VERBOSE},
+    types::str::string_repr_fmt,
+};
+
+/// Python regex flag: locale-dependent matching. Only valid for bytes patterns.
+const LOCALE: u16 = 4;
+
+/// Flags which select the character model — at most one may be set.
+const TYPE_FLAGS: u16 = ASCII | LOCALE | UNICODE;
+
+/// Characters with special meaning in a Python pattern (outside character classes).
+const SPECIAL_CHARS: &str = ".\\[{()*+?^$|";
+
+/// Repeat counts must be below this value (CPython's `MAXREPEAT`).
+const MAX_REPEAT: usize = 4_294_967_295;
+
+/// Characters skipped in verbose mode.
+const WHITESPACE: &str = " \t\n\r\x0b\x0c";
+
+/// Error produced while parsing a pattern or replacement template.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum ReSyntaxError {
+    /// Invalid syntax, raised as `re.error` with the character position in the source.
+    ///
+    /// `line_col` is set for sources spanning several lines, where CPython also reports
+    /// the line and column.
+    Pattern {
+        msg: String,
+        pos: usize,
+        line_col: Option<(usize, usize)>,
+    },
+    /// Invalid combination of flags, raised as `ValueError`.
+    Flags(&'static str),
+    /// A template referenced a group name the pattern doesn't define, raised as `IndexError`.
+    UnknownGroupName(String),
+}
+
+impl fmt::Display for ReSyntaxError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Pattern { msg, pos, line_col } => {
+                write!(f, "{msg} at position {pos}")?;
+                if let Some((line, col)) = line_col {
+                    write!(f, " (line {line}, column {col})")?;
+                }
+                Ok(())
+            }
+            Self::Flags(msg) => f.write_str(msg),
+            Self::UnknownGroupName(name) => write!(f, "unknown group name {}", py_repr(name)),
+        }
+    }
+}
+
+impl From<ReSyntaxError> for RunError {
+    fn from(err: ReSyntaxError) -> Self {
+        let exc_type = match err {
+            ReSyntaxError::Pattern { .. } => ExcType::RePatternError,
+            ReSyntaxError::Flags(_) => ExcType::ValueError,
+            ReSyntaxError::UnknownGroupName(_) => ExcType::IndexError,
+        };
+        SimpleException::new_msg(exc_type, err.to_string()).into()
+    }
+}
+
+/// A Python pattern translated into `fancy-regex` syntax.
+#[derive(Debug)]
+pub(crate) struct ParsedPattern {
+    /// Inline flag prefix for the effective flags, e.g. `(?im)` (empty when none apply).
+    pub prefix: String,
+    /// The translated pattern body, without flags.
+    pub body: String,
+    /// The flags passed to `compile()` combined with global inline flags.
+    pub flags: u16,
+    /// Number of capture groups.
+    pub group_count: usize,
+    /// Named groups as `(name, group number)`, in definition order.
+    pub group_names: Vec<(String, usize)>,
+    /// Innermost enclosing capture group of each group (index 0 = group 1).
+    pub group_parents: Vec<Option<usize>>,
+    /// `(byte offset in body, character position in source)` at the start of each token.
+    offsets: Vec<(usize, usize)>,
+}
+
+impl ParsedPattern {
+    /// Maps a byte offset in the translated body back to a character position in the
+    /// Python source, for reporting errors raised by the regex engine.
+    pub fn source_position(&self, body_offset: usize) -> usize {
+        let idx = self.offsets.partition_point(|&(out, _)| out <= body_offset);
+        idx.checked_sub(1).map_or(0, |i| self.offsets[i].1)
+    }
+}
+
+impl ReSyntaxError {
+    /// Adds the line and column of the error position when `source` spans several lines.
+    fn with_location(mut self, source: &str) -> Self {
+        if let Self::Pattern { pos, line_col, .. } = &mut self
+            && source.contains('\n')
+        {
+            let before: Vec<char> = source.chars().take(*pos).collect();
+            let line = before.iter().filter(|&&c| c == '\n').count() + 1;
+            let col = before.iter().rev().take_while(|&&c| c != '\n').count() + 1;
+            *line_col = Some((line, col));
+        }
+        self
+    }
+}
+
+/// Parses a Python regular expression and translates it into `fancy-regex` syntax.
+///
+/// `flags` are the flags passed to `re.compile()`; global inline flags found in the
+/// pattern are added to them.
+pub(crate) fn parse_pattern(pattern: &str, flags: u16) -> Result<ParsedPattern, ReSyntaxError> {
+    translate_pattern(pattern, flags).map_err(|err| err.with_location(pattern))
+}
+
+/// Implementation of [`parse_pattern`], without line and column in errors.
+fn translate_pattern(pattern: &str, flags: u16) -> Result<ParsedPattern, ReSyntaxError> {
+    let mut parser = Parser {
+        source: Tokenizer::new(pattern)?,
+        out: String::with_capacity(pattern.len()),
+        offsets: Vec::new(),
+        flags,
+        groups: Vec::new(),
+        open_groups: Vec::new(),
+        group_ref_pos: Vec::new(),
+    };
+    parser.parse_sub(flags & VERBOSE != 0, 0, flags & MULTILINE != 0)?;
+
+    if parser.flags & LOCALE != 0 {
+        return Err(ReSyntaxError::Flags("cannot use LOCALE flag with a str pattern"));
+    }
+    if parser.flags & ASCII != 0 && parser.flags & UNICODE != 0 {
+        return Err(ReSyntaxError::Flags("ASCII and UNICODE flags are incompatible"));
+    }
+    if parser.source.next.is_some() {
+        return Err(parser.source.error("unbalanced parenthesis", 0));
+    }
+    for &(group, pos) in &parser.group_ref_pos {
+        if group > parser.groups.len() {
+            return Err(ReSyntaxError::Pattern {
+                msg: format!("invalid group reference {group}"),
+                pos,
+                line_col: None,
+            });
+        }
+    }
+
+    let mut prefix = String::new();
+    for (flag, c) in [(IGNORECASE, 'i'), (MULTILINE, 'm'), (DOTALL, 's')] {
+        if parser.flags & flag != 0 {
+            prefix.push(c);
+        }
+    }
+    if !prefix.is_empty() {
+        prefix = format!("(?{prefix})");
+    }
+
+    let mut group_names = Vec::new();
+    for (idx, group) in parser.groups.iter().enumerate() {
+        if let Some(name) = &group.name {
+            group_names.push((name.clone(), idx + 1));
+        }
+    }
+    Ok(ParsedPattern {
+        prefix,
+        body: parser.out,
+        flags: parser.flags,
+        group_count: parser.groups.len(),
+        group_names,
+        group_parents: parser.groups.iter().map(|g| g.parent).collect(),
+        offsets: parser.offsets,
+    })
+}
+
+/// One piece of a parsed replacement template.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum TemplatePart {
+    /// Literal text, with escapes already processed.
+    Literal(String),
+    /// The text matched by a group (empty if the group didn't participate).
+    Group(usize),
+}
+
+/// Parses a replacement template as used by `re.sub()` and `Match.expand()`.
+///
+/// Supports `\1`..`\99`, `\g<N>`, `\g<name>`, octal escapes and the standard character
+/// escapes, with CPython's error messages for invalid references and escapes.
+pub(crate) fn parse_template(
+    template: &str,
+    group_count: usize,
+    group_names: &[(String, usize)],
+) -> Result<Vec<TemplatePart>, ReSyntaxError> {
+    translate_template(template, group_count, group_names).map_err(|err| err.with_location(template))
+}
+
+/// Implementation of [`parse_template`], without line and column in errors.
+fn translate_template(
+    template: &str,
+    group_count: usize,
+    group_names: &[(String, usize)],
+) -> Result<Vec<TemplatePart>, ReSyntaxError> {
+    let mut s = Tokenizer::new(template)?;
+    let mut result = Vec::new();
+    let mut literal = String::new();
+
+    let mut add_group = |s: &Tokenizer, literal: &mut String, index: usize, offset: usize| {
+        if index > group_count {
+            return Err(s.error(format!("invalid group reference {index}"), offset));
+        }
+        if !literal.is_empty() {
+            result.push(TemplatePart::Literal(std::mem::take(literal)));
+        }
+        result.push(TemplatePart::Group(index));
+        Ok(())
+    };
+
+    while let Some(this) = s.get()? {
+        let Token::Escape(c) = this else {
+            literal.push(this.first_char());
+            continue;
+        };
+        match c {
+            'g' => {
+                if !s.match_char('<')? {
+                    return Err(s.error("missing <", 0));
+                }
+                let name = s.getuntil('>', "group name")?;
+                let index = if is_ascii_decimal(&name) {
+                    name.parse::<usize>()
+                        .map_err(|_| s.error(format!("invalid group reference {name}"), name.chars().count() + 1))?
+                } else {
+                    s.check_group_name(&name, 1)?;
+                    group_names
+                        .iter()
+                        .find(|(n, _)| *n == name)
+                        .map(|&(_, idx)| idx)
+                        .ok_or(ReSyntaxError::UnknownGroupName(name.clone()))?
+                };
+                add_group(&s, &mut literal, index, name.chars().count() + 1)?;
+            }
+            '0' => {
+                let mut digits = String::from('0');
+                for _ in 0..2 {
+                    match s.next_char() {
+                        Some(d @ '0'..='7') => {
+                            digits.push(d);
+                            s.get()?;
+                        }
+                        _ => break,
+                    }
+                }
+                let value = u32::from_str_radix(&digits, 8).expect("octal digits") & 0xff;
+                literal.push(char::from_u32(value).expect("value below 0x100 is a valid char"));
+            }
+            '1'..='9' => {
+                let mut escape = String::from(c);
+                let mut octal = false;
+                if let Some(d) = s.next_char().filter(char::is_ascii_digit) {
+                    s.get()?;
+                    escape.push(d);
+                    if let Some(d3 @ '0'..='7') = s.next_char()
+                        && is_octal_digit(c)
+                        && is_octal_digit(d)
+                    {
+                        s.get()?;
+                        escape.push(d3);
+                        octal = true;
+                        let value = u32::from_str_radix(&escape, 8).expect("octal digits");
+                        if value > 0o377 {
+                            return Err(s.error(
+                                format!("octal escape value \\{escape} outside of range 0-0o377"),
+                                escape.len() + 1,
+                            ));
+                        }
+                        literal.push(char::from_u32(value).expect("value below 0x100 is a valid char"));
+                    }
+                }
+                if !octal {
+                    let index = escape.parse().expect("one or two decimal digits");
+                    add_group(&s, &mut literal, index, escape.len())?;
+                }
+            }
+            _ => {
+                if let Some(ch) = simple_escape(c) {
+                    literal.push(ch);
+                } else if c == 'b' {
+                    literal.push('\x08');
+                } else if c.is_ascii_alphabetic() {
+                    return Err(s.error(format!("bad escape \\{c}"), 2));
+                } else {
+                    literal.push('\\');
+                    literal.push(c);
+                }
+            }
+        }
+    }
+    if !literal.is_empty() {
+        result.push(TemplatePart::Literal(literal));
+    }
+    Ok(result)
+}
+
+/// Appends the expansion of a parsed template to `out`.
+///
+/// `group` returns the text matched by a group, or `None` if it didn't participate,
+/// in which case nothing is appended (matching CPython 3.5+).
+pub(crate) fn expand_template<'a>(parts: &[TemplatePart], group: impl Fn(usize) -> Option<&'a str>, out: &mut String) {
+    for part in parts {
+        match part {
+            TemplatePart::Literal(s) => out.push_str(s),
+            TemplatePart::Group(idx) => out.push_str(group(*idx).unwrap_or_default()),
+        }
+    }
+}
+
+/// A pattern token: a single character or a backslash escape (`\` plus one character).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Token {
+    Char(char),
+    Escape(char),
+}
+
+impl Token {
+    /// Length of the token in source characters.
+    fn len(self) -> usize {
+        match self {
+            Self::Char(_) => 1,
+            Self::Escape(_) => 2,
+        }
+    }
+
+    /// The first source character of the token.
+    fn first_char(self) -> char {
+        match self {
+            Self::Char(c) => c,
+            Self::Escape(_) => '\\',
+        }
+    }
+
+    /// Appends the source text of the token to `out`.
+    fn push_to(self, out: &mut String) {
+        match self {
+            Self::Char(c) => out.push(c),
+            Self::Escape(c) => {
+                out.push('\\');
+                out.push(c);
+            }
+        }
+    }
+
+    /// The source text of the token.
+    fn text(self) -> String {
+        let mut s = String::new();
+        self.push_to(&mut s);
+        s
+    }
+}
+
+/// Splits a pattern into tokens, tracking positions the same way as CPython's tokenizer
+/// so error positions match.
+struct Tokenizer {
+    chars: Vec<char>,
+    /// Index of the character after the current `next` token.
+    index: usize,
+    /// The token which `get()` will return next.
+    next: Option<Token>,
+}
+
+impl Tokenizer {
+    fn new(source: &str) -> Result<Self, ReSyntaxError> {
+        let mut tokenizer = Self {
+            chars: source.chars().collect(),
+            index: 0,
+            next: None,
+        };
+        tokenizer.advance()?;
+        Ok(tokenizer)
+    }
+
+    fn advance(&mut self) -> Result<(), ReSyntaxError> {
+        let Some(&c) = self.chars.get(self.index) else {
+            self.next = None;
+            return Ok(());
+        };
+        if c == '\\' {
+            let Some(&escaped) = self.chars.get(self.index + 1) else {
+                return Err(ReSyntaxError::Pattern {
+                    msg: "bad escape (end of pattern)".to_owned(),
+                    pos: self.chars.len() - 1,
+                    line_col: None,
+                });
+            };
+            self.index += 2;
+            self.next = Some(Token::Escape(escaped));
+        } else {
+            self.index += 1;
+            self.next = Some(Token::Char(c));
+        }
+        Ok(())
+    }
+
+    /// Position of the next token in the source.
+    fn tell(&self) -> usize {
+        self.index - self.next.map_or(0, Token::len)
+    }
+
+    fn seek(&mut self, index: usize) -> Result<(), ReSyntaxError> {
+        self.index = index;
+        self.advance()
+    }
+
+    fn get(&mut self) -> Result<Option<Token>, ReSyntaxError> {
+        let this = self.next;
+        self.advance()?;
+        Ok(this)
+    }
+
+    /// The next token if it is a plain character.
+    fn next_char(&self) -> Option<char> {
+        match self.next {
+            Some(Token::Char(c)) => Some(c),
+            _ => None,
+        }
+    }
+
+    /// Consumes the next token if it is the plain character `c`.
+    fn match_char(&mut self, c: char) -> Result<bool, ReSyntaxError> {
+        if self.next == Some(Token::Char(c)) {
+            self.advance()?;
+            Ok(true)
+        } else {
+            Ok(false)
+        }
+    }
+
+    /// Consumes up to `n` plain characters satisfying `pred`.
+    fn getwhile(&mut self, n: usize, pred: impl Fn(char) -> bool) -> Result<String, ReSyntaxError> {
+        let mut result = String::new();
+        for _ in 0..n {
+            match self.next_char() {
+                Some(c) if pred(c) => {
+                    result.push(c);
+                    self.advance()?;
+                }
+                _ => break,
+            }
+        }
+        Ok(result)
+    }
+
+    /// Consumes tokens up to and including `terminator`, returning the text before it.
+    fn getuntil(&mut self, terminator: char, name: &str) -> Result<String, ReSyntaxError> {
+        let mut result = String::new();
+        loop {
+            let c = self.next;
+            self.advance()?;
+            match c {
+                None if result.is_empty() => return Err(self.error(format!("missing {name}"), 0)),
+                None => {
+                    let len = result.chars().count();
+                    return Err(self.error(format!("missing {terminator}, unterminated name"), len));
+                }
+                Some(Token::Char(c)) if c == terminator => {
+                    if result.is_empty() {
+                        return Err(self.error(format!("missing {name}"), 1));
+                    }
+                    return Ok(result);
+                }
+                Some(token) => token.push_to(&mut result),
+            }
+        }
+    }
+
+    /// Validates a group name, which must be a Python identifier.
+    fn check_group_name(&self, name: &str, offset: usize) -> Result<(), ReSyntaxError> {
+        if is_identifier(name) {
+            Ok(())
+        } else {
+            Err(self.error(
+                format!("bad character in group name {}", py_repr(name)),
+                name.chars().count() + offset,
+            ))
+        }
+    }
+
+    /// Creates an error positioned `offset` characters before the next token.
+    fn error(&self, msg: impl Into<String>, offset: usize) -> ReSyntaxError {
+        ReSyntaxError::Pattern {
+            msg: msg.into(),
+            pos: self.tell() - offset,
+            line_col: None,
+        }
+    }
+}
+
+/// Bookkeeping for one capture group.
+struct GroupInfo {
+    name: Option<String>,
+    parent: Option<usize>,
+    closed: bool,
+}
+
+/// The most recently parsed item of a sequence, used to validate quantifiers.
+#[derive(Clone, Copy)]
+enum LastItem {
+    /// Nothing yet: a quantifier here has nothing to repeat.
+    Nothing,
+    /// A zero-width assertion (`^`, `$`, `\b`...), which can't be repeated.
+    At,
+    /// A quantified item, which can't be repeated again.
+    Repeat,
+    /// A repeatable item starting at this byte offset in the output.
+    Item(usize),
+}
+
+/// An escape sequence outside a character class.
+enum Escape {
+    /// Emitted unchanged (e.g. `\d`).
+    Category(&'static str),
+    /// A zero-width assertion.
+    At(&'static str),
+    Literal(char),
+    GroupRef(usize),
+}
+
+/// An element of a character class.
+enum ClassItem {
+    Literal(char),
+    Category(&'static str),
+    Range(char, char),
+}
+
+/// Recursive-descent translator mirroring `re._parser._parse_sub` and `_parse`.
+struct Parser {
+    source: Tokenizer,
+    out: String,
+    offsets: Vec<(usize, usize)>,
+    flags: u16,
+    groups: Vec<GroupInfo>,
+    open_groups: Vec<usize>,
+    /// Numeric conditional references `(?(N)...)` and where they appeared, checked at the end.
+    group_ref_pos: Vec<(usize, usize)>,
+}
+
+impl Parser {
+    /// Parses an alternation: `a|b|c`.
+    fn parse_sub(&mut self, mut verbose: bool, nested: usize, mut multiline: bool) -> Result<(), ReSyntaxError> {
+        let mut first = true;
+        loop {
+            self.parse(verbose, nested + 1, nested == 0 && first, multiline)?;
+            first = false;
+            if !self.source.match_char('|')? {
+                return Ok(());
+            }
+            self.out.push('|');
+            if nested == 0 {
+                verbose = self.flags & VERBOSE != 0;
+                multiline = self.flags & MULTILINE != 0;
+            }
+        }
+    }
+
+    /// Parses a sequence of items up to `|`, `)` or the end of the pattern.
+    fn parse(
+        &mut self,
+        mut verbose: bool,
+        nested: usize,
+        first: bool,
+        mut multiline: bool,
+    ) -> Result<(), ReSyntaxError> {
+        let mut last = LastItem::Nothing;
+        let mut has_items = false;
+
+        while let Some(this) = self.source.next {
+            if matches!(this, Token::Char('|' | ')')) {
+                break;
+            }
+            let token_pos = self.source.tell();
+            self.source.get()?;
+
+            if verbose {
+                if let Token::Char(c) = this {
+                    if WHITESPACE.contains(c) {
+                        continue;
+                    }
+                    if c == '#' {
+                        while !matches!(self.source.get()?, None | Some(Token::Char('\n'))) {}
+                        continue;
+                    }
+                }
+            }
+
+            self.offsets.push((self.out.len(), token_pos));
+            let item_start = self.out.len();
+            match this {
+                Token::Escape(c) => match self.escape(c)? {
+                    Escape::Category(s) => {
+                        self.out.push_str(s);
+                        last = LastItem::Item(item_start);
+                    }
+                    Escape::At(s) => {
+                        self.out.push_str(s);
+                        last = LastItem::At;
+                    }
+                    Escape::Literal(c) => {
+                        push_literal(&mut self.out, c);
+                        last = LastItem::Item(item_start);
+                    }
+                    Escape::GroupRef(group) => {
+                        self.push_group_ref(group);
+                        last = LastItem::Item(item_start);
+                    }
+                },
+                Token::Char(c) if !SPECIAL_CHARS.contains(c) => {
+                    push_literal(&mut self.out, c);
+                    last = LastItem::Item(item_start);
+                }
+                Token::Char('[') => {
+                    self.parse_class()?;
+                    last = LastItem::Item(item_start);
+                }
+                Token::Char(c @ ('?' | '*' | '+' | '{')) => {
+                    let here = self.source.tell();
+                    let (min, max) = match c {
+                        '?' => (0, Some(1)),
+                        '*' => (0, None),
+                        '+' => (1, None),
+                        _ => match self.parse_brace_repeat(here)? {
+                            Some(bounds) => bounds,
+                            None => {
+                                // Not a valid `{m,n}`: the brace is a literal
+                                push_literal(&mut self.out, '{');
+                                last = LastItem::Item(item_start);
+                                has_items = true;
+                                continue;
+                            }
+                        },
+                    };
+                    let atom_start = match last {
+                        LastItem::Nothing | LastItem::At => {
+                            return Err(self.source.error("nothing to repeat", self.source.tell() - here + 1));
+                        }
+                        LastItem::Repeat => {
+                            return Err(self.source.error("multiple repeat", self.source.tell() - here + 1));
+                        }
+                        LastItem::Item(start) => start,
+                    };
+                    let quantifier = match (min, max) {
+                        (0, Some(1)) => "?".to_owned(),
+                        (0, None) => "*".to_owned(),
+                        (1, None) => "+".to_owned(),
+                        (min, None) => format!("{{{min},}}"),
+                        (min, Some(max)) if min == max => format!("{{{min}}}"),
+                        (min, Some(max)) => format!("{{{min},{max}}}"),
+                    };
+                    if self.source.match_char('?')? {
+                        self.out.push_str(&quantifier);
+                        self.out.push('?');
+                    } else if self.source.match_char('+')? {
+                        // Possessive: `x*+` is equivalent to the atomic group `(?>x*)`
+                        self.out.insert_str(atom_start, "(?>");
+                        for (out, _) in self.offsets.iter_mut().filter(|(out, _)| *out >= atom_start) {
+                            *out += 3;
+                        }
+                        self.out.push_str(&quantifier);
+                        self.out.push(')');
+                    } else {
+                        self.out.push_str(&quantifier);
+                    }
+                    last = LastItem::Repeat;
+                }
+                Token::Char('.') => {
+                    self.out.push('.');
+                    last = LastItem::Item(item_start);
+                }
+                Token::Char('(') => {
+                    let start = self.source.tell() - 1;
+                    match self.parse_group(start, verbose, nested, multiline)? {
+                        GroupOutcome::Item => last = LastItem::Item(item_start),
+                        GroupOutcome::Comment => continue,
+                        GroupOutcome::GlobalFlags => {
+                            if !first || has_items {
+                                return Err(self.source.error(
+                                    "global flags not at the start of the expression",
+                                    self.source.tell() - start,
+                                ));
+                            }
+                            verbose = self.flags & VERBOSE != 0;
+                            multiline = self.flags & MULTILINE != 0;
+                            continue;
+                        }
+                    }
+                }
+                Token::Char('^') => {
+                    self.out.push('^');
+                    last = LastItem::At;
+                }
+                Token::Char('$') => {
+                    // Python's `$` also matches just before a trailing newline
+                    self.out.push_str(if multiline { "$" } else { "(?=\\n?\\z)" });
+                    last = LastItem::At;
+                }
+                Token::Char(c) => unreachable!("unhandled special character {c:?}"),
+            }
+            has_items = true;
+        }
+        Ok(())
+    }
+
+    /// Parses the rest of a `{m,n}` quantifier after the `{`.
+    ///
+    /// Returns `None` (with the tokenizer rewound to `here`) if the brace doesn't start a
+    /// valid quantifier, in which case it's a literal `{`.
+    fn parse_brace_repeat(&mut self, here: usize) -> Result<Option<(usize, Option<usize>)>, ReSyntaxError> {
+        if self.source.next == Some(Token::Char('}')) {
+            return Ok(None);
+        }
+        let lo = self.source.getwhile(usize::MAX, |c| c.is_ascii_digit())?;
+        let hi = if self.source.match_char(',')? {
+            self.source.getwhile(usize::MAX, |c| c.is_ascii_digit())?
+        } else {
+            lo.clone()
+        };
+        if !self.source.match_char('}')? {
+            self.source.seek(here)?;
+            return Ok(None);
+        }
+        let parse_bound = |digits: &str| {
+            digits
+                .parse::<usize>()
+                .ok()
+                .filter(|&n| n < MAX_REPEAT)
+                .ok_or_else(|| ReSyntaxError::Pattern {
+                    msg: "the repetition number is too large".to_owned(),
+                    pos: here - 1,
+                    line_col: None,
+                })
+        };
+        let min = if lo.is_empty() { 0 } else { parse_bound(&lo)? };
+        let max = if hi.is_empty() { None } else { Some(parse_bound(&hi)?) };
+        if max.is_some_and(|max| max < min) {
+            return Err(self
+                .source
+                .error("min repeat greater than max repeat", self.source.tell() - here));
+        }
+        Ok(Some((min, max)))
+    }
+
+    /// Parses a group after its opening `(`, emitting the translated group.
+    fn parse_group(
+        &mut self,
+        start: usize,
+        verbose: bool,
+        nested: usize,
+        multiline: bool,
+    ) -> Result<GroupOutcome, ReSyntaxError> {
+        let mut name = None;
+        let mut add_flags = 0;
+        let mut del_flags = 0;
+        let open: &str;
+
+        if self.source.match_char('?')? {
+            let Some(token) = self.source.get()? else {
+                return Err(self.source.error("unexpected end of pattern", 0));
+            };
+            let c = match token {
+                Token::Char(c) => c,
+                // An escape never starts an extension
+                Token::Escape(_) => '\\',
+            };
+            match c {
+                'P' => {
+                    if self.source.match_char('<')? {
+                        let group_name = self.source.getuntil('>', "group name")?;
+                        self.source.check_group_name(&group_name, 1)?;
+                        name = Some(group_name);
+                        open = "(";
+                    } else if self.source.match_char('=')? {
+                        let group_name = self.source.getuntil(')', "group name")?;
+                        self.source.check_group_name(&group_name, 1)?;
+                        let offset = group_name.chars().count() + 1;
+                        let Some(group) = self.group_by_name(&group_name) else {
+                            return Err(self
+                                .source
+                                .error(format!("unknown group name {}", py_repr(&group_name)), offset));
+                        };
+                        if !self.groups[group - 1].closed {
+                            return Err(self.source.error("cannot refer to an open group", offset));
+                        }
+                        self.push_group_ref(group);
+                        return Ok(GroupOutcome::Item);
+                    } else {
+                        let Some(token) = self.source.get()? else {
+                            return Err(self.source.error("unexpected end of pattern", 0));
+                        };
+                        return Err(self
+                            .source
+                            .error(format!("unknown extension ?P{}", token.text()), token.len() + 2));
+                    }
+                }
+                ':' => open = "(?:",
+                '#' => loop {
+                    match self.source.get()? {
+                        None => {
+                            return Err(ReSyntaxError::Pattern {
+                                msg: "missing ), unterminated comment".to_owned(),
+                                pos: start,
+                                line_col: None,
+                            });
+                        }
+                        Some(Token::Char(')')) => return Ok(GroupOutcome::Comment),
+                        Some(_) => {}
+                    }
+                },
+                '=' | '!' | '<' => {
+                    let open = match c {
+                        '=' => "(?=",
+                        '!' => "(?!",
+                        _ => match self.source.get()? {
+                            None => return Err(self.source.error("unexpected end of pattern", 0)),
+                            Some(Token::Char('=')) => "(?<=",
+                            Some(Token::Char('!')) => "(?<!",
+                            Some(token) => {
+                                return Err(self
+                                    .source
+                                    .error(format!("unknown extension ?<{}", token.text()), token.len() + 2));
+                            }
+                        },
+                    };
+                    self.out.push_str(open);
+                    self.parse_sub(verbose, nested + 1, multiline)?;
+                    self.close_group(start)?;
+                    return Ok(GroupOutcome::Item);
+                }
+                '(' => {
+                    let cond_name = self.source.getuntil(')', "group name")?;
+                    let offset = cond_name.chars().count() + 1;
+                    let group = if is_ascii_decimal(&cond_name) {
+                        let group = cond_name.parse::<usize>().map_err(|_| {
+                            self.source
+                                .error(format!("invalid group reference {cond_name}"), offset)
+                        })?;
+                        if group == 0 {
+                            return Err(self.source.error("bad group number", offset));
+                        }
+                        if !self.group_ref_pos.iter().any(|&(g, _)| g == group) {
+                            self.group_ref_pos.push((group, self.source.tell() - offset));
+                        }
+                        group
+                    } else {
+                        self.source.check_group_name(&cond_name, 1)?;
+                        self.group_by_name(&cond_name).ok_or_else(|| {
+                            self.source
+                                .error(format!("unknown group name {}", py_repr(&cond_name)), offset)
+                        })?
+                    };
+                    self.out.push_str(&format!("(?({group})"));
+                    self.parse(verbose, nested + 1, false, multiline)?;
+                    if self.source.match_char('|')? {
+                        self.out.push('|');
+                        self.parse(verbose, nested + 1, false, multiline)?;
+                        if self.source.next == Some(Token::Char('|')) {
+                            return Err(self.source.error("conditional backref with more than two branches", 0));
+                        }
+                    }
+                    self.close_group(start)?;
+                    return Ok(GroupOutcome::Item);
+                }
+                '>' => open = "(?>",
+                c if flag_value(c).is_some() || c == '-' => match self.parse_flags(c)? {
+                    None => return Ok(GroupOutcome::GlobalFlags),
+                    Some((add, del)) => {
+                        add_flags = add;
+                        del_flags = del;
+                        open = "";
+                    }
+                },
+                _ => {
+                    return Err(self
+                        .source
+                        .error(format!("unknown extension ?{}", token.text()), token.len() + 1));
+                }
+            }
+        } else {
+            open = "(";
+        }
+
+        let capture = open == "(";
+        if capture {
+            if let Some(name) = &name
+                && let Some(existing) = self.group_by_name(name)
+            {
+                return Err(self.source.error(
+                    format!(
+                        "redefinition of group name {} as group {}; was group {existing}",
+                        py_repr(name),
+                        self.groups.len() + 1
+                    ),
+                    name.chars().count() + 1,
+                ));
+            }
+            self.groups.push(GroupInfo {
+                name,
+                parent: self.open_groups.last().copied(),
+                closed: false,
+            });
+            self.open_groups.push(self.groups.len());
+            self.out.push('(');
+        } else if open.is_empty() {
+            // Scoped flags: only `i`, `m` and `s` are understood by the regex engine
+            let mut on = String::new();
+            let mut off = String::new();
+            for (flag, c) in [(IGNORECASE, 'i'), (MULTILINE, 'm'), (DOTALL, 's')] {
+                if add_flags & flag != 0 {
+                    on.push(c);
+                }
+                if del_flags & flag != 0 {
+                    off.push(c);
+                }
+            }
+            self.out.push_str("(?");
+            self.out.push_str(&on);
+            if !off.is_empty() {
+                self.out.push('-');
+                self.out.push_str(&off);
+            }
+            self.out.push(':');
+        } else {
+            self.out.push_str(open);
+        }
+
+        let sub_verbose = (verbose || add_flags & VERBOSE != 0) && del_flags & VERBOSE == 0;
+        let sub_multiline = (multiline || add_flags & MULTILINE != 0) && del_flags & MULTILINE == 0;
+        self.parse_sub(sub_verbose, nested + 1, sub_multiline)?;
+        self.close_group(start)?;
+        if capture {
+            let group = self.open_groups.pop().expect("capture group was opened");
+            self.groups[group - 1].closed = true;
+        }
+        Ok(GroupOutcome::Item)
+    }
+
+    /// Consumes the `)` closing a group opened at `start`.
+    fn close_group(&mut self, start: usize) -> Result<(), ReSyntaxError> {
+        if !self.source.match_char(')')? {
+            return Err(self
+                .source
+                .error("missing ), unterminated subpattern", self.source.tell() - start));
+        }
+        self.out.push(')');
+        Ok(())
+    }
+
+    /// Parses inline flags after `(?`, starting with `c`.
+    ///
+    /// Returns `None` for global flags `(?imsx)` (which are added to `self.flags`), or
+    /// the flags turned on and off for a scoped group `(?i-s:...)`.
+    fn parse_flags(&mut self, mut c: char) -> Result<Option<(u16, u16)>, ReSyntaxError> {
+        let mut add_flags = 0;
+        let mut del_flags = 0;
+        if c != '-' {
+            loop {
+                let flag = flag_value(c).expect("caller checked flag character");
+                if c == 'L' {
+                    return Err(self
+                        .source
+                        .error("bad inline flags: cannot use 'L' flag with a str pattern", 0));
+                }
+                add_flags |= flag;
+                if flag & TYPE_FLAGS != 0 && add_flags & TYPE_FLAGS != flag {
+                    return Err(self
+                        .source
+                        .error("bad inline flags: flags 'a', 'u' and 'L' are incompatible", 0));
+                }
+                c = match self.source.get()? {
+                    None => return Err(self.source.error("missing -, : or )", 0)),
+                    Some(Token::Char(c @ (')' | '-' | ':'))) => c,
+                    Some(Token::Char(c)) if flag_value(c).is_some() => c,
+                    Some(token) => {
+                        let msg = if matches!(token, Token::Char(c) if c.is_alphabetic()) {
+                            "unknown flag"
+                        } else {
+                            "missing -, : or )"
+                        };
+                        return Err(self.source.error(msg, token.len()));
+                    }
+                };
+                if matches!(c, ')' | '-' | ':') {
+                    break;
+                }
+            }
+        }
+        if c == ')' {
+            self.flags |= add_flags;
+            return Ok(None);
+        }
+        if c == '-' {
+            c = match self.source.get()? {
+                None => return Err(self.source.error("missing flag", 0)),
+                Some(Token::Char(c)) if flag_value(c).is_some() => c,
+                Some(token) => {
+                    let msg = if matches!(token, Token::Char(c) if c.is_alphabetic()) {
+                        "unknown flag"
+                    } else {
+                        "missing flag"
+                    };
+                    return Err(self.source.error(msg, token.len()));
+                }
+            };
+            loop {
+                let flag = flag_value(c).expect("checked flag character");
+                if flag & TYPE_FLAGS != 0 {
+                    return Err(self
+                        .source
+                        .error("bad inline flags: cannot turn off flags 'a', 'u' and 'L'", 0));
+                }
+                del_flags |= flag;
+                c = match self.source.get()? {
+                    None => return Err(self.source.error("missing :", 0)),
+                    Some(Token::Char(':')) => break,
+                    Some(Token::Char(c)) if flag_value(c).is_some() => c,
+                    Some(token) => {
+                        let msg = if matches!(token, Token::Char(c) if c.is_alphabetic()) {
+                            "unknown flag"
+                        } else {
+                            "missing :"
+                        };
+                        return Err(self.source.error(msg, token.len()));
+                    }
+                };
+            }
+        }
+        if add_flags & del_flags != 0 {
+            return Err(self.source.error("bad inline flags: flag turned on and off", 1));
+        }
+        Ok(Some((add_flags, del_flags)))
+    }
+
+    /// Parses a character class after its opening `[`, emitting the translated class.
+    fn parse_class(&mut self) -> Result<(), ReSyntaxError> {
+        let here = self.source.tell() - 1;
+        let negate = self.source.match_char('^')?;
+        let mut items = Vec::new();
+        loop {
+            let Some(this) = self.source.get()? else {
+                return Err(self
+                    .source
+                    .error("unterminated character set", self.source.tell() - here));
+            };
+            let code1 = match this {
+                Token::Char(']') if !items.is_empty() => break,
+                Token::Escape(c) => self.class_escape(c)?,
+                Token::Char(c) => ClassItem::Literal(c),
+            };
+            if !self.source.match_char('-')? {
+                items.push(code1);
+                continue;
+            }
+            // Potential range
+            let Some(that) = self.source.get()? else {
+                return Err(self
+                    .source
+                    .error("unterminated character set", self.source.tell() - here));
+            };
+            if that == Token::Char(']') {
+                items.push(code1);
+                items.push(ClassItem::Literal('-'));
+                break;
+            }
+            let code2 = match that {
+                Token::Escape(c) => self.class_escape(c)?,
+                Token::Char(c) => ClassItem::Literal(c),
+            };
+            let bad_range = || {
+                self.source.error(
+                    format!("bad character range {}-{}", this.text(), that.text()),
+                    this.len() + 1 + that.len(),
+                )
+            };
+            let (ClassItem::Literal(lo), ClassItem::Literal(hi)) = (code1, code2) else {
+                return Err(bad_range());
+            };
+            if hi < lo {
+                return Err(bad_range());
+            }
+            items.push(ClassItem::Range(lo, hi));
+        }
+
+        self.out.push('[');
+        if negate {
+            self.out.push('^');
+        }
+        for item in items {
+            match item {
+                ClassItem::Literal(c) => push_class_literal(&mut self.out, c),
+                ClassItem::Category(s) => self.out.push_str(s),
+                ClassItem::Range(lo, hi) => {
+                    push_class_literal(&mut self.out, lo);
+                    self.out.push('-');
+                    push_class_literal(&mut self.out, hi);
+                }
+            }
+        }
+        self.out.push(']');
+        Ok(())
+    }
+
+    /// Handles an escape outside a character class (`re._parser._escape`).
+    fn escape(&mut self, c: char) -> Result<Escape, ReSyntaxError> {
+        match c {
+            'A' => return Ok(Escape::At("\\A")),
+            'b' => return Ok(Escape::At("\\b")),
+            'B' => return Ok(Escape::At("\\B")),
+            'Z' => return Ok(Escape::At("\\z")),
+            'd' => return Ok(Escape::Category("\\d")),
+            'D' => return Ok(Escape::Category("\\D")),
+            's' => return Ok(Escape::Category("\\s")),
+            'S' => return Ok(Escape::Category("\\S")),
+            'w' => return Ok(Escape::Category("\\w")),
+            'W' => return Ok(Escape::Category("\\W")),
+            _ => {}
+        }
+        if let Some(ch) = simple_escape(c) {
+            return Ok(Escape::Literal(ch));
+        }
+        match c {
+            'x' | 'u' | 'U' | 'N' => self.unicode_escape(c).map(Escape::Literal),
+            '0' => {
+                let digits = self.source.getwhile(2, is_octal_digit)?;
+                let value = u32::from_str_radix(&format!("0{digits}"), 8).expect("octal digits");
+                Ok(Escape::Literal(
+                    char::from_u32(value).expect("octal escape is below 0o100"),
+                ))
+            }
+            '1'..='9' => {
+                // Octal escape *or* decimal group reference
+                let mut escape = String::from(c);
+                if let Some(d) = self.source.next_char().filter(char::is_ascii_digit) {
+                    self.source.get()?;
+                    escape.push(d);
+                    if let Some(d3 @ '0'..='7') = self.source.next_char()
+                        && is_octal_digit(c)
+                        && is_octal_digit(d)
+                    {
+                        self.source.get()?;
+                        escape.push(d3);
+                        let value = u32::from_str_radix(&escape, 8).expect("octal digits");
+                        if value > 0o377 {
+                            return Err(self.source.error(
+                                format!("octal escape value \\{escape} outside of range 0-0o377"),
+                                escape.len() + 1,
+                            ));
+                        }
+                        return Ok(Escape::Literal(char::from_u32(value).expect("value is below 0x100")));
+                    }
+                }
+                let group: usize = escape.parse().expect("one or two decimal digits");
+                if group <= self.groups.len() {
+                    if !self.groups[group - 1].closed {
+                        return Err(self.source.error("cannot refer to an open group", escape.len() + 1));
+                    }
+                    return Ok(Escape::GroupRef(group));
+                }
+                Err(self
+                    .source
+                    .error(format!("invalid group reference {group}"), escape.len()))
+            }
+            c if c.is_ascii_alphabetic() => Err(self.source.error(format!("bad escape \\{c}"), 2)),
+            c => Ok(Escape::Literal(c)),
+        }
+    }
+
+    /// Handles an escape inside a character class (`re._parser._class_escape`).
+    fn class_escape(&mut self, c: char) -> Result<ClassItem, ReSyntaxError> {
+        if let Some(ch) = simple_escape(c) {
+            return Ok(ClassItem::Literal(ch));
+        }
+        match c {
+            'b' => Ok(ClassItem::Literal('\x08')),
+            'd' => Ok(ClassItem::Category("\\d")),
+            'D' => Ok(ClassItem::Category("\\D")),
+            's' => Ok(ClassItem::Category("\\s")),
+            'S' => Ok(ClassItem::Category("\\S")),
+            'w' => Ok(ClassItem::Category("\\w")),
+            'W' => Ok(ClassItem::Category("\\W")),
+            'x' | 'u' | 'U' | 'N' => self.unicode_escape(c).map(ClassItem::Literal),
+            '0'..='7' => {
+                let digits = self.source.getwhile(2, is_octal_digit)?;
+                let escape = format!("{c}{digits}");
+                let value = u32::from_str_radix(&escape, 8).expect("octal digits");
+                if value > 0o377 {
+                    return Err(self.source.error(
+                        format!("octal escape value \\{escape} outside of range 0-0o377"),
+                        escape.len() + 1,
+                    ));
+                }
+                Ok(ClassItem::Literal(char::from_u32(value).expect("value is below 0x100")))
+            }
+            c if c.is_ascii_alphanumeric() => Err(self.source.error(format!("bad escape \\{c}"), 2)),
+            c => Ok(ClassItem::Literal(c)),
+        }
+    }
+
+    /// Handles `\xhh`, `\uhhhh`, `\Uhhhhhhhh` and `\N{name}` escapes.
+    fn unicode_escape(&mut self, c: char) -> Result<char, ReSyntaxError> {
+        let digits = match c {
+            'x' => 2,
+            'u' => 4,
+            'U' => 8,
+            _ => {
+                // Monty has no Unicode name database, so named escapes can't be resolved
+                if !self.source.match_char('{')? {
+                    return Err(self.source.error("missing {", 0));
+                }
+                let name = self.source.getuntil('}', "character name")?;
+                return Err(self.source.error(
+                    format!("undefined character name {}", py_repr(&name)),
+                    name.chars().count() + 4,
+                ));
+            }
+        };
+        let hex = self.source.getwhile(digits, |c| c.is_ascii_hexdigit())?;
+        let escape_len = hex.len() + 2;
+        if hex.len() != digits {
+            return Err(self.source.error(format!("incomplete escape \\{c}{hex}"), escape_len));
+        }
+        u32::from_str_radix(&hex, 16)
+            .ok()
+            .and_then(char::from_u32)
+            .ok_or_else(|| self.source.error(format!("bad escape \\{c}{hex}"), escape_len))
+    }
+
+    /// Emits a backreference to `group`, wrapped so following digits aren't absorbed.
+    fn push_group_ref(&mut self, group: usize) {
+        self.out.push_str(&format!("(?:\\{group})"));
+    }
+
+    /// Looks up a named group, returning its 1-based number.
+    fn group_by_name(&self, name: &str) -> Option<usize> {
+        self.groups
+            .iter()
+            .position(|g| g.name.as_deref() == Some(name))
+            .map(|idx| idx + 1)
+    }
+}
+
+/// What a parenthesized construct turned out to be.
+enum GroupOutcome {
+    /// A group, lookaround, conditional or backreference: a repeatable item.
+    Item,
+    /// A `(?#...)` comment, which emits nothing.
+    Comment,
+    /// Global inline flags `(?imsx)`, which emit nothing.
+    GlobalFlags,
+}
+
+/// Maps an inline flag letter to its flag value.
+fn flag_value(c: char) -> Option<u16> {
+    match c {
+        'i' => Some(IGNORECASE),
+        'L' => Some(LOCALE),
+        'm' => Some(MULTILINE),
+        's' => Some(DOTALL),
+        'x' => Some(VERBOSE),
+        'a' => Some(ASCII),
+        'u' => Some(UNICODE),
+        _ => None,
+    }
+}
+
+/// Single-character escapes shared by patterns and templates (except `\b`, which differs).
+fn simple_escape(c: char) -> Option<char> {
+    match c {
+        'a' => Some('\x07'),
+        'f' => Some('\x0c'),
+        'n' => Some('\n'),
+        'r' => Some('\r'),
+        't' => Some('\t'),
+        'v' => Some('\x0b'),
+        '\\' => Some('\\'),
+        _ => None,
+    }
+}
+
+/// Appends a literal character outside a class, escaping regex metacharacters.
+fn push_literal(out: &mut String, c: char) {
+    if "\\.+*?()|[]{}^$".contains(c) {
+        out.push('\\');
+    }
+    out.push(c);
+}
+
+/// Appends a literal character inside a class, escaping class metacharacters.
+fn push_class_literal(out: &mut String, c: char) {
+    if "\\[]^-&~".contains(c) {
+        out.push('\\');
+    }
+    out.push(c);
+}
+
+fn is_octal_digit(c: char) -> bool {
+    matches!(c, '0'..='7')
+}
+
+fn is_ascii_decimal(s: &str) -> bool {
+    !s.is_empty() && s.bytes().all(|b| b.is_ascii_digit())
+}
+
+/// Approximates `str.isidentifier()`.
+fn is_identifier(s: &str) -> bool {
+    let mut chars = s.chars();
+    chars.next().is_some_and(|c| c == '_' || c.is_alphabetic()) && chars.all(|c| c == '_' || c.is_alphanumeric())
+}
+
+/// Formats a string the way Python's `repr()` would.
+fn py_repr(s: &str) -> String {
+    let mut out = String::new();
+    string_repr_fmt(s, &mut out).expect("writing to a String cannot fail");
+    out
+}
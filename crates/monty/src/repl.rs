@@ -20,7 +20,7 @@ use crate::{
     io::PrintWriter,
     namespace::NamespaceId,
     object::MontyObject,
-    os::OsFunction,
+    os::{OsFunction, OsHandler},
     parse::parse_with_interner,
     prepare::prepare_with_existing_names,
     resource::ResourceTracker,
@@ -41,6 +41,8 @@ pub struct MontyRepl<T: ResourceTracker> {
     /// Incremental `feed()` / `start()` snippets intentionally use internal script names
     /// like `<python-input-0>` to match CPython's interactive traceback style.
     script_name: String,
+    /// Value of `__name__` in the snippets, `"__main__"` unless set with `set_module_name()`.
+    module_name: String,
     /// Counter for generated `<python-input-N>` snippet filenames.
     next_input_id: u64,
     /// Stable mapping of global variable names to namespace slot IDs.
@@ -68,6 +70,7 @@ impl<T: ResourceTracker> MontyRepl<T> {
 
         Self {
             script_name: script_name.to_owned(),
+            module_name: "__main__".to_owned(),
             next_input_id: 0,
             global_name_map: AHashMap::new(),
             interns: Interns::new(InternerBuilder::default(), Vec::new()),
@@ -76,6 +79,15 @@ impl<T: ResourceTracker> MontyRepl<T> {
         }
     }
 
+    /// Sets the value of `__name__` seen by snippets fed after this call.
+    ///
+    /// Defaults to `"__main__"`, as in CPython's interactive interpreter; hosts using the REPL
+    /// as something other than the main program can set another name so that
+    /// `if __name__ == "__main__":` blocks are skipped.
+    pub fn set_module_name(&mut self, module_name: String) {
+        self.module_name = module_name;
+    }
+
     /// Starts executing a new snippet and returns suspendable REPL progress.
     ///
     /// This is the REPL equivalent of `MontyRun::start`: execution may complete,
@@ -115,6 +127,7 @@ impl<T: ResourceTracker> MontyRepl<T> {
             &input_script_name,
             this.global_name_map.clone(),
             &this.interns,
+            &this.module_name,
             input_names,
         ) {
             Ok(exec) => exec,
@@ -123,7 +136,13 @@ impl<T: ResourceTracker> MontyRepl<T> {
 
         this.ensure_globals_size(executor.namespace_size);
 
-        let mut vm = VM::new(mem::take(&mut this.globals), &mut this.heap, &executor.interns, print);
+        let mut vm = VM::new(
+            mem::take(&mut this.globals),
+            &mut this.heap,
+            &executor.interns,
+            repl_argv(),
+            print,
+        );
 
         // Inject inputs with VM alive
         if let Err(error) = inject_inputs_into_vm(&executor, input_values, &mut vm) {
@@ -174,12 +193,19 @@ impl<T: ResourceTracker> MontyRepl<T> {
             &input_script_name,
             self.global_name_map.clone(),
             &self.interns,
+            &self.module_name,
             input_names,
         )?;
 
         self.ensure_globals_size(executor.namespace_size);
 
-        let mut vm = VM::new(mem::take(&mut self.globals), &mut self.heap, &executor.interns, print);
+        let mut vm = VM::new(
+            mem::take(&mut self.globals),
+            &mut self.heap,
+            &executor.interns,
+            repl_argv(),
+            print,
+        );
 
         if let Err(e) = inject_inputs_into_vm(&executor, input_values, &mut vm) {
             self.globals = vm.take_globals();
@@ -481,6 +507,20 @@ impl<T: ResourceTracker> ReplOsCall<T> {
     ) -> Result<ReplProgress<T>, Box<ReplStartError<T>>> {
         self.snapshot.run(result, print)
     }
+
+    /// Performs the OS call with `handler` and resumes execution with its result.
+    ///
+    /// # Arguments
+    /// * `handler` — The host implementation of the OS functions, e.g. a `MemoryFs`.
+    /// * `print` — Writer for `print()` output.
+    pub fn resume_with(
+        self,
+        handler: &mut impl OsHandler,
+        print: PrintWriter<'_>,
+    ) -> Result<ReplProgress<T>, Box<ReplStartError<T>>> {
+        let result = handler.handle_os_call(self.function, &self.args, &self.kwargs);
+        self.resume(result, print)
+    }
 }
 
 // ---------------------------------------------------------------------------
@@ -837,6 +877,7 @@ impl ReplExecutor {
         script_name: &str,
         mut existing_name_map: AHashMap<String, NamespaceId>,
         existing_interns: &Interns,
+        module_name: &str,
         input_names: Vec<String>,
     ) -> Result<Self, MontyException> {
         // Pre-register input names so they get stable slots before preparation.
@@ -850,15 +891,21 @@ impl ReplExecutor {
         let seeded_interner = InternerBuilder::from_interns(existing_interns, &code);
         let parse_result = parse_with_interner(&code, script_name, seeded_interner)
             .map_err(|e| e.into_python_exc(script_name, &code))?;
-        let prepared = prepare_with_existing_names(parse_result, existing_name_map)
+        let prepared = prepare_with_existing_names(parse_result, existing_name_map, module_name)
             .map_err(|e| e.into_python_exc(script_name, &code))?;
 
         let existing_functions = existing_interns.functions_clone();
         let mut interns = Interns::new(prepared.interner, Vec::new());
         let namespace_size_u16 = u16::try_from(prepared.namespace_size).expect("module namespace size exceeds u16");
-        let compile_result =
-            Compiler::compile_module_with_functions(&prepared.nodes, &interns, namespace_size_u16, existing_functions)
-                .map_err(|e| e.into_python_exc(script_name, &code))?;
+        let compile_result = Compiler::compile_module_with_functions(
+            &prepared.nodes,
+            &interns,
+            namespace_size_u16,
+            &prepared.global_names,
+            &prepared.module_dunders,
+            existing_functions,
+        )
+        .map_err(|e| e.into_python_exc(script_name, &code))?;
         interns.set_functions(compile_result.functions);
 
         Ok(Self {
@@ -928,12 +975,7 @@ impl<T: ResourceTracker> ReplSnapshot<T> {
         let vm_result = match ext_result {
             ExtFunctionResult::Return(obj) => vm.resume(obj),
             ExtFunctionResult::Error(exc) => vm.resume_with_exception(exc.into()),
-            ExtFunctionResult::Future(raw_call_id) => {
-                let call_id = CallId::new(raw_call_id);
-                vm.add_pending_call(call_id);
-                vm.push(Value::ExternalFuture(call_id));
-                vm.run()
-            }
+            ExtFunctionResult::Future(raw_call_id) => vm.resume_with_future(CallId::new(raw_call_id)),
             ExtFunctionResult::NotFound(function_name) => {
                 vm.resume_with_exception(ExtFunctionResult::not_found_exc(&function_name))
             }
@@ -956,6 +998,12 @@ impl<T: ResourceTracker> ReplSnapshot<T> {
 // Private helper functions
 // ---------------------------------------------------------------------------
 
+/// The command line arguments a REPL snippet sees as `sys.argv`: `['']`, like CPython's
+/// interactive interpreter.
+fn repl_argv() -> Vec<String> {
+    vec![String::new()]
+}
+
 /// Injects input values into the VM's global namespace slots.
 ///
 /// Converts each `MontyObject` to a `Value` while the VM is alive, then stores
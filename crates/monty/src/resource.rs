@@ -282,6 +282,16 @@ pub trait ResourceTracker: fmt::Debug {
     ///
     /// Returns `Ok(())` to allow the operation, or `Err(ResourceError)` to reject.
     fn check_large_result(&self, estimated_bytes: usize) -> Result<(), ResourceError>;
+
+    /// Returns the time limit and the time elapsed so far, or `None` if there is no time limit.
+    ///
+    /// Used to budget native operations that can't call `check_time` while they run,
+    /// such as regex backtracking, so they stop close to the configured deadline.
+    ///
+    /// The default reports no time limit.
+    fn time_usage(&self) -> Option<(Duration, Duration)> {
+        None
+    }
 }
 
 /// A resource tracker that imposes no limits except default recursion limit.
@@ -556,4 +566,8 @@ impl ResourceTracker for LimitedTracker {
         }
         Ok(())
     }
+
+    fn time_usage(&self) -> Option<(Duration, Duration)> {
+        self.limits.max_duration.map(|max| (max, self.start_time.elapsed()))
+    }
 }
@@ -60,6 +60,14 @@ impl MontyRun {
         &self.executor.code
     }
 
+    /// Sets the command line arguments the code sees as `sys.argv`.
+    ///
+    /// By default `sys.argv` is `[script_name]`; hosts running a script with arguments,
+    /// like `monty script.py a b`, pass the full list including the script name.
+    pub fn set_argv(&mut self, argv: Vec<String>) {
+        self.executor.argv = argv;
+    }
+
     /// Executes the code and returns both the result and reference count data, used for testing only.
     #[cfg(feature = "ref-count-return")]
     pub fn run_ref_counts(&self, inputs: Vec<MontyObject>) -> Result<RefCountOutput, MontyException> {
@@ -147,7 +155,8 @@ impl MontyRun {
         // Create heap and VM with empty globals, then populate inputs with VM alive
         let mut heap = Heap::new(executor.namespace_size, resource_tracker);
         let globals = executor.empty_globals();
-        let mut vm = VM::new(globals, &mut heap, &executor.interns, print);
+        let mut vm = VM::new(globals, &mut heap, &executor.interns, executor.argv.clone(), print);
+        vm.close_files_at_exit = true;
         executor.populate_inputs(inputs, &mut vm)?;
 
         // Start execution
@@ -177,6 +186,8 @@ pub(crate) struct Executor {
     pub(crate) interns: Interns,
     /// Source code for error reporting (extracting preview lines for tracebacks).
     pub(crate) code: String,
+    /// The command line arguments the code sees as `sys.argv`.
+    argv: Vec<String>,
     /// Estimated heap capacity for pre-allocation on subsequent runs.
     /// Uses AtomicUsize for thread-safety (required by PyO3's Sync bound).
     heap_capacity: AtomicUsize,
@@ -191,6 +202,7 @@ impl Clone for Executor {
             module_code: self.module_code.clone(),
             interns: self.interns.clone(),
             code: self.code.clone(),
+            argv: self.argv.clone(),
             heap_capacity: AtomicUsize::new(self.heap_capacity.load(Ordering::Relaxed)),
         }
     }
@@ -200,15 +212,22 @@ impl Executor {
     /// Creates a new executor with the given code, filename, and input names.
     pub(crate) fn new(code: String, script_name: &str, input_names: Vec<String>) -> Result<Self, MontyException> {
         let parse_result = parse(&code, script_name).map_err(|e| e.into_python_exc(script_name, &code))?;
-        let prepared = prepare(parse_result, input_names).map_err(|e| e.into_python_exc(script_name, &code))?;
+        let prepared =
+            prepare(parse_result, input_names, script_name).map_err(|e| e.into_python_exc(script_name, &code))?;
 
         // Create interns with empty functions (functions will be set after compilation)
         let mut interns = Interns::new(prepared.interner, Vec::new());
 
         // Compile the module to bytecode, which also compiles all nested functions
         let namespace_size_u16 = u16::try_from(prepared.namespace_size).expect("module namespace size exceeds u16");
-        let compile_result = Compiler::compile_module(&prepared.nodes, &interns, namespace_size_u16)
-            .map_err(|e| e.into_python_exc(script_name, &code))?;
+        let compile_result = Compiler::compile_module(
+            &prepared.nodes,
+            &interns,
+            namespace_size_u16,
+            &prepared.global_names,
+            &prepared.module_dunders,
+        )
+        .map_err(|e| e.into_python_exc(script_name, &code))?;
 
         // Set the compiled functions in the interns
         interns.set_functions(compile_result.functions);
@@ -220,6 +239,8 @@ impl Executor {
             module_code: compile_result.code,
             interns,
             code,
+            // `sys.argv` defaults to just the script name, as for `python script.py`
+            argv: vec![script_name.to_owned()],
             heap_capacity: AtomicUsize::new(prepared.namespace_size),
         })
     }
@@ -245,7 +266,7 @@ impl Executor {
         let globals = self.empty_globals();
 
         // Create VM first, then populate inputs with VM alive
-        let mut vm = VM::new(globals, &mut heap, &self.interns, print);
+        let mut vm = VM::new(globals, &mut heap, &self.interns, self.argv.clone(), print);
         self.populate_inputs(inputs, &mut vm)?;
         let mut frame_exit_result = vm.run_module(&self.module_code);
 
@@ -315,7 +336,13 @@ impl Executor {
         let globals = self.empty_globals();
 
         // Create VM, populate inputs, and run
-        let mut vm = VM::new(globals, &mut heap, &self.interns, PrintWriter::Stdout);
+        let mut vm = VM::new(
+            globals,
+            &mut heap,
+            &self.interns,
+            self.argv.clone(),
+            PrintWriter::Stdout,
+        );
         self.populate_inputs(inputs, &mut vm)?;
         let frame_exit_result = vm.run_module(&self.module_code);
 
@@ -16,10 +16,9 @@ use crate::{
     heap::Heap,
     io::PrintWriter,
     object::MontyObject,
-    os::OsFunction,
+    os::{OsFunction, OsHandler},
     resource::ResourceTracker,
     run::Executor,
-    value::Value,
 };
 
 // ---------------------------------------------------------------------------
@@ -263,6 +262,20 @@ impl<T: ResourceTracker> OsCall<T> {
     ) -> Result<RunProgress<T>, MontyException> {
         self.snapshot.run(result, print)
     }
+
+    /// Performs the OS call with `handler` and resumes execution with its result.
+    ///
+    /// # Arguments
+    /// * `handler` — The host implementation of the OS functions, e.g. a `MemoryFs`.
+    /// * `print` — Writer for `print()` output.
+    pub fn resume_with(
+        self,
+        handler: &mut impl OsHandler,
+        print: PrintWriter<'_>,
+    ) -> Result<RunProgress<T>, MontyException> {
+        let result = handler.handle_os_call(self.function, &self.args, &self.kwargs);
+        self.resume(result, print)
+    }
 }
 
 // ---------------------------------------------------------------------------
@@ -540,12 +553,7 @@ impl<T: ResourceTracker> Snapshot<T> {
         let vm_result = match ext_result {
             ExtFunctionResult::Return(obj) => vm.resume(obj),
             ExtFunctionResult::Error(exc) => vm.resume_with_exception(exc.into()),
-            ExtFunctionResult::Future(raw_call_id) => {
-                let call_id = CallId::new(raw_call_id);
-                vm.add_pending_call(call_id);
-                vm.push(Value::ExternalFuture(call_id));
-                vm.run()
-            }
+            ExtFunctionResult::Future(raw_call_id) => vm.resume_with_future(CallId::new(raw_call_id)),
             ExtFunctionResult::NotFound(function_name) => {
                 vm.resume_with_exception(ExtFunctionResult::not_found_exc(&function_name))
             }
@@ -1,4 +1,4 @@
-//! Shared sorting utilities for `sorted()` and `list.sort()`.
+//! Shared sorting utilities for `sorted()`, `list.sort()`, `heapq` and `bisect`.
 //!
 //! Both `sorted()` and `list.sort()` use index-based sorting: they build
 //! a vector of indices `[0, 1, 2, ...]`, sort the indices by comparing the
@@ -6,18 +6,65 @@
 //! the sorted indices.
 //!
 //! This module provides [`sort_indices`] for the comparison step and
-//! [`apply_permutation`] for the in-place rearrangement step.
+//! [`apply_permutation`] for the in-place rearrangement step, combined with key
+//! function evaluation in [`sort_values`]. [`py_lt`] exposes the
+//! same ordering for `heapq` and `bisect`, which only ever ask whether `a < b`,
+//! and `reprlib` uses [`sort_indices`] to order dict keys without moving them.
 
 use std::cmp::Ordering;
 
+use itertools::Itertools;
+
 use crate::{
+    args::ArgValues,
     bytecode::VM,
-    exception_private::{ExcType, RunError},
+    exception_private::{ExcType, RunError, RunResult},
+    heap::HeapGuard,
     resource::ResourceTracker,
     types::PyTrait,
     value::Value,
 };
 
+/// Sorts `items` in place, comparing `key_fn(item)` instead of the items when a key
+/// function is given.
+///
+/// Key values are computed once per item. The sort is stable, and with `reverse`
+/// equal items keep their original order, as in CPython.
+pub fn sort_values(
+    items: &mut [Value],
+    key_fn: Option<&Value>,
+    reverse: bool,
+    vm: &mut VM<'_, '_, impl ResourceTracker>,
+) -> RunResult<()> {
+    // Compute key values if a key function was provided, otherwise we'll sort by the items themselves
+    let mut keys_guard;
+    let (compare_values, vm) = if let Some(f) = key_fn {
+        let keys: Vec<Value> = Vec::with_capacity(items.len());
+        // Use a HeapGuard to ensure that if key function evaluation fails partway through,
+        // we clean up any keys that were successfully computed
+        keys_guard = HeapGuard::new(keys, vm);
+        let (keys, vm) = keys_guard.as_parts_mut();
+        items
+            .iter()
+            .map(|item| {
+                let item = item.clone_with_heap(vm);
+                vm.evaluate_function("sorted() key argument", f, ArgValues::One(item))
+            })
+            .process_results(|keys_iter| keys.extend(keys_iter))?;
+        keys_guard.as_parts()
+    } else {
+        (&*items, vm)
+    };
+
+    // Sort indices by comparing key values (or items themselves if no key)
+    let mut indices: Vec<usize> = (0..compare_values.len()).collect();
+    sort_indices(&mut indices, compare_values, reverse, vm)?;
+
+    // Rearrange items in-place according to the sorted permutation
+    apply_permutation(items, &mut indices);
+    Ok(())
+}
+
 /// Sorts a vector of indices by comparing items at those positions.
 ///
 /// Compares `values[a]` vs `values[b]` using `py_cmp`, optionally reversing
@@ -51,11 +98,7 @@ pub fn sort_indices(
                 }
             }
             Ok(None) => {
-                sort_error = Some(ExcType::type_error(format!(
-                    "'<' not supported between instances of '{}' and '{}'",
-                    values[a].py_type(vm.heap),
-                    values[b].py_type(vm.heap)
-                )));
+                sort_error = Some(lt_not_supported(&values[a], &values[b], vm));
                 Ordering::Equal
             }
             Err(e) => {
@@ -71,6 +114,25 @@ pub fn sort_indices(
     }
 }
 
+/// Returns whether `a < b`, comparing the same way as [`sort_indices`].
+///
+/// Raises `TypeError` when the values are not orderable, like `sorted()` does.
+pub fn py_lt(a: &Value, b: &Value, vm: &mut VM<'_, '_, impl ResourceTracker>) -> RunResult<bool> {
+    match a.py_cmp(b, vm)? {
+        Some(ord) => Ok(ord.is_lt()),
+        None => Err(lt_not_supported(a, b, vm)),
+    }
+}
+
+/// Builds the `TypeError` raised when two values cannot be ordered.
+fn lt_not_supported(a: &Value, b: &Value, vm: &VM<'_, '_, impl ResourceTracker>) -> RunError {
+    ExcType::type_error(format!(
+        "'<' not supported between instances of '{}' and '{}'",
+        a.py_type(vm.heap),
+        b.py_type(vm.heap)
+    ))
+}
+
 /// Rearranges `items` in-place according to a permutation of indices.
 ///
 /// After calling this, `items[i]` will hold the value that was originally at
@@ -81,7 +143,7 @@ pub fn sort_indices(
 /// Each element is moved at most twice (one swap = two moves), so the total
 /// work is O(n) moves. This is at most 2x the moves of building a fresh
 /// `Vec`, but avoids allocating a second buffer.
-pub fn apply_permutation(items: &mut [Value], indices: &mut [usize]) {
+fn apply_permutation(items: &mut [Value], indices: &mut [usize]) {
     for i in 0..items.len() {
         if indices[i] == i {
             continue;
@@ -0,0 +1,653 @@
+//! Python `csv` dialects, and the CSV parser and row formatter they configure.
+//!
+//! The parser is a port of the state machine in CPython's `_csv.c`, so quoting, escaping
+//! and line-ending edge cases (including fields spanning several lines) behave the same.
+//! Rows are parsed eagerly from a complete list of lines rather than lazily per
+//! iteration step.
+//!
+//! Only the built-in `excel`, `excel-tab` and `unix` dialects are available by name;
+//! `register_dialect()` is not supported.
+
+use std::fmt::Write;
+
+use ahash::AHashSet;
+
+use crate::{
+    bytecode::{CallResult, VM},
+    exception_private::{ExcType, RunError, RunResult, SimpleException},
+    heap::{Heap, HeapData, HeapId},
+    intern::StaticStrings,
+    resource::{ResourceError, ResourceTracker},
+    types::{
+        PyTrait, Type,
+        re_pattern::value_to_str,
+        str::{allocate_char, allocate_string},
+        r#type::parse_f64_from_str,
+    },
+    value::{EitherStr, Value},
+};
+
+/// Names of the formatting parameters accepted by `reader()`, `writer()` and friends,
+/// in the order [`Dialect::from_params`] expects them.
+pub(crate) const FMTPARAMS: [&str; 8] = [
+    "delimiter",
+    "doublequote",
+    "escapechar",
+    "lineterminator",
+    "quotechar",
+    "quoting",
+    "skipinitialspace",
+    "strict",
+];
+
+/// Names of the built-in dialects, as returned by `csv.list_dialects()`.
+pub(crate) const DIALECT_NAMES: [&str; 3] = ["excel", "excel-tab", "unix"];
+
+/// Maximum field size accepted by the parser, CPython's default `field_size_limit()`.
+const FIELD_SIZE_LIMIT: usize = 128 * 1024;
+
+/// A `csv` quoting mode, numbered like the `csv.QUOTE_*` constants.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub(crate) enum Quoting {
+    /// `QUOTE_MINIMAL`: quote fields only when they contain special characters.
+    Minimal = 0,
+    /// `QUOTE_ALL`: quote every field.
+    All = 1,
+    /// `QUOTE_NONNUMERIC`: quote non-numbers; the reader converts unquoted fields to float.
+    NonNumeric = 2,
+    /// `QUOTE_NONE`: never quote; special characters must be escaped.
+    None = 3,
+    /// `QUOTE_STRINGS`: quote strings; the reader converts unquoted fields to float or `None`.
+    Strings = 4,
+    /// `QUOTE_NOTNULL`: quote everything but `None`; the reader reads empty unquoted fields as `None`.
+    NotNull = 5,
+}
+
+impl Quoting {
+    /// All quoting modes paired with their `csv` module constant names.
+    pub const ALL: [(StaticStrings, Self); 6] = [
+        (StaticStrings::QuoteMinimal, Self::Minimal),
+        (StaticStrings::QuoteAll, Self::All),
+        (StaticStrings::QuoteNonnumeric, Self::NonNumeric),
+        (StaticStrings::QuoteNone, Self::None),
+        (StaticStrings::QuoteStrings, Self::Strings),
+        (StaticStrings::QuoteNotnull, Self::NotNull),
+    ];
+
+    fn from_i64(value: i64) -> Option<Self> {
+        Self::ALL
+            .iter()
+            .find(|(_, quoting)| *quoting as i64 == value)
+            .map(|(_, quoting)| *quoting)
+    }
+}
+
+/// A CSV dialect: the formatting parameters shared by readers and writers.
+///
+/// Exposed to Python as `_csv.Dialect` objects, e.g. `csv.excel` or the result of
+/// `csv.Sniffer().sniff()`, with the parameters as read-only attributes.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub(crate) struct Dialect {
+    pub delimiter: char,
+    pub quotechar: Option<char>,
+    pub escapechar: Option<char>,
+    pub doublequote: bool,
+    pub skipinitialspace: bool,
+    pub lineterminator: String,
+    pub quoting: Quoting,
+    pub strict: bool,
+}
+
+impl Default for Dialect {
+    /// The `excel` dialect.
+    fn default() -> Self {
+        Self {
+            delimiter: ',',
+            quotechar: Some('"'),
+            escapechar: None,
+            doublequote: true,
+            skipinitialspace: false,
+            lineterminator: "\r\n".to_owned(),
+            quoting: Quoting::Minimal,
+            strict: false,
+        }
+    }
+}
+
+/// A parsed field: a string, or a float / `None` for the numeric quoting modes.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum Field {
+    Str(String),
+    Float(f64),
+    None,
+}
+
+impl Field {
+    /// Allocates the field as a Python value.
+    pub fn into_value(self, heap: &mut Heap<impl ResourceTracker>) -> RunResult<Value> {
+        match self {
+            Self::Str(s) => allocate_string(s, heap),
+            Self::Float(f) => Ok(Value::Float(f)),
+            Self::None => Ok(Value::None),
+        }
+    }
+}
+
+/// A field to write: its text (or `None`) and whether the quoting mode asks for quotes.
+pub(crate) struct OutField {
+    pub text: Option<String>,
+    pub quoted: bool,
+}
+
+impl Dialect {
+    /// Looks up a built-in dialect by name.
+    pub fn builtin(name: &str) -> Option<Self> {
+        match name {
+            "excel" => Some(Self::default()),
+            "excel-tab" => Some(Self {
+                delimiter: '\t',
+                ..Self::default()
+            }),
+            "unix" => Some(Self {
+                lineterminator: "\n".to_owned(),
+                quoting: Quoting::All,
+                ..Self::default()
+            }),
+            _ => None,
+        }
+    }
+
+    /// Builds a dialect from a `dialect` argument and the formatting parameters.
+    ///
+    /// `dialect` is a built-in dialect name or a `csv.Dialect` object and defaults to
+    /// `excel`; `fmtparams` holds the values bound to [`FMTPARAMS`], which override it.
+    pub fn from_params(
+        dialect: Option<&Value>,
+        fmtparams: &[Option<Value>; FMTPARAMS.len()],
+        vm: &VM<'_, '_, impl ResourceTracker>,
+    ) -> RunResult<Self> {
+        let mut result = match dialect {
+            None => Self::default(),
+            Some(value) => Self::from_value(value, vm)?,
+        };
+        let [
+            delimiter,
+            doublequote,
+            escapechar,
+            lineterminator,
+            quotechar,
+            quoting,
+            skipinitialspace,
+            strict,
+        ] = fmtparams;
+
+        if let Some(value) = delimiter {
+            result.delimiter = char_param("delimiter", value, false, vm)?.expect("None is not allowed");
+        }
+        if let Some(value) = doublequote {
+            result.doublequote = value.py_bool(vm);
+        }
+        if let Some(value) = escapechar {
+            result.escapechar = char_param("escapechar", value, true, vm)?;
+        }
+        if let Some(value) = lineterminator {
+            if !value.is_str(vm.heap) {
+                return Err(ExcType::type_error("\"lineterminator\" must be a string"));
+            }
+            result.lineterminator = value_to_str(value, vm.heap, vm.interns)?.into_owned();
+        }
+        if let Some(value) = quotechar {
+            result.quotechar = char_param("quotechar", value, true, vm)?;
+        }
+        match quoting {
+            Some(Value::Int(quoting)) => {
+                result.quoting =
+                    Quoting::from_i64(*quoting).ok_or_else(|| ExcType::type_error("bad \"quoting\" value"))?;
+            }
+            Some(_) => return Err(ExcType::type_error("\"quoting\" must be an integer")),
+            // Clearing the quote character without choosing a quoting mode disables quoting.
+            None if matches!(quotechar, Some(Value::None)) => result.quoting = Quoting::None,
+            None => {}
+        }
+        if let Some(value) = skipinitialspace {
+            result.skipinitialspace = value.py_bool(vm);
+        }
+        if let Some(value) = strict {
+            result.strict = value.py_bool(vm);
+        }
+        result.validate()?;
+        Ok(result)
+    }
+
+    /// Resolves a `dialect` argument: a built-in dialect name or a `csv.Dialect` object.
+    fn from_value(value: &Value, vm: &VM<'_, '_, impl ResourceTracker>) -> RunResult<Self> {
+        if let Value::Ref(id) = value
+            && let HeapData::CsvDialect(dialect) = vm.heap.get(*id)
+        {
+            return Ok(dialect.clone());
+        }
+        if value.is_str(vm.heap) {
+            let name = value_to_str(value, vm.heap, vm.interns)?;
+            return Self::builtin(&name).ok_or_else(|| ExcType::csv_error("unknown dialect"));
+        }
+        Err(ExcType::type_error(format!(
+            "dialect must be a str or csv.Dialect, not {}",
+            value.py_type(vm.heap)
+        )))
+    }
+
+    /// Checks the combination of parameters, raising CPython's errors for invalid ones.
+    pub fn validate(&self) -> RunResult<()> {
+        if self.quoting != Quoting::None && self.quotechar.is_none() {
+            return Err(ExcType::type_error("quotechar must be set if quoting enabled"));
+        }
+        let bad_value = |names: &str| -> RunResult<()> {
+            Err(SimpleException::new_msg(ExcType::ValueError, format!("bad {names} value")).into())
+        };
+        let chars = [
+            ("delimiter", Some(self.delimiter)),
+            ("escapechar", self.escapechar),
+            ("quotechar", self.quotechar),
+        ];
+        for (name, c) in chars {
+            // Only the delimiter may be a space when leading spaces are skipped.
+            if let Some(c) = c
+                && (c == '\r' || c == '\n' || (c == ' ' && self.skipinitialspace && name != "delimiter"))
+            {
+                return bad_value(name);
+            }
+        }
+        for (i, (name, c)) in chars.iter().enumerate() {
+            if let Some(c) = c {
+                if let Some((other, _)) = chars[i + 1..].iter().find(|(_, other)| *other == Some(*c)) {
+                    return bad_value(&format!("{name} or {other}"));
+                }
+                if self.lineterminator.contains(*c) {
+                    return bad_value(&format!("{name} or lineterminator"));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Parses `lines` into rows.
+    ///
+    /// Each line is fed to the parser followed by an end-of-line marker, exactly as
+    /// CPython's reader does per iteration step, so quoted fields may span lines.
+    pub fn parse(&self, lines: &[String], heap: &Heap<impl ResourceTracker>) -> RunResult<Vec<Vec<Field>>> {
+        let mut parser = Parser::new(self);
+        let mut rows = Vec::new();
+        for line in lines {
+            heap.check_time()?;
+            for c in line.chars() {
+                parser.process(Some(c))?;
+            }
+            parser.process(None)?;
+            if parser.state == State::StartRecord {
+                rows.push(std::mem::take(&mut parser.fields));
+            }
+        }
+        // Input ended inside a record: keep whatever was read, unless strict.
+        if !parser.field.is_empty() || parser.state == State::InQuotedField {
+            if self.strict {
+                return Err(ExcType::csv_error("unexpected end of data"));
+            }
+            parser.save_field()?;
+            rows.push(parser.fields);
+        }
+        Ok(rows)
+    }
+
+    /// Formats one record, including the line terminator.
+    pub fn format_row(&self, fields: &[OutField]) -> RunResult<String> {
+        let mut record = String::new();
+        for (i, field) in fields.iter().enumerate() {
+            if i > 0 {
+                record.push(self.delimiter);
+            }
+            let text = field.text.as_deref().unwrap_or_default();
+            let mut quoted = field.quoted;
+            // An unquoted empty field would be skipped as leading space when read back.
+            if !quoted && text.is_empty() && self.delimiter == ' ' && self.skipinitialspace {
+                if self.quoting == Quoting::None
+                    || (field.text.is_none() && matches!(self.quoting, Quoting::Strings | Quoting::NotNull))
+                {
+                    return Err(ExcType::csv_error(
+                        "empty field must be quoted if delimiter is a space and skipinitialspace is true",
+                    ));
+                }
+                quoted = true;
+            }
+            self.append_field(&mut record, text, quoted)?;
+        }
+        // A record consisting of one empty field would read back as an empty row.
+        if fields.len() == 1 && record.is_empty() {
+            if self.quoting == Quoting::None {
+                return Err(ExcType::csv_error("single empty field record must be quoted"));
+            }
+            self.append_field(&mut record, "", true)?;
+        }
+        record.push_str(&self.lineterminator);
+        Ok(record)
+    }
+
+    /// Appends one field, quoting or escaping special characters as the dialect requires.
+    fn append_field(&self, record: &mut String, text: &str, quoted: bool) -> RunResult<()> {
+        let mut quoted = quoted;
+        let mut body = String::with_capacity(text.len());
+        for c in text.chars() {
+            let special = c == self.delimiter
+                || Some(c) == self.escapechar
+                || Some(c) == self.quotechar
+                || c == '\n'
+                || c == '\r'
+                || self.lineterminator.contains(c);
+            if special {
+                let mut want_escape = false;
+                if self.quoting == Quoting::None {
+                    want_escape = true;
+                } else {
+                    if Some(c) == self.quotechar {
+                        if self.doublequote {
+                            body.push(c);
+                        } else {
+                            want_escape = true;
+                        }
+                    } else if Some(c) == self.escapechar {
+                        want_escape = true;
+                    }
+                    if !want_escape {
+                        quoted = true;
+                    }
+                }
+                if want_escape {
+                    let Some(escapechar) = self.escapechar else {
+                        return Err(ExcType::csv_error("need to escape, but no escapechar set"));
+                    };
+                    body.push(escapechar);
+                }
+            }
+            body.push(c);
+        }
+        let quotechar = self.quotechar.filter(|_| quoted);
+        record.extend(quotechar);
+        record.push_str(&body);
+        record.extend(quotechar);
+        Ok(())
+    }
+}
+
+/// Reads a parameter that must be a single character, or `None` if `allow_none`.
+fn char_param(
+    name: &str,
+    value: &Value,
+    allow_none: bool,
+    vm: &VM<'_, '_, impl ResourceTracker>,
+) -> RunResult<Option<char>> {
+    if allow_none && matches!(value, Value::None) {
+        return Ok(None);
+    }
+    if !value.is_str(vm.heap) {
+        let expected = if allow_none { "string or None" } else { "string" };
+        return Err(ExcType::type_error(format!(
+            "\"{name}\" must be {expected}, not {}",
+            value.py_type(vm.heap)
+        )));
+    }
+    let s = value_to_str(value, vm.heap, vm.interns)?;
+    let mut chars = s.chars();
+    match (chars.next(), chars.next()) {
+        (Some(c), None) => Ok(Some(c)),
+        _ => Err(ExcType::type_error(format!("\"{name}\" must be a 1-character string"))),
+    }
+}
+
+/// States of the CSV parser, named after those in CPython's `_csv.c`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum State {
+    StartRecord,
+    StartField,
+    EscapedChar,
+    InField,
+    InQuotedField,
+    EscapeInQuotedField,
+    QuoteInQuotedField,
+    EatCrnl,
+    AfterEscapedCrnl,
+}
+
+/// Incremental CSV parser fed one character at a time, with `None` marking end of line.
+struct Parser<'d> {
+    dialect: &'d Dialect,
+    state: State,
+    /// The field being read.
+    field: String,
+    /// Number of characters in `field`.
+    field_len: usize,
+    /// Whether the field being read had no opening quote.
+    unquoted_field: bool,
+    /// Fields of the record being read.
+    fields: Vec<Field>,
+}
+
+impl<'d> Parser<'d> {
+    fn new(dialect: &'d Dialect) -> Self {
+        Self {
+            dialect,
+            state: State::StartRecord,
+            field: String::new(),
+            field_len: 0,
+            unquoted_field: true,
+            fields: Vec::new(),
+        }
+    }
+
+    /// Ends the current field, converting it as the quoting mode requires.
+    fn save_field(&mut self) -> RunResult<()> {
+        let quoting = self.dialect.quoting;
+        let text = std::mem::take(&mut self.field);
+        let field =
+            if self.unquoted_field && self.field_len == 0 && matches!(quoting, Quoting::NotNull | Quoting::Strings) {
+                Field::None
+            } else if self.unquoted_field
+                && self.field_len != 0
+                && matches!(quoting, Quoting::NonNumeric | Quoting::Strings)
+            {
+                Field::Float(parse_f64_from_str(&text)?)
+            } else {
+                Field::Str(text)
+            };
+        self.fields.push(field);
+        self.field_len = 0;
+        self.unquoted_field = true;
+        Ok(())
+    }
+
+    fn add_char(&mut self, c: char) -> RunResult<()> {
+        if self.field_len >= FIELD_SIZE_LIMIT {
+            return Err(ExcType::csv_error(format!(
+                "field larger than field limit ({FIELD_SIZE_LIMIT})"
+            )));
+        }
+        self.field.push(c);
+        self.field_len += 1;
+        Ok(())
+    }
+
+    /// Ends the field at a line break, then either finishes the record or skips the rest
+    /// of the line ending.
+    fn end_of_line(&mut self, c: Option<char>) -> RunResult<()> {
+        self.save_field()?;
+        self.state = if c.is_none() {
+            State::StartRecord
+        } else {
+            State::EatCrnl
+        };
+        Ok(())
+    }
+
+    /// Processes one character, or the end of a line when `c` is `None`.
+    fn process(&mut self, c: Option<char>) -> RunResult<()> {
+        let dialect = self.dialect;
+        let is_newline = matches!(c, Some('\n' | '\r'));
+        let is_quote = c.is_some() && c == dialect.quotechar && dialect.quoting != Quoting::None;
+        let is_escape = c.is_some() && c == dialect.escapechar;
+        let is_delimiter = c == Some(dialect.delimiter);
+
+        match self.state {
+            State::StartRecord if c.is_none() => {}
+            State::StartRecord if is_newline => self.state = State::EatCrnl,
+            State::StartRecord | State::StartField => {
+                self.state = State::StartField;
+                if is_newline || c.is_none() {
+                    self.end_of_line(c)?;
+                } else if is_quote {
+                    self.unquoted_field = false;
+                    self.state = State::InQuotedField;
+                } else if is_escape {
+                    self.state = State::EscapedChar;
+                } else if c == Some(' ') && dialect.skipinitialspace {
+                    // Ignore spaces at the start of a field.
+                } else if is_delimiter {
+                    self.save_field()?;
+                } else if let Some(c) = c {
+                    self.add_char(c)?;
+                    self.state = State::InField;
+                }
+            }
+            State::EscapedChar => {
+                if is_newline {
+                    self.add_char(c.expect("newline is a character"))?;
+                    self.state = State::AfterEscapedCrnl;
+                } else {
+                    self.add_char(c.unwrap_or('\n'))?;
+                    self.state = State::InField;
+                }
+            }
+            State::AfterEscapedCrnl if c.is_none() => {}
+            State::AfterEscapedCrnl | State::InField => {
+                self.state = State::InField;
+                if is_newline || c.is_none() {
+                    self.end_of_line(c)?;
+                } else if is_escape {
+                    self.state = State::EscapedChar;
+                } else if is_delimiter {
+                    self.save_field()?;
+                    self.state = State::StartField;
+                } else if let Some(c) = c {
+                    self.add_char(c)?;
+                }
+            }
+            State::InQuotedField => {
+                if c.is_none() {
+                    // A line break inside quotes is part of the field, carried by the line itself.
+                } else if is_escape {
+                    self.state = State::EscapeInQuotedField;
+                } else if is_quote {
+                    self.state = if dialect.doublequote {
+                        State::QuoteInQuotedField
+                    } else {
+                        State::InField
+                    };
+                } else if let Some(c) = c {
+                    self.add_char(c)?;
+                }
+            }
+            State::EscapeInQuotedField => {
+                self.add_char(c.unwrap_or('\n'))?;
+                self.state = State::InQuotedField;
+            }
+            State::QuoteInQuotedField => {
+                if is_quote {
+                    // A doubled quote inside a quoted field is a literal quote.
+                    self.add_char(c.expect("quote is a character"))?;
+                    self.state = State::InQuotedField;
+                } else if is_delimiter {
+                    self.save_field()?;
+                    self.state = State::StartField;
+                } else if is_newline || c.is_none() {
+                    self.end_of_line(c)?;
+                } else if !dialect.strict {
+                    self.add_char(c.expect("checked above"))?;
+                    self.state = State::InField;
+                } else {
+                    return Err(ExcType::csv_error(format!(
+                        "'{}' expected after '{}'",
+                        dialect.delimiter,
+                        dialect.quotechar.expect("quoting implies a quotechar")
+                    )));
+                }
+            }
+            State::EatCrnl => {
+                if is_newline {
+                } else if c.is_none() {
+                    self.state = State::StartRecord;
+                } else {
+                    return Err(ExcType::csv_error(
+                        "new-line character seen in unquoted field - do you need to open the file with newline=''?",
+                    ));
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+impl PyTrait for Dialect {
+    fn py_type(&self, _heap: &Heap<impl ResourceTracker>) -> Type {
+        Type::CsvDialect
+    }
+
+    fn py_len(&self, _vm: &VM<'_, '_, impl ResourceTracker>) -> Option<usize> {
+        None
+    }
+
+    fn py_eq(&self, _other: &Self, _vm: &mut VM<'_, '_, impl ResourceTracker>) -> Result<bool, ResourceError> {
+        // Dialects compare by identity (handled at the Value level)
+        Ok(false)
+    }
+
+    fn py_dec_ref_ids(&mut self, _stack: &mut Vec<HeapId>) {
+        // No heap references — all parameters are owned.
+    }
+
+    fn py_bool(&self, _vm: &VM<'_, '_, impl ResourceTracker>) -> bool {
+        true
+    }
+
+    fn py_repr_fmt(
+        &self,
+        f: &mut impl Write,
+        _vm: &VM<'_, '_, impl ResourceTracker>,
+        _heap_ids: &mut AHashSet<HeapId>,
+    ) -> std::fmt::Result {
+        f.write_str("<_csv.Dialect object>")
+    }
+
+    fn py_estimate_size(&self) -> usize {
+        std::mem::size_of::<Self>() + self.lineterminator.len()
+    }
+
+    fn py_getattr(&self, attr: &EitherStr, vm: &mut VM<'_, '_, impl ResourceTracker>) -> RunResult<Option<CallResult>> {
+        let optional_char = |c: Option<char>, heap: &mut Heap<_>| -> Result<Value, ResourceError> {
+            c.map_or(Ok(Value::None), |c| allocate_char(c, heap))
+        };
+        let value = match attr.static_string() {
+            Some(StaticStrings::Delimiter) => allocate_char(self.delimiter, vm.heap)?,
+            Some(StaticStrings::Quotechar) => optional_char(self.quotechar, vm.heap)?,
+            Some(StaticStrings::Escapechar) => optional_char(self.escapechar, vm.heap)?,
+            Some(StaticStrings::Doublequote) => Value::Bool(self.doublequote),
+            Some(StaticStrings::Skipinitialspace) => Value::Bool(self.skipinitialspace),
+            Some(StaticStrings::Lineterminator) => allocate_string(self.lineterminator.clone(), vm.heap)?,
+            Some(StaticStrings::Quoting) => Value::Int(self.quoting as i64),
+            Some(StaticStrings::Strict) => Value::Bool(self.strict),
+            _ => return Err(ExcType::attribute_error(Type::CsvDialect, attr.as_str(vm.interns))),
+        };
+        Ok(Some(CallResult::Value(value)))
+    }
+}
+
+/// Allocates `dialect` as a `_csv.Dialect` object.
+pub(crate) fn allocate_dialect(dialect: Dialect, heap: &mut Heap<impl ResourceTracker>) -> Result<Value, RunError> {
+    Ok(Value::Ref(heap.allocate(HeapData::CsvDialect(Box::new(dialect)))?))
+}
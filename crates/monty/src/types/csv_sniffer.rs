@@ -0,0 +1,464 @@
+//! Python `csv.Sniffer` type implementation.
+//!
+//! A port of CPython's pure-Python `Sniffer`: `sniff()` guesses a dialect from a sample,
+//! first from quoted fields and then from per-line character frequencies, and
+//! `has_header()` votes on whether the first row looks like column names. Both follow
+//! CPython's heuristics step by step so they reach the same conclusions.
+
+use std::fmt::Write;
+
+use ahash::AHashSet;
+
+use crate::{
+    args::ArgValues,
+    bytecode::{CallResult, VM},
+    defer_drop,
+    exception_private::{ExcType, RunResult},
+    heap::{DropWithHeap, Heap, HeapData, HeapId},
+    intern::StaticStrings,
+    modules::re::{DOTALL, MULTILINE, should_escape},
+    resource::{ResourceError, ResourceTracker},
+    types::{
+        PyTrait, RePattern, Type,
+        csv_dialect::{Dialect, Field, allocate_dialect},
+        re_pattern::{backtrack_budget, value_to_str},
+    },
+    value::{EitherStr, Value},
+};
+
+/// Patterns finding a quoted field with its surrounding delimiter, most specific first.
+const QUOTE_PATTERNS: [&str; 4] = [
+    r#"(?P<delim>[^\w\n"'])(?P<space> ?)(?P<quote>["']).*?(?P=quote)(?P=delim)"#,
+    r#"(?:^|\n)(?P<quote>["']).*?(?P=quote)(?P<delim>[^\w\n"'])(?P<space> ?)"#,
+    r#"(?P<delim>[^\w\n"'])(?P<space> ?)(?P<quote>["']).*?(?P=quote)(?:$|\n)"#,
+    r#"(?:^|\n)(?P<quote>["']).*?(?P=quote)(?:$|\n)"#,
+];
+
+/// Delimiters preferred when several characters are equally consistent.
+const PREFERRED_DELIMITERS: [char; 5] = [',', '\t', ';', ' ', ':'];
+
+/// Number of rows after the first that `has_header()` examines.
+const HEADER_CHECK_ROWS: usize = 21;
+
+/// A `csv.Sniffer` instance; it holds no state.
+#[derive(Debug, Clone, Default, serde::Serialize, serde::Deserialize)]
+pub(crate) struct CsvSniffer;
+
+impl CsvSniffer {
+    /// `Sniffer()` constructor.
+    pub fn init(vm: &mut VM<'_, '_, impl ResourceTracker>, args: ArgValues) -> RunResult<Value> {
+        args.check_zero_args("Sniffer", vm.heap)?;
+        Ok(Value::Ref(vm.heap.allocate(HeapData::CsvSniffer(Self))?))
+    }
+}
+
+/// Guesses the dialect of `sample`, optionally restricting the delimiter to `delimiters`.
+pub(crate) fn sniff(sample: &str, delimiters: Option<&str>, tracker: &impl ResourceTracker) -> RunResult<Dialect> {
+    let (quotechar, doublequote, mut delimiter, mut skipinitialspace) =
+        guess_quote_and_delimiter(sample, delimiters, tracker)?;
+    if delimiter.is_none() {
+        (delimiter, skipinitialspace) = guess_delimiter(sample, delimiters);
+    }
+    let Some(delimiter) = delimiter else {
+        return Err(ExcType::csv_error("Could not determine delimiter"));
+    };
+    Ok(Dialect {
+        delimiter,
+        quotechar: Some(quotechar.unwrap_or('"')),
+        doublequote,
+        skipinitialspace,
+        ..Dialect::default()
+    })
+}
+
+/// Looks for text enclosed in identical quotes surrounded by the same delimiter.
+///
+/// Returns `(quotechar, doublequote, delimiter, skipinitialspace)`; the most frequent
+/// quote and delimiter win, with ties going to the first seen.
+fn guess_quote_and_delimiter(
+    data: &str,
+    delimiters: Option<&str>,
+    tracker: &impl ResourceTracker,
+) -> RunResult<(Option<char>, bool, Option<char>, bool)> {
+    let mut matches = Vec::new();
+    for pattern in QUOTE_PATTERNS {
+        let mut regex = RePattern::compile(pattern.to_owned(), DOTALL | MULTILINE, backtrack_budget(tracker)?)?;
+        matches = regex.named_groups(data, ["quote", "delim", "space"], tracker)?;
+        if !matches.is_empty() {
+            break;
+        }
+    }
+    if matches.is_empty() {
+        return Ok((None, false, None, false));
+    }
+
+    let mut quotes: Vec<(char, usize)> = Vec::new();
+    let mut delims: Vec<(char, usize)> = Vec::new();
+    let mut spaces = 0;
+    for [quote, delim, space] in &matches {
+        if let Some(quote) = quote.as_deref().and_then(|q| q.chars().next()) {
+            count_char(&mut quotes, quote);
+        }
+        let Some(delim) = delim else {
+            continue;
+        };
+        if let Some(delim) = delim.chars().next()
+            && delimiters.is_none_or(|delimiters| delimiters.contains(delim))
+        {
+            count_char(&mut delims, delim);
+        }
+        if space.as_deref().is_some_and(|space| !space.is_empty()) {
+            spaces += 1;
+        }
+    }
+
+    let quotechar = most_common(&quotes).expect("every match has a quote");
+    let (delimiter, skipinitialspace) = match most_common(&delims) {
+        Some(delim) => {
+            let count = delims.iter().find(|(c, _)| *c == delim).map_or(0, |(_, count)| *count);
+            (Some(delim), count == spaces)
+        }
+        // A single column of quoted data.
+        None => (None, false),
+    };
+
+    // An extra quote between delimiters means quotes are doubled inside fields.
+    let delim = delimiter.map(escape_char).unwrap_or_default();
+    let dq_pattern =
+        format!(r"(({delim})|^)\W*{quotechar}[^{delim}\n]*{quotechar}[^{delim}\n]*{quotechar}\W*(({delim})|$)");
+    let mut dq_regex = RePattern::compile(dq_pattern, MULTILINE, backtrack_budget(tracker)?)?;
+    let doublequote = dq_regex.is_match(data, tracker)?;
+
+    Ok((Some(quotechar), doublequote, delimiter, skipinitialspace))
+}
+
+/// Finds the character occurring the same number of times on (nearly) every line.
+///
+/// Lines are examined in chunks of ten until exactly one candidate stands out, using
+/// the mode of each character's per-line frequency and a consistency threshold that
+/// is relaxed from 100% down to 90%. Returns `(delimiter, skipinitialspace)`.
+fn guess_delimiter(data: &str, delimiters: Option<&str>) -> (Option<char>, bool) {
+    let lines: Vec<&str> = data.split('\n').filter(|line| !line.is_empty()).collect();
+    let skipinitialspace = |delim: char| {
+        let first = lines[0];
+        first.matches(delim).count() == first.matches(&format!("{delim} ")).count()
+    };
+
+    let chunk_length = lines.len().min(10);
+    let mut iteration = 0;
+    // Per character (7-bit ASCII), how many lines had each frequency, in order of first sight.
+    let mut char_frequency: Vec<Vec<(usize, usize)>> = vec![Vec::new(); 127];
+    // Per character, the (frequency, adjusted count) mode, in order of first insertion.
+    let mut modes: Vec<(char, (usize, i64))> = Vec::new();
+    let mut delims: Vec<(char, (usize, i64))> = Vec::new();
+    let (mut start, mut end) = (0, chunk_length);
+    while start < lines.len() {
+        iteration += 1;
+        for line in &lines[start..end.min(lines.len())] {
+            let mut counts = [0usize; 127];
+            for c in line.chars() {
+                if let Some(count) = counts.get_mut(c as usize) {
+                    *count += 1;
+                }
+            }
+            for (meta_frequency, freq) in char_frequency.iter_mut().zip(counts) {
+                match meta_frequency.iter_mut().find(|(f, _)| *f == freq) {
+                    Some((_, lines_with_freq)) => *lines_with_freq += 1,
+                    None => meta_frequency.push((freq, 1)),
+                }
+            }
+        }
+
+        for (code, items) in char_frequency.iter().enumerate() {
+            let c = char::from(u8::try_from(code).expect("ASCII code"));
+            let mode = match items.as_slice() {
+                [] | [(0, _)] => continue,
+                [(freq, count)] => (*freq, to_i64(*count)),
+                _ => {
+                    // The most common frequency, less the lines having any other frequency.
+                    let mut best = 0;
+                    for (i, item) in items.iter().enumerate() {
+                        if item.1 > items[best].1 {
+                            best = i;
+                        }
+                    }
+                    let others: usize = items
+                        .iter()
+                        .enumerate()
+                        .filter(|(i, _)| *i != best)
+                        .map(|(_, item)| item.1)
+                        .sum();
+                    (items[best].0, to_i64(items[best].1) - to_i64(others))
+                }
+            };
+            match modes.iter_mut().find(|(k, _)| *k == c) {
+                Some((_, existing)) => *existing = mode,
+                None => modes.push((c, mode)),
+            }
+        }
+
+        // (rows of consistent data) / (number of rows) = 100%
+        #[expect(clippy::cast_precision_loss, reason = "line counts are far below 2^52")]
+        let total = (chunk_length * iteration).min(lines.len()) as f64;
+        let mut consistency = 1.0;
+        while delims.is_empty() && consistency >= 0.9 {
+            for &(k, (freq, count)) in &modes {
+                #[expect(clippy::cast_precision_loss, reason = "line counts are far below 2^52")]
+                let ratio = count as f64 / total;
+                if freq > 0
+                    && count > 0
+                    && ratio >= consistency
+                    && delimiters.is_none_or(|delimiters| delimiters.contains(k))
+                {
+                    match delims.iter_mut().find(|(d, _)| *d == k) {
+                        Some((_, existing)) => *existing = (freq, count),
+                        None => delims.push((k, (freq, count))),
+                    }
+                }
+            }
+            consistency -= 0.01;
+        }
+
+        if let [(delim, _)] = delims.as_slice() {
+            return (Some(*delim), skipinitialspace(*delim));
+        }
+
+        // Analyze another chunk of lines.
+        start = end;
+        end += chunk_length;
+    }
+
+    if delims.is_empty() {
+        return (None, false);
+    }
+    // More than one candidate: fall back to the preferred list, then to the most dominant.
+    if let Some(delim) = PREFERRED_DELIMITERS
+        .into_iter()
+        .find(|d| delims.iter().any(|(k, _)| k == d))
+    {
+        return (Some(delim), skipinitialspace(delim));
+    }
+    let (delim, _) = delims
+        .iter()
+        .max_by_key(|(k, v)| (*v, *k))
+        .expect("delims is not empty");
+    (Some(*delim), skipinitialspace(*delim))
+}
+
+/// Votes on whether the first row of `sample` is a header.
+///
+/// Each column whose values after the first row are consistently numeric, or
+/// consistently of one length, votes for a header if the first row differs.
+pub(crate) fn has_header(sample: &str, heap: &Heap<impl ResourceTracker>) -> RunResult<bool> {
+    let dialect = sniff(sample, None, heap.tracker())?;
+    dialect.validate()?;
+    let lines: Vec<String> = sample.split_inclusive('\n').map(str::to_owned).collect();
+    let rows = dialect.parse(&lines, heap)?;
+    let mut rows = rows.into_iter().map(|row| {
+        row.into_iter()
+            .map(|field| match field {
+                Field::Str(s) => s,
+                _ => unreachable!("sniffed dialects use QUOTE_MINIMAL"),
+            })
+            .collect::<Vec<_>>()
+    });
+    let Some(header) = rows.next() else {
+        return Err(ExcType::StopIteration.into());
+    };
+
+    let columns = header.len();
+    // Column index to its type so far: `None` until the first row is seen; removed once inconsistent.
+    let mut column_types: Vec<(usize, Option<ColumnType>)> = (0..columns).map(|col| (col, None)).collect();
+    for row in rows.take(HEADER_CHECK_ROWS) {
+        if row.len() != columns {
+            // Skip rows with an irregular number of columns.
+            continue;
+        }
+        column_types.retain_mut(|(col, column_type)| {
+            let this_type = ColumnType::of(&row[*col]);
+            match column_type {
+                None => {
+                    *column_type = Some(this_type);
+                    true
+                }
+                Some(existing) => *existing == this_type,
+            }
+        });
+    }
+
+    let mut votes = 0i64;
+    for (col, column_type) in &column_types {
+        let header_matches = match column_type {
+            Some(ColumnType::Length(len)) => header[*col].chars().count() == *len,
+            Some(ColumnType::Complex) => is_complex_literal(&header[*col]),
+            // A column whose type was never determined cannot be called with the header value.
+            None => false,
+        };
+        votes += if header_matches { -1 } else { 1 };
+    }
+    Ok(votes > 0)
+}
+
+/// The type `has_header()` infers for a column value.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ColumnType {
+    /// The value parses as a Python `complex` number.
+    Complex,
+    /// Any other value, compared by its length in characters.
+    Length(usize),
+}
+
+impl ColumnType {
+    fn of(value: &str) -> Self {
+        if is_complex_literal(value) {
+            Self::Complex
+        } else {
+            Self::Length(value.chars().count())
+        }
+    }
+}
+
+/// Returns whether `complex(value)` would succeed in Python.
+fn is_complex_literal(value: &str) -> bool {
+    let mut s = value.trim();
+    if let Some(inner) = s.strip_prefix('(').and_then(|s| s.strip_suffix(')')) {
+        s = inner.trim();
+    }
+    if s.is_empty() || s.chars().any(char::is_whitespace) {
+        return false;
+    }
+    let Some(body) = s.strip_suffix(['j', 'J']) else {
+        return is_float_literal(s);
+    };
+    // Split a real part from the imaginary part at the last sign not part of an exponent.
+    let split = body
+        .char_indices()
+        .filter(|&(i, c)| i > 0 && matches!(c, '+' | '-') && !body[..i].ends_with(['e', 'E']))
+        .last()
+        .map(|(i, _)| i);
+    let (real, imag) = match split {
+        Some(i) => (Some(&body[..i]), &body[i..]),
+        None => (None, body),
+    };
+    real.is_none_or(is_float_literal) && (matches!(imag, "" | "+" | "-") || is_float_literal(imag))
+}
+
+/// Returns whether `value` is a float literal as accepted by Python's `float()`, without
+/// surrounding whitespace.
+fn is_float_literal(value: &str) -> bool {
+    let unsigned = value.strip_prefix(['+', '-']).unwrap_or(value);
+    if matches!(unsigned.to_ascii_lowercase().as_str(), "inf" | "infinity" | "nan") {
+        return true;
+    }
+    // Underscores are only allowed between digits.
+    let bytes = unsigned.as_bytes();
+    for (i, b) in bytes.iter().enumerate() {
+        if *b == b'_' && !(i > 0 && bytes[i - 1].is_ascii_digit() && bytes.get(i + 1).is_some_and(u8::is_ascii_digit)) {
+            return false;
+        }
+    }
+    let digits: String = unsigned.chars().filter(|c| *c != '_').collect();
+    digits.starts_with(|c: char| c.is_ascii_digit() || c == '.') && digits.parse::<f64>().is_ok()
+}
+
+/// Increments the count for `c`, keeping characters in order of first sight.
+fn count_char(counts: &mut Vec<(char, usize)>, c: char) {
+    match counts.iter_mut().find(|(k, _)| *k == c) {
+        Some((_, count)) => *count += 1,
+        None => counts.push((c, 1)),
+    }
+}
+
+/// Returns the character with the highest count, the first seen on ties.
+fn most_common(counts: &[(char, usize)]) -> Option<char> {
+    let mut best: Option<(char, usize)> = None;
+    for &(c, count) in counts {
+        if best.is_none_or(|(_, best_count)| count > best_count) {
+            best = Some((c, count));
+        }
+    }
+    best.map(|(c, _)| c)
+}
+
+/// Escapes a character for use in a Python regex, as `re.escape()` would.
+fn escape_char(c: char) -> String {
+    if should_escape(c) {
+        format!("\\{c}")
+    } else {
+        c.to_string()
+    }
+}
+
+fn to_i64(count: usize) -> i64 {
+    i64::try_from(count).expect("line count fits in i64")
+}
+
+impl PyTrait for CsvSniffer {
+    fn py_type(&self, _heap: &Heap<impl ResourceTracker>) -> Type {
+        Type::CsvSniffer
+    }
+
+    fn py_len(&self, _vm: &VM<'_, '_, impl ResourceTracker>) -> Option<usize> {
+        None
+    }
+
+    fn py_eq(&self, _other: &Self, _vm: &mut VM<'_, '_, impl ResourceTracker>) -> Result<bool, ResourceError> {
+        // Sniffers compare by identity (handled at the Value level)
+        Ok(false)
+    }
+
+    fn py_dec_ref_ids(&mut self, _stack: &mut Vec<HeapId>) {
+        // No heap references.
+    }
+
+    fn py_bool(&self, _vm: &VM<'_, '_, impl ResourceTracker>) -> bool {
+        true
+    }
+
+    fn py_repr_fmt(
+        &self,
+        f: &mut impl Write,
+        _vm: &VM<'_, '_, impl ResourceTracker>,
+        _heap_ids: &mut AHashSet<HeapId>,
+    ) -> std::fmt::Result {
+        f.write_str("<csv.Sniffer object>")
+    }
+
+    fn py_estimate_size(&self) -> usize {
+        std::mem::size_of::<Self>()
+    }
+
+    fn py_call_attr(
+        &mut self,
+        _self_id: HeapId,
+        vm: &mut VM<'_, '_, impl ResourceTracker>,
+        attr: &EitherStr,
+        args: ArgValues,
+    ) -> RunResult<CallResult> {
+        let result = match attr.static_string() {
+            Some(StaticStrings::Sniff) => {
+                let params = args.bind_params("sniff", ["sample", "delimiters"], 1, vm.heap, vm.interns)?;
+                defer_drop!(params, vm);
+                let [sample, delimiters] = params;
+                let sample = value_to_str(sample.as_ref().expect("required argument"), vm.heap, vm.interns)?;
+                let delimiters = match delimiters {
+                    None | Some(Value::None) => None,
+                    Some(delimiters) => Some(value_to_str(delimiters, vm.heap, vm.interns)?),
+                };
+                let dialect = sniff(&sample, delimiters.as_deref(), vm.heap.tracker())?;
+                allocate_dialect(dialect, vm.heap)?
+            }
+            Some(StaticStrings::HasHeader) => {
+                let sample = args.get_one_arg("has_header", vm.heap)?;
+                defer_drop!(sample, vm);
+                let sample = value_to_str(sample, vm.heap, vm.interns)?;
+                Value::Bool(has_header(&sample, vm.heap)?)
+            }
+            _ => {
+                args.drop_with_heap(vm);
+                return Err(ExcType::attribute_error(Type::CsvSniffer, attr.as_str(vm.interns)));
+            }
+        };
+        Ok(CallResult::Value(result))
+    }
+}
@@ -0,0 +1,355 @@
+//! Python `csv` writer objects: `csv.writer()` results and `csv.DictWriter`.
+//!
+//! Both write formatted records to an `io.StringIO` buffer; a `DictWriter` additionally
+//! maps dicts to rows using its field names. Writing to other file-like objects is not
+//! supported.
+
+use std::fmt::Write;
+
+use ahash::AHashSet;
+
+use crate::{
+    args::ArgValues,
+    bytecode::{CallResult, VM},
+    defer_drop, defer_drop_mut,
+    exception_private::{ExcType, RunResult, SimpleException},
+    heap::{DropWithHeap, Heap, HeapData, HeapId},
+    heap_data::HeapDataMut,
+    intern::StaticStrings,
+    resource::{ResourceError, ResourceTracker},
+    types::{
+        MontyIter, PyTrait, Type,
+        csv_dialect::{Dialect, FMTPARAMS, OutField, Quoting, allocate_dialect},
+        re_pattern::value_to_str,
+    },
+    value::{EitherStr, Value},
+};
+
+/// Field mapping of a `csv.DictWriter`.
+#[derive(Debug, serde::Serialize, serde::Deserialize)]
+pub(crate) struct DictFields {
+    /// Keys whose values make up each row, in order.
+    fieldnames: Vec<Value>,
+    /// Value written for keys missing from a row dict.
+    restval: Value,
+    /// Whether keys not in `fieldnames` raise `ValueError` (`extrasaction='raise'`).
+    raise_on_extra: bool,
+}
+
+/// A CSV writer bound to an `io.StringIO` target.
+#[derive(Debug, serde::Serialize, serde::Deserialize)]
+pub(crate) struct CsvWriter {
+    /// The `io.StringIO` records are written to; the writer owns a reference to it.
+    target: HeapId,
+    dialect: Dialect,
+    /// Present for a `csv.DictWriter`.
+    dict: Option<DictFields>,
+}
+
+impl CsvWriter {
+    /// Creates a writer for `target`, taking ownership of the reference.
+    ///
+    /// Raises `TypeError` (after dropping `target`) unless it is an `io.StringIO`.
+    pub fn new(target: Value, dialect: Dialect, vm: &mut VM<'_, '_, impl ResourceTracker>) -> RunResult<Self> {
+        if let Value::Ref(id) = target
+            && matches!(vm.heap.get(id), HeapData::StringIO(_))
+        {
+            return Ok(Self {
+                target: id,
+                dialect,
+                dict: None,
+            });
+        }
+        target.drop_with_heap(vm);
+        Err(ExcType::type_error("argument 1 must have a \"write\" method"))
+    }
+
+    /// `DictWriter(f, fieldnames, restval='', extrasaction='raise', dialect='excel', **fmtparams)` constructor.
+    pub fn init_dict(vm: &mut VM<'_, '_, impl ResourceTracker>, args: ArgValues) -> RunResult<Value> {
+        let params = args.bind_params(
+            "DictWriter",
+            [
+                "f",
+                "fieldnames",
+                "restval",
+                "extrasaction",
+                "dialect",
+                FMTPARAMS[0],
+                FMTPARAMS[1],
+                FMTPARAMS[2],
+                FMTPARAMS[3],
+                FMTPARAMS[4],
+                FMTPARAMS[5],
+                FMTPARAMS[6],
+                FMTPARAMS[7],
+            ],
+            2,
+            vm.heap,
+            vm.interns,
+        )?;
+        let [f, fieldnames, restval, extrasaction, dialect, fmtparams @ ..] = params;
+        let f = f.expect("required argument");
+        let fieldnames = fieldnames.expect("required argument");
+        defer_drop!(extrasaction, vm);
+
+        let raise_on_extra = match extrasaction {
+            None => Ok(true),
+            Some(value) => match value_to_str(value, vm.heap, vm.interns) {
+                Ok(action) => match action.to_lowercase().as_str() {
+                    "raise" => Ok(true),
+                    "ignore" => Ok(false),
+                    _ => Err(SimpleException::new_msg(
+                        ExcType::ValueError,
+                        format!("extrasaction ({action}) must be 'raise' or 'ignore'"),
+                    )
+                    .into()),
+                },
+                Err(err) => Err(err),
+            },
+        };
+        let parsed = raise_on_extra.and_then(|raise_on_extra| {
+            Dialect::from_params(dialect.as_ref(), &fmtparams, vm).map(|dialect| (raise_on_extra, dialect))
+        });
+        dialect.drop_with_heap(vm);
+        fmtparams.drop_with_heap(vm);
+        let (raise_on_extra, dialect) = match parsed {
+            Ok(parsed) => parsed,
+            Err(err) => {
+                f.drop_with_heap(vm);
+                fieldnames.drop_with_heap(vm);
+                restval.drop_with_heap(vm);
+                return Err(err);
+            }
+        };
+
+        let fieldnames: Vec<Value> = match MontyIter::new(fieldnames, vm).and_then(|iter| iter.collect(vm)) {
+            Ok(fieldnames) => fieldnames,
+            Err(err) => {
+                f.drop_with_heap(vm);
+                restval.drop_with_heap(vm);
+                return Err(err);
+            }
+        };
+        let mut writer = match Self::new(f, dialect, vm) {
+            Ok(writer) => writer,
+            Err(err) => {
+                fieldnames.drop_with_heap(vm);
+                restval.drop_with_heap(vm);
+                return Err(err);
+            }
+        };
+        writer.dict = Some(DictFields {
+            fieldnames,
+            restval: restval.unwrap_or(Value::InternString(StaticStrings::EmptyString.into())),
+            raise_on_extra,
+        });
+        Ok(Value::Ref(vm.heap.allocate(HeapData::CsvWriter(Box::new(writer)))?))
+    }
+
+    /// Returns the `io.StringIO` this writer writes to.
+    #[must_use]
+    pub fn target(&self) -> HeapId {
+        self.target
+    }
+
+    /// Returns the `DictWriter` field names and `restval`, which may hold heap references.
+    pub fn values(&self) -> impl Iterator<Item = &Value> {
+        self.dict
+            .iter()
+            .flat_map(|dict| dict.fieldnames.iter().chain(std::iter::once(&dict.restval)))
+    }
+
+    /// Formats `fields` as one record and appends it to the target.
+    ///
+    /// Returns the number of characters written.
+    fn write_fields(&self, fields: &[Value], vm: &mut VM<'_, '_, impl ResourceTracker>) -> RunResult<usize> {
+        let fields: Vec<OutField> = fields.iter().map(|field| self.out_field(field, vm)).collect();
+        let record = self.dialect.format_row(&fields)?;
+        Heap::with_entry_mut(vm, self.target, |vm, data| match data {
+            HeapDataMut::StringIO(buffer) => buffer.write(&record, vm.heap.tracker()),
+            _ => unreachable!("csv writer target is always a StringIO"),
+        })
+    }
+
+    /// Converts a value to the text written for it and whether the quoting mode quotes it.
+    fn out_field(&self, value: &Value, vm: &VM<'_, '_, impl ResourceTracker>) -> OutField {
+        let quoted = match self.dialect.quoting {
+            Quoting::NonNumeric => !matches!(value.py_type(vm.heap), Type::Int | Type::Float | Type::Bool),
+            Quoting::All => true,
+            Quoting::Strings => value.is_str(vm.heap),
+            Quoting::NotNull => !matches!(value, Value::None),
+            Quoting::Minimal | Quoting::None => false,
+        };
+        let text = match value {
+            Value::None => None,
+            _ => Some(value.py_str(vm).into_owned()),
+        };
+        OutField { text, quoted }
+    }
+
+    /// Writes one row: an iterable of fields, or a dict for a `DictWriter`.
+    fn write_row(&self, row: Value, vm: &mut VM<'_, '_, impl ResourceTracker>) -> RunResult<usize> {
+        let fields = match &self.dict {
+            Some(dict) => dict_to_fields(dict, row, vm)?,
+            None => {
+                let row_type = row.py_type(vm.heap);
+                match MontyIter::new(row, vm) {
+                    Ok(iter) => iter.collect(vm)?,
+                    Err(_) => return Err(ExcType::csv_error(format!("iterable expected, not {row_type}"))),
+                }
+            }
+        };
+        defer_drop!(fields, vm);
+        self.write_fields(fields, vm)
+    }
+}
+
+/// Maps a row dict to the values of `dict.fieldnames`, filling in `restval` for missing keys.
+fn dict_to_fields(dict: &DictFields, row: Value, vm: &mut VM<'_, '_, impl ResourceTracker>) -> RunResult<Vec<Value>> {
+    defer_drop!(row, vm);
+    let row_type = row.py_type(vm.heap);
+    let Value::Ref(row_id) = row else {
+        return Err(ExcType::attribute_error(row_type, "keys"));
+    };
+    if !matches!(vm.heap.get(*row_id), HeapData::Dict(_)) {
+        return Err(ExcType::attribute_error(row_type, "keys"));
+    }
+    Heap::with_entry_mut(vm, *row_id, |vm, data| {
+        let HeapDataMut::Dict(row) = data else {
+            unreachable!("checked above")
+        };
+        if dict.raise_on_extra {
+            let mut wrong_fields = Vec::new();
+            for (key, _) in row.iter() {
+                let mut known = false;
+                for fieldname in &dict.fieldnames {
+                    if key.py_eq(fieldname, vm)? {
+                        known = true;
+                        break;
+                    }
+                }
+                if !known {
+                    wrong_fields.push(key.py_repr(vm).into_owned());
+                }
+            }
+            if !wrong_fields.is_empty() {
+                return Err(SimpleException::new_msg(
+                    ExcType::ValueError,
+                    format!("dict contains fields not in fieldnames: {}", wrong_fields.join(", ")),
+                )
+                .into());
+            }
+        }
+        let mut fields = Vec::with_capacity(dict.fieldnames.len());
+        for fieldname in &dict.fieldnames {
+            let value = match row.get(fieldname, vm) {
+                Ok(value) => value.unwrap_or(&dict.restval).clone_with_heap(vm.heap),
+                Err(err) => {
+                    fields.drop_with_heap(vm);
+                    return Err(err);
+                }
+            };
+            fields.push(value);
+        }
+        Ok(fields)
+    })
+}
+
+impl PyTrait for CsvWriter {
+    fn py_type(&self, _heap: &Heap<impl ResourceTracker>) -> Type {
+        if self.dict.is_some() {
+            Type::CsvDictWriter
+        } else {
+            Type::CsvWriter
+        }
+    }
+
+    fn py_len(&self, _vm: &VM<'_, '_, impl ResourceTracker>) -> Option<usize> {
+        None
+    }
+
+    fn py_eq(&self, _other: &Self, _vm: &mut VM<'_, '_, impl ResourceTracker>) -> Result<bool, ResourceError> {
+        // Writers compare by identity (handled at the Value level)
+        Ok(false)
+    }
+
+    fn py_dec_ref_ids(&mut self, stack: &mut Vec<HeapId>) {
+        stack.push(self.target);
+        if let Some(dict) = &mut self.dict {
+            for fieldname in &mut dict.fieldnames {
+                fieldname.py_dec_ref_ids(stack);
+            }
+            dict.restval.py_dec_ref_ids(stack);
+        }
+    }
+
+    fn py_bool(&self, _vm: &VM<'_, '_, impl ResourceTracker>) -> bool {
+        true
+    }
+
+    fn py_repr_fmt(
+        &self,
+        f: &mut impl Write,
+        vm: &VM<'_, '_, impl ResourceTracker>,
+        _heap_ids: &mut AHashSet<HeapId>,
+    ) -> std::fmt::Result {
+        write!(f, "<{} object>", self.py_type(vm.heap))
+    }
+
+    fn py_estimate_size(&self) -> usize {
+        std::mem::size_of::<Self>()
+            + self.dialect.lineterminator.len()
+            + self
+                .dict
+                .as_ref()
+                .map_or(0, |dict| dict.fieldnames.len() * std::mem::size_of::<Value>())
+    }
+
+    fn py_getattr(&self, attr: &EitherStr, vm: &mut VM<'_, '_, impl ResourceTracker>) -> RunResult<Option<CallResult>> {
+        match attr.static_string() {
+            Some(StaticStrings::Dialect) => Ok(Some(CallResult::Value(allocate_dialect(
+                self.dialect.clone(),
+                vm.heap,
+            )?))),
+            _ => Err(ExcType::attribute_error(self.py_type(vm.heap), attr.as_str(vm.interns))),
+        }
+    }
+
+    fn py_call_attr(
+        &mut self,
+        _self_id: HeapId,
+        vm: &mut VM<'_, '_, impl ResourceTracker>,
+        attr: &EitherStr,
+        args: ArgValues,
+    ) -> RunResult<CallResult> {
+        let result = match attr.static_string() {
+            Some(StaticStrings::Writerow) => {
+                let row = args.get_one_arg("writerow", vm.heap)?;
+                let written = self.write_row(row, vm)?;
+                Value::Int(i64::try_from(written).expect("character count fits in i64"))
+            }
+            Some(StaticStrings::Writerows) => {
+                let rows = args.get_one_arg("writerows", vm.heap)?;
+                let rows: Vec<Value> = MontyIter::new(rows, vm)?.collect(vm)?;
+                let rows = rows.into_iter();
+                defer_drop_mut!(rows, vm);
+                for row in rows.by_ref() {
+                    vm.heap.check_time()?;
+                    self.write_row(row, vm)?;
+                }
+                Value::None
+            }
+            Some(StaticStrings::Writeheader) if self.dict.is_some() => {
+                args.check_zero_args("writeheader", vm.heap)?;
+                let dict = self.dict.as_ref().expect("checked above");
+                let written = self.write_fields(&dict.fieldnames, vm)?;
+                Value::Int(i64::try_from(written).expect("character count fits in i64"))
+            }
+            _ => {
+                args.drop_with_heap(vm);
+                return Err(ExcType::attribute_error(self.py_type(vm.heap), attr.as_str(vm.interns)));
+            }
+        };
+        Ok(CallResult::Value(result))
+    }
+}
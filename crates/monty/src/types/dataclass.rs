@@ -111,6 +111,25 @@ impl Dataclass {
         &self.attrs
     }
 
+    /// Returns a mutable reference to the attrs Dict, bypassing the frozen check.
+    ///
+    /// Used to populate a copy created by [`Self::with_attrs`].
+    pub fn attrs_mut(&mut self) -> &mut Dict {
+        &mut self.attrs
+    }
+
+    /// Creates an instance of the same dataclass holding `attrs` (ownership transferred).
+    #[must_use]
+    pub fn with_attrs(&self, attrs: Dict) -> Self {
+        Self {
+            name: self.name.clone(),
+            type_id: self.type_id,
+            field_names: self.field_names.clone(),
+            attrs,
+            frozen: self.frozen,
+        }
+    }
+
     /// Returns whether this dataclass instance is frozen (immutable).
     #[must_use]
     pub fn is_frozen(&self) -> bool {
@@ -132,10 +151,7 @@ impl Dataclass {
     ) -> RunResult<Option<Value>> {
         if self.frozen {
             // Get attribute name for error message
-            let attr_name = match &name {
-                Value::InternString(id) => vm.interns.get_str(*id).to_string(),
-                _ => "<unknown>".to_string(),
-            };
+            let attr_name = name.py_str(vm).into_owned();
             // Drop the values we were given ownership of
             name.drop_with_heap(vm);
             value.drop_with_heap(vm);
@@ -144,6 +160,23 @@ impl Dataclass {
         self.attrs.set(name, value, vm)
     }
 
+    /// Deletes an attribute, returning whether it existed.
+    ///
+    /// Returns `FrozenInstanceError` if the dataclass is frozen.
+    pub fn del_attr(&mut self, name: &Value, vm: &mut VM<'_, '_, impl ResourceTracker>) -> RunResult<bool> {
+        if self.frozen {
+            return Err(ExcType::frozen_instance_error_delete(&name.py_str(vm)));
+        }
+        match self.attrs.pop(name, vm)? {
+            Some((key, value)) => {
+                key.drop_with_heap(vm);
+                value.drop_with_heap(vm);
+                Ok(true)
+            }
+            None => Ok(false),
+        }
+    }
+
     /// Computes the hash for this dataclass if it's frozen.
     ///
     /// Returns `Ok(Some(hash))` for frozen (immutable) dataclasses, `Ok(None)` for mutable ones.
@@ -0,0 +1,613 @@
+//! Python file objects returned by `open()` and `Path.open()`.
+//!
+//! A file holds the integer handle the host returned for `OsFunction::Open`. Every read,
+//! write, seek and close is yielded to the host as an `OsCall` naming that handle, so large
+//! files can be processed a line or a block at a time instead of loaded into one string.
+//!
+//! Text files are `_io.TextIOWrapper` objects; binary files are `_io.BufferedReader`,
+//! `_io.BufferedWriter` or `_io.BufferedRandom` depending on the mode, as in CPython.
+//! Decoding and newline translation are left to the host, so only UTF-8 is supported and
+//! `newline` may only be `None`, `''` or `'\n'` (equivalent for `'\n'`-terminated lines).
+//!
+//! `for` loops and comprehensions read a file a line at a time (see `Opcode::ForIter`).
+//! Builtins such as `list()`, `enumerate()` or `csv.reader()` iterate their arguments
+//! without yielding, so a file passed to one is read to the end first and the call gets
+//! an iterator over its lines instead (see `VM::call_reading_files`).
+//!
+//! Files dropped without being closed are closed when the module finishes, as are the files
+//! still open at the end of a `MontyRun` (see `Opcode::ReturnValue`). A REPL keeps the files
+//! its globals hold open for later snippets.
+
+use std::{fmt::Write, mem};
+
+use ahash::AHashSet;
+
+use crate::{
+    args::{ArgValues, KwargsValues},
+    bytecode::{CallResult, VM},
+    defer_drop,
+    exception_private::{ExcType, RunError, RunResult, SimpleException},
+    heap::{DropWithHeap, Heap, HeapData, HeapId},
+    intern::{Interns, StaticStrings},
+    os::{OsFunction, OsResultConversion},
+    os_handler::OpenMode,
+    resource::{ResourceError, ResourceTracker},
+    types::{
+        Bytes, List, MontyIter, Path, PyTrait, Type,
+        re_pattern::value_to_str,
+        str::{StringRepr, allocate_string},
+    },
+    value::{EitherStr, Value},
+};
+
+/// The parameters of `open()` after `file`, which are also those of `Path.open()`.
+pub(crate) const OPEN_PARAMS: [&str; 5] = ["mode", "buffering", "encoding", "errors", "newline"];
+
+/// Parses the mode passed to `open()`, raising `ValueError` with CPython's messages for invalid modes.
+fn parse_mode(mode: &str) -> RunResult<OpenMode> {
+    let error = |msg: String| -> RunError { SimpleException::new_msg(ExcType::ValueError, msg).into() };
+    // Every character is ASCII once the first check passes, so slicing after it is safe.
+    if !mode.chars().all(|c| "axrwb+t".contains(c)) || mode.char_indices().any(|(i, c)| mode[i + 1..].contains(c)) {
+        return Err(error(format!("invalid mode: {}", StringRepr(mode))));
+    }
+    let binary = mode.contains('b');
+    if binary && mode.contains('t') {
+        return Err(error("can't have text and binary mode at once".to_owned()));
+    }
+    let mut access = mode.bytes().filter(|c| b"rwax".contains(c));
+    match (access.next(), access.next()) {
+        (Some(access), None) => Ok(OpenMode {
+            access,
+            update: mode.contains('+'),
+            binary,
+        }),
+        (Some(_), Some(_)) => Err(error(
+            "must have exactly one of create/read/write/append mode".to_owned(),
+        )),
+        (None, _) => Err(error(
+            "Must have exactly one of create/read/write/append mode and at most one plus".to_owned(),
+        )),
+    }
+}
+
+/// The `mode` attribute of a binary file, normalized as CPython does (`'w+b'` is `'rb+'`).
+fn binary_mode_attr(mode: OpenMode) -> String {
+    match (mode.access, mode.update) {
+        (b'r' | b'w', true) => "rb+".to_owned(),
+        (access, true) => format!("{}b+", char::from(access)),
+        (access, false) => format!("{}b", char::from(access)),
+    }
+}
+
+/// Validates `open()` arguments and yields `OsFunction::Open` for the host to open `path`.
+///
+/// Shared by the `open()` builtin and `Path.open()`; `name` becomes the file's `name`
+/// attribute. The VM builds the file from the returned handle (`OsResultConversion::OpenFile`).
+pub(crate) fn open_file(
+    vm: &mut VM<'_, '_, impl ResourceTracker>,
+    path: Path,
+    name: String,
+    options: [Option<&Value>; 5],
+) -> RunResult<CallResult> {
+    let [mode, buffering, encoding, errors, newline] = options;
+    let mode = match mode {
+        None => "r".to_owned(),
+        Some(value) if value.is_str(vm.heap) => value_to_str(value, vm.heap, vm.interns)?.into_owned(),
+        Some(value) => {
+            return Err(ExcType::type_error(format!(
+                "open() argument 'mode' must be str, not {}",
+                value.py_type(vm.heap)
+            )));
+        }
+    };
+    let open_mode = parse_mode(&mode)?;
+    let buffering = match buffering {
+        Some(buffering) => buffering.as_int(vm.heap)?,
+        None => -1,
+    };
+    let encoding = optional_str_arg("encoding", encoding, vm.heap, vm.interns)?;
+    let errors = optional_str_arg("errors", errors, vm.heap, vm.interns)?;
+    let newline = optional_str_arg("newline", newline, vm.heap, vm.interns)?;
+    if open_mode.binary {
+        let argument = if encoding.is_some() {
+            Some("an encoding")
+        } else if errors.is_some() {
+            Some("an errors")
+        } else if newline.is_some() {
+            Some("a newline")
+        } else {
+            None
+        };
+        if let Some(argument) = argument {
+            return Err(SimpleException::new_msg(
+                ExcType::ValueError,
+                format!("binary mode doesn't take {argument} argument"),
+            )
+            .into());
+        }
+    }
+    if let Some(encoding) = &encoding
+        && !matches!(encoding.to_ascii_lowercase().as_str(), "utf-8" | "utf8" | "utf_8")
+    {
+        return Err(ExcType::lookup_error_unknown_encoding(encoding));
+    }
+    if let Some(errors) = &errors
+        && errors != "strict"
+    {
+        return Err(SimpleException::new_msg(
+            ExcType::ValueError,
+            format!("open() only supports errors='strict', not {}", StringRepr(errors)),
+        )
+        .into());
+    }
+    if let Some(newline) = &newline {
+        match newline.as_str() {
+            "" | "\n" => {}
+            "\r" | "\r\n" => {
+                return Err(SimpleException::new_msg(
+                    ExcType::ValueError,
+                    format!("open() does not support newline={}", StringRepr(newline)),
+                )
+                .into());
+            }
+            other => {
+                return Err(SimpleException::new_msg(
+                    ExcType::ValueError,
+                    format!("illegal newline value: {}", StringRepr(other)),
+                )
+                .into());
+            }
+        }
+    }
+
+    // host files are always buffered, but unbuffered binary files behave the same
+    if buffering == 0 && !open_mode.binary {
+        return Err(SimpleException::new_msg(ExcType::ValueError, "can't have unbuffered text I/O").into());
+    }
+
+    let (mode, encoding) = if open_mode.binary {
+        (binary_mode_attr(open_mode), None)
+    } else {
+        (mode, Some(encoding.unwrap_or_else(|| "utf-8".to_owned())))
+    };
+    let mode_arg = allocate_string(open_mode.host_mode(), vm.heap)?;
+    let path_arg = match vm.heap.allocate(HeapData::Path(path)) {
+        Ok(id) => Value::Ref(id),
+        Err(err) => {
+            mode_arg.drop_with_heap(vm);
+            return Err(err.into());
+        }
+    };
+    vm.set_os_result_conversion(OsResultConversion::OpenFile { name, mode, encoding });
+    Ok(CallResult::OsCall(OsFunction::Open, ArgValues::Two(path_arg, mode_arg)))
+}
+
+/// Extracts an optional `str` argument of `open()`, where `None` means the default.
+fn optional_str_arg(
+    param: &str,
+    value: Option<&Value>,
+    heap: &Heap<impl ResourceTracker>,
+    interns: &Interns,
+) -> RunResult<Option<String>> {
+    match value {
+        None | Some(Value::None) => Ok(None),
+        Some(value) if value.is_str(heap) => Ok(Some(value_to_str(value, heap, interns)?.into_owned())),
+        Some(value) => Err(ExcType::type_error(format!(
+            "open() argument '{param}' must be str or None, not {}",
+            value.py_type(heap)
+        ))),
+    }
+}
+
+/// Returns the contents of `value` if it is `bytes`.
+fn bytes_contents<'a>(value: &Value, heap: &'a Heap<impl ResourceTracker>, interns: &'a Interns) -> Option<&'a [u8]> {
+    match value {
+        Value::InternBytes(id) => Some(interns.get_bytes(*id)),
+        Value::Ref(id) => match heap.get(*id) {
+            HeapData::Bytes(b) => Some(b.as_slice()),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+/// Extracts a `size` argument of `read()` and `readline()`, where `None` or a negative
+/// value means "no limit" and is passed to the host as -1.
+fn size_arg(value: Option<&Value>, heap: &Heap<impl ResourceTracker>) -> RunResult<i64> {
+    match value {
+        None | Some(Value::None) => Ok(-1),
+        Some(Value::Int(size)) => Ok((*size).max(-1)),
+        Some(Value::Bool(size)) => Ok(i64::from(*size)),
+        Some(value) => Err(ExcType::type_error(format!(
+            "argument should be integer or None, not '{}'",
+            value.py_type(heap)
+        ))),
+    }
+}
+
+/// Splits the rest of a file, as returned by `OsFunction::FileRead`, into a list of lines
+/// that keep their `'\n'` terminators.
+///
+/// Implements `OsResultConversion::Readlines` for `readlines()`.
+pub(crate) fn readlines_from(data: Value, vm: &mut VM<'_, '_, impl ResourceTracker>) -> RunResult<Value> {
+    defer_drop!(data, vm);
+    let mut lines = Vec::new();
+    if data.is_str(vm.heap) {
+        let text = value_to_str(data, vm.heap, vm.interns)?.into_owned();
+        for line in text.split_inclusive('\n') {
+            lines.push(allocate_string(line.to_owned(), vm.heap)?);
+        }
+    } else if let Some(bytes) = bytes_contents(data, vm.heap, vm.interns) {
+        let bytes = bytes.to_vec();
+        for line in bytes.split_inclusive(|b| *b == b'\n') {
+            lines.push(Value::Ref(
+                vm.heap.allocate(HeapData::Bytes(Bytes::new(line.to_vec())))?,
+            ));
+        }
+    } else {
+        return Err(ExcType::type_error(format!(
+            "file.read() result must be str or bytes, not {}",
+            data.py_type(vm.heap)
+        )));
+    }
+    Ok(Value::Ref(vm.heap.allocate(HeapData::List(List::new(lines)))?))
+}
+
+/// A file opened on the host.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub(crate) struct File {
+    /// The handle the host returned for `OsFunction::Open`.
+    handle: i64,
+    /// The `name` attribute: the path as passed to `open()`.
+    name: String,
+    /// The `mode` attribute.
+    mode: String,
+    /// The `encoding` attribute of a text file, `None` for a binary file.
+    encoding: Option<String>,
+    readable: bool,
+    writable: bool,
+    /// Whether `close()` has been called.
+    closed: bool,
+}
+
+impl File {
+    /// Builds the file for the handle the host returned for `OsFunction::Open`.
+    ///
+    /// Implements `OsResultConversion::OpenFile`; `mode` has already been validated by `open()`.
+    pub(crate) fn from_handle(
+        handle: Value,
+        name: String,
+        mode: String,
+        encoding: Option<String>,
+        vm: &mut VM<'_, '_, impl ResourceTracker>,
+    ) -> RunResult<Value> {
+        let Value::Int(handle) = handle else {
+            let err = ExcType::type_error(format!(
+                "open() result must be an int handle, not {}",
+                handle.py_type(vm.heap)
+            ));
+            handle.drop_with_heap(vm);
+            return Err(err);
+        };
+        let open_mode = parse_mode(&mode).expect("open() validates the mode");
+        let file = Self {
+            handle,
+            name,
+            mode,
+            encoding,
+            readable: open_mode.readable(),
+            writable: open_mode.writable(),
+            closed: false,
+        };
+        Ok(Value::Ref(vm.heap.allocate(HeapData::File(file))?))
+    }
+
+    /// Returns the arguments of the `OsFunction::FileReadline` call that reads the next line
+    /// for a `for` loop.
+    pub(crate) fn for_iter_args(&self) -> RunResult<ArgValues> {
+        self.check_readable()?;
+        Ok(ArgValues::Two(Value::Int(self.handle), Value::Int(-1)))
+    }
+
+    /// Returns the arguments of the `OsFunction::FileRead` call that reads the rest of the file.
+    pub(crate) fn read_rest_args(&self) -> ArgValues {
+        ArgValues::Two(Value::Int(self.handle), Value::Int(-1))
+    }
+
+    /// Marks the file closed, returning its handle for `OsFunction::FileClose` unless it
+    /// was closed already.
+    pub(crate) fn close(&mut self) -> Option<i64> {
+        (!mem::replace(&mut self.closed, true)).then_some(self.handle)
+    }
+
+    /// Returns the handle of a file that hasn't been closed.
+    pub(crate) fn open_handle(&self) -> Option<i64> {
+        (!self.closed).then_some(self.handle)
+    }
+
+    /// Raises `ValueError` if the file has been closed.
+    pub(crate) fn check_open(&self) -> RunResult<()> {
+        if self.closed {
+            Err(SimpleException::new_msg(ExcType::ValueError, "I/O operation on closed file.").into())
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Raises `io.UnsupportedOperation` if the file was not opened for reading.
+    pub(crate) fn check_readable(&self) -> RunResult<()> {
+        self.check_open()?;
+        match (self.readable, self.encoding.is_some()) {
+            (true, _) => Ok(()),
+            (false, true) => Err(ExcType::unsupported_operation("not readable")),
+            (false, false) => Err(ExcType::unsupported_operation("read")),
+        }
+    }
+
+    /// Raises `io.UnsupportedOperation` if the file was not opened for writing.
+    fn check_writable(&self) -> RunResult<()> {
+        match (self.writable, self.encoding.is_some()) {
+            (true, _) => Ok(()),
+            (false, true) => Err(ExcType::unsupported_operation("not writable")),
+            (false, false) => Err(ExcType::unsupported_operation("write")),
+        }
+    }
+
+    /// Raises `TypeError` unless `data` is `str` for a text file or `bytes` for a binary file.
+    fn check_data(&self, data: &Value, heap: &Heap<impl ResourceTracker>, interns: &Interns) -> RunResult<()> {
+        if self.encoding.is_some() {
+            if !data.is_str(heap) {
+                return Err(ExcType::type_error(format!(
+                    "write() argument must be str, not {}",
+                    data.py_type(heap)
+                )));
+            }
+        } else if bytes_contents(data, heap, interns).is_none() {
+            return Err(ExcType::type_error(format!(
+                "a bytes-like object is required, not '{}'",
+                data.py_type(heap)
+            )));
+        }
+        Ok(())
+    }
+
+    /// Validates the arguments of `seek()`, which text files restrict as in CPython.
+    fn check_seek(&self, offset: i64, whence: i64) -> RunResult<()> {
+        let text = self.encoding.is_some();
+        match whence {
+            0 if offset < 0 && text => {
+                Err(SimpleException::new_msg(ExcType::ValueError, format!("negative seek position {offset}")).into())
+            }
+            0 if offset < 0 => Err(SimpleException::new_msg(ExcType::OSError, "[Errno 22] Invalid argument").into()),
+            1 | 2 if offset != 0 && text => {
+                let kind = if whence == 1 { "cur" } else { "end" };
+                Err(ExcType::unsupported_operation(format!(
+                    "can't do nonzero {kind}-relative seeks"
+                )))
+            }
+            0..=2 => Ok(()),
+            _ if text => Err(SimpleException::new_msg(
+                ExcType::ValueError,
+                format!("invalid whence ({whence}, should be 0, 1 or 2)"),
+            )
+            .into()),
+            _ => {
+                Err(SimpleException::new_msg(ExcType::ValueError, format!("whence value {whence} unsupported")).into())
+            }
+        }
+    }
+
+    fn file_type(&self) -> Type {
+        match (&self.encoding, self.readable, self.writable) {
+            (Some(_), _, _) => Type::TextIOWrapper,
+            (None, true, true) => Type::BufferedRandom,
+            (None, true, false) => Type::BufferedReader,
+            (None, false, _) => Type::BufferedWriter,
+        }
+    }
+
+    /// Implements the methods of an open file; `close()` and `__exit__()` are handled by the caller.
+    fn call_open_method(
+        &self,
+        self_id: HeapId,
+        vm: &mut VM<'_, '_, impl ResourceTracker>,
+        method: StaticStrings,
+        args: ArgValues,
+    ) -> RunResult<CallResult> {
+        let handle = Value::Int(self.handle);
+        let result = match method {
+            StaticStrings::DunderEnter => {
+                args.check_zero_args("__enter__", vm.heap)?;
+                vm.heap.inc_ref(self_id);
+                CallResult::Value(Value::Ref(self_id))
+            }
+            StaticStrings::Read | StaticStrings::Readline => {
+                let (name, function) = if method == StaticStrings::Read {
+                    ("read", OsFunction::FileRead)
+                } else {
+                    ("readline", OsFunction::FileReadline)
+                };
+                let size = args.get_zero_one_arg(name, vm.heap)?;
+                defer_drop!(size, vm);
+                let size = size_arg(size.as_ref(), vm.heap)?;
+                self.check_readable()?;
+                CallResult::OsCall(function, ArgValues::Two(handle, Value::Int(size)))
+            }
+            StaticStrings::Readlines => {
+                args.check_zero_args("readlines", vm.heap)?;
+                self.check_readable()?;
+                vm.set_os_result_conversion(OsResultConversion::Readlines);
+                CallResult::OsCall(OsFunction::FileRead, self.read_rest_args())
+            }
+            StaticStrings::Write => {
+                let data = args.get_one_arg("write", vm.heap)?;
+                let checked = self
+                    .check_data(&data, vm.heap, vm.interns)
+                    .and_then(|()| self.check_writable());
+                if let Err(err) = checked {
+                    data.drop_with_heap(vm);
+                    return Err(err);
+                }
+                CallResult::OsCall(OsFunction::FileWrite, ArgValues::Two(handle, data))
+            }
+            StaticStrings::Writelines => {
+                let lines = args.get_one_arg("writelines", vm.heap)?;
+                let lines: Vec<Value> = MontyIter::new(lines, vm)?.collect(vm)?;
+                defer_drop!(lines, vm);
+                self.check_writable()?;
+                // The lines are joined into a single write, so the host sees one call.
+                let data = if self.encoding.is_some() {
+                    let mut text = String::new();
+                    for line in lines.iter() {
+                        self.check_data(line, vm.heap, vm.interns)?;
+                        text.push_str(&value_to_str(line, vm.heap, vm.interns)?);
+                    }
+                    allocate_string(text, vm.heap)?
+                } else {
+                    let mut data = Vec::new();
+                    for line in lines.iter() {
+                        self.check_data(line, vm.heap, vm.interns)?;
+                        data.extend_from_slice(bytes_contents(line, vm.heap, vm.interns).unwrap_or_default());
+                    }
+                    Value::Ref(vm.heap.allocate(HeapData::Bytes(Bytes::new(data)))?)
+                };
+                vm.set_os_result_conversion(OsResultConversion::Discard);
+                CallResult::OsCall(OsFunction::FileWrite, ArgValues::Two(handle, data))
+            }
+            StaticStrings::Seek => {
+                let (offset, whence) = args.get_one_two_args("seek", vm.heap)?;
+                defer_drop!(offset, vm);
+                defer_drop!(whence, vm);
+                let offset = offset.as_int(vm.heap)?;
+                let whence = match whence {
+                    Some(whence) => whence.as_int(vm.heap)?,
+                    None => 0,
+                };
+                self.check_seek(offset, whence)?;
+                CallResult::OsCall(
+                    OsFunction::FileSeek,
+                    ArgValues::ArgsKargs {
+                        args: vec![handle, Value::Int(offset), Value::Int(whence)],
+                        kwargs: KwargsValues::Empty,
+                    },
+                )
+            }
+            StaticStrings::Tell => {
+                args.check_zero_args("tell", vm.heap)?;
+                CallResult::OsCall(OsFunction::FileTell, ArgValues::One(handle))
+            }
+            StaticStrings::Flush => {
+                // Writes go straight to the host, so there is nothing to flush.
+                args.check_zero_args("flush", vm.heap)?;
+                CallResult::Value(Value::None)
+            }
+            StaticStrings::Readable => {
+                args.check_zero_args("readable", vm.heap)?;
+                CallResult::Value(Value::Bool(self.readable))
+            }
+            StaticStrings::Writable => {
+                args.check_zero_args("writable", vm.heap)?;
+                CallResult::Value(Value::Bool(self.writable))
+            }
+            StaticStrings::Seekable => {
+                args.check_zero_args("seekable", vm.heap)?;
+                CallResult::Value(Value::Bool(true))
+            }
+            _ => {
+                args.drop_with_heap(vm);
+                return Err(ExcType::attribute_error(self.file_type(), method.into()));
+            }
+        };
+        Ok(result)
+    }
+}
+
+impl PyTrait for File {
+    fn py_type(&self, _heap: &Heap<impl ResourceTracker>) -> Type {
+        self.file_type()
+    }
+
+    fn py_len(&self, _vm: &VM<'_, '_, impl ResourceTracker>) -> Option<usize> {
+        None
+    }
+
+    fn py_eq(&self, _other: &Self, _vm: &mut VM<'_, '_, impl ResourceTracker>) -> Result<bool, ResourceError> {
+        // Files compare by identity (handled at the Value level)
+        Ok(false)
+    }
+
+    fn py_dec_ref_ids(&mut self, _stack: &mut Vec<HeapId>) {
+        // No heap references — the file only holds the host's handle.
+    }
+
+    fn py_bool(&self, _vm: &VM<'_, '_, impl ResourceTracker>) -> bool {
+        true
+    }
+
+    fn py_repr_fmt(
+        &self,
+        f: &mut impl Write,
+        _vm: &VM<'_, '_, impl ResourceTracker>,
+        _heap_ids: &mut AHashSet<HeapId>,
+    ) -> std::fmt::Result {
+        match &self.encoding {
+            Some(encoding) => write!(
+                f,
+                "<_io.TextIOWrapper name={} mode={} encoding={}>",
+                StringRepr(&self.name),
+                StringRepr(&self.mode),
+                StringRepr(encoding)
+            ),
+            None => write!(f, "<{} name={}>", self.file_type(), StringRepr(&self.name)),
+        }
+    }
+
+    fn py_estimate_size(&self) -> usize {
+        std::mem::size_of::<Self>() + self.name.len() + self.mode.len()
+    }
+
+    fn py_getattr(&self, attr: &EitherStr, vm: &mut VM<'_, '_, impl ResourceTracker>) -> RunResult<Option<CallResult>> {
+        let value = match (attr.static_string(), &self.encoding) {
+            (Some(StaticStrings::Closed), _) => Value::Bool(self.closed),
+            (Some(StaticStrings::Name), _) => allocate_string(self.name.clone(), vm.heap)?,
+            (Some(StaticStrings::Mode), _) => allocate_string(self.mode.clone(), vm.heap)?,
+            (Some(StaticStrings::Encoding), Some(encoding)) => allocate_string(encoding.clone(), vm.heap)?,
+            _ => return Err(ExcType::attribute_error(self.file_type(), attr.as_str(vm.interns))),
+        };
+        Ok(Some(CallResult::Value(value)))
+    }
+
+    fn py_call_attr(
+        &mut self,
+        self_id: HeapId,
+        vm: &mut VM<'_, '_, impl ResourceTracker>,
+        attr: &EitherStr,
+        args: ArgValues,
+    ) -> RunResult<CallResult> {
+        let Some(method) = attr.static_string() else {
+            args.drop_with_heap(vm);
+            return Err(ExcType::attribute_error(self.file_type(), attr.as_str(vm.interns)));
+        };
+        if matches!(method, StaticStrings::Close | StaticStrings::DunderExit) {
+            if method == StaticStrings::Close {
+                args.check_zero_args("close", vm.heap)?;
+            } else {
+                // `__exit__(exc_type, exc, tb)` closes the file whatever the arguments are.
+                args.drop_with_heap(vm);
+            }
+            let Some(handle) = self.close() else {
+                return Ok(CallResult::Value(Value::None));
+            };
+            vm.set_os_result_conversion(OsResultConversion::Discard);
+            return Ok(CallResult::OsCall(
+                OsFunction::FileClose,
+                ArgValues::One(Value::Int(handle)),
+            ));
+        }
+        if let Err(err) = self.check_open() {
+            args.drop_with_heap(vm);
+            return Err(err);
+        }
+        self.call_open_method(self_id, vm, method, args)
+    }
+}
@@ -0,0 +1,238 @@
+//! Hash object type for the `hashlib` and `hmac` modules.
+//!
+//! `HashObject` wraps an incremental [`Hasher`] (or a pair of them for HMAC) and
+//! provides the Python hash object protocol: `update()`, `digest()`, `hexdigest()`,
+//! `copy()`, plus the `name`, `digest_size` and `block_size` attributes.
+//!
+//! All state is owned (no heap references), so reference counting is trivial —
+//! `py_dec_ref_ids` is a no-op.
+
+use std::fmt::Write;
+
+use ahash::AHashSet;
+
+use crate::{
+    args::ArgValues,
+    bytecode::{CallResult, VM},
+    defer_drop,
+    digest::{HashAlgorithm, Hasher, to_hex},
+    exception_private::{ExcType, RunResult},
+    heap::{Heap, HeapData, HeapId},
+    intern::{Interns, StaticStrings},
+    resource::{ResourceError, ResourceTracker},
+    types::{Bytes, PyTrait, Str, Type},
+    value::{EitherStr, Value},
+};
+
+/// Number of bytes hashed between resource tracker time checks.
+///
+/// Hashing is linear in the input size, so large inputs are processed in chunks
+/// to let `max_duration` interrupt a long-running `update()`.
+const HASH_CHUNK_SIZE: usize = 64 * 1024;
+
+/// A `hashlib` hash object or an `hmac.HMAC` object.
+///
+/// Created by `hashlib.new()`, the named `hashlib` constructors, and `hmac.new()`.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub(crate) struct HashObject {
+    state: HashState,
+}
+
+/// The digest state behind a [`HashObject`].
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+enum HashState {
+    /// A plain message digest.
+    Plain(Hasher),
+    /// HMAC (RFC 2104): `inner` is keyed with `key ^ ipad` and receives the message,
+    /// `outer` is keyed with `key ^ opad` and receives the inner digest on finalization.
+    Hmac { inner: Hasher, outer: Hasher },
+}
+
+impl HashObject {
+    /// Creates a plain hash object from an initialized hasher.
+    pub fn new(hasher: Hasher) -> Self {
+        Self {
+            state: HashState::Plain(hasher),
+        }
+    }
+
+    /// Creates an HMAC object for `algorithm` keyed with `key`.
+    pub fn new_hmac(algorithm: HashAlgorithm, key: &[u8]) -> Self {
+        let block_size = algorithm.block_size();
+        let mut key_block = if key.len() > block_size {
+            crate::digest::digest(algorithm, key)
+        } else {
+            key.to_vec()
+        };
+        key_block.resize(block_size, 0);
+
+        let mut inner = Hasher::new(algorithm);
+        let mut outer = Hasher::new(algorithm);
+        let ipad: Vec<u8> = key_block.iter().map(|b| b ^ 0x36).collect();
+        let opad: Vec<u8> = key_block.iter().map(|b| b ^ 0x5c).collect();
+        inner.update(&ipad);
+        outer.update(&opad);
+        Self {
+            state: HashState::Hmac { inner, outer },
+        }
+    }
+
+    /// Feeds more data into the hash, checking the time limit between chunks.
+    pub fn update(&mut self, data: &[u8], heap: &Heap<impl ResourceTracker>) -> Result<(), ResourceError> {
+        let hasher = match &mut self.state {
+            HashState::Plain(hasher) => hasher,
+            HashState::Hmac { inner, .. } => inner,
+        };
+        for chunk in data.chunks(HASH_CHUNK_SIZE) {
+            heap.check_time()?;
+            hasher.update(chunk);
+        }
+        Ok(())
+    }
+
+    /// Returns the digest of all data fed so far without changing the state.
+    pub fn digest(&self) -> Vec<u8> {
+        match &self.state {
+            HashState::Plain(hasher) => hasher.finalize(),
+            HashState::Hmac { inner, outer } => {
+                let mut outer = outer.clone();
+                outer.update(&inner.finalize());
+                outer.finalize()
+            }
+        }
+    }
+
+    /// The algorithm name as reported by the `name` attribute (e.g. `sha256`, `hmac-sha256`).
+    fn name(&self) -> String {
+        match &self.state {
+            HashState::Plain(hasher) => hasher.algorithm().to_string(),
+            HashState::Hmac { inner, .. } => format!("hmac-{}", inner.algorithm()),
+        }
+    }
+
+    /// Size of the digest in bytes.
+    fn digest_size(&self) -> usize {
+        match &self.state {
+            HashState::Plain(hasher) | HashState::Hmac { inner: hasher, .. } => hasher.digest_size(),
+        }
+    }
+
+    /// Internal block size of the underlying algorithm in bytes.
+    fn block_size(&self) -> usize {
+        match &self.state {
+            HashState::Plain(hasher) | HashState::Hmac { inner: hasher, .. } => hasher.algorithm().block_size(),
+        }
+    }
+}
+
+impl PyTrait for HashObject {
+    fn py_type(&self, _heap: &Heap<impl ResourceTracker>) -> Type {
+        match self.state {
+            HashState::Plain(_) => Type::Hash,
+            HashState::Hmac { .. } => Type::Hmac,
+        }
+    }
+
+    fn py_len(&self, _vm: &VM<'_, '_, impl ResourceTracker>) -> Option<usize> {
+        None
+    }
+
+    fn py_eq(&self, _other: &Self, _vm: &mut VM<'_, '_, impl ResourceTracker>) -> Result<bool, ResourceError> {
+        // Hash objects compare by identity (handled at the Value level)
+        Ok(false)
+    }
+
+    fn py_dec_ref_ids(&mut self, _stack: &mut Vec<HeapId>) {
+        // No heap references — all state is owned.
+    }
+
+    fn py_bool(&self, _vm: &VM<'_, '_, impl ResourceTracker>) -> bool {
+        true
+    }
+
+    fn py_repr_fmt(
+        &self,
+        f: &mut impl Write,
+        _vm: &VM<'_, '_, impl ResourceTracker>,
+        _heap_ids: &mut AHashSet<HeapId>,
+    ) -> std::fmt::Result {
+        match &self.state {
+            HashState::Plain(hasher) if hasher.algorithm() == HashAlgorithm::Blake2b => {
+                f.write_str("<_blake2.blake2b object>")
+            }
+            HashState::Plain(hasher) => write!(f, "<{} _hashlib.HASH object>", hasher.algorithm()),
+            HashState::Hmac { .. } => f.write_str("<hmac.HMAC object>"),
+        }
+    }
+
+    fn py_estimate_size(&self) -> usize {
+        std::mem::size_of::<Self>() + 2 * self.block_size()
+    }
+
+    fn py_getattr(&self, attr: &EitherStr, vm: &mut VM<'_, '_, impl ResourceTracker>) -> RunResult<Option<CallResult>> {
+        let value = match attr.static_string() {
+            Some(StaticStrings::Name) => Value::Ref(vm.heap.allocate(HeapData::Str(Str::new(self.name())))?),
+            Some(StaticStrings::DigestSize) => {
+                Value::Int(i64::try_from(self.digest_size()).expect("digest size fits in i64"))
+            }
+            Some(StaticStrings::BlockSize) => {
+                Value::Int(i64::try_from(self.block_size()).expect("block size fits in i64"))
+            }
+            _ => return Err(ExcType::attribute_error(self.py_type(vm.heap), attr.as_str(vm.interns))),
+        };
+        Ok(Some(CallResult::Value(value)))
+    }
+
+    fn py_call_attr(
+        &mut self,
+        _self_id: HeapId,
+        vm: &mut VM<'_, '_, impl ResourceTracker>,
+        attr: &EitherStr,
+        args: ArgValues,
+    ) -> RunResult<CallResult> {
+        let result = match attr.static_string() {
+            Some(StaticStrings::Update) => {
+                let data = args.get_one_arg("update", vm.heap)?;
+                defer_drop!(data, vm);
+                let data = hash_data_arg(data, vm.heap, vm.interns)?;
+                self.update(data, vm.heap)?;
+                Value::None
+            }
+            Some(StaticStrings::Digest) => {
+                args.check_zero_args("digest", vm.heap)?;
+                Value::Ref(vm.heap.allocate(HeapData::Bytes(Bytes::new(self.digest())))?)
+            }
+            Some(StaticStrings::Hexdigest) => {
+                args.check_zero_args("hexdigest", vm.heap)?;
+                Value::Ref(vm.heap.allocate(HeapData::Str(Str::new(to_hex(&self.digest()))))?)
+            }
+            Some(StaticStrings::Copy) => {
+                args.check_zero_args("copy", vm.heap)?;
+                Value::Ref(vm.heap.allocate(HeapData::Hash(Box::new(self.clone())))?)
+            }
+            _ => return Err(ExcType::attribute_error(self.py_type(vm.heap), attr.as_str(vm.interns))),
+        };
+        Ok(CallResult::Value(result))
+    }
+}
+
+/// Extracts the bytes to hash from an argument, matching CPython's error messages.
+///
+/// Strings must be encoded first (`TypeError: Strings must be encoded before hashing`),
+/// and other non-bytes objects are rejected as not supporting the buffer API.
+pub(crate) fn hash_data_arg<'a>(
+    value: &Value,
+    heap: &'a Heap<impl ResourceTracker>,
+    interns: &'a Interns,
+) -> RunResult<&'a [u8]> {
+    match value {
+        Value::InternBytes(id) => Ok(interns.get_bytes(*id)),
+        Value::InternString(_) => Err(ExcType::type_error("Strings must be encoded before hashing")),
+        Value::Ref(id) => match heap.get(*id) {
+            HeapData::Bytes(b) => Ok(b.as_slice()),
+            HeapData::Str(_) => Err(ExcType::type_error("Strings must be encoded before hashing")),
+            _ => Err(ExcType::type_error("object supporting the buffer API required")),
+        },
+        _ => Err(ExcType::type_error("object supporting the buffer API required")),
+    }
+}
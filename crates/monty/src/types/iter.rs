@@ -26,19 +26,31 @@
 //! This allows `advance_on_heap()` to coordinate access without extracting
 //! the iterator from the heap (avoiding `std::mem::replace` overhead).
 //!
+//! Iterators over another iterator and `iter(callable, sentinel)` iterators need the
+//! VM to advance (the first may wrap the second, which calls back into Python code),
+//! which is why `advance_on_heap()` takes the VM rather than just the heap.
+//!
+//! `ForIter` calls the callable of an `iter(callable, sentinel)` iterator itself, so in a
+//! `for` loop it may be a function that pauses for the host, like `f.readline` or an
+//! external function (see `MontyIter::sentinel_callable()`). Elsewhere, e.g. in `list()`
+//! or `next()`, such callables raise `TypeError`.
+//!
 //! ## Builtin Support
 //!
 //! The `iterator_next()` helper implements the `next()` builtin.
 
+use smallvec::smallvec;
+
 use crate::{
     args::ArgValues,
-    bytecode::VM,
-    exception_private::{ExcType, RunResult},
+    bytecode::{CallResult, VM},
+    defer_drop,
+    exception_private::{ExcType, RunError, RunResult},
     heap::{ContainsHeap, DropWithHeap, Heap, HeapData, HeapGuard, HeapId},
     heap_data::HeapDataMut,
     intern::{BytesId, Interns, StringId},
     resource::ResourceTracker,
-    types::{PyTrait, Range, dict_view::DictView, str::allocate_char},
+    types::{PyTrait, Range, allocate_tuple, dict_view::DictView, str::allocate_char},
     value::Value,
 };
 
@@ -63,16 +75,25 @@ impl MontyIter {
     ///
     /// - `iter(iterable)` - Returns an iterator for the iterable. If the argument is
     ///   already an iterator, returns the same object.
-    /// - `iter(callable, sentinel)` - Not yet supported.
+    /// - `iter(callable, sentinel)` - Returns an iterator that calls `callable` with no
+    ///   arguments for each item, stopping when it returns a value equal to `sentinel`.
+    ///   Callables that pause for the host can only be iterated by a `for` loop.
     pub fn init(vm: &mut VM<'_, '_, impl ResourceTracker>, args: ArgValues) -> RunResult<Value> {
         let (iterable, sentinel) = args.get_one_two_args("iter", vm.heap)?;
 
-        if let Some(s) = sentinel {
-            // Two-argument form: iter(callable, sentinel)
-            // This is the sentinel iteration protocol, not yet supported
-            iterable.drop_with_heap(vm);
-            s.drop_with_heap(vm);
-            return Err(ExcType::type_error("iter(callable, sentinel) is not yet supported"));
+        if let Some(sentinel) = sentinel {
+            if !iterable.is_callable(vm.heap) {
+                iterable.drop_with_heap(vm);
+                sentinel.drop_with_heap(vm);
+                return Err(ExcType::type_error("iter(v, w): v must be callable"));
+            }
+            let iter = Self {
+                index: 0,
+                iter_value: IterValue::CallUntilSentinel { exhausted: false },
+                value: allocate_tuple(smallvec![iterable, sentinel], vm.heap)?,
+            };
+            let id = vm.heap.allocate(HeapData::Iter(iter))?;
+            return Ok(Value::Ref(id));
         }
 
         // Check if already an iterator - return self
@@ -110,7 +131,21 @@ impl MontyIter {
                 value,
             })
         } else {
-            let err = ExcType::type_error_not_iterable(value.py_type(vm.heap));
+            let err = if let Value::Ref(id) = value
+                && let HeapData::File(file) = vm.heap.get(id)
+            {
+                // The lines of a file come from the host, which can't be asked for them here.
+                // Calls retry with the lines once the host has read them (see `VM::call_reading_files`).
+                match file.check_readable() {
+                    Ok(()) => {
+                        vm.file_to_read = Some(id);
+                        ExcType::type_error("file objects can't be iterated here, use readlines() instead")
+                    }
+                    Err(err) => err,
+                }
+            } else {
+                ExcType::type_error_not_iterable(value.py_type(vm.heap))
+            };
             value.drop_with_heap(vm);
             Err(err)
         }
@@ -193,6 +228,8 @@ impl MontyIter {
                     expected_len: if *checks_mutation { *len } else { None },
                 })
             }
+            IterValue::Iterator { heap_id } => Some(IterState::Iterator { heap_id: *heap_id }),
+            IterValue::CallUntilSentinel { exhausted } => (!exhausted).then_some(IterState::CallUntilSentinel),
         }
     }
 
@@ -261,7 +298,7 @@ impl MontyIter {
                     Some(Ok(Some(Value::Int(i64::from(bytes[i])))))
                 }
             }
-            IterValue::HeapRef { .. } => None,
+            IterValue::HeapRef { .. } | IterValue::Iterator { .. } | IterValue::CallUntilSentinel { .. } => None,
         }
     }
 
@@ -340,7 +377,34 @@ impl MontyIter {
                 self.index += 1;
                 Ok(Some(item))
             }
+            IterValue::Iterator { heap_id } => advance_on_heap(vm, *heap_id),
+            IterValue::CallUntilSentinel { exhausted } => {
+                if *exhausted {
+                    return Ok(None);
+                }
+                let Value::Ref(pair_id) = &self.value else {
+                    unreachable!("iter(callable, sentinel) holds its arguments in a tuple");
+                };
+                let item = call_until_sentinel(*pair_id, vm)?;
+                *exhausted = item.is_none();
+                Ok(item)
+            }
+        }
+    }
+
+    /// Returns the callable of an `iter(callable, sentinel)` iterator that isn't exhausted.
+    ///
+    /// `ForIter` calls it itself rather than advancing the iterator with `advance_on_heap()`,
+    /// so the call may pause for the host, then checks the item with `sentinel_item()`.
+    pub(crate) fn sentinel_callable(&self, heap: &Heap<impl ResourceTracker>) -> Option<Value> {
+        if !matches!(self.iter_value, IterValue::CallUntilSentinel { exhausted: false }) {
+            return None;
         }
+        let Value::Ref(pair_id) = &self.value else {
+            unreachable!("iter(callable, sentinel) holds its arguments in a tuple");
+        };
+        let (callable, _) = sentinel_pair(*pair_id, heap);
+        Some(callable.clone_with_heap(heap))
     }
 
     /// Returns the remaining size for iterables based on current state.
@@ -348,9 +412,18 @@ impl MontyIter {
     /// For immutable types (Range, Tuple, Str, Bytes, FrozenSet), returns the exact remaining count.
     /// For List, returns current length minus index (may change if list is mutated).
     /// For Dict and Set, returns the captured length minus index (used for size-change detection).
+    /// For another iterator, returns that iterator's size hint, and for `iter(callable, sentinel)`,
+    /// which can't know how many items it will yield, returns 0.
     pub fn size_hint(&self, heap: &Heap<impl ResourceTracker>) -> usize {
         let len = match &self.iter_value {
             IterValue::Range { len, .. } | IterValue::IterStr { len, .. } | IterValue::InternBytes { len, .. } => *len,
+            IterValue::Iterator { heap_id } => {
+                let HeapData::Iter(iter) = heap.get(*heap_id) else {
+                    panic!("Iterator should wrap an iterator on the heap")
+                };
+                return iter.size_hint(heap);
+            }
+            IterValue::CallUntilSentinel { .. } => return 0,
             IterValue::HeapRef { heap_id, len, .. } => {
                 // For List (len=None), check current length dynamically
                 len.unwrap_or_else(|| {
@@ -405,20 +478,20 @@ impl<T: ResourceTracker> Iterator for HeapedMontyIter<'_, '_, '_, T> {
 /// This is more efficient than `std::mem::replace` with a placeholder because
 /// it avoids creating and moving placeholder objects on every iteration.
 ///
+/// Iterators over another iterator advance that iterator, and `iter(callable, sentinel)`
+/// iterators call their callable, which may run Python code.
+///
 /// Returns `Ok(None)` when the iterator is exhausted.
-/// Returns `Err` for dict/set size changes or allocation failures.
-pub(crate) fn advance_on_heap(
-    heap: &mut Heap<impl ResourceTracker>,
-    iter_id: HeapId,
-    interns: &Interns,
-) -> RunResult<Option<Value>> {
+/// Returns `Err` for dict/set size changes, allocation failures, or errors raised by the callable.
+pub(crate) fn advance_on_heap(vm: &mut VM<'_, '_, impl ResourceTracker>, iter_id: HeapId) -> RunResult<Option<Value>> {
+    let heap = &mut *vm.heap;
     // Fast path: Range and InternBytes don't need additional heap access,
     // so we can handle them with a single mutable borrow.
     {
         let HeapDataMut::Iter(iter) = heap.get_mut(iter_id) else {
             panic!("advance_on_heap: expected Iterator on heap");
         };
-        if let Some(result) = iter.try_advance_simple(interns) {
+        if let Some(result) = iter.try_advance_simple(vm.interns) {
             return result;
         }
     }
@@ -451,6 +524,21 @@ pub(crate) fn advance_on_heap(
             };
             (item, None)
         }
+        // The wrapped iterator keeps its own position, so there's nothing to advance here
+        IterState::Iterator { heap_id } => return advance_on_heap(vm, heap_id),
+        IterState::CallUntilSentinel => {
+            let Value::Ref(pair_id) = iter.value() else {
+                unreachable!("iter(callable, sentinel) holds its arguments in a tuple");
+            };
+            let item = call_until_sentinel(*pair_id, vm)?;
+            if item.is_none()
+                && let HeapDataMut::Iter(iter) = vm.heap.get_mut(iter_id)
+                && let IterValue::CallUntilSentinel { exhausted } = &mut iter.iter_value
+            {
+                *exhausted = true;
+            }
+            return Ok(item);
+        }
     };
 
     // Phase 3: Advance the iterator
@@ -462,6 +550,100 @@ pub(crate) fn advance_on_heap(
     Ok(Some(value))
 }
 
+/// Checks an item `ForIter` got by calling the callable of the `iter(callable, sentinel)`
+/// iterator `iter_id` (see `MontyIter::sentinel_callable()`).
+///
+/// Returns `Ok(None)`, marking the iterator exhausted, if `item` equals the sentinel.
+pub(crate) fn sentinel_item(
+    vm: &mut VM<'_, '_, impl ResourceTracker>,
+    iter_id: HeapId,
+    item: Value,
+) -> RunResult<Option<Value>> {
+    let HeapData::Iter(iter) = vm.heap.get(iter_id) else {
+        panic!("sentinel_item: expected Iterator on heap");
+    };
+    let Value::Ref(pair_id) = iter.value() else {
+        unreachable!("iter(callable, sentinel) holds its arguments in a tuple");
+    };
+    let (_, sentinel) = sentinel_pair(*pair_id, vm.heap);
+    let sentinel = sentinel.clone_with_heap(vm.heap);
+    defer_drop!(sentinel, vm);
+
+    let item = check_sentinel(item, sentinel, vm)?;
+    if item.is_none()
+        && let HeapDataMut::Iter(iter) = vm.heap.get_mut(iter_id)
+        && let IterValue::CallUntilSentinel { exhausted } = &mut iter.iter_value
+    {
+        *exhausted = true;
+    }
+    Ok(item)
+}
+
+/// Calls the callable of an `iter(callable, sentinel)` iterator for its next item.
+///
+/// `pair_id` is the iterator's `(callable, sentinel)` tuple. Returns `Ok(None)` once the
+/// callable returns a value equal to the sentinel.
+///
+/// Only `ForIter` can pause for the host to run the callable, so callables that need
+/// to, like file methods and external functions, raise `TypeError` here.
+fn call_until_sentinel(pair_id: HeapId, vm: &mut VM<'_, '_, impl ResourceTracker>) -> RunResult<Option<Value>> {
+    let (callable, sentinel) = sentinel_pair(pair_id, vm.heap);
+    // The callable may drop the iterator, so hold our own references to both
+    let callable = callable.clone_with_heap(vm.heap);
+    let sentinel = sentinel.clone_with_heap(vm.heap);
+    defer_drop!(callable, vm);
+    defer_drop!(sentinel, vm);
+
+    let item = match vm.call_function(callable, ArgValues::Empty)? {
+        CallResult::Value(item) => item,
+        CallResult::FramePushed => vm.run_pushed_frame("iter()")?,
+        CallResult::External(_, args) | CallResult::OsCall(_, args) | CallResult::MethodCall(_, args) => {
+            args.drop_with_heap(vm);
+            return Err(sentinel_host_call_error());
+        }
+        CallResult::AwaitValue(value) => {
+            value.drop_with_heap(vm);
+            return Err(sentinel_host_call_error());
+        }
+    };
+    check_sentinel(item, sentinel, vm)
+}
+
+/// Returns `Ok(None)`, dropping `item`, if it equals `sentinel`, and `Some(item)` otherwise.
+fn check_sentinel(
+    item: Value,
+    sentinel: &Value,
+    vm: &mut VM<'_, '_, impl ResourceTracker>,
+) -> RunResult<Option<Value>> {
+    match item.py_eq(sentinel, vm) {
+        Ok(false) => Ok(Some(item)),
+        Ok(true) => {
+            item.drop_with_heap(vm);
+            Ok(None)
+        }
+        Err(err) => {
+            item.drop_with_heap(vm);
+            Err(err.into())
+        }
+    }
+}
+
+/// Returns the `(callable, sentinel)` pair in the tuple `pair_id` of an `iter(callable, sentinel)` iterator.
+fn sentinel_pair(pair_id: HeapId, heap: &Heap<impl ResourceTracker>) -> (&Value, &Value) {
+    let HeapData::Tuple(pair) = heap.get(pair_id) else {
+        panic!("sentinel_pair: expected the (callable, sentinel) tuple on heap");
+    };
+    let [callable, sentinel] = pair.as_slice() else {
+        panic!("sentinel_pair: expected a (callable, sentinel) pair");
+    };
+    (callable, sentinel)
+}
+
+/// The error for an `iter(callable, sentinel)` callable that pauses for the host outside a `for` loop.
+fn sentinel_host_call_error() -> RunError {
+    ExcType::type_error("iter(v, w): v can only be an external or OS function when iterated by a for loop")
+}
+
 /// Gets an item from a heap-allocated container at the given index.
 ///
 /// Returns `Ok(None)` if the index is out of bounds (for lists that shrunk during iteration).
@@ -567,40 +749,47 @@ fn get_heap_item(
 /// # Arguments
 /// * `iter_value` - Must be an iterator (heap-allocated MontyIter)
 /// * `default` - Optional default value to return when exhausted
-/// * `heap` - The heap for memory operations
-/// * `interns` - String interning table
+/// * `vm` - The VM, which `iter(callable, sentinel)` iterators call back into
 ///
 /// # Errors
 /// Returns `StopIteration` if exhausted with no default, or propagates errors from iteration.
 pub fn iterator_next(
     iter_value: &Value,
     default: Option<Value>,
-    heap: &mut Heap<impl ResourceTracker>,
-    interns: &Interns,
+    vm: &mut VM<'_, '_, impl ResourceTracker>,
 ) -> RunResult<Value> {
     let Value::Ref(iter_id) = iter_value else {
         // Not a heap value - can't be an iterator
         if let Some(d) = default {
-            d.drop_with_heap(heap);
+            d.drop_with_heap(vm);
         }
-        return Err(ExcType::type_error_not_iterable(iter_value.py_type(heap)));
+        return Err(ExcType::type_error_not_iterable(iter_value.py_type(vm.heap)));
     };
 
     // Check that it's actually an iterator
-    if !matches!(heap.get(*iter_id), HeapData::Iter(_)) {
+    if !matches!(vm.heap.get(*iter_id), HeapData::Iter(_)) {
         if let Some(d) = default {
-            d.drop_with_heap(heap);
+            d.drop_with_heap(vm);
         }
-        let data_type = heap.get(*iter_id).py_type(heap);
+        let data_type = vm.heap.get(*iter_id).py_type(vm.heap);
         return Err(ExcType::type_error(format!("'{data_type}' object is not an iterator")));
     }
 
     // Get next item using the MontyIter::advance_on_heap method
-    match advance_on_heap(heap, *iter_id, interns)? {
+    let item = match advance_on_heap(vm, *iter_id) {
+        Ok(item) => item,
+        Err(err) => {
+            if let Some(d) = default {
+                d.drop_with_heap(vm);
+            }
+            return Err(err);
+        }
+    };
+    match item {
         Some(item) => {
             // Drop default if provided since we don't need it
             if let Some(d) = default {
-                d.drop_with_heap(heap);
+                d.drop_with_heap(vm);
             }
             Ok(item)
         }
@@ -635,6 +824,10 @@ enum IterState {
         index: usize,
         expected_len: Option<usize>,
     },
+    /// Iterator over the iterator at `heap_id`, which is advanced instead.
+    Iterator { heap_id: HeapId },
+    /// `iter(callable, sentinel)` iterator that hasn't yet seen the sentinel.
+    CallUntilSentinel,
 }
 
 /// Type-specific iteration data for different Python iterable types.
@@ -681,6 +874,16 @@ enum IterValue {
         len: Option<usize>,
         checks_mutation: bool,
     },
+    /// Iterating over another iterator, as in `list(iter(xs))` or a `for` loop over `csv.reader()`.
+    ///
+    /// Advances the wrapped iterator, so items taken here are gone from it too.
+    Iterator { heap_id: HeapId },
+    /// Calling a callable until it returns a sentinel, from `iter(callable, sentinel)`.
+    ///
+    /// The callable and sentinel are held as a `(callable, sentinel)` tuple in `MontyIter::value`,
+    /// so they're reference counted and traversed by the GC like any other iterated value.
+    /// `exhausted` is set once the sentinel is seen, after which the callable isn't called again.
+    CallUntilSentinel { exhausted: bool },
 }
 
 impl IterValue {
@@ -785,6 +988,8 @@ impl IterValue {
             HeapData::Str(s) => Some(Self::from_str(s.as_str())),
             // Range: copy values for iteration
             HeapData::Range(range) => Some(Self::from_range(range)),
+            // Iterator: advance it in place
+            HeapData::Iter(_) => Some(Self::Iterator { heap_id }),
             // other types are not iterable
             _ => None,
         }
@@ -1,7 +1,6 @@
 use std::fmt::Write;
 
 use ahash::AHashSet;
-use itertools::Itertools;
 use smallvec::SmallVec;
 
 use super::{MontyIter, PyTrait};
@@ -11,9 +10,10 @@ use crate::{
     defer_drop, defer_drop_mut,
     exception_private::{ExcType, RunError, RunResult},
     heap::{DropWithHeap, Heap, HeapData, HeapGuard, HeapId},
+    heap_data::HeapDataMut,
     intern::StaticStrings,
     resource::{ResourceError, ResourceTracker},
-    sorting::{apply_permutation, sort_indices},
+    sorting::sort_values,
     types::Type,
     value::{EitherStr, Value},
 };
@@ -684,38 +684,46 @@ fn do_list_sort(list: &mut List, args: ArgValues, vm: &mut VM<'_, '_, impl Resou
     };
     defer_drop!(key_fn, vm);
 
-    // Step 1: Borrow from the list for in-place sorting
-    let items = list.as_vec_mut();
-
-    // 2. Compute key values if a key function was provided, otherwise we'll sort by the items themselves
-    let mut keys_guard;
-    let (compare_values, vm) = if let Some(f) = key_fn {
-        let keys: Vec<Value> = Vec::with_capacity(items.len());
-        // Use a HeapGuard to ensure that if key function evaluation fails partway through,
-        // we clean up any keys that were successfully computed
-        keys_guard = HeapGuard::new(keys, vm);
-        let (keys, vm) = keys_guard.as_parts_mut();
-        items
-            .iter()
-            .map(|item| {
-                let item = item.clone_with_heap(vm);
-                vm.evaluate_function("sorted() key argument", f, ArgValues::One(item))
-            })
-            .process_results(|keys_iter| keys.extend(keys_iter))?;
-        keys_guard.as_parts()
-    } else {
-        (&*items, vm)
-    };
+    sort_values(list.as_vec_mut(), key_fn.as_ref(), reverse, vm)
+}
 
-    // 3. Sort indices by comparing key values (or items themselves if no key)
-    let len = compare_values.len();
-    let mut indices: Vec<usize> = (0..len).collect();
+/// Runs `f` on the items of the list `id` while they are detached from the heap.
+///
+/// This lets `heapq` reorder a list with comparisons that need the VM. While detached
+/// the list reads as empty, so a comparison that reaches the list again sees a valid
+/// (empty) object rather than an entry checked out of the heap. The items are restored
+/// whether or not `f` succeeds, and the `contains_refs` flag is updated for any items
+/// `f` added. Anything `f` appended to the list itself in the meantime is discarded,
+/// as CPython raises for a heap that is resized during a comparison.
+///
+/// # Panics
+/// Panics if `id` is not a list.
+pub(crate) fn with_detached_items<'a, 'p, T: ResourceTracker, R>(
+    vm: &mut VM<'a, 'p, T>,
+    id: HeapId,
+    f: impl FnOnce(&mut VM<'a, 'p, T>, &mut Vec<Value>) -> R,
+) -> R {
+    let mut items = Heap::with_entry_mut(vm, id, |_, data| {
+        let HeapDataMut::List(list) = data else {
+            panic!("with_detached_items: heap entry is not a list")
+        };
+        std::mem::take(&mut list.items)
+    });
 
-    sort_indices(&mut indices, compare_values, reverse, vm)?;
+    let result = f(vm, &mut items);
 
-    // 4. Rearrange items in-place according to the sorted permutation
-    apply_permutation(items, &mut indices);
-    Ok(())
+    let stray = Heap::with_entry_mut(vm, id, |vm, data| {
+        let HeapDataMut::List(list) = data else {
+            panic!("with_detached_items: heap entry is not a list")
+        };
+        if !list.contains_refs && items.iter().any(|item| matches!(item, Value::Ref(_))) {
+            list.contains_refs = true;
+            vm.heap.mark_potential_cycle();
+        }
+        std::mem::replace(&mut list.items, items)
+    });
+    stray.drop_with_heap(vm);
+    result
 }
 
 /// Writes a formatted sequence of values to a formatter.
@@ -865,7 +873,7 @@ mod tests {
         let new_value = Value::Int(99);
         heap.inc_ref(index_id);
 
-        let mut vm = VM::new(Vec::new(), &mut heap, &interns, PrintWriter::Disabled);
+        let mut vm = VM::new(Vec::new(), &mut heap, &interns, Vec::new(), PrintWriter::Disabled);
         let result = Heap::with_entry_mut(&mut vm, list_id, |vm, mut data| data.py_setitem(key, new_value, vm));
 
         assert!(result.is_ok());
@@ -893,7 +901,7 @@ mod tests {
         let new_value = Value::Int(99);
         heap.inc_ref(index_id);
 
-        let mut vm = VM::new(Vec::new(), &mut heap, &interns, PrintWriter::Disabled);
+        let mut vm = VM::new(Vec::new(), &mut heap, &interns, Vec::new(), PrintWriter::Disabled);
         let result = Heap::with_entry_mut(&mut vm, list_id, |vm, mut data| data.py_setitem(key, new_value, vm));
 
         assert!(result.is_ok());
@@ -919,7 +927,7 @@ mod tests {
         heap.inc_ref(index_id);
 
         // This should fail with IndexError because i64::MAX is out of bounds for a 1-element list
-        let mut vm = VM::new(Vec::new(), &mut heap, &interns, PrintWriter::Disabled);
+        let mut vm = VM::new(Vec::new(), &mut heap, &interns, Vec::new(), PrintWriter::Disabled);
         let result = Heap::with_entry_mut(&mut vm, list_id, |vm, mut data| data.py_setitem(key, new_value, vm));
 
         assert!(result.is_err());
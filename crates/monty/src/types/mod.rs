@@ -6,43 +6,67 @@
 /// The `AbstractValue` trait provides a common interface for all heap-allocated
 /// types, enabling efficient dispatch via `enum_dispatch`.
 pub mod bytes;
+pub mod csv_dialect;
+pub mod csv_sniffer;
+pub mod csv_writer;
 pub mod dataclass;
 pub mod dict;
 pub mod dict_view;
+pub mod file;
+pub mod hash_object;
 pub mod iter;
 pub mod list;
 pub mod long_int;
 pub mod module;
 pub mod namedtuple;
+pub mod object;
+pub mod operator_callable;
 pub mod path;
 pub mod property;
 pub mod py_trait;
 pub mod range;
 pub mod re_match;
 pub mod re_pattern;
+pub mod sequence_matcher;
 pub mod set;
 pub mod slice;
 pub mod str;
+pub mod string_io;
+pub mod r#struct;
+pub mod template;
 pub mod tuple;
 pub mod r#type;
+pub mod uuid;
 
 pub(crate) use bytes::Bytes;
+pub(crate) use csv_dialect::Dialect as CsvDialect;
+pub(crate) use csv_sniffer::CsvSniffer;
+pub(crate) use csv_writer::CsvWriter;
 pub(crate) use dataclass::Dataclass;
 pub(crate) use dict::Dict;
 pub(crate) use dict_view::{DictItemsView, DictKeysView, DictValuesView};
+pub(crate) use file::File;
+pub(crate) use hash_object::HashObject;
 pub(crate) use iter::MontyIter;
 pub(crate) use list::List;
 pub(crate) use long_int::LongInt;
 pub(crate) use module::Module;
 pub(crate) use namedtuple::NamedTuple;
+pub(crate) use object::Object;
+pub(crate) use operator_callable::OperatorCallable;
 pub(crate) use path::Path;
 pub(crate) use property::Property;
 pub(crate) use py_trait::PyTrait;
 pub(crate) use range::Range;
 pub(crate) use re_match::ReMatch;
 pub(crate) use re_pattern::RePattern;
+pub(crate) use sequence_matcher::SequenceMatcher;
 pub(crate) use set::{FrozenSet, Set};
 pub(crate) use slice::Slice;
 pub(crate) use str::Str;
+pub(crate) use string_io::StringIO;
+pub(crate) use r#struct::Struct;
+pub(crate) use template::Template;
 pub(crate) use tuple::{Tuple, allocate_tuple};
 pub(crate) use r#type::Type;
+pub(crate) use uuid::Uuid;
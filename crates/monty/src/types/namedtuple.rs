@@ -111,6 +111,16 @@ impl NamedTuple {
         self.items.len()
     }
 
+    /// Creates a named tuple of the same type holding `items` (ownership transferred).
+    ///
+    /// # Panics
+    ///
+    /// Panics if `items` has a different length than this tuple.
+    #[must_use]
+    pub fn with_items(&self, items: Vec<Value>) -> Self {
+        Self::new(self.name.clone(), self.field_names.clone(), items)
+    }
+
     /// Returns whether the tuple contains any heap references.
     ///
     /// When false, `py_dec_ref_ids` can skip iteration.
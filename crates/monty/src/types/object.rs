@@ -0,0 +1,75 @@
+//! Python `object` type implementation.
+//!
+//! Instances of `object` have no state or attributes of their own: each is equal only to
+//! itself and hashes by identity. Their use is as unique sentinel values, like
+//! `MISSING = object()`, that can't be confused with anything the caller passes in.
+
+use std::{cmp::Ordering, fmt::Write};
+
+use ahash::AHashSet;
+
+use crate::{
+    args::ArgValues,
+    bytecode::VM,
+    exception_private::RunResult,
+    heap::{Heap, HeapData, HeapId},
+    resource::{ResourceError, ResourceTracker},
+    types::{PyTrait, Type},
+    value::Value,
+};
+
+/// A plain `object()` instance.
+#[derive(Debug, Clone, Copy, serde::Serialize, serde::Deserialize)]
+pub(crate) struct Object;
+
+impl Object {
+    /// Creates a new instance from the `object()` constructor call, which takes no arguments.
+    pub fn init(vm: &mut VM<'_, '_, impl ResourceTracker>, args: ArgValues) -> RunResult<Value> {
+        args.check_zero_args("object", vm.heap)?;
+        Ok(Value::Ref(vm.heap.allocate(HeapData::Object(Self))?))
+    }
+}
+
+impl PyTrait for Object {
+    fn py_type(&self, _heap: &Heap<impl ResourceTracker>) -> Type {
+        Type::Object
+    }
+
+    fn py_len(&self, _vm: &VM<'_, '_, impl ResourceTracker>) -> Option<usize> {
+        None
+    }
+
+    fn py_eq(&self, _other: &Self, _vm: &mut VM<'_, '_, impl ResourceTracker>) -> Result<bool, ResourceError> {
+        // Identical objects are found equal before getting here, and distinct ones never are.
+        Ok(false)
+    }
+
+    fn py_cmp(
+        &self,
+        _other: &Self,
+        _vm: &mut VM<'_, '_, impl ResourceTracker>,
+    ) -> Result<Option<Ordering>, ResourceError> {
+        Ok(None)
+    }
+
+    fn py_dec_ref_ids(&mut self, _stack: &mut Vec<HeapId>) {
+        // No heap references.
+    }
+
+    fn py_bool(&self, _vm: &VM<'_, '_, impl ResourceTracker>) -> bool {
+        true
+    }
+
+    fn py_repr_fmt(
+        &self,
+        f: &mut impl Write,
+        _vm: &VM<'_, '_, impl ResourceTracker>,
+        _heap_ids: &mut AHashSet<HeapId>,
+    ) -> std::fmt::Result {
+        f.write_str("<object object>")
+    }
+
+    fn py_estimate_size(&self) -> usize {
+        std::mem::size_of::<Self>()
+    }
+}
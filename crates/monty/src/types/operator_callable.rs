@@ -0,0 +1,340 @@
+//! Callable objects from the `operator` module: `itemgetter`, `attrgetter` and `methodcaller`.
+//!
+//! Each stores the arguments it was created with and applies them to the single object
+//! it is called with, which makes them convenient `key=` functions for `sorted()`,
+//! `min()`/`max()`, `heapq` and `bisect`.
+
+use std::fmt::Write;
+
+use ahash::AHashSet;
+
+use crate::{
+    args::ArgValues,
+    bytecode::{CallResult, VM},
+    defer_drop,
+    exception_private::{ExcType, RunResult},
+    heap::{ContainsHeap, DropWithHeap, Heap, HeapData, HeapId},
+    resource::{ResourceError, ResourceTracker},
+    types::{Dict, PyTrait, Type, allocate_tuple, str::StringRepr},
+    value::{EitherStr, Value},
+};
+
+/// An `operator.itemgetter`, `operator.attrgetter` or `operator.methodcaller` object.
+#[derive(Debug, serde::Serialize, serde::Deserialize)]
+pub(crate) enum OperatorCallable {
+    /// `itemgetter(*items)`: returns `obj[item]`, or a tuple for several items.
+    ItemGetter(Vec<Value>),
+    /// `attrgetter(*attrs)`: returns `obj.attr`, or a tuple for several attributes.
+    ///
+    /// Each attribute is stored split on dots, so `'a.b'` looks up `obj.a.b`.
+    AttrGetter(Vec<Vec<EitherStr>>),
+    /// `methodcaller(name, /, *args, **kwargs)`: returns `obj.name(*args, **kwargs)`.
+    MethodCaller {
+        name: EitherStr,
+        args: Vec<Value>,
+        /// A dict of the keyword arguments, if any were given.
+        kwargs: Option<Value>,
+    },
+}
+
+impl OperatorCallable {
+    /// `itemgetter(item, /, *items)` constructor.
+    pub fn init_item_getter(vm: &mut VM<'_, '_, impl ResourceTracker>, args: ArgValues) -> RunResult<Value> {
+        let items: Vec<Value> = args.into_pos_only("itemgetter", vm.heap)?.collect();
+        if items.is_empty() {
+            return Err(ExcType::type_error("itemgetter expected 1 argument, got 0"));
+        }
+        Ok(Value::Ref(
+            vm.heap.allocate(HeapData::OperatorCallable(Self::ItemGetter(items)))?,
+        ))
+    }
+
+    /// `attrgetter(attr, /, *attrs)` constructor.
+    pub fn init_attr_getter(vm: &mut VM<'_, '_, impl ResourceTracker>, args: ArgValues) -> RunResult<Value> {
+        let attrs = args.into_pos_only("attrgetter", vm.heap)?;
+        defer_drop!(attrs, vm);
+        if attrs.len() == 0 {
+            return Err(ExcType::type_error("attrgetter expected 1 argument, got 0"));
+        }
+
+        let mut paths = Vec::with_capacity(attrs.len());
+        for attr in attrs.as_slice() {
+            let Some(name) = attr.as_either_str(vm.heap) else {
+                return Err(ExcType::type_error("attribute name must be a string"));
+            };
+            let path = match name {
+                EitherStr::Interned(id) if !vm.interns.get_str(id).contains('.') => vec![name],
+                name => name
+                    .into_string(vm.interns)
+                    .split('.')
+                    .map(|part| EitherStr::from(part.to_owned()))
+                    .collect(),
+            };
+            paths.push(path);
+        }
+        Ok(Value::Ref(
+            vm.heap.allocate(HeapData::OperatorCallable(Self::AttrGetter(paths)))?,
+        ))
+    }
+
+    /// `methodcaller(name, /, *args, **kwargs)` constructor.
+    pub fn init_method_caller(vm: &mut VM<'_, '_, impl ResourceTracker>, args: ArgValues) -> RunResult<Value> {
+        let (mut pos, kwargs) = args.into_parts();
+        let Some(name) = pos.next() else {
+            kwargs.drop_with_heap(vm);
+            return Err(ExcType::type_error(
+                "methodcaller needs at least one argument, the method name",
+            ));
+        };
+        let name_str = name.as_either_str(vm.heap);
+        name.drop_with_heap(vm);
+        let Some(name) = name_str else {
+            pos.drop_with_heap(vm);
+            kwargs.drop_with_heap(vm);
+            return Err(ExcType::type_error("method name must be a string"));
+        };
+        // Heap strings naming builtin methods are interned so method dispatch finds them.
+        let name = match name {
+            EitherStr::Heap(s) => EitherStr::from(s),
+            interned @ EitherStr::Interned(_) => interned,
+        };
+
+        let args: Vec<Value> = pos.collect();
+        let kwargs = if kwargs.is_empty() {
+            None
+        } else {
+            match Dict::from_pairs(kwargs.into_iter().collect(), vm) {
+                Ok(dict) => Some(Value::Ref(vm.heap.allocate(HeapData::Dict(dict))?)),
+                Err(err) => {
+                    args.drop_with_heap(vm);
+                    return Err(err);
+                }
+            }
+        };
+        Ok(Value::Ref(vm.heap.allocate(HeapData::OperatorCallable(
+            Self::MethodCaller { name, args, kwargs },
+        ))?))
+    }
+
+    /// Calls the object stored at `id` with `args`, which must be the single object to
+    /// apply it to.
+    ///
+    /// `methodcaller` returns whatever the method call does, so it may need the host.
+    pub fn call(vm: &mut VM<'_, '_, impl ResourceTracker>, id: HeapId, args: ArgValues) -> RunResult<CallResult> {
+        let HeapData::OperatorCallable(callable) = vm.heap.get(id) else {
+            unreachable!("OperatorCallable::call: heap entry is not an operator callable")
+        };
+        // Take owned copies so the heap is free while the lookups run.
+        let callable = callable.clone_with_heap(vm.heap);
+        defer_drop!(callable, vm);
+
+        let obj = args.get_one_arg(callable.name(), vm.heap)?;
+        match callable {
+            Self::ItemGetter(items) => {
+                defer_drop!(obj, vm);
+                if let [item] = items.as_slice() {
+                    return obj.py_getitem(item, vm).map(CallResult::Value);
+                }
+                let mut values = Vec::with_capacity(items.len());
+                for item in items {
+                    match obj.py_getitem(item, vm) {
+                        Ok(value) => values.push(value),
+                        Err(err) => {
+                            values.drop_with_heap(vm);
+                            return Err(err);
+                        }
+                    }
+                }
+                Ok(CallResult::Value(allocate_tuple(values.into(), vm.heap)?))
+            }
+            Self::AttrGetter(paths) => {
+                defer_drop!(obj, vm);
+                if let [path] = paths.as_slice() {
+                    return get_attr_path(obj, path, vm).map(CallResult::Value);
+                }
+                let mut values = Vec::with_capacity(paths.len());
+                for path in paths {
+                    match get_attr_path(obj, path, vm) {
+                        Ok(value) => values.push(value),
+                        Err(err) => {
+                            values.drop_with_heap(vm);
+                            return Err(err);
+                        }
+                    }
+                }
+                Ok(CallResult::Value(allocate_tuple(values.into(), vm.heap)?))
+            }
+            Self::MethodCaller { name, args, kwargs } => {
+                let args = args.iter().map(|arg| arg.clone_with_heap(vm)).collect();
+                let kwargs = kwargs.as_ref().map(|kwargs| kwargs.clone_with_heap(vm));
+                vm.call_method(obj, name, args, kwargs)
+            }
+        }
+    }
+
+    /// Returns the Python-visible name, used in argument errors.
+    fn name(&self) -> &'static str {
+        match self {
+            Self::ItemGetter(_) => "itemgetter",
+            Self::AttrGetter(_) => "attrgetter",
+            Self::MethodCaller { .. } => "methodcaller",
+        }
+    }
+
+    /// Returns a copy that owns new references to the stored values.
+    fn clone_with_heap(&self, heap: &impl ContainsHeap) -> Self {
+        match self {
+            Self::ItemGetter(items) => Self::ItemGetter(items.iter().map(|item| item.clone_with_heap(heap)).collect()),
+            Self::AttrGetter(paths) => Self::AttrGetter(paths.clone()),
+            Self::MethodCaller { name, args, kwargs } => Self::MethodCaller {
+                name: name.clone(),
+                args: args.iter().map(|arg| arg.clone_with_heap(heap)).collect(),
+                kwargs: kwargs.as_ref().map(|kwargs| kwargs.clone_with_heap(heap)),
+            },
+        }
+    }
+
+    /// Returns the stored values, for the garbage collector.
+    pub fn values(&self) -> impl Iterator<Item = &Value> {
+        let (items, kwargs): (&[Value], Option<&Value>) = match self {
+            Self::ItemGetter(items) => (items, None),
+            Self::AttrGetter(_) => (&[], None),
+            Self::MethodCaller { args, kwargs, .. } => (args, kwargs.as_ref()),
+        };
+        items.iter().chain(kwargs)
+    }
+
+    /// Returns whether any stored value is a heap reference.
+    pub fn has_refs(&self) -> bool {
+        self.values().any(|value| matches!(value, Value::Ref(_)))
+    }
+}
+
+impl DropWithHeap for OperatorCallable {
+    fn drop_with_heap<H: ContainsHeap>(self, heap: &mut H) {
+        match self {
+            Self::ItemGetter(items) => items.drop_with_heap(heap),
+            Self::AttrGetter(_) => {}
+            Self::MethodCaller { args, kwargs, .. } => {
+                args.drop_with_heap(heap);
+                kwargs.drop_with_heap(heap);
+            }
+        }
+    }
+}
+
+/// Follows a dotted attribute path from `obj`, returning the final attribute.
+fn get_attr_path(obj: &Value, path: &[EitherStr], vm: &mut VM<'_, '_, impl ResourceTracker>) -> RunResult<Value> {
+    let mut current = obj.clone_with_heap(vm);
+    for attr in path {
+        let result = current.py_getattr(attr, vm);
+        current.drop_with_heap(vm);
+        current = match result? {
+            CallResult::Value(value) => value,
+            // attrgetter() only retrieves attribute values, like getattr()
+            _ => return Err(ExcType::type_error("attrgetter(): attribute is not a simple value")),
+        };
+    }
+    Ok(current)
+}
+
+impl PyTrait for OperatorCallable {
+    fn py_type(&self, _heap: &Heap<impl ResourceTracker>) -> Type {
+        match self {
+            Self::ItemGetter(_) => Type::ItemGetter,
+            Self::AttrGetter(_) => Type::AttrGetter,
+            Self::MethodCaller { .. } => Type::MethodCaller,
+        }
+    }
+
+    fn py_len(&self, _vm: &VM<'_, '_, impl ResourceTracker>) -> Option<usize> {
+        None
+    }
+
+    fn py_eq(&self, _other: &Self, _vm: &mut VM<'_, '_, impl ResourceTracker>) -> Result<bool, ResourceError> {
+        // Operator callables compare by identity (handled at the Value level)
+        Ok(false)
+    }
+
+    fn py_dec_ref_ids(&mut self, stack: &mut Vec<HeapId>) {
+        match self {
+            Self::ItemGetter(items) => {
+                for item in items {
+                    item.py_dec_ref_ids(stack);
+                }
+            }
+            Self::AttrGetter(_) => {}
+            Self::MethodCaller { args, kwargs, .. } => {
+                for arg in args {
+                    arg.py_dec_ref_ids(stack);
+                }
+                if let Some(kwargs) = kwargs {
+                    kwargs.py_dec_ref_ids(stack);
+                }
+            }
+        }
+    }
+
+    fn py_bool(&self, _vm: &VM<'_, '_, impl ResourceTracker>) -> bool {
+        true
+    }
+
+    fn py_repr_fmt(
+        &self,
+        f: &mut impl Write,
+        vm: &VM<'_, '_, impl ResourceTracker>,
+        heap_ids: &mut AHashSet<HeapId>,
+    ) -> std::fmt::Result {
+        write!(f, "{}(", self.py_type(vm.heap))?;
+        match self {
+            Self::ItemGetter(items) => {
+                for (i, item) in items.iter().enumerate() {
+                    if i > 0 {
+                        f.write_str(", ")?;
+                    }
+                    item.py_repr_fmt(f, vm, heap_ids)?;
+                }
+            }
+            Self::AttrGetter(paths) => {
+                for (i, path) in paths.iter().enumerate() {
+                    if i > 0 {
+                        f.write_str(", ")?;
+                    }
+                    let dotted: Vec<&str> = path.iter().map(|attr| attr.as_str(vm.interns)).collect();
+                    write!(f, "{}", StringRepr(&dotted.join(".")))?;
+                }
+            }
+            Self::MethodCaller { name, args, kwargs } => {
+                write!(f, "{}", StringRepr(name.as_str(vm.interns)))?;
+                for arg in args {
+                    f.write_str(", ")?;
+                    arg.py_repr_fmt(f, vm, heap_ids)?;
+                }
+                if let Some(Value::Ref(id)) = kwargs
+                    && let HeapData::Dict(dict) = vm.heap.get(*id)
+                {
+                    for (key, value) in dict {
+                        write!(f, ", {}=", key.py_str(vm))?;
+                        value.py_repr_fmt(f, vm, heap_ids)?;
+                    }
+                }
+            }
+        }
+        f.write_char(')')
+    }
+
+    fn py_estimate_size(&self) -> usize {
+        std::mem::size_of::<Self>()
+            + match self {
+                Self::ItemGetter(items) => items.len() * std::mem::size_of::<Value>(),
+                Self::AttrGetter(paths) => paths
+                    .iter()
+                    .flatten()
+                    .map(|attr| std::mem::size_of::<EitherStr>() + attr.py_estimate_size())
+                    .sum(),
+                Self::MethodCaller { name, args, .. } => {
+                    name.py_estimate_size() + args.len() * std::mem::size_of::<Value>()
+                }
+            }
+    }
+}
@@ -4,7 +4,7 @@
 //! (require `OsAccess` implementation). Pure methods are handled directly by the VM,
 //! while filesystem methods yield external function calls for the host to resolve.
 
-use std::fmt::Write;
+use std::{cmp::Ordering, fmt::Write};
 
 use ahash::AHashSet;
 use smallvec::SmallVec;
@@ -13,12 +13,18 @@ use crate::{
     args::{ArgValues, KwargsValues},
     bytecode::{CallResult, VM},
     defer_drop,
-    exception_private::{ExcType, RunResult},
+    exception_private::{ExcType, RunError, RunResult, SimpleException},
     heap::{DropWithHeap, Heap, HeapData, HeapId},
     intern::{Interns, StaticStrings},
-    os::OsFunction,
+    modules::os_path,
+    os::{OsFunction, OsResultConversion},
+    os_handler::glob_component_match,
     resource::{ResourceError, ResourceTracker},
-    types::{PyTrait, Str, Type, allocate_tuple},
+    types::{
+        PyTrait, Str, Type, allocate_tuple,
+        file::{OPEN_PARAMS, open_file},
+        str::{StringRepr, allocate_string},
+    },
     value::{EitherStr, Value},
 };
 
@@ -56,27 +62,48 @@ impl Path {
 
     /// Returns the final component of the path.
     ///
-    /// Returns an empty string if the path ends with a separator or is empty.
+    /// Returns an empty string for a root path or the empty path `.`.
     #[must_use]
     pub fn name(&self) -> &str {
-        self.path.rsplit_once('/').map_or(self.path.as_str(), |(_, name)| name)
+        if self.path == "." {
+            return "";
+        }
+        os_path::split(&self.path).1
     }
 
     /// Returns the path without its final component (parent directory).
     ///
     /// For relative paths without a directory (like `file.txt`), returns `.`.
-    /// Returns `None` only for the root path `/`.
+    /// Returns `None` only for root paths (`/` or `//`).
     #[must_use]
     pub fn parent(&self) -> Option<&str> {
-        if self.path == "/" {
-            return None;
-        }
-        match self.path.rsplit_once('/') {
-            Some((parent, _)) => Some(if parent.is_empty() { "/" } else { parent }),
-            None => Some("."), // Relative path without directory component
+        let parent = parent_of(&self.path);
+        (parent != self.path || self.path == ".").then_some(parent)
+    }
+
+    /// Returns the logical ancestors of the path, nearest first, as for `Path.parents`.
+    #[must_use]
+    pub fn parents(&self) -> Vec<&str> {
+        let mut parents = Vec::new();
+        let mut current = self.path.as_str();
+        loop {
+            let parent = parent_of(current);
+            if parent == current {
+                return parents;
+            }
+            parents.push(parent);
+            current = parent;
         }
     }
 
+    /// Returns the root of the path: `/` (or `//`) for absolute paths, empty otherwise.
+    ///
+    /// POSIX paths have no drive, so this is also the path's `anchor`.
+    #[must_use]
+    pub fn root(&self) -> &str {
+        root_of(&self.path)
+    }
+
     /// Returns the final component without its last suffix.
     ///
     /// If the name has multiple suffixes (e.g., "file.tar.gz"), only the
@@ -136,20 +163,12 @@ impl Path {
     /// Absolute paths start with "/" as the first component.
     #[must_use]
     pub fn parts(&self) -> Vec<&str> {
-        if self.path.is_empty() {
-            return Vec::new();
-        }
-
+        let root = self.root();
         let mut parts = Vec::new();
-        if self.path.starts_with('/') {
-            parts.push("/");
-            let rest = &self.path[1..];
-            if !rest.is_empty() {
-                parts.extend(rest.split('/').filter(|s| !s.is_empty()));
-            }
-        } else {
-            parts.extend(self.path.split('/').filter(|s| !s.is_empty()));
+        if !root.is_empty() {
+            parts.push(root);
         }
+        parts.extend(tail_of(&self.path));
         parts
     }
 
@@ -188,17 +207,15 @@ impl Path {
             return Err("Path has no name".to_owned());
         }
 
-        if let Some(parent) = self.parent() {
-            if parent == "/" {
-                Ok(format!("/{name}"))
-            } else if parent == "." {
-                // Relative path without directory - just use the new name
-                Ok(name.to_owned())
-            } else {
-                Ok(format!("{parent}/{name}"))
-            }
-        } else {
+        let parent = parent_of(&self.path);
+        if parent.ends_with('/') {
+            // The parent is the root
+            Ok(format!("{parent}{name}"))
+        } else if parent == "." {
+            // Relative path without directory - just use the new name
             Ok(name.to_owned())
+        } else {
+            Ok(format!("{parent}/{name}"))
         }
     }
 
@@ -246,6 +263,99 @@ impl Path {
         self.with_name(&new_name)
     }
 
+    /// Returns this path relative to `other`, as for `Path.relative_to(other, walk_up=False)`.
+    ///
+    /// With `walk_up`, `other` may be any path with the same root, and `..` segments are added
+    /// to walk up from it.
+    ///
+    /// # Errors
+    /// Returns the `ValueError` message if the path is not relative to `other`.
+    pub fn relative_to(&self, other: &Self, walk_up: bool) -> Result<String, String> {
+        let (root, tail) = (self.root(), tail_of(&self.path));
+        let candidates = std::iter::once(other.as_str()).chain(other.parents());
+        for (step, candidate) in candidates.enumerate() {
+            let candidate_tail = tail_of(candidate);
+            if root_of(candidate) == root && tail.starts_with(&candidate_tail) {
+                let mut parts = vec![".."; step];
+                parts.extend_from_slice(&tail[candidate_tail.len()..]);
+                return Ok(parts.join("/"));
+            } else if !walk_up {
+                return Err(format!(
+                    "{} is not in the subpath of {}",
+                    StringRepr(&self.path),
+                    StringRepr(&other.path)
+                ));
+            } else if candidate_tail.last() == Some(&"..") {
+                return Err(format!("'..' segment in {} cannot be walked", StringRepr(&other.path)));
+            }
+        }
+        Err(format!(
+            "{} and {} have different anchors",
+            StringRepr(&self.path),
+            StringRepr(&other.path)
+        ))
+    }
+
+    /// Returns whether this path is `other` or one of its descendants.
+    #[must_use]
+    pub fn is_relative_to(&self, other: &Self) -> bool {
+        self.root() == other.root() && tail_of(&self.path).starts_with(&tail_of(&other.path))
+    }
+
+    /// Matches the path against a glob pattern, as for `Path.match(pattern)`.
+    ///
+    /// A relative pattern is matched from the right, an absolute one against the whole path;
+    /// each pattern segment matches a single path segment (there is no `**`).
+    ///
+    /// # Errors
+    /// Returns the `ValueError` message if the pattern is empty.
+    pub fn matches(&self, pattern: &Self, case_sensitive: bool) -> Result<bool, String> {
+        let path_parts = self.parts();
+        let pattern_parts = pattern.parts();
+        if pattern_parts.is_empty() {
+            return Err("empty pattern".to_owned());
+        }
+        if path_parts.len() < pattern_parts.len()
+            || (path_parts.len() > pattern_parts.len() && !pattern.root().is_empty())
+        {
+            return Ok(false);
+        }
+        Ok(path_parts
+            .iter()
+            .rev()
+            .zip(pattern_parts.iter().rev())
+            .all(|(part, pattern)| glob_part_match(pattern, part, case_sensitive)))
+    }
+
+    /// Matches the whole path against a glob pattern, as for `Path.full_match(pattern)`.
+    ///
+    /// A `**` segment matches any number of segments, but at least one at the end of the pattern.
+    #[must_use]
+    pub fn full_match(&self, pattern: &Self, case_sensitive: bool) -> bool {
+        fn match_parts(pattern: &[&str], parts: &[&str], case_sensitive: bool) -> bool {
+            match pattern.split_first() {
+                None => parts.is_empty(),
+                Some((&"**", [])) => !parts.is_empty(),
+                Some((&"**", rest)) => (0..=parts.len()).any(|skip| match_parts(rest, &parts[skip..], case_sensitive)),
+                // Wildcards only match non-empty segments, so `/*` doesn't match `/`
+                Some((first, rest)) => parts.split_first().is_some_and(|(part, parts_rest)| {
+                    (first.is_empty() || !part.is_empty())
+                        && glob_part_match(first, part, case_sensitive)
+                        && match_parts(rest, parts_rest, case_sensitive)
+                }),
+            }
+        }
+        // The empty path `.` is matched as an empty string
+        fn segments(path: &str) -> Vec<&str> {
+            if path == "." {
+                vec![""]
+            } else {
+                path.split('/').collect()
+            }
+        }
+        match_parts(&segments(&pattern.path), &segments(&self.path), case_sensitive)
+    }
+
     /// Returns the path as a POSIX string (forward slashes).
     ///
     /// Since paths are already stored in POSIX format, this just returns the path.
@@ -263,25 +373,39 @@ impl Path {
     /// - If an absolute path appears, it replaces everything before it.
     pub fn init(vm: &mut VM<'_, '_, impl ResourceTracker>, args: ArgValues) -> RunResult<Value> {
         let heap = &mut *vm.heap;
-        let interns = vm.interns;
         let pos_args = args.into_pos_only("Path", heap)?;
         defer_drop!(pos_args, heap);
+        let path = Self::from_segments(pos_args.as_slice(), heap, vm.interns)?;
+        Ok(Value::Ref(heap.allocate(HeapData::Path(path))?))
+    }
 
-        let path = match pos_args.as_slice() {
-            [] => {
-                // No arguments, return Path('.')
-                Self::new(".".to_owned())
-            }
-            [single] => {
-                // Single argument, just convert to Path
-                Self::new(extract_path_string(single, heap, interns)?.to_owned())
-            }
+    /// `Path.home()`: the user's home directory, from the host's `HOME` environment variable.
+    pub fn home(vm: &mut VM<'_, '_, impl ResourceTracker>, args: ArgValues) -> RunResult<CallResult> {
+        args.check_zero_args("home", vm.heap)?;
+        home_call(vm, String::new())
+    }
+
+    /// `Path.cwd()`: the current directory, as the host's absolute form of `Path('.')`.
+    pub fn cwd(vm: &mut VM<'_, '_, impl ResourceTracker>, args: ArgValues) -> RunResult<CallResult> {
+        args.check_zero_args("cwd", vm.heap)?;
+        let path = Value::Ref(vm.heap.allocate(HeapData::Path(Self::new(".".to_owned())))?);
+        vm.set_os_result_conversion(OsResultConversion::ToPath);
+        Ok(CallResult::OsCall(OsFunction::Absolute, ArgValues::One(path)))
+    }
+
+    /// Joins path segments as the `Path()` constructor and `with_segments()` do.
+    ///
+    /// - No segments gives `Path('.')`
+    /// - `('a', 'b', 'c')` gives `Path('a/b/c')`
+    /// - If an absolute path appears, it replaces everything before it.
+    fn from_segments(segments: &[Value], heap: &mut Heap<impl ResourceTracker>, interns: &Interns) -> RunResult<Self> {
+        match segments {
+            [] => Ok(Self::new(".".to_owned())),
             [first_arg, rest @ ..] => {
                 let base = Self::new(extract_path_string(first_arg, heap, interns)?.to_owned());
-                fold_joinpath(base, rest, heap, interns)?
+                fold_joinpath(base, rest, heap, interns)
             }
-        };
-        Ok(Value::Ref(heap.allocate(HeapData::Path(path))?))
+        }
     }
 }
 
@@ -351,23 +475,131 @@ pub(crate) fn path_div(
     Ok(Some(Value::Ref(heap.allocate(HeapData::Path(Path::new(result)))?)))
 }
 
-/// Normalizes a path string to POSIX format.
+/// Handles the `/` operator with a `str` on the left and a `Path` on the right.
+///
+/// In Python, `'/usr' / Path('bin')` produces `Path('/usr/bin')`.
+pub(crate) fn path_rdiv(
+    other: &Value,
+    path_id: HeapId,
+    heap: &mut Heap<impl ResourceTracker>,
+    interns: &Interns,
+) -> RunResult<Option<Value>> {
+    let other_str = match other {
+        Value::InternString(string_id) => interns.get_str(*string_id),
+        Value::Ref(other_id) => match heap.get(*other_id) {
+            HeapData::Str(s) => s.as_str(),
+            _ => return Ok(None),
+        },
+        _ => return Ok(None),
+    };
+    let HeapData::Path(path) = heap.get(path_id) else {
+        return Ok(None);
+    };
+
+    let result = Path::new(other_str.to_owned()).joinpath(path.as_str());
+    Ok(Some(Value::Ref(heap.allocate(HeapData::Path(Path::new(result)))?)))
+}
+
+/// Builds a `Path` from the path the host returned for `OsFunction::Absolute` or `OsFunction::Resolve`.
+///
+/// Implements `OsResultConversion::ToPath`; the host may return a `str` or a `Path`.
+pub(crate) fn path_from(value: Value, vm: &mut VM<'_, '_, impl ResourceTracker>) -> RunResult<Value> {
+    defer_drop!(value, vm);
+    let Ok(path) = fspath_arg(value, vm.heap, vm.interns) else {
+        return Err(ExcType::type_error(format!(
+            "path result must be str or Path, not {}",
+            value.py_type(vm.heap)
+        )));
+    };
+    Ok(Value::Ref(vm.heap.allocate(HeapData::Path(Path::new(path)))?))
+}
+
+/// Builds the result of `expanduser()` or `Path.home()` from the `HOME` variable the host
+/// returned for `OsFunction::Getenv`, joining `rest` (the path after `~/`) onto it.
+///
+/// Implements `OsResultConversion::ExpandUser`.
+pub(crate) fn expanduser_from(value: Value, rest: &str, vm: &mut VM<'_, '_, impl ResourceTracker>) -> RunResult<Value> {
+    defer_drop!(value, vm);
+    if matches!(value, Value::None) {
+        return Err(home_error());
+    }
+    let Ok(home) = fspath_arg(value, vm.heap, vm.interns) else {
+        return Err(ExcType::type_error(format!(
+            "home directory must be str, not {}",
+            value.py_type(vm.heap)
+        )));
+    };
+    let path = Path::new(Path::new(home).joinpath(rest));
+    Ok(Value::Ref(vm.heap.allocate(HeapData::Path(path))?))
+}
+
+/// Asks the host for the `HOME` environment variable, to be joined with `rest` by `expanduser_from`.
+fn home_call(vm: &mut VM<'_, '_, impl ResourceTracker>, rest: String) -> RunResult<CallResult> {
+    let key = allocate_string("HOME".to_owned(), vm.heap)?;
+    vm.set_os_result_conversion(OsResultConversion::ExpandUser { rest });
+    Ok(CallResult::OsCall(OsFunction::Getenv, ArgValues::Two(key, Value::None)))
+}
+
+/// The `RuntimeError` CPython raises when it can't find a home directory to expand `~` to.
+fn home_error() -> RunError {
+    SimpleException::new_msg(ExcType::RuntimeError, "Could not determine home directory.").into()
+}
+
+/// Normalizes a path string to POSIX format, as `PurePosixPath` does.
 ///
 /// - Converts backslashes to forward slashes
-/// - Removes trailing slashes (except for root "/")
-/// - Does NOT resolve `.` or `..` components (that requires I/O for symlinks)
+/// - Collapses repeated slashes, keeping exactly two leading slashes (a distinct POSIX root)
+/// - Removes `.` components and trailing slashes, so the empty path becomes `.`
+/// - Does NOT resolve `..` components (that requires I/O for symlinks)
 fn normalize_path(mut path: String) -> String {
     // Convert backslashes to forward slashes
     if path.contains('\\') {
         path = path.replace('\\', "/");
     }
 
-    // Remove trailing slashes, but keep root "/"
-    while path.len() > 1 && path.ends_with('/') {
-        path.pop();
+    let root = root_of(&path);
+    let tail = tail_of(&path);
+    if root.is_empty() && tail.is_empty() {
+        return ".".to_owned();
+    }
+    format!("{root}{}", tail.join("/"))
+}
+
+/// Returns the root of a path: `//` for exactly two leading slashes, `/` for one or more than two.
+fn root_of(path: &str) -> &str {
+    if path.starts_with("//") && !path.starts_with("///") {
+        "//"
+    } else if path.starts_with('/') {
+        "/"
+    } else {
+        ""
+    }
+}
+
+/// Returns the components of a path after its root, skipping empty and `.` components.
+fn tail_of(path: &str) -> Vec<&str> {
+    path.split('/')
+        .filter(|part| !part.is_empty() && *part != ".")
+        .collect()
+}
+
+/// Returns the parent of a normalized path: the root is its own parent, as is `.`.
+fn parent_of(path: &str) -> &str {
+    let root = root_of(path);
+    match path[root.len()..].rfind('/') {
+        Some(index) => &path[..root.len() + index],
+        None if !root.is_empty() => root,
+        None => ".",
     }
+}
 
-    path
+/// Matches a path segment against a glob pattern segment, optionally ignoring case.
+fn glob_part_match(pattern: &str, part: &str, case_sensitive: bool) -> bool {
+    if case_sensitive {
+        glob_component_match(pattern, part)
+    } else {
+        glob_component_match(&pattern.to_lowercase(), &part.to_lowercase())
+    }
 }
 
 /// Prepends the path string argument to existing arguments for OS calls.
@@ -393,11 +625,153 @@ fn prepend_path_arg(path_arg: Value, args: ArgValues) -> ArgValues {
     }
 }
 
+/// Extracts the path string from a `str` or `Path` argument to an OS function.
+///
+/// Raises the same `TypeError` as CPython's `os.fspath()` for any other type.
+pub(crate) fn fspath_arg(value: &Value, heap: &Heap<impl ResourceTracker>, interns: &Interns) -> RunResult<String> {
+    match value {
+        Value::InternString(string_id) => return Ok(interns.get_str(*string_id).to_owned()),
+        Value::Ref(heap_id) => match heap.get(*heap_id) {
+            HeapData::Str(s) => return Ok(s.as_str().to_owned()),
+            HeapData::Path(p) => return Ok(p.as_str().to_owned()),
+            _ => {}
+        },
+        _ => {}
+    }
+    Err(ExcType::type_error(format!(
+        "expected str, bytes or os.PathLike object, not {}",
+        value.py_type(heap)
+    )))
+}
+
+/// Builds an OS call for a module function (e.g. `os.walk()`) whose leading parameters are paths.
+///
+/// The first `N` positional arguments are converted to `Path` objects so hosts receive
+/// paths however they were passed. Any other arguments are passed through for the host
+/// to interpret, as they are for `Path` methods.
+pub(crate) fn path_os_call<const N: usize>(
+    function: OsFunction,
+    func_name: &str,
+    params: [&str; N],
+    args: ArgValues,
+    heap: &mut Heap<impl ResourceTracker>,
+    interns: &Interns,
+) -> RunResult<CallResult> {
+    let (pos, kwargs) = args.into_parts();
+    let mut pos: Vec<Value> = pos.collect();
+    if pos.len() < N {
+        let missing = &params[pos.len()..];
+        pos.drop_with_heap(heap);
+        kwargs.drop_with_heap(heap);
+        return Err(ExcType::type_error_missing_positional_with_names(func_name, missing));
+    }
+
+    let paths: RunResult<Vec<String>> = pos[..N].iter().map(|value| fspath_arg(value, heap, interns)).collect();
+    let paths = match paths {
+        Ok(paths) => paths,
+        Err(err) => {
+            pos.drop_with_heap(heap);
+            kwargs.drop_with_heap(heap);
+            return Err(err);
+        }
+    };
+    for (index, path) in paths.into_iter().enumerate() {
+        match heap.allocate(HeapData::Path(Path::new(path))) {
+            Ok(id) => {
+                let old = std::mem::replace(&mut pos[index], Value::Ref(id));
+                old.drop_with_heap(heap);
+            }
+            Err(err) => {
+                pos.drop_with_heap(heap);
+                kwargs.drop_with_heap(heap);
+                return Err(err.into());
+            }
+        }
+    }
+    Ok(CallResult::OsCall(function, ArgValues::ArgsKargs { args: pos, kwargs }))
+}
+
 impl Path {
+    /// `Path.open(mode='r', buffering=-1, encoding=None, errors=None, newline=None)`.
+    ///
+    /// Takes the same arguments as the `open()` builtin after `file`.
+    fn open(&self, vm: &mut VM<'_, '_, impl ResourceTracker>, args: ArgValues) -> RunResult<CallResult> {
+        let params = args.bind_params("open", OPEN_PARAMS, 0, vm.heap, vm.interns)?;
+        defer_drop!(params, vm);
+        let [mode, buffering, encoding, errors, newline] = params;
+        let options = [
+            mode.as_ref(),
+            buffering.as_ref(),
+            encoding.as_ref(),
+            errors.as_ref(),
+            newline.as_ref(),
+        ];
+        open_file(vm, self.clone(), self.path.clone(), options)
+    }
+
+    /// `Path.expanduser()`: replaces a leading `~` with the user's home directory.
+    ///
+    /// Paths that don't start with `~` are returned unchanged without asking the host.
+    fn expanduser(&self, vm: &mut VM<'_, '_, impl ResourceTracker>, args: ArgValues) -> RunResult<CallResult> {
+        args.check_zero_args("expanduser", vm.heap)?;
+        let Some(rest) = self.path.strip_prefix('~') else {
+            return Ok(CallResult::Value(Value::Ref(
+                vm.heap.allocate(HeapData::Path(self.clone()))?,
+            )));
+        };
+        // `~user` would need the password database, which sandboxed code can't read
+        if !rest.is_empty() && !rest.starts_with('/') {
+            return Err(home_error());
+        }
+        home_call(vm, rest.trim_start_matches('/').to_owned())
+    }
+
+    /// `Path.relative_to(other, walk_up=False)`.
+    fn relative_to_call(&self, vm: &mut VM<'_, '_, impl ResourceTracker>, args: ArgValues) -> RunResult<Value> {
+        let params = args.bind_params("relative_to", ["other", "walk_up"], 1, vm.heap, vm.interns)?;
+        defer_drop!(params, vm);
+        let [Some(other), walk_up] = params else {
+            unreachable!("required parameters are always bound")
+        };
+        let other = Self::new(extract_path_string(other, vm.heap, vm.interns)?.to_owned());
+        let walk_up = walk_up.as_ref().is_some_and(|walk_up| walk_up.py_bool(vm));
+        let result = self
+            .relative_to(&other, walk_up)
+            .map_err(|e| SimpleException::new_msg(ExcType::ValueError, &e))?;
+        Ok(Value::Ref(vm.heap.allocate(HeapData::Path(Self::new(result)))?))
+    }
+
+    /// `Path.match(pattern, *, case_sensitive=None)` and `Path.full_match(...)`.
+    ///
+    /// `case_sensitive=None` means case-sensitive, as on POSIX.
+    fn match_call(
+        &self,
+        vm: &mut VM<'_, '_, impl ResourceTracker>,
+        method: StaticStrings,
+        args: ArgValues,
+    ) -> RunResult<Value> {
+        let params = args.bind_params(method.into(), ["pattern", "case_sensitive"], 1, vm.heap, vm.interns)?;
+        defer_drop!(params, vm);
+        let [Some(pattern), case_sensitive] = params else {
+            unreachable!("required parameters are always bound")
+        };
+        let pattern = Self::new(extract_path_string(pattern, vm.heap, vm.interns)?.to_owned());
+        let case_sensitive = case_sensitive
+            .as_ref()
+            .is_none_or(|case_sensitive| matches!(case_sensitive, Value::None) || case_sensitive.py_bool(vm));
+        let matched = if method == StaticStrings::FullMatch {
+            self.full_match(&pattern, case_sensitive)
+        } else {
+            self.matches(&pattern, case_sensitive)
+                .map_err(|e| SimpleException::new_msg(ExcType::ValueError, &e))?
+        };
+        Ok(Value::Bool(matched))
+    }
+
     /// Resolves a known attribute by its `StaticStrings` variant.
     ///
     /// Returns `Ok(Some(value))` for recognized property names (`name`, `parent`,
-    /// `stem`, `suffix`, `suffixes`, `parts`), or `Ok(None)` if the variant doesn't
+    /// `parents`, `stem`, `suffix`, `suffixes`, `parts`, `anchor`, `root`, `drive`), or `Ok(None)` if the variant doesn't
     /// correspond to a Path attribute. Used by `py_getattr` to share logic between
     /// the interned fast path and the heap string slow path.
     fn getattr_by_static(&self, ss: StaticStrings, heap: &mut Heap<impl ResourceTracker>) -> RunResult<Option<Value>> {
@@ -416,6 +790,20 @@ impl Path {
                     Value::Ref(heap.allocate(HeapData::Path(same_path))?)
                 }
             }
+            StaticStrings::Parents => {
+                let parents = self.parents();
+                let mut items = SmallVec::with_capacity(parents.len());
+                for parent in parents {
+                    let path_id = heap.allocate(HeapData::Path(Self::new(parent.to_owned())))?;
+                    items.push(Value::Ref(path_id));
+                }
+                allocate_tuple(items, heap)?
+            }
+            StaticStrings::Anchor | StaticStrings::Root => {
+                let root = self.root();
+                Value::Ref(heap.allocate(HeapData::Str(Str::new(root.to_owned())))?)
+            }
+            StaticStrings::Drive => Value::Ref(heap.allocate(HeapData::Str(Str::new(String::new())))?),
             StaticStrings::Stem => {
                 let stem = self.stem();
                 Value::Ref(heap.allocate(HeapData::Str(Str::new(stem.to_owned())))?)
@@ -464,6 +852,15 @@ impl PyTrait for Path {
         Ok(self.path == other.path)
     }
 
+    /// Orders paths by their `/`-separated parts, as CPython does, so `a/b` sorts before `a-b`.
+    fn py_cmp(
+        &self,
+        other: &Self,
+        _vm: &mut VM<'_, '_, impl ResourceTracker>,
+    ) -> Result<Option<Ordering>, ResourceError> {
+        Ok(Some(self.path.split('/').cmp(other.path.split('/'))))
+    }
+
     fn py_bool(&self, _vm: &VM<'_, '_, impl ResourceTracker>) -> bool {
         // Paths are always truthy (even empty paths)
         true
@@ -500,6 +897,15 @@ impl PyTrait for Path {
         attr: &EitherStr,
         args: ArgValues,
     ) -> RunResult<CallResult> {
+        match attr.static_string() {
+            Some(StaticStrings::Open) => return self.open(vm, args),
+            Some(StaticStrings::Expanduser) => return self.expanduser(vm, args),
+            Some(StaticStrings::RelativeTo) => return self.relative_to_call(vm, args).map(CallResult::Value),
+            Some(method @ (StaticStrings::Match | StaticStrings::FullMatch)) => {
+                return self.match_call(vm, method, args).map(CallResult::Value);
+            }
+            _ => {}
+        }
         let heap = &mut *vm.heap;
         let interns = vm.interns;
         let Some(method) = attr.static_string() else {
@@ -512,6 +918,9 @@ impl PyTrait for Path {
             // Package path as first argument for OS call (as Path, not string)
             let path_arg = Value::Ref(heap.allocate(HeapData::Path(self.clone()))?);
             let os_args = prepend_path_arg(path_arg, args);
+            if matches!(os_fn, OsFunction::Resolve | OsFunction::Absolute) {
+                vm.set_os_result_conversion(OsResultConversion::ToPath);
+            }
             return Ok(CallResult::OsCall(os_fn, os_args));
         }
 
@@ -527,13 +936,25 @@ impl PyTrait for Path {
                 let path = fold_joinpath(self.clone(), pos_args.as_slice(), heap, interns)?;
                 Ok(Value::Ref(heap.allocate(HeapData::Path(path))?))
             }
+            StaticStrings::IsRelativeTo => {
+                let other_val = args.get_one_arg("is_relative_to", heap)?;
+                defer_drop!(other_val, heap);
+                let other = Self::new(extract_path_string(other_val, heap, interns)?.to_owned());
+                Ok(Value::Bool(self.is_relative_to(&other)))
+            }
+            StaticStrings::WithSegments => {
+                let pos_args = args.into_pos_only("with_segments", heap)?;
+                defer_drop!(pos_args, heap);
+                let path = Self::from_segments(pos_args.as_slice(), heap, interns)?;
+                Ok(Value::Ref(heap.allocate(HeapData::Path(path))?))
+            }
             StaticStrings::WithName => {
                 let name_val = args.get_one_arg("with_name", heap)?;
                 defer_drop!(name_val, heap);
                 let name = extract_path_string(name_val, heap, interns)?;
                 let result = self
                     .with_name(name)
-                    .map_err(|e| crate::exception_private::SimpleException::new_msg(ExcType::ValueError, &e))?;
+                    .map_err(|e| SimpleException::new_msg(ExcType::ValueError, &e))?;
                 Ok(Value::Ref(heap.allocate(HeapData::Path(Self::new(result)))?))
             }
             StaticStrings::WithStem => {
@@ -542,7 +963,7 @@ impl PyTrait for Path {
                 let stem = extract_path_string(stem_val, heap, interns)?;
                 let result = self
                     .with_stem(stem)
-                    .map_err(|e| crate::exception_private::SimpleException::new_msg(ExcType::ValueError, &e))?;
+                    .map_err(|e| SimpleException::new_msg(ExcType::ValueError, &e))?;
                 Ok(Value::Ref(heap.allocate(HeapData::Path(Self::new(result)))?))
             }
             StaticStrings::WithSuffix => {
@@ -551,7 +972,7 @@ impl PyTrait for Path {
                 let suffix = extract_path_string(suffix_val, heap, interns)?;
                 let result = self
                     .with_suffix(suffix)
-                    .map_err(|e| crate::exception_private::SimpleException::new_msg(ExcType::ValueError, &e))?;
+                    .map_err(|e| SimpleException::new_msg(ExcType::ValueError, &e))?;
                 Ok(Value::Ref(heap.allocate(HeapData::Path(Self::new(result)))?))
             }
             StaticStrings::AsPosix | StaticStrings::Fspath => {
@@ -586,6 +1007,10 @@ impl PyTrait for Path {
             "suffix" => StaticStrings::Suffix,
             "suffixes" => StaticStrings::Suffixes,
             "parts" => StaticStrings::Parts,
+            "parents" => StaticStrings::Parents,
+            "anchor" => StaticStrings::Anchor,
+            "root" => StaticStrings::Root,
+            "drive" => StaticStrings::Drive,
             _ => return Err(ExcType::attribute_error(Type::Path, attr_str)),
         };
         let v = self
@@ -3,7 +3,8 @@
 //! `ReMatch` represents the result of a successful regex match operation.
 //! It stores the matched text, capture groups, and their positions, providing
 //! Python-compatible access via `.group()`, `.groups()`, `.start()`, `.end()`,
-//! and `.span()` methods.
+//! `.span()` and `.expand()` methods and the `.lastindex`, `.lastgroup` and `.regs`
+//! attributes.
 //!
 //! All data is stored as owned values (no heap references), so reference counting
 //! is trivial — `py_dec_ref_ids` is a no-op.
@@ -16,11 +17,13 @@ use smallvec::smallvec;
 use crate::{
     args::ArgValues,
     bytecode::{CallResult, VM},
+    defer_drop,
     exception_private::{ExcType, RunResult},
     heap::{Heap, HeapData, HeapId},
     intern::{Interns, StaticStrings},
+    re_syntax::{expand_template, parse_template},
     resource::{ResourceError, ResourceTracker},
-    types::{Dict, PyTrait, Str, Type, allocate_tuple, str::string_repr_fmt},
+    types::{Dict, PyTrait, RePattern, Str, Type, allocate_tuple, re_pattern::value_to_str, str::string_repr_fmt},
     value::{EitherStr, Value},
 };
 
@@ -59,6 +62,8 @@ pub(crate) struct ReMatch {
     group_spans: Vec<Option<(usize, usize)>>,
     /// Named groups: maps group name → 1-based group index.
     named_groups: Vec<(String, usize)>,
+    /// The last capture group to close during matching (`.lastindex`), if any matched.
+    last_index: Option<usize>,
     /// Owned copy of the input string (returned by `.string` attribute).
     input_string: String,
     /// The original pattern string (used in repr output).
@@ -75,14 +80,8 @@ impl ReMatch {
     /// # Arguments
     /// * `caps` - The successful capture result from the regex engine
     /// * `input` - The full input string that was searched
-    /// * `pattern` - The original pattern string (for repr)
-    /// * `regex` - The compiled regex, used to extract named group mappings
-    pub fn from_captures(
-        caps: &fancy_regex::Captures<'_>,
-        input: &str,
-        pattern: &str,
-        regex: &fancy_regex::Regex,
-    ) -> Self {
+    /// * `pattern` - The pattern that matched, providing named groups and group nesting
+    pub fn from_captures(caps: &fancy_regex::Captures<'_>, input: &str, pattern: &RePattern) -> Self {
         let full = caps.get(0).expect("group 0 always exists on a successful match");
         let full_match = full.as_str().to_owned();
         let start = byte_to_char_offset(input, full.start());
@@ -105,13 +104,7 @@ impl ReMatch {
             }
         }
 
-        // Extract named group name→index mappings from the regex
-        let mut named_groups = Vec::new();
-        for (idx, name) in regex.capture_names().enumerate() {
-            if let Some(name) = name {
-                named_groups.push((name.to_owned(), idx));
-            }
-        }
+        let last_index = last_closed_group(caps, pattern.group_parents());
 
         Self {
             full_match,
@@ -119,12 +112,67 @@ impl ReMatch {
             end,
             groups,
             group_spans,
-            named_groups,
+            named_groups: pattern.group_names().to_vec(),
+            last_index,
             input_string: input.to_owned(),
-            pattern_string: pattern.to_owned(),
+            pattern_string: pattern.pattern().to_owned(),
         }
     }
 
+    /// Resolves a group argument — integer, bool, or string (named group) — to a group number.
+    ///
+    /// Raises `IndexError` for unknown names and non-integer, non-string values.
+    /// Range checking of numbers is left to the caller.
+    fn group_number(&self, val: &Value, heap: &Heap<impl ResourceTracker>, interns: &Interns) -> RunResult<i64> {
+        let name = match val {
+            Value::Int(n) => return Ok(*n),
+            Value::Bool(b) => return Ok(i64::from(*b)),
+            Value::InternString(id) => interns.get_str(*id),
+            Value::Ref(heap_id) => match heap.get(*heap_id) {
+                HeapData::Str(s) => s.as_str(),
+                _ => return Err(ExcType::re_match_group_index_error()),
+            },
+            _ => return Err(ExcType::re_match_group_index_error()),
+        };
+        self.named_groups
+            .iter()
+            .find(|(group_name, _)| group_name == name)
+            .map(|&(_, idx)| i64::try_from(idx).expect("group number fits in i64"))
+            .ok_or_else(ExcType::re_match_group_index_error)
+    }
+
+    /// Returns the text matched by a group (index 0 = full match), or `None` if the
+    /// group didn't participate.
+    fn group_str(&self, idx: usize) -> Option<&str> {
+        if idx == 0 {
+            Some(&self.full_match)
+        } else {
+            self.groups.get(idx - 1).and_then(Option::as_deref)
+        }
+    }
+
+    /// `m.expand(template)` — return the template with group references substituted,
+    /// as done by `re.sub()`.
+    fn expand(&self, template: &str, heap: &mut Heap<impl ResourceTracker>) -> RunResult<Value> {
+        let parts = parse_template(template, self.groups.len(), &self.named_groups)?;
+        let mut result = String::new();
+        expand_template(&parts, |i| self.group_str(i), &mut result);
+        Ok(Value::Ref(heap.allocate(HeapData::Str(Str::new(result)))?))
+    }
+
+    /// Returns the `.regs` tuple: `(start, end)` for the full match and every group,
+    /// with `(-1, -1)` for groups that didn't participate.
+    #[expect(clippy::cast_possible_wrap, reason = "positions are always small enough for i64")]
+    fn get_regs(&self, heap: &mut Heap<impl ResourceTracker>) -> RunResult<Value> {
+        let mut regs = smallvec![];
+        let spans = std::iter::once(Some((self.start, self.end))).chain(self.group_spans.iter().copied());
+        for span in spans {
+            let (s, e) = span.map_or((-1, -1), |(s, e)| (s as i64, e as i64));
+            regs.push(allocate_tuple(smallvec![Value::Int(s), Value::Int(e)], heap)?);
+        }
+        Ok(allocate_tuple(regs, heap)?)
+    }
+
     /// Returns the match for a given group number.
     ///
     /// Group 0 is the full match, groups 1..N are capture groups.
@@ -153,41 +201,12 @@ impl ReMatch {
         }
     }
 
-    /// Returns the match for a named group.
-    ///
-    /// Looks up the group name in `named_groups` and delegates to `get_group`.
-    /// Raises `IndexError` if the name is not found.
-    fn get_group_by_name(&self, name: &str, heap: &mut Heap<impl ResourceTracker>) -> RunResult<Value> {
-        for (group_name, idx) in &self.named_groups {
-            if group_name == name {
-                #[expect(clippy::cast_possible_wrap, reason = "group indices are always small")]
-                return self.get_group(*idx as i64, heap);
-            }
-        }
-        Err(ExcType::re_match_group_index_error())
-    }
-
     /// Implements `m[key]` subscript access on match objects.
     ///
     /// Supports integer indexing (like `m[0]`, `m[1]`), bool indexing,
     /// and string indexing for named groups (like `m['name']`).
     pub fn py_getitem(&self, key: &Value, vm: &mut VM<'_, '_, impl ResourceTracker>) -> RunResult<Value> {
-        match key {
-            Value::Int(n) => self.get_group(*n, vm.heap),
-            Value::Bool(b) => self.get_group(i64::from(*b), vm.heap),
-            Value::InternString(id) => {
-                let name = vm.interns.get_str(*id);
-                self.get_group_by_name(name, vm.heap)
-            }
-            Value::Ref(heap_id) => match vm.heap.get(*heap_id) {
-                HeapData::Str(s) => {
-                    let name = s.as_str().to_owned();
-                    self.get_group_by_name(&name, vm.heap)
-                }
-                _ => Err(ExcType::re_match_group_index_error()),
-            },
-            _ => Err(ExcType::re_match_group_index_error()),
-        }
+        resolve_group_arg(self, key, vm.heap, vm.interns)
     }
 
     /// Returns a dict mapping named group names to their matched strings.
@@ -360,6 +379,23 @@ impl PyTrait for ReMatch {
                 let v = Value::Ref(vm.heap.allocate(HeapData::Str(s))?);
                 Ok(Some(CallResult::Value(v)))
             }
+            Some(StaticStrings::Lastindex) => {
+                let v = self.last_index.map_or(Value::None, |idx| {
+                    Value::Int(i64::try_from(idx).expect("group number fits in i64"))
+                });
+                Ok(Some(CallResult::Value(v)))
+            }
+            Some(StaticStrings::Lastgroup) => {
+                let name = self
+                    .last_index
+                    .and_then(|last| self.named_groups.iter().find(|&&(_, idx)| idx == last));
+                let v = match name {
+                    Some((name, _)) => Value::Ref(vm.heap.allocate(HeapData::Str(Str::new(name.clone())))?),
+                    None => Value::None,
+                };
+                Ok(Some(CallResult::Value(v)))
+            }
+            Some(StaticStrings::Regs) => Ok(Some(CallResult::Value(self.get_regs(vm.heap)?))),
             _ => Err(ExcType::attribute_error(Type::ReMatch, attr.as_str(vm.interns))),
         }
     }
@@ -385,17 +421,23 @@ impl PyTrait for ReMatch {
                 result
             }
             Some(StaticStrings::Start) => {
-                let n = extract_optional_group_arg(args, "re.Match.start", 0, vm.heap)?;
+                let n = extract_optional_group_arg(self, args, "re.Match.start", vm.heap, vm.interns)?;
                 self.get_start(n)?
             }
             Some(StaticStrings::End) => {
-                let n = extract_optional_group_arg(args, "re.Match.end", 0, vm.heap)?;
+                let n = extract_optional_group_arg(self, args, "re.Match.end", vm.heap, vm.interns)?;
                 self.get_end(n)?
             }
             Some(StaticStrings::Span) => {
-                let n = extract_optional_group_arg(args, "re.Match.span", 0, vm.heap)?;
+                let n = extract_optional_group_arg(self, args, "re.Match.span", vm.heap, vm.interns)?;
                 self.get_span(n, vm.heap)?
             }
+            Some(StaticStrings::Expand) => {
+                let template = args.get_one_arg("re.Match.expand", vm.heap)?;
+                defer_drop!(template, vm);
+                let template = value_to_str(template, vm.heap, vm.interns)?.into_owned();
+                self.expand(&template, vm.heap)?
+            }
             _ => return Err(ExcType::attribute_error(Type::ReMatch, attr.as_str(vm.interns))),
         };
         Ok(CallResult::Value(result))
@@ -456,47 +498,53 @@ fn resolve_group_arg(
     heap: &mut Heap<impl ResourceTracker>,
     interns: &Interns,
 ) -> RunResult<Value> {
-    match val {
-        Value::Int(n) => m.get_group(*n, heap),
-        Value::Bool(b) => m.get_group(i64::from(*b), heap),
-        Value::InternString(id) => {
-            let name = interns.get_str(*id);
-            m.get_group_by_name(name, heap)
-        }
-        Value::Ref(heap_id) => match heap.get(*heap_id) {
-            HeapData::Str(s) => {
-                let name = s.as_str().to_owned();
-                m.get_group_by_name(&name, heap)
-            }
-            _ => Err(ExcType::re_match_group_index_error()),
-        },
-        _ => Err(ExcType::re_match_group_index_error()),
-    }
+    let n = m.group_number(val, heap, interns)?;
+    m.get_group(n, heap)
 }
 
-/// Extracts an optional integer argument for group-related methods.
+/// Extracts an optional group argument for group-related methods.
 ///
-/// Many `re.Match` methods accept an optional group number that defaults to 0.
-/// This helper extracts the argument, validates it is an integer (or string for
-/// named groups), and returns the group number.
+/// `re.Match.start`, `end` and `span` accept an optional group number or name that
+/// defaults to 0 (the full match). Names are resolved to their group number.
 fn extract_optional_group_arg(
+    m: &ReMatch,
     args: ArgValues,
     name: &str,
-    default: i64,
     heap: &mut Heap<impl ResourceTracker>,
+    interns: &Interns,
 ) -> RunResult<i64> {
-    let opt = args.get_zero_one_arg(name, heap)?;
-    match opt {
-        None => Ok(default),
-        Some(Value::Int(n)) => Ok(n),
-        // CPython treats bool as int subclass: True=1, False=0.
-        Some(Value::Bool(b)) => Ok(i64::from(b)),
-        // String group names are not valid for start/end/span — they take integers only
-        Some(other) => {
-            other.drop_with_heap(heap);
-            Err(ExcType::re_match_group_index_error())
+    let Some(arg) = args.get_zero_one_arg(name, heap)? else {
+        return Ok(0);
+    };
+    let result = m.group_number(&arg, heap, interns);
+    arg.drop_with_heap(heap);
+    result
+}
+
+/// Finds the capture group that closed last during matching, CPython's `lastindex`.
+///
+/// Among the participating groups, the one ending furthest right closed last. When
+/// several end at the same position, an enclosing group closes after the groups
+/// nested inside it, and otherwise the later group in the pattern closes last.
+fn last_closed_group(caps: &fancy_regex::Captures<'_>, parents: &[Option<usize>]) -> Option<usize> {
+    let is_ancestor = |ancestor: usize, mut group: usize| {
+        while let Some(parent) = parents.get(group - 1).copied().flatten() {
+            if parent == ancestor {
+                return true;
+            }
+            group = parent;
         }
+        false
+    };
+    let mut last: Option<(usize, usize)> = None;
+    for idx in 1..caps.len() {
+        let Some(m) = caps.get(idx) else { continue };
+        last = match last {
+            Some((best, end)) if m.end() < end || (m.end() == end && is_ancestor(best, idx)) => Some((best, end)),
+            _ => Some((idx, m.end())),
+        };
     }
+    last.map(|(idx, _)| idx)
 }
 
 /// Converts a byte offset in a UTF-8 string to a character (code point) offset.
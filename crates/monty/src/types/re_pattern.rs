@@ -6,13 +6,21 @@
 //! to ReDoS. Monty's resource limits (time and allocation budgets) are the primary defense
 //! against catastrophic backtracking in untrusted patterns.
 //!
+//! The VM only checks the time limit between bytecode instructions, so a single regex call
+//! could otherwise run far past `max_duration`. When a time limit is set, the regexes are
+//! compiled with a backtrack limit derived from the remaining time (see [`backtrack_budget`]),
+//! and exhausting it raises an uncatchable `TimeoutError` like any other resource error.
+//!
+//! Patterns are parsed and translated from Python syntax by [`crate::re_syntax`] before
+//! being handed to `fancy_regex`, so syntax errors carry CPython's messages and positions.
+//!
 //! Custom serde serializes only the pattern string and flags, recompiling the regex
 //! on deserialization. This supports Monty's snapshot/restore feature.
 
 use std::{borrow::Cow, fmt::Write};
 
 use ahash::AHashSet;
-use fancy_regex::Regex;
+use fancy_regex::{Regex, RegexBuilder, RuntimeError};
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
 use smallvec::SmallVec;
 
@@ -20,12 +28,13 @@ use crate::{
     args::ArgValues,
     bytecode::{CallResult, VM},
     defer_drop, defer_drop_mut,
-    exception_private::{ExcType, RunResult},
+    exception_private::{ExcType, RunError, RunResult},
     heap::{DropWithHeap, Heap, HeapData, HeapId},
     intern::{Interns, StaticStrings},
-    modules::re::{ASCII, DOTALL, IGNORECASE, MULTILINE},
+    modules::re::{ASCII, DOTALL, IGNORECASE, MULTILINE, UNICODE, VERBOSE},
+    re_syntax::{ParsedPattern, expand_template, parse_pattern, parse_template},
     resource::{ResourceError, ResourceTracker, check_estimated_size},
-    types::{List, PyTrait, ReMatch, Str, Type, allocate_tuple, str::string_repr_fmt},
+    types::{Dict, List, PyTrait, ReMatch, Str, Type, allocate_tuple, str::string_repr_fmt},
     value::{EitherStr, Value},
 };
 
@@ -38,12 +47,31 @@ use crate::{
 ///
 /// Custom serde serializes only the pattern string and flags, recompiling the
 /// regex on deserialization. This supports Monty's snapshot/restore feature.
+///
+/// Matching methods take `&mut self` because the compiled regexes are rebuilt
+/// whenever the time-derived backtrack budget moves to a different bucket.
 #[derive(Debug)]
 pub(crate) struct RePattern {
     /// The original Python regex pattern string.
     pattern: String,
-    /// Python regex flags bitmask (IGNORECASE=2, MULTILINE=8, DOTALL=16, ASCII=256).
+    /// Python regex flags bitmask (IGNORECASE=2, MULTILINE=8, DOTALL=16, VERBOSE=64, ASCII=256),
+    /// including flags set by global inline groups like `(?i)`.
     flags: u16,
+    /// Number of capture groups in the pattern.
+    group_count: usize,
+    /// Named groups as `(name, group number)`, in definition order.
+    group_names: Vec<(String, usize)>,
+    /// Innermost enclosing capture group of each group (index 0 = group 1).
+    group_parents: Vec<Option<usize>>,
+    /// Global flag prefix of the translated pattern, e.g. `(?i)`.
+    prefix: String,
+    /// The translated pattern body, kept to rebuild the regexes with a new backtrack limit.
+    body: String,
+    /// Backtrack limit the regexes were compiled with, `None` for `fancy_regex`'s default.
+    ///
+    /// Only set when the tracker has a time limit, in which case exceeding it means the
+    /// remaining time is used up.
+    backtrack_limit: Option<usize>,
     /// The compiled Rust regex, unanchored.
     compiled: Regex,
     /// The compiled regex anchored with `\A(?:...)` for `match()`.
@@ -65,37 +93,107 @@ pub(crate) struct RePattern {
 impl RePattern {
     /// Creates a compiled pattern from a Python regex string and flags.
     ///
-    /// Translates Python flag constants into inline regex flag prefixes and compiles
-    /// the pattern. Also pre-compiles anchored variants for `match` (`\A(?:pattern)`)
+    /// Translates the Python pattern into `fancy_regex` syntax (see [`crate::re_syntax`])
+    /// and compiles it. Also pre-compiles anchored variants for `match` (`\A(?:pattern)`)
     /// and `fullmatch` (`\A(?:pattern)\z`) to correctly handle alternations.
     ///
+    /// `backtrack_limit` is normally [`backtrack_budget`] for the current tracker, or
+    /// `None` to use the engine's default limit.
+    ///
     /// # Errors
     ///
-    /// Returns `re.PatternError` if the pattern is invalid.
-    pub fn compile(pattern: String, flags: u16) -> RunResult<Self> {
-        let compiled = compile_regex(&pattern, flags)?;
-        let compiled_match = compile_regex(&format!("\\A(?:{pattern})"), flags)?;
-        let compiled_fullmatch = compile_regex(&format!("\\A(?:{pattern})\\z"), flags)?;
+    /// Returns `re.PatternError` if the pattern is invalid, or `ValueError` for
+    /// incompatible flags.
+    pub fn compile(pattern: String, flags: u16, backtrack_limit: Option<usize>) -> RunResult<Self> {
+        let parsed = parse_pattern(&pattern, flags)?;
+        let [compiled, compiled_match, compiled_fullmatch] =
+            compile_regexes(&parsed.prefix, &parsed.body, backtrack_limit)
+                .map_err(|(err, body)| pattern_error(&parsed, &body, err))?;
         Ok(Self {
             pattern,
-            flags,
+            flags: parsed.flags,
+            group_count: parsed.group_count,
+            group_names: parsed.group_names,
+            group_parents: parsed.group_parents,
+            prefix: parsed.prefix,
+            body: parsed.body,
+            backtrack_limit,
             compiled,
             compiled_match,
             compiled_fullmatch,
         })
     }
 
+    /// Rebuilds the compiled regexes if the backtrack budget for the tracker's
+    /// remaining time differs from the one they were compiled with.
+    ///
+    /// Budgets are rounded to powers of two, so a pattern reused throughout a run is
+    /// recompiled at most once each time the remaining time halves.
+    ///
+    /// # Errors
+    ///
+    /// Returns `ResourceError::Time` if the time limit has already been exceeded.
+    fn refresh_budget(&mut self, tracker: &impl ResourceTracker) -> RunResult<()> {
+        let backtrack_limit = backtrack_budget(tracker)?;
+        if backtrack_limit != self.backtrack_limit {
+            let [compiled, compiled_match, compiled_fullmatch] =
+                compile_regexes(&self.prefix, &self.body, backtrack_limit)
+                    .map_err(|(err, _)| ExcType::re_pattern_error(err))?;
+            self.compiled = compiled;
+            self.compiled_match = compiled_match;
+            self.compiled_fullmatch = compiled_fullmatch;
+            self.backtrack_limit = backtrack_limit;
+        }
+        Ok(())
+    }
+
+    /// Converts an error raised while executing the regex into a Python exception.
+    ///
+    /// Exceeding a time-derived backtrack limit means the remaining time is spent, so it
+    /// becomes an uncatchable `TimeoutError`; anything else is a `re.PatternError`.
+    fn execution_error(&self, err: fancy_regex::Error, tracker: &impl ResourceTracker) -> RunError {
+        if self.backtrack_limit.is_some()
+            && matches!(
+                err,
+                fancy_regex::Error::RuntimeError(RuntimeError::BacktrackLimitExceeded)
+            )
+            && let Some((limit, elapsed)) = tracker.time_usage()
+        {
+            // The budget is an estimate of the remaining time, so the clock may not
+            // quite have reached the limit yet.
+            let elapsed = elapsed.max(limit);
+            return ResourceError::Time { limit, elapsed }.into();
+        }
+        ExcType::re_pattern_error(err)
+    }
+
+    /// The original Python pattern string.
+    pub fn pattern(&self) -> &str {
+        &self.pattern
+    }
+
+    /// Named groups as `(name, group number)`, in definition order.
+    pub fn group_names(&self) -> &[(String, usize)] {
+        &self.group_names
+    }
+
+    /// Innermost enclosing capture group of each group (index 0 = group 1).
+    pub fn group_parents(&self) -> &[Option<usize>] {
+        &self.group_parents
+    }
+
     /// `pattern.search(string)` — find first match anywhere in the string.
     ///
     /// Returns a `ReMatch` heap object on success, or `Value::None` if no match.
-    pub fn search(&self, text: &str, heap: &mut Heap<impl ResourceTracker>) -> RunResult<Value> {
+    pub fn search(&mut self, text: &str, heap: &mut Heap<impl ResourceTracker>) -> RunResult<Value> {
+        self.refresh_budget(heap.tracker())?;
         match self.compiled.captures(text) {
             Ok(Some(caps)) => {
-                let m = ReMatch::from_captures(&caps, text, &self.pattern, &self.compiled);
+                let m = ReMatch::from_captures(&caps, text, self);
                 Ok(Value::Ref(heap.allocate(HeapData::ReMatch(m))?))
             }
             Ok(None) => Ok(Value::None),
-            Err(err) => Err(ExcType::re_pattern_error(err)),
+            Err(err) => Err(self.execution_error(err, heap.tracker())),
         }
     }
 
@@ -106,14 +204,15 @@ impl RePattern {
     /// anchor forces the engine to try all alternatives at position 0.
     ///
     /// Returns a `ReMatch` heap object on success, or `Value::None` if no match.
-    pub fn match_start(&self, text: &str, heap: &mut Heap<impl ResourceTracker>) -> RunResult<Value> {
+    pub fn match_start(&mut self, text: &str, heap: &mut Heap<impl ResourceTracker>) -> RunResult<Value> {
+        self.refresh_budget(heap.tracker())?;
         match self.compiled_match.captures(text) {
             Ok(Some(caps)) => {
-                let match_obj = ReMatch::from_captures(&caps, text, &self.pattern, &self.compiled);
+                let match_obj = ReMatch::from_captures(&caps, text, self);
                 Ok(Value::Ref(heap.allocate(HeapData::ReMatch(match_obj))?))
             }
             Ok(None) => Ok(Value::None),
-            Err(err) => Err(ExcType::re_pattern_error(err)),
+            Err(err) => Err(self.execution_error(err, heap.tracker())),
         }
     }
 
@@ -124,14 +223,15 @@ impl RePattern {
     /// anchors force the engine to try all alternatives for a full-string match.
     ///
     /// Returns a `ReMatch` heap object on success, or `Value::None` if no match.
-    pub fn fullmatch(&self, text: &str, heap: &mut Heap<impl ResourceTracker>) -> RunResult<Value> {
+    pub fn fullmatch(&mut self, text: &str, heap: &mut Heap<impl ResourceTracker>) -> RunResult<Value> {
+        self.refresh_budget(heap.tracker())?;
         match self.compiled_fullmatch.captures(text) {
             Ok(Some(caps)) => {
-                let match_obj = ReMatch::from_captures(&caps, text, &self.pattern, &self.compiled);
+                let match_obj = ReMatch::from_captures(&caps, text, self);
                 Ok(Value::Ref(heap.allocate(HeapData::ReMatch(match_obj))?))
             }
             Ok(None) => Ok(Value::None),
-            Err(err) => Err(ExcType::re_pattern_error(err)),
+            Err(err) => Err(self.execution_error(err, heap.tracker())),
         }
     }
 
@@ -141,7 +241,8 @@ impl RePattern {
     /// - No capture groups: returns a list of matched strings
     /// - One capture group: returns a list of the group's matched strings
     /// - Multiple capture groups: returns a list of tuples of matched strings
-    pub fn findall(&self, text: &str, heap: &mut Heap<impl ResourceTracker>) -> RunResult<Value> {
+    pub fn findall(&mut self, text: &str, heap: &mut Heap<impl ResourceTracker>) -> RunResult<Value> {
+        self.refresh_budget(heap.tracker())?;
         let cap_count = self.compiled.captures_len();
         let mut results = Vec::new();
 
@@ -149,14 +250,17 @@ impl RePattern {
             // No capture groups — return list of full match strings
             0 | 1 => {
                 for m in self.compiled.find_iter(text) {
-                    let s = Str::new(m.map_err(ExcType::re_pattern_error)?.as_str().to_owned());
+                    check_deadline(heap.tracker())?;
+                    let m = m.map_err(|err| self.execution_error(err, heap.tracker()))?;
+                    let s = Str::new(m.as_str().to_owned());
                     results.push(Value::Ref(heap.allocate(HeapData::Str(s))?));
                 }
             }
             // One capture group — return list of the group's strings
             2 => {
                 for caps in self.compiled.captures_iter(text) {
-                    let caps = caps.map_err(ExcType::re_pattern_error)?;
+                    check_deadline(heap.tracker())?;
+                    let caps = caps.map_err(|err| self.execution_error(err, heap.tracker()))?;
                     let val = caps.get(1).map(|m| m.as_str().to_owned()).unwrap_or_default();
                     let s = Str::new(val);
                     results.push(Value::Ref(heap.allocate(HeapData::Str(s))?));
@@ -165,7 +269,8 @@ impl RePattern {
             // Multiple capture groups — return list of tuples
             _ => {
                 for caps in self.compiled.captures_iter(text) {
-                    let caps = caps.map_err(ExcType::re_pattern_error)?;
+                    check_deadline(heap.tracker())?;
+                    let caps = caps.map_err(|err| self.execution_error(err, heap.tracker()))?;
                     let mut elements: SmallVec<[Value; 3]> = SmallVec::with_capacity(cap_count - 1);
                     for cap in caps.iter().skip(1) {
                         let val = cap.map(|m| m.as_str().to_owned()).unwrap_or_default();
@@ -184,56 +289,91 @@ impl RePattern {
     /// `pattern.sub(repl, string, count=0)` — substitute matches with a replacement.
     ///
     /// When `count` is 0, all matches are replaced. Otherwise, at most `count`
-    /// replacements are made. The replacement string supports `$1`, `$2`, etc.
-    /// for backreferences to captured groups.
+    /// replacements are made. `repl` is either a template string supporting `\1`,
+    /// `\g<name>` and the standard escapes, or a callable which receives each
+    /// `re.Match` and returns the replacement string.
+    ///
+    /// Returns the new string and the number of replacements made (for `subn`).
     ///
     /// Builds the result string in a single pass by iterating matches and appending
     /// replacements directly. Checks the running output size against resource limits
-    /// after each match, bailing out immediately if the budget is exceeded. This
-    /// avoids both false rejections from conservative pre-estimates and untracked
-    /// Rust heap allocations from delegating to `fancy_regex::replace_all()`.
-    pub fn sub(&self, repl: &str, text: &str, count: usize, heap: &mut Heap<impl ResourceTracker>) -> RunResult<Value> {
-        // Translate Python-style backreferences (\1, \2) to regex crate style ($1, $2)
-        let rust_repl = translate_replacement(repl);
+    /// after each match, bailing out immediately if the budget is exceeded.
+    pub fn sub(
+        &mut self,
+        repl: &Value,
+        text: &str,
+        count: usize,
+        vm: &mut VM<'_, '_, impl ResourceTracker>,
+    ) -> RunResult<(String, usize)> {
+        // A template is parsed once up front so errors are raised even when nothing matches.
+        let template = if repl.is_str(vm.heap) {
+            let repl = value_to_str(repl, vm.heap, vm.interns)?;
+            Some(parse_template(&repl, self.group_count, &self.group_names)?)
+        } else {
+            None
+        };
         let effective_count = if count == 0 { usize::MAX } else { count };
+        self.refresh_budget(vm.heap.tracker())?;
 
         let mut result = String::new();
         let mut last_end = 0;
+        let mut replacements = 0;
 
         for caps in self.compiled.captures_iter(text).take(effective_count) {
-            let caps = caps.map_err(ExcType::re_pattern_error)?;
+            check_deadline(vm.heap.tracker())?;
+            let caps = caps.map_err(|err| self.execution_error(err, vm.heap.tracker()))?;
             let m = caps.get(0).expect("capture group 0 always exists");
             result.push_str(&text[last_end..m.start()]);
-            caps.expand(rust_repl.as_ref(), &mut result);
+            match &template {
+                Some(parts) => expand_template(parts, |i| caps.get(i).map(|g| g.as_str()), &mut result),
+                None => self.call_repl(repl, &caps, text, &mut result, vm)?,
+            }
             last_end = m.end();
+            replacements += 1;
             // Check running size: current result + remaining unprocessed text.
-            check_estimated_size(result.len() + (text.len() - last_end), heap.tracker())?;
+            check_estimated_size(result.len() + (text.len() - last_end), vm.heap.tracker())?;
         }
 
         result.push_str(&text[last_end..]);
-        let s = Str::new(result);
-        Ok(Value::Ref(heap.allocate(HeapData::Str(s))?))
+        Ok((result, replacements))
+    }
+
+    /// Calls a callable `repl` with the match object for `caps`, appending the
+    /// returned string to `result`.
+    fn call_repl(
+        &self,
+        repl: &Value,
+        caps: &fancy_regex::Captures<'_>,
+        text: &str,
+        result: &mut String,
+        vm: &mut VM<'_, '_, impl ResourceTracker>,
+    ) -> RunResult<()> {
+        let m = ReMatch::from_captures(caps, text, self);
+        let m = Value::Ref(vm.heap.allocate(HeapData::ReMatch(m))?);
+        let replacement = vm.evaluate_function("re.sub()", repl, ArgValues::One(m))?;
+        defer_drop!(replacement, vm);
+        if !replacement.is_str(vm.heap) {
+            return Err(ExcType::type_error(format!(
+                "expected str instance, {} found",
+                replacement.py_type(vm.heap)
+            )));
+        }
+        result.push_str(&value_to_str(replacement, vm.heap, vm.interns)?);
+        Ok(())
     }
 
     /// `pattern.split(string, maxsplit=0)` — split string by pattern occurrences.
     ///
     /// Returns a list of strings. If `maxsplit` is non-zero, at most `maxsplit`
     /// splits occur and the remainder of the string is returned as the final element.
-    pub fn split(&self, text: &str, maxsplit: usize, heap: &mut Heap<impl ResourceTracker>) -> RunResult<Value> {
-        let pieces: Vec<&str> = if maxsplit == 0 {
-            self.compiled
-                .split(text)
-                .collect::<Result<Vec<_>, _>>()
-                .map_err(ExcType::re_pattern_error)?
-        } else {
-            self.compiled
-                .splitn(text, maxsplit + 1)
-                .collect::<Result<Vec<_>, _>>()
-                .map_err(ExcType::re_pattern_error)?
-        };
+    pub fn split(&mut self, text: &str, maxsplit: usize, heap: &mut Heap<impl ResourceTracker>) -> RunResult<Value> {
+        self.refresh_budget(heap.tracker())?;
+        let limit = if maxsplit == 0 { usize::MAX } else { maxsplit + 1 };
 
-        let mut results = Vec::with_capacity(pieces.len());
-        for piece in pieces {
+        let mut results = Vec::new();
+        for piece in self.compiled.splitn(text, limit) {
+            check_deadline(heap.tracker())?;
+            let piece = piece.map_err(|err| self.execution_error(err, heap.tracker()))?;
             let s = Str::new(piece.to_owned());
             results.push(Value::Ref(heap.allocate(HeapData::Str(s))?));
         }
@@ -247,17 +387,47 @@ impl RePattern {
     /// Eagerly collects all match objects into a list. This differs from CPython's
     /// lazy iterator but produces the same results when iterated. The VM's `GetIter`
     /// opcode handles iteration over the returned list.
-    pub fn finditer(&self, text: &str, heap: &mut Heap<impl ResourceTracker>) -> RunResult<Value> {
+    pub fn finditer(&mut self, text: &str, heap: &mut Heap<impl ResourceTracker>) -> RunResult<Value> {
+        self.refresh_budget(heap.tracker())?;
         let mut results = Vec::new();
         for caps in self.compiled.captures_iter(text) {
-            let caps = caps.map_err(ExcType::re_pattern_error)?;
-            let m = ReMatch::from_captures(&caps, text, &self.pattern, &self.compiled);
+            check_deadline(heap.tracker())?;
+            let caps = caps.map_err(|err| self.execution_error(err, heap.tracker()))?;
+            let m = ReMatch::from_captures(&caps, text, self);
             results.push(Value::Ref(heap.allocate(HeapData::ReMatch(m))?));
         }
 
         let list = List::new(results);
         Ok(Value::Ref(heap.allocate(HeapData::List(list))?))
     }
+
+    /// Returns whether the pattern matches anywhere in `text`, without allocating a match.
+    pub fn is_match(&mut self, text: &str, tracker: &impl ResourceTracker) -> RunResult<bool> {
+        self.refresh_budget(tracker)?;
+        self.compiled
+            .is_match(text)
+            .map_err(|err| self.execution_error(err, tracker))
+    }
+
+    /// Collects the given named groups of every non-overlapping match in `text`.
+    ///
+    /// For Rust callers that need group text rather than match objects. A group that
+    /// did not participate in a match, or is not defined by the pattern, is `None`.
+    pub fn named_groups<const N: usize>(
+        &mut self,
+        text: &str,
+        names: [&str; N],
+        tracker: &impl ResourceTracker,
+    ) -> RunResult<Vec<[Option<String>; N]>> {
+        self.refresh_budget(tracker)?;
+        let mut results = Vec::new();
+        for caps in self.compiled.captures_iter(text) {
+            check_deadline(tracker)?;
+            let caps = caps.map_err(|err| self.execution_error(err, tracker))?;
+            results.push(names.map(|name| caps.name(name).map(|m| m.as_str().to_owned())));
+        }
+        Ok(results)
+    }
 }
 
 impl PyTrait for RePattern {
@@ -290,21 +460,32 @@ impl PyTrait for RePattern {
     ) -> std::fmt::Result {
         write!(f, "re.compile(")?;
         string_repr_fmt(&self.pattern, f)?;
-        if self.flags != 0 {
+        // `str` patterns are always Unicode, so CPython never shows the flag
+        let flags = self.flags & !UNICODE;
+        if flags != 0 {
             let mut flag_parts = smallvec::SmallVec::<[&'static str; 4]>::new();
-            if self.flags & IGNORECASE != 0 {
+            if flags & IGNORECASE != 0 {
                 flag_parts.push("re.IGNORECASE");
             }
-            if self.flags & MULTILINE != 0 {
+            if flags & MULTILINE != 0 {
                 flag_parts.push("re.MULTILINE");
             }
-            if self.flags & DOTALL != 0 {
+            if flags & DOTALL != 0 {
                 flag_parts.push("re.DOTALL");
             }
-            if self.flags & ASCII != 0 {
+            if flags & VERBOSE != 0 {
+                flag_parts.push("re.VERBOSE");
+            }
+            if flags & ASCII != 0 {
                 flag_parts.push("re.ASCII");
             }
+            // Any remaining bits have no name, CPython shows them in hex
+            let unknown = flags & !(IGNORECASE | MULTILINE | DOTALL | UNICODE | VERBOSE | ASCII);
             write!(f, ", {}", flag_parts.join("|"))?;
+            if unknown != 0 {
+                let sep = if flag_parts.is_empty() { "" } else { "|" };
+                write!(f, "{sep}{unknown:#x}")?;
+            }
         }
         write!(f, ")")
     }
@@ -320,7 +501,31 @@ impl PyTrait for RePattern {
                 let v = Value::Ref(vm.heap.allocate(HeapData::Str(s))?);
                 Ok(Some(CallResult::Value(v)))
             }
-            Some(StaticStrings::Flags) => Ok(Some(CallResult::Value(Value::Int(i64::from(self.flags))))),
+            Some(StaticStrings::Flags) => {
+                // `str` patterns are always Unicode unless ASCII was requested
+                let flags = if self.flags & ASCII == 0 {
+                    self.flags | UNICODE
+                } else {
+                    self.flags
+                };
+                Ok(Some(CallResult::Value(Value::Int(i64::from(flags)))))
+            }
+            Some(StaticStrings::Groups) => {
+                let groups = i64::try_from(self.group_count).expect("group count fits in i64");
+                Ok(Some(CallResult::Value(Value::Int(groups))))
+            }
+            Some(StaticStrings::Groupindex) => {
+                let mut pairs = Vec::with_capacity(self.group_names.len());
+                for (name, idx) in &self.group_names {
+                    let key = Value::Ref(vm.heap.allocate(HeapData::Str(Str::new(name.clone())))?);
+                    let idx = i64::try_from(*idx).expect("group number fits in i64");
+                    pairs.push((key, Value::Int(idx)));
+                }
+                let dict = Dict::from_pairs(pairs, vm)?;
+                Ok(Some(CallResult::Value(Value::Ref(
+                    vm.heap.allocate(HeapData::Dict(dict))?,
+                ))))
+            }
             _ => Err(ExcType::attribute_error(Type::RePattern, attr.as_str(vm.interns))),
         }
     }
@@ -357,7 +562,8 @@ impl PyTrait for RePattern {
                 let text = value_to_str(arg, vm.heap, vm.interns)?.into_owned();
                 self.findall(&text, vm.heap)
             }
-            Some(StaticStrings::Sub) => call_pattern_sub(self, args, vm.heap, vm.interns),
+            Some(StaticStrings::Sub) => call_pattern_sub(self, args, "Pattern.sub", false, vm),
+            Some(StaticStrings::Subn) => call_pattern_sub(self, args, "Pattern.subn", true, vm),
             Some(StaticStrings::Split) => call_pattern_split(self, args, vm.heap, vm.interns),
             Some(StaticStrings::Finditer) => {
                 let arg = args.get_one_arg("Pattern.finditer", vm.heap)?;
@@ -371,105 +577,123 @@ impl PyTrait for RePattern {
     }
 }
 
-/// Handles `pattern.sub(repl, string, count=0)` argument extraction and dispatch.
+/// Handles `pattern.sub(repl, string, count=0)` and `pattern.subn(...)` argument
+/// extraction and dispatch.
 ///
 /// Separated from the main `py_call_attr` match to keep the borrow checker happy —
 /// extracting multiple string arguments requires careful ordering of borrows.
-/// Supports `count` as either positional or keyword argument.
+/// Supports `count` as either positional or keyword argument. `subn` returns a
+/// `(new_string, number_of_subs)` tuple instead of just the string.
 fn call_pattern_sub(
-    pattern: &RePattern,
+    pattern: &mut RePattern,
     args: ArgValues,
-    heap: &mut Heap<impl ResourceTracker>,
-    interns: &Interns,
+    func_name: &str,
+    return_count: bool,
+    vm: &mut VM<'_, '_, impl ResourceTracker>,
 ) -> RunResult<Value> {
     let (pos, kwargs) = args.into_parts();
-    defer_drop_mut!(pos, heap);
+    defer_drop_mut!(pos, vm);
     let kwargs = kwargs.into_iter();
-    defer_drop_mut!(kwargs, heap);
+    defer_drop_mut!(kwargs, vm);
 
     let Some(repl_val) = pos.next() else {
-        return Err(ExcType::type_error("Pattern.sub() missing required argument: 'repl'"));
+        return Err(ExcType::type_error(format!(
+            "{func_name}() missing required argument: 'repl'"
+        )));
     };
-    defer_drop!(repl_val, heap);
+    defer_drop!(repl_val, vm);
 
     let Some(string_val) = pos.next() else {
-        return Err(ExcType::type_error("Pattern.sub() missing required argument: 'string'"));
+        return Err(ExcType::type_error(format!(
+            "{func_name}() missing required argument: 'string'"
+        )));
     };
-    defer_drop!(string_val, heap);
+    defer_drop!(string_val, vm);
 
     let pos_count = pos.next();
 
     if let Some(extra) = pos.next() {
-        extra.drop_with_heap(heap);
-        return Err(ExcType::type_error(
-            "Pattern.sub() takes at most 3 positional arguments",
-        ));
+        extra.drop_with_heap(vm);
+        return Err(ExcType::type_error(format!(
+            "{func_name}() takes at most 3 positional arguments"
+        )));
     }
 
     // Extract count from kwargs if not given positionally
     let mut kw_count: Option<Value> = None;
     for (key, value) in kwargs {
-        defer_drop!(key, heap);
-        let Some(keyword_name) = key.as_either_str(heap) else {
-            value.drop_with_heap(heap);
+        defer_drop!(key, vm);
+        let Some(keyword_name) = key.as_either_str(vm.heap) else {
+            value.drop_with_heap(vm);
             return Err(ExcType::type_error("keywords must be strings"));
         };
-        let key_str = keyword_name.as_str(interns);
+        let key_str = keyword_name.as_str(vm.interns);
         if key_str == "count" {
             if pos_count.is_some() {
-                value.drop_with_heap(heap);
-                return Err(ExcType::type_error(
-                    "Pattern.sub() got multiple values for argument 'count'",
-                ));
+                value.drop_with_heap(vm);
+                return Err(ExcType::type_error(format!(
+                    "{func_name}() got multiple values for argument 'count'"
+                )));
             }
-            kw_count.replace(value).drop_with_heap(heap);
+            kw_count.replace(value).drop_with_heap(vm);
         } else {
-            value.drop_with_heap(heap);
+            value.drop_with_heap(vm);
             return Err(ExcType::type_error(format!(
-                "'{key_str}' is an invalid keyword argument for Pattern.sub()"
+                "'{key_str}' is an invalid keyword argument for {func_name}()"
             )));
         }
     }
 
-    let count_val = pos_count.or(kw_count);
-
-    #[expect(
-        clippy::cast_sign_loss,
-        clippy::cast_possible_truncation,
-        reason = "n is checked non-negative above"
-    )]
-    let count = match count_val {
-        Some(Value::Int(n)) if n >= 0 => n as usize,
-        Some(Value::Bool(b)) => usize::from(b),
-        Some(Value::Int(_)) => {
-            let text = value_to_str(string_val, heap, interns)?.into_owned();
-            let s = Str::new(text);
-            return Ok(Value::Ref(heap.allocate(HeapData::Str(s))?));
-        }
+    let count = extract_count(pos_count.or(kw_count), vm.heap)?;
+    let text = value_to_str(string_val, vm.heap, vm.interns)?.into_owned();
+    let (result, n) = match count {
+        Some(count) => pattern.sub(repl_val, &text, count, vm)?,
+        // Negative count — no substitutions are made
+        None => (text, 0),
+    };
+    sub_result(result, n, return_count, vm.heap)
+}
+
+/// Extracts the `count` argument of `sub`/`subn`.
+///
+/// Returns `Some(0)` (replace all) if not provided, and `None` for a negative count,
+/// which makes no substitutions.
+pub(crate) fn extract_count(val: Option<Value>, heap: &mut Heap<impl ResourceTracker>) -> RunResult<Option<usize>> {
+    match val {
+        None => Ok(Some(0)),
+        Some(Value::Int(n)) => Ok(usize::try_from(n).ok()),
+        Some(Value::Bool(b)) => Ok(Some(usize::from(b))),
         Some(other) => {
             let t = other.py_type(heap);
             other.drop_with_heap(heap);
-            return Err(ExcType::type_error(format!("expected int for count, not {t}")));
+            Err(ExcType::type_error(format!(
+                "'{t}' object cannot be interpreted as an integer for 'count' argument"
+            )))
         }
-        None => 0,
-    };
+    }
+}
 
-    // Check that repl is a string — callable replacement is not supported
-    if !repl_val.is_str(heap) {
-        return Err(ExcType::type_error(
-            "callable replacement is not yet supported in re.sub()",
-        ));
+/// Builds the return value of `sub` (the new string) or `subn` (a `(string, count)` tuple).
+pub(crate) fn sub_result(
+    result: String,
+    count: usize,
+    return_count: bool,
+    heap: &mut Heap<impl ResourceTracker>,
+) -> RunResult<Value> {
+    let s = Value::Ref(heap.allocate(HeapData::Str(Str::new(result)))?);
+    if return_count {
+        let count = i64::try_from(count).expect("substitution count fits in i64");
+        Ok(allocate_tuple(smallvec::smallvec![s, Value::Int(count)], heap)?)
+    } else {
+        Ok(s)
     }
-    let repl = value_to_str(repl_val, heap, interns)?.into_owned();
-    let text = value_to_str(string_val, heap, interns)?.into_owned();
-    pattern.sub(&repl, &text, count, heap)
 }
 
 /// Handles `pattern.split(string, maxsplit=0)` argument extraction and dispatch.
 ///
 /// Supports `maxsplit` as either positional or keyword argument.
 fn call_pattern_split(
-    pattern: &RePattern,
+    pattern: &mut RePattern,
     args: ArgValues,
     heap: &mut Heap<impl ResourceTracker>,
     interns: &Interns,
@@ -546,145 +770,95 @@ fn extract_maxsplit(val: Option<Value>, heap: &mut Heap<impl ResourceTracker>) -
     }
 }
 
-/// Compiles a Python regex pattern string with flags into a Rust `Regex`.
+/// Backtracking steps the regex engine is assumed to execute per millisecond.
+///
+/// Used to convert the remaining execution time into a backtrack limit. `fancy_regex` 0.17's
+/// backtracking VM measured 10k to 17k steps per millisecond in release builds on
+/// catastrophic patterns such as `((a+)\2)+b` and `(a|aa)+(?=b)c`, so this is the low end of
+/// that range. Slower hosts simply overrun the deadline by a proportional amount rather than
+/// running unbounded.
+const BACKTRACK_STEPS_PER_MS: u128 = 10_000;
+
+/// `fancy_regex`'s default backtrack limit, which applies when there is no time limit.
+const DEFAULT_BACKTRACK_LIMIT: usize = 1_000_000;
+
+/// Returns the backtrack limit to compile regexes with for the tracker's remaining time.
 ///
-/// Translates Python flag constants into inline regex flag prefixes:
-/// - `re.IGNORECASE` (2) → `(?i)` prefix
-/// - `re.MULTILINE` (8) → `(?m)` prefix
-/// - `re.DOTALL` (16) → `(?s)` prefix
+/// Returns `None` to leave `fancy_regex`'s default limit in place (exceeding it raises
+/// `re.PatternError`): either there is no time limit, or enough time remains that the
+/// default limit is the smaller of the two. Otherwise the remaining time is converted to
+/// steps with [`BACKTRACK_STEPS_PER_MS`] and rounded up to a power of two, so that
+/// [`RePattern`]s only need recompiling when the remaining time halves.
 ///
 /// # Errors
 ///
-/// Returns `re.PatternError(...)` if the pattern is invalid.
-pub(crate) fn compile_regex(pattern: &str, flags: u16) -> RunResult<Regex> {
-    let mut prefix = String::new();
-    if flags & IGNORECASE != 0 {
-        prefix.push('i');
-    }
-    if flags & MULTILINE != 0 {
-        prefix.push('m');
-    }
-    if flags & DOTALL != 0 {
-        prefix.push('s');
-    }
-    // Note: re.ASCII (256) is accepted but has no effect on the regex compilation.
-    // `fancy_regex` doesn't support `(?-u)` to disable Unicode mode, so `\w`, `\d`, `\s`
-    // always match Unicode characters. This is a known limitation — Python 3 defaults to
-    // Unicode mode anyway, so the behavioral difference only matters for non-ASCII input.
-
-    let full_pattern = if prefix.is_empty() {
-        pattern.to_owned()
-    } else {
-        format!("(?{prefix}){pattern}")
+/// Returns `ResourceError::Time` if the time limit has already been exceeded, which
+/// `check_time` may not have noticed yet since it only samples the clock periodically.
+pub(crate) fn backtrack_budget(tracker: &impl ResourceTracker) -> Result<Option<usize>, ResourceError> {
+    let Some((limit, elapsed)) = tracker.time_usage() else {
+        return Ok(None);
     };
-
-    Regex::new(&full_pattern).map_err(ExcType::re_pattern_error)
+    if elapsed > limit {
+        return Err(ResourceError::Time { limit, elapsed });
+    }
+    let steps = (limit - elapsed).as_micros().saturating_mul(BACKTRACK_STEPS_PER_MS) / 1000;
+    let steps = usize::try_from(steps).unwrap_or(usize::MAX).max(1);
+    let steps = steps.checked_next_power_of_two().unwrap_or(usize::MAX);
+    Ok((steps < DEFAULT_BACKTRACK_LIMIT).then_some(steps))
 }
 
-/// Translates Python-style replacement backreferences to `fancy_regex` syntax.
-///
-/// Python uses `\1`, `\2`, `\g<1>`, `\g<name>` for backreferences in replacement strings.
-/// `fancy_regex` uses `$1`, `$2`, `${1}`, `${name}`. This function converts between them.
-///
-/// # Supported translations
-///
-/// - `\1`–`\9` → `$1`–`$9` (single-digit backreferences)
-/// - `\g<N>` → `${N}` (numeric backreference with explicit syntax)
-/// - `\g<name>` → `${name}` (named group backreference)
-/// - `\\` → literal backslash
-/// - `$` → `$$` (escape literal `$` so `fancy_regex` doesn't misinterpret it)
+/// Fails with `ResourceError::Time` once the time limit has passed.
 ///
-/// Returns a `Cow` to avoid allocation when no translation is needed.
+/// Called between matches by methods that find every match: the backtrack budget bounds
+/// each match on its own, so without this a subject with many expensive matches could
+/// overrun the deadline once per match.
+fn check_deadline(tracker: &impl ResourceTracker) -> Result<(), ResourceError> {
+    backtrack_budget(tracker).map(drop)
+}
+
+/// Compiles the unanchored, `match()` and `fullmatch()` regexes for a translated pattern.
 ///
-/// # Limitations
+/// Note: re.ASCII (256) is accepted but has no effect on the regex compilation.
+/// `fancy_regex` doesn't support `(?-u)` to disable Unicode mode, so `\w`, `\d`, `\s`
+/// always match Unicode characters. This is a known limitation — Python 3 defaults to
+/// Unicode mode anyway, so the behavioral difference only matters for non-ASCII input.
 ///
-/// TODO: Multi-digit backreferences like `\10` are not fully supported. CPython
-/// greedily reads all digits after `\` and interprets them as a group number if
-/// that group exists, otherwise falls back to octal escapes. Currently `\10` is
-/// translated as `$1` followed by literal `0`, which is wrong when 10+ groups
-/// exist. Fixing this requires passing the pattern's capture group count into
-/// this function to disambiguate.
-fn translate_replacement(repl: &str) -> Cow<'_, str> {
-    // Fast path: no backslashes and no literal `$` means nothing to translate or escape.
-    if !repl.contains('\\') && !repl.contains('$') {
-        return Cow::Borrowed(repl);
-    }
-
-    let mut result = String::with_capacity(repl.len());
-    let mut chars = repl.chars().peekable();
-
-    while let Some(c) = chars.next() {
-        if c == '\\' {
-            match chars.peek() {
-                Some(&d) if d.is_ascii_digit() => {
-                    // TODO: This only handles single-digit backrefs (\1–\9).
-                    // Multi-digit like \10 should be ${10} when group 10 exists,
-                    // but that requires knowing the group count. See docstring.
-                    result.push('$');
-                    result.push(d);
-                    chars.next();
-                }
-                Some(&'g') => {
-                    chars.next(); // consume 'g'
-                    translate_g_backref(&mut chars, &mut result);
-                }
-                Some(&'\\') => {
-                    result.push('\\');
-                    chars.next();
-                }
-                _ => {
-                    result.push('\\');
-                }
-            }
-        } else if c == '$' {
-            // Escape literal `$` as `$$` so `fancy_regex` doesn't interpret `$1` etc.
-            // as backreferences.
-            result.push('$');
-            result.push('$');
-        } else {
-            result.push(c);
+/// On failure, returns the engine error along with the body that was being compiled
+/// so [`pattern_error`] can map its position back to the Python source.
+fn compile_regexes(
+    prefix: &str,
+    body: &str,
+    backtrack_limit: Option<usize>,
+) -> Result<[Regex; 3], (fancy_regex::Error, String)> {
+    let build = |body: String| {
+        let mut builder = RegexBuilder::new(&format!("{prefix}{body}"));
+        if let Some(limit) = backtrack_limit {
+            builder.backtrack_limit(limit);
         }
-    }
-
-    Cow::Owned(result)
+        builder.build().map_err(|err| (err, body))
+    };
+    Ok([
+        build(body.to_owned())?,
+        build(format!("\\A(?:{body})"))?,
+        build(format!("\\A(?:{body})\\z"))?,
+    ])
 }
 
-/// Translates a `\g<...>` backreference to `fancy_regex` `${...}` syntax.
+/// Converts a `fancy_regex` compile error for `body` into `re.PatternError(...)`.
 ///
-/// Called after `\g` has been consumed. Reads `<name_or_number>` from the iterator
-/// and writes `${name_or_number}` to the result. If the syntax is malformed
-/// (missing `<` or `>`), the literal characters are written through unchanged.
-fn translate_g_backref(chars: &mut std::iter::Peekable<std::str::Chars<'_>>, result: &mut String) {
-    if chars.peek() != Some(&'<') {
-        // Not \g<...>, just literal \g
-        result.push('\\');
-        result.push('g');
-        return;
-    }
-    chars.next(); // consume '<'
-
-    // Collect everything until '>'
-    let mut name = String::new();
-    loop {
-        match chars.next() {
-            Some('>') => break,
-            Some(ch) => name.push(ch),
-            None => {
-                // Unterminated \g<... — emit literally
-                result.push('\\');
-                result.push('g');
-                result.push('<');
-                result.push_str(&name);
-                return;
-            }
+/// `body` is either the translated pattern itself or an anchored wrapper around it;
+/// when the engine reports a position it is mapped back to the Python source.
+fn pattern_error(parsed: &ParsedPattern, body: &str, err: fancy_regex::Error) -> RunError {
+    match err {
+        fancy_regex::Error::ParseError(offset, kind) => {
+            // Anchored variants wrap the body in `\A(?:`, which is 5 bytes longer
+            let wrapper = if body.len() > parsed.body.len() { 5 } else { 0 };
+            let body_offset = offset.saturating_sub(parsed.prefix.len() + wrapper);
+            let pos = parsed.source_position(body_offset);
+            ExcType::re_pattern_error(format!("{kind} at position {pos}"))
         }
+        other => ExcType::re_pattern_error(other),
     }
-
-    // Write as ${name_or_number} for fancy_regex
-    result.push('$');
-    result.push('{');
-    result.push_str(&name);
-    result.push('}');
 }
 
 /// Extracts a string from a `Value`, supporting both interned and heap strings.
@@ -721,6 +895,6 @@ impl Serialize for RePattern {
 impl<'de> Deserialize<'de> for RePattern {
     fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
         let (pattern, flags): (String, u16) = Deserialize::deserialize(deserializer)?;
-        Self::compile(pattern, flags).map_err(|e| serde::de::Error::custom(format!("{e:?}")))
+        Self::compile(pattern, flags, None).map_err(|e| serde::de::Error::custom(format!("{e:?}")))
     }
 }
@@ -0,0 +1,601 @@
+//! Python `difflib.SequenceMatcher` type implementation.
+//!
+//! This is a port of CPython's Ratcliff/Obershelp matcher, including the junk and
+//! "autojunk" popularity heuristics, so ratios and opcodes match CPython exactly.
+//!
+//! Sequences are either strings (compared character by character) or lists/tuples of
+//! strings (typically lines). Elements are mapped to dense integer symbols when the
+//! matcher is built, so the matching itself never touches the heap.
+//!
+//! Only the sequences given to the constructor are supported: `set_seq1()`,
+//! `set_seq2()` and `set_seqs()` are not implemented.
+
+use std::fmt::Write;
+
+use ahash::{AHashMap, AHashSet};
+use smallvec::smallvec;
+
+use crate::{
+    args::ArgValues,
+    bytecode::{CallResult, VM},
+    defer_drop,
+    exception_private::{ExcType, RunError, RunResult},
+    heap::{DropWithHeap, Heap, HeapData, HeapId},
+    intern::{StaticStrings, StringId},
+    resource::{ResourceError, ResourceTracker},
+    types::{List, NamedTuple, PyTrait, Str, Type, allocate_tuple, re_pattern::value_to_str},
+    value::{EitherStr, Value},
+};
+
+/// A `difflib.SequenceMatcher` comparing two symbolised sequences.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub(crate) struct SequenceMatcher {
+    /// The first sequence, as symbols.
+    a: Vec<u32>,
+    /// The second sequence, as symbols.
+    b: Vec<u32>,
+    /// Per-symbol flag: elements of `b` for which `isjunk` returned true.
+    junk: Vec<bool>,
+    /// Per-symbol flag: elements of `b` discarded by the autojunk heuristic.
+    popular: Vec<bool>,
+    /// Cached result of `get_matching_blocks()`.
+    matching_blocks: Option<Vec<(usize, usize, usize)>>,
+}
+
+/// An edit operation from `get_opcodes()`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum OpTag {
+    Replace,
+    Delete,
+    Insert,
+    Equal,
+}
+
+impl OpTag {
+    /// The tag string CPython uses for this operation.
+    fn as_value(self) -> Value {
+        match self {
+            Self::Replace => StaticStrings::Replace.into(),
+            Self::Delete => StaticStrings::Delete.into(),
+            Self::Insert => StaticStrings::Insert.into(),
+            Self::Equal => StaticStrings::Equal.into(),
+        }
+    }
+}
+
+/// An opcode: `(tag, i1, i2, j1, j2)` describing how to turn `a[i1:i2]` into `b[j1:j2]`.
+pub(crate) type Opcode = (OpTag, usize, usize, usize, usize);
+
+/// Maps sequence elements to dense integer symbols shared by both sequences.
+#[derive(Debug, Default)]
+pub(crate) struct Symbols {
+    ids: AHashMap<String, u32>,
+    names: Vec<String>,
+}
+
+impl Symbols {
+    /// Returns the symbol for `element`, assigning a new one if needed.
+    pub fn intern(&mut self, element: &str) -> u32 {
+        if let Some(id) = self.ids.get(element) {
+            return *id;
+        }
+        let id = u32::try_from(self.names.len()).expect("symbol count fits in u32");
+        self.ids.insert(element.to_owned(), id);
+        self.names.push(element.to_owned());
+        id
+    }
+
+    /// Symbolises a whole sequence.
+    pub fn intern_all(&mut self, elements: &[String]) -> Vec<u32> {
+        elements.iter().map(|element| self.intern(element)).collect()
+    }
+
+    /// Number of distinct symbols assigned so far.
+    pub fn len(&self) -> usize {
+        self.names.len()
+    }
+}
+
+impl SequenceMatcher {
+    /// Builds a matcher over two symbolised sequences.
+    ///
+    /// `junk` flags symbols rejected by `isjunk` and must cover every symbol. With
+    /// `autojunk`, elements making up more than 1% of a `b` of at least 200 items are
+    /// treated as popular and ignored when searching for matches, as in CPython.
+    pub fn new(a: Vec<u32>, b: Vec<u32>, junk: Vec<bool>, autojunk: bool) -> Self {
+        let mut popular = vec![false; junk.len()];
+        let n = b.len();
+        if autojunk && n >= 200 {
+            let ntest = n / 100 + 1;
+            let mut counts = vec![0usize; junk.len()];
+            for &elt in &b {
+                counts[elt as usize] += 1;
+            }
+            for (elt, count) in counts.into_iter().enumerate() {
+                popular[elt] = count > ntest && !junk[elt];
+            }
+        }
+        Self {
+            a,
+            b,
+            junk,
+            popular,
+            matching_blocks: None,
+        }
+    }
+
+    /// `SequenceMatcher(isjunk=None, a='', b='', autojunk=True)` constructor.
+    pub fn init(vm: &mut VM<'_, '_, impl ResourceTracker>, args: ArgValues) -> RunResult<Value> {
+        let params = args.bind_params(
+            "SequenceMatcher",
+            ["isjunk", "a", "b", "autojunk"],
+            0,
+            vm.heap,
+            vm.interns,
+        )?;
+        defer_drop!(params, vm);
+        let [isjunk, a, b, autojunk] = params;
+        let autojunk = autojunk.as_ref().is_none_or(|v| v.py_bool(vm));
+
+        let mut symbols = Symbols::default();
+        let a = match a {
+            Some(a) => symbols.intern_all(&sequence_elements(a, vm)?),
+            None => Vec::new(),
+        };
+        let b = match b {
+            Some(b) => symbols.intern_all(&sequence_elements(b, vm)?),
+            None => Vec::new(),
+        };
+        let junk = match isjunk {
+            Some(isjunk) if !matches!(isjunk, Value::None) => junk_symbols(isjunk, &b, &symbols, vm)?,
+            _ => vec![false; symbols.len()],
+        };
+        let matcher = Self::new(a, b, junk, autojunk);
+        Ok(Value::Ref(
+            vm.heap.allocate(HeapData::SequenceMatcher(Box::new(matcher)))?,
+        ))
+    }
+
+    /// Whether `elt` is a junk element of `b`.
+    fn is_bjunk(&self, elt: u32) -> bool {
+        self.junk[elt as usize]
+    }
+
+    /// Maps each symbol to the ascending indices at which it appears in `b`,
+    /// leaving out junk and popular elements.
+    fn b2j(&self) -> Vec<Vec<usize>> {
+        let mut b2j = vec![Vec::new(); self.junk.len()];
+        for (j, &elt) in self.b.iter().enumerate() {
+            if !self.junk[elt as usize] && !self.popular[elt as usize] {
+                b2j[elt as usize].push(j);
+            }
+        }
+        b2j
+    }
+
+    /// Finds the longest matching block in `a[alo:ahi]` and `b[blo:bhi]`.
+    ///
+    /// Returns `(i, j, k)` such that `a[i:i+k] == b[j:j+k]`, preferring the earliest
+    /// block in `a` and then in `b`, then extends it with adjacent equal junk exactly
+    /// like CPython's `find_longest_match`.
+    fn find_longest_match(
+        &self,
+        b2j: &[Vec<usize>],
+        (alo, ahi, blo, bhi): (usize, usize, usize, usize),
+        heap: &Heap<impl ResourceTracker>,
+    ) -> Result<(usize, usize, usize), ResourceError> {
+        let (a, b) = (&self.a, &self.b);
+        let (mut besti, mut bestj, mut bestsize) = (alo, blo, 0);
+        let mut j2len: AHashMap<usize, usize> = AHashMap::new();
+        for i in alo..ahi {
+            heap.check_time()?;
+            let mut newj2len = AHashMap::new();
+            for &j in &b2j[a[i] as usize] {
+                if j < blo {
+                    continue;
+                }
+                if j >= bhi {
+                    break;
+                }
+                let k = j.checked_sub(1).and_then(|prev| j2len.get(&prev)).copied().unwrap_or(0) + 1;
+                newj2len.insert(j, k);
+                if k > bestsize {
+                    (besti, bestj, bestsize) = (i + 1 - k, j + 1 - k, k);
+                }
+            }
+            j2len = newj2len;
+        }
+
+        // Extend the match with adjacent non-junk elements (popular elements can
+        // match here), then with adjacent junk on both sides.
+        for junk in [false, true] {
+            while besti > alo && bestj > blo && self.is_bjunk(b[bestj - 1]) == junk && a[besti - 1] == b[bestj - 1] {
+                (besti, bestj, bestsize) = (besti - 1, bestj - 1, bestsize + 1);
+            }
+            while besti + bestsize < ahi
+                && bestj + bestsize < bhi
+                && self.is_bjunk(b[bestj + bestsize]) == junk
+                && a[besti + bestsize] == b[bestj + bestsize]
+            {
+                bestsize += 1;
+            }
+        }
+        Ok((besti, bestj, bestsize))
+    }
+
+    /// Returns the matching blocks, ending with the `(len(a), len(b), 0)` sentinel.
+    pub fn matching_blocks(
+        &mut self,
+        heap: &Heap<impl ResourceTracker>,
+    ) -> Result<&[(usize, usize, usize)], ResourceError> {
+        if self.matching_blocks.is_none() {
+            let (la, lb) = (self.a.len(), self.b.len());
+            let b2j = self.b2j();
+            let mut queue = vec![(0, la, 0, lb)];
+            let mut blocks = Vec::new();
+            while let Some(range @ (alo, ahi, blo, bhi)) = queue.pop() {
+                let (i, j, k) = self.find_longest_match(&b2j, range, heap)?;
+                if k > 0 {
+                    blocks.push((i, j, k));
+                    if alo < i && blo < j {
+                        queue.push((alo, i, blo, j));
+                    }
+                    if i + k < ahi && j + k < bhi {
+                        queue.push((i + k, ahi, j + k, bhi));
+                    }
+                }
+            }
+            blocks.sort_unstable();
+
+            // Collapse adjacent blocks.
+            let (mut i1, mut j1, mut k1) = (0, 0, 0);
+            let mut non_adjacent = Vec::with_capacity(blocks.len() + 1);
+            for (i2, j2, k2) in blocks {
+                if i1 + k1 == i2 && j1 + k1 == j2 {
+                    k1 += k2;
+                } else {
+                    if k1 > 0 {
+                        non_adjacent.push((i1, j1, k1));
+                    }
+                    (i1, j1, k1) = (i2, j2, k2);
+                }
+            }
+            if k1 > 0 {
+                non_adjacent.push((i1, j1, k1));
+            }
+            non_adjacent.push((la, lb, 0));
+            self.matching_blocks = Some(non_adjacent);
+        }
+        Ok(self
+            .matching_blocks
+            .as_deref()
+            .expect("matching blocks were just computed"))
+    }
+
+    /// Returns the opcodes describing how to turn `a` into `b`.
+    pub fn opcodes(&mut self, heap: &Heap<impl ResourceTracker>) -> Result<Vec<Opcode>, ResourceError> {
+        let (mut i, mut j) = (0, 0);
+        let mut answer = Vec::new();
+        for &(ai, bj, size) in self.matching_blocks(heap)? {
+            let tag = if i < ai && j < bj {
+                Some(OpTag::Replace)
+            } else if i < ai {
+                Some(OpTag::Delete)
+            } else if j < bj {
+                Some(OpTag::Insert)
+            } else {
+                None
+            };
+            if let Some(tag) = tag {
+                answer.push((tag, i, ai, j, bj));
+            }
+            (i, j) = (ai + size, bj + size);
+            if size > 0 {
+                answer.push((OpTag::Equal, ai, i, bj, j));
+            }
+        }
+        Ok(answer)
+    }
+
+    /// Groups opcodes into hunks with up to `n` lines of context, as used by `unified_diff`.
+    pub fn grouped_opcodes(
+        &mut self,
+        n: usize,
+        heap: &Heap<impl ResourceTracker>,
+    ) -> Result<Vec<Vec<Opcode>>, ResourceError> {
+        let mut codes = self.opcodes(heap)?;
+        if codes.is_empty() {
+            codes.push((OpTag::Equal, 0, 1, 0, 1));
+        }
+        if let Some(first) = codes.first_mut()
+            && first.0 == OpTag::Equal
+        {
+            let (tag, i1, i2, j1, j2) = *first;
+            *first = (tag, i1.max(i2.saturating_sub(n)), i2, j1.max(j2.saturating_sub(n)), j2);
+        }
+        if let Some(last) = codes.last_mut()
+            && last.0 == OpTag::Equal
+        {
+            let (tag, i1, i2, j1, j2) = *last;
+            *last = (tag, i1, i2.min(i1 + n), j1, j2.min(j1 + n));
+        }
+
+        let nn = n + n;
+        let mut groups = Vec::new();
+        let mut group = Vec::new();
+        for (tag, mut i1, i2, mut j1, j2) in codes {
+            if tag == OpTag::Equal && i2 - i1 > nn {
+                group.push((tag, i1, i2.min(i1 + n), j1, j2.min(j1 + n)));
+                groups.push(std::mem::take(&mut group));
+                i1 = i1.max(i2.saturating_sub(n));
+                j1 = j1.max(j2.saturating_sub(n));
+            }
+            group.push((tag, i1, i2, j1, j2));
+        }
+        if !group.is_empty() && !(group.len() == 1 && group[0].0 == OpTag::Equal) {
+            groups.push(group);
+        }
+        Ok(groups)
+    }
+
+    /// `ratio()`: twice the number of matched elements over the total length.
+    pub fn ratio(&mut self, heap: &Heap<impl ResourceTracker>) -> Result<f64, ResourceError> {
+        let matches = self.matching_blocks(heap)?.iter().map(|(_, _, size)| size).sum();
+        Ok(calculate_ratio(matches, self.a.len() + self.b.len()))
+    }
+
+    /// `quick_ratio()`: an upper bound on `ratio()` ignoring element order.
+    pub fn quick_ratio(&self) -> f64 {
+        let mut avail: Vec<isize> = vec![0; self.junk.len()];
+        for &elt in &self.b {
+            avail[elt as usize] += 1;
+        }
+        let mut matches = 0;
+        for &elt in &self.a {
+            let numb = &mut avail[elt as usize];
+            if *numb > 0 {
+                matches += 1;
+            }
+            *numb -= 1;
+        }
+        calculate_ratio(matches, self.a.len() + self.b.len())
+    }
+
+    /// `real_quick_ratio()`: an upper bound on `ratio()` using only the lengths.
+    pub fn real_quick_ratio(&self) -> f64 {
+        let (la, lb) = (self.a.len(), self.b.len());
+        calculate_ratio(la.min(lb), la + lb)
+    }
+
+    /// `find_longest_match(alo=0, ahi=None, blo=0, bhi=None)` method.
+    fn call_find_longest_match(&self, args: ArgValues, vm: &mut VM<'_, '_, impl ResourceTracker>) -> RunResult<Value> {
+        let params = args.bind_params(
+            "find_longest_match",
+            ["alo", "ahi", "blo", "bhi"],
+            0,
+            vm.heap,
+            vm.interns,
+        )?;
+        defer_drop!(params, vm);
+        let [alo, ahi, blo, bhi] = params;
+        let (la, lb) = (self.a.len(), self.b.len());
+        let alo = bound_arg(alo.as_ref(), 0, la, vm.heap)?;
+        let ahi = bound_arg(ahi.as_ref(), la, la, vm.heap)?;
+        let blo = bound_arg(blo.as_ref(), 0, lb, vm.heap)?;
+        let bhi = bound_arg(bhi.as_ref(), lb, lb, vm.heap)?;
+        let block = self.find_longest_match(&self.b2j(), (alo, ahi.max(alo), blo, bhi.max(blo)), vm.heap)?;
+        allocate_match(block, vm.heap)
+    }
+}
+
+/// Shared ratio formula: `2.0 * matches / length`, or 1.0 for two empty sequences.
+pub(crate) fn calculate_ratio(matches: usize, length: usize) -> f64 {
+    if length == 0 {
+        1.0
+    } else {
+        2.0 * matches as f64 / length as f64
+    }
+}
+
+/// Extracts the elements of a sequence argument as strings.
+///
+/// A `str` yields its characters; a list or tuple must contain only strings.
+pub(crate) fn sequence_elements(value: &Value, vm: &VM<'_, '_, impl ResourceTracker>) -> RunResult<Vec<String>> {
+    if value.is_str(vm.heap) {
+        let s = value_to_str(value, vm.heap, vm.interns)?;
+        return Ok(s.chars().map(String::from).collect());
+    }
+    let items = match value {
+        Value::Ref(id) => match vm.heap.get(*id) {
+            HeapData::List(list) => list.as_slice(),
+            HeapData::Tuple(tuple) => tuple.as_slice(),
+            _ => return Err(sequence_type_error(value, vm.heap)),
+        },
+        _ => return Err(sequence_type_error(value, vm.heap)),
+    };
+    items
+        .iter()
+        .map(|item| {
+            if item.is_str(vm.heap) {
+                Ok(value_to_str(item, vm.heap, vm.interns)?.into_owned())
+            } else {
+                Err(ExcType::type_error(format!(
+                    "sequence elements must be str, not {}",
+                    item.py_type(vm.heap)
+                )))
+            }
+        })
+        .collect()
+}
+
+/// Builds the `TypeError` for an argument that is not a str, list or tuple.
+fn sequence_type_error(value: &Value, heap: &Heap<impl ResourceTracker>) -> RunError {
+    ExcType::type_error(format!(
+        "expected a str, list or tuple sequence, not {}",
+        value.py_type(heap)
+    ))
+}
+
+/// Calls `isjunk` once per distinct element of `b` and flags the symbols it rejects.
+fn junk_symbols(
+    isjunk: &Value,
+    b: &[u32],
+    symbols: &Symbols,
+    vm: &mut VM<'_, '_, impl ResourceTracker>,
+) -> RunResult<Vec<bool>> {
+    let mut junk = vec![false; symbols.len()];
+    let mut seen = AHashSet::new();
+    for &elt in b {
+        if !seen.insert(elt) {
+            continue;
+        }
+        let element = Value::Ref(
+            vm.heap
+                .allocate(HeapData::Str(Str::new(symbols.names[elt as usize].clone())))?,
+        );
+        let result = vm.evaluate_function("SequenceMatcher() isjunk", isjunk, ArgValues::One(element))?;
+        junk[elt as usize] = result.py_bool(vm);
+        result.drop_with_heap(vm);
+    }
+    Ok(junk)
+}
+
+/// Reads an optional index argument, clamped to `0..=len`; `None` selects `default`.
+fn bound_arg(value: Option<&Value>, default: usize, len: usize, heap: &Heap<impl ResourceTracker>) -> RunResult<usize> {
+    match value {
+        None | Some(Value::None) => Ok(default),
+        Some(value) => {
+            let index = value.as_int(heap)?;
+            Ok(usize::try_from(index).unwrap_or(0).min(len))
+        }
+    }
+}
+
+/// Converts an index to an `int` value.
+fn int(value: usize) -> Value {
+    Value::Int(i64::try_from(value).expect("sequence index fits in i64"))
+}
+
+/// Allocates a `Match(a=..., b=..., size=...)` named tuple.
+fn allocate_match((i, j, k): (usize, usize, usize), heap: &mut Heap<impl ResourceTracker>) -> RunResult<Value> {
+    let fields = vec![
+        StringId::from_ascii(b'a').into(),
+        StringId::from_ascii(b'b').into(),
+        StaticStrings::Size.into(),
+    ];
+    let tuple = NamedTuple::new(StaticStrings::MatchClass, fields, vec![int(i), int(j), int(k)]);
+    Ok(Value::Ref(heap.allocate(HeapData::NamedTuple(tuple))?))
+}
+
+/// Allocates an opcode as a `(tag, i1, i2, j1, j2)` tuple.
+pub(crate) fn allocate_opcode(
+    (tag, i1, i2, j1, j2): Opcode,
+    heap: &mut Heap<impl ResourceTracker>,
+) -> Result<Value, ResourceError> {
+    allocate_tuple(smallvec![tag.as_value(), int(i1), int(i2), int(j1), int(j2)], heap)
+}
+
+/// Allocates a list of opcode tuples.
+fn allocate_opcodes(opcodes: Vec<Opcode>, heap: &mut Heap<impl ResourceTracker>) -> Result<Value, ResourceError> {
+    let mut items = Vec::with_capacity(opcodes.len());
+    for opcode in opcodes {
+        items.push(allocate_opcode(opcode, heap)?);
+    }
+    Ok(Value::Ref(heap.allocate(HeapData::List(List::new(items)))?))
+}
+
+impl PyTrait for SequenceMatcher {
+    fn py_type(&self, _heap: &Heap<impl ResourceTracker>) -> Type {
+        Type::SequenceMatcher
+    }
+
+    fn py_len(&self, _vm: &VM<'_, '_, impl ResourceTracker>) -> Option<usize> {
+        None
+    }
+
+    fn py_eq(&self, _other: &Self, _vm: &mut VM<'_, '_, impl ResourceTracker>) -> Result<bool, ResourceError> {
+        // Matchers compare by identity (handled at the Value level)
+        Ok(false)
+    }
+
+    fn py_dec_ref_ids(&mut self, _stack: &mut Vec<HeapId>) {
+        // No heap references — sequences are stored as symbols.
+    }
+
+    fn py_bool(&self, _vm: &VM<'_, '_, impl ResourceTracker>) -> bool {
+        true
+    }
+
+    fn py_repr_fmt(
+        &self,
+        f: &mut impl Write,
+        _vm: &VM<'_, '_, impl ResourceTracker>,
+        _heap_ids: &mut AHashSet<HeapId>,
+    ) -> std::fmt::Result {
+        f.write_str("<difflib.SequenceMatcher object>")
+    }
+
+    fn py_estimate_size(&self) -> usize {
+        std::mem::size_of::<Self>()
+            + (self.a.len() + self.b.len()) * std::mem::size_of::<u32>()
+            + self.junk.len() * 2
+            + self
+                .matching_blocks
+                .as_ref()
+                .map_or(0, |blocks| blocks.len() * std::mem::size_of::<(usize, usize, usize)>())
+    }
+
+    fn py_call_attr(
+        &mut self,
+        _self_id: HeapId,
+        vm: &mut VM<'_, '_, impl ResourceTracker>,
+        attr: &EitherStr,
+        args: ArgValues,
+    ) -> RunResult<CallResult> {
+        let result = match attr.static_string() {
+            Some(StaticStrings::Ratio) => {
+                args.check_zero_args("ratio", vm.heap)?;
+                Value::Float(self.ratio(vm.heap)?)
+            }
+            Some(StaticStrings::QuickRatio) => {
+                args.check_zero_args("quick_ratio", vm.heap)?;
+                Value::Float(self.quick_ratio())
+            }
+            Some(StaticStrings::RealQuickRatio) => {
+                args.check_zero_args("real_quick_ratio", vm.heap)?;
+                Value::Float(self.real_quick_ratio())
+            }
+            Some(StaticStrings::FindLongestMatch) => self.call_find_longest_match(args, vm)?,
+            Some(StaticStrings::GetMatchingBlocks) => {
+                args.check_zero_args("get_matching_blocks", vm.heap)?;
+                let blocks = self.matching_blocks(vm.heap)?.to_vec();
+                let mut items = Vec::with_capacity(blocks.len());
+                for block in blocks {
+                    items.push(allocate_match(block, vm.heap)?);
+                }
+                Value::Ref(vm.heap.allocate(HeapData::List(List::new(items)))?)
+            }
+            Some(StaticStrings::GetOpcodes) => {
+                args.check_zero_args("get_opcodes", vm.heap)?;
+                let opcodes = self.opcodes(vm.heap)?;
+                allocate_opcodes(opcodes, vm.heap)?
+            }
+            Some(StaticStrings::GetGroupedOpcodes) => {
+                let n = args.get_zero_one_arg("get_grouped_opcodes", vm.heap)?;
+                defer_drop!(n, vm);
+                let n = match n {
+                    Some(n) => usize::try_from(n.as_int(vm.heap)?).unwrap_or(0),
+                    None => 3,
+                };
+                let groups = self.grouped_opcodes(n, vm.heap)?;
+                let mut items = Vec::with_capacity(groups.len());
+                for group in groups {
+                    items.push(allocate_opcodes(group, vm.heap)?);
+                }
+                Value::Ref(vm.heap.allocate(HeapData::List(List::new(items)))?)
+            }
+            _ => return Err(ExcType::attribute_error(Type::SequenceMatcher, attr.as_str(vm.interns))),
+        };
+        Ok(CallResult::Value(result))
+    }
+}
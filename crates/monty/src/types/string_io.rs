@@ -0,0 +1,453 @@
+//! Python `io.StringIO` type implementation.
+//!
+//! An in-memory text buffer with a file-like interface, used to build up text
+//! incrementally (e.g. as the target of `csv.writer()`) or to read text line by line.
+//!
+//! Positions are measured in characters, as in CPython. Seeking past the end is allowed;
+//! a subsequent write pads the gap with NUL characters.
+//!
+//! The `newline` argument behaves as in CPython: `None` translates `'\r\n'` and `'\r'`
+//! to `'\n'` on write, `'\r'` and `'\r\n'` translate `'\n'` to themselves on write and
+//! end lines when reading, and `''` recognises all three line endings without translating.
+
+use std::{borrow::Cow, fmt::Write};
+
+use ahash::AHashSet;
+
+use crate::{
+    args::ArgValues,
+    bytecode::{CallResult, VM},
+    defer_drop,
+    exception_private::{ExcType, RunError, RunResult, SimpleException},
+    heap::{DropWithHeap, Heap, HeapData, HeapId},
+    intern::StaticStrings,
+    resource::{ResourceError, ResourceTracker, check_estimated_size},
+    types::{
+        List, MontyIter, PyTrait, Type,
+        re_pattern::value_to_str,
+        str::{StringRepr, allocate_string},
+    },
+    value::{EitherStr, Value},
+};
+
+/// The `newline` mode of a `StringIO`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+enum Newline {
+    /// `newline=None`: `'\r\n'` and `'\r'` are written as `'\n'`.
+    Translate,
+    /// `newline=''`: lines end at `'\n'`, `'\r'` or `'\r\n'`, nothing is translated.
+    Universal,
+    /// `newline='\n'` (the default): nothing is translated.
+    #[default]
+    Lf,
+    /// `newline='\r'`: `'\n'` is written as `'\r'`, and lines end at `'\r'`.
+    Cr,
+    /// `newline='\r\n'`: `'\n'` is written as `'\r\n'`, and lines end at `'\r\n'`.
+    CrLf,
+}
+
+impl Newline {
+    /// Applies the write translation of this mode to `text`.
+    fn translate<'t>(self, text: &'t str) -> Cow<'t, str> {
+        match self {
+            Self::Translate if text.contains('\r') => Cow::Owned(text.replace("\r\n", "\n").replace('\r', "\n")),
+            Self::Cr if text.contains('\n') => Cow::Owned(text.replace('\n', "\r")),
+            Self::CrLf if text.contains('\n') => Cow::Owned(text.replace('\n', "\r\n")),
+            _ => Cow::Borrowed(text),
+        }
+    }
+}
+
+/// An `io.StringIO` text buffer.
+#[derive(Debug, Clone, Default, serde::Serialize, serde::Deserialize)]
+pub(crate) struct StringIO {
+    /// The buffer contents.
+    buffer: String,
+    /// Byte offset of the current position, clamped to the end of the buffer.
+    pos: usize,
+    /// Number of characters the position lies beyond the end of the buffer.
+    past_end: usize,
+    /// How line endings are translated on write and recognised on read.
+    newline: Newline,
+    /// Whether `close()` has been called.
+    closed: bool,
+}
+
+impl StringIO {
+    /// `StringIO(initial_value='', newline='\n')` constructor.
+    pub fn init(vm: &mut VM<'_, '_, impl ResourceTracker>, args: ArgValues) -> RunResult<Value> {
+        let params = args.bind_params("StringIO", ["initial_value", "newline"], 0, vm.heap, vm.interns)?;
+        defer_drop!(params, vm);
+        let [initial_value, newline] = params;
+
+        let newline = match newline {
+            None => Newline::Lf,
+            Some(Value::None) => Newline::Translate,
+            Some(value) if value.is_str(vm.heap) => match value_to_str(value, vm.heap, vm.interns)?.as_ref() {
+                "" => Newline::Universal,
+                "\n" => Newline::Lf,
+                "\r" => Newline::Cr,
+                "\r\n" => Newline::CrLf,
+                other => {
+                    return Err(SimpleException::new_msg(
+                        ExcType::ValueError,
+                        format!("illegal newline value: {}", StringRepr(other)),
+                    )
+                    .into());
+                }
+            },
+            Some(value) => {
+                return Err(ExcType::type_error(format!(
+                    "newline must be str or None, not {}",
+                    value.py_type(vm.heap)
+                )));
+            }
+        };
+        let buffer = match initial_value {
+            None | Some(Value::None) => String::new(),
+            Some(value) if value.is_str(vm.heap) => newline
+                .translate(&value_to_str(value, vm.heap, vm.interns)?)
+                .into_owned(),
+            Some(value) => {
+                return Err(ExcType::type_error(format!(
+                    "initial_value must be str or None, not {}",
+                    value.py_type(vm.heap)
+                )));
+            }
+        };
+        let string_io = Self {
+            buffer,
+            newline,
+            ..Self::default()
+        };
+        Ok(Value::Ref(vm.heap.allocate(HeapData::StringIO(string_io))?))
+    }
+
+    /// Reads the remaining lines, as iterating over the buffer would.
+    ///
+    /// Used by `csv.reader()` to consume a buffer passed in place of a file.
+    pub fn read_lines(&mut self) -> RunResult<Vec<String>> {
+        self.check_open()?;
+        let mut lines = Vec::new();
+        while self.pos < self.buffer.len() {
+            lines.push(self.readline(None).to_owned());
+        }
+        Ok(lines)
+    }
+
+    /// Appends `text` at the current position, overwriting any characters already there.
+    ///
+    /// Returns the number of characters written.
+    pub fn write(&mut self, text: &str, tracker: &impl ResourceTracker) -> RunResult<usize> {
+        self.check_open()?;
+        let written = text.chars().count();
+        let text = self.newline.translate(text);
+        let text = text.as_ref();
+        check_estimated_size(self.buffer.len() + self.past_end + text.len(), tracker)?;
+        if self.pos == self.buffer.len() {
+            self.buffer.extend(std::iter::repeat_n('\0', self.past_end));
+            self.past_end = 0;
+            self.buffer.push_str(text);
+            self.pos = self.buffer.len();
+        } else {
+            let end = advance_chars(&self.buffer, self.pos, written);
+            self.buffer.replace_range(self.pos..end, text);
+            self.pos += text.len();
+        }
+        Ok(written)
+    }
+
+    /// Returns the current position in characters.
+    fn tell(&self) -> usize {
+        self.buffer[..self.pos].chars().count() + self.past_end
+    }
+
+    /// Moves to the absolute character position `target`.
+    fn seek_to(&mut self, target: usize) {
+        let mut chars = 0;
+        for (offset, _) in self.buffer.char_indices() {
+            if chars == target {
+                self.pos = offset;
+                self.past_end = 0;
+                return;
+            }
+            chars += 1;
+        }
+        self.pos = self.buffer.len();
+        self.past_end = target - chars;
+    }
+
+    /// Reads up to `size` characters (everything if `None`).
+    fn read(&mut self, size: Option<usize>) -> &str {
+        let start = self.pos;
+        let end = match size {
+            Some(size) => advance_chars(&self.buffer, start, size),
+            None => self.buffer.len(),
+        };
+        self.pos = end;
+        &self.buffer[start..end]
+    }
+
+    /// Reads one line including its terminator, stopping after `size` characters if given.
+    fn readline(&mut self, size: Option<usize>) -> &str {
+        let start = self.pos;
+        let rest = &self.buffer[start..];
+        let mut end = rest.len();
+        for (count, (offset, c)) in rest.char_indices().enumerate() {
+            if size.is_some_and(|size| count >= size) {
+                end = offset;
+                break;
+            }
+            let crlf = c == '\r' && rest[offset + 1..].starts_with('\n') && size.is_none_or(|s| count + 1 < s);
+            let line_len = match self.newline {
+                Newline::Translate | Newline::Lf => usize::from(c == '\n'),
+                Newline::Universal if crlf => 2,
+                Newline::Universal => usize::from(c == '\n' || c == '\r'),
+                Newline::Cr => usize::from(c == '\r'),
+                Newline::CrLf => 2 * usize::from(crlf),
+            };
+            if line_len > 0 {
+                end = offset + line_len;
+                break;
+            }
+        }
+        self.pos = start + end;
+        &self.buffer[start..start + end]
+    }
+
+    /// Raises `ValueError` if the buffer has been closed.
+    fn check_open(&self) -> RunResult<()> {
+        if self.closed {
+            Err(SimpleException::new_msg(ExcType::ValueError, "I/O operation on closed file").into())
+        } else {
+            Ok(())
+        }
+    }
+}
+
+/// Returns the byte offset `count` characters after `start`, clamped to the end of `text`.
+fn advance_chars(text: &str, start: usize, count: usize) -> usize {
+    text[start..]
+        .char_indices()
+        .nth(count)
+        .map_or(text.len(), |(offset, _)| start + offset)
+}
+
+/// Extracts an optional `size` argument where `None` or a negative value means "no limit".
+fn optional_size(value: Option<&Value>, heap: &Heap<impl ResourceTracker>) -> RunResult<Option<usize>> {
+    match value {
+        None | Some(Value::None) => Ok(None),
+        Some(value) => Ok(usize::try_from(value.as_int(heap)?).ok()),
+    }
+}
+
+impl PyTrait for StringIO {
+    fn py_type(&self, _heap: &Heap<impl ResourceTracker>) -> Type {
+        Type::StringIO
+    }
+
+    fn py_len(&self, _vm: &VM<'_, '_, impl ResourceTracker>) -> Option<usize> {
+        None
+    }
+
+    fn py_eq(&self, _other: &Self, _vm: &mut VM<'_, '_, impl ResourceTracker>) -> Result<bool, ResourceError> {
+        // Buffers compare by identity (handled at the Value level)
+        Ok(false)
+    }
+
+    fn py_dec_ref_ids(&mut self, _stack: &mut Vec<HeapId>) {
+        // No heap references — the buffer is owned.
+    }
+
+    fn py_bool(&self, _vm: &VM<'_, '_, impl ResourceTracker>) -> bool {
+        true
+    }
+
+    fn py_repr_fmt(
+        &self,
+        f: &mut impl Write,
+        _vm: &VM<'_, '_, impl ResourceTracker>,
+        _heap_ids: &mut AHashSet<HeapId>,
+    ) -> std::fmt::Result {
+        f.write_str("<_io.StringIO object>")
+    }
+
+    fn py_estimate_size(&self) -> usize {
+        std::mem::size_of::<Self>() + self.buffer.len()
+    }
+
+    fn py_getattr(&self, attr: &EitherStr, vm: &mut VM<'_, '_, impl ResourceTracker>) -> RunResult<Option<CallResult>> {
+        match attr.static_string() {
+            Some(StaticStrings::Closed) => Ok(Some(CallResult::Value(Value::Bool(self.closed)))),
+            _ => Err(ExcType::attribute_error(Type::StringIO, attr.as_str(vm.interns))),
+        }
+    }
+
+    fn py_call_attr(
+        &mut self,
+        self_id: HeapId,
+        vm: &mut VM<'_, '_, impl ResourceTracker>,
+        attr: &EitherStr,
+        args: ArgValues,
+    ) -> RunResult<CallResult> {
+        let Some(method) = attr.static_string() else {
+            args.drop_with_heap(vm);
+            return Err(ExcType::attribute_error(Type::StringIO, attr.as_str(vm.interns)));
+        };
+        if method == StaticStrings::Close {
+            args.check_zero_args("close", vm.heap)?;
+            self.closed = true;
+            return Ok(CallResult::Value(Value::None));
+        }
+        if method == StaticStrings::DunderExit {
+            // `__exit__(exc_type, exc, tb)` closes the buffer whatever the arguments are.
+            args.drop_with_heap(vm);
+            self.closed = true;
+            return Ok(CallResult::Value(Value::None));
+        }
+        if let Err(err) = self.check_open() {
+            args.drop_with_heap(vm);
+            return Err(err);
+        }
+        let result = match method {
+            StaticStrings::Write => {
+                let text = args.get_one_arg("write", vm.heap)?;
+                defer_drop!(text, vm);
+                if !text.is_str(vm.heap) {
+                    return Err(ExcType::type_error(format!(
+                        "string argument expected, got '{}'",
+                        text.py_type(vm.heap)
+                    )));
+                }
+                let text = value_to_str(text, vm.heap, vm.interns)?;
+                let written = self.write(&text, vm.heap.tracker())?;
+                Value::Int(i64::try_from(written).expect("character count fits in i64"))
+            }
+            StaticStrings::Writelines => {
+                let lines = args.get_one_arg("writelines", vm.heap)?;
+                let lines: Vec<Value> = MontyIter::new(lines, vm)?.collect(vm)?;
+                defer_drop!(lines, vm);
+                for line in lines.iter() {
+                    if !line.is_str(vm.heap) {
+                        return Err(ExcType::type_error(format!(
+                            "string argument expected, got '{}'",
+                            line.py_type(vm.heap)
+                        )));
+                    }
+                    let line = value_to_str(line, vm.heap, vm.interns)?;
+                    self.write(&line, vm.heap.tracker())?;
+                }
+                Value::None
+            }
+            StaticStrings::Getvalue => {
+                args.check_zero_args("getvalue", vm.heap)?;
+                allocate_string(self.buffer.clone(), vm.heap)?
+            }
+            StaticStrings::Read => {
+                let size = args.get_zero_one_arg("read", vm.heap)?;
+                defer_drop!(size, vm);
+                let size = optional_size(size.as_ref(), vm.heap)?;
+                let text = self.read(size).to_owned();
+                allocate_string(text, vm.heap)?
+            }
+            StaticStrings::Readline => {
+                let size = args.get_zero_one_arg("readline", vm.heap)?;
+                defer_drop!(size, vm);
+                let size = optional_size(size.as_ref(), vm.heap)?;
+                let line = self.readline(size).to_owned();
+                allocate_string(line, vm.heap)?
+            }
+            StaticStrings::Readlines => {
+                let hint = args.get_zero_one_arg("readlines", vm.heap)?;
+                defer_drop!(hint, vm);
+                let hint = optional_size(hint.as_ref(), vm.heap)?.filter(|hint| *hint > 0);
+                let mut lines = Vec::new();
+                let mut total = 0;
+                while self.pos < self.buffer.len() {
+                    let line = self.readline(None).to_owned();
+                    total += line.chars().count();
+                    lines.push(allocate_string(line, vm.heap)?);
+                    if hint.is_some_and(|hint| total >= hint) {
+                        break;
+                    }
+                }
+                Value::Ref(vm.heap.allocate(HeapData::List(List::new(lines)))?)
+            }
+            StaticStrings::Seek => {
+                let (pos, whence) = args.get_one_two_args("seek", vm.heap)?;
+                defer_drop!(pos, vm);
+                defer_drop!(whence, vm);
+                let pos = pos.as_int(vm.heap)?;
+                let whence = match whence {
+                    Some(whence) => whence.as_int(vm.heap)?,
+                    None => 0,
+                };
+                match whence {
+                    0 => {
+                        let Ok(target) = usize::try_from(pos) else {
+                            return Err(SimpleException::new_msg(
+                                ExcType::ValueError,
+                                format!("Negative seek position {pos}"),
+                            )
+                            .into());
+                        };
+                        self.seek_to(target);
+                    }
+                    1 | 2 if pos != 0 => {
+                        let kind = if whence == 1 { "cur" } else { "end" };
+                        return Err(SimpleException::new_msg(
+                            ExcType::OSError,
+                            format!("Can't do nonzero {kind}-relative seeks"),
+                        )
+                        .into());
+                    }
+                    1 => {}
+                    2 => {
+                        self.pos = self.buffer.len();
+                        self.past_end = 0;
+                    }
+                    _ => {
+                        return Err(SimpleException::new_msg(
+                            ExcType::ValueError,
+                            format!("Invalid whence ({whence}, should be 0, 1 or 2)"),
+                        )
+                        .into());
+                    }
+                }
+                Value::Int(i64::try_from(self.tell()).expect("position fits in i64"))
+            }
+            StaticStrings::DunderEnter => {
+                args.check_zero_args("__enter__", vm.heap)?;
+                vm.heap.inc_ref(self_id);
+                Value::Ref(self_id)
+            }
+            StaticStrings::Tell => {
+                args.check_zero_args("tell", vm.heap)?;
+                Value::Int(i64::try_from(self.tell()).expect("position fits in i64"))
+            }
+            StaticStrings::Truncate => {
+                let size = args.get_zero_one_arg("truncate", vm.heap)?;
+                defer_drop!(size, vm);
+                let size = match size {
+                    None | Some(Value::None) => self.tell(),
+                    Some(size) => {
+                        let size = size.as_int(vm.heap)?;
+                        usize::try_from(size).map_err(|_| -> RunError {
+                            SimpleException::new_msg(ExcType::ValueError, format!("Negative size value {size}")).into()
+                        })?
+                    }
+                };
+                // Truncating never moves the position, which may end up past the end.
+                let position = self.tell();
+                self.buffer.truncate(advance_chars(&self.buffer, 0, size));
+                self.seek_to(position);
+                Value::Int(i64::try_from(size).expect("size fits in i64"))
+            }
+            _ => {
+                args.drop_with_heap(vm);
+                return Err(ExcType::attribute_error(Type::StringIO, attr.as_str(vm.interns)));
+            }
+        };
+        Ok(CallResult::Value(result))
+    }
+}
@@ -0,0 +1,655 @@
+//! Python `struct.Struct` type implementation.
+//!
+//! A `Struct` is a compiled format string describing a fixed-size binary layout. The
+//! `struct` module functions compile their format argument and delegate to it.
+//!
+//! A format may start with a byte-order character:
+//! - `@` (the default): native byte order, sizes and alignment
+//! - `=`: native byte order with standard sizes and no alignment
+//! - `<`, `>` and `!`: little-endian, big-endian and network (big-endian) byte order
+//!   with standard sizes and no alignment
+//!
+//! "Native" always means a 64-bit little-endian platform, so results match CPython on
+//! x86-64 and AArch64 whatever the host is. `pack_into()` is not supported as there are
+//! no writable buffers.
+
+use std::fmt::Write;
+
+use ahash::AHashSet;
+use num_bigint::BigInt;
+use num_traits::ToPrimitive;
+
+use crate::{
+    args::ArgValues,
+    bytecode::{CallResult, VM},
+    defer_drop,
+    exception_private::{ExcType, RunError, RunResult, SimpleException},
+    heap::{DropWithHeap, Heap, HeapData, HeapId},
+    intern::{Interns, StaticStrings},
+    resource::{ResourceError, ResourceTracker, check_estimated_size},
+    types::{
+        Bytes, List, LongInt, MontyIter, PyTrait, Type, allocate_tuple,
+        str::{allocate_string, string_repr_fmt},
+    },
+    value::{EitherStr, Value},
+};
+
+/// A compiled `struct.Struct` format.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub(crate) struct Struct {
+    /// The format string, exposed as the `format` attribute.
+    format: String,
+    /// Whether values are stored least significant byte first.
+    little_endian: bool,
+    /// The fields of the format, excluding pad bytes.
+    codes: Vec<FormatCode>,
+    /// Size in bytes of the packed data, exposed as the `size` attribute.
+    size: usize,
+    /// Number of values the format packs and unpacks.
+    len: usize,
+}
+
+/// A run of identical fields within a format.
+#[derive(Debug, Clone, Copy, serde::Serialize, serde::Deserialize)]
+struct FormatCode {
+    /// The format character, e.g. `b'i'`.
+    code: u8,
+    /// Byte offset of the first field.
+    offset: usize,
+    /// Size in bytes of each field; for `s` and `p` this is the repeat count.
+    size: usize,
+    /// Number of consecutive fields; always 1 for `s` and `p`.
+    count: usize,
+}
+
+impl Struct {
+    /// `Struct(format)` constructor.
+    pub fn init(vm: &mut VM<'_, '_, impl ResourceTracker>, args: ArgValues) -> RunResult<Value> {
+        let [format] = args.bind_params("Struct", ["format"], 1, vm.heap, vm.interns)?;
+        let format = format.expect("required parameter is always bound");
+        defer_drop!(format, vm);
+        let compiled = Self::compile(format, vm.heap, vm.interns)?;
+        Ok(Value::Ref(vm.heap.allocate(HeapData::Struct(compiled))?))
+    }
+
+    /// Compiles a `str` or `bytes` format string.
+    pub(crate) fn compile(format: &Value, heap: &Heap<impl ResourceTracker>, interns: &Interns) -> RunResult<Self> {
+        let format = if let Some(s) = format.as_either_str(heap) {
+            s.as_str(interns).to_owned()
+        } else if let Some(b) = bytes_of(format, heap, interns) {
+            String::from_utf8_lossy(b).into_owned()
+        } else {
+            return Err(ExcType::type_error(format!(
+                "Struct() argument 1 must be a str or bytes object, not {}",
+                format.py_type(heap)
+            )));
+        };
+        Self::parse(format)
+    }
+
+    /// Parses a format string, computing the offset of every field.
+    fn parse(format: String) -> RunResult<Self> {
+        let bytes = format.as_bytes();
+        let (native, little_endian, mut pos) = match bytes.first() {
+            Some(b'@') => (true, true, 1),
+            Some(b'=' | b'<') => (false, true, 1),
+            Some(b'>' | b'!') => (false, false, 1),
+            _ => (true, true, 0),
+        };
+        let mut codes = Vec::new();
+        let mut size: usize = 0;
+        let mut len: usize = 0;
+        while pos < bytes.len() {
+            if is_space(bytes[pos]) {
+                pos += 1;
+                continue;
+            }
+            let count = if bytes[pos].is_ascii_digit() {
+                let mut count: usize = 0;
+                while let Some(&digit) = bytes.get(pos)
+                    && digit.is_ascii_digit()
+                {
+                    count = count
+                        .checked_mul(10)
+                        .and_then(|n| n.checked_add(usize::from(digit - b'0')))
+                        .ok_or_else(size_too_long)?;
+                    pos += 1;
+                }
+                if pos == bytes.len() {
+                    return Err(ExcType::struct_error("repeat count given without format specifier"));
+                }
+                count
+            } else {
+                1
+            };
+            let code = bytes[pos];
+            pos += 1;
+            let item_size =
+                item_size(code, native).ok_or_else(|| ExcType::struct_error("bad char in struct format"))?;
+            if native {
+                // Native fields are aligned to their own size
+                size = size.checked_next_multiple_of(item_size).ok_or_else(size_too_long)?;
+            }
+            let field = FormatCode {
+                code,
+                offset: size,
+                size: item_size,
+                count,
+            };
+            match code {
+                b'x' => {}
+                b's' | b'p' => {
+                    codes.push(FormatCode {
+                        size: count,
+                        count: 1,
+                        ..field
+                    });
+                    len += 1;
+                }
+                _ => {
+                    codes.push(field);
+                    len += count;
+                }
+            }
+            size = count
+                .checked_mul(item_size)
+                .and_then(|n| n.checked_add(size))
+                .filter(|n| *n <= isize::MAX.unsigned_abs())
+                .ok_or_else(size_too_long)?;
+        }
+        Ok(Self {
+            format,
+            little_endian,
+            codes,
+            size,
+            len,
+        })
+    }
+
+    /// Size in bytes of the packed data.
+    pub(crate) fn size(&self) -> usize {
+        self.size
+    }
+
+    /// Packs `values` according to the format, returning a `bytes` object.
+    pub(crate) fn pack(&self, values: &[Value], vm: &mut VM<'_, '_, impl ResourceTracker>) -> RunResult<Value> {
+        if values.len() != self.len {
+            return Err(ExcType::struct_error(format!(
+                "pack expected {} items for packing (got {})",
+                self.len,
+                values.len()
+            )));
+        }
+        check_estimated_size(self.size, vm.heap.tracker())?;
+        let mut packed = vec![0; self.size];
+        let mut values = values.iter();
+        for code in &self.codes {
+            for i in 0..code.count {
+                let start = code.offset + i * code.size;
+                let value = values.next().expect("value count checked above");
+                self.pack_field(code.code, value, &mut packed[start..start + code.size], vm)?;
+            }
+        }
+        Ok(Value::Ref(vm.heap.allocate(HeapData::Bytes(Bytes::new(packed)))?))
+    }
+
+    /// Unpacks a buffer of exactly `size` bytes into a tuple.
+    pub(crate) fn unpack(&self, buffer: &Value, vm: &mut VM<'_, '_, impl ResourceTracker>) -> RunResult<Value> {
+        let data = buffer_arg(buffer, vm.heap, vm.interns)?;
+        if data.len() != self.size {
+            return Err(ExcType::struct_error(format!(
+                "unpack requires a buffer of {} bytes",
+                self.size
+            )));
+        }
+        self.unpack_tuple(&data, vm.heap)
+    }
+
+    /// Unpacks `size` bytes starting at `offset`, which may be negative to count from the end.
+    pub(crate) fn unpack_from(
+        &self,
+        buffer: &Value,
+        offset: Option<&Value>,
+        vm: &mut VM<'_, '_, impl ResourceTracker>,
+    ) -> RunResult<Value> {
+        let data = buffer_arg(buffer, vm.heap, vm.interns)?;
+        let offset = match offset {
+            Some(offset) => offset.as_int(vm.heap)?,
+            None => 0,
+        };
+        let size = i64::try_from(self.size).expect("struct size is at most isize::MAX");
+        let buffer_len = i64::try_from(data.len()).expect("buffer length fits in i64");
+        let mut start = offset;
+        if offset < 0 {
+            if offset + size > 0 {
+                return Err(ExcType::struct_error(format!(
+                    "not enough data to unpack {size} bytes at offset {offset}"
+                )));
+            }
+            if offset + buffer_len < 0 {
+                return Err(ExcType::struct_error(format!(
+                    "offset {offset} out of range for {buffer_len}-byte buffer"
+                )));
+            }
+            start += buffer_len;
+        }
+        if buffer_len - start < size {
+            return Err(ExcType::struct_error(format!(
+                "unpack_from requires a buffer of at least {} bytes for unpacking {size} bytes at offset {start} \
+                 (actual buffer size is {buffer_len})",
+                i128::from(size) + i128::from(start)
+            )));
+        }
+        let start = usize::try_from(start).expect("offset checked to be non-negative");
+        self.unpack_tuple(&data[start..start + self.size], vm.heap)
+    }
+
+    /// Unpacks consecutive `size`-byte chunks of a buffer, returning an iterator of tuples.
+    ///
+    /// The whole buffer is unpacked up front, so errors are raised by this call rather
+    /// than during iteration.
+    pub(crate) fn iter_unpack(&self, buffer: &Value, vm: &mut VM<'_, '_, impl ResourceTracker>) -> RunResult<Value> {
+        let data = buffer_arg(buffer, vm.heap, vm.interns)?;
+        if self.size == 0 {
+            return Err(ExcType::struct_error(
+                "cannot iteratively unpack with a struct of length 0",
+            ));
+        }
+        if data.len() % self.size != 0 {
+            return Err(ExcType::struct_error(format!(
+                "iterative unpacking requires a buffer of a multiple of {} bytes",
+                self.size
+            )));
+        }
+        let mut tuples = Vec::with_capacity(data.len() / self.size);
+        for chunk in data.chunks_exact(self.size) {
+            match self.unpack_tuple(chunk, vm.heap) {
+                Ok(tuple) => tuples.push(tuple),
+                Err(err) => {
+                    tuples.drop_with_heap(vm);
+                    return Err(err);
+                }
+            }
+        }
+        let list = Value::Ref(vm.heap.allocate(HeapData::List(List::new(tuples)))?);
+        let iter = MontyIter::new(list, vm)?;
+        Ok(Value::Ref(vm.heap.allocate(HeapData::Iter(iter))?))
+    }
+
+    /// Packs a single value into `field`, whose length is the field size.
+    fn pack_field(
+        &self,
+        code: u8,
+        value: &Value,
+        field: &mut [u8],
+        vm: &VM<'_, '_, impl ResourceTracker>,
+    ) -> RunResult<()> {
+        match code {
+            b'c' => match bytes_of(value, vm.heap, vm.interns) {
+                Some(&[byte]) => field[0] = byte,
+                _ => return Err(ExcType::struct_error("char format requires a bytes object of length 1")),
+            },
+            b's' | b'p' => {
+                let Some(data) = bytes_of(value, vm.heap, vm.interns) else {
+                    return Err(ExcType::struct_error(format!(
+                        "argument for '{}' must be a bytes object",
+                        char::from(code)
+                    )));
+                };
+                if code == b's' {
+                    let n = data.len().min(field.len());
+                    field[..n].copy_from_slice(&data[..n]);
+                } else if let Some((length, rest)) = field.split_first_mut() {
+                    // Pascal string: a length byte, then at most 255 bytes of data
+                    let n = data.len().min(rest.len());
+                    rest[..n].copy_from_slice(&data[..n]);
+                    *length = u8::try_from(n).unwrap_or(u8::MAX);
+                }
+            }
+            b'?' => field[0] = u8::from(value.py_bool(vm)),
+            b'e' => {
+                let half = pack_half(float_arg(value, vm.heap)?)?;
+                self.store(field, &half.to_le_bytes());
+            }
+            b'f' => {
+                let x = float_arg(value, vm.heap)?;
+                #[expect(clippy::cast_possible_truncation, reason = "rounding to f32 is intended")]
+                let single = x as f32;
+                if single.is_infinite() && !x.is_infinite() {
+                    return Err(SimpleException::new_msg(
+                        ExcType::OverflowError,
+                        "float too large to pack with f format",
+                    )
+                    .into());
+                }
+                self.store(field, &single.to_le_bytes());
+            }
+            b'd' => self.store(field, &float_arg(value, vm.heap)?.to_le_bytes()),
+            _ => {
+                let Some(n) = int_arg(value, vm.heap)? else {
+                    return Err(int_range_error(code, field.len()));
+                };
+                let (min, max) = int_range(code, field.len());
+                if !(min..=max).contains(&n) {
+                    return Err(int_range_error(code, field.len()));
+                }
+                self.store(field, &n.to_le_bytes());
+            }
+        }
+        Ok(())
+    }
+
+    /// Unpacks `data`, which is exactly `size` bytes long, into a tuple.
+    fn unpack_tuple(&self, data: &[u8], heap: &mut Heap<impl ResourceTracker>) -> RunResult<Value> {
+        let mut values = Vec::with_capacity(self.len);
+        for code in &self.codes {
+            for i in 0..code.count {
+                let start = code.offset + i * code.size;
+                match self.unpack_field(code.code, &data[start..start + code.size], heap) {
+                    Ok(value) => values.push(value),
+                    Err(err) => {
+                        values.drop_with_heap(heap);
+                        return Err(err);
+                    }
+                }
+            }
+        }
+        Ok(allocate_tuple(values.into(), heap)?)
+    }
+
+    /// Unpacks a single field.
+    fn unpack_field(&self, code: u8, field: &[u8], heap: &mut Heap<impl ResourceTracker>) -> RunResult<Value> {
+        let value = match code {
+            b'c' | b's' => Value::Ref(heap.allocate(HeapData::Bytes(Bytes::new(field.to_vec())))?),
+            b'p' => {
+                let data = match field.split_first() {
+                    Some((&length, rest)) => &rest[..usize::from(length).min(rest.len())],
+                    None => &[],
+                };
+                Value::Ref(heap.allocate(HeapData::Bytes(Bytes::new(data.to_vec())))?)
+            }
+            b'?' => Value::Bool(field[0] != 0),
+            b'e' => Value::Float(unpack_half(u16::try_from(self.load(field)).expect("2-byte field"))),
+            b'f' => Value::Float(f64::from(f32::from_bits(
+                u32::try_from(self.load(field)).expect("4-byte field"),
+            ))),
+            b'd' => Value::Float(f64::from_bits(self.load(field))),
+            b'b' | b'h' | b'i' | b'l' | b'q' | b'n' => {
+                // Sign-extend from the field width
+                let shift = 64 - 8 * field.len();
+                Value::Int((self.load(field) << shift).cast_signed() >> shift)
+            }
+            _ => {
+                let n = self.load(field);
+                match i64::try_from(n) {
+                    Ok(n) => Value::Int(n),
+                    Err(_) => LongInt::new(BigInt::from(n)).into_value(heap)?,
+                }
+            }
+        };
+        Ok(value)
+    }
+
+    /// Writes the first `field.len()` bytes of a little-endian encoding in this format's byte order.
+    fn store(&self, field: &mut [u8], le_bytes: &[u8]) {
+        field.copy_from_slice(&le_bytes[..field.len()]);
+        if !self.little_endian {
+            field.reverse();
+        }
+    }
+
+    /// Reads a field of at most 8 bytes in this format's byte order.
+    fn load(&self, field: &[u8]) -> u64 {
+        let mut buf = [0; 8];
+        buf[..field.len()].copy_from_slice(field);
+        if !self.little_endian {
+            buf[..field.len()].reverse();
+        }
+        u64::from_le_bytes(buf)
+    }
+}
+
+/// Size in bytes of a format character, or `None` if it is not valid in this mode.
+///
+/// `n`, `N` and `P` only exist in native mode, where `l` and `L` are 8 bytes wide.
+fn item_size(code: u8, native: bool) -> Option<usize> {
+    match code {
+        b'x' | b'c' | b'b' | b'B' | b'?' | b's' | b'p' => Some(1),
+        b'h' | b'H' | b'e' => Some(2),
+        b'i' | b'I' | b'f' => Some(4),
+        b'l' | b'L' => Some(if native { 8 } else { 4 }),
+        b'q' | b'Q' | b'd' => Some(8),
+        b'n' | b'N' | b'P' if native => Some(8),
+        _ => None,
+    }
+}
+
+/// Whether `byte` is whitespace, which is ignored between format characters.
+fn is_space(byte: u8) -> bool {
+    matches!(byte, b' ' | b'\t' | b'\n' | b'\x0b' | b'\x0c' | b'\r')
+}
+
+/// Inclusive range of values an integer format character accepts.
+fn int_range(code: u8, size: usize) -> (i128, i128) {
+    let bits = 8 * size;
+    match code {
+        b'b' | b'h' | b'i' | b'l' | b'q' | b'n' => (-(1 << (bits - 1)), (1 << (bits - 1)) - 1),
+        // Pointers accept negative values, which are stored in two's complement
+        b'P' => (-(1 << 63), (1 << 64) - 1),
+        _ => (0, (1 << bits) - 1),
+    }
+}
+
+/// Builds the `struct.error` for an integer outside the range of its format character.
+fn int_range_error(code: u8, size: usize) -> RunError {
+    if code == b'P' {
+        return ExcType::struct_error("int too large to convert");
+    }
+    let (min, max) = int_range(code, size);
+    ExcType::struct_error(format!(
+        "'{}' format requires {min} <= number <= {max}",
+        char::from(code)
+    ))
+}
+
+/// The `struct.error` for a format whose size overflows.
+fn size_too_long() -> RunError {
+    ExcType::struct_error("total struct size too long")
+}
+
+/// Extracts an integer to pack, or `None` if it is too large for any format.
+///
+/// Bools are integers; floats and other types are rejected.
+fn int_arg(value: &Value, heap: &Heap<impl ResourceTracker>) -> RunResult<Option<i128>> {
+    match value {
+        Value::Int(i) => Ok(Some(i128::from(*i))),
+        Value::Bool(b) => Ok(Some(i128::from(*b))),
+        Value::Ref(id) => match heap.get(*id) {
+            HeapData::LongInt(li) => Ok(li.inner().to_i128()),
+            _ => Err(ExcType::struct_error("required argument is not an integer")),
+        },
+        _ => Err(ExcType::struct_error("required argument is not an integer")),
+    }
+}
+
+/// Extracts a float to pack; integers are converted.
+fn float_arg(value: &Value, heap: &Heap<impl ResourceTracker>) -> RunResult<f64> {
+    let x = match value {
+        Value::Float(f) => Some(*f),
+        Value::Int(i) => Some(*i as f64),
+        Value::Bool(b) => Some(f64::from(u8::from(*b))),
+        Value::Ref(id) => match heap.get(*id) {
+            HeapData::LongInt(li) => li.to_f64().filter(|f| f.is_finite()),
+            _ => None,
+        },
+        _ => None,
+    };
+    x.ok_or_else(|| ExcType::struct_error("required argument is not a float"))
+}
+
+/// Returns the contents of a `bytes` value.
+fn bytes_of<'a>(value: &Value, heap: &'a Heap<impl ResourceTracker>, interns: &'a Interns) -> Option<&'a [u8]> {
+    match value {
+        Value::InternBytes(id) => Some(interns.get_bytes(*id)),
+        Value::Ref(id) => match heap.get(*id) {
+            HeapData::Bytes(b) => Some(b.as_slice()),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+/// Copies the contents of a buffer argument to unpack.
+///
+/// The data is copied so the heap is free to allocate the unpacked values.
+fn buffer_arg(value: &Value, heap: &Heap<impl ResourceTracker>, interns: &Interns) -> RunResult<Vec<u8>> {
+    bytes_of(value, heap, interns).map(<[u8]>::to_vec).ok_or_else(|| {
+        ExcType::type_error(format!(
+            "a bytes-like object is required, not '{}'",
+            value.py_type(heap)
+        ))
+    })
+}
+
+/// Converts a float to IEEE 754 half precision, rounding half to even.
+///
+/// Raises `OverflowError` for finite values too large to represent; infinities and NaNs
+/// are preserved.
+fn pack_half(x: f64) -> RunResult<u16> {
+    let sign = u16::from(x.is_sign_negative()) << 15;
+    if x.is_nan() {
+        return Ok(sign | 0x7e00);
+    }
+    if x.is_infinite() {
+        return Ok(sign | 0x7c00);
+    }
+    let bits = x.abs().to_bits();
+    let exponent = i64::try_from(bits >> 52).expect("11-bit exponent") - 1023;
+    let mantissa = (bits & ((1 << 52) - 1)) | (1 << 52);
+    // Keep 11 significant bits for normal halves; subnormal halves count units of 2**-24.
+    // Rounding may carry into the exponent, which the addition below handles naturally.
+    let half = if exponent < -14 {
+        round_shift(mantissa, 28 - exponent)
+    } else {
+        round_shift(mantissa, 42) + (u64::try_from(exponent + 14).expect("exponent is at least -14") << 10)
+    };
+    if half >= 0x7c00 {
+        return Err(SimpleException::new_msg(ExcType::OverflowError, "float too large to pack with e format").into());
+    }
+    Ok(sign | u16::try_from(half).expect("checked above"))
+}
+
+/// Shifts `n` right by `shift` bits, rounding half to even.
+fn round_shift(n: u64, shift: i64) -> u64 {
+    let Ok(shift @ 1..64) = u32::try_from(shift) else {
+        // The mantissa has 53 bits, so larger shifts always round to zero
+        return 0;
+    };
+    let quotient = n >> shift;
+    let remainder = n & ((1 << shift) - 1);
+    let half = 1 << (shift - 1);
+    if remainder > half || (remainder == half && quotient & 1 == 1) {
+        quotient + 1
+    } else {
+        quotient
+    }
+}
+
+/// Converts an IEEE 754 half precision value to a float.
+fn unpack_half(half: u16) -> f64 {
+    let exponent = i32::from((half >> 10) & 0x1f);
+    let fraction = f64::from(half & 0x3ff);
+    let magnitude = match exponent {
+        0 => fraction * 2f64.powi(-24),
+        0x1f if fraction == 0.0 => f64::INFINITY,
+        0x1f => f64::NAN,
+        _ => (fraction + 1024.0) * 2f64.powi(exponent - 25),
+    };
+    if half & 0x8000 == 0 { magnitude } else { -magnitude }
+}
+
+impl PyTrait for Struct {
+    fn py_type(&self, _heap: &Heap<impl ResourceTracker>) -> Type {
+        Type::Struct
+    }
+
+    fn py_len(&self, _vm: &VM<'_, '_, impl ResourceTracker>) -> Option<usize> {
+        None
+    }
+
+    fn py_eq(&self, _other: &Self, _vm: &mut VM<'_, '_, impl ResourceTracker>) -> Result<bool, ResourceError> {
+        // Structs compare by identity (handled at the Value level)
+        Ok(false)
+    }
+
+    fn py_dec_ref_ids(&mut self, _stack: &mut Vec<HeapId>) {
+        // No heap references — the format is owned.
+    }
+
+    fn py_bool(&self, _vm: &VM<'_, '_, impl ResourceTracker>) -> bool {
+        true
+    }
+
+    fn py_repr_fmt(
+        &self,
+        f: &mut impl Write,
+        _vm: &VM<'_, '_, impl ResourceTracker>,
+        _heap_ids: &mut AHashSet<HeapId>,
+    ) -> std::fmt::Result {
+        f.write_str("Struct(")?;
+        string_repr_fmt(&self.format, f)?;
+        f.write_char(')')
+    }
+
+    fn py_estimate_size(&self) -> usize {
+        std::mem::size_of::<Self>() + self.format.len() + self.codes.len() * std::mem::size_of::<FormatCode>()
+    }
+
+    fn py_getattr(&self, attr: &EitherStr, vm: &mut VM<'_, '_, impl ResourceTracker>) -> RunResult<Option<CallResult>> {
+        let value = match attr.static_string() {
+            Some(StaticStrings::Format) => allocate_string(self.format.clone(), vm.heap)?,
+            Some(StaticStrings::Size) => {
+                Value::Int(i64::try_from(self.size).expect("struct size is at most isize::MAX"))
+            }
+            _ => return Err(ExcType::attribute_error(Type::Struct, attr.as_str(vm.interns))),
+        };
+        Ok(Some(CallResult::Value(value)))
+    }
+
+    fn py_call_attr(
+        &mut self,
+        _self_id: HeapId,
+        vm: &mut VM<'_, '_, impl ResourceTracker>,
+        attr: &EitherStr,
+        args: ArgValues,
+    ) -> RunResult<CallResult> {
+        let result = match attr.static_string() {
+            Some(StaticStrings::Pack) => {
+                let values: Vec<Value> = args.into_pos_only("pack", vm.heap)?.collect();
+                defer_drop!(values, vm);
+                self.pack(values, vm)
+            }
+            Some(StaticStrings::Unpack) => {
+                let buffer = args.get_one_arg("unpack", vm.heap)?;
+                defer_drop!(buffer, vm);
+                self.unpack(buffer, vm)
+            }
+            Some(StaticStrings::UnpackFrom) => {
+                let params = args.bind_params("unpack_from", ["buffer", "offset"], 1, vm.heap, vm.interns)?;
+                defer_drop!(params, vm);
+                let [buffer, offset] = params;
+                self.unpack_from(buffer.as_ref().expect("required argument"), offset.as_ref(), vm)
+            }
+            Some(StaticStrings::IterUnpack) => {
+                let buffer = args.get_one_arg("iter_unpack", vm.heap)?;
+                defer_drop!(buffer, vm);
+                self.iter_unpack(buffer, vm)
+            }
+            _ => {
+                args.drop_with_heap(vm);
+                return Err(ExcType::attribute_error(Type::Struct, attr.as_str(vm.interns)));
+            }
+        }?;
+        Ok(CallResult::Value(result))
+    }
+}
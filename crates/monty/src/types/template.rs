@@ -0,0 +1,290 @@
+//! Python `string.Template` type implementation.
+//!
+//! Supports `$`-based substitution as described in PEP 292:
+//! - `$$` is an escape and is replaced with a single `$`
+//! - `$identifier` names a placeholder matching a mapping key
+//! - `${identifier}` is equivalent, for use when followed by identifier characters
+//!
+//! Identifiers are ASCII only (`[_a-zA-Z][_a-zA-Z0-9]*`), matching CPython's default
+//! `idpattern`. Subclassing to customise the delimiter or pattern is not supported.
+
+use std::fmt::Write;
+
+use ahash::AHashSet;
+
+use crate::{
+    args::{ArgValues, KwargsValues},
+    bytecode::{CallResult, VM},
+    defer_drop,
+    exception_private::{ExcType, RunError, RunResult, SimpleException},
+    heap::{DropWithHeap, Heap, HeapData, HeapId},
+    intern::StaticStrings,
+    resource::{ResourceError, ResourceTracker, check_estimated_size},
+    types::{PyTrait, Str, Type, re_pattern::value_to_str},
+    value::{EitherStr, Value},
+};
+
+/// A `string.Template` object holding the template string.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub(crate) struct Template {
+    /// The template string, exposed as the `template` attribute.
+    template: String,
+}
+
+/// A placeholder found after a `$` delimiter.
+enum Placeholder<'a> {
+    /// `$$` — a literal `$`.
+    Escaped,
+    /// `$name` or `${name}`.
+    Named(&'a str),
+    /// A `$` not followed by a valid placeholder.
+    Invalid,
+}
+
+impl Template {
+    /// `Template(template)` constructor.
+    pub fn init(vm: &mut VM<'_, '_, impl ResourceTracker>, args: ArgValues) -> RunResult<Value> {
+        let [template] = args.bind_params("Template", ["template"], 1, vm.heap, vm.interns)?;
+        let template = template.expect("required parameter is always bound");
+        defer_drop!(template, vm);
+        let template = Self {
+            template: value_to_str(template, vm.heap, vm.interns)?.into_owned(),
+        };
+        Ok(Value::Ref(vm.heap.allocate(HeapData::Template(template))?))
+    }
+
+    /// Implements `substitute()` and `safe_substitute()`.
+    ///
+    /// Placeholder values come from keyword arguments first, then from the optional
+    /// positional mapping, and are converted with `str()`. With `safe` set, missing
+    /// keys and invalid placeholders are left in the result unchanged instead of
+    /// raising `KeyError` / `ValueError`.
+    fn substitute(
+        &self,
+        args: ArgValues,
+        method: &str,
+        safe: bool,
+        vm: &mut VM<'_, '_, impl ResourceTracker>,
+    ) -> RunResult<Value> {
+        let (mut pos_args, kwargs) = args.into_parts();
+        if pos_args.len() > 1 {
+            let given = pos_args.len() + 1;
+            pos_args.drop_with_heap(vm);
+            kwargs.drop_with_heap(vm);
+            return Err(ExcType::type_error(format!(
+                "Template.{method}() takes from 1 to 2 positional arguments but {given} were given"
+            )));
+        }
+        let mapping = pos_args.next();
+        defer_drop!(mapping, vm);
+        let (names, values) = collect_kwargs(kwargs, vm)?;
+        defer_drop!(values, vm);
+
+        let t = self.template.as_str();
+        let mut result = String::with_capacity(t.len());
+        let mut pos = 0;
+        while let Some(offset) = t[pos..].find('$') {
+            let start = pos + offset;
+            result.push_str(&t[pos..start]);
+            let (placeholder, end) = parse_placeholder(t, start + 1);
+            match placeholder {
+                Placeholder::Escaped => result.push('$'),
+                Placeholder::Named(name) => match lookup(name, &names, values, mapping.as_ref(), vm)? {
+                    Some(value) => result.push_str(&value),
+                    None if safe => result.push_str(&t[start..end]),
+                    None => return Err(SimpleException::new_msg(ExcType::KeyError, name).into()),
+                },
+                Placeholder::Invalid if safe => result.push('$'),
+                Placeholder::Invalid => return Err(invalid_placeholder_error(t, start)),
+            }
+            pos = end;
+            check_estimated_size(result.len() + (t.len() - pos), vm.heap.tracker())?;
+        }
+        result.push_str(&t[pos..]);
+        Ok(Value::Ref(vm.heap.allocate(HeapData::Str(Str::new(result)))?))
+    }
+}
+
+/// Parses the placeholder following a `$` at byte offset `after`.
+///
+/// Returns the placeholder and the byte offset just past it. Invalid placeholders
+/// consume only the `$` itself.
+fn parse_placeholder(t: &str, after: usize) -> (Placeholder<'_>, usize) {
+    let bytes = t.as_bytes();
+    if bytes.get(after) == Some(&b'$') {
+        return (Placeholder::Escaped, after + 1);
+    }
+    let len = identifier_len(&bytes[after..]);
+    if len > 0 {
+        return (Placeholder::Named(&t[after..after + len]), after + len);
+    }
+    if bytes.get(after) == Some(&b'{') {
+        let len = identifier_len(&bytes[after + 1..]);
+        if len > 0 && bytes.get(after + 1 + len) == Some(&b'}') {
+            return (Placeholder::Named(&t[after + 1..after + 1 + len]), after + len + 2);
+        }
+    }
+    (Placeholder::Invalid, after)
+}
+
+/// Length of the ASCII identifier at the start of `bytes`, or 0 if there is none.
+fn identifier_len(bytes: &[u8]) -> usize {
+    match bytes.first() {
+        Some(b) if b.is_ascii_alphabetic() || *b == b'_' => {
+            1 + bytes[1..]
+                .iter()
+                .take_while(|b| b.is_ascii_alphanumeric() || **b == b'_')
+                .count()
+        }
+        _ => 0,
+    }
+}
+
+/// Splits keyword arguments into parallel name and value lists for placeholder lookup.
+fn collect_kwargs(
+    kwargs: KwargsValues,
+    vm: &mut VM<'_, '_, impl ResourceTracker>,
+) -> RunResult<(Vec<String>, Vec<Value>)> {
+    let mut names = Vec::with_capacity(kwargs.len());
+    let mut values = Vec::with_capacity(kwargs.len());
+    let mut kwargs = kwargs.into_iter();
+    while let Some((key, value)) = kwargs.next() {
+        let Some(name) = key.as_either_str(vm.heap) else {
+            key.drop_with_heap(vm);
+            value.drop_with_heap(vm);
+            kwargs.drop_with_heap(vm);
+            values.drop_with_heap(vm);
+            return Err(ExcType::type_error("keywords must be strings"));
+        };
+        names.push(name.as_str(vm.interns).to_owned());
+        key.drop_with_heap(vm);
+        values.push(value);
+    }
+    Ok((names, values))
+}
+
+/// Looks up a placeholder value, returning its `str()` or `None` if the key is missing.
+///
+/// Keyword arguments take precedence over the mapping. Dict mappings are searched
+/// directly; any other object is subscripted, so its errors propagate unchanged.
+fn lookup(
+    name: &str,
+    kwarg_names: &[String],
+    kwarg_values: &[Value],
+    mapping: Option<&Value>,
+    vm: &mut VM<'_, '_, impl ResourceTracker>,
+) -> RunResult<Option<String>> {
+    if let Some(i) = kwarg_names.iter().position(|key| key == name) {
+        return Ok(Some(kwarg_values[i].py_str(vm).into_owned()));
+    }
+    let Some(mapping) = mapping else {
+        return Ok(None);
+    };
+    if let Value::Ref(id) = mapping
+        && let HeapData::Dict(dict) = vm.heap.get(*id)
+    {
+        return Ok(dict
+            .get_by_str(name, vm.heap, vm.interns)
+            .map(|value| value.py_str(vm).into_owned()));
+    }
+    let key = Value::Ref(vm.heap.allocate(HeapData::Str(Str::new(name.to_owned())))?);
+    defer_drop!(key, vm);
+    let value = mapping.py_getitem(key, vm)?;
+    defer_drop!(value, vm);
+    Ok(Some(value.py_str(vm).into_owned()))
+}
+
+/// Builds CPython's `ValueError` for an invalid placeholder whose `$` is at byte offset `start`.
+///
+/// Lines are split like `str.splitlines()`, and the column counts characters from the
+/// start of the line up to and including the `$`.
+fn invalid_placeholder_error(t: &str, start: usize) -> RunError {
+    let mut line = 1;
+    let mut col = 1;
+    let mut chars = t[..start].chars().peekable();
+    while let Some(c) = chars.next() {
+        if is_line_break(c) {
+            if c == '\r' && chars.peek() == Some(&'\n') {
+                chars.next();
+            }
+            line += 1;
+            col = 1;
+        } else {
+            col += 1;
+        }
+    }
+    SimpleException::new_msg(
+        ExcType::ValueError,
+        format!("Invalid placeholder in string: line {line}, col {col}"),
+    )
+    .into()
+}
+
+/// Whether `c` is a line boundary for `str.splitlines()`.
+pub(crate) fn is_line_break(c: char) -> bool {
+    matches!(
+        c,
+        '\n' | '\r' | '\x0b' | '\x0c' | '\x1c' | '\x1d' | '\x1e' | '\u{85}' | '\u{2028}' | '\u{2029}'
+    )
+}
+
+impl PyTrait for Template {
+    fn py_type(&self, _heap: &Heap<impl ResourceTracker>) -> Type {
+        Type::Template
+    }
+
+    fn py_len(&self, _vm: &VM<'_, '_, impl ResourceTracker>) -> Option<usize> {
+        None
+    }
+
+    fn py_eq(&self, _other: &Self, _vm: &mut VM<'_, '_, impl ResourceTracker>) -> Result<bool, ResourceError> {
+        // Templates compare by identity (handled at the Value level)
+        Ok(false)
+    }
+
+    fn py_dec_ref_ids(&mut self, _stack: &mut Vec<HeapId>) {
+        // No heap references — the template string is owned.
+    }
+
+    fn py_bool(&self, _vm: &VM<'_, '_, impl ResourceTracker>) -> bool {
+        true
+    }
+
+    fn py_repr_fmt(
+        &self,
+        f: &mut impl Write,
+        _vm: &VM<'_, '_, impl ResourceTracker>,
+        _heap_ids: &mut AHashSet<HeapId>,
+    ) -> std::fmt::Result {
+        f.write_str("<string.Template object>")
+    }
+
+    fn py_estimate_size(&self) -> usize {
+        std::mem::size_of::<Self>() + self.template.len()
+    }
+
+    fn py_getattr(&self, attr: &EitherStr, vm: &mut VM<'_, '_, impl ResourceTracker>) -> RunResult<Option<CallResult>> {
+        let value = match attr.static_string() {
+            Some(StaticStrings::Template) => {
+                Value::Ref(vm.heap.allocate(HeapData::Str(Str::new(self.template.clone())))?)
+            }
+            _ => return Err(ExcType::attribute_error(Type::Template, attr.as_str(vm.interns))),
+        };
+        Ok(Some(CallResult::Value(value)))
+    }
+
+    fn py_call_attr(
+        &mut self,
+        _self_id: HeapId,
+        vm: &mut VM<'_, '_, impl ResourceTracker>,
+        attr: &EitherStr,
+        args: ArgValues,
+    ) -> RunResult<CallResult> {
+        let result = match attr.static_string() {
+            Some(StaticStrings::Substitute) => self.substitute(args, "substitute", false, vm),
+            Some(StaticStrings::SafeSubstitute) => self.substitute(args, "safe_substitute", true, vm),
+            _ => return Err(ExcType::attribute_error(Type::Template, attr.as_str(vm.interns))),
+        }?;
+        Ok(CallResult::Value(result))
+    }
+}
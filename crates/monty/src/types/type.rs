@@ -4,14 +4,15 @@ use num_bigint::BigInt;
 
 use crate::{
     args::ArgValues,
-    bytecode::VM,
+    bytecode::{CallResult, VM},
     defer_drop,
     exception_private::{ExcType, RunError, RunResult, SimpleException},
     heap::{DropWithHeap, Heap, HeapData},
     intern::{StaticStrings, StringId},
     resource::ResourceTracker,
     types::{
-        Bytes, Dict, FrozenSet, List, LongInt, MontyIter, Path, PyTrait, Range, Set, Slice, Str, Tuple,
+        Bytes, CsvSniffer, CsvWriter, Dict, FrozenSet, List, LongInt, MontyIter, Object, OperatorCallable, Path,
+        PyTrait, Range, SequenceMatcher, Set, Slice, Str, StringIO, Struct, Template, Tuple, Uuid,
         bytes::bytes_fromhex, dict::dict_fromkeys, str::StringRepr,
     },
     value::Value,
@@ -54,8 +55,14 @@ pub enum Type {
     /// Coroutine type for async functions and external futures.
     Coroutine,
     Module,
-    /// Marker types like stdout/stderr - displays as "TextIOWrapper"
+    /// Text files from `open()` and marker types like stdout/stderr - displays as "_io.TextIOWrapper"
     TextIOWrapper,
+    /// A binary file opened for reading only - displays as "_io.BufferedReader"
+    BufferedReader,
+    /// A binary file opened for writing only - displays as "_io.BufferedWriter"
+    BufferedWriter,
+    /// A binary file opened for reading and writing - displays as "_io.BufferedRandom"
+    BufferedRandom,
     /// typing module special forms (Any, Optional, Union, etc.) - displays as "typing._SpecialForm"
     SpecialForm,
     /// A filesystem path from `pathlib.Path` - displays as "PosixPath"
@@ -66,6 +73,36 @@ pub enum Type {
     RePattern,
     /// A regex match result from `re.match()` / `re.search()` etc. - displays as "re.Match"
     ReMatch,
+    /// A hash object from `hashlib.sha256()` etc. - displays as "_hashlib.HASH"
+    Hash,
+    /// An HMAC object from `hmac.new()` - displays as "hmac.HMAC"
+    Hmac,
+    /// A template from `string.Template()` - displays as "string.Template"
+    Template,
+    /// A matcher from `difflib.SequenceMatcher()` - displays as "difflib.SequenceMatcher"
+    SequenceMatcher,
+    /// An in-memory text stream from `io.StringIO()` - displays as "_io.StringIO"
+    StringIO,
+    /// A dialect from `csv.get_dialect()` - displays as "_csv.Dialect"
+    CsvDialect,
+    /// A writer from `csv.writer()` - displays as "_csv.writer"
+    CsvWriter,
+    /// A writer from `csv.DictWriter()` - displays as "csv.DictWriter"
+    CsvDictWriter,
+    /// A sniffer from `csv.Sniffer()` - displays as "csv.Sniffer"
+    CsvSniffer,
+    /// A callable from `operator.itemgetter()` - displays as "operator.itemgetter"
+    ItemGetter,
+    /// A callable from `operator.attrgetter()` - displays as "operator.attrgetter"
+    AttrGetter,
+    /// A callable from `operator.methodcaller()` - displays as "operator.methodcaller"
+    MethodCaller,
+    /// A compiled format from `struct.Struct()` - displays as "_struct.Struct"
+    Struct,
+    /// A UUID from `uuid.UUID()` - displays as "uuid.UUID"
+    Uuid,
+    /// The base of every type, whose instances are used as sentinels - displays as "object"
+    Object,
 }
 
 impl fmt::Display for Type {
@@ -99,11 +136,29 @@ impl fmt::Display for Type {
             Self::Coroutine => f.write_str("coroutine"),
             Self::Module => f.write_str("module"),
             Self::TextIOWrapper => f.write_str("_io.TextIOWrapper"),
+            Self::BufferedReader => f.write_str("_io.BufferedReader"),
+            Self::BufferedWriter => f.write_str("_io.BufferedWriter"),
+            Self::BufferedRandom => f.write_str("_io.BufferedRandom"),
             Self::SpecialForm => f.write_str("typing._SpecialForm"),
             Self::Path => f.write_str("PosixPath"),
             Self::Property => f.write_str("property"),
             Self::RePattern => f.write_str("re.Pattern"),
             Self::ReMatch => f.write_str("re.Match"),
+            Self::Hash => f.write_str("_hashlib.HASH"),
+            Self::Hmac => f.write_str("hmac.HMAC"),
+            Self::Template => f.write_str("string.Template"),
+            Self::SequenceMatcher => f.write_str("difflib.SequenceMatcher"),
+            Self::StringIO => f.write_str("_io.StringIO"),
+            Self::CsvDialect => f.write_str("_csv.Dialect"),
+            Self::CsvWriter => f.write_str("_csv.writer"),
+            Self::CsvDictWriter => f.write_str("csv.DictWriter"),
+            Self::CsvSniffer => f.write_str("csv.Sniffer"),
+            Self::ItemGetter => f.write_str("operator.itemgetter"),
+            Self::AttrGetter => f.write_str("operator.attrgetter"),
+            Self::MethodCaller => f.write_str("operator.methodcaller"),
+            Self::Struct => f.write_str("_struct.Struct"),
+            Self::Uuid => f.write_str("uuid.UUID"),
+            Self::Object => f.write_str("object"),
         }
     }
 }
@@ -135,6 +190,7 @@ impl Type {
             "iter" => Some(Self::Iterator),
             "type" => Some(Self::Type),
             "property" => Some(Self::Property),
+            "object" => Some(Self::Object),
             _ => None,
         }
     }
@@ -143,15 +199,259 @@ impl Type {
     ///
     /// This handles Python's subtype relationships:
     /// - `bool` is a subtype of `int` (so `isinstance(True, int)` returns True)
+    /// - exception types follow the exception hierarchy
+    /// - every type is a subtype of `object`
     #[must_use]
     pub fn is_instance_of(self, other: Self) -> bool {
-        if self == other {
-            true
-        } else if self == Self::Bool && other == Self::Int {
+        match (self, other) {
+            _ if self == other => true,
             // bool is a subtype of int in Python
-            true
-        } else {
-            false
+            (Self::Bool, Self::Int) | (_, Self::Object) => true,
+            (Self::Exception(exc_type), Self::Exception(other_exc_type)) => exc_type.is_subclass_of(other_exc_type),
+            _ => false,
+        }
+    }
+
+    /// Returns the methods instances of this type support, as reported by `hasattr()` and `dir()`.
+    ///
+    /// Must list exactly the method names the type's `py_call_attr` dispatches. Types whose
+    /// methods aren't listed here (including dataclasses, whose methods the host resolves)
+    /// return an empty slice.
+    #[must_use]
+    pub fn methods(self) -> &'static [StaticStrings] {
+        use StaticStrings as S;
+        match self {
+            Self::Str => &[
+                S::Capitalize,
+                S::Casefold,
+                S::Center,
+                S::Count,
+                S::Encode,
+                S::Endswith,
+                S::Find,
+                S::Index,
+                S::Isalnum,
+                S::Isalpha,
+                S::Isascii,
+                S::Isdecimal,
+                S::Isdigit,
+                S::Isidentifier,
+                S::Islower,
+                S::Isnumeric,
+                S::Isspace,
+                S::Istitle,
+                S::Isupper,
+                S::Join,
+                S::Ljust,
+                S::Lower,
+                S::Lstrip,
+                S::Partition,
+                S::Removeprefix,
+                S::Removesuffix,
+                S::Replace,
+                S::Rfind,
+                S::Rindex,
+                S::Rjust,
+                S::Rpartition,
+                S::Rsplit,
+                S::Rstrip,
+                S::Split,
+                S::Splitlines,
+                S::Startswith,
+                S::Strip,
+                S::Swapcase,
+                S::Title,
+                S::Upper,
+                S::Zfill,
+            ],
+            Self::Bytes => &[
+                S::Capitalize,
+                S::Center,
+                S::Count,
+                S::Decode,
+                S::Endswith,
+                S::Find,
+                S::Fromhex,
+                S::Hex,
+                S::Index,
+                S::Isalnum,
+                S::Isalpha,
+                S::Isascii,
+                S::Isdigit,
+                S::Islower,
+                S::Isspace,
+                S::Istitle,
+                S::Isupper,
+                S::Join,
+                S::Ljust,
+                S::Lower,
+                S::Lstrip,
+                S::Partition,
+                S::Removeprefix,
+                S::Removesuffix,
+                S::Replace,
+                S::Rfind,
+                S::Rindex,
+                S::Rjust,
+                S::Rpartition,
+                S::Rsplit,
+                S::Rstrip,
+                S::Split,
+                S::Splitlines,
+                S::Startswith,
+                S::Strip,
+                S::Swapcase,
+                S::Title,
+                S::Upper,
+                S::Zfill,
+            ],
+            Self::List => &[
+                S::Append,
+                S::Clear,
+                S::Copy,
+                S::Count,
+                S::Extend,
+                S::Index,
+                S::Insert,
+                S::Pop,
+                S::Remove,
+                S::Reverse,
+                S::Sort,
+            ],
+            Self::Tuple => &[S::Count, S::Index],
+            Self::Dict => &[
+                S::Clear,
+                S::Copy,
+                S::Fromkeys,
+                S::Get,
+                S::Items,
+                S::Keys,
+                S::Pop,
+                S::Popitem,
+                S::Setdefault,
+                S::Update,
+                S::Values,
+            ],
+            Self::Set => &[
+                S::Add,
+                S::Clear,
+                S::Copy,
+                S::Difference,
+                S::Discard,
+                S::Intersection,
+                S::Isdisjoint,
+                S::Issubset,
+                S::Issuperset,
+                S::Pop,
+                S::Remove,
+                S::SymmetricDifference,
+                S::Union,
+                S::Update,
+            ],
+            Self::FrozenSet => &[
+                S::Copy,
+                S::Difference,
+                S::Intersection,
+                S::Isdisjoint,
+                S::Issubset,
+                S::Issuperset,
+                S::SymmetricDifference,
+                S::Union,
+            ],
+            Self::DictKeys | Self::DictItems => &[S::Isdisjoint],
+            Self::Path => &[
+                S::Fspath,
+                S::Absolute,
+                S::AsPosix,
+                S::Exists,
+                S::Expanduser,
+                S::FullMatch,
+                S::Glob,
+                S::IsAbsolute,
+                S::IsDir,
+                S::IsFile,
+                S::IsRelativeTo,
+                S::IsSymlink,
+                S::Iterdir,
+                S::Joinpath,
+                S::Match,
+                S::Mkdir,
+                S::Open,
+                S::ReadBytes,
+                S::ReadText,
+                S::RelativeTo,
+                S::Rename,
+                S::Resolve,
+                S::Rglob,
+                S::Rmdir,
+                S::Samefile,
+                S::StatMethod,
+                S::Touch,
+                S::Unlink,
+                S::WithName,
+                S::WithSegments,
+                S::WithStem,
+                S::WithSuffix,
+                S::WriteBytes,
+                S::WriteText,
+            ],
+            Self::RePattern => &[
+                S::Findall,
+                S::Finditer,
+                S::Fullmatch,
+                S::Match,
+                S::Search,
+                S::Split,
+                S::Sub,
+                S::Subn,
+            ],
+            Self::ReMatch => &[S::End, S::Expand, S::Group, S::Groupdict, S::Groups, S::Span, S::Start],
+            Self::Hash | Self::Hmac => &[S::Copy, S::Digest, S::Hexdigest, S::Update],
+            Self::TextIOWrapper | Self::BufferedReader | Self::BufferedWriter | Self::BufferedRandom => &[
+                S::DunderEnter,
+                S::DunderExit,
+                S::Close,
+                S::Flush,
+                S::Read,
+                S::Readable,
+                S::Readline,
+                S::Readlines,
+                S::Seek,
+                S::Seekable,
+                S::Tell,
+                S::Writable,
+                S::Write,
+                S::Writelines,
+            ],
+            Self::StringIO => &[
+                S::DunderEnter,
+                S::DunderExit,
+                S::Close,
+                S::Getvalue,
+                S::Read,
+                S::Readline,
+                S::Readlines,
+                S::Seek,
+                S::Tell,
+                S::Truncate,
+                S::Write,
+                S::Writelines,
+            ],
+            Self::Template => &[S::SafeSubstitute, S::Substitute],
+            Self::SequenceMatcher => &[
+                S::FindLongestMatch,
+                S::GetGroupedOpcodes,
+                S::GetMatchingBlocks,
+                S::GetOpcodes,
+                S::QuickRatio,
+                S::Ratio,
+                S::RealQuickRatio,
+            ],
+            Self::CsvWriter => &[S::Writerow, S::Writerows],
+            Self::CsvDictWriter => &[S::Writeheader, S::Writerow, S::Writerows],
+            Self::CsvSniffer => &[S::HasHeader, S::Sniff],
+            Self::Struct => &[S::IterUnpack, S::Pack, S::Unpack, S::UnpackFrom],
+            _ => &[],
         }
     }
 
@@ -222,6 +522,17 @@ impl Type {
             Self::Slice => Slice::init(vm, args),
             Self::Iterator => MontyIter::init(vm, args),
             Self::Path => Path::init(vm, args),
+            Self::Template => Template::init(vm, args),
+            Self::SequenceMatcher => SequenceMatcher::init(vm, args),
+            Self::StringIO => StringIO::init(vm, args),
+            Self::CsvDictWriter => CsvWriter::init_dict(vm, args),
+            Self::CsvSniffer => CsvSniffer::init(vm, args),
+            Self::ItemGetter => OperatorCallable::init_item_getter(vm, args),
+            Self::AttrGetter => OperatorCallable::init_attr_getter(vm, args),
+            Self::MethodCaller => OperatorCallable::init_method_caller(vm, args),
+            Self::Struct => Struct::init(vm, args),
+            Self::Uuid => Uuid::init(vm, args),
+            Self::Object => Object::init(vm, args),
 
             // Primitive types - inline implementation
             Self::Int => {
@@ -313,7 +624,7 @@ fn f64_to_i64_truncate(value: f64) -> i64 {
 /// - The special values `inf`, `-inf`, `infinity`, and `nan` (case-insensitive)
 ///
 /// Underscore digit separators are not currently supported.
-fn parse_f64_from_str(value: &str) -> RunResult<f64> {
+pub(crate) fn parse_f64_from_str(value: &str) -> RunResult<f64> {
     let trimmed = value.trim();
     if trimmed.is_empty() {
         return Err(value_error_could_not_convert_string_to_float(value));
@@ -387,18 +698,21 @@ fn value_error_invalid_literal_for_int(value: &str) -> RunError {
 /// Dispatches a classmethod call on a type object.
 ///
 /// Handles classmethods like `dict.fromkeys()` and `bytes.fromhex()` that are
-/// called on the type itself rather than on an instance.
+/// called on the type itself rather than on an instance. Some, like `Path.cwd()`,
+/// need the host and return an OS call.
 pub(crate) fn call_type_method(
     t: Type,
     method_id: StringId,
     args: ArgValues,
     vm: &mut VM<'_, '_, impl ResourceTracker>,
-) -> Result<Value, RunError> {
+) -> Result<CallResult, RunError> {
     match (t, method_id) {
-        (Type::Dict, m) if m == StaticStrings::Fromkeys => return dict_fromkeys(args, vm),
+        (Type::Dict, m) if m == StaticStrings::Fromkeys => return dict_fromkeys(args, vm).map(CallResult::Value),
         (Type::Bytes, m) if m == StaticStrings::Fromhex => {
-            return bytes_fromhex(args, vm.heap, vm.interns);
+            return bytes_fromhex(args, vm.heap, vm.interns).map(CallResult::Value);
         }
+        (Type::Path, m) if m == StaticStrings::Home => return Path::home(vm, args),
+        (Type::Path, m) if m == StaticStrings::Cwd => return Path::cwd(vm, args),
         _ => {}
     }
     // Other types or unknown methods - report actual type name, not 'type'
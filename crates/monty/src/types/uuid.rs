@@ -0,0 +1,384 @@
+//! Python `uuid.UUID` type implementation.
+//!
+//! A `Uuid` is an immutable 128-bit value. It can be built from a hex string (braces,
+//! hyphens and a `urn:uuid:` prefix are optional), from 16 big-endian or little-endian
+//! bytes, from the six RFC 4122 fields, or from an integer, and exposes the same
+//! read-only attributes as CPython's `uuid.UUID`. UUIDs compare and hash by value.
+
+use std::{cmp::Ordering, fmt::Write};
+
+use crate::{
+    args::ArgValues,
+    bytecode::{CallResult, VM},
+    defer_drop,
+    exception_private::{ExcType, RunError, RunResult, SimpleException},
+    heap::{Heap, HeapData, HeapId},
+    intern::StaticStrings,
+    resource::{ResourceError, ResourceTracker},
+    types::{
+        Bytes, LongInt, PyTrait, Type, allocate_tuple,
+        str::{allocate_string, string_repr_fmt},
+    },
+    value::{EitherStr, Value},
+};
+use ahash::AHashSet;
+use num_bigint::BigInt;
+use num_traits::ToPrimitive;
+
+/// Python `uuid.UUID` object.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize)]
+pub(crate) struct Uuid {
+    /// The UUID as a 128-bit integer, exposed as the `int` attribute.
+    value: u128,
+}
+
+impl Uuid {
+    /// Creates a UUID from its 128-bit integer value.
+    #[must_use]
+    pub fn new(value: u128) -> Self {
+        Self { value }
+    }
+
+    /// Creates a UUID from 16 big-endian bytes, setting the RFC 4122 variant and `version`.
+    ///
+    /// This is `UUID(bytes=bytes, version=version)`, as used by `uuid3()`, `uuid4()` and `uuid5()`.
+    #[must_use]
+    pub fn with_version(bytes: [u8; 16], version: u8) -> Self {
+        Self::new(set_version(u128::from_be_bytes(bytes), version))
+    }
+
+    /// Returns the UUID as a 128-bit integer.
+    #[must_use]
+    pub fn value(self) -> u128 {
+        self.value
+    }
+
+    /// Returns the 16 big-endian bytes of the UUID.
+    #[must_use]
+    pub fn to_bytes(self) -> [u8; 16] {
+        self.value.to_be_bytes()
+    }
+
+    /// `UUID(hex=None, bytes=None, bytes_le=None, fields=None, int=None, version=None)` constructor.
+    pub fn init(vm: &mut VM<'_, '_, impl ResourceTracker>, args: ArgValues) -> RunResult<Value> {
+        let params = args.bind_params(
+            "UUID",
+            ["hex", "bytes", "bytes_le", "fields", "int", "version"],
+            0,
+            vm.heap,
+            vm.interns,
+        )?;
+        defer_drop!(params, vm);
+        let [hex, bytes, bytes_le, fields, int, version] = params;
+        let given = [hex, bytes, bytes_le, fields, int]
+            .iter()
+            .filter(|arg| arg.as_ref().is_some_and(|value| !matches!(value, Value::None)))
+            .count();
+        if given != 1 {
+            return Err(ExcType::type_error(
+                "one of the hex, bytes, bytes_le, fields, or int arguments must be given",
+            ));
+        }
+        let arg = |param: &Option<Value>| param.as_ref().filter(|value| !matches!(value, Value::None));
+
+        let mut value = if let Some(hex) = arg(hex) {
+            parse_hex(hex, vm)?
+        } else if let Some(bytes) = arg(bytes) {
+            u128::from_be_bytes(bytes_arg(bytes, "bytes", vm)?)
+        } else if let Some(bytes_le) = arg(bytes_le) {
+            u128::from_be_bytes(swap_fields(bytes_arg(bytes_le, "bytes_le", vm)?))
+        } else if let Some(fields) = arg(fields) {
+            fields_arg(fields, vm)?
+        } else {
+            let int = arg(int).expect("one argument is given");
+            int_arg(int, vm.heap)?.ok_or_else(|| value_error("int is out of range (need a 128-bit value)"))?
+        };
+
+        if let Some(version) = arg(version) {
+            let version = match version {
+                Value::Int(v) => *v,
+                Value::Bool(b) => i64::from(*b),
+                _ => return Err(int_type_error(version, vm.heap)),
+            };
+            let version = u8::try_from(version)
+                .ok()
+                .filter(|v| (1..=5).contains(v))
+                .ok_or_else(|| value_error("illegal version number"))?;
+            value = set_version(value, version);
+        }
+        Ok(Value::Ref(vm.heap.allocate(HeapData::Uuid(Self::new(value)))?))
+    }
+
+    /// Returns the 32 lowercase hex digits of the UUID, as the `hex` attribute.
+    #[must_use]
+    pub fn hex(self) -> String {
+        format!("{:032x}", self.value)
+    }
+
+    /// Returns the RFC 4122 variant of the UUID, as one of the `uuid.RESERVED_*`/`RFC_4122` strings.
+    #[must_use]
+    pub fn variant(self) -> StaticStrings {
+        if self.value & (0x8000 << 48) == 0 {
+            StaticStrings::ReservedNcsValue
+        } else if self.value & (0x4000 << 48) == 0 {
+            StaticStrings::Rfc4122Value
+        } else if self.value & (0x2000 << 48) == 0 {
+            StaticStrings::ReservedMicrosoftValue
+        } else {
+            StaticStrings::ReservedFutureValue
+        }
+    }
+
+    /// Returns the version number of an RFC 4122 UUID, or `None` for other variants.
+    #[must_use]
+    #[expect(clippy::cast_possible_truncation, reason = "masked to 4 bits")]
+    pub fn version(self) -> Option<u8> {
+        if self.variant() == StaticStrings::Rfc4122Value {
+            Some(((self.value >> 76) & 0xf) as u8)
+        } else {
+            None
+        }
+    }
+
+    /// Returns the six RFC 4122 fields as the `fields` attribute does.
+    #[expect(clippy::cast_possible_truncation, reason = "each field is masked to at most 48 bits")]
+    fn fields(self) -> [u64; 6] {
+        let v = self.value;
+        [
+            (v >> 96) as u64,
+            ((v >> 80) & 0xffff) as u64,
+            ((v >> 64) & 0xffff) as u64,
+            ((v >> 56) & 0xff) as u64,
+            ((v >> 48) & 0xff) as u64,
+            (v & 0xffff_ffff_ffff) as u64,
+        ]
+    }
+}
+
+/// Formats a UUID in its canonical `12345678-1234-5678-1234-567812345678` form.
+#[must_use]
+pub(crate) fn uuid_str(value: u128) -> String {
+    let hex = format!("{value:032x}");
+    format!(
+        "{}-{}-{}-{}-{}",
+        &hex[..8],
+        &hex[8..12],
+        &hex[12..16],
+        &hex[16..20],
+        &hex[20..]
+    )
+}
+
+/// Sets the RFC 4122 variant and the `version` bits of a UUID value.
+fn set_version(value: u128, version: u8) -> u128 {
+    let value = (value & !(0xc000 << 48)) | (0x8000 << 48);
+    (value & !(0xf000 << 64)) | (u128::from(version) << 76)
+}
+
+/// Converts between the big-endian and `bytes_le` layouts, which store the first three
+/// fields little-endian.
+fn swap_fields(mut bytes: [u8; 16]) -> [u8; 16] {
+    bytes[..4].reverse();
+    bytes[4..6].reverse();
+    bytes[6..8].reverse();
+    bytes
+}
+
+/// Parses the `hex` argument: a string of 32 hex digits with optional braces, hyphens
+/// and `urn:`/`uuid:` prefixes.
+fn parse_hex(hex: &Value, vm: &VM<'_, '_, impl ResourceTracker>) -> RunResult<u128> {
+    let Some(s) = hex.as_either_str(vm.heap) else {
+        return Err(ExcType::attribute_error(hex.py_type(vm.heap), "replace"));
+    };
+    let s = s.as_str(vm.interns).replace("urn:", "").replace("uuid:", "");
+    let digits = s.trim_matches(|c| c == '{' || c == '}').replace('-', "");
+    if digits.chars().count() != 32 {
+        return Err(value_error("badly formed hexadecimal UUID string"));
+    }
+    u128::from_str_radix(&digits, 16).map_err(|_| {
+        let mut repr = String::new();
+        // Writing to a String never fails
+        string_repr_fmt(&digits, &mut repr).unwrap();
+        value_error(format!("invalid literal for int() with base 16: {repr}"))
+    })
+}
+
+/// Extracts a 16-byte `bytes` or `bytes_le` argument.
+fn bytes_arg(value: &Value, name: &str, vm: &VM<'_, '_, impl ResourceTracker>) -> RunResult<[u8; 16]> {
+    let data = match value {
+        Value::InternBytes(id) => vm.interns.get_bytes(*id),
+        Value::Ref(id) => match vm.heap.get(*id) {
+            HeapData::Bytes(b) => b.as_slice(),
+            _ => return Err(bytes_type_error(value, name, vm.heap)),
+        },
+        _ => return Err(bytes_type_error(value, name, vm.heap)),
+    };
+    data.try_into()
+        .map_err(|_| value_error(format!("{name} is not a 16-char string")))
+}
+
+/// The `TypeError` for a `bytes` or `bytes_le` argument that is not `bytes`.
+fn bytes_type_error(value: &Value, name: &str, heap: &Heap<impl ResourceTracker>) -> RunError {
+    ExcType::type_error(format!("{name} must be a bytes object, not {}", value.py_type(heap)))
+}
+
+/// Builds a UUID value from the `fields` argument, a 6-tuple of integers.
+fn fields_arg(fields: &Value, vm: &VM<'_, '_, impl ResourceTracker>) -> RunResult<u128> {
+    const BITS: [u32; 6] = [32, 16, 16, 8, 8, 48];
+
+    let items = match fields {
+        Value::Ref(id) => match vm.heap.get(*id) {
+            HeapData::Tuple(t) => t.as_slice(),
+            HeapData::List(l) => l.as_slice(),
+            _ => return Err(fields_type_error(fields, vm.heap)),
+        },
+        _ => return Err(fields_type_error(fields, vm.heap)),
+    };
+    if items.len() != 6 {
+        return Err(value_error("fields is not a 6-tuple"));
+    }
+    let mut parts = [0u128; 6];
+    for (index, (item, bits)) in items.iter().zip(BITS).enumerate() {
+        parts[index] = int_arg(item, vm.heap)?
+            .filter(|&part| part < 1 << bits)
+            .ok_or_else(|| {
+                let article = if bits == 8 { "an" } else { "a" };
+                value_error(format!(
+                    "field {} out of range (need {article} {bits}-bit value)",
+                    index + 1
+                ))
+            })?;
+    }
+    let [
+        time_low,
+        time_mid,
+        time_hi_version,
+        clock_seq_hi_variant,
+        clock_seq_low,
+        node,
+    ] = parts;
+    let clock_seq = (clock_seq_hi_variant << 8) | clock_seq_low;
+    Ok((time_low << 96) | (time_mid << 80) | (time_hi_version << 64) | (clock_seq << 48) | node)
+}
+
+/// The `TypeError` for a `fields` argument that is not a sequence.
+fn fields_type_error(value: &Value, heap: &Heap<impl ResourceTracker>) -> RunError {
+    ExcType::type_error(format!("object of type '{}' has no len()", value.py_type(heap)))
+}
+
+/// Extracts an integer argument, or `None` if it is negative or needs more than 128 bits.
+fn int_arg(value: &Value, heap: &Heap<impl ResourceTracker>) -> RunResult<Option<u128>> {
+    match value {
+        Value::Int(i) => Ok(u128::try_from(*i).ok()),
+        Value::Bool(b) => Ok(Some(u128::from(*b))),
+        Value::Ref(id) => match heap.get(*id) {
+            HeapData::LongInt(li) => Ok(li.inner().to_u128()),
+            _ => Err(int_type_error(value, heap)),
+        },
+        _ => Err(int_type_error(value, heap)),
+    }
+}
+
+/// The `TypeError` CPython raises when range-checking an integer argument of another type.
+fn int_type_error(value: &Value, heap: &Heap<impl ResourceTracker>) -> RunError {
+    ExcType::type_error(format!(
+        "'<=' not supported between instances of 'int' and '{}'",
+        value.py_type(heap)
+    ))
+}
+
+/// Creates a `ValueError` with `msg`.
+fn value_error(msg: impl Into<String>) -> RunError {
+    SimpleException::new_msg(ExcType::ValueError, msg.into()).into()
+}
+
+/// Converts an unsigned integer to an `int` value, promoting to `LongInt` when needed.
+fn int_value(value: u128, heap: &mut Heap<impl ResourceTracker>) -> Result<Value, ResourceError> {
+    match i64::try_from(value) {
+        Ok(i) => Ok(Value::Int(i)),
+        Err(_) => LongInt::new(BigInt::from(value)).into_value(heap),
+    }
+}
+
+impl PyTrait for Uuid {
+    fn py_type(&self, _heap: &Heap<impl ResourceTracker>) -> Type {
+        Type::Uuid
+    }
+
+    fn py_len(&self, _vm: &VM<'_, '_, impl ResourceTracker>) -> Option<usize> {
+        None
+    }
+
+    fn py_eq(&self, other: &Self, _vm: &mut VM<'_, '_, impl ResourceTracker>) -> Result<bool, ResourceError> {
+        Ok(self.value == other.value)
+    }
+
+    fn py_cmp(
+        &self,
+        other: &Self,
+        _vm: &mut VM<'_, '_, impl ResourceTracker>,
+    ) -> Result<Option<Ordering>, ResourceError> {
+        Ok(Some(self.value.cmp(&other.value)))
+    }
+
+    fn py_dec_ref_ids(&mut self, _stack: &mut Vec<HeapId>) {
+        // No heap references — the value is a plain integer.
+    }
+
+    fn py_bool(&self, _vm: &VM<'_, '_, impl ResourceTracker>) -> bool {
+        true
+    }
+
+    fn py_repr_fmt(
+        &self,
+        f: &mut impl Write,
+        _vm: &VM<'_, '_, impl ResourceTracker>,
+        _heap_ids: &mut AHashSet<HeapId>,
+    ) -> std::fmt::Result {
+        write!(f, "UUID('{}')", uuid_str(self.value))
+    }
+
+    fn py_estimate_size(&self) -> usize {
+        std::mem::size_of::<Self>()
+    }
+
+    fn py_getattr(&self, attr: &EitherStr, vm: &mut VM<'_, '_, impl ResourceTracker>) -> RunResult<Option<CallResult>> {
+        let [
+            time_low,
+            time_mid,
+            time_hi_version,
+            clock_seq_hi_variant,
+            clock_seq_low,
+            node,
+        ] = self.fields();
+        let value = match attr.as_str(vm.interns) {
+            "hex" => allocate_string(self.hex(), vm.heap)?,
+            "int" => int_value(self.value, vm.heap)?,
+            "bytes" => Value::Ref(
+                vm.heap
+                    .allocate(HeapData::Bytes(Bytes::new(self.to_bytes().to_vec())))?,
+            ),
+            "bytes_le" => {
+                let bytes = swap_fields(self.to_bytes()).to_vec();
+                Value::Ref(vm.heap.allocate(HeapData::Bytes(Bytes::new(bytes)))?)
+            }
+            "urn" => allocate_string(format!("urn:uuid:{}", uuid_str(self.value)), vm.heap)?,
+            "variant" => self.variant().into(),
+            "version" => self.version().map_or(Value::None, |v| Value::Int(i64::from(v))),
+            "fields" => {
+                let items = self.fields().map(|field| Value::Int(field.cast_signed()));
+                allocate_tuple(items.into_iter().collect(), vm.heap)?
+            }
+            "time_low" => Value::Int(time_low.cast_signed()),
+            "time_mid" => Value::Int(time_mid.cast_signed()),
+            "time_hi_version" => Value::Int(time_hi_version.cast_signed()),
+            "clock_seq_hi_variant" => Value::Int(clock_seq_hi_variant.cast_signed()),
+            "clock_seq_low" => Value::Int(clock_seq_low.cast_signed()),
+            "clock_seq" => Value::Int((((clock_seq_hi_variant & 0x3f) << 8) | clock_seq_low).cast_signed()),
+            "node" => Value::Int(node.cast_signed()),
+            "time" => Value::Int((((time_hi_version & 0x0fff) << 48) | (time_mid << 32) | time_low).cast_signed()),
+            _ => return Ok(None),
+        };
+        Ok(Some(CallResult::Value(value)))
+    }
+}
@@ -17,6 +17,7 @@ use crate::{
     asyncio::CallId,
     builtins::Builtins,
     bytecode::{CallResult, VM},
+    defer_drop,
     exception_private::{ExcType, RunError, RunResult, SimpleException},
     heap::{ContainsHeap, Heap, HeapData, HeapGuard, HeapId},
     heap_data::HeapDataMut,
@@ -947,6 +948,12 @@ impl PyTrait for Value {
                 {
                     return path::path_div(*id, other, vm.heap, interns);
                 }
+                // Check for str / Path - the reflected path concatenation
+                if let Self::Ref(id) = other
+                    && matches!(vm.heap.get(*id), HeapData::Path(_))
+                {
+                    return path::path_rdiv(self, *id, vm.heap, interns);
+                }
                 Ok(None)
             }
         }
@@ -1662,42 +1669,86 @@ impl Value {
     /// On success, drops the old attribute value if one existed.
     pub fn py_set_attr(
         &self,
-        name_id: StringId,
+        attr: &EitherStr,
         value: Self,
         vm: &mut VM<'_, '_, impl ResourceTracker>,
     ) -> RunResult<()> {
-        let attr_name = vm.interns.get_str(name_id);
-
-        if let Self::Ref(heap_id) = self {
-            let heap_id = *heap_id;
-            let is_dataclass = matches!(vm.heap.get(heap_id), HeapData::Dataclass(_));
-
-            if is_dataclass {
-                let name_value = Self::InternString(name_id);
-                Heap::with_entry_mut(vm, heap_id, |vm, data| {
-                    if let HeapDataMut::Dataclass(dc) = data {
-                        match dc.set_attr(name_value, value, vm) {
-                            Ok(old_value) => {
-                                if let Some(old) = old_value {
-                                    old.drop_with_heap(vm.heap);
-                                }
-                                Ok(())
-                            }
-                            Err(e) => Err(e),
-                        }
-                    } else {
-                        unreachable!("type changed during borrow")
+        if let Self::Ref(heap_id) = self
+            && matches!(vm.heap.get(*heap_id), HeapData::Dataclass(_))
+        {
+            let name_value = match attr.to_value(vm.heap) {
+                Ok(name_value) => name_value,
+                Err(e) => {
+                    value.drop_with_heap(vm.heap);
+                    return Err(e.into());
+                }
+            };
+            Heap::with_entry_mut(vm, *heap_id, |vm, data| {
+                if let HeapDataMut::Dataclass(dc) = data {
+                    if let Some(old) = dc.set_attr(name_value, value, vm)? {
+                        old.drop_with_heap(vm.heap);
                     }
-                })
-            } else {
-                let type_name = vm.heap.get(heap_id).py_type(vm.heap);
-                value.drop_with_heap(vm.heap);
-                Err(ExcType::attribute_error_no_setattr(type_name, attr_name))
-            }
+                    Ok(())
+                } else {
+                    unreachable!("type changed during borrow")
+                }
+            })
         } else {
             let type_name = self.py_type(vm.heap);
             value.drop_with_heap(vm.heap);
-            Err(ExcType::attribute_error_no_setattr(type_name, attr_name))
+            Err(ExcType::attribute_error_no_setattr(type_name, attr.as_str(vm.interns)))
+        }
+    }
+
+    /// Deletes an attribute from this value, as `delattr()` does.
+    ///
+    /// Like `py_set_attr`, only Dataclass objects support it; deleting a declared field
+    /// of a frozen dataclass raises `FrozenInstanceError`. Returns AttributeError for
+    /// other types, or when the dataclass has no such attribute.
+    pub fn py_del_attr(&self, attr: &EitherStr, vm: &mut VM<'_, '_, impl ResourceTracker>) -> RunResult<()> {
+        if let Self::Ref(heap_id) = self
+            && matches!(vm.heap.get(*heap_id), HeapData::Dataclass(_))
+        {
+            let name_value = attr.to_value(vm.heap)?;
+            defer_drop!(name_value, vm);
+            let removed = Heap::with_entry_mut(vm, *heap_id, |vm, data| {
+                if let HeapDataMut::Dataclass(dc) = data {
+                    dc.del_attr(name_value, vm)
+                } else {
+                    unreachable!("type changed during borrow")
+                }
+            })?;
+            if removed {
+                Ok(())
+            } else {
+                let HeapData::Dataclass(dc) = vm.heap.get(*heap_id) else {
+                    unreachable!("type changed during borrow")
+                };
+                Err(ExcType::attribute_error(dc.name(vm.interns), attr.as_str(vm.interns)))
+            }
+        } else {
+            Err(ExcType::attribute_error_no_setattr(
+                self.py_type(vm.heap),
+                attr.as_str(vm.interns),
+            ))
+        }
+    }
+
+    /// Whether this value can be called, as reported by `callable()`.
+    ///
+    /// Must agree with `VM::call_function`: functions (including host functions),
+    /// builtins, types and exception types are callable; instances are not.
+    pub fn is_callable(&self, heap: &Heap<impl ResourceTracker>) -> bool {
+        match self {
+            Self::Builtin(_) | Self::ModuleFunction(_) | Self::DefFunction(_) | Self::ExtFunction(_) => true,
+            Self::Ref(heap_id) => matches!(
+                heap.get(*heap_id),
+                HeapData::Closure(_)
+                    | HeapData::FunctionDefaults(_)
+                    | HeapData::ExtFunction(_)
+                    | HeapData::OperatorCallable(_)
+            ),
+            _ => false,
         }
     }
 
@@ -2023,6 +2074,14 @@ impl EitherStr {
         }
     }
 
+    /// Converts this `EitherStr` into a `str` value, allocating heap-owned strings.
+    pub fn to_value(&self, heap: &mut Heap<impl ResourceTracker>) -> Result<Value, ResourceError> {
+        match self {
+            Self::Interned(id) => Ok(Value::InternString(*id)),
+            Self::Heap(s) => Ok(Value::Ref(heap.allocate(HeapData::Str(Str::from(s.clone())))?)),
+        }
+    }
+
     /// Converts this `EitherStr` into an owned `String`.
     ///
     /// For interned strings, looks up and clones the string content.
@@ -0,0 +1,46 @@
+# Tests for the base64 module
+
+import base64
+
+# === b64encode / b64decode ===
+assert base64.b64encode(b'hello world') == b'aGVsbG8gd29ybGQ=', 'b64encode'
+assert base64.b64encode(b'') == b'', 'b64encode empty'
+assert base64.b64decode(b'aGVsbG8gd29ybGQ=') == b'hello world', 'b64decode bytes'
+assert base64.b64decode('aGVsbG8gd29ybGQ=') == b'hello world', 'b64decode str'
+assert base64.b64encode(b'\xfb\xff', altchars=b'-_') == b'-_8=', 'b64encode altchars'
+assert base64.b64decode(b'-_8=', altchars=b'-_') == b'\xfb\xff', 'b64decode altchars'
+
+try:
+    base64.b64decode(b'aGV$sbG8=', validate=True)
+    assert False, 'validate=True rejects non-alphabet characters'
+except ValueError as e:
+    assert str(e) == 'Only base64 data is allowed', 'validate=True error message'
+
+# === standard / urlsafe ===
+assert base64.standard_b64encode(b'\xfb\xff') == b'+/8=', 'standard_b64encode'
+assert base64.standard_b64decode(b'+/8=') == b'\xfb\xff', 'standard_b64decode'
+assert base64.urlsafe_b64encode(b'\xfb\xff') == b'-_8=', 'urlsafe_b64encode'
+assert base64.urlsafe_b64decode(b'-_8=') == b'\xfb\xff', 'urlsafe_b64decode'
+
+# === base32 ===
+assert base64.b32encode(b'hello') == b'NBSWY3DP', 'b32encode'
+assert base64.b32encode(b'a') == b'ME======', 'b32encode pads'
+assert base64.b32decode(b'NBSWY3DP') == b'hello', 'b32decode'
+assert base64.b32decode(b'nbswy3dp', casefold=True) == b'hello', 'b32decode casefold'
+
+# === base16 ===
+assert base64.b16encode(b'\x01\xab') == b'01AB', 'b16encode uses uppercase'
+assert base64.b16decode(b'01AB') == b'\x01\xab', 'b16decode'
+assert base64.b16decode(b'01ab', casefold=True) == b'\x01\xab', 'b16decode casefold'
+
+try:
+    base64.b16decode(b'01ab')
+    assert False, 'b16decode rejects lowercase without casefold'
+except ValueError as e:
+    assert str(e) == 'Non-base16 digit found', 'b16decode error message'
+
+# === round trip ===
+data = bytes(range(256))
+assert base64.b64decode(base64.b64encode(data)) == data, 'b64 round trip'
+assert base64.b32decode(base64.b32encode(data)) == data, 'b32 round trip'
+assert base64.b16decode(base64.b16encode(data)) == data, 'b16 round trip'
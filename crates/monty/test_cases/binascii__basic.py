@@ -0,0 +1,46 @@
+# Tests for the binascii module
+
+import binascii
+
+# === hexlify / unhexlify ===
+assert binascii.hexlify(b'\x00\xffab') == b'00ff6162', 'hexlify encodes bytes as hex'
+assert binascii.b2a_hex(b'hi') == b'6869', 'b2a_hex is an alias of hexlify'
+assert binascii.unhexlify(b'00ff6162') == b'\x00\xffab', 'unhexlify decodes hex bytes'
+assert binascii.unhexlify('6869') == b'hi', 'unhexlify accepts ASCII str'
+assert binascii.a2b_hex(b'ABCD') == b'\xab\xcd', 'a2b_hex accepts uppercase digits'
+
+try:
+    binascii.unhexlify(b'abc')
+    assert False, 'odd-length hex should raise'
+except binascii.Error as e:
+    assert str(e) == 'Odd-length string', 'odd-length error message'
+
+try:
+    binascii.unhexlify(b'zz')
+    assert False, 'non-hex digit should raise'
+except ValueError as e:
+    assert str(e) == 'Non-hexadecimal digit found', 'binascii.Error is a ValueError'
+
+# === base64 ===
+assert binascii.b2a_base64(b'hello') == b'aGVsbG8=\n', 'b2a_base64 appends newline'
+assert binascii.b2a_base64(b'hello', newline=False) == b'aGVsbG8=', 'b2a_base64 newline=False'
+assert binascii.a2b_base64(b'aGVsbG8=\n') == b'hello', 'a2b_base64 decodes'
+assert binascii.a2b_base64('aGVs bG8=') == b'hello', 'a2b_base64 skips invalid characters'
+
+try:
+    binascii.a2b_base64(b'aGVsbG8')
+    assert False, 'incorrect padding should raise'
+except binascii.Error as e:
+    assert str(e) == 'Incorrect padding', 'padding error message'
+
+# === crc32 ===
+assert binascii.crc32(b'hello world') == 222957957, 'crc32 of bytes'
+assert binascii.crc32(b'') == 0, 'crc32 of empty bytes'
+assert binascii.crc32(b'world', binascii.crc32(b'hello ')) == 222957957, 'crc32 running value'
+
+# === type errors ===
+try:
+    binascii.hexlify('abc')
+    assert False, 'hexlify of str should raise'
+except TypeError as e:
+    assert str(e) == "a bytes-like object is required, not 'str'", 'hexlify str error message'
@@ -0,0 +1,73 @@
+# Tests for the bisect module
+
+import bisect
+
+a = [1, 2, 2, 2, 3, 5]
+
+# === bisect_left / bisect_right ===
+assert bisect.bisect_left(a, 2) == 1, 'bisect_left before equal items'
+assert bisect.bisect_right(a, 2) == 4, 'bisect_right after equal items'
+assert bisect.bisect(a, 2) == 4, 'bisect is bisect_right'
+assert bisect.bisect_left(a, 0) == 0, 'smaller than everything'
+assert bisect.bisect_right(a, 9) == 6, 'larger than everything'
+assert bisect.bisect_left(a, 4) == 5, 'missing item'
+assert bisect.bisect_left([], 1) == 0, 'empty list'
+assert bisect.bisect_left(a, 2, 2) == 2, 'lo'
+assert bisect.bisect_right(a, 2, 0, 3) == 3, 'hi'
+assert bisect.bisect_right(a, 2, lo=1, hi=None) == 4, 'hi=None means len(a)'
+assert bisect.bisect_left((1, 3, 5), 3) == 1, 'tuples can be searched'
+assert bisect.bisect_left('aceg', 'd') == 2, 'strings can be searched'
+assert bisect.bisect_right(range(0, 100, 10), 35) == 4, 'ranges can be searched'
+
+# grades example from the docs
+def grade(score, breakpoints=[60, 70, 80, 90], grades='FDCBA'):
+    return grades[bisect.bisect(breakpoints, score)]
+
+
+assert [grade(s) for s in [33, 99, 77, 70, 89, 90, 100]] == ['F', 'A', 'C', 'C', 'B', 'A', 'A'], 'grades'
+
+# === key ===
+records = [('a', 1), ('b', 3), ('c', 3), ('d', 7)]
+assert bisect.bisect_left(records, 3, key=lambda r: r[1]) == 1, 'bisect_left key'
+assert bisect.bisect_right(records, 3, key=lambda r: r[1]) == 3, 'bisect_right key'
+assert bisect.bisect_left(['a', 'bbb', 'cccc'], 2, key=len) == 1, 'key=len'
+
+# === insort ===
+b = [1, 3, 5]
+assert bisect.insort(b, 4) is None, 'insort returns None'
+assert b == [1, 3, 4, 5], f'insort: {b}'
+bisect.insort_left(b, 3)
+assert b == [1, 3, 3, 4, 5], f'insort_left: {b}'
+bisect.insort_right(b, 0)
+bisect.insort_right(b, 9)
+assert b == [0, 1, 3, 3, 4, 5, 9], f'insort at both ends: {b}'
+bisect.insort(b, 2, 0, 3)
+assert b == [0, 1, 2, 3, 3, 4, 5, 9], f'insort with bounds: {b}'
+
+pairs = [(1, 'x'), (1, 'y')]
+bisect.insort_left(pairs, (1, 'z'), key=lambda p: p[0])
+assert pairs == [(1, 'z'), (1, 'x'), (1, 'y')], f'insort_left key: {pairs}'
+bisect.insort_right(pairs, (1, 'w'), key=lambda p: p[0])
+assert pairs == [(1, 'z'), (1, 'x'), (1, 'y'), (1, 'w')], f'insort_right key: {pairs}'
+
+words = []
+for w in ['pear', 'fig', 'banana', 'kiwi']:
+    bisect.insort(words, w)
+assert words == ['banana', 'fig', 'kiwi', 'pear'], f'insort strings: {words}'
+
+# === errors ===
+try:
+    bisect.bisect_left(a, 2, -1)
+    assert False, 'negative lo should raise'
+except ValueError as e:
+    assert str(e) == 'lo must be non-negative', f'lo message: {e}'
+try:
+    bisect.insort((1, 2), 3)
+    assert False, 'insort into a tuple should raise'
+except AttributeError as e:
+    assert str(e) == "'tuple' object has no attribute 'insert'", f'insort message: {e}'
+try:
+    bisect.bisect([1, 2], 'a')
+    assert False, 'incomparable item should raise'
+except TypeError as e:
+    assert str(e) == "'<' not supported between instances of 'str' and 'int'", f'compare message: {e}'
@@ -0,0 +1,50 @@
+# Test aiter() and anext(), which raise TypeError for every type monty has
+
+# === aiter() ===
+for value in [[1, 2], 'ab', iter([1]), None]:
+    try:
+        aiter(value)
+        assert False, 'aiter() of a non-async iterable should raise TypeError'
+    except TypeError as e:
+        assert str(e) == f"'{type(value).__name__}' object is not an async iterable", str(e)
+
+try:
+    aiter()
+    assert False, 'aiter() without arguments should raise TypeError'
+except TypeError as e:
+    assert str(e) == 'aiter() takes exactly one argument (0 given)', str(e)
+
+try:
+    aiter([], [])
+    assert False, 'aiter() with two arguments should raise TypeError'
+except TypeError as e:
+    assert str(e) == 'aiter() takes exactly one argument (2 given)', str(e)
+
+# === anext() ===
+for value in [iter([1, 2]), [1], 42]:
+    try:
+        anext(value)
+        assert False, 'anext() of a non-async iterator should raise TypeError'
+    except TypeError as e:
+        assert str(e) == f"'{type(value).__name__}' object is not an async iterator", str(e)
+
+try:
+    anext([], 'default')
+    assert False, 'anext() default is only used for StopAsyncIteration'
+except TypeError as e:
+    assert str(e) == "'list' object is not an async iterator", str(e)
+
+try:
+    anext()
+    assert False, 'anext() without arguments should raise TypeError'
+except TypeError as e:
+    assert str(e) == 'anext expected at least 1 argument, got 0', str(e)
+
+try:
+    anext([], 1, 2)
+    assert False, 'anext() with three arguments should raise TypeError'
+except TypeError as e:
+    assert str(e) == 'anext expected at most 2 arguments, got 3', str(e)
+
+assert repr(aiter) == '<built-in function aiter>', 'aiter repr'
+assert repr(anext) == '<built-in function anext>', 'anext repr'
@@ -0,0 +1,68 @@
+# Test format() and ascii()
+
+# === format ===
+assert format(3.14159, '.2f') == '3.14', 'float precision'
+assert format(42, '08b') == '00101010', 'int binary with zero padding'
+assert format(255, 'x') == 'ff', 'int hex'
+assert format(255, 'X') == 'FF', 'int upper hex'
+assert format(0.25, '%') == '25.000000%', 'float percent'
+assert format('hi', '^6') == '  hi  ', 'str centered'
+assert format('hi', '*>5') == '***hi', 'str with fill'
+assert format(-5, '+') == '-5', 'int with sign'
+assert format(5, '+') == '+5', 'positive int with sign'
+
+# without a spec, or with an empty one, format() is str()
+assert format(42) == '42', 'int without spec'
+assert format(1.5, '') == '1.5', 'float with empty spec'
+assert format(0.1 + 0.2) == str(0.1 + 0.2), 'float without spec matches str()'
+assert format([1, 'a']) == "[1, 'a']", 'list without spec'
+assert format(None) == 'None', 'None without spec'
+assert format(True) == 'True', 'bool without spec'
+
+# format() matches f-strings
+spec = '>' + '4'
+assert format(7, spec) == '   7', 'spec built at runtime'
+assert format(7, spec) == f'{7:{spec}}', 'format() matches the f-string'
+assert format(2.5, '.3e') == f'{2.5:.3e}', 'format() matches the f-string for exponents'
+
+try:
+    format(1, 2)
+    assert False, 'format() with a non-str spec should raise TypeError'
+except TypeError as e:
+    assert str(e) == 'format() argument 2 must be str, not int', str(e)
+
+try:
+    format('hello', 'd')
+    assert False, 'format() with a spec for another type should raise ValueError'
+except ValueError as e:
+    assert str(e) == "Unknown format code 'd' for object of type 'str'", str(e)
+
+try:
+    format(1, 'xyz')
+    assert False, 'format() with an invalid spec should raise ValueError'
+except ValueError as e:
+    assert str(e) == "Invalid format specifier 'xyz' for object of type 'int'", str(e)
+
+try:
+    format()
+    assert False, 'format() with no args should raise TypeError'
+except TypeError:
+    pass
+
+# === ascii ===
+assert ascii('abc') == "'abc'", 'ascii of ascii str'
+assert ascii('caf\xe9') == "'caf\\xe9'", 'ascii escapes latin-1 chars'
+assert ascii('日本') == "'\\u65e5\\u672c'", 'ascii escapes BMP chars'
+assert ascii('\U0001f600') == "'\\U0001f600'", 'ascii escapes astral chars'
+assert ascii(['\xe9', 1]) == "['\\xe9', 1]", 'ascii escapes inside containers'
+assert ascii(42) == '42', 'ascii of int'
+assert ascii(None) == 'None', 'ascii of None'
+
+word = 'na\xefve'
+assert ascii(word) == f'{word!a}', 'ascii() matches the !a conversion'
+
+try:
+    ascii()
+    assert False, 'ascii() with no args should raise TypeError'
+except TypeError as e:
+    assert str(e) == 'ascii() takes exactly one argument (0 given)', str(e)
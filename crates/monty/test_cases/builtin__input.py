@@ -0,0 +1,35 @@
+# call-external
+# input() is answered by the host: the test host echoes the prompt back as the line read
+
+# === reading lines ===
+assert input('hello') == 'hello', 'the trailing newline is stripped'
+assert input(42) == '42', 'the prompt is converted with str()'
+answers = [input(f'line {i}') for i in range(3)]
+assert answers == ['line 0', 'line 1', 'line 2'], 'input in a comprehension'
+
+
+def ask(question):
+    return input(question + '? ').upper()
+
+
+assert ask('name') == 'NAME? ', 'input inside a function'
+
+# === end of input ===
+try:
+    input()
+    assert False, 'input should raise EOFError'
+except EOFError as e:
+    assert str(e) == 'EOF when reading a line', 'EOFError message'
+
+try:
+    input('')
+    assert False, 'an empty prompt should raise EOFError'
+except EOFError:
+    pass
+
+# === arguments ===
+try:
+    input('a', 'b')
+    assert False, 'input with two arguments should raise'
+except TypeError:
+    pass
@@ -0,0 +1,234 @@
+# Test hasattr(), callable(), dir(), vars(), globals() and locals()
+import hashlib
+import io
+import re
+import string
+import sys
+from pathlib import Path
+
+# === hasattr ===
+assert hasattr({'a': 1}, 'items'), 'dict has items'
+assert hasattr([], 'append'), 'list has append'
+assert hasattr('abc', 'upper'), 'str has upper'
+assert hasattr(b'abc', 'decode'), 'bytes has decode'
+assert hasattr((1, 2), 'count'), 'tuple has count'
+assert hasattr({1}, 'add'), 'set has add'
+assert not hasattr(frozenset(), 'add'), 'frozenset has no add'
+assert not hasattr({'a': 1}, 'append'), 'dict has no append'
+assert not hasattr(1, 'items'), 'int has no items'
+assert not hasattr(None, 'x'), 'None has no x'
+assert hasattr(slice(1, 2), 'start'), 'slice has start'
+assert hasattr(sys, 'version'), 'module attribute'
+assert not hasattr(sys, 'no_such_thing'), 'missing module attribute'
+
+name = 'up' + 'per'
+assert hasattr('abc', name), 'hasattr with a runtime name'
+
+try:
+    raise ValueError('boom')
+except ValueError as e:
+    assert hasattr(e, 'args'), 'exception has args'
+
+try:
+    hasattr([], 1)
+    assert False, 'hasattr() with non-string name should raise TypeError'
+except TypeError as e:
+    assert str(e) == "attribute name must be string, not 'int'", str(e)
+
+try:
+    hasattr([])
+    assert False, 'hasattr() with 1 arg should raise TypeError'
+except TypeError as e:
+    assert str(e) == 'hasattr expected 2 arguments, got 1', str(e)
+
+# === setattr / delattr on builtin types ===
+try:
+    setattr(1, 'x', 2)
+    assert False, 'setattr() on int should raise AttributeError'
+except AttributeError as e:
+    assert str(e) == "'int' object has no attribute 'x' and no __dict__ for setting new attributes", str(e)
+
+try:
+    delattr(1, 'x')
+    assert False, 'delattr() on int should raise AttributeError'
+except AttributeError:
+    pass
+
+try:
+    setattr([], 1, 2)
+    assert False, 'setattr() with non-string name should raise TypeError'
+except TypeError as e:
+    assert str(e) == "attribute name must be string, not 'int'", str(e)
+
+try:
+    setattr([], 'x')
+    assert False, 'setattr() with 2 args should raise TypeError'
+except TypeError as e:
+    assert str(e) == 'setattr expected 3 arguments, got 2', str(e)
+
+
+# === callable ===
+def f():
+    return 1
+
+
+def make_adder(n):
+    def add(x):
+        return x + n
+
+    return add
+
+
+def with_default(a=1):
+    return a
+
+
+assert callable(f), 'function is callable'
+assert callable(make_adder(1)), 'closure is callable'
+assert callable(with_default), 'function with defaults is callable'
+assert callable(lambda: 0), 'lambda is callable'
+assert callable(len), 'builtin is callable'
+assert callable(int), 'type is callable'
+assert callable(ValueError), 'exception type is callable'
+assert callable(sys.exit), 'module function is callable'
+assert not callable(1), 'int is not callable'
+assert not callable('f'), 'str is not callable'
+assert not callable([f]), 'list is not callable'
+assert not callable(None), 'None is not callable'
+assert not callable(ValueError('x')), 'exception instance is not callable'
+
+try:
+    callable()
+    assert False, 'callable() with no args should raise TypeError'
+except TypeError as e:
+    assert str(e) == 'callable() takes exactly one argument (0 given)', str(e)
+
+# === dir ===
+assert 'items' in dir({}), 'dir(dict) has items'
+assert 'append' in dir([]), 'dir(list) has append'
+assert 'upper' in dir(''), 'dir(str) has upper'
+assert 'version' in dir(sys), 'dir(module) has its attributes'
+names = dir([])
+assert names == sorted(names), 'dir() is sorted'
+
+version_info = sys.version_info
+assert 'major' in dir(version_info), 'dir(namedtuple) has its fields'
+assert 'releaselevel' in dir(version_info), 'dir(namedtuple) has its fields'
+assert hasattr(version_info, 'minor'), 'hasattr on namedtuple field'
+assert not hasattr(version_info, 'middle'), 'hasattr on missing namedtuple field'
+
+path = Path('/a/b.txt')
+assert hasattr(path, 'with_suffix'), 'Path has with_suffix'
+assert hasattr(path, 'read_text'), 'Path has read_text'
+assert not hasattr(path, 'no_such_method'), 'Path has no no_such_method'
+assert 'exists' in dir(path), 'dir(Path) has exists'
+
+pattern = re.compile('a+')
+assert hasattr(pattern, 'findall'), 'Pattern has findall'
+assert 'sub' in dir(pattern), 'dir(Pattern) has sub'
+match = pattern.match('aa')
+assert hasattr(match, 'group'), 'Match has group'
+assert 'span' in dir(match), 'dir(Match) has span'
+
+assert hasattr(hashlib.sha256(), 'hexdigest'), 'hash object has hexdigest'
+assert hasattr(io.StringIO(), 'getvalue'), 'StringIO has getvalue'
+assert hasattr(string.Template('$x'), 'substitute'), 'Template has substitute'
+
+pair = (1, 2)
+
+module_names = dir()
+assert 'f' in module_names, 'dir() at module level has functions'
+assert 'pair' in module_names, 'dir() at module level has variables'
+assert module_names == sorted(module_names), 'dir() is sorted'
+
+
+def dir_in_function(a, b=2):
+    c = a + b
+    return dir()
+
+
+assert dir_in_function(1) == ['a', 'b', 'c'], 'dir() in a function lists its locals'
+
+# === vars ===
+try:
+    vars(1)
+    assert False, 'vars() of int should raise TypeError'
+except TypeError as e:
+    assert str(e) == 'vars() argument must have __dict__ attribute', str(e)
+
+assert vars(sys)['platform'] == sys.platform, 'vars(module) has its attributes'
+
+# === globals ===
+g = globals()
+assert g['f'] is f, 'globals() has functions'
+assert g['pair'] == pair, 'globals() has variables'
+assert 'not_yet_assigned' not in g, 'globals() skips unbound names'
+assert g['__name__'] == '__main__', 'globals() has __name__'
+assert g['__doc__'] is None, 'globals() has __doc__'
+assert g['__file__'].endswith('builtin__introspection.py'), 'globals() has __file__'
+not_yet_assigned = 1
+
+
+def read_globals():
+    return globals()['pair']
+
+
+assert read_globals() == pair, 'globals() in a function is the module namespace'
+
+try:
+    globals(1)
+    assert False, 'globals() with an arg should raise TypeError'
+except TypeError as e:
+    assert str(e) == 'globals() takes no arguments (1 given)', str(e)
+
+
+# === locals ===
+def read_locals(a, b=2):
+    c = a + b
+    return locals()
+
+
+assert read_locals(1) == {'a': 1, 'b': 2, 'c': 3}, 'locals() in a function'
+
+
+def read_locals_unbound(a):
+    before = locals()
+    later = a
+    return before
+
+
+assert read_locals_unbound(5) == {'a': 5}, 'locals() skips unbound locals'
+
+
+def read_locals_captured():
+    x = 1
+
+    def inner():
+        return x
+
+    return locals()
+
+
+captured = read_locals_captured()
+assert captured['x'] == 1, 'locals() has captured variables'
+assert sorted(captured) == ['inner', 'x'], 'locals() has nested functions'
+
+
+def read_free_variable():
+    y = 10
+
+    def inner():
+        return y, locals()
+
+    return inner()
+
+
+assert read_free_variable() == (10, {'y': 10}), 'locals() has free variables'
+
+assert locals()['pair'] == pair, 'locals() at module level is globals()'
+
+try:
+    locals(1)
+    assert False, 'locals() with an arg should raise TypeError'
+except TypeError as e:
+    assert str(e) == 'locals() takes no arguments (1 given)', str(e)
@@ -0,0 +1,79 @@
+# Test iter(callable, sentinel) and iterating over iterator objects
+
+# === iter(callable, sentinel) ===
+values = [1, 2, 3, 0, 4]
+
+
+def take():
+    return values.pop(0)
+
+
+assert list(iter(take, 0)) == [1, 2, 3], 'stops at the sentinel'
+assert values == [4], 'the sentinel is consumed, but nothing after it'
+
+lines = ['a', 'b', '', 'c']
+seen = []
+for line in iter(lambda: lines.pop(0), ''):
+    seen.append(line)
+assert seen == ['a', 'b'], 'for loop over a callable iterator'
+assert lines == ['c'], 'for loop stops at the sentinel'
+
+counter = [0]
+
+
+def count():
+    counter[0] += 1
+    return counter[0]
+
+
+it = iter(count, 3)
+assert next(it) == 1, 'next() calls the callable'
+assert next(it) == 2, 'next() calls the callable again'
+assert next(it, 'done') == 'done', 'next() default once the sentinel is returned'
+assert next(it, 'done') == 'done', 'the iterator stays exhausted'
+assert counter[0] == 3, 'the callable is not called again once exhausted'
+
+assert [n * 10 for n in iter(count, 6)] == [40, 50], 'comprehension over a callable iterator'
+assert list(iter(int, 0)) == [], 'builtin callable returning the sentinel straight away'
+
+chunks = [[1], [2, 3], [], [4]]
+assert list(iter(lambda: chunks.pop(0), [])) == [[1], [2, 3]], 'the sentinel is compared with =='
+
+it = iter(count, 100)
+assert iter(it) is it, 'iter() of a callable iterator returns it'
+
+
+def boom():
+    raise ValueError('boom')
+
+
+try:
+    next(iter(boom, None))
+    assert False, 'errors from the callable should propagate'
+except ValueError as e:
+    assert str(e) == 'boom', str(e)
+
+try:
+    iter(1, 2)
+    assert False, 'iter() of a non-callable with a sentinel should raise TypeError'
+except TypeError as e:
+    assert str(e) == 'iter(v, w): v must be callable', str(e)
+
+# === iterating over iterators ===
+it = iter([1, 2, 3, 4])
+assert next(it) == 1, 'next() before list()'
+assert list(it) == [2, 3, 4], 'list() continues from where the iterator is'
+assert list(it) == [], 'the iterator is exhausted'
+
+it = iter([1, 2, 3])
+for x in it:
+    if x == 1:
+        break
+assert list(it) == [2, 3], 'for loop over an iterator advances it'
+
+assert sum(iter([1, 2, 3])) == 6, 'sum() of an iterator'
+assert sorted(iter([3, 1, 2])) == [1, 2, 3], 'sorted() of an iterator'
+assert tuple(zip(iter([1, 2]), iter('ab'))) == ((1, 'a'), (2, 'b')), 'zip() of iterators'
+a, b = iter([1, 2])
+assert (a, b) == (1, 2), 'unpacking an iterator'
+assert [c.upper() for c in iter('ab')] == ['A', 'B'], 'comprehension over an iterator'
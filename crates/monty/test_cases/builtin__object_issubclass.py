@@ -0,0 +1,75 @@
+# Test object() sentinels and issubclass()
+
+# === object ===
+MISSING = object()
+other = object()
+assert MISSING is MISSING, 'an object is itself'
+assert MISSING is not other, 'each object() is a new object'
+assert MISSING == MISSING, 'an object equals itself'
+assert MISSING != other, 'distinct objects are not equal'
+assert MISSING != None, 'objects are not equal to None'
+assert bool(MISSING), 'objects are truthy'
+assert type(MISSING) is object, 'type of an object() instance'
+assert repr(MISSING).startswith('<object object'), repr(MISSING)
+
+d = {MISSING: 1, other: 2}
+assert d[MISSING] == 1, 'objects are hashable'
+assert len(d) == 2, 'distinct objects are distinct keys'
+assert len({MISSING, MISSING, other}) == 2, 'objects in a set'
+assert hash(MISSING) == hash(MISSING), 'hash is stable'
+
+
+def lookup(mapping, key, default=MISSING):
+    value = mapping.get(key, MISSING)
+    if value is MISSING:
+        if default is MISSING:
+            raise KeyError(key)
+        return default
+    return value
+
+
+assert lookup({'a': None}, 'a') is None, 'sentinel lets None be a value'
+assert lookup({}, 'a', 0) == 0, 'sentinel default'
+
+assert isinstance(MISSING, object), 'object() instance is an object'
+assert isinstance(1, object), 'int is an object'
+assert isinstance('a', object), 'str is an object'
+assert isinstance(None, object), 'None is an object'
+assert isinstance(ValueError('x'), object), 'exception is an object'
+assert not isinstance(MISSING, int), 'object() instance is not an int'
+
+try:
+    object(1)
+    assert False, 'object() with an arg should raise TypeError'
+except TypeError:
+    pass
+
+# === issubclass ===
+assert issubclass(bool, int), 'bool is a subclass of int'
+assert not issubclass(int, bool), 'int is not a subclass of bool'
+assert issubclass(int, int), 'a class is a subclass of itself'
+assert issubclass(int, object), 'every class is a subclass of object'
+assert issubclass(object, object), 'object is a subclass of itself'
+assert not issubclass(object, int), 'object is not a subclass of int'
+assert issubclass(ValueError, Exception), 'exception hierarchy'
+assert issubclass(KeyError, LookupError), 'exception hierarchy'
+assert issubclass(ValueError, object), 'exceptions are subclasses of object'
+assert not issubclass(ValueError, KeyError), 'unrelated exceptions'
+assert not issubclass(Exception, ValueError), 'base is not a subclass of derived'
+assert issubclass(ValueError, (TypeError, ValueError)), 'tuple of classes'
+assert issubclass(str, (int, (bytes, str))), 'nested tuple of classes'
+assert not issubclass(str, ()), 'empty tuple'
+assert issubclass(type(KeyError('k')), LookupError), 'type() of an exception'
+assert isinstance(KeyError('k'), type(LookupError('l'))), 'isinstance with type() of an exception'
+
+try:
+    issubclass(1, int)
+    assert False, 'issubclass() with a non-class should raise TypeError'
+except TypeError as e:
+    assert str(e) == 'issubclass() arg 1 must be a class', str(e)
+
+try:
+    issubclass(int, 1)
+    assert False, 'issubclass() with a non-class classinfo should raise TypeError'
+except TypeError as e:
+    assert str(e) == 'issubclass() arg 2 must be a class, a tuple of classes, or a union', str(e)
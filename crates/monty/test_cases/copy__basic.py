@@ -0,0 +1,105 @@
+# Tests for the copy module
+
+import copy
+import sys
+from pathlib import Path
+
+# === shallow copy ===
+inner = [1, 2]
+outer = [inner, 'x']
+c = copy.copy(outer)
+assert c == outer, 'shallow copy is equal'
+assert c is not outer, 'shallow copy is a new list'
+assert c[0] is inner, 'shallow copy shares items'
+c.append(3)
+assert outer == [inner, 'x'], 'original unchanged by append'
+
+d = {'a': [1], 'b': 2}
+dc = copy.copy(d)
+assert dc == d and dc is not d, 'shallow dict copy'
+assert dc['a'] is d['a'], 'shallow dict copy shares values'
+
+s = {1, 2, 3}
+sc = copy.copy(s)
+assert sc == s and sc is not s, 'shallow set copy'
+
+t = (1, [2])
+assert copy.copy(t) is t, 'tuples are returned unchanged'
+fs = frozenset({1, 2})
+assert copy.copy(fs) is fs, 'frozensets are returned unchanged'
+assert copy.copy('abc') == 'abc', 'str copy'
+assert copy.copy(42) == 42, 'int copy'
+assert copy.copy(None) is None, 'None copy'
+assert copy.copy(len) is len, 'functions are returned unchanged'
+
+# === deep copy ===
+outer = [[1, 2], {'k': [3]}, (4, [5])]
+dc = copy.deepcopy(outer)
+assert dc == outer, 'deep copy is equal'
+assert dc[0] is not outer[0], 'nested list copied'
+assert dc[1]['k'] is not outer[1]['k'], 'list in dict copied'
+assert dc[2] is not outer[2], 'tuple holding a list is copied'
+assert dc[2][1] is not outer[2][1], 'list in tuple copied'
+dc[0].append(99)
+assert outer[0] == [1, 2], 'original unchanged by nested append'
+
+t = (1, 'a', (2, 3))
+assert copy.deepcopy(t) is t, 'immutable tuple returned unchanged'
+fs = frozenset({1, (2, 3)})
+fc = copy.deepcopy(fs)
+assert fc == fs and fc is not fs, 'frozenset deep copy is a new object'
+s = {1, (2, 3)}
+assert copy.deepcopy(s) == s and copy.deepcopy(s) is not s, 'set deep copy'
+
+# shared references are preserved
+shared = [1]
+data = {'a': shared, 'b': shared}
+dc = copy.deepcopy(data)
+assert dc['a'] is dc['b'], 'shared reference preserved'
+assert dc['a'] is not shared, 'shared object still copied'
+
+# cycles
+a = [1]
+a.append(a)
+b = copy.deepcopy(a)
+assert b is not a, 'cyclic list copied'
+assert b[1] is b, 'self reference points to the copy'
+assert b[0] == 1, 'cyclic list contents'
+
+d = {'name': 'root'}
+d['self'] = d
+d['children'] = [d]
+dc = copy.deepcopy(d)
+assert dc['self'] is dc, 'dict self reference'
+assert dc['children'][0] is dc, 'cycle through a list'
+assert dc is not d, 'cyclic dict copied'
+
+lst = []
+t = (lst,)
+lst.append(t)
+tc = copy.deepcopy(t)
+assert tc is not t, 'tuple in a cycle copied'
+assert tc[0][0] is tc, 'cycle through a tuple'
+
+# paths, exceptions
+p = Path('/tmp/x')
+assert copy.deepcopy(p) == p, 'path deep copy'
+assert copy.copy(p) == p, 'path shallow copy'
+e = ValueError('bad')
+ec = copy.deepcopy(e)
+assert type(ec) is ValueError and str(ec) == 'bad', 'exception copy'
+
+# memo
+assert copy.deepcopy([1], None) == [1], 'memo=None'
+
+# === errors ===
+try:
+    copy.copy(sys)
+    assert False, 'module copy should raise'
+except TypeError as e:
+    assert str(e) == "cannot pickle 'module' object", f'module message: {e}'
+try:
+    copy.deepcopy({'m': sys})
+    assert False, 'nested module copy should raise'
+except TypeError as e:
+    assert str(e) == "cannot pickle 'module' object", f'nested module message: {e}'
@@ -0,0 +1,27 @@
+# call-external
+# Tests for copying dataclasses
+
+import copy
+
+point = make_mutable_point()
+c = copy.copy(point)
+assert c == point, 'shallow dataclass copy is equal'
+assert c is not point, 'shallow dataclass copy is a new object'
+c.x = 10
+assert point.x == 1, 'original unchanged'
+assert repr(c) == 'MutablePoint(x=10, y=2)', f'copy repr: {c!r}'
+
+point.y = [1, 2]
+c = copy.copy(point)
+assert c.y is point.y, 'shallow copy shares attributes'
+dc = copy.deepcopy(point)
+assert dc.y == [1, 2] and dc.y is not point.y, 'deep copy copies attributes'
+
+point.y = point
+dc = copy.deepcopy(point)
+assert dc.y is dc, 'dataclass self reference'
+
+frozen = make_point()
+fc = copy.deepcopy(frozen)
+assert fc == frozen, 'frozen dataclass deep copy'
+assert hash(fc) == hash(frozen), 'frozen copy hashes equal'
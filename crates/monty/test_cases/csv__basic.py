@@ -0,0 +1,183 @@
+# Tests for the csv module
+
+import csv
+import io
+
+# === reader ===
+rows = list(csv.reader(['a,b,c', '1,"2,3",4', '', 'x']))
+assert rows == [['a', 'b', 'c'], ['1', '2,3', '4'], [], ['x']], f'reader: {rows}'
+rows = list(csv.reader('name,quote\nbob,"he said ""hi"""\n'.splitlines()))
+assert rows == [['name', 'quote'], ['bob', 'he said "hi"']], f'doubled quotes: {rows}'
+rows = list(csv.reader(io.StringIO('a,"multi\nline",b\r\nc,d\r\n')))
+assert rows == [['a', 'multi\nline', 'b'], ['c', 'd']], f'multi-line field: {rows}'
+rows = list(csv.reader(['a;b', 'c;d'], delimiter=';'))
+assert rows == [['a', 'b'], ['c', 'd']], 'delimiter'
+rows = list(csv.reader(['a, b,  "c"'], skipinitialspace=True))
+assert rows == [['a', 'b', 'c']], f'skipinitialspace: {rows}'
+rows = list(csv.reader(['a\\,b,c'], escapechar='\\'))
+assert rows == [['a,b', 'c']], f'escapechar: {rows}'
+rows = list(csv.reader(["'a,b',c"], quotechar="'"))
+assert rows == [['a,b', 'c']], 'quotechar'
+rows = list(csv.reader(['"a,b",c'], quoting=csv.QUOTE_NONE))
+assert rows == [['"a', 'b"', 'c']], f'QUOTE_NONE: {rows}'
+rows = list(csv.reader(['1,"2",3.5'], quoting=csv.QUOTE_NONNUMERIC))
+assert rows == [[1.0, '2', 3.5]], f'QUOTE_NONNUMERIC: {rows}'
+rows = list(csv.reader(['a\tb'], dialect='excel-tab'))
+assert rows == [['a', 'b']], 'excel-tab dialect'
+rows = list(csv.reader(['a\tb'], csv.excel_tab))
+assert rows == [['a', 'b']], 'dialect object'
+reader = csv.reader(['a,b'])
+assert next(reader) == ['a', 'b'], 'next on reader'
+assert list(reader) == [], 'reader exhausted'
+
+# === writer ===
+buf = io.StringIO()
+w = csv.writer(buf)
+assert w.writerow(['a', 1, 2.5, None, True]) == 15, 'writerow returns chars written'
+w.writerows([['x,y', 'he said "hi"'], ['line\nbreak', '']])
+expected = 'a,1,2.5,,True\r\n"x,y","he said ""hi"""\r\n"line\nbreak",\r\n'
+assert buf.getvalue() == expected, f'writer output: {buf.getvalue()!r}'
+
+buf = io.StringIO()
+w = csv.writer(buf, delimiter='|', lineterminator='\n', quoting=csv.QUOTE_ALL)
+w.writerow(['a', 1])
+assert buf.getvalue() == '"a"|"1"\n', f'QUOTE_ALL: {buf.getvalue()!r}'
+
+buf = io.StringIO()
+csv.writer(buf, quoting=csv.QUOTE_NONNUMERIC, lineterminator='\n').writerow(['a', 1, 2.0, None])
+assert buf.getvalue() == '"a",1,2.0,""\n', f'QUOTE_NONNUMERIC writer: {buf.getvalue()!r}'
+
+buf = io.StringIO()
+csv.writer(buf, quoting=csv.QUOTE_NONE, escapechar='\\', lineterminator='\n').writerow(['a,b', 'c"d'])
+assert buf.getvalue() == 'a\\,b,c\\"d\n', f'QUOTE_NONE with escapechar: {buf.getvalue()!r}'
+
+buf = io.StringIO()
+csv.writer(buf, doublequote=False, escapechar='\\', lineterminator='\n').writerow(['say "hi"'])
+assert buf.getvalue() == 'say \\"hi\\"\n', f'escaped quote: {buf.getvalue()!r}'
+
+buf = io.StringIO()
+csv.writer(buf, lineterminator='\n').writerow([''])
+assert buf.getvalue() == '""\n', 'single empty field is quoted'
+
+buf = io.StringIO()
+csv.writer(buf, lineterminator='\n').writerow('abc')
+assert buf.getvalue() == 'a,b,c\n', 'any iterable row'
+
+buf = io.StringIO()
+w = csv.writer(buf, dialect='unix')
+w.writerow(['a', 'b'])
+assert buf.getvalue() == '"a","b"\n', f'unix dialect: {buf.getvalue()!r}'
+assert w.dialect.lineterminator == '\n', 'writer dialect attribute'
+
+# round trip
+data = [['id', 'text'], ['1', 'comma, here'], ['2', 'quote " here'], ['3', 'new\nline']]
+buf = io.StringIO()
+csv.writer(buf).writerows(data)
+buf.seek(0)
+assert list(csv.reader(buf)) == data, 'round trip'
+
+# === DictReader ===
+text = 'name,age\nalice,30\n\nbob,25,extra\ncarol\n'
+rows = list(csv.DictReader(io.StringIO(text)))
+assert rows == [
+    {'name': 'alice', 'age': '30'},
+    {'name': 'bob', 'age': '25', None: ['extra']},
+    {'name': 'carol', 'age': None},
+], f'DictReader: {rows}'
+rows = list(csv.DictReader(['1,2,3', '4'], fieldnames=['a', 'b'], restkey='rest', restval='?'))
+assert rows == [{'a': '1', 'b': '2', 'rest': ['3']}, {'a': '4', 'b': '?'}], f'DictReader options: {rows}'
+assert list(csv.DictReader(['a,b'])) == [], 'header only'
+assert list(csv.DictReader([])) == [], 'empty input'
+rows = list(csv.DictReader(['a;b', '1;2'], delimiter=';'))
+assert rows == [{'a': '1', 'b': '2'}], 'DictReader fmtparams'
+
+# === DictWriter ===
+buf = io.StringIO()
+w = csv.DictWriter(buf, fieldnames=['name', 'age'], lineterminator='\n')
+w.writeheader()
+w.writerow({'name': 'alice', 'age': 30})
+w.writerows([{'name': 'bob'}, {'age': 5, 'name': 'x,y'}])
+assert buf.getvalue() == 'name,age\nalice,30\nbob,\n"x,y",5\n', f'DictWriter: {buf.getvalue()!r}'
+assert type(w) is csv.DictWriter, 'DictWriter type'
+
+buf = io.StringIO()
+w = csv.DictWriter(buf, ['a'], restval='-', extrasaction='ignore', lineterminator='\n')
+w.writerow({'b': 1})
+assert buf.getvalue() == '-\n', f'restval and ignore: {buf.getvalue()!r}'
+
+try:
+    csv.DictWriter(io.StringIO(), ['a']).writerow({'a': 1, 'b': 2})
+    assert False, 'extra keys should raise'
+except ValueError as e:
+    assert str(e) == "dict contains fields not in fieldnames: 'b'", f'extra keys message: {e}'
+try:
+    csv.DictWriter(io.StringIO(), ['a'], extrasaction='bad')
+    assert False, 'bad extrasaction should raise'
+except ValueError as e:
+    assert str(e) == "extrasaction (bad) must be 'raise' or 'ignore'", f'extrasaction message: {e}'
+
+# === dialects ===
+assert csv.list_dialects() == ['excel', 'excel-tab', 'unix'], f'list_dialects: {csv.list_dialects()}'
+d = csv.get_dialect('excel')
+assert d.delimiter == ',', 'excel delimiter'
+assert d.quotechar == '"', 'excel quotechar'
+assert d.doublequote is True, 'excel doublequote'
+assert d.escapechar is None, 'excel escapechar'
+assert d.lineterminator == '\r\n', 'excel lineterminator'
+assert d.quoting == csv.QUOTE_MINIMAL, 'excel quoting'
+assert d.skipinitialspace is False, 'excel skipinitialspace'
+assert d.strict is False, 'excel strict'
+assert csv.get_dialect('excel-tab').delimiter == '\t', 'excel-tab delimiter'
+assert csv.get_dialect('unix').quoting == csv.QUOTE_ALL, 'unix quoting'
+assert (csv.QUOTE_MINIMAL, csv.QUOTE_ALL, csv.QUOTE_NONNUMERIC, csv.QUOTE_NONE) == (0, 1, 2, 3), 'QUOTE constants'
+try:
+    csv.get_dialect('nope')
+    assert False, 'unknown dialect should raise'
+except csv.Error as e:
+    assert str(e) == 'unknown dialect', f'unknown dialect message: {e}'
+
+# === errors ===
+assert list(csv.reader(['a,"b'])) == [['a', 'b']], 'unterminated quote is lenient by default'
+assert list(csv.reader(['a,"b', 'c'])) == [['a', 'bc']], 'quoted field continues on next line'
+try:
+    list(csv.reader(['a,"b'], strict=True))
+    assert False, 'unterminated quote should raise in strict mode'
+except csv.Error as e:
+    assert str(e) == 'unexpected end of data', f'eof message: {e}'
+try:
+    list(csv.reader(['"a"b'], strict=True))
+    assert False, 'strict mode should raise'
+except csv.Error as e:
+    assert str(e) == "',' expected after '\"'", f'strict message: {e}'
+try:
+    list(csv.reader([1]))
+    assert False, 'non-string line should raise'
+except csv.Error as e:
+    assert str(e).startswith('iterator should return strings, not int'), f'non-string message: {e}'
+try:
+    csv.writer(io.StringIO(), quoting=csv.QUOTE_NONE).writerow(['a,b'])
+    assert False, 'unescapable field should raise'
+except csv.Error as e:
+    assert str(e) == 'need to escape, but no escapechar set', f'escape message: {e}'
+try:
+    csv.writer(io.StringIO()).writerow(1)
+    assert False, 'non-iterable row should raise'
+except csv.Error as e:
+    assert str(e) == 'iterable expected, not int', f'row message: {e}'
+try:
+    csv.writer(io.StringIO(), delimiter='ab')
+    assert False, 'long delimiter should raise'
+except TypeError as e:
+    assert str(e) == '"delimiter" must be a 1-character string', f'delimiter message: {e}'
+try:
+    csv.writer(io.StringIO(), quotechar=None, quoting=csv.QUOTE_ALL)
+    assert False, 'missing quotechar should raise'
+except TypeError as e:
+    assert str(e) == 'quotechar must be set if quoting enabled', f'quotechar message: {e}'
+assert csv.writer(io.StringIO(), quotechar=None).dialect.quoting == csv.QUOTE_NONE, 'quotechar=None disables quoting'
+try:
+    csv.writer(1)
+    assert False, 'target without write should raise'
+except TypeError as e:
+    assert str(e) == 'argument 1 must have a "write" method', f'target message: {e}'
+assert issubclass(csv.Error, Exception), 'csv.Error is an Exception'
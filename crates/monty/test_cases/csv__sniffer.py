@@ -0,0 +1,47 @@
+# Tests for csv.Sniffer
+
+import csv
+import io
+
+sniffer = csv.Sniffer()
+
+# === sniff ===
+d = sniffer.sniff('a;b;c\n1;2;3\n4;5;6\n')
+assert d.delimiter == ';', f'semicolon delimiter: {d.delimiter!r}'
+assert d.quotechar == '"', 'default quotechar'
+assert d.doublequote is False, 'doublequote'
+assert d.skipinitialspace is False, 'skipinitialspace'
+assert d.quoting == csv.QUOTE_MINIMAL, 'quoting'
+assert d.lineterminator == '\r\n', 'lineterminator'
+
+d = sniffer.sniff('name,age\n"smith, j",30\n"doe, a",25\n')
+assert d.delimiter == ',', f'quoted comma delimiter: {d.delimiter!r}'
+assert d.quotechar == '"', 'double quote'
+
+d = sniffer.sniff("'a'|'b'\n'c'|'d'\n")
+assert (d.delimiter, d.quotechar) == ('|', "'"), f'single quotes: {d.delimiter!r} {d.quotechar!r}'
+
+assert sniffer.sniff('x\ty\n1\t2\n3\t4\n').delimiter == '\t', 'tab delimiter'
+assert sniffer.sniff('a b c\n1 2 3\n').delimiter == ' ', 'space delimiter'
+assert sniffer.sniff('a:b:c\n1:2:3', delimiters=':').delimiter == ':', 'restricted delimiters'
+
+# a sniffed dialect can be used directly
+sample = 'id;name\n1;alice\n2;bob\n'
+rows = list(csv.reader(io.StringIO(sample), sniffer.sniff(sample)))
+assert rows == [['id', 'name'], ['1', 'alice'], ['2', 'bob']], f'reader with sniffed dialect: {rows}'
+
+try:
+    sniffer.sniff('abc', delimiters=',')
+    assert False, 'undetectable delimiter should raise'
+except csv.Error as e:
+    assert str(e) == 'Could not determine delimiter', f'sniff message: {e}'
+try:
+    sniffer.sniff('')
+    assert False, 'empty sample should raise'
+except csv.Error as e:
+    assert str(e) == 'Could not determine delimiter', f'empty sample message: {e}'
+
+# === has_header ===
+assert sniffer.has_header('name,age\nalice,30\nbob,25\n'), 'text header over numbers'
+assert not sniffer.has_header('1,2\n3,4\n5,6\n'), 'numeric first row'
+assert not sniffer.has_header('a,b\nc,d\n'), 'same-length text columns'
@@ -0,0 +1,54 @@
+# call-external
+# === Introspection builtins on host dataclasses ===
+point = make_point()
+mutable = make_mutable_point()
+
+# === hasattr ===
+assert hasattr(point, 'x'), 'hasattr on a field'
+assert not hasattr(point, 'z'), 'hasattr on a missing attribute'
+
+# === dir ===
+names = dir(point)
+assert 'x' in names, 'dir() has the fields'
+assert 'y' in names, 'dir() has the fields'
+assert names == sorted(names), 'dir() is sorted'
+
+# === vars ===
+assert vars(point) == {'x': 1, 'y': 2}, 'vars() of a dataclass'
+
+# === setattr ===
+setattr(mutable, 'x', 10)
+assert mutable.x == 10, 'setattr on a field'
+
+label = 'la' + 'bel'
+setattr(mutable, label, 'p')
+assert mutable.label == 'p', 'setattr with a runtime name'
+assert getattr(mutable, label) == 'p', 'getattr with a runtime name'
+assert hasattr(mutable, 'label'), 'hasattr after setattr'
+assert vars(mutable) == {'x': 10, 'y': 2, 'label': 'p'}, 'vars() after setattr'
+
+try:
+    setattr(point, 'x', 5)
+    assert False, 'setattr() on a frozen dataclass should raise'
+except AttributeError as e:
+    assert str(e) == "cannot assign to field 'x'", str(e)
+
+# === delattr ===
+delattr(mutable, 'label')
+assert not hasattr(mutable, 'label'), 'hasattr after delattr'
+
+try:
+    delattr(mutable, 'label')
+    assert False, 'delattr() of a missing attribute should raise'
+except AttributeError as e:
+    assert str(e) == "'MutablePoint' object has no attribute 'label'", str(e)
+
+try:
+    delattr(point, 'x')
+    assert False, 'delattr() on a frozen dataclass should raise'
+except AttributeError as e:
+    assert str(e) == "cannot delete field 'x'", str(e)
+
+# === callable ===
+assert callable(add_ints), 'host function is callable'
+assert not callable(point), 'dataclass instance is not callable'
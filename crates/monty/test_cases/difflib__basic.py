@@ -0,0 +1,109 @@
+# Tests for the difflib module
+
+import difflib
+from difflib import SequenceMatcher
+
+# === SequenceMatcher ratios ===
+s = SequenceMatcher(None, 'abcd', 'bcde')
+assert s.ratio() == 0.75, f'ratio: {s.ratio()}'
+assert s.quick_ratio() == 0.75, 'quick_ratio'
+assert s.real_quick_ratio() == 1.0, 'real_quick_ratio'
+assert SequenceMatcher(None, '', '').ratio() == 1.0, 'empty sequences'
+assert SequenceMatcher(None, 'abc', 'xyz').ratio() == 0.0, 'no match'
+assert SequenceMatcher(a='hello', b='hello').ratio() == 1.0, 'keyword arguments'
+assert SequenceMatcher().ratio() == 1.0, 'default sequences'
+assert round(SequenceMatcher(None, 'private', 'privacy').ratio(), 4) == 0.7143, 'rounded ratio'
+
+# === matching blocks ===
+s = SequenceMatcher(None, 'abxcd', 'abcd')
+blocks = s.get_matching_blocks()
+assert blocks == [(0, 0, 2), (3, 2, 2), (5, 4, 0)], f'blocks: {blocks}'
+assert blocks[0].a == 0 and blocks[0].b == 0 and blocks[0].size == 2, 'Match fields'
+m = SequenceMatcher(None, ' abcd', 'abcd abcd').find_longest_match(0, 5, 0, 9)
+assert m == (0, 4, 5), f'find_longest_match: {m}'
+assert SequenceMatcher(None, 'xabc', 'abc').find_longest_match() == (1, 0, 3), 'find_longest_match defaults'
+
+# === opcodes ===
+s = SequenceMatcher(None, 'qabxcd', 'abycdf')
+assert s.get_opcodes() == [
+    ('delete', 0, 1, 0, 0),
+    ('equal', 1, 3, 0, 2),
+    ('replace', 3, 4, 2, 3),
+    ('equal', 4, 6, 3, 5),
+    ('insert', 6, 6, 5, 6),
+], f'opcodes: {s.get_opcodes()}'
+
+a = [str(i) for i in range(1, 40)]
+b = a[:]
+b[8:8] = ['i']
+b[30] = '30x'
+groups = list(SequenceMatcher(None, a, b).get_grouped_opcodes())
+assert groups == [
+    [('equal', 5, 8, 5, 8), ('insert', 8, 8, 8, 9), ('equal', 8, 11, 9, 12)],
+    [('equal', 26, 29, 27, 30), ('replace', 29, 30, 30, 31), ('equal', 30, 33, 31, 34)],
+], f'grouped opcodes: {groups}'
+
+# === isjunk and autojunk ===
+s = SequenceMatcher(lambda x: x == ' ', 'private Thread currentThread;', 'private volatile Thread currentThread;')
+assert round(s.ratio(), 3) == 0.866, f'isjunk ratio: {s.ratio()}'
+popular = 'a' * 100 + 'b' * 100
+assert SequenceMatcher(None, 'ba', popular).ratio() == 0.0, 'autojunk ignores popular elements'
+assert SequenceMatcher(None, 'ba', popular, autojunk=False).ratio() == 1 / 101, 'autojunk disabled'
+assert SequenceMatcher(None, 'ab', popular).ratio() == 1 / 101, 'popular elements extend matches'
+
+# === line sequences ===
+s = SequenceMatcher(None, ['one\n', 'two\n', 'three\n'], ('one\n', 'three\n'))
+assert s.get_opcodes() == [('equal', 0, 1, 0, 1), ('delete', 1, 2, 1, 1), ('equal', 2, 3, 1, 2)], 'line opcodes'
+
+# === unified_diff ===
+diff = list(
+    difflib.unified_diff(
+        'one two three four'.split(),
+        'zero one tree four'.split(),
+        'Original',
+        'Current',
+        '2005-01-26 23:30:50',
+        '2010-04-02 10:20:52',
+        lineterm='',
+    )
+)
+assert diff == [
+    '--- Original\t2005-01-26 23:30:50',
+    '+++ Current\t2010-04-02 10:20:52',
+    '@@ -1,4 +1,4 @@',
+    '+zero',
+    ' one',
+    '-two',
+    '-three',
+    '+tree',
+    ' four',
+], f'unified_diff: {diff}'
+
+before = ['a\n', 'b\n', 'c\n']
+after = ['a\n', 'B\n', 'c\n']
+diff = list(difflib.unified_diff(before, after, fromfile='x.txt', tofile='y.txt'))
+assert ''.join(diff) == '--- x.txt\n+++ y.txt\n@@ -1,3 +1,3 @@\n a\n-b\n+B\n c\n', f'unified_diff text: {diff}'
+assert list(difflib.unified_diff(before, before)) == [], 'no differences'
+diff = list(difflib.unified_diff([], ['new\n']))
+assert diff == ['--- \n', '+++ \n', '@@ -0,0 +1 @@\n', '+new\n'], f'insert into empty: {diff}'
+lines = [f'{i}\n' for i in range(20)]
+changed = lines[:]
+changed[10] = 'ten\n'
+diff = list(difflib.unified_diff(lines, changed, n=1))
+assert diff[2:] == ['@@ -10,3 +10,3 @@\n', ' 9\n', '-10\n', '+ten\n', ' 11\n'], f'context lines: {diff}'
+
+# === get_close_matches ===
+assert difflib.get_close_matches('appel', ['ape', 'apple', 'peach', 'puppy']) == ['apple', 'ape'], 'close matches'
+assert difflib.get_close_matches('wheel', ['while', 'for', 'with']) == ['while'], 'close match'
+assert difflib.get_close_matches('xyz', ['abc']) == [], 'no close matches'
+assert difflib.get_close_matches('ab', ['ab', 'abc', 'abd'], n=2) == ['ab', 'abd'], 'n and tie order'
+try:
+    difflib.get_close_matches('a', ['a'], n=0)
+    assert False, 'n=0 should raise'
+except ValueError as e:
+    assert str(e) == 'n must be > 0: 0', str(e)
+try:
+    difflib.get_close_matches('a', ['a'], cutoff=1.5)
+    assert False, 'bad cutoff should raise'
+except ValueError as e:
+    assert str(e) == 'cutoff must be in [0.0, 1.0]: 1.5', str(e)
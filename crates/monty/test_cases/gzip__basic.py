@@ -0,0 +1,79 @@
+# Tests for the gzip module
+
+import gzip
+import zlib
+
+# === round trips ===
+data = b'hello world ' * 100
+assert gzip.decompress(gzip.compress(data)) == data, 'gzip round trip'
+assert gzip.decompress(gzip.compress(b'')) == b'', 'empty round trip'
+assert gzip.decompress(gzip.compress(data, 1)) == data, 'round trip at level 1'
+assert gzip.decompress(gzip.compress(data, compresslevel=0)) == data, 'round trip without compression'
+assert gzip.decompress(b'') == b'', 'empty input has no members'
+
+# === header ===
+compressed = gzip.compress(b'hello', mtime=1234)
+assert compressed[:4] == b'\x1f\x8b\x08\x00', 'magic, method and flags'
+assert compressed[4:8] == b'\xd2\x04\x00\x00', 'mtime is little-endian'
+assert compressed[8:10] == b'\x02\xff', 'extra flags for level 9 and unknown OS'
+assert gzip.compress(b'hello', mtime=0)[4:8] == b'\x00\x00\x00\x00', 'explicit zero mtime'
+
+# === decompressing CPython output ===
+cpython = b'\x1f\x8b\x08\x00\x00\x00\x00\x00\x02\xff\xcbH\xcd\xc9\xc9\x07\x00\x86\xa6\x106\x05\x00\x00\x00'
+assert gzip.decompress(cpython) == b'hello', 'decompress CPython output'
+assert gzip.decompress(cpython + cpython) == b'hellohello', 'multiple members are concatenated'
+assert gzip.decompress(cpython + b'\x00\x00' + cpython) == b'hellohello', 'zero padding between members'
+assert zlib.decompress(gzip.compress(data), 31) == data, 'zlib reads gzip output'
+
+# name field (FNAME) is skipped
+named = b'\x1f\x8b\x08\x08\x00\x00\x00\x00\x02\xffa.txt\x00' + cpython[10:]
+assert gzip.decompress(named) == b'hello', 'header with a file name'
+
+# === errors ===
+try:
+    gzip.decompress(b'hello')
+    assert False, 'bad magic should raise'
+except gzip.BadGzipFile as e:
+    assert str(e) == "Not a gzipped file (b'he')", 'magic error message'
+
+try:
+    gzip.decompress(cpython + b'x')
+    assert False, 'garbage after a member should raise'
+except OSError as e:
+    assert str(e) == "Not a gzipped file (b'x')", 'BadGzipFile is an OSError'
+
+try:
+    gzip.decompress(cpython[:-3])
+    assert False, 'truncated trailer should raise'
+except EOFError as e:
+    assert str(e) == 'Compressed file ended before the end-of-stream marker was reached', 'truncated message'
+
+try:
+    gzip.decompress(cpython[:15])
+    assert False, 'truncated stream should raise'
+except EOFError as e:
+    assert str(e) == 'Compressed file ended before the end-of-stream marker was reached', 'truncated stream'
+
+try:
+    gzip.decompress(cpython[:-8] + b'\x00\x00\x00\x00\x05\x00\x00\x00')
+    assert False, 'bad crc should raise'
+except gzip.BadGzipFile as e:
+    assert str(e) == 'CRC check failed', 'crc error message'
+
+try:
+    gzip.decompress(cpython[:-4] + b'\x06\x00\x00\x00')
+    assert False, 'bad length should raise'
+except gzip.BadGzipFile as e:
+    assert str(e) == 'Incorrect length of data produced', 'length error message'
+
+try:
+    gzip.decompress(b'\x1f\x8b\x07' + cpython[3:])
+    assert False, 'unknown method should raise'
+except gzip.BadGzipFile as e:
+    assert str(e) == 'Unknown compression method', 'method error message'
+
+try:
+    gzip.compress(b'a', 12)
+    assert False, 'bad level should raise'
+except zlib.error as e:
+    assert str(e) == 'Bad compression level', 'level error message'
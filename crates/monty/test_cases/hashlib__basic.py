@@ -0,0 +1,83 @@
+# Tests for the hashlib module
+
+import hashlib
+
+# === one-shot digests ===
+assert hashlib.md5(b'abc').hexdigest() == '900150983cd24fb0d6963f7d28e17f72', 'md5'
+assert hashlib.sha1(b'abc').hexdigest() == 'a9993e364706816aba3e25717850c26c9cd0d89d', 'sha1'
+assert (
+    hashlib.sha256(b'abc').hexdigest() == 'ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad'
+), 'sha256'
+assert hashlib.sha224(b'').hexdigest() == 'd14a028c2a3a2bc9476102bb288234c415a2b01f828ea62ac5b3e42f', 'sha224 empty'
+assert hashlib.sha512(b'abc').hexdigest() == (
+    'ddaf35a193617abacc417349ae20413112e6fa4e89a97ea20a9eeee64b55d39a'
+    '2192992a274fc1a836ba3c23a3feebbd454d4423643ce80e2a9ac94fa54ca49f'
+), 'sha512'
+assert hashlib.sha384(b'abc').hexdigest().startswith('cb00753f45a35e8b'), 'sha384'
+assert hashlib.blake2b(b'abc', digest_size=16).hexdigest() == 'cf4ab791c62b8d2b2109c90275287816', 'blake2b digest_size'
+
+# === incremental updates ===
+h = hashlib.sha256()
+h.update(b'a')
+h.update(b'bc')
+assert h.digest() == hashlib.sha256(b'abc').digest(), 'update is incremental'
+assert len(h.digest()) == 32, 'sha256 digest is 32 bytes'
+
+c = h.copy()
+c.update(b'd')
+assert c.hexdigest() == hashlib.sha256(b'abcd').hexdigest(), 'copy continues independently'
+assert h.hexdigest() == hashlib.sha256(b'abc').hexdigest(), 'original unchanged by copy update'
+
+# === attributes ===
+assert h.name == 'sha256', 'name attribute'
+assert h.digest_size == 32, 'digest_size attribute'
+assert h.block_size == 64, 'block_size attribute'
+assert hashlib.sha512().block_size == 128, 'sha512 block_size'
+assert hashlib.blake2b().digest_size == 64, 'blake2b default digest_size'
+
+# === hashlib.new ===
+assert hashlib.new('md5', b'abc').hexdigest() == hashlib.md5(b'abc').hexdigest(), 'new by name'
+assert hashlib.new('SHA256').name == 'sha256', 'new accepts uppercase names'
+
+try:
+    hashlib.new('nope')
+    assert False, 'unknown algorithm should raise'
+except ValueError as e:
+    assert str(e) == 'unsupported hash type nope', 'unknown algorithm message'
+
+# === errors ===
+try:
+    hashlib.sha256('abc')
+    assert False, 'str data should raise'
+except TypeError as e:
+    assert str(e) == 'Strings must be encoded before hashing', 'str data message'
+
+try:
+    hashlib.blake2b(digest_size=65)
+    assert False, 'digest_size out of range should raise'
+except ValueError:
+    pass
+
+try:
+    hashlib.blake2b(b'abc', 16)
+    assert False, 'positional digest_size should raise'
+except TypeError as e:
+    assert str(e) == 'blake2b() takes at most 1 positional argument (2 given)', str(e)
+
+try:
+    hashlib.blake2b(b'abc', 16, b'key')
+    assert False, 'positional key should raise'
+except TypeError as e:
+    assert str(e) == 'blake2b() takes at most 1 positional argument (3 given)', str(e)
+
+try:
+    hashlib.sha256(b'abc', True)
+    assert False, 'positional usedforsecurity should raise'
+except TypeError:
+    pass
+
+try:
+    hashlib.new('blake2b', b'abc', 16)
+    assert False, 'positional digest_size for new() should raise'
+except TypeError:
+    pass
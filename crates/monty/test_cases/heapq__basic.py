@@ -0,0 +1,92 @@
+# Tests for the heapq module
+
+import heapq
+
+# === heappush / heappop ===
+h = []
+for x in [5, 1, 8, 3, 2, 9, 1]:
+    heapq.heappush(h, x)
+assert h == [1, 2, 1, 5, 3, 9, 8], f'heap layout after pushes: {h}'
+assert h[0] == 1, 'smallest item at index 0'
+popped = [heapq.heappop(h) for _ in range(7)]
+assert popped == [1, 1, 2, 3, 5, 8, 9], f'pops come out sorted: {popped}'
+assert h == [], 'heap emptied'
+
+h = []
+heapq.heappush(h, (2, 'b'))
+heapq.heappush(h, (1, 'a'))
+heapq.heappush(h, (3, 'c'))
+assert heapq.heappop(h) == (1, 'a'), 'tuples compare element-wise'
+assert h == [(2, 'b'), (3, 'c')], f'remaining tuples: {h}'
+
+# === heapify ===
+data = [9, 4, 7, 1, 8, 2, 6, 3, 5, 0]
+heapq.heapify(data)
+assert data == [0, 1, 2, 3, 4, 7, 6, 9, 5, 8], f'heapify layout: {data}'
+assert heapq.heapify([]) is None, 'heapify returns None'
+words = ['pear', 'apple', 'fig']
+heapq.heapify(words)
+assert words[0] == 'apple', 'heapify strings'
+
+# === heapreplace / heappushpop ===
+h = [1, 3, 5]
+assert heapq.heapreplace(h, 4) == 1, 'heapreplace returns old smallest'
+assert h == [3, 4, 5], f'heapreplace layout: {h}'
+assert heapq.heapreplace(h, 0) == 3, 'heapreplace pops before pushing'
+assert h == [0, 4, 5], f'heapreplace smaller item: {h}'
+assert heapq.heappushpop(h, -1) == -1, 'heappushpop returns smaller new item'
+assert h == [0, 4, 5], 'heap unchanged when new item is smallest'
+assert heapq.heappushpop(h, 7) == 0, 'heappushpop returns old smallest'
+assert h == [4, 7, 5], f'heappushpop layout: {h}'
+assert heapq.heappushpop([], 3) == 3, 'heappushpop on empty heap'
+
+# === nlargest / nsmallest ===
+nums = [5, 1, 8, 3, 2, 9, 1]
+assert heapq.nlargest(3, nums) == [9, 8, 5], 'nlargest'
+assert heapq.nsmallest(3, nums) == [1, 1, 2], 'nsmallest'
+assert heapq.nsmallest(0, nums) == [], 'n of zero'
+assert heapq.nlargest(-2, nums) == [], 'negative n'
+assert heapq.nsmallest(10, nums) == [1, 1, 2, 3, 5, 8, 9], 'n larger than input'
+assert heapq.nlargest(2, 'hello') == ['o', 'l'], 'any iterable'
+assert heapq.nlargest(2, ['bb', 'a', 'ccc'], key=len) == ['ccc', 'bb'], 'nlargest key'
+assert heapq.nsmallest(2, ['bb', 'a', 'ccc'], key=len) == ['a', 'bb'], 'nsmallest key'
+pairs = [('a', 2), ('b', 1), ('c', 2)]
+assert heapq.nlargest(2, pairs, key=lambda p: p[1]) == [('a', 2), ('c', 2)], 'ties keep input order'
+assert heapq.nsmallest(1, [], key=None) == [], 'empty input'
+
+# === merge ===
+assert list(heapq.merge([1, 4, 7], [2, 5, 8], [3, 6, 9])) == [1, 2, 3, 4, 5, 6, 7, 8, 9], 'merge'
+assert list(heapq.merge([1, 3], [], [2])) == [1, 2, 3], 'merge with empty input'
+assert list(heapq.merge()) == [], 'merge of nothing'
+assert list(heapq.merge([5, 3, 1], [4, 2], reverse=True)) == [5, 4, 3, 2, 1], 'merge reverse'
+merged = list(heapq.merge(['a', 'ccc'], ['bb', 'dddd'], key=len))
+assert merged == ['a', 'bb', 'ccc', 'dddd'], f'merge key: {merged}'
+merged = list(heapq.merge([(1, 'x')], [(1, 'y')], key=lambda p: p[0]))
+assert merged == [(1, 'x'), (1, 'y')], 'merge is stable'
+
+# === errors ===
+try:
+    heapq.heappop([])
+    assert False, 'heappop on empty heap should raise'
+except IndexError as e:
+    assert str(e) == 'index out of range', f'heappop message: {e}'
+try:
+    heapq.heapreplace([], 1)
+    assert False, 'heapreplace on empty heap should raise'
+except IndexError as e:
+    assert str(e) == 'index out of range', f'heapreplace message: {e}'
+try:
+    heapq.heappush((1, 2), 3)
+    assert False, 'heappush on tuple should raise'
+except TypeError as e:
+    assert str(e) == 'heappush() argument 1 must be list, not tuple', f'heappush message: {e}'
+try:
+    heapq.heapify({1: 2})
+    assert False, 'heapify on dict should raise'
+except TypeError as e:
+    assert str(e) == 'heapify() argument must be list, not dict', f'heapify message: {e}'
+try:
+    heapq.heapify([1, 'a', 2])
+    assert False, 'mixed types should raise'
+except TypeError as e:
+    assert str(e) == "'<' not supported between instances of 'str' and 'int'", f'compare message: {e}'
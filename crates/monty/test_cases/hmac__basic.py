@@ -0,0 +1,46 @@
+# Tests for the hmac module
+
+import hashlib
+import hmac
+
+# === hmac.new ===
+h = hmac.new(b'key', b'The quick brown fox jumps over the lazy dog', 'sha256')
+assert h.hexdigest() == 'f7bc83f430538424b13298e6aa6fb143ef4d59a14946175997479dbc2d1a3cd8', 'hmac sha256'
+assert h.name == 'hmac-sha256', 'hmac name'
+assert h.digest_size == 32, 'hmac digest_size'
+
+h = hmac.new(b'key', digestmod=hashlib.md5)
+h.update(b'The quick brown fox jumps over the lazy dog')
+assert h.hexdigest() == '80070713463e7749b90c2dc24911e275', 'hmac md5 via constructor and update'
+
+# keys longer than the block size are hashed first
+long_key = b'k' * 200
+assert hmac.new(long_key, b'msg', 'sha1').digest() == hmac.new(
+    hashlib.sha1(long_key).digest(), b'msg', 'sha1'
+).digest(), 'long keys are hashed'
+
+# === hmac.digest ===
+assert hmac.digest(b'key', b'msg', 'sha256') == hmac.new(b'key', b'msg', 'sha256').digest(), 'hmac.digest'
+
+# === compare_digest ===
+assert hmac.compare_digest(b'abc', b'abc'), 'equal bytes'
+assert not hmac.compare_digest(b'abc', b'abd'), 'different bytes'
+assert not hmac.compare_digest(b'abc', b'ab'), 'different lengths'
+assert not hmac.compare_digest(b'abcabc', b'abc'), 'longer first input sharing a prefix'
+assert not hmac.compare_digest(b'abc', b'abcabc'), 'longer second input sharing a prefix'
+assert not hmac.compare_digest(b'', b'abc'), 'empty first input'
+assert not hmac.compare_digest('abcabc', 'abc'), 'strings of different lengths sharing a prefix'
+assert hmac.compare_digest(b'', b''), 'empty inputs'
+assert hmac.compare_digest('abc', 'abc'), 'equal ASCII strings'
+
+try:
+    hmac.compare_digest('abc', b'abc')
+    assert False, 'mixed str and bytes should raise'
+except TypeError:
+    pass
+
+try:
+    hmac.new(b'key')
+    assert False, 'missing digestmod should raise'
+except TypeError:
+    pass
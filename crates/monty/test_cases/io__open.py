@@ -0,0 +1,218 @@
+# call-external
+import csv
+import io
+from pathlib import Path
+
+# === reading text ===
+f = open('/virtual/file.txt')
+assert repr(f) == "<_io.TextIOWrapper name='/virtual/file.txt' mode='r' encoding='utf-8'>", 'text repr'
+assert f.name == '/virtual/file.txt', 'name'
+assert f.mode == 'r', 'mode'
+assert f.encoding == 'utf-8', 'encoding'
+assert f.closed == False, 'not closed'
+assert f.readable() == True, 'readable'
+assert f.writable() == False, 'not writable'
+assert f.read(5) == 'hello', 'read(n)'
+assert f.tell() == 5, 'tell after read(n)'
+assert f.read() == ' world\n', 'read rest'
+assert f.read() == '', 'read at end'
+assert f.seek(0) == 0, 'seek to start'
+assert f.readline() == 'hello world\n', 'readline'
+assert f.readline() == '', 'readline at end'
+assert hasattr(f, 'readline'), 'file has readline'
+assert 'seek' in dir(f), 'dir(file) has seek'
+f.close()
+assert f.closed == True, 'closed'
+f.close()  # closing twice is fine
+
+try:
+    f.read()
+    assert False, 'read on closed file should fail'
+except ValueError as e:
+    assert str(e) == 'I/O operation on closed file.', 'closed error'
+
+# === writing text ===
+with open('/virtual/lines.txt', 'w') as f:
+    assert f.mode == 'w', 'write mode'
+    assert f.write('one\n') == 4, 'write returns length'
+    f.writelines(['two\n', 'three\n'])
+    try:
+        f.read()
+        assert False, 'read on write-only file should fail'
+    except io.UnsupportedOperation as e:
+        assert str(e) == 'not readable', 'not readable'
+assert f.closed, 'with closes the file'
+assert Path('/virtual/lines.txt').read_text() == 'one\ntwo\nthree\n', 'contents written'
+
+# === iterating over lines ===
+lines = []
+with open('/virtual/lines.txt') as f:
+    for line in f:
+        lines.append(line)
+assert lines == ['one\n', 'two\n', 'three\n'], 'for loop over file'
+
+with open('/virtual/lines.txt') as f:
+    assert [line.strip() for line in f] == ['one', 'two', 'three'], 'comprehension over file'
+
+with open('/virtual/lines.txt') as f:
+    assert f.readline() == 'one\n', 'readline before loop'
+    assert f.readlines() == ['two\n', 'three\n'], 'readlines rest'
+
+with open('/virtual/lines.txt') as f:
+    for line in f:
+        if line == 'two\n':
+            break
+    assert f.readline() == 'three\n', 'loop stops after break'
+
+# === builtins iterating over lines ===
+with open('/virtual/lines.txt') as f:
+    assert list(f) == ['one\n', 'two\n', 'three\n'], 'list() of file'
+    assert list(f) == [], 'list() at end of file'
+
+with open('/virtual/lines.txt') as f:
+    assert list(enumerate(f, 1)) == [(1, 'one\n'), (2, 'two\n'), (3, 'three\n')], 'enumerate() of file'
+
+with open('/virtual/lines.txt') as f:
+    assert f.readline() == 'one\n', 'readline before sorted()'
+    assert sorted(f) == ['three\n', 'two\n'], 'sorted() of the rest of a file'
+
+with open('/virtual/lines.txt') as f:
+    assert '|'.join(f) == 'one\n|two\n|three\n', 'str.join() of file'
+
+with open('/virtual/lines.txt') as f, open('/virtual/lines.txt') as g:
+    assert g.readline() == 'one\n', 'readline before zip()'
+    assert list(zip(f, g)) == [('one\n', 'two\n'), ('two\n', 'three\n')], 'zip() of two files'
+
+with open('/virtual/lines.txt') as f:
+    assert list(zip(f, f)) == [('one\n', 'two\n')], 'zip() of a file with itself'
+
+with open('/virtual/lines.txt') as f:
+    assert max(f, key=len) == 'three\n', 'max() with a key'
+
+with open('/virtual/data.bin', 'rb') as f:
+    assert list(f) == [b'\x00\x01\x02\x03'], 'list() of binary file'
+
+# === iter(f.readline, sentinel) ===
+with open('/virtual/lines.txt') as f:
+    lines = []
+    for line in iter(f.readline, ''):
+        lines.append(line)
+assert lines == ['one\n', 'two\n', 'three\n'], 'for loop over iter(f.readline, sentinel)'
+
+with open('/virtual/lines.txt') as f:
+    assert [line for line in iter(f.readline, 'two\n')] == ['one\n'], 'comprehension stops at the sentinel'
+    assert f.readline() == 'three\n', 'the line after the sentinel is left unread'
+
+with open('/virtual/lines.txt', 'a') as f:
+    try:
+        list(f)
+        assert False, 'list() of write-only file should fail'
+    except io.UnsupportedOperation as e:
+        assert str(e) == 'not readable', 'list() not readable'
+
+with open('/virtual/rows.csv', 'w') as f:
+    f.write('a,b\n1,2\n3,4\n')
+with open('/virtual/rows.csv', newline='') as f:
+    assert list(csv.reader(f)) == [['a', 'b'], ['1', '2'], ['3', '4']], 'csv.reader() of file'
+with open('/virtual/rows.csv', newline='') as f:
+    assert [row['b'] for row in csv.DictReader(f)] == ['2', '4'], 'csv.DictReader() of file'
+
+# === appending ===
+with Path('/virtual/lines.txt').open('a') as f:
+    f.write('four\n')
+assert Path('/virtual/lines.txt').read_text() == 'one\ntwo\nthree\nfour\n', 'append'
+
+# === read and write ===
+with open('/virtual/lines.txt', 'r+') as f:
+    assert f.read(3) == 'one', 'r+ read'
+    f.seek(0)
+    f.write('ONE')
+assert Path('/virtual/lines.txt').read_text() == 'ONE\ntwo\nthree\nfour\n', 'r+ overwrite'
+
+# === binary files ===
+with open('/virtual/data.bin', 'rb') as f:
+    assert repr(f) == "<_io.BufferedReader name='/virtual/data.bin'>", 'binary repr'
+    assert f.mode == 'rb', 'binary mode'
+    assert f.read(2) == b'\x00\x01', 'binary read(n)'
+    assert f.seek(-1, 2) == 3, 'seek from end'
+    assert f.read() == b'\x03', 'binary read rest'
+
+with Path('/virtual/out.bin').open('wb') as f:
+    assert repr(f) == "<_io.BufferedWriter name='/virtual/out.bin'>", 'binary writer repr'
+    assert f.write(b'abc\ndef') == 7, 'binary write'
+assert Path('/virtual/out.bin').read_bytes() == b'abc\ndef', 'binary contents'
+
+with open('/virtual/out.bin', 'w+b') as f:
+    assert repr(f) == "<_io.BufferedRandom name='/virtual/out.bin'>", 'binary random repr'
+    assert f.mode == 'rb+', 'w+b mode'
+    f.write(b'xy\nz')
+    f.seek(0)
+    assert f.readlines() == [b'xy\n', b'z'], 'binary readlines'
+
+# === exclusive creation ===
+with open('/virtual/new.txt', 'x') as f:
+    f.write('new')
+try:
+    open('/virtual/new.txt', 'x')
+    assert False, 'x mode should fail for existing file'
+except FileExistsError as e:
+    assert str(e) == "[Errno 17] File exists: '/virtual/new.txt'", 'file exists'
+
+# === errors ===
+try:
+    open('/nonexistent/file.txt')
+    assert False, 'missing file should fail'
+except FileNotFoundError as e:
+    assert str(e) == "[Errno 2] No such file or directory: '/nonexistent/file.txt'", 'missing file'
+
+try:
+    open('/virtual/file.txt', 'rw')
+    assert False, 'bad mode should fail'
+except ValueError as e:
+    assert str(e) == 'must have exactly one of create/read/write/append mode', 'bad mode'
+
+try:
+    open('/virtual/file.txt', 'rb', encoding='utf-8')
+    assert False, 'binary with encoding should fail'
+except ValueError as e:
+    assert str(e) == "binary mode doesn't take an encoding argument", 'binary encoding'
+
+try:
+    open('/virtual/file.txt', 'rt', buffering=0)
+    assert False, 'unbuffered text should fail'
+except ValueError as e:
+    assert str(e) == "can't have unbuffered text I/O", 'unbuffered text'
+
+with open('/virtual/file.txt') as f:
+    try:
+        f.write('x')
+        assert False, 'write on read-only file should fail'
+    except io.UnsupportedOperation as e:
+        assert str(e) == 'not writable', 'not writable'
+    try:
+        f.seek(1, 1)
+        assert False, 'relative seek in text file should fail'
+    except io.UnsupportedOperation as e:
+        assert str(e) == "can't do nonzero cur-relative seeks", 'text relative seek'
+
+with open('/virtual/data.bin', 'rb') as f:
+    try:
+        f.write(b'x')
+        assert False, 'write on binary reader should fail'
+    except io.UnsupportedOperation as e:
+        assert str(e) == 'write', 'binary not writable'
+
+with open('/virtual/out.bin', 'wb') as f:
+    try:
+        f.write('text')
+        assert False, 'str to binary file should fail'
+    except TypeError as e:
+        assert str(e) == "a bytes-like object is required, not 'str'", 'binary write str'
+
+# === exceptions propagate through with ===
+try:
+    with open('/virtual/file.txt') as f:
+        raise KeyError('boom')
+except KeyError:
+    pass
+assert f.closed, 'with closes the file on error'
@@ -0,0 +1,104 @@
+# Tests for io.StringIO
+
+import io
+from io import StringIO
+
+# === writing and getvalue ===
+buf = StringIO()
+assert buf.write('hello') == 5, 'write returns chars written'
+assert buf.write(' wörld') == 6, 'write counts chars, not bytes'
+assert buf.getvalue() == 'hello wörld', f'getvalue: {buf.getvalue()!r}'
+assert buf.tell() == 11, f'tell after write: {buf.tell()}'
+buf.writelines(['\n', 'a', 'b'])
+assert buf.getvalue() == 'hello wörld\nab', 'writelines'
+assert type(buf) is io.StringIO, 'type is io.StringIO'
+
+# === reading ===
+buf = StringIO('line 1\nline 2\nline 3')
+assert buf.tell() == 0, 'initial value does not move the position'
+assert buf.readline() == 'line 1\n', 'readline'
+assert buf.read(4) == 'line', 'read with size'
+assert buf.read() == ' 2\nline 3', 'read rest'
+assert buf.read() == '', 'read at end'
+assert buf.readline() == '', 'readline at end'
+buf.seek(0)
+assert buf.readlines() == ['line 1\n', 'line 2\n', 'line 3'], 'readlines'
+buf.seek(0)
+assert buf.readline(3) == 'lin', 'readline with size'
+assert buf.read(-1) == 'e 1\nline 2\nline 3', 'read with negative size'
+assert StringIO(None).getvalue() == '', 'None initial value'
+assert StringIO(initial_value='x').read() == 'x', 'keyword initial value'
+
+# === seek, truncate and overwrite ===
+buf = StringIO('abcdef')
+assert buf.seek(2) == 2, 'seek returns new position'
+buf.write('XY')
+assert buf.getvalue() == 'abXYef', f'overwrite: {buf.getvalue()!r}'
+assert buf.seek(0, 2) == 6, 'seek relative to end'
+assert buf.seek(0, 1) == 6, 'seek relative to current'
+assert buf.truncate(3) == 3, 'truncate returns size'
+assert buf.getvalue() == 'abX', 'truncate shrinks buffer'
+assert buf.tell() == 6, 'truncate does not move position'
+buf.write('!')
+assert buf.getvalue() == 'abX\x00\x00\x00!', f'write past end pads with NUL: {buf.getvalue()!r}'
+buf.seek(1)
+buf.truncate()
+assert buf.getvalue() == 'a', 'truncate at current position'
+
+# === newline handling ===
+assert StringIO('a\r\nb\rc\n', newline='').readlines() == ['a\r\n', 'b\r', 'c\n'], 'universal newlines'
+assert StringIO('a\r\nb\rc\n').readlines() == ['a\r\n', 'b\rc\n'], 'default splits on \\n only'
+buf = StringIO('a\r\nb\rc', newline=None)
+assert buf.getvalue() == 'a\nb\nc', f'newline=None translates initial value: {buf.getvalue()!r}'
+buf.seek(0, 2)
+assert buf.write('\r\nd') == 3, 'write returns untranslated length'
+assert buf.getvalue() == 'a\nb\nc\nd', 'newline=None translates writes'
+buf = StringIO('x\ny', newline='\r\n')
+assert buf.getvalue() == 'x\r\ny', 'newline=\\r\\n translates \\n'
+assert buf.readlines() == ['x\r\n', 'y'], 'newline=\\r\\n splits on \\r\\n'
+buf = StringIO('x\ny\rz', newline='\r')
+assert buf.readlines() == ['x\r', 'y\r', 'z'], 'newline=\\r splits on \\r'
+
+# === closing ===
+buf = StringIO('data')
+assert not buf.closed, 'open buffer'
+buf.close()
+assert buf.closed, 'closed buffer'
+buf.close()
+try:
+    buf.read()
+    assert False, 'read on closed buffer should raise'
+except ValueError as e:
+    assert str(e) == 'I/O operation on closed file', f'closed message: {e}'
+try:
+    buf.getvalue()
+    assert False, 'getvalue on closed buffer should raise'
+except ValueError as e:
+    assert str(e) == 'I/O operation on closed file', f'closed getvalue message: {e}'
+
+# === errors ===
+try:
+    StringIO().write(1)
+    assert False, 'write of int should raise'
+except TypeError as e:
+    assert str(e) == "string argument expected, got 'int'", f'write type message: {e}'
+try:
+    StringIO(b'x')
+    assert False, 'bytes initial value should raise'
+except TypeError as e:
+    assert str(e) == "initial_value must be str or None, not bytes", f'initial value message: {e}'
+try:
+    StringIO(newline='x')
+    assert False, 'illegal newline should raise'
+except ValueError as e:
+    assert str(e) == "illegal newline value: 'x'", f'newline message: {e}'
+try:
+    StringIO().seek(-1)
+    assert False, 'negative seek should raise'
+except ValueError as e:
+    assert str(e) == 'Negative seek position -1', f'seek message: {e}'
+try:
+    StringIO('abc').seek(1, 1)
+    assert False, 'nonzero relative seek should raise'
+except OSError as e:
+    assert str(e) == "Can't do nonzero cur-relative seeks", f'relative seek message: {e}'
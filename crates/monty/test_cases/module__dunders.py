@@ -0,0 +1,57 @@
+"""Module docstring for the dunder tests."""
+
+# === __name__ ===
+assert __name__ == '__main__', '__name__ of the main module'
+
+ran_main = False
+if __name__ == '__main__':
+    ran_main = True
+assert ran_main, 'main guard runs'
+
+
+def get_name():
+    return __name__
+
+
+assert get_name() == '__main__', '__name__ read in a function'
+
+# === __file__ ===
+assert isinstance(__file__, str), '__file__ is a str'
+assert __file__.endswith('module__dunders.py'), '__file__ is the script name'
+
+# === __doc__ ===
+assert __doc__ == 'Module docstring for the dunder tests.', '__doc__ is the module docstring'
+
+
+def get_doc():
+    """Function docstring."""
+    return __doc__
+
+
+assert get_doc() == 'Module docstring for the dunder tests.', '__doc__ in a function is the module docstring'
+
+# === shadowing ===
+__name__ = 'renamed'
+assert __name__ == 'renamed', 'assigned __name__ shadows the default'
+assert get_name() == 'renamed', 'function defined before the assignment sees the new __name__'
+assert globals()['__name__'] == 'renamed', 'globals() has the assigned __name__'
+assert globals()['__file__'].endswith('module__dunders.py'), 'globals() has the unassigned __file__'
+
+
+def set_doc():
+    global __doc__
+    __doc__ = 'set in a function'
+
+
+assert get_doc() == 'Module docstring for the dunder tests.', '__doc__ keeps its default until assigned'
+set_doc()
+assert __doc__ == 'set in a function', '__doc__ assigned through a global declaration'
+assert get_doc() == 'set in a function', 'function sees __doc__ assigned through a global declaration'
+
+
+def local_doc():
+    __doc__ = 'local'
+    return __doc__
+
+
+assert local_doc() == 'local', 'local __doc__ shadows the default'
@@ -0,0 +1,126 @@
+# Tests for the operator module
+
+import operator
+import sys
+
+# === arithmetic ===
+assert operator.add(2, 3) == 5, 'add'
+assert operator.add('a', 'b') == 'ab', 'add strings'
+assert operator.sub(5, 7) == -2, 'sub'
+assert operator.mul(3, 4) == 12, 'mul'
+assert operator.mul('ab', 2) == 'abab', 'mul sequence'
+assert operator.truediv(7, 2) == 3.5, 'truediv'
+assert operator.floordiv(7, 2) == 3, 'floordiv'
+assert operator.mod(7, 3) == 1, 'mod'
+assert operator.pow(2, 10) == 1024, 'pow'
+assert operator.pow(2, 100) == 1267650600228229401496703205376, 'pow big int'
+assert operator.neg(5) == -5, 'neg'
+assert operator.pos(-5) == -5, 'pos'
+assert operator.abs(-3.5) == 3.5, 'abs'
+assert operator.invert(5) == -6, 'invert'
+assert operator.inv(0) == -1, 'inv'
+assert operator.lshift(1, 4) == 16, 'lshift'
+assert operator.rshift(16, 2) == 4, 'rshift'
+assert operator.and_(12, 10) == 8, 'and_'
+assert operator.or_(12, 10) == 14, 'or_'
+assert operator.xor(12, 10) == 6, 'xor'
+assert operator.or_({1}, {2}) == {1, 2}, 'or_ on sets'
+assert operator.index(7) == 7, 'index int'
+assert operator.index(True) == 1, 'index bool'
+
+# === comparisons and truth ===
+assert operator.eq(1, 1.0) is True, 'eq'
+assert operator.ne(1, 2) is True, 'ne'
+assert operator.lt(1, 2) is True, 'lt'
+assert operator.le(2, 2) is True, 'le'
+assert operator.gt('b', 'a') is True, 'gt'
+assert operator.ge(1, 2) is False, 'ge'
+assert operator.not_([]) is True, 'not_'
+assert operator.truth([0]) is True, 'truth'
+assert operator.truth('') is False, 'truth of empty string'
+x = [1]
+assert operator.is_(x, x) is True, 'is_'
+assert operator.is_not(x, [1]) is True, 'is_not'
+
+# === sequences ===
+assert operator.contains([1, 2, 3], 2) is True, 'contains'
+assert operator.contains('abc', 'z') is False, 'contains string'
+assert operator.getitem([10, 20, 30], 1) == 20, 'getitem'
+assert operator.getitem({'a': 1}, 'a') == 1, 'getitem dict'
+assert operator.concat([1], [2]) == [1, 2], 'concat'
+assert operator.concat('a', 'b') == 'ab', 'concat strings'
+assert operator.countOf([1, 2, 1, 1], 1) == 3, 'countOf'
+assert operator.indexOf('hello', 'l') == 2, 'indexOf'
+
+# === functional use ===
+assert sorted([3, 1, 2], key=operator.neg) == [3, 2, 1], 'function as sort key'
+assert list(map(operator.mul, [1, 2, 3], [4, 5, 6])) == [4, 10, 18], 'function with map'
+
+# === itemgetter ===
+first = operator.itemgetter(0)
+assert first([5, 6]) == 5, 'itemgetter single'
+assert first('xyz') == 'x', 'itemgetter string'
+assert operator.itemgetter(1, 0)('ab') == ('b', 'a'), 'itemgetter multiple'
+assert operator.itemgetter('k')({'k': 'v'}) == 'v', 'itemgetter dict key'
+assert operator.itemgetter(slice(1, 3))([1, 2, 3, 4]) == [2, 3], 'itemgetter slice'
+rows = [('b', 2), ('a', 3), ('c', 1)]
+assert sorted(rows, key=operator.itemgetter(1)) == [('c', 1), ('b', 2), ('a', 3)], 'itemgetter sort key'
+assert max(rows, key=operator.itemgetter(0)) == ('c', 1), 'itemgetter max key'
+assert repr(operator.itemgetter(1, 'a')) == "operator.itemgetter(1, 'a')", 'itemgetter repr'
+
+# === attrgetter ===
+assert operator.attrgetter('major')(sys.version_info) == sys.version_info.major, 'attrgetter'
+pair = operator.attrgetter('major', 'minor')(sys.version_info)
+assert pair == (sys.version_info.major, sys.version_info.minor), 'attrgetter multiple'
+assert operator.attrgetter('version_info.major')(sys) == sys.version_info.major, 'attrgetter dotted'
+assert repr(operator.attrgetter('a.b', 'c')) == "operator.attrgetter('a.b', 'c')", 'attrgetter repr'
+
+# === methodcaller ===
+assert operator.methodcaller('upper')('abc') == 'ABC', 'methodcaller'
+assert operator.methodcaller('count', 'a')('banana') == 3, 'methodcaller args'
+items = [3, 1, 2]
+operator.methodcaller('sort', reverse=True)(items)
+assert items == [3, 2, 1], 'methodcaller kwargs'
+assert repr(operator.methodcaller('m', 1, k=2)) == "operator.methodcaller('m', 1, k=2)", 'methodcaller repr'
+
+# === errors ===
+try:
+    operator.add(1, 'a')
+    assert False, 'add of int and str should raise'
+except TypeError as e:
+    assert str(e) == "unsupported operand type(s) for +: 'int' and 'str'", f'add message: {e}'
+try:
+    operator.neg('a')
+    assert False, 'neg of str should raise'
+except TypeError as e:
+    assert str(e) == "bad operand type for unary -: 'str'", f'neg message: {e}'
+try:
+    operator.index(1.5)
+    assert False, 'index of float should raise'
+except TypeError as e:
+    assert str(e) == "'float' object cannot be interpreted as an integer", f'index message: {e}'
+try:
+    operator.concat(1, 2)
+    assert False, 'concat of ints should raise'
+except TypeError as e:
+    assert str(e) == "'int' object can't be concatenated", f'concat message: {e}'
+try:
+    operator.indexOf([1, 2], 3)
+    assert False, 'indexOf of missing item should raise'
+except ValueError as e:
+    assert str(e) == 'sequence.index(x): x not in sequence', f'indexOf message: {e}'
+try:
+    operator.itemgetter(5)([1])
+    assert False, 'itemgetter out of range should raise'
+except IndexError as e:
+    assert str(e) == 'list index out of range', f'itemgetter message: {e}'
+try:
+    operator.attrgetter(1)
+    assert False, 'non-string attribute should raise'
+except TypeError as e:
+    assert str(e) == 'attribute name must be a string', f'attrgetter message: {e}'
+try:
+    operator.methodcaller()
+    assert False, 'methodcaller without a name should raise'
+except TypeError as e:
+    assert str(e) == 'methodcaller needs at least one argument, the method name', f'methodcaller message: {e}'
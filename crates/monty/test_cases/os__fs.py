@@ -0,0 +1,59 @@
+# call-external
+# Tests for the os and os.path functions that yield the same OS calls as Path methods
+import os
+import os.path
+from pathlib import Path
+
+# === os.path queries ===
+assert os.path.exists('/virtual/file.txt') == True, 'exists file'
+assert os.path.exists(Path('/virtual/subdir')) == True, 'exists directory given a Path'
+assert os.path.exists('/nonexistent') == False, 'exists missing'
+assert os.path.lexists('/virtual/file.txt') == True, 'lexists file'
+assert os.path.isfile('/virtual/file.txt') == True, 'isfile file'
+assert os.path.isfile('/virtual/subdir') == False, 'isfile directory'
+assert os.path.isdir('/virtual/subdir') == True, 'isdir directory'
+assert os.path.isdir('/virtual/file.txt') == False, 'isdir file'
+assert os.path.islink('/virtual/file.txt') == False, 'islink'
+assert os.path.getsize('/virtual/file.txt') == 12, 'getsize'
+assert os.path.getsize('/virtual/empty.txt') == 0, 'getsize empty file'
+assert os.path.getmtime('/virtual/file.txt') == 1_700_000_000.0, 'getmtime'
+assert os.path.realpath('/virtual/subdir/../file.txt') == '/virtual/file.txt', 'realpath'
+assert os.path.abspath('/virtual/subdir/..') == '/virtual', 'abspath absolute'
+assert os.path.abspath('a/./b') == 'a/b', 'abspath relative uses the host cwd'
+assert os.getcwd() == '.', 'getcwd'
+try:
+    os.path.getsize('/nonexistent')
+    assert False, 'getsize should raise for a missing file'
+except FileNotFoundError as e:
+    assert str(e) == "[Errno 2] No such file or directory: '/nonexistent'", str(e)
+
+# === os.listdir() and os.stat() ===
+assert sorted(os.listdir('/virtual')) == ['data.bin', 'empty.txt', 'file.txt', 'readonly.txt', 'subdir'], 'listdir'
+assert sorted(os.listdir(Path('/virtual/subdir'))) == ['deep', 'nested.txt'], 'listdir given a Path'
+assert os.stat('/virtual/file.txt').st_size == 12, 'stat'
+
+# === os.makedirs(), os.mkdir(), os.rmdir() ===
+os.makedirs('/virtual/a/b/c')
+assert os.path.isdir('/virtual/a/b/c'), 'makedirs creates parents'
+os.makedirs('/virtual/a/b/c', exist_ok=True)
+try:
+    os.makedirs('/virtual/a/b/c')
+    assert False, 'makedirs should raise for an existing directory'
+except OSError as e:
+    assert 'File exists' in str(e), str(e)
+os.mkdir('/virtual/made')
+assert os.path.isdir('/virtual/made'), 'mkdir'
+os.rmdir('/virtual/made')
+assert not os.path.exists('/virtual/made'), 'rmdir'
+
+# === os.rename(), os.replace(), os.remove(), os.unlink() ===
+Path('/virtual/one.txt').write_text('one')
+assert os.rename('/virtual/one.txt', '/virtual/two.txt') is None, 'rename returns None'
+assert Path('/virtual/two.txt').read_text() == 'one', 'rename moves the file'
+os.replace('/virtual/two.txt', Path('/virtual/three.txt'))
+assert os.path.exists('/virtual/three.txt') and not os.path.exists('/virtual/two.txt'), 'replace'
+os.remove('/virtual/three.txt')
+assert not os.path.exists('/virtual/three.txt'), 'remove'
+Path('/virtual/four.txt').write_text('four')
+os.unlink(os.path.join('/virtual', 'four.txt'))
+assert not os.path.isfile('/virtual/four.txt'), 'unlink'
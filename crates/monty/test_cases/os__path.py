@@ -0,0 +1,82 @@
+import os
+import os.path
+import posixpath
+from os.path import basename, join, splitext
+from pathlib import Path
+
+import os.path as osp
+
+# === module ===
+assert osp.join('a', 'b') == 'a/b', 'import os.path as binds the submodule'
+assert posixpath.join('a', 'b') == 'a/b', 'posixpath can be imported directly'
+assert os.sep == '/' and os.path.sep == '/', 'sep'
+assert os.curdir == '.' and os.pardir == '..', 'curdir and pardir'
+assert os.linesep == '\n', 'linesep'
+assert os.name == 'posix', 'name'
+
+# === join() ===
+assert join('a', 'b', 'c') == 'a/b/c', 'join parts'
+assert join('a/', 'b') == 'a/b', 'join keeps a single separator'
+assert join('a', '/b', 'c') == '/b/c', 'absolute part restarts the path'
+assert join('a', '', 'b', '') == 'a/b/', 'empty parts'
+assert join('', 'a') == 'a', 'empty first part'
+assert join(Path('a'), 'b') == 'a/b', 'join accepts Path'
+try:
+    join('a', 1)
+    assert False, 'join should reject int'
+except TypeError as e:
+    assert str(e) == "join() argument must be str, bytes, or os.PathLike object, not 'int'", str(e)
+
+# === split(), basename(), dirname() ===
+assert os.path.split('a/b/c.txt') == ('a/b', 'c.txt'), 'split'
+assert os.path.split('a/b/') == ('a/b', ''), 'split trailing slash'
+assert os.path.split('c.txt') == ('', 'c.txt'), 'split without directory'
+assert os.path.split('//x') == ('//', 'x'), 'split keeps root slashes'
+assert os.path.split('a//b') == ('a', 'b'), 'split strips repeated slashes'
+assert basename('/a/b.txt') == 'b.txt', 'basename'
+assert basename('/a/b/') == '', 'basename with trailing slash'
+assert os.path.dirname('/a/b.txt') == '/a', 'dirname'
+assert os.path.dirname('/a') == '/', 'dirname of top level'
+assert os.path.dirname('//a//b') == '//a', 'dirname strips slashes'
+assert os.path.dirname('b.txt') == '', 'dirname without directory'
+try:
+    basename(1)
+    assert False, 'basename should reject int'
+except TypeError as e:
+    assert str(e) == 'expected str, bytes or os.PathLike object, not int', str(e)
+
+# === splitext() ===
+assert splitext('a/b.tar.gz') == ('a/b.tar', '.gz'), 'splitext'
+assert splitext('a/b') == ('a/b', ''), 'splitext without extension'
+assert splitext('.bashrc') == ('.bashrc', ''), 'splitext hidden file'
+assert splitext('.a.b') == ('.a', '.b'), 'splitext hidden file with extension'
+assert splitext('a.d/b') == ('a.d/b', ''), 'splitext dot in directory'
+assert splitext('a/..') == ('a/..', ''), 'splitext dotdot'
+assert splitext('a/...b') == ('a/...b', ''), 'splitext leading dots'
+
+# === isabs(), normcase() ===
+assert os.path.isabs('/a') == True, 'isabs absolute'
+assert os.path.isabs('a/b') == False, 'isabs relative'
+assert os.path.normcase('A/b') == 'A/b', 'normcase is identity'
+
+# === normpath() ===
+assert os.path.normpath('a/./b/../c') == 'a/c', 'normpath'
+assert os.path.normpath('') == '.', 'normpath empty'
+assert os.path.normpath('a/..') == '.', 'normpath to current directory'
+assert os.path.normpath('//a//b/../c/.') == '//a/c', 'normpath keeps two leading slashes'
+assert os.path.normpath('///a') == '/a', 'normpath collapses three leading slashes'
+assert os.path.normpath('/../a') == '/a', 'normpath cannot go above root'
+assert os.path.normpath('../../a/..') == '../..', 'normpath keeps leading dotdot'
+assert os.path.normpath(Path('a/b/..')) == 'a', 'normpath accepts Path'
+
+# === abspath() of absolute paths ===
+assert os.path.abspath('/a/./b/../c') == '/a/c', 'abspath normalizes'
+
+# === fspath() ===
+assert os.fspath('a/b') == 'a/b', 'fspath str'
+assert os.fspath(Path('a/b')) == 'a/b', 'fspath Path'
+try:
+    os.fspath(1)
+    assert False, 'fspath should reject int'
+except TypeError as e:
+    assert str(e) == 'expected str, bytes or os.PathLike object, not int', str(e)
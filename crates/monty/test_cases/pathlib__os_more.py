@@ -0,0 +1,140 @@
+# call-external
+import os
+import shutil
+from pathlib import Path
+
+# === exists(follow_symlinks=False) ===
+assert Path('/virtual/file.txt').exists(follow_symlinks=False) == True, 'exists without following symlinks'
+assert Path('/nonexistent').exists(follow_symlinks=False) == False, 'missing without following symlinks'
+
+# === glob() ===
+names = sorted(p.name for p in Path('/virtual').glob('*.txt'))
+assert names == ['empty.txt', 'file.txt', 'readonly.txt'], 'glob in directory'
+assert sorted(str(p) for p in Path('/virtual').glob('subdir/*')) == [
+    '/virtual/subdir/deep',
+    '/virtual/subdir/nested.txt',
+], 'glob with directory component'
+assert sorted(str(p) for p in Path('/virtual').glob('**/*.txt')) == [
+    '/virtual/empty.txt',
+    '/virtual/file.txt',
+    '/virtual/readonly.txt',
+    '/virtual/subdir/deep/file.txt',
+    '/virtual/subdir/nested.txt',
+], 'glob with **'
+assert list(Path('/virtual').glob('*.nothing')) == [], 'glob without matches'
+assert list(Path('/nonexistent').glob('*')) == [], 'glob missing directory'
+
+# === rglob() ===
+assert sorted(str(p) for p in Path('/virtual/subdir').rglob('*.txt')) == [
+    '/virtual/subdir/deep/file.txt',
+    '/virtual/subdir/nested.txt',
+], 'rglob'
+assert sorted(p.name for p in Path('/virtual').rglob('file.t?t')) == ['file.txt', 'file.txt'], 'rglob with ?'
+
+# === touch() ===
+Path('/virtual/touched.txt').touch()
+assert Path('/virtual/touched.txt').read_text() == '', 'touch creates empty file'
+Path('/virtual/file.txt').touch()
+assert Path('/virtual/file.txt').read_text() == 'hello world\n', 'touch keeps existing contents'
+try:
+    Path('/virtual/file.txt').touch(exist_ok=False)
+    assert False, 'touch(exist_ok=False) should fail for existing file'
+except FileExistsError as e:
+    assert str(e) == "[Errno 17] File exists: '/virtual/file.txt'", 'touch exist_ok=False'
+try:
+    Path('/nonexistent/dir/file.txt').touch()
+    assert False, 'touch should fail without parent'
+except FileNotFoundError as e:
+    assert str(e) == "[Errno 2] No such file or directory: '/nonexistent/dir/file.txt'", 'touch missing parent'
+
+# === samefile() ===
+assert Path('/virtual/file.txt').samefile('/virtual/file.txt') == True, 'samefile same path'
+assert Path('/virtual/file.txt').samefile(Path('/virtual/data.bin')) == False, 'samefile different path'
+try:
+    Path('/virtual/file.txt').samefile('/nonexistent/file.txt')
+    assert False, 'samefile should fail for missing file'
+except FileNotFoundError as e:
+    assert str(e) == "[Errno 2] No such file or directory: '/nonexistent/file.txt'", 'samefile missing'
+
+# === os.walk() ===
+walked = [(dirpath, sorted(dirnames), sorted(filenames)) for dirpath, dirnames, filenames in os.walk('/virtual/subdir')]
+assert walked == [
+    ('/virtual/subdir', ['deep'], ['nested.txt']),
+    ('/virtual/subdir/deep', [], ['file.txt']),
+], 'os.walk top down'
+bottom_up = [dirpath for dirpath, _, _ in os.walk(Path('/virtual/subdir'), topdown=False)]
+assert bottom_up == ['/virtual/subdir/deep', '/virtual/subdir'], 'os.walk bottom up'
+assert list(os.walk('/nonexistent')) == [], 'os.walk missing directory'
+
+# === shutil.copy() ===
+assert shutil.copy('/virtual/file.txt', '/virtual/copy.txt') == '/virtual/copy.txt', 'copy returns destination'
+assert Path('/virtual/copy.txt').read_text() == 'hello world\n', 'copy contents'
+assert shutil.copy('/virtual/file.txt', '/virtual/subdir') == '/virtual/subdir/file.txt', 'copy into directory'
+assert Path('/virtual/subdir/file.txt').read_text() == 'hello world\n', 'copy into directory contents'
+try:
+    shutil.copy('/nonexistent/file.txt', '/virtual/copy.txt')
+    assert False, 'copy should fail for missing source'
+except FileNotFoundError as e:
+    assert str(e) == "[Errno 2] No such file or directory: '/nonexistent/file.txt'", 'copy missing source'
+
+# === shutil.copytree() ===
+assert shutil.copytree('/virtual/subdir', '/virtual/tree') == '/virtual/tree', 'copytree returns destination'
+assert Path('/virtual/tree/nested.txt').read_text() == 'nested content', 'copytree file'
+assert Path('/virtual/tree/deep/file.txt').read_text() == 'deep', 'copytree nested file'
+assert Path('/virtual/tree/deep').is_dir(), 'copytree nested directory'
+try:
+    shutil.copytree('/virtual/subdir', '/virtual/tree')
+    assert False, 'copytree should fail for existing destination'
+except FileExistsError as e:
+    assert str(e) == "[Errno 17] File exists: '/virtual/tree'", 'copytree existing destination'
+shutil.copytree('/virtual/subdir', '/virtual/tree', dirs_exist_ok=True)
+
+# === shutil.rmtree() ===
+shutil.rmtree('/virtual/tree')
+assert not Path('/virtual/tree').exists(), 'rmtree removes directory'
+assert not Path('/virtual/tree/deep/file.txt').exists(), 'rmtree removes nested files'
+try:
+    shutil.rmtree('/virtual/tree')
+    assert False, 'rmtree should fail for missing directory'
+except FileNotFoundError as e:
+    assert str(e) == "[Errno 2] No such file or directory: '/virtual/tree'", 'rmtree missing'
+shutil.rmtree('/virtual/tree', ignore_errors=True)
+try:
+    shutil.rmtree('/virtual/file.txt')
+    assert False, 'rmtree should fail for a file'
+except NotADirectoryError as e:
+    assert str(e) == "[Errno 20] Not a directory: '/virtual/file.txt'", 'rmtree file'
+
+# === path arguments must be paths ===
+try:
+    shutil.copy(1, '/virtual/copy.txt')
+    assert False, 'copy should reject int source'
+except TypeError as e:
+    assert str(e) == 'expected str, bytes or os.PathLike object, not int', 'copy int source'
+
+# === resolve() and absolute() return paths ===
+resolved = Path('/virtual/file.txt').resolve()
+assert isinstance(resolved, Path), 'resolve returns a Path'
+assert resolved == Path('/virtual/file.txt'), 'resolve value'
+assert Path('/virtual/subdir').absolute() == Path('/virtual/subdir'), 'absolute returns a Path'
+
+# === Path.cwd() ===
+assert isinstance(Path.cwd(), Path), 'cwd returns a Path'
+
+# === Path.home() and expanduser() read HOME, which the virtual environment lacks ===
+try:
+    Path.home()
+    assert False, 'home should fail without HOME'
+except RuntimeError as e:
+    assert str(e) == 'Could not determine home directory.', 'home without HOME'
+try:
+    Path('~/notes.txt').expanduser()
+    assert False, 'expanduser should fail without HOME'
+except RuntimeError as e:
+    assert str(e) == 'Could not determine home directory.', 'expanduser without HOME'
+try:
+    Path('~someone').expanduser()
+    assert False, 'expanduser should fail for another user'
+except RuntimeError as e:
+    assert str(e) == 'Could not determine home directory.', 'expanduser other user'
+assert Path('notes/~').expanduser() == Path('notes/~'), 'expanduser only expands a leading tilde'
@@ -0,0 +1,122 @@
+# === normalization ===
+from pathlib import Path, PosixPath, PurePath, PurePosixPath
+
+assert str(Path('a//b/./c/')) == 'a/b/c', 'repeated slashes and dots removed'
+assert str(Path('')) == '.', 'empty path is dot'
+assert str(Path('./a')) == 'a', 'leading dot removed'
+assert str(Path('//a')) == '//a', 'two leading slashes kept'
+assert str(Path('///a')) == '/a', 'three leading slashes collapse'
+assert str(Path('a/../b')) == 'a/../b', 'dot-dot kept'
+assert Path('.').name == '', 'dot has empty name'
+assert Path('.').parts == (), 'dot has no parts'
+assert Path('//a/b').parts == ('//', 'a', 'b'), 'double slash root part'
+
+# === anchor, root, drive ===
+assert Path('/a/b').anchor == '/', 'absolute anchor'
+assert Path('/a/b').root == '/', 'absolute root'
+assert Path('/a/b').drive == '', 'posix has no drive'
+assert Path('a/b').anchor == '', 'relative anchor'
+assert Path('a/b').root == '', 'relative root'
+assert Path('//a').anchor == '//', 'double slash anchor'
+
+# === parent and parents ===
+assert str(Path('//a').parent) == '//', 'parent of double slash path'
+assert str(Path('.').parent) == '.', 'parent of dot'
+assert [str(p) for p in Path('/a/b/c').parents] == ['/a/b', '/a', '/'], 'absolute parents'
+assert [str(p) for p in Path('a/b').parents] == ['a', '.'], 'relative parents'
+assert list(Path('/').parents) == [], 'root has no parents'
+assert list(Path('.').parents) == [], 'dot has no parents'
+parents = Path('/x/y/z').parents
+assert len(parents) == 3, 'parents length'
+assert parents[0] == Path('/x/y'), 'parents indexing'
+assert parents[-1] == Path('/'), 'parents negative indexing'
+
+# === relative_to ===
+assert str(Path('/a/b/c').relative_to('/a')) == 'b/c', 'relative_to str'
+assert str(Path('/a/b/c').relative_to(Path('/a/b'))) == 'c', 'relative_to Path'
+assert str(Path('a/b').relative_to('a/b')) == '.', 'relative_to self'
+assert str(Path('a/b').relative_to('.')) == 'a/b', 'relative_to dot'
+assert str(Path('/a/b').relative_to('/a/c', walk_up=True)) == '../b', 'relative_to walk_up'
+assert str(Path('/a/b').relative_to('/c/d', walk_up=True)) == '../../a/b', 'relative_to walk_up to root'
+try:
+    Path('/a/b').relative_to('/c')
+    assert False, 'relative_to should raise'
+except ValueError as e:
+    assert str(e) == "'/a/b' is not in the subpath of '/c'", 'relative_to message'
+try:
+    Path('a/b').relative_to('/a', walk_up=True)
+    assert False, 'relative_to different anchors should raise'
+except ValueError as e:
+    assert str(e) == "'a/b' and '/a' have different anchors", 'different anchors message'
+try:
+    Path('a/b').relative_to('c/..', walk_up=True)
+    assert False, 'relative_to walk up dot-dot should raise'
+except ValueError as e:
+    assert str(e) == "'..' segment in 'c/..' cannot be walked", 'dot-dot message'
+
+# === is_relative_to ===
+assert Path('/a/b').is_relative_to('/a') == True, 'is_relative_to parent'
+assert Path('/a/b').is_relative_to('/a/b') == True, 'is_relative_to self'
+assert Path('/a/b').is_relative_to('/c') == False, 'is_relative_to other'
+assert Path('a/b').is_relative_to('/a') == False, 'is_relative_to different anchor'
+assert Path('/ab').is_relative_to('/a') == False, 'is_relative_to compares whole parts'
+
+# === match ===
+assert Path('a/b.py').match('*.py') == True, 'match name'
+assert Path('/a/b/c.py').match('b/*.py') == True, 'match from the right'
+assert Path('/a/b/c.py').match('a/*.py') == False, 'match wrong parent'
+assert Path('/a/b.py').match('/*.py') == False, 'absolute pattern matches whole path'
+assert Path('/b.py').match('/*.py') == True, 'absolute pattern'
+assert Path('b.py').match('a/b.py') == False, 'pattern longer than path'
+assert Path('a/b.txt').match('*.TXT') == False, 'match is case sensitive'
+assert Path('a/b.txt').match('*.TXT', case_sensitive=False) == True, 'match case insensitive'
+assert Path('a/b1.py').match('b[0-9].py') == True, 'match character class'
+assert Path('a/b.py').match('?.py') == True, 'match single character'
+try:
+    Path('a').match('')
+    assert False, 'empty pattern should raise'
+except ValueError as e:
+    assert str(e) == 'empty pattern', 'empty pattern message'
+
+# === full_match ===
+assert Path('a/b.py').full_match('a/*.py') == True, 'full_match whole path'
+assert Path('a/b.py').full_match('*.py') == False, 'full_match is not from the right'
+assert Path('a/b/c.py').full_match('**/*.py') == True, 'full_match recursive'
+assert Path('c.py').full_match('**/*.py') == True, 'full_match recursive matches zero segments'
+assert Path('a').full_match('a/**') == False, 'trailing recursive needs a segment'
+assert Path('a/b/c').full_match('a/**') == True, 'trailing recursive'
+assert Path('/a/b').full_match('**/b') == True, 'full_match absolute path'
+assert Path('/').full_match('/*') == False, 'wildcard needs a name'
+assert Path('A/B').full_match('a/b', case_sensitive=False) == True, 'full_match case insensitive'
+
+# === with_segments ===
+assert Path('x').with_segments('a', 'b') == Path('a/b'), 'with_segments joins'
+assert Path('x').with_segments() == Path('.'), 'with_segments empty'
+
+# === with_name on roots ===
+assert str(Path('//a').with_name('b')) == '//b', 'with_name under double slash root'
+
+# === expanduser without a tilde ===
+assert Path('a/b').expanduser() == Path('a/b'), 'expanduser without tilde'
+
+# === division with str on the left ===
+assert '/usr' / Path('bin') == Path('/usr/bin'), 'str / Path'
+assert str('a' / Path('b') / 'c') == 'a/b/c', 'str / Path / str'
+assert 'a' / Path('/b') == Path('/b'), 'str / absolute Path'
+
+# === ordering ===
+assert Path('a') < Path('b'), 'path less than'
+assert Path('a/b') < Path('a-b'), 'paths compare by parts'
+assert Path('b') > Path('a/c'), 'path greater than'
+assert Path('a') <= Path('a'), 'path less or equal'
+assert sorted([Path('b'), Path('a/c'), Path('a')]) == [Path('a'), Path('a/c'), Path('b')], 'sorted paths'
+assert max(Path('x'), Path('y')) == Path('y'), 'max of paths'
+
+# === hashing ===
+assert hash(Path('a/b')) == hash(Path('a//b/')), 'equal paths hash equally'
+assert len({Path('a'), Path('./a'), Path('b')}) == 2, 'paths in a set'
+
+# === pure path classes ===
+assert PurePosixPath('a/b') == Path('a/b'), 'PurePosixPath equals Path'
+assert PurePath('a').name == 'a', 'PurePath'
+assert PosixPath('/a').is_absolute() == True, 'PosixPath'
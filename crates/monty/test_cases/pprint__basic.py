@@ -0,0 +1,157 @@
+# Tests for the pprint module
+
+import io
+import pprint
+
+# === short values ===
+assert pprint.pformat(1) == '1', 'int'
+assert pprint.pformat('abc') == "'abc'", 'str'
+assert pprint.pformat([1, 2, 3]) == '[1, 2, 3]', 'short list fits on one line'
+assert pprint.pformat((1,)) == '(1,)', 'one-tuple'
+assert pprint.pformat(()) == '()', 'empty tuple'
+assert pprint.pformat({}) == '{}', 'empty dict'
+assert pprint.pformat({'b': 1, 'a': 2}) == "{'a': 2, 'b': 1}", 'dicts are sorted'
+assert pprint.pformat({'b': 1, 'a': 2}, sort_dicts=False) == "{'b': 1, 'a': 2}", 'sort_dicts=False'
+assert pprint.pformat({1: 'x', 'a': 'y', None: 'z'}) == "{None: 'z', 1: 'x', 'a': 'y'}", 'mixed keys sort by type'
+assert pprint.pformat(None) == 'None', 'None'
+
+# === wrapping ===
+data = {'numbers': list(range(12)), 'name': 'monty', 'nested': {'a': [1, 2], 'b': (3, 4)}}
+expected = """{'name': 'monty',
+ 'nested': {'a': [1, 2], 'b': (3, 4)},
+ 'numbers': [0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11]}"""
+assert pprint.pformat(data) == expected, 'dict wraps one item per line'
+
+expected = """[0,
+ 1,
+ 2,
+ 3,
+ 4,
+ 5,
+ 6,
+ 7,
+ 8,
+ 9]"""
+assert pprint.pformat(list(range(10)), width=20) == expected, 'list wraps one item per line'
+assert pprint.pformat(list(range(10)), width=20, compact=True) == '[0, 1, 2, 3, 4, 5,\n 6, 7, 8, 9]', 'compact'
+
+expected = """{'a': [1,
+       2,
+       3],
+ 'b': 'x'}"""
+assert pprint.pformat({'a': [1, 2, 3], 'b': 'x'}, width=10) == expected, 'values indent past their key'
+
+expected = """(1,
+ 2,
+ 3)"""
+assert pprint.pformat((1, 2, 3), width=5) == expected, 'tuple'
+assert pprint.pformat(('a' * 10,), width=10) == "('aaaaaaaaaa',)", 'long one-tuple keeps its comma'
+
+expected = """[[1,
+  2],
+ [3,
+  4]]"""
+assert pprint.pformat([[1, 2], [3, 4]], width=6) == expected, 'nested lists'
+
+expected = """{1,
+ 2,
+ 3}"""
+assert pprint.pformat({3, 1, 2}, width=5) == expected, 'sets are sorted'
+expected = """frozenset({1,
+           2,
+           3})"""
+assert pprint.pformat(frozenset({3, 2, 1}), width=5) == expected, 'frozenset'
+assert pprint.pformat({3, 1, 2}) == '{1, 2, 3}', 'short sets use their repr'
+
+# === indent ===
+expected = """[   1,
+    2,
+    3]"""
+assert pprint.pformat([1, 2, 3], indent=4, width=5) == expected, 'indent'
+expected = """{   'a': 1,
+    'b': 2}"""
+assert pprint.pformat({'a': 1, 'b': 2}, indent=4, width=10) == expected, 'dict indent'
+
+# === strings and bytes ===
+s = 'the quick brown fox jumps over the lazy dog'
+expected = """('the quick '
+ 'brown fox '
+ 'jumps over '
+ 'the lazy '
+ 'dog')"""
+assert pprint.pformat(s, width=15) == expected, 'long strings split at spaces'
+expected = """['the quick '
+ 'brown fox '
+ 'jumps over '
+ 'the lazy dog']"""
+assert pprint.pformat([s], width=16) == expected, 'nested strings have no parentheses'
+expected = """('line one\\n'
+ 'line two')"""
+assert pprint.pformat('line one\nline two', width=15) == expected, 'strings split at line breaks'
+expected = """(b'\\x00\\x01\\x02\\x03'
+ b'\\x04\\x05\\x06\\x07')"""
+assert pprint.pformat(bytes(range(8)), width=20) == expected, 'bytes split in groups of 4'
+assert pprint.pformat(b'abcd', width=3) == "b'abcd'", 'short bytes are not split'
+
+# === depth ===
+nested = [1, [2, [3, [4]]]]
+assert pprint.pformat(nested, depth=2) == '[1, [2, [...]]]', 'depth elides deep lists'
+assert pprint.pformat({'a': {'b': {}}}, depth=1) == "{'a': {...}}", 'depth elides dicts'
+assert pprint.pformat({'a': {'b': {'c': 1}}}, depth=2) == "{'a': {'b': {...}}}", 'empty containers are not elided'
+assert pprint.pformat((1, (2,)), depth=1) == '(1, (...,))', 'depth elides tuples'
+
+# === underscore_numbers ===
+assert pprint.pformat(1234567, underscore_numbers=True) == '1_234_567', 'underscore_numbers'
+assert pprint.pformat([-1000, 10**20], underscore_numbers=True) == '[-1_000, 100_000_000_000_000_000_000]', 'long ints'
+assert pprint.pformat(True, underscore_numbers=True) == 'True', 'bools are not numbers'
+
+# === recursion ===
+a = [1, 2]
+a.append(a)
+assert pprint.pformat(a) == f'[1, 2, <Recursion on list with id={id(a)}>]', 'recursive list'
+d = {}
+d['self'] = d
+assert pprint.pformat(d) == f"{{'self': <Recursion on dict with id={id(d)}>}}", 'recursive dict'
+assert pprint.isrecursive(a), 'isrecursive'
+assert not pprint.isrecursive([1, [2]]), 'not recursive'
+assert pprint.saferepr(a) == f'[1, 2, <Recursion on list with id={id(a)}>]', 'saferepr'
+shared = [1]
+assert pprint.pformat([shared, shared]) == '[[1], [1]]', 'shared references are not recursion'
+
+# === saferepr and isreadable ===
+assert pprint.saferepr({'b': 1, 'a': [1, 2]}) == "{'a': [1, 2], 'b': 1}", 'saferepr sorts dicts'
+assert pprint.isreadable([1, 'a', (2.5, None)]), 'readable'
+assert not pprint.isreadable([len]), 'builtins are not readable'
+assert not pprint.isreadable(a), 'recursive values are not readable'
+
+# === pprint and pp ===
+out = io.StringIO()
+pprint.pprint({'b': 1, 'a': 2}, stream=out)
+assert out.getvalue() == "{'a': 2, 'b': 1}\n", 'pprint writes a line to the stream'
+out = io.StringIO()
+pprint.pp({'b': 1, 'a': 2}, stream=out)
+assert out.getvalue() == "{'b': 1, 'a': 2}\n", 'pp does not sort dicts'
+out = io.StringIO()
+pprint.pp({'b': 1, 'a': 2}, out, sort_dicts=True)
+assert out.getvalue() == "{'a': 2, 'b': 1}\n", 'pp with sort_dicts'
+out = io.StringIO()
+pprint.pprint(list(range(3)), out, width=3)
+assert out.getvalue() == '[0,\n 1,\n 2]\n', 'positional stream and width'
+assert pprint.pprint([1, 2]) is None, 'pprint to stdout returns None'
+
+# === errors ===
+try:
+    pprint.pformat(1, indent=-1)
+    assert False, 'negative indent should raise'
+except ValueError as e:
+    assert str(e) == 'indent must be >= 0', f'indent message: {e}'
+try:
+    pprint.pformat(1, depth=0)
+    assert False, 'zero depth should raise'
+except ValueError as e:
+    assert str(e) == 'depth must be > 0', f'depth message: {e}'
+try:
+    pprint.pformat(1, width=0)
+    assert False, 'zero width should raise'
+except ValueError as e:
+    assert str(e) == 'width must be != 0', f'width message: {e}'
@@ -0,0 +1,30 @@
+# === print() to an io.StringIO ===
+import io
+import sys
+
+buf = io.StringIO()
+print('hello', 'world', file=buf)
+print(1, 2, sep='-', end='!', file=buf)
+assert buf.getvalue() == 'hello world\n1-2!', 'print writes to StringIO'
+
+buf = io.StringIO()
+print(file=buf)
+assert buf.getvalue() == '\n', 'print with no args to StringIO'
+
+# === file=None and sys.stdout mean standard output ===
+print('to stdout', file=None)
+print('to stdout', file=sys.stdout)
+
+# === sys.stdout and sys.stderr methods ===
+assert sys.stdout.write('') == 0, 'write empty string'
+assert sys.stdout.write('written\n') == 8, 'write returns character count'
+assert sys.stderr.write('é\n') == 2, 'write counts characters not bytes'
+assert sys.stdout.flush() is None, 'flush returns None'
+assert sys.stderr.flush() is None, 'stderr flush returns None'
+print('to stderr', file=sys.stderr)
+
+try:
+    sys.stdout.write(1)
+    assert False, 'write should reject non-str'
+except TypeError as e:
+    assert str(e) == 'write() argument must be str, not int', 'write non-str message'
@@ -0,0 +1,39 @@
+# Tests for re.error messages and positions on invalid patterns
+
+import re
+
+
+def error_of(pattern, flags=0):
+    try:
+        re.compile(pattern, flags)
+    except re.error as e:
+        return str(e)
+    return None
+
+
+assert error_of('(abc') == 'missing ), unterminated subpattern at position 0', 'unterminated group'
+assert error_of('abc)') == 'unbalanced parenthesis at position 3', 'unbalanced parenthesis'
+assert error_of('[abc') == 'unterminated character set at position 0', 'unterminated set'
+assert error_of('a**') == 'multiple repeat at position 2', 'multiple repeat'
+assert error_of('*a') == 'nothing to repeat at position 0', 'nothing to repeat'
+assert error_of('[z-a]') == 'bad character range z-a at position 1', 'bad range'
+assert error_of(r'\q') == r'bad escape \q at position 0', 'bad escape'
+assert error_of(r'(a)\2') == 'invalid group reference 2 at position 4', 'invalid backreference'
+assert error_of('(?P<a>x)(?P<a>y)') == (
+    "redefinition of group name 'a' as group 2; was group 1 at position 12"
+), 'duplicate group name'
+assert error_of('(?P=b)') == "unknown group name 'b' at position 4", 'unknown named backreference'
+assert error_of('(?P<1a>x)') == "bad character in group name '1a' at position 4", 'bad group name'
+assert error_of('a{3,2}') == 'min repeat greater than max repeat at position 2', 'bad repeat range'
+assert error_of('(?z)') == 'unknown extension ?z at position 1', 'unknown flag'
+assert error_of('(?i') == 'missing -, : or ) at position 3', 'unterminated flag group'
+assert error_of('(?x)a  # comment\n  (b') == (
+    'missing ), unterminated subpattern at position 19 (line 2, column 3)'
+), 'multi-line patterns report line and column'
+
+# module-level functions raise the same errors
+try:
+    re.search('(', 'x')
+    assert False, 'invalid pattern should raise'
+except re.error as e:
+    assert str(e) == 'missing ), unterminated subpattern at position 0', 'search raises re.error too'
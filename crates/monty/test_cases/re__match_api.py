@@ -0,0 +1,63 @@
+# Tests for re.Match and re.Pattern introspection
+
+import re
+
+# === Pattern.groups and Pattern.groupindex ===
+p = re.compile(r'(?P<year>\d{4})-(?P<month>\d\d)(-(\d\d))?')
+assert p.groups == 4, 'Pattern.groups counts all capture groups'
+assert p.groupindex == {'year': 1, 'month': 2}, 'Pattern.groupindex maps names to numbers'
+assert re.compile('abc').groupindex == {}, 'groupindex is empty without named groups'
+
+# === groupdict ===
+m = p.match('2024-05')
+assert m.groupdict() == {'year': '2024', 'month': '05'}, 'groupdict'
+m = re.match(r'(?P<a>x)|(?P<b>y)', 'y')
+assert m.groupdict() == {'a': None, 'b': 'y'}, 'groupdict with unmatched group'
+assert m.groupdict('-') == {'a': '-', 'b': 'y'}, 'groupdict with default'
+
+# === start / end / span accept group names ===
+m = p.match('2024-05-17')
+assert m.start('month') == 5, 'start with group name'
+assert m.end('year') == 4, 'end with group name'
+assert m.span('month') == (5, 7), 'span with group name'
+assert m.span(4) == (8, 10), 'span of unnamed group'
+try:
+    m.span('day')
+    assert False, 'unknown group name should raise IndexError'
+except IndexError as e:
+    assert str(e) == 'no such group', 'unknown group name message'
+
+# === lastindex and lastgroup ===
+m = re.match(r'(a)(b)', 'ab')
+assert m.lastindex == 2, 'lastindex is the last group to close'
+assert m.lastgroup is None, 'lastgroup is None for unnamed groups'
+m = re.match(r'((a)b)', 'ab')
+assert m.lastindex == 1, 'outer group closes after nested group'
+m = re.match(r'(?P<x>a)(?P<y>b)?', 'a')
+assert m.lastindex == 1, 'unmatched groups are ignored by lastindex'
+assert m.lastgroup == 'x', 'lastgroup names the last group'
+m = re.match(r'(?P<key>\w+)|(?P<num>\d+)', 'abc')
+assert m.lastgroup == 'key', 'lastgroup with alternation'
+assert re.match('abc', 'abc').lastindex is None, 'lastindex is None without groups'
+
+# === regs ===
+m = re.search(r'(a)(x)?', 'ba')
+assert m.regs == ((1, 2), (1, 2), (-1, -1)), 'regs lists spans with -1 for unmatched groups'
+
+# === expand ===
+m = re.match(r'(?P<first>\w+) (?P<last>\w+)', 'Jane Doe')
+assert m.expand(r'\2, \1') == 'Doe, Jane', 'expand with numbered groups'
+assert m.expand(r'\g<last>: \g<first>') == 'Doe: Jane', 'expand with named groups'
+assert m.expand(r'\g<0>\n') == 'Jane Doe\n', 'expand with group 0 and escapes'
+
+# === named backreferences ===
+m = re.search(r'(?P<word>\w+) (?P=word)', 'say bye bye now')
+assert m is not None, '(?P=name) backreference matches'
+assert m.group('word') == 'bye', 'named backreference group'
+assert re.search(r'(?P<q>[\'"]).*?(?P=q)', 'say "hi" now').group() == '"hi"', 'named backreference to quote'
+assert re.sub(r'(?P<ch>\w)(?P=ch)', r'\g<ch>', 'aabbcd') == 'abcd', 'named backreference in sub'
+
+# === m[name] ===
+m = re.match(r'(?P<k>\w+)=(?P<v>\w+)', 'a=1')
+assert m['k'] == 'a', 'subscript with group name'
+assert m[2] == '1', 'subscript with group number'
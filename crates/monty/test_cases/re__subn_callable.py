@@ -0,0 +1,80 @@
+# Tests for re.subn() and re.sub() with a callable replacement
+
+import re
+
+# === re.subn() ===
+assert re.subn(r'\d', '#', 'a1b22c') == ('a#b##c', 3), 'subn returns new string and count'
+assert re.subn(r'\d', '#', 'abc') == ('abc', 0), 'subn with no matches returns count 0'
+assert re.subn(r'\d', '#', 'a1b2c3', count=2) == ('a#b#c3', 2), 'subn respects count'
+assert re.subn(r'(\w+)@(\w+)', r'\2 at \1', 'user@host') == ('host at user', 1), 'subn with backreferences'
+
+p = re.compile(r'o')
+assert p.subn('0', 'foo boo') == ('f00 b00', 4), 'Pattern.subn counts all replacements'
+assert p.subn('0', 'foo boo', 1) == ('f0o boo', 1), 'Pattern.subn with positional count'
+assert p.subn('0', 'foo', -1) == ('foo', 0), 'Pattern.subn with negative count makes no substitutions'
+
+# === re.sub() with a callable replacement ===
+result = re.sub(r'\d+', lambda m: str(int(m.group()) * 2), 'a1 b20 c300')
+assert result == 'a2 b40 c600', 'callable repl receives each match'
+
+
+def shout(m):
+    return m.group(1).upper() + '!'
+
+
+assert re.sub(r'(\w+)', shout, 'hi there') == 'HI! THERE!', 'named function as repl'
+assert re.compile(r'(\w+)').sub(shout, 'hey', count=1) == 'HEY!', 'Pattern.sub with callable repl'
+assert re.subn(r'[aeiou]', lambda m: m.group().upper(), 'banana') == ('bAnAnA', 3), 'subn with callable repl'
+
+# the callable sees the match object, including positions and named groups
+spans = []
+
+
+def record(m):
+    spans.append(m.span())
+    return m.group('word')[::-1]
+
+
+assert re.sub(r'(?P<word>[a-z]+)', record, 'ab cd') == 'ba dc', 'callable repl with named group'
+assert spans == [(0, 2), (3, 5)], 'callable repl is called once per match in order'
+
+# returning an empty string deletes the match
+assert re.sub(r'\s+', lambda m: '', 'a b  c') == 'abc', 'callable repl returning empty string'
+
+# === callable must return a string ===
+try:
+    re.sub('a', lambda m: 1, 'aaa')
+    assert False, 'non-str return from repl should raise TypeError'
+except TypeError as e:
+    assert 'expected str instance, int found' in str(e), 'non-str repl result error message'
+
+# exceptions raised by the callable propagate
+try:
+    re.sub('a', lambda m: 1 // 0, 'a')
+    assert False, 'exception in repl should propagate'
+except ZeroDivisionError:
+    pass
+
+# === template escapes ===
+assert re.sub('a', r'\n', 'a') == '\n', r'\n in template is a newline'
+assert re.sub('a', r'\\', 'a') == '\\', r'\\ in template is a backslash'
+assert re.sub('(a)', r'\g<1>0', 'a') == 'a0', r'\g<1> followed by a digit'
+assert re.sub('(a)|b', r'[\1]', 'ab') == '[a][]', 'unmatched group expands to empty string'
+
+try:
+    re.sub('(a)', r'\2', 'a')
+    assert False, 'invalid group reference should raise'
+except re.error as e:
+    assert str(e) == 'invalid group reference 2 at position 1', 'invalid group reference message'
+
+try:
+    re.sub('(?P<a>x)', r'\g<b>', 'x')
+    assert False, 'unknown group name should raise IndexError'
+except IndexError as e:
+    assert str(e) == "unknown group name 'b'", 'unknown group name message'
+
+try:
+    re.sub('x', r'\q', 'x')
+    assert False, 'bad escape in template should raise'
+except re.error as e:
+    assert str(e) == r'bad escape \q at position 0', 'bad template escape message'
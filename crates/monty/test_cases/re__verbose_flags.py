@@ -0,0 +1,62 @@
+# Tests for re.VERBOSE, re.UNICODE and inline flag groups
+
+import re
+
+# === flag constants ===
+assert re.VERBOSE == 64, 're.VERBOSE value'
+assert re.X == re.VERBOSE, 're.X is an alias of re.VERBOSE'
+assert re.UNICODE == 32, 're.UNICODE value'
+assert re.U == re.UNICODE, 're.U is an alias of re.UNICODE'
+
+# === re.VERBOSE ===
+pattern = re.compile(
+    r"""
+    (\d{3})   # area code
+    [-\s]?    # optional separator
+    (\d{4})   # number
+    """,
+    re.VERBOSE,
+)
+m = pattern.search('call 555-1234 now')
+assert m is not None, 'verbose pattern matches'
+assert m.groups() == ('555', '1234'), 'verbose pattern groups'
+assert pattern.flags & re.VERBOSE, 'flags include VERBOSE'
+
+assert re.fullmatch(r'a b c', 'abc', re.X) is not None, 'whitespace ignored in verbose mode'
+assert re.fullmatch(r'a\ b', 'a b', re.X) is not None, 'escaped space matches in verbose mode'
+assert re.fullmatch(r'a[ ]b', 'a b', re.X) is not None, 'space in class matches in verbose mode'
+assert re.fullmatch(r'a\#b  # comment', 'a#b', re.X) is not None, 'escaped hash in verbose mode'
+
+# === global inline flags ===
+assert re.match(r'(?i)hello', 'HELLO') is not None, '(?i) makes the pattern case-insensitive'
+assert re.compile(r'(?i)a').flags & re.IGNORECASE, 'inline flag is reported in .flags'
+assert re.findall(r'(?m)^\w', 'a\nb\nc') == ['a', 'b', 'c'], '(?m) multiline'
+assert re.match(r'(?s)a.b', 'a\nb') is not None, '(?s) dotall'
+assert re.match(r'(?x) a  b ', 'ab') is not None, '(?x) verbose'
+assert re.match(r'(?im)^B', 'a\nb') is None, 'match is anchored at the string start even with (?m)'
+assert re.search(r'(?im)^B', 'a\nb') is not None, 'combined inline flags'
+
+# === scoped inline flags ===
+assert re.match(r'(?i:a)b', 'Ab') is not None, 'scoped (?i:...) applies inside the group'
+assert re.match(r'(?i:a)b', 'AB') is None, 'scoped (?i:...) does not apply outside the group'
+assert re.match(r'(?-i:a)b', 'aB', re.I) is not None, 'scoped (?-i:...) turns a flag off'
+assert re.match(r'(?-i:a)b', 'AB', re.I) is None, 'scoped (?-i:...) only affects its group'
+
+# === $ matches before a trailing newline ===
+assert re.search(r'a$', 'a\n') is not None, '$ matches before a final newline'
+assert re.search(r'a\Z', 'a\n') is None, r'\Z only matches at the very end'
+assert re.findall(r'\w$', 'a\nb\n', re.M) == ['a', 'b'], '$ with MULTILINE'
+
+# === repr shows effective flags ===
+assert repr(re.compile('a', re.X)) == "re.compile('a', re.VERBOSE)", 'repr with VERBOSE'
+assert repr(re.compile('(?i)a')) == "re.compile('(?i)a', re.IGNORECASE)", 'repr includes inline flags'
+assert repr(re.compile('a', re.U)) == "re.compile('a')", 'repr omits UNICODE for str patterns'
+assert re.compile('a').flags == re.UNICODE, 'str patterns are UNICODE by default'
+assert re.compile('a', re.A).flags == re.ASCII, 'ASCII replaces the default UNICODE flag'
+
+# === incompatible flags ===
+try:
+    re.compile('a', re.A | re.U)
+    assert False, 'ASCII|UNICODE should raise'
+except ValueError as e:
+    assert str(e) == 'ASCII and UNICODE flags are incompatible', 'incompatible flags message'
@@ -0,0 +1,43 @@
+# Tests for the reprlib module
+
+import reprlib
+
+# === short values ===
+assert reprlib.repr(1) == '1', 'int'
+assert reprlib.repr('abc') == "'abc'", 'str'
+assert reprlib.repr([1, 2, 3]) == '[1, 2, 3]', 'short list'
+assert reprlib.repr((1,)) == '(1,)', 'one-tuple'
+assert reprlib.repr(()) == '()', 'empty tuple'
+assert reprlib.repr({}) == '{}', 'empty dict'
+assert reprlib.repr(set()) == 'set()', 'empty set'
+assert reprlib.repr(frozenset()) == 'frozenset()', 'empty frozenset'
+assert reprlib.repr(None) == 'None', 'None'
+assert reprlib.repr(True) == 'True', 'bool'
+
+# === containers ===
+assert reprlib.repr(list(range(10))) == '[0, 1, 2, 3, 4, 5, ...]', 'long list'
+assert reprlib.repr(tuple(range(7))) == '(0, 1, 2, 3, 4, 5, ...)', 'long tuple'
+assert reprlib.repr({5, 3, 1, 4, 2, 0, 6}) == '{0, 1, 2, 3, 4, 5, ...}', 'sets are sorted'
+assert reprlib.repr(frozenset('abc')) == "frozenset({'a', 'b', 'c'})", 'frozenset'
+d = {'e': 5, 'd': 4, 'c': 3, 'b': 2, 'a': 1}
+assert reprlib.repr(d) == "{'a': 1, 'b': 2, 'c': 3, 'd': 4, ...}", 'long dict is sorted'
+assert reprlib.repr({2: 'b', 'a': 1}) == "{2: 'b', 'a': 1}", 'unorderable keys keep their order'
+assert reprlib.repr([[[[[[[1]]]]]]]) == '[[[[[[[...]]]]]]]', 'deep nesting is elided'
+assert reprlib.repr([[[[[[{'a': 1}]]]]]]) == '[[[[[[{...}]]]]]]', 'deep dicts are elided'
+assert reprlib.repr([[[[[[[]]]]]]]) == '[[[[[[[]]]]]]]', 'empty containers are not elided'
+
+# === strings ===
+s = 'abcdefghijklmnopqrstuvwxyz' * 2
+assert reprlib.repr(s) == "'abcdefghijkl...nopqrstuvwxyz'", 'long string'
+assert len(reprlib.repr(s)) == 30, 'long string length'
+assert reprlib.repr('a' * 28) == "'" + 'a' * 28 + "'", 'string at the limit'
+assert reprlib.repr('\x00' * 10) == "'\\x00\\x00\\x00...0\\x00\\x00\\x00'", 'escaped short string'
+
+# === numbers and others ===
+assert reprlib.repr(10**50) == '100000000000000000...0000000000000000000', 'long int'
+assert reprlib.repr(10**39) == '1' + '0' * 39, 'int at the limit'
+assert reprlib.repr(-(10**39)) == '-10000000000000000...0000000000000000000', 'negative long int'
+assert reprlib.repr(b'x' * 40) == "b'xxxxxxxxxxx...xxxxxxxxxxxxx'", 'long bytes'
+assert reprlib.repr([s, 10**50]) == "['abcdefghijkl...nopqrstuvwxyz', 100000000000000000...0000000000000000000]", (
+    'items are shortened'
+)
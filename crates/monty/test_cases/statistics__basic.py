@@ -0,0 +1,167 @@
+# Tests for the statistics module
+
+import math
+import statistics
+
+# === mean ===
+assert statistics.mean([1, 2, 3, 4, 4]) == 2.8, 'mean of ints'
+assert statistics.mean([1, 2, 3]) == 2, 'exact mean of ints'
+assert type(statistics.mean([1, 2, 3])) == int, 'exact mean of ints is an int'
+assert statistics.mean([1, 2]) == 1.5, 'inexact mean of ints is a float'
+assert statistics.mean([2.5, 3.25, 5.5, 11.25, 11.75]) == 6.85, 'mean of floats'
+assert statistics.mean([1.5, 2]) == 1.75, 'mixed ints and floats'
+assert type(statistics.mean([1.0, 3.0])) == float, 'mean of floats is a float'
+assert statistics.mean([True, False]) == 0.5, 'bools count as ints'
+assert statistics.mean(iter([1, 2, 3, 6])) == 3, 'iterators work'
+assert statistics.mean(range(101)) == 50, 'ranges work'
+assert statistics.mean([1e50, 1, -1e50] * 1000) == 1 / 3, 'mean is computed exactly'
+assert statistics.mean([0.1, 0.2, 0.3]) == 0.2, 'exact float mean'
+assert statistics.mean([10**30, 1]) == 5e29, 'long ints'
+assert statistics.mean([10**30, 10**30]) == 10**30, 'exact mean of long ints'
+assert statistics.mean([1, float('inf')]) == float('inf'), 'infinite data'
+assert math.isnan(statistics.mean([float('inf'), float('-inf')])), 'inf - inf is nan'
+
+# === fmean ===
+assert statistics.fmean([3.5, 4.0, 5.25]) == 4.25, 'fmean'
+assert type(statistics.fmean([1, 2, 3])) == float, 'fmean is always a float'
+assert statistics.fmean(iter([1, 2])) == 1.5, 'fmean of an iterator'
+assert statistics.fmean([0.1] * 10) == 0.1, 'fmean uses an exact sum'
+assert statistics.fmean([1, 2, 3], [3, 2, 1]) == 10 / 6, 'weighted fmean'
+assert statistics.fmean([85, 92, 83, 91], weights=[0.20, 0.20, 0.30, 0.30]) == 87.6, 'weights keyword'
+assert statistics.fmean([1, 2], None) == 1.5, 'weights=None'
+
+# === median ===
+assert statistics.median([1, 3, 5]) == 3, 'odd median'
+assert statistics.median([1, 3, 5, 7]) == 4.0, 'even median'
+assert statistics.median([1, 2]) == 1.5, 'even median interpolates'
+assert type(statistics.median([2, 4])) == float, 'even median divides'
+assert statistics.median([5, 1, 3]) == 3, 'median sorts the data'
+assert statistics.median([2.5]) == 2.5, 'single data point'
+assert statistics.median_low([1, 3, 5, 7]) == 3, 'median_low'
+assert statistics.median_low([1, 3, 5]) == 3, 'odd median_low'
+assert statistics.median_high([1, 3, 5, 7]) == 5, 'median_high'
+assert statistics.median_high([1, 3, 5]) == 3, 'odd median_high'
+assert statistics.median_low(['b', 'c', 'a', 'd']) == 'b', 'median_low of strings'
+
+# === mode and multimode ===
+assert statistics.mode([1, 1, 2, 3, 3, 3, 3, 4]) == 3, 'mode'
+assert statistics.mode(['red', 'blue', 'blue', 'red', 'green', 'red', 'red']) == 'red', 'nominal mode'
+assert statistics.mode(['red', 'red', 'green', 'blue', 'blue']) == 'red', 'ties go to the first seen'
+assert statistics.mode('abcb') == 'b', 'mode of a string'
+m = statistics.mode([1, 1.0, 2])
+assert m == 1 and type(m) == int, 'equal values keep the first one'
+assert statistics.multimode('aabbbbccddddeeffffgg') == ['b', 'd', 'f'], 'multimode'
+assert statistics.multimode('aabbbbbbbbcc') == ['b'], 'single multimode'
+assert statistics.multimode([]) == [], 'empty multimode'
+assert statistics.multimode([(1, 2), (1, 2), (3,)]) == [(1, 2)], 'tuples are hashable'
+
+# === variance and pvariance ===
+data = [2.75, 1.75, 1.25, 0.25, 0.5, 1.25, 3.5]
+assert statistics.variance(data) == 1.3720238095238095, 'variance'
+assert statistics.variance(data, statistics.mean(data)) == 1.3720238095238095, 'variance with xbar'
+assert statistics.variance([1, 2, 3, 4]) == 1.6666666666666667, 'variance of ints'
+v = statistics.variance([1, 2, 3])
+assert v == 1 and type(v) == int, 'exact variance of ints is an int'
+assert statistics.variance([1, 2, 3, 4], 2) == 2, 'int xbar'
+assert statistics.variance([1, 2, 3, 4], 2.5) == 1.6666666666666667, 'float xbar'
+assert statistics.variance([1.5, 2.5]) == 0.5, 'variance of floats'
+assert statistics.pvariance([0.0, 0.25, 0.25, 1.25, 1.5, 1.75, 2.75, 3.25]) == 1.25, 'pvariance'
+assert statistics.pvariance([1, 2, 3, 4], mu=2.5) == 1.25, 'pvariance with mu'
+p = statistics.pvariance([5])
+assert p == 0 and type(p) == int, 'pvariance of one int'
+assert statistics.variance([1e9 + 1, 1e9 + 2, 1e9 + 3]) == 1.0, 'variance is computed exactly'
+assert statistics.variance([1, float('inf')]) == float('inf'), 'infinite variance'
+
+# === stdev and pstdev ===
+assert statistics.stdev([1.5, 2.5, 2.5, 2.75, 3.25, 4.75]) == 1.0810874155219827, 'stdev'
+assert statistics.pstdev([1.5, 2.5, 2.5, 2.75, 3.25, 4.75]) == 0.986893273527251, 'pstdev'
+assert statistics.stdev([1, 2, 3, 4]) == 1.2909944487358056, 'stdev of ints'
+s = statistics.stdev([1, 2, 3])
+assert s == 1.0 and type(s) == float, 'stdev is always a float'
+assert statistics.pstdev([5]) == 0.0, 'pstdev of one value'
+assert statistics.stdev([2, 4, 4, 4, 5, 5, 7, 9], 5) == 2.138089935299395, 'stdev with xbar'
+assert statistics.pstdev([2, 4, 4, 4, 5, 5, 7, 9]) == 2.0, 'exact pstdev'
+
+# === quantiles ===
+deciles = list(range(1, 11))
+assert statistics.quantiles(deciles) == [2.75, 5.5, 8.25], 'quartiles'
+assert statistics.quantiles(deciles, method='inclusive') == [3.25, 5.5, 7.75], 'inclusive quartiles'
+assert statistics.quantiles(deciles, n=2) == [5.5], 'median cut'
+assert statistics.quantiles([1.0, 5, 7], n=5) == [0.2, 3.4, 5.8, 7.4], 'exclusive clamps at the ends'
+assert statistics.quantiles([1, 3], n=1) == [], 'n=1 has no cut points'
+assert statistics.quantiles([5], n=3) == [5, 5], 'single data point'
+assert statistics.quantiles([5], n=3, method='other') == [5, 5], 'method is not checked for one data point'
+q = statistics.quantiles([1, 2, 3, 4, 5], n=4, method='inclusive')
+assert q == [2.0, 3.0, 4.0], f'inclusive cut points on data points: {q}'
+
+# === errors ===
+try:
+    statistics.mean([])
+    assert False, 'empty mean should raise'
+except statistics.StatisticsError as e:
+    assert str(e) == 'mean requires at least one data point', f'mean message: {e}'
+try:
+    statistics.fmean([])
+    assert False, 'empty fmean should raise'
+except statistics.StatisticsError as e:
+    assert str(e) == 'fmean requires at least one data point', f'fmean message: {e}'
+try:
+    statistics.median([])
+    assert False, 'empty median should raise'
+except ValueError as e:
+    assert str(e) == 'no median for empty data', f'StatisticsError is a ValueError: {e}'
+try:
+    statistics.mode([])
+    assert False, 'empty mode should raise'
+except statistics.StatisticsError as e:
+    assert str(e) == 'no mode for empty data', f'mode message: {e}'
+try:
+    statistics.variance([1])
+    assert False, 'variance of one value should raise'
+except statistics.StatisticsError as e:
+    assert str(e) == 'variance requires at least two data points', f'variance message: {e}'
+try:
+    statistics.pstdev([])
+    assert False, 'empty pstdev should raise'
+except statistics.StatisticsError as e:
+    assert str(e) == 'pstdev requires at least one data point', f'pstdev message: {e}'
+try:
+    statistics.stdev([1, float('inf')])
+    assert False, 'infinite stdev should raise'
+except ValueError as e:
+    assert str(e) == 'inf or nan encountered in data', f'stdev message: {e}'
+try:
+    statistics.quantiles([1, 2], n=0)
+    assert False, 'n=0 should raise'
+except statistics.StatisticsError as e:
+    assert str(e) == 'n must be at least 1', f'quantiles n message: {e}'
+try:
+    statistics.quantiles([])
+    assert False, 'empty quantiles should raise'
+except statistics.StatisticsError as e:
+    assert str(e) == 'must have at least one data point', f'quantiles message: {e}'
+try:
+    statistics.quantiles([1, 2], method='middle')
+    assert False, 'unknown method should raise'
+except ValueError as e:
+    assert str(e) == "Unknown method: 'middle'", f'method message: {e}'
+try:
+    statistics.mean(['a'])
+    assert False, 'strings have no mean'
+except TypeError as e:
+    assert str(e) == "can't convert type 'str' to numerator/denominator", f'mean type message: {e}'
+try:
+    statistics.fmean([1, 2], [1])
+    assert False, 'mismatched weights should raise'
+except statistics.StatisticsError as e:
+    assert str(e) == 'data and weights must be the same length', f'weights message: {e}'
+try:
+    statistics.fmean([1, 2], [1, -1])
+    assert False, 'zero total weight should raise'
+except statistics.StatisticsError as e:
+    assert str(e) == 'sum of weights must be non-zero', f'zero weights message: {e}'
+try:
+    statistics.mode([[1], [1]])
+    assert False, 'unhashable data should raise'
+except TypeError as e:
+    assert "unhashable type: 'list'" in str(e), f'mode type message: {e}'
@@ -0,0 +1,66 @@
+# Tests for the string module
+
+import string
+from string import Template
+
+# === constants ===
+assert string.ascii_lowercase == 'abcdefghijklmnopqrstuvwxyz', 'ascii_lowercase'
+assert string.ascii_uppercase == 'ABCDEFGHIJKLMNOPQRSTUVWXYZ', 'ascii_uppercase'
+assert string.ascii_letters == string.ascii_lowercase + string.ascii_uppercase, 'ascii_letters'
+assert string.digits == '0123456789', 'digits'
+assert string.hexdigits == '0123456789abcdefABCDEF', 'hexdigits'
+assert string.octdigits == '01234567', 'octdigits'
+assert string.punctuation == '!"#$%&\'()*+,-./:;<=>?@[\\]^_`{|}~', 'punctuation'
+assert string.whitespace == ' \t\n\r\x0b\x0c', 'whitespace'
+assert string.printable == string.digits + string.ascii_letters + string.punctuation + string.whitespace, 'printable'
+assert len(string.punctuation) == 32, 'punctuation length'
+assert all(c in string.hexdigits for c in 'deadBEEF'), 'membership'
+
+# === substitute ===
+t = Template('$who likes $what')
+assert t.template == '$who likes $what', 'template attribute'
+assert t.substitute(who='tim', what='kung pao') == 'tim likes kung pao', 'kwargs'
+assert t.substitute({'who': 'tim', 'what': 'pie'}) == 'tim likes pie', 'mapping'
+assert t.substitute({'who': 'tim', 'what': 'pie'}, what='cake') == 'tim likes cake', 'kwargs override mapping'
+assert Template('${noun}ification').substitute(noun='demo') == 'demoification', 'braced'
+assert Template('$$5 for $item').substitute(item='tea') == '$5 for tea', 'escaped delimiter'
+assert Template('$n items').substitute(n=3) == '3 items', 'values converted with str'
+assert Template('$x and $x').substitute(x=[1, 2]) == '[1, 2] and [1, 2]', 'repeated placeholder'
+assert Template('no placeholders').substitute() == 'no placeholders', 'no placeholders'
+assert Template('$a_1$b').substitute(a_1='x', b='y') == 'xy', 'identifier characters'
+assert Template('$ñ').safe_substitute() == '$ñ', 'identifiers are ascii only'
+
+try:
+    t.substitute(who='tim')
+    assert False, 'missing key should raise'
+except KeyError as e:
+    assert e.args == ('what',), f'missing key error: {e.args}'
+
+try:
+    Template('cost: $5').substitute()
+    assert False, 'invalid placeholder should raise'
+except ValueError as e:
+    assert str(e) == 'Invalid placeholder in string: line 1, col 7', str(e)
+
+try:
+    Template('line one\nline $ two').substitute()
+    assert False, 'invalid placeholder should raise'
+except ValueError as e:
+    assert str(e) == 'Invalid placeholder in string: line 2, col 6', str(e)
+
+try:
+    Template('${bad').substitute(bad=1)
+    assert False, 'unclosed brace should raise'
+except ValueError as e:
+    assert str(e) == 'Invalid placeholder in string: line 1, col 1', str(e)
+
+# === safe_substitute ===
+assert t.safe_substitute(who='tim') == 'tim likes $what', 'missing key left in place'
+assert Template('${who} and ${what}').safe_substitute(what='x') == '${who} and x', 'braced left in place'
+assert Template('cost: $5 and $$').safe_substitute() == 'cost: $5 and $', 'invalid placeholder left in place'
+assert Template('trailing $').safe_substitute() == 'trailing $', 'trailing delimiter'
+assert Template('${bad').safe_substitute(bad=1) == '${bad', 'unclosed brace left in place'
+
+# === type ===
+assert isinstance(t, Template), 'isinstance'
+assert isinstance(t, string.Template), 'isinstance via module attribute'
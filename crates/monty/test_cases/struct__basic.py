@@ -0,0 +1,224 @@
+# Tests for the struct module
+
+import struct
+
+# === pack ===
+assert struct.pack('<i', 1) == b'\x01\x00\x00\x00', 'little-endian int'
+assert struct.pack('>i', 1) == b'\x00\x00\x00\x01', 'big-endian int'
+assert struct.pack('!H', 0x1234) == b'\x12\x34', 'network order'
+assert struct.pack('=h', -2) == b'\xfe\xff', 'native order, standard size'
+assert struct.pack('<bBhHiIqQ', -1, 255, -1, 65535, -1, 2**32 - 1, -1, 2**64 - 1) == b'\xff' * 30, 'all integer sizes'
+assert struct.pack('<?', []) == b'\x00', 'bool packs truthiness'
+assert struct.pack('<??', True, 'x') == b'\x01\x01', 'truthy values'
+assert struct.pack('<i', True) == b'\x01\x00\x00\x00', 'bools are ints'
+assert struct.pack('<c', b'a') == b'a', 'char'
+assert struct.pack('<3s', b'abcdef') == b'abc', 's truncates'
+assert struct.pack('<3s', b'a') == b'a\x00\x00', 's pads with NULs'
+assert struct.pack('<5p', b'abcdef') == b'\x04abcd', 'pascal string'
+assert struct.pack('<0s', b'ab') == b'', 'zero-length string'
+assert struct.pack('<2xh', 1) == b'\x00\x00\x01\x00', 'pad bytes'
+assert struct.pack('<3b', 1, 2, 3) == b'\x01\x02\x03', 'repeat count'
+assert struct.pack('< i  h ', 1, 2) == b'\x01\x00\x00\x00\x02\x00', 'whitespace is ignored'
+assert struct.pack(b'<h', 1) == b'\x01\x00', 'bytes format'
+
+# === floats ===
+assert struct.pack('<d', 1.5) == b'\x00\x00\x00\x00\x00\x00\xf8?', 'double'
+assert struct.pack('>d', -0.0) == b'\x80' + b'\x00' * 7, 'negative zero'
+assert struct.pack('<f', 1.5) == b'\x00\x00\xc0?', 'single'
+assert struct.pack('<f', 2) == b'\x00\x00\x00@', 'ints pack as floats'
+assert struct.pack('<e', 1.5) == b'\x00>', 'half'
+assert struct.pack('<e', 1.00146484375) == b'\x02<', 'half rounds to even'
+assert struct.pack('<e', 65519.99) == b'\xff{', 'largest half'
+assert struct.pack('<e', 1e-08) == b'\x00\x00', 'half underflows to zero'
+assert struct.pack('<e', 6e-08) == b'\x01\x00', 'subnormal half'
+assert struct.pack('<e', float('inf')) == b'\x00|', 'infinite half'
+assert struct.pack('<f', 3.4028235e38) == b'\xff\xff\x7f\x7f', 'largest single'
+assert struct.unpack('<e', b'\x00|') == (float('inf'),), 'unpack infinite half'
+assert struct.unpack('<e', b'\x01\x00') == (5.960464477539063e-08,), 'unpack subnormal half'
+assert struct.unpack('>e', b'\xc0\x00') == (-2.0,), 'unpack big-endian half'
+assert struct.unpack('<f', b'\xcd\xcc\xcc=') == (0.10000000149011612,), 'unpack single'
+x = struct.unpack('<d', struct.pack('<d', 0.1))[0]
+assert x == 0.1, 'doubles round-trip'
+
+# === unpack ===
+assert struct.unpack('<i', b'\x01\x00\x00\x00') == (1,), 'unpack int'
+assert struct.unpack('>h', b'\xff\xfe') == (-2,), 'unpack signed'
+assert struct.unpack('>H', b'\xff\xfe') == (65534,), 'unpack unsigned'
+assert struct.unpack('<Q', b'\xff' * 8) == (2**64 - 1,), 'unpack large unsigned'
+assert struct.unpack('<q', b'\xff' * 8) == (-1,), 'unpack long long'
+assert struct.unpack('<?c3s', b'\x02abc\x00') == (True, b'a', b'bc\x00'), 'bool, char and string'
+assert struct.unpack('<5p', b'\x09abcd') == (b'abcd',), 'pascal length is clamped'
+assert struct.unpack('<0s0p', b'') == (b'', b''), 'empty strings'
+assert struct.unpack('<xbx', b'\x00\x07\x00') == (7,), 'pad bytes are skipped'
+assert struct.unpack('<', b'') == (), 'empty format'
+magic, version = struct.unpack('>4sH', b'\x89PNG\x00\x02')
+assert magic == b'\x89PNG' and version == 2, 'file header'
+
+# === native alignment ===
+assert struct.calcsize('@bi') == 8, 'native alignment'
+assert struct.calcsize('bi') == 8, 'native is the default'
+assert struct.calcsize('=bi') == 5, 'no alignment with ='
+assert struct.calcsize('ib') == 5, 'no trailing padding'
+assert struct.calcsize('@l') == 8, 'native long'
+assert struct.calcsize('<l') == 4, 'standard long'
+assert struct.calcsize('@bq') == 16, 'long long alignment'
+assert struct.calcsize('b?h') == 4, 'short alignment'
+assert struct.calcsize('@b0i') == 4, 'zero count still aligns'
+assert struct.calcsize('nNP') == 24, 'native-only codes'
+assert struct.calcsize('') == 0, 'empty format size'
+assert struct.calcsize('10s') == 10, 'string size'
+assert struct.pack('@bi', 1, 2) == b'\x01\x00\x00\x00\x02\x00\x00\x00', 'native padding is zeroed'
+assert struct.unpack('bi', b'\x01\x00\x00\x00\x02\x00\x00\x00') == (1, 2), 'native unpack'
+
+# === unpack_from and iter_unpack ===
+data = b'\x00\x01\x00\x02\x00\x03'
+assert struct.unpack_from('>h', data) == (1,), 'unpack_from default offset'
+assert struct.unpack_from('>h', data, 2) == (2,), 'unpack_from offset'
+assert struct.unpack_from('>h', data, offset=4) == (3,), 'offset keyword'
+assert struct.unpack_from('>h', data, -2) == (3,), 'negative offset'
+assert struct.unpack_from('>h', buffer=data) == (1,), 'buffer keyword'
+assert list(struct.iter_unpack('>h', data)) == [(1,), (2,), (3,)], 'iter_unpack'
+assert list(struct.iter_unpack('>h', b'')) == [], 'iter_unpack of an empty buffer'
+
+# === Struct ===
+s = struct.Struct('<ih')
+assert s.format == '<ih', 'format attribute'
+assert s.size == 6, 'size attribute'
+assert repr(s) == "Struct('<ih')", 'repr'
+assert s.pack(1, 2) == b'\x01\x00\x00\x00\x02\x00', 'Struct.pack'
+assert s.unpack(b'\x01\x00\x00\x00\x02\x00') == (1, 2), 'Struct.unpack'
+assert s.unpack_from(b'xx\x01\x00\x00\x00\x02\x00', 2) == (1, 2), 'Struct.unpack_from'
+assert list(s.iter_unpack(b'\x01\x00\x00\x00\x02\x00' * 2)) == [(1, 2), (1, 2)], 'Struct.iter_unpack'
+assert struct.Struct(b'>h').format == '>h', 'bytes format becomes a str'
+assert struct.Struct(format='q').size == 8, 'format keyword'
+assert isinstance(s, struct.Struct), 'isinstance'
+assert s != struct.Struct('<ih'), 'structs compare by identity'
+
+# === errors ===
+try:
+    struct.calcsize('z')
+    assert False, 'bad char should raise'
+except struct.error as e:
+    assert str(e) == 'bad char in struct format', f'bad char message: {e}'
+try:
+    struct.calcsize('<n')
+    assert False, 'n is native only'
+except struct.error as e:
+    assert str(e) == 'bad char in struct format', f'native-only message: {e}'
+try:
+    struct.calcsize('3 s')
+    assert False, 'count and char must be adjacent'
+except struct.error as e:
+    assert str(e) == 'bad char in struct format', f'separated count message: {e}'
+try:
+    struct.calcsize('i3')
+    assert False, 'trailing count should raise'
+except struct.error as e:
+    assert str(e) == 'repeat count given without format specifier', f'repeat count message: {e}'
+try:
+    struct.calcsize('99999999999999999999i')
+    assert False, 'huge count should raise'
+except struct.error as e:
+    assert str(e) == 'total struct size too long', f'size message: {e}'
+try:
+    struct.pack('<2i', 1)
+    assert False, 'too few values should raise'
+except struct.error as e:
+    assert str(e) == 'pack expected 2 items for packing (got 1)', f'count message: {e}'
+try:
+    struct.pack('<h', 40000)
+    assert False, 'out of range should raise'
+except struct.error as e:
+    assert str(e) == "'h' format requires -32768 <= number <= 32767", f'range message: {e}'
+try:
+    struct.pack('<B', -1)
+    assert False, 'negative unsigned should raise'
+except struct.error as e:
+    assert str(e) == "'B' format requires 0 <= number <= 255", f'unsigned range message: {e}'
+try:
+    struct.pack('<Q', 2**70)
+    assert False, 'long int out of range should raise'
+except struct.error as e:
+    assert str(e) == "'Q' format requires 0 <= number <= 18446744073709551615", f'Q range message: {e}'
+try:
+    struct.pack('@l', 2**63)
+    assert False, 'native long out of range should raise'
+except struct.error as e:
+    assert str(e) == "'l' format requires -9223372036854775808 <= number <= 9223372036854775807", f'l message: {e}'
+try:
+    struct.pack('<i', 1.0)
+    assert False, 'float for int should raise'
+except struct.error as e:
+    assert str(e) == 'required argument is not an integer', f'int type message: {e}'
+try:
+    struct.pack('<d', '1')
+    assert False, 'str for float should raise'
+except struct.error as e:
+    assert str(e) == 'required argument is not a float', f'float type message: {e}'
+try:
+    struct.pack('<c', b'ab')
+    assert False, 'long char should raise'
+except struct.error as e:
+    assert str(e) == 'char format requires a bytes object of length 1', f'char message: {e}'
+try:
+    struct.pack('<s', 'a')
+    assert False, 'str for s should raise'
+except struct.error as e:
+    assert str(e) == "argument for 's' must be a bytes object", f's message: {e}'
+try:
+    struct.pack('<e', 1e6)
+    assert False, 'large half should raise'
+except OverflowError as e:
+    assert str(e) == 'float too large to pack with e format', f'half overflow message: {e}'
+try:
+    struct.pack('<f', 1e300)
+    assert False, 'large single should raise'
+except OverflowError as e:
+    assert str(e) == 'float too large to pack with f format', f'single overflow message: {e}'
+try:
+    struct.unpack('<i', b'12')
+    assert False, 'short buffer should raise'
+except struct.error as e:
+    assert str(e) == 'unpack requires a buffer of 4 bytes', f'unpack message: {e}'
+try:
+    struct.unpack('<i', 'abcd')
+    assert False, 'str buffer should raise'
+except TypeError as e:
+    assert str(e) == "a bytes-like object is required, not 'str'", f'buffer type message: {e}'
+try:
+    struct.unpack_from('<i', b'123456', 4)
+    assert False, 'offset past the data should raise'
+except struct.error as e:
+    assert str(e) == (
+        'unpack_from requires a buffer of at least 8 bytes for unpacking 4 bytes at offset 4 (actual buffer size is 6)'
+    ), f'unpack_from message: {e}'
+try:
+    struct.unpack_from('<i', b'123456', -2)
+    assert False, 'negative offset without enough data should raise'
+except struct.error as e:
+    assert str(e) == 'not enough data to unpack 4 bytes at offset -2', f'negative offset message: {e}'
+try:
+    struct.unpack_from('<h', b'12', -10)
+    assert False, 'negative offset before the start should raise'
+except struct.error as e:
+    assert str(e) == 'offset -10 out of range for 2-byte buffer', f'offset range message: {e}'
+try:
+    struct.iter_unpack('<h', b'123')
+    assert False, 'uneven buffer should raise'
+except struct.error as e:
+    assert str(e) == 'iterative unpacking requires a buffer of a multiple of 2 bytes', f'iter message: {e}'
+try:
+    struct.iter_unpack('', b'')
+    assert False, 'empty struct should raise'
+except struct.error as e:
+    assert str(e) == 'cannot iteratively unpack with a struct of length 0', f'empty iter message: {e}'
+try:
+    struct.Struct(1)
+    assert False, 'int format should raise'
+except TypeError as e:
+    assert str(e) == 'Struct() argument 1 must be a str or bytes object, not int', f'format type message: {e}'
+try:
+    struct.pack('z', 1)
+    assert False, 'error is an Exception'
+except Exception as e:
+    assert type(e) is struct.error, 'error type'
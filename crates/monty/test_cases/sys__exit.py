@@ -0,0 +1,90 @@
+import sys
+
+# === sys.exit with a code ===
+try:
+    sys.exit(3)
+    assert False, 'sys.exit should raise'
+except SystemExit as e:
+    assert e.code == 3, 'exit code'
+    assert str(e) == '3', 'str of exit code'
+
+# === sys.exit without a code ===
+try:
+    sys.exit()
+except SystemExit as e:
+    assert e.code is None, 'no exit code'
+    assert str(e) == '', 'str without exit code'
+
+try:
+    sys.exit(None)
+except SystemExit as e:
+    assert e.code is None, 'explicit None exit code'
+
+# === sys.exit with a message ===
+try:
+    sys.exit('failed')
+except SystemExit as e:
+    assert e.code == 'failed', 'message exit code'
+    assert str(e) == 'failed', 'str of message'
+
+# === sys.exit with a str that reads as a number ===
+try:
+    sys.exit('3')
+except SystemExit as e:
+    assert e.code == '3', 'str exit code stays a str'
+    assert e.args == ('3',), 'args of str exit code'
+    assert repr(e) == "SystemExit('3')", repr(e)
+
+# === sys.exit with a bool ===
+try:
+    sys.exit(True)
+except SystemExit as e:
+    assert e.code is True, 'bool exit code stays a bool'
+    assert str(e) == 'True', 'str of bool exit code'
+    assert e.args == (True,), 'args of bool exit code'
+    assert repr(e) == 'SystemExit(True)', repr(e)
+
+try:
+    sys.exit(3)
+except SystemExit as e:
+    assert e.args == (3,), 'args of int exit code'
+    assert repr(e) == 'SystemExit(3)', repr(e)
+
+# === SystemExit is not an Exception ===
+caught = None
+try:
+    try:
+        sys.exit(1)
+    except Exception:
+        caught = 'exception'
+except BaseException:
+    caught = 'base'
+assert caught == 'base', 'SystemExit skips except Exception'
+
+# === SystemExit raised directly ===
+try:
+    raise SystemExit(2)
+except SystemExit as e:
+    assert e.code == 2, 'SystemExit(2).code'
+
+try:
+    raise SystemExit
+except SystemExit as e:
+    assert e.code is None, 'bare SystemExit code'
+
+# === finally runs on exit ===
+ran_finally = False
+try:
+    try:
+        sys.exit(0)
+    finally:
+        ran_finally = True
+except SystemExit:
+    pass
+assert ran_finally, 'finally runs when exiting'
+
+# === sys.argv is a list ===
+assert isinstance(sys.argv, list), 'argv is a list'
+
+sys.exit('bye')
+# Raise=SystemExit('bye')
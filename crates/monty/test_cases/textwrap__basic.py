@@ -0,0 +1,86 @@
+# Tests for the textwrap module
+
+import textwrap
+
+text = 'The quick brown fox jumps over the lazy dog and keeps running far away.'
+
+# === wrap ===
+assert textwrap.wrap(text, 20) == [
+    'The quick brown fox',
+    'jumps over the lazy',
+    'dog and keeps',
+    'running far away.',
+], 'wrap width 20'
+assert textwrap.wrap(text) == [text[:70].rsplit(' ', 1)[0], 'away.'], 'wrap default width 70'
+assert textwrap.wrap('') == [], 'wrap empty'
+assert textwrap.wrap('   ') == [], 'wrap whitespace only'
+assert textwrap.wrap('a  b', width=10) == ['a  b'], 'inner whitespace kept'
+assert textwrap.wrap('  leading', width=20) == ['  leading'], 'leading whitespace kept on first line'
+assert textwrap.wrap('one two three', width=8, initial_indent='* ', subsequent_indent='  ') == [
+    '* one',
+    '  two',
+    '  three',
+], 'indents'
+assert textwrap.wrap('abcdefghij', width=4) == ['abcd', 'efgh', 'ij'], 'long word broken'
+assert textwrap.wrap('abcdefghij xy', width=4, break_long_words=False) == ['abcdefghij', 'xy'], 'long word kept'
+assert textwrap.wrap('a\tb', width=20) == ['a       b'], 'tabs expanded'
+assert textwrap.wrap('a\tb', width=20, tabsize=4) == ['a   b'], 'tabsize'
+assert textwrap.wrap('a\nb\nc', width=20) == ['a b c'], 'newlines replaced'
+assert textwrap.wrap('a b ', width=3, drop_whitespace=False) == ['a b', ' '], 'drop_whitespace=False'
+
+# === hyphens ===
+assert textwrap.wrap('a well-known example', width=12) == ['a well-known', 'example'], 'hyphenated fits'
+assert textwrap.wrap('a well-known example', width=8) == ['a well-', 'known', 'example'], 'break after hyphen'
+assert textwrap.wrap('a well-known example', width=8, break_on_hyphens=False) == [
+    'a well-k',
+    'nown',
+    'example',
+], 'break_on_hyphens=False'
+assert textwrap.wrap('this--that and more', width=6) == ['this--', 'that', 'and', 'more'], 'em-dash'
+
+# === sentence endings ===
+assert textwrap.fill('Hi there. How are you?', fix_sentence_endings=True) == 'Hi there.  How are you?', (
+    'fix_sentence_endings'
+)
+
+# === max_lines ===
+assert textwrap.wrap(text, width=20, max_lines=2) == ['The quick brown fox', 'jumps over the [...]'], 'max_lines'
+assert textwrap.wrap(text, width=20, max_lines=2, placeholder='...') == [
+    'The quick brown fox',
+    'jumps over the...',
+], 'placeholder'
+try:
+    textwrap.wrap(text, width=4, max_lines=1)
+    assert False, 'placeholder too large should raise'
+except ValueError as e:
+    assert str(e) == 'placeholder too large for max width', str(e)
+try:
+    textwrap.wrap(text, width=0)
+    assert False, 'zero width should raise'
+except ValueError as e:
+    assert str(e) == 'invalid width 0 (must be > 0)', str(e)
+
+# === fill ===
+assert textwrap.fill(text, 20) == '\n'.join(textwrap.wrap(text, 20)), 'fill joins wrap'
+assert textwrap.fill('') == '', 'fill empty'
+
+# === shorten ===
+assert textwrap.shorten('Hello  world!', width=12) == 'Hello world!', 'shorten fits'
+assert textwrap.shorten('Hello  world!', width=11) == 'Hello [...]', 'shorten truncates'
+assert textwrap.shorten('Hello world', width=10, placeholder='...') == 'Hello...', 'shorten placeholder'
+assert textwrap.shorten('  spaced \n out  ', width=20) == 'spaced out', 'shorten collapses whitespace'
+
+# === dedent ===
+assert textwrap.dedent('    a\n      b\n    c') == 'a\n  b\nc', 'dedent common margin'
+assert textwrap.dedent('  a\n\n  b\n') == 'a\n\nb\n', 'dedent ignores blank lines'
+assert textwrap.dedent('  a\n    \n  b') == 'a\n\nb', 'dedent normalizes whitespace-only lines'
+assert textwrap.dedent('\ta\n  b') == '\ta\n  b', 'dedent tabs and spaces differ'
+assert textwrap.dedent('no indent\n  here') == 'no indent\n  here', 'dedent nothing common'
+assert textwrap.dedent('') == '', 'dedent empty'
+
+# === indent ===
+assert textwrap.indent('a\nb\n', '> ') == '> a\n> b\n', 'indent'
+assert textwrap.indent('a\n\n  \nb', '+') == '+a\n\n  \n+b', 'indent skips blank lines'
+assert textwrap.indent('a\r\nb', '-') == '-a\r\n-b', 'indent keeps line endings'
+assert textwrap.indent('a\n\nb', '#', lambda line: True) == '#a\n#\n#b', 'indent predicate'
+assert textwrap.indent('x\nyy\nz', '*', lambda line: len(line.strip()) > 1) == 'x\n*yy\nz', 'indent selective predicate'
@@ -0,0 +1,37 @@
+# Tests for the unicodedata module
+
+import unicodedata
+
+decomposed = 'é'
+composed = 'é'
+
+# === normalize ===
+assert unicodedata.normalize('NFC', decomposed) == composed, 'NFC composes'
+assert unicodedata.normalize('NFD', composed) == decomposed, 'NFD decomposes'
+assert unicodedata.normalize('NFKC', 'ﬁ') == 'fi', 'NFKC compatibility ligature'
+assert unicodedata.normalize('NFKD', '①') == '1', 'NFKD circled digit'
+assert unicodedata.normalize('NFC', 'ﬁ') == 'ﬁ', 'NFC keeps ligature'
+assert unicodedata.normalize('NFKC', 'x²') == 'x2', 'NFKC superscript'
+assert unicodedata.normalize('NFD', '가') == '가', 'NFD hangul'
+assert unicodedata.normalize('NFC', '가') == '가', 'NFC hangul'
+assert unicodedata.normalize('NFC', 'plain ascii') == 'plain ascii', 'ascii unchanged'
+assert unicodedata.normalize('NFC', '') == '', 'empty'
+assert len(unicodedata.normalize('NFD', 'Å')) == 2, 'NFD length'
+
+# === is_normalized ===
+assert unicodedata.is_normalized('NFC', composed), 'composed is NFC'
+assert not unicodedata.is_normalized('NFC', decomposed), 'decomposed is not NFC'
+assert unicodedata.is_normalized('NFD', decomposed), 'decomposed is NFD'
+assert not unicodedata.is_normalized('NFKC', 'ﬁ'), 'ligature is not NFKC'
+
+# === errors ===
+try:
+    unicodedata.normalize('nfc', 'x')
+    assert False, 'lowercase form should raise'
+except ValueError as e:
+    assert str(e) == 'invalid normalization form', str(e)
+try:
+    unicodedata.normalize('NFC', 1)
+    assert False, 'non-str should raise'
+except TypeError as e:
+    assert str(e) == 'normalize() argument 2 must be str, not int', str(e)
@@ -0,0 +1,130 @@
+# Tests for the uuid module
+
+import uuid
+
+# === parsing ===
+u = uuid.UUID('12345678-1234-5678-1234-567812345678')
+assert str(u) == '12345678-1234-5678-1234-567812345678', 'str is the hyphenated form'
+assert repr(u) == "UUID('12345678-1234-5678-1234-567812345678')", 'repr'
+assert uuid.UUID('{12345678-1234-5678-1234-567812345678}') == u, 'braces are stripped'
+assert uuid.UUID('12345678123456781234567812345678') == u, 'hyphens are optional'
+assert uuid.UUID('urn:uuid:12345678-1234-5678-1234-567812345678') == u, 'urn prefix'
+assert uuid.UUID('12345678-1234-5678-1234-567812345678'.upper()) == u, 'upper case hex'
+assert uuid.UUID(hex='12345678123456781234567812345678') == u, 'hex keyword'
+assert f'{u}' == '12345678-1234-5678-1234-567812345678', 'f-string uses str'
+
+# === other constructors ===
+assert uuid.UUID(bytes=b'\x12\x34\x56\x78' * 4) == u, 'from bytes'
+assert uuid.UUID(bytes_le=b'xV4\x124\x12xV\x124Vx\x124Vx') == u, 'from bytes_le'
+assert uuid.UUID(fields=(0x12345678, 0x1234, 0x5678, 0x12, 0x34, 0x567812345678)) == u, 'from fields'
+assert uuid.UUID(int=0x12345678123456781234567812345678) == u, 'from int'
+assert uuid.UUID(int=0).int == 0, 'nil UUID'
+assert str(uuid.UUID(int=0)) == '00000000-0000-0000-0000-000000000000', 'nil UUID str'
+v = uuid.UUID(int=0, version=4)
+assert v.version == 4, 'version argument sets the version'
+assert v.variant == uuid.RFC_4122, 'version argument sets the variant'
+
+# === attributes ===
+assert u.hex == '12345678123456781234567812345678', 'hex'
+assert u.int == 24197857161011715162171839636988778104, 'int'
+assert u.bytes == b'\x12\x34\x56\x78' * 4, 'bytes'
+assert u.bytes_le == b'xV4\x124\x12xV\x124Vx\x124Vx', 'bytes_le'
+assert u.urn == 'urn:uuid:12345678-1234-5678-1234-567812345678', 'urn'
+assert u.fields == (305419896, 4660, 22136, 18, 52, 95073701484152), 'fields'
+assert u.time_low == 0x12345678, 'time_low'
+assert u.time_mid == 0x1234, 'time_mid'
+assert u.time_hi_version == 0x5678, 'time_hi_version'
+assert u.clock_seq_hi_variant == 0x12, 'clock_seq_hi_variant'
+assert u.clock_seq_low == 0x34, 'clock_seq_low'
+assert u.node == 0x567812345678, 'node'
+assert u.time == 466142576285865592, 'time'
+assert u.clock_seq == 4660, 'clock_seq'
+assert u.variant == uuid.RESERVED_NCS, 'NCS variant'
+assert u.version is None, 'no version outside RFC 4122'
+assert uuid.UUID(int=0xC << 60).variant == uuid.RESERVED_MICROSOFT, 'Microsoft variant'
+assert uuid.UUID(int=0xE << 60).variant == uuid.RESERVED_FUTURE, 'future variant'
+assert uuid.RFC_4122 == 'specified in RFC 4122', 'RFC_4122 constant'
+assert uuid.RESERVED_NCS == 'reserved for NCS compatibility', 'RESERVED_NCS constant'
+
+# === name-based UUIDs ===
+assert str(uuid.uuid3(uuid.NAMESPACE_DNS, 'python.org')) == '6fa459ea-ee8a-3ca4-894e-db77e160355e', 'uuid3'
+assert str(uuid.uuid5(uuid.NAMESPACE_DNS, 'python.org')) == '886313e1-3b8a-5372-9b90-0c9aee199e5d', 'uuid5'
+assert str(uuid.uuid5(uuid.NAMESPACE_URL, b'http://x')) == 'c059942f-567e-54eb-aeb5-5fd9830bce43', 'uuid5 with bytes'
+assert uuid.uuid3(uuid.NAMESPACE_DNS, 'python.org').version == 3, 'uuid3 version'
+assert uuid.uuid5(uuid.NAMESPACE_OID, 'x').version == 5, 'uuid5 version'
+assert uuid.uuid5(uuid.NAMESPACE_X500, 'x').variant == uuid.RFC_4122, 'uuid5 variant'
+assert uuid.uuid5(uuid.NAMESPACE_DNS, 'a') == uuid.uuid5(uuid.NAMESPACE_DNS, 'a'), 'uuid5 is deterministic'
+assert uuid.uuid5(uuid.NAMESPACE_DNS, 'a') != uuid.uuid5(uuid.NAMESPACE_URL, 'a'), 'namespace matters'
+assert str(uuid.NAMESPACE_DNS) == '6ba7b810-9dad-11d1-80b4-00c04fd430c8', 'NAMESPACE_DNS'
+assert str(uuid.NAMESPACE_X500) == '6ba7b814-9dad-11d1-80b4-00c04fd430c8', 'NAMESPACE_X500'
+
+# === comparison and hashing ===
+a = uuid.UUID(int=1)
+b = uuid.UUID(int=2)
+assert a < b, 'less than'
+assert b >= a, 'greater or equal'
+assert a != b, 'not equal'
+assert a == uuid.UUID(int=1), 'equal'
+assert a != 1, 'not equal to int'
+assert sorted([b, a]) == [a, b], 'sorting'
+assert hash(a) == hash(uuid.UUID(int=1)), 'equal UUIDs hash equal'
+assert len({a, b, uuid.UUID(int=1)}) == 2, 'set deduplicates'
+assert {a: 'x'}[uuid.UUID(int=1)] == 'x', 'dict key'
+assert isinstance(a, uuid.UUID), 'isinstance'
+assert type(a) is uuid.UUID, 'type'
+assert bool(uuid.UUID(int=0)), 'UUIDs are truthy'
+
+# === errors ===
+try:
+    uuid.UUID('1234')
+    assert False, 'short hex should raise'
+except ValueError as e:
+    assert str(e) == 'badly formed hexadecimal UUID string', 'short hex message'
+
+try:
+    uuid.UUID()
+    assert False, 'no arguments should raise'
+except TypeError as e:
+    assert str(e) == 'one of the hex, bytes, bytes_le, fields, or int arguments must be given', 'no arguments message'
+
+try:
+    uuid.UUID(bytes=b'abc')
+    assert False, 'short bytes should raise'
+except ValueError as e:
+    assert str(e) == 'bytes is not a 16-char string', 'short bytes message'
+
+try:
+    uuid.UUID(int=-1)
+    assert False, 'negative int should raise'
+except ValueError as e:
+    assert str(e) == 'int is out of range (need a 128-bit value)', 'negative int message'
+
+try:
+    uuid.UUID(int=1, version=9)
+    assert False, 'bad version should raise'
+except ValueError as e:
+    assert str(e) == 'illegal version number', 'bad version message'
+
+try:
+    uuid.UUID(fields=(1, 2, 3))
+    assert False, 'short fields should raise'
+except ValueError as e:
+    assert str(e) == 'fields is not a 6-tuple', 'short fields message'
+
+try:
+    uuid.uuid3('x', 'y')
+    assert False, 'non-UUID namespace should raise'
+except AttributeError as e:
+    assert str(e) == "'str' object has no attribute 'bytes'", 'namespace message'
+
+try:
+    uuid.uuid5(uuid.NAMESPACE_DNS, 5)
+    assert False, 'int name should raise'
+except TypeError as e:
+    assert str(e) == "can't concat int to bytes", 'name message'
+
+try:
+    uuid.UUID(int=1) < 1
+    assert False, 'ordering against int should raise'
+except TypeError:
+    pass
@@ -0,0 +1,34 @@
+# call-external
+# Tests for uuid.uuid4() and os.urandom(), which get random bytes from the host
+
+import os
+import uuid
+
+# === uuid4 ===
+a = uuid.uuid4()
+b = uuid.uuid4()
+assert isinstance(a, uuid.UUID), 'uuid4 returns a UUID'
+assert a.version == 4, 'uuid4 version'
+assert a.variant == uuid.RFC_4122, 'uuid4 variant'
+assert a != b, 'uuid4 values differ'
+assert len(str(a)) == 36, 'uuid4 str length'
+assert str(a)[14] == '4', 'version digit in str'
+assert uuid.UUID(str(a)) == a, 'uuid4 round-trips through str'
+
+# === os.urandom ===
+data = os.urandom(8)
+assert isinstance(data, bytes), 'urandom returns bytes'
+assert len(data) == 8, 'urandom length'
+assert os.urandom(0) == b'', 'urandom(0) is empty'
+
+try:
+    os.urandom(-1)
+    assert False, 'negative size should raise'
+except ValueError as e:
+    assert str(e) == 'negative argument not allowed', 'negative size message'
+
+try:
+    os.urandom('a')
+    assert False, 'str size should raise'
+except TypeError as e:
+    assert str(e) == "'str' object cannot be interpreted as an integer", 'str size message'
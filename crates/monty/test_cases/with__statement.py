@@ -0,0 +1,86 @@
+import io
+
+# === with binds the result of __enter__ ===
+with io.StringIO('abc') as s:
+    assert s.read() == 'abc', 'as target is the StringIO'
+assert s.closed, '__exit__ closes StringIO'
+
+# === no as target ===
+buf = io.StringIO()
+with buf:
+    buf.write('x')
+assert buf.closed, 'with without as'
+
+# === multiple items ===
+with io.StringIO('a') as a, io.StringIO('b') as b:
+    assert a.read() + b.read() == 'ab', 'multiple items'
+assert a.closed and b.closed, 'multiple items closed'
+
+# === parenthesised target ===
+a = io.StringIO('one')
+with a as (first):
+    assert first is a, 'parenthesised target'
+
+
+def make():
+    with io.StringIO('in function') as s:
+        return s.read()
+
+
+assert make() == 'in function', 'return inside with'
+
+# === exceptions are not suppressed by StringIO ===
+s = io.StringIO()
+try:
+    with s:
+        raise ValueError('inner')
+    assert False, 'exception should propagate'
+except ValueError as e:
+    assert str(e) == 'inner', 'exception propagates'
+assert s.closed, 'closed after exception'
+
+# === break and continue inside with ===
+count = 0
+for i in range(3):
+    with io.StringIO() as s:
+        if i == 1:
+            continue
+        count += 1
+    assert s.closed, 'closed after continue'
+assert count == 2, 'continue inside with'
+
+for i in range(3):
+    with io.StringIO() as s:
+        break
+assert s.closed, 'closed after break'
+
+# === nested with ===
+with io.StringIO('x') as outer:
+    with io.StringIO('y') as inner:
+        assert outer.read() + inner.read() == 'xy', 'nested'
+    assert inner.closed and not outer.closed, 'inner closed first'
+assert outer.closed, 'outer closed'
+
+# === entering a closed StringIO fails ===
+try:
+    with s:
+        pass
+    assert False, 'closed StringIO should not enter'
+except ValueError as e:
+    assert str(e) == 'I/O operation on closed file.', 'enter closed'
+
+# === with doesn't add names to the namespace ===
+with io.StringIO() as s:
+    assert '$with0_mgr' not in globals(), 'no manager global inside with'
+assert '$with0_mgr' not in globals(), 'no manager global'
+assert not [name for name in globals() if name.startswith('$')], 'no hidden globals'
+assert not [name for name in dir() if name.startswith('$')], 'no hidden names in dir()'
+
+
+def with_locals():
+    with io.StringIO() as f:
+        pass
+    return sorted(locals())
+
+
+assert with_locals() == ['f'], 'no hidden locals'
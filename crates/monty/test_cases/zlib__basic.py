@@ -0,0 +1,85 @@
+# Tests for the zlib module
+
+import zlib
+
+# === round trips ===
+data = b'hello world ' * 100
+assert zlib.decompress(zlib.compress(data)) == data, 'zlib round trip'
+assert zlib.decompress(zlib.compress(b'')) == b'', 'empty round trip'
+assert len(zlib.compress(data)) < len(data), 'repetitive data shrinks'
+for level in range(-1, 10):
+    assert zlib.decompress(zlib.compress(data, level)) == data, f'round trip at level {level}'
+assert zlib.decompress(zlib.compress(data, wbits=-15), wbits=-15) == data, 'raw deflate round trip'
+assert zlib.decompress(zlib.compress(data, wbits=31), wbits=31) == data, 'gzip container round trip'
+assert zlib.compress(data)[:2] == b'x\x9c', 'zlib header for the default level'
+assert zlib.compress(data, wbits=31)[:3] == b'\x1f\x8b\x08', 'gzip header magic and method'
+
+# === decompressing CPython output ===
+compressed = b'x\x9c\xcbH\xcd\xc9\xc9W(\xcf/\xcaI\x01\x00\x1a\x0b\x04]'
+assert zlib.decompress(compressed) == b'hello world', 'decompress zlib stream'
+assert zlib.decompress(compressed, 47) == b'hello world', 'auto-detect zlib header'
+assert zlib.decompress(compressed + b'trailing') == b'hello world', 'trailing data is ignored'
+raw = b'\xcbH\xcd\xc9\xc9W(\xcf/\xcaI\x01\x00'
+assert zlib.decompress(raw, -15) == b'hello world', 'decompress raw stream'
+gz = b'\x1f\x8b\x08\x00\x00\x00\x00\x00\x00\x03\xcbH\xcd\xc9\xc9W(\xcf/\xcaI\x01\x00\x85\x11J\r\x0b\x00\x00\x00'
+assert zlib.decompress(gz, 31) == b'hello world', 'decompress gzip stream'
+assert zlib.decompress(gz, 47) == b'hello world', 'auto-detect gzip header'
+
+# === errors ===
+try:
+    zlib.decompress(b'xx')
+    assert False, 'bad header should raise'
+except zlib.error as e:
+    assert str(e) == 'Error -3 while decompressing data: incorrect header check', 'header error message'
+
+try:
+    zlib.decompress(compressed[:-1])
+    assert False, 'truncated stream should raise'
+except zlib.error as e:
+    assert str(e) == 'Error -5 while decompressing data: incomplete or truncated stream', 'truncated message'
+
+try:
+    zlib.decompress(b'x\x9cKLJ\x06\x00\x02M\x01\x00')
+    assert False, 'bad checksum should raise'
+except zlib.error as e:
+    assert str(e) == 'Error -3 while decompressing data: incorrect data check', 'checksum message'
+
+try:
+    zlib.compress(b'a', 10)
+    assert False, 'bad level should raise'
+except zlib.error as e:
+    assert str(e) == 'Bad compression level', 'level error message'
+
+try:
+    zlib.decompress(compressed, 100)
+    assert False, 'bad wbits should raise'
+except zlib.error as e:
+    assert str(e) == 'Error -2 while preparing to decompress data: inconsistent stream state', 'wbits message'
+
+try:
+    zlib.compress('text')
+    assert False, 'str should raise'
+except TypeError as e:
+    assert str(e) == "a bytes-like object is required, not 'str'", 'str error message'
+
+assert issubclass(zlib.error, Exception), 'zlib.error is an Exception'
+
+# === checksums ===
+assert zlib.crc32(b'hello') == 907060870, 'crc32'
+assert zlib.crc32(b'hello', 5) == 4277152246, 'crc32 with start value'
+assert zlib.crc32(b'') == 0, 'crc32 of empty bytes'
+assert zlib.adler32(b'hello') == 103547413, 'adler32'
+assert zlib.adler32(b'hello', 5) == 104858137, 'adler32 with start value'
+assert zlib.adler32(b'') == 1, 'adler32 of empty bytes'
+assert zlib.adler32(b'world', zlib.adler32(b'hello ')) == zlib.adler32(b'hello world'), 'adler32 running value'
+assert zlib.adler32(b'\xff' * 10000) == 3055807275, 'adler32 over a long input'
+
+# === constants ===
+assert zlib.MAX_WBITS == 15, 'MAX_WBITS'
+assert zlib.DEFLATED == 8, 'DEFLATED'
+assert zlib.DEF_BUF_SIZE == 16384, 'DEF_BUF_SIZE'
+assert zlib.DEF_MEM_LEVEL == 8, 'DEF_MEM_LEVEL'
+assert zlib.Z_NO_COMPRESSION == 0, 'Z_NO_COMPRESSION'
+assert zlib.Z_BEST_SPEED == 1, 'Z_BEST_SPEED'
+assert zlib.Z_BEST_COMPRESSION == 9, 'Z_BEST_COMPRESSION'
+assert zlib.Z_DEFAULT_COMPRESSION == -1, 'Z_DEFAULT_COMPRESSION'
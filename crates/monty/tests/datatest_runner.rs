@@ -8,6 +8,7 @@ use std::{
     path::Path,
     sync::{
         OnceLock,
+        atomic::{AtomicU64, Ordering},
         mpsc::{self, RecvTimeoutError},
     },
     thread,
@@ -663,6 +664,21 @@ struct MutableVfs {
     deleted_files: HashSet<String>,
     /// Directories deleted during test execution.
     deleted_dirs: HashSet<String>,
+    /// Files opened with `open()`, by handle.
+    open_files: HashMap<i64, OpenVirtualFile>,
+    /// The last handle returned by `OsFunction::Open`.
+    last_handle: i64,
+}
+
+/// A file opened with `OsFunction::Open`, whose contents are stored back when it's closed.
+struct OpenVirtualFile {
+    path: String,
+    content: Vec<u8>,
+    /// Byte offset of the next read or write.
+    position: usize,
+    binary: bool,
+    writable: bool,
+    append: bool,
 }
 
 thread_local! {
@@ -758,6 +774,14 @@ fn get_virtual_dir_entries(path: &str) -> Option<Vec<String>> {
     })
 }
 
+/// Helper to get a kwarg by name.
+fn get_kwarg<'a>(kwargs: &'a [(MontyObject, MontyObject)], name: &str) -> Option<&'a MontyObject> {
+    kwargs
+        .iter()
+        .find(|(key, _)| matches!(key, MontyObject::String(key_str) if key_str == name))
+        .map(|(_, value)| value)
+}
+
 /// Helper to get a boolean kwarg by name.
 fn get_kwarg_bool(kwargs: &[(MontyObject, MontyObject)], name: &str) -> bool {
     for (key, value) in kwargs {
@@ -770,6 +794,25 @@ fn get_kwarg_bool(kwargs: &[(MontyObject, MontyObject)], name: &str) -> bool {
     false
 }
 
+/// Returns `size` pseudo-random bytes for `os.urandom()`.
+///
+/// A splitmix64 sequence over a global counter: distinct across calls (so `uuid4()` values
+/// differ), but with no real entropy involved.
+fn pseudo_random_bytes(size: usize) -> Vec<u8> {
+    static STATE: AtomicU64 = AtomicU64::new(0);
+    let mut bytes = Vec::with_capacity(size);
+    while bytes.len() < size {
+        let mut z = STATE
+            .fetch_add(0x9e37_79b9_7f4a_7c15, Ordering::Relaxed)
+            .wrapping_add(0x9e37_79b9_7f4a_7c15);
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^= z >> 31;
+        bytes.extend(z.to_le_bytes().into_iter().take(size - bytes.len()));
+    }
+    bytes
+}
+
 /// Dispatches an OS function call using the virtual filesystem.
 ///
 /// Returns an `ExternalResult` to pass back to the Monty interpreter.
@@ -800,6 +843,41 @@ fn dispatch_os_call(
         return MontyObject::Dict(env_dict.into()).into();
     }
 
+    // Urandom takes a size, not a path
+    if function == OsFunction::Urandom {
+        let MontyObject::Int(size) = args[0] else {
+            panic!("os.urandom: size must be an int, got {:?}", args[0]);
+        };
+        return MontyObject::Bytes(pseudo_random_bytes(
+            usize::try_from(size).expect("size is non-negative"),
+        ))
+        .into();
+    }
+
+    // Input takes a prompt, echoed back as the line read; an empty prompt means the input has run out
+    if function == OsFunction::Input {
+        let MontyObject::String(prompt) = &args[0] else {
+            panic!("input: prompt must be a str, got {:?}", args[0]);
+        };
+        if prompt.is_empty() {
+            return MontyException::new(ExcType::EOFError, Some("EOF when reading a line".to_owned())).into();
+        }
+        return MontyObject::String(format!("{prompt}\n")).into();
+    }
+
+    // The file.* functions take a handle, not a path
+    if matches!(
+        function,
+        OsFunction::FileRead
+            | OsFunction::FileReadline
+            | OsFunction::FileWrite
+            | OsFunction::FileSeek
+            | OsFunction::FileTell
+            | OsFunction::FileClose
+    ) {
+        return dispatch_file_call(function, args);
+    }
+
     // Extract path from MontyObject::Path (or String for backwards compatibility)
     let path = match &args[0] {
         MontyObject::Path(p) => p.clone(),
@@ -808,7 +886,15 @@ fn dispatch_os_call(
     };
 
     match function {
-        OsFunction::GetEnviron => unreachable!("handled above"),
+        OsFunction::GetEnviron
+        | OsFunction::Urandom
+        | OsFunction::FileRead
+        | OsFunction::FileReadline
+        | OsFunction::FileWrite
+        | OsFunction::FileSeek
+        | OsFunction::FileTell
+        | OsFunction::FileClose
+        | OsFunction::Input => unreachable!("handled above"),
         OsFunction::Exists => {
             let exists = get_virtual_file(&path).is_some() || is_virtual_dir(&path);
             MontyObject::Bool(exists).into()
@@ -1006,6 +1092,147 @@ fn dispatch_os_call(
                 .into()
             }
         }
+        OsFunction::Glob | OsFunction::Rglob => {
+            let pattern = String::try_from(&args[1]).expect("glob: second arg must be pattern string");
+            let pattern = if function == OsFunction::Rglob {
+                format!("**/{pattern}")
+            } else {
+                pattern
+            };
+            let pattern_parts: Vec<&str> = pattern.split('/').filter(|part| !part.is_empty()).collect();
+            let prefix = format!("{path}/");
+            let matches: Vec<MontyObject> = virtual_descendants(&path)
+                .into_iter()
+                .filter(|entry| {
+                    let relative: Vec<&str> = entry[prefix.len()..].split('/').collect();
+                    glob_match(&pattern_parts, &relative)
+                })
+                .map(MontyObject::Path)
+                .collect();
+            MontyObject::List(matches).into()
+        }
+        OsFunction::Touch => {
+            if get_virtual_file(&path).is_some() || is_virtual_dir(&path) {
+                if get_kwarg(kwargs, "exist_ok").is_some_and(|v| matches!(v, MontyObject::Bool(false))) {
+                    return virtual_os_error(ExcType::FileExistsError, 17, "File exists", &path);
+                }
+                return MontyObject::None.into();
+            }
+            if !is_virtual_dir(&parent_path(&path)) {
+                return virtual_os_error(ExcType::FileNotFoundError, 2, "No such file or directory", &path);
+            }
+            MUTABLE_VFS.with(|vfs| {
+                let mut vfs = vfs.borrow_mut();
+                vfs.files.insert(path.clone(), (Vec::new(), 0o644));
+                vfs.deleted_files.remove(&path);
+            });
+            MontyObject::None.into()
+        }
+        OsFunction::Samefile => {
+            let other = match &args[1] {
+                MontyObject::Path(p) => p.clone(),
+                MontyObject::String(s) => s.clone(),
+                other => panic!("samefile: second arg must be path, got {other:?}"),
+            };
+            for p in [&path, &other] {
+                if get_virtual_file(p).is_none() && !is_virtual_dir(p) {
+                    return virtual_os_error(ExcType::FileNotFoundError, 2, "No such file or directory", p);
+                }
+            }
+            MontyObject::Bool(path == other).into()
+        }
+        OsFunction::Walk => {
+            let topdown = get_kwarg(kwargs, "topdown").is_none_or(|v| matches!(v, MontyObject::Bool(true)));
+            let mut walk = Vec::new();
+            if is_virtual_dir(&path) {
+                virtual_walk(&path, topdown, &mut walk);
+            }
+            MontyObject::List(walk).into()
+        }
+        OsFunction::Copy => {
+            let mut dest = match &args[1] {
+                MontyObject::Path(p) => p.clone(),
+                other => panic!("copy: second arg must be path, got {other:?}"),
+            };
+            if is_virtual_dir(&path) {
+                return virtual_os_error(ExcType::IsADirectoryError, 21, "Is a directory", &path);
+            }
+            let Some(file) = get_virtual_file(&path) else {
+                return virtual_os_error(ExcType::FileNotFoundError, 2, "No such file or directory", &path);
+            };
+            if is_virtual_dir(&dest) {
+                let name = path.rsplit('/').next().unwrap_or_default();
+                dest = format!("{dest}/{name}");
+            }
+            if !is_virtual_dir(&parent_path(&dest)) {
+                return virtual_os_error(ExcType::FileNotFoundError, 2, "No such file or directory", &dest);
+            }
+            MUTABLE_VFS.with(|vfs| {
+                let mut vfs = vfs.borrow_mut();
+                vfs.files.insert(dest.clone(), (file.content, file.mode));
+                vfs.deleted_files.remove(&dest);
+            });
+            MontyObject::String(dest).into()
+        }
+        OsFunction::Copytree => {
+            let dest = match &args[1] {
+                MontyObject::Path(p) => p.clone(),
+                other => panic!("copytree: second arg must be path, got {other:?}"),
+            };
+            if !is_virtual_dir(&path) {
+                return virtual_os_error(ExcType::FileNotFoundError, 2, "No such file or directory", &path);
+            }
+            let dirs_exist_ok = get_kwarg_bool(kwargs, "dirs_exist_ok");
+            if (is_virtual_dir(&dest) && !dirs_exist_ok) || get_virtual_file(&dest).is_some() {
+                return virtual_os_error(ExcType::FileExistsError, 17, "File exists", &dest);
+            }
+            create_parent_dirs(&dest);
+            for entry in virtual_descendants(&path) {
+                let target = format!("{dest}{}", &entry[path.len()..]);
+                if is_virtual_dir(&entry) {
+                    create_parent_dirs(&target);
+                } else if let Some(file) = get_virtual_file(&entry) {
+                    MUTABLE_VFS.with(|vfs| {
+                        let mut vfs = vfs.borrow_mut();
+                        vfs.deleted_files.remove(&target);
+                        vfs.files.insert(target, (file.content, file.mode));
+                    });
+                }
+            }
+            MontyObject::String(dest).into()
+        }
+        OsFunction::Rmtree => {
+            let ignore_errors = get_kwarg_bool(kwargs, "ignore_errors");
+            if !is_virtual_dir(&path) {
+                if ignore_errors {
+                    return MontyObject::None.into();
+                }
+                if get_virtual_file(&path).is_some() {
+                    return virtual_os_error(ExcType::NotADirectoryError, 20, "Not a directory", &path);
+                }
+                return virtual_os_error(ExcType::FileNotFoundError, 2, "No such file or directory", &path);
+            }
+            let mut entries = virtual_descendants(&path);
+            entries.push(path);
+            for entry in entries {
+                let is_dir = is_virtual_dir(&entry);
+                MUTABLE_VFS.with(|vfs| {
+                    let mut vfs = vfs.borrow_mut();
+                    if is_dir {
+                        vfs.dirs.remove(&entry);
+                        vfs.deleted_dirs.insert(entry);
+                    } else {
+                        vfs.files.remove(&entry);
+                        vfs.deleted_files.insert(entry);
+                    }
+                });
+            }
+            MontyObject::None.into()
+        }
+        OsFunction::Open => {
+            let mode = String::try_from(&args[1]).expect("open: second arg must be mode string");
+            open_virtual_file(path, &mode)
+        }
         OsFunction::Rename => {
             // args[0] is src path, args[1] is dest path
             let dest = match &args[1] {
@@ -1043,6 +1270,146 @@ fn dispatch_os_call(
     }
 }
 
+/// Opens a virtual file for `OsFunction::Open`, returning its handle.
+///
+/// `mode` is the normalized mode Monty passes, e.g. `r`, `w+` or `ab`.
+fn open_virtual_file(path: String, mode: &str) -> ExtFunctionResult {
+    if is_virtual_dir(&path) {
+        return MontyException::new(
+            ExcType::IsADirectoryError,
+            Some(format!("[Errno 21] Is a directory: '{path}'")),
+        )
+        .into();
+    }
+    let access = mode.as_bytes()[0];
+    let content = match (access, get_virtual_file(&path)) {
+        (b'r', None) => {
+            return MontyException::new(
+                ExcType::FileNotFoundError,
+                Some(format!("[Errno 2] No such file or directory: '{path}'")),
+            )
+            .into();
+        }
+        (b'x', Some(_)) => {
+            return MontyException::new(
+                ExcType::FileExistsError,
+                Some(format!("[Errno 17] File exists: '{path}'")),
+            )
+            .into();
+        }
+        (b'r' | b'a', Some(file)) => file.content,
+        _ => Vec::new(),
+    };
+    MUTABLE_VFS.with(|vfs| {
+        let mut vfs = vfs.borrow_mut();
+        if access != b'r' {
+            // like a real open(), creating or truncating happens straight away
+            vfs.files.insert(path.clone(), (content.clone(), 0o644));
+            vfs.deleted_files.remove(&path);
+        }
+        vfs.last_handle += 1;
+        let handle = vfs.last_handle;
+        let file = OpenVirtualFile {
+            path,
+            position: if access == b'a' { content.len() } else { 0 },
+            content,
+            binary: mode.ends_with('b'),
+            writable: access != b'r' || mode.contains('+'),
+            append: access == b'a',
+        };
+        vfs.open_files.insert(handle, file);
+        MontyObject::Int(handle).into()
+    })
+}
+
+/// Dispatches the `file.*` OS functions, which operate on a handle from `open_virtual_file`.
+fn dispatch_file_call(function: OsFunction, args: &[MontyObject]) -> ExtFunctionResult {
+    let MontyObject::Int(handle) = args[0] else {
+        panic!("{function}: first arg must be a handle, got {:?}", args[0]);
+    };
+    let int_arg = |index: usize| -> i64 {
+        i64::try_from(&args[index]).unwrap_or_else(|_| panic!("{function}: arg {index} must be an int"))
+    };
+    let to_int = |n: usize| MontyObject::Int(i64::try_from(n).expect("virtual files are small"));
+    MUTABLE_VFS.with(|vfs| {
+        let mut vfs = vfs.borrow_mut();
+        if function == OsFunction::FileClose {
+            let file = vfs.open_files.remove(&handle).expect("unknown file handle");
+            if file.writable {
+                vfs.files.insert(file.path.clone(), (file.content, 0o644));
+                vfs.deleted_files.remove(&file.path);
+            }
+            return MontyObject::None.into();
+        }
+        let file = vfs.open_files.get_mut(&handle).expect("unknown file handle");
+        match function {
+            OsFunction::FileRead | OsFunction::FileReadline => {
+                let size = int_arg(1);
+                // reading past the end (after a seek) gives nothing
+                let start = file.position.min(file.content.len());
+                let rest = &file.content[start..];
+                let mut end = if function == OsFunction::FileReadline {
+                    rest.iter().position(|&b| b == b'\n').map_or(rest.len(), |i| i + 1)
+                } else {
+                    rest.len()
+                };
+                if let Ok(size) = usize::try_from(size) {
+                    end = if file.binary {
+                        end.min(size)
+                    } else {
+                        // text sizes count characters
+                        let text = std::str::from_utf8(&rest[..end]).expect("virtual text files are utf-8");
+                        text.char_indices().nth(size).map_or(end, |(i, _)| i)
+                    };
+                }
+                let chunk = rest[..end].to_vec();
+                file.position = start + end;
+                if file.binary {
+                    MontyObject::Bytes(chunk).into()
+                } else {
+                    MontyObject::String(String::from_utf8(chunk).expect("virtual text files are utf-8")).into()
+                }
+            }
+            OsFunction::FileWrite => {
+                let (data, count) = match &args[1] {
+                    MontyObject::Bytes(b) => (b.clone(), b.len()),
+                    MontyObject::String(s) => (s.as_bytes().to_vec(), s.chars().count()),
+                    other => panic!("file.write: second arg must be str or bytes, got {other:?}"),
+                };
+                if file.append {
+                    file.position = file.content.len();
+                }
+                let end = file.position + data.len();
+                if file.content.len() < end {
+                    file.content.resize(end, 0);
+                }
+                file.content[file.position..end].copy_from_slice(&data);
+                file.position = end;
+                to_int(count).into()
+            }
+            OsFunction::FileSeek => {
+                let base = match int_arg(2) {
+                    0 => 0,
+                    1 => file.position,
+                    _ => file.content.len(),
+                };
+                let base = i64::try_from(base).expect("virtual files are small");
+                match usize::try_from(base + int_arg(1)) {
+                    Ok(position) => {
+                        file.position = position;
+                        to_int(position).into()
+                    }
+                    Err(_) => {
+                        MontyException::new(ExcType::OSError, Some("[Errno 22] Invalid argument".to_owned())).into()
+                    }
+                }
+            }
+            OsFunction::FileTell => to_int(file.position).into(),
+            _ => unreachable!("not a file.* function: {function}"),
+        }
+    })
+}
+
 /// Helper to create parent directories recursively.
 fn create_parent_dirs(path: &str) {
     if is_virtual_dir(path) {
@@ -1062,6 +1429,86 @@ fn create_parent_dirs(path: &str) {
     });
 }
 
+/// Returns the parent directory of a virtual path.
+fn parent_path(path: &str) -> String {
+    match path.rsplit_once('/') {
+        Some(("", _)) => "/".to_owned(),
+        Some((parent, _)) => parent.to_owned(),
+        None => ".".to_owned(),
+    }
+}
+
+/// Builds an `OSError` subclass exception in CPython's `[Errno N] message: 'path'` format.
+fn virtual_os_error(exc_type: ExcType, errno: i32, message: &str, path: &str) -> ExtFunctionResult {
+    MontyException::new(exc_type, Some(format!("[Errno {errno}] {message}: '{path}'"))).into()
+}
+
+/// Lists every file and directory below a virtual directory, parents before their children.
+fn virtual_descendants(dir: &str) -> Vec<String> {
+    let mut entries = get_virtual_dir_entries(dir).unwrap_or_default();
+    entries.sort();
+    let mut descendants = Vec::new();
+    for entry in entries {
+        let is_dir = is_virtual_dir(&entry);
+        descendants.push(entry.clone());
+        if is_dir {
+            descendants.extend(virtual_descendants(&entry));
+        }
+    }
+    descendants
+}
+
+/// Appends the `(dirpath, dirnames, filenames)` tuples `os.walk()` yields for a virtual directory.
+fn virtual_walk(dir: &str, topdown: bool, walk: &mut Vec<MontyObject>) {
+    let mut entries = get_virtual_dir_entries(dir).unwrap_or_default();
+    entries.sort();
+    let (subdirs, files): (Vec<String>, Vec<String>) = entries.into_iter().partition(|entry| is_virtual_dir(entry));
+    let names = |paths: &[String]| -> Vec<MontyObject> {
+        paths
+            .iter()
+            .map(|p| MontyObject::String(p[dir.len() + 1..].to_owned()))
+            .collect()
+    };
+    let tuple = MontyObject::Tuple(vec![
+        MontyObject::String(dir.to_owned()),
+        MontyObject::List(names(&subdirs)),
+        MontyObject::List(names(&files)),
+    ]);
+    if topdown {
+        walk.push(tuple);
+    }
+    for subdir in &subdirs {
+        virtual_walk(subdir, topdown, walk);
+    }
+    if !topdown {
+        walk.push(tuple);
+    }
+}
+
+/// Matches a path's components against a glob pattern's, where `**` matches any number of components.
+fn glob_match(pattern: &[&str], parts: &[&str]) -> bool {
+    match pattern.split_first() {
+        None => parts.is_empty(),
+        Some((&"**", rest)) => (0..=parts.len()).any(|skip| glob_match(rest, &parts[skip..])),
+        Some((first, rest)) => parts.split_first().is_some_and(|(part, parts_rest)| {
+            glob_component_match(first.as_bytes(), part.as_bytes()) && glob_match(rest, parts_rest)
+        }),
+    }
+}
+
+/// Matches a single path component against a glob component supporting `*` and `?`.
+fn glob_component_match(pattern: &[u8], name: &[u8]) -> bool {
+    match (pattern.split_first(), name.split_first()) {
+        (None, _) => name.is_empty(),
+        (Some((b'*', rest)), _) => {
+            glob_component_match(rest, name) || (!name.is_empty() && glob_component_match(pattern, &name[1..]))
+        }
+        (Some(_), None) => false,
+        (Some((b'?', rest)), Some((_, name_rest))) => glob_component_match(rest, name_rest),
+        (Some((p, rest)), Some((n, name_rest))) => p == n && glob_component_match(rest, name_rest),
+    }
+}
+
 /// Represents a test failure with details about expected vs actual values.
 #[derive(Debug)]
 struct TestFailure {
@@ -1,4 +1,4 @@
-use monty::{MontyObject, MontyRun};
+use monty::{MontyObject, MontyRun, NoLimitTracker, PrintWriter};
 
 /// Test we can reuse exec without borrow checker issues.
 #[test]
@@ -69,3 +69,132 @@ fn subscript_augassign_matmul_reports_not_supported() {
         "Traceback (most recent call last):\n  File \"test.py\", line 2\n    d['x'] @= 2\n    ~~~~~~\nSyntaxError: matrix multiplication augmented assignment (@=) is not yet supported"
     );
 }
+
+/// `sys.argv` defaults to the script name, and hosts can replace it with `set_argv()`.
+#[test]
+fn sys_argv() {
+    let code = "import sys; sys.argv".to_owned();
+    let ex = MontyRun::new(code.clone(), "script.py", vec![]).unwrap();
+    let r = ex.run_no_limits(vec![]).unwrap();
+    assert_eq!(r, MontyObject::List(vec![MontyObject::String("script.py".to_owned())]));
+
+    let mut ex = MontyRun::new(code, "script.py", vec![]).unwrap();
+    ex.set_argv(vec!["script.py".to_owned(), "-v".to_owned()]);
+    // the arguments survive dump/load with the rest of the runner
+    let ex = MontyRun::load(&ex.dump().unwrap()).unwrap();
+    let r = ex.run_no_limits(vec![]).unwrap();
+    assert_eq!(
+        r,
+        MontyObject::List(vec![
+            MontyObject::String("script.py".to_owned()),
+            MontyObject::String("-v".to_owned())
+        ])
+    );
+}
+
+/// An uncaught `SystemExit` is a clean exit, with an exit code unlike any other exception.
+#[test]
+fn system_exit_code() {
+    let exit_code = |code: &str| {
+        let ex = MontyRun::new(code.to_owned(), "test.py", vec![]).unwrap();
+        ex.run_no_limits(vec![]).unwrap_err().exit_code()
+    };
+    assert_eq!(exit_code("import sys; sys.exit()"), Some(0));
+    assert_eq!(exit_code("import sys; sys.exit(0)"), Some(0));
+    assert_eq!(exit_code("import sys; sys.exit(42)"), Some(42));
+    assert_eq!(exit_code("import sys; sys.exit(True)"), Some(1));
+    assert_eq!(exit_code("raise SystemExit(3)"), Some(3));
+    assert_eq!(exit_code("1 / 0"), None);
+
+    let ex = MontyRun::new("import sys; sys.exit('bad input')".to_owned(), "test.py", vec![]).unwrap();
+    let err = ex.run_no_limits(vec![]).unwrap_err();
+    assert_eq!(err.exit_code(), Some(1));
+    assert_eq!(err.exit_message(), Some("bad input"));
+
+    // a str code that reads as a number is still a message, not an exit status
+    let ex = MontyRun::new("import sys; sys.exit('3')".to_owned(), "test.py", vec![]).unwrap();
+    let err = ex.run_no_limits(vec![]).unwrap_err();
+    assert_eq!(err.exit_code(), Some(1));
+    assert_eq!(err.exit_message(), Some("3"));
+    assert_eq!(err.py_repr(), "SystemExit('3')");
+
+    let ex = MontyRun::new("import sys; sys.exit(3)".to_owned(), "test.py", vec![]).unwrap();
+    let err = ex.run_no_limits(vec![]).unwrap_err();
+    assert_eq!(err.exit_message(), None);
+    assert_eq!(err.py_repr(), "SystemExit(3)");
+
+    let ex = MontyRun::new("import sys; sys.exit(True)".to_owned(), "test.py", vec![]).unwrap();
+    let err = ex.run_no_limits(vec![]).unwrap_err();
+    assert_eq!(err.exit_message(), None);
+    assert_eq!(err.py_repr(), "SystemExit(True)");
+}
+
+/// A `bool` exit code stays a `bool` when a host passes the `SystemExit` back into a run,
+/// e.g. from a nested script.
+#[test]
+fn system_exit_bool_code_round_trips() {
+    for (code, expected) in [("True", true), ("False", false)] {
+        let inner = MontyRun::new(format!("import sys; sys.exit({code})"), "inner.py", vec![]).unwrap();
+        let exit = inner.run_no_limits(vec![]).unwrap_err();
+        assert_eq!(exit.exit_code(), Some(i64::from(expected)));
+
+        let outer = "try:
+    run_inner()
+except SystemExit as e:
+    result = (e.code, e.args, repr(e))
+result";
+        let outer = MontyRun::new(outer.to_owned(), "outer.py", vec![]).unwrap();
+        let progress = outer.start(vec![], NoLimitTracker, PrintWriter::Stdout).unwrap();
+        let call = progress.into_function_call().expect("expected run_inner() call");
+        let result = call.resume(exit, PrintWriter::Stdout).unwrap().into_complete().unwrap();
+        assert_eq!(
+            result,
+            MontyObject::Tuple(vec![
+                MontyObject::Bool(expected),
+                MontyObject::Tuple(vec![MontyObject::Bool(expected)]),
+                MontyObject::String(format!("SystemExit({code})")),
+            ])
+        );
+    }
+}
+
+/// A `for` loop over `iter(callable, sentinel)` pauses for each call to an external function,
+/// comparing the host's result with the sentinel on resume.
+#[test]
+fn iter_sentinel_calls_external_function_in_for_loop() {
+    let code = "items = []
+for item in iter(next_item, 0):
+    items.append(item)
+items";
+    let runner = MontyRun::new(code.to_owned(), "test.py", vec![]).unwrap();
+    let progress = runner.start(vec![], NoLimitTracker, PrintWriter::Stdout).unwrap();
+    let lookup = progress.into_name_lookup().expect("expected next_item lookup");
+    let function = MontyObject::Function {
+        name: "next_item".to_owned(),
+        docstring: None,
+    };
+    let mut progress = lookup.resume(function, PrintWriter::Stdout).unwrap();
+    for item in [1, 2, 0] {
+        let call = progress.into_function_call().expect("expected next_item() call");
+        assert_eq!(call.function_name, "next_item");
+        progress = call.resume(MontyObject::Int(item), PrintWriter::Stdout).unwrap();
+    }
+    assert_eq!(
+        progress.into_complete().unwrap(),
+        MontyObject::List(vec![MontyObject::Int(1), MontyObject::Int(2)])
+    );
+}
+
+/// `__name__` is `'__main__'` and `__file__` is the script name given to `MontyRun::new`.
+#[test]
+fn module_dunders() {
+    let ex = MontyRun::new("(__name__, __file__)".to_owned(), "dir/script.py", vec![]).unwrap();
+    let r = ex.run_no_limits(vec![]).unwrap();
+    assert_eq!(
+        r,
+        MontyObject::Tuple(vec![
+            MontyObject::String("__main__".to_owned()),
+            MontyObject::String("dir/script.py".to_owned())
+        ])
+    );
+}
@@ -0,0 +1,369 @@
+//! Tests for `MemoryFs`, the in-memory filesystem that answers `OsCall`s.
+//!
+//! Each test runs Python code to completion, resuming every `OsCall` with a `MemoryFs`,
+//! then checks the result and the state of the filesystem.
+
+use monty::{
+    ExcType, MemoryFs, MontyException, MontyObject, MontyRepl, MontyRun, NoLimitTracker, OsFunction, OsHandler,
+    PrintWriter, ReplProgress, RunProgress,
+};
+
+/// Runs code to completion, resolving every OS call against `fs`.
+fn run(code: &str, fs: &mut MemoryFs) -> Result<MontyObject, MontyException> {
+    let runner = MontyRun::new(code.to_owned(), "test.py", vec![]).unwrap();
+    let mut progress = runner.start(vec![], NoLimitTracker, PrintWriter::Stdout)?;
+    loop {
+        match progress {
+            RunProgress::Complete(value) => return Ok(value),
+            RunProgress::OsCall(call) => progress = call.resume_with(fs, PrintWriter::Stdout)?,
+            other => panic!("expected OsCall or Complete, got {other:?}"),
+        }
+    }
+}
+
+/// Feeds a REPL snippet to completion, resolving every OS call against `fs`.
+fn feed_repl(repl: MontyRepl<NoLimitTracker>, code: &str, fs: &mut MemoryFs) -> MontyRepl<NoLimitTracker> {
+    let mut progress = repl.feed_start(code, vec![], PrintWriter::Stdout).unwrap();
+    loop {
+        match progress {
+            ReplProgress::Complete { repl, .. } => return repl,
+            ReplProgress::OsCall(call) => progress = call.resume_with(fs, PrintWriter::Stdout).unwrap(),
+            other => panic!("expected OsCall or Complete, got {other:?}"),
+        }
+    }
+}
+
+/// Runs code that should raise, returning the exception type and message.
+fn run_err(code: &str, fs: &mut MemoryFs) -> (ExcType, String) {
+    let exc = run(code, fs).expect_err("expected an exception");
+    (exc.exc_type(), exc.message().unwrap_or_default().to_owned())
+}
+
+fn str_list(items: &[&str]) -> MontyObject {
+    MontyObject::List(items.iter().map(|s| MontyObject::String((*s).to_owned())).collect())
+}
+
+#[test]
+fn read_and_write_files() {
+    let mut fs = MemoryFs::new().file("/data/in.txt", "hello");
+    let code = "from pathlib import Path
+n = Path('/data/out.txt').write_text(Path('/data/in.txt').read_text() * 2)
+Path('/data/raw.bin').write_bytes(b'\\x00\\x01')
+n";
+    assert_eq!(run(code, &mut fs).unwrap(), MontyObject::Int(10));
+    assert_eq!(fs.read("/data/out.txt"), Some(&b"hellohello"[..]));
+    assert_eq!(fs.read("/data/raw.bin"), Some(&b"\x00\x01"[..]));
+    assert_eq!(fs.used_bytes(), 17);
+}
+
+#[test]
+fn relative_paths_resolve_against_root() {
+    let mut fs = MemoryFs::new().file("/a/b.txt", "b");
+    let code = "from pathlib import Path
+(Path('a/../a/./b.txt').read_text(), str(Path('a/b.txt').resolve()))";
+    assert_eq!(
+        run(code, &mut fs).unwrap(),
+        MontyObject::Tuple(vec![
+            MontyObject::String("b".to_owned()),
+            MontyObject::String("/a/b.txt".to_owned()),
+        ])
+    );
+}
+
+#[test]
+fn directories() {
+    let mut fs = MemoryFs::new().file("/d/x.txt", "x").dir("/d/sub");
+    let code = "from pathlib import Path
+Path('/d/new/deep').mkdir(parents=True)
+Path('/d/x.txt').rename('/d/sub/y.txt')
+sorted(str(p) for p in Path('/d').iterdir())";
+    assert_eq!(run(code, &mut fs).unwrap(), str_list(&["/d/new", "/d/sub"]));
+    assert!(fs.is_dir("/d/new/deep"));
+    assert!(!fs.exists("/d/x.txt"));
+    assert_eq!(fs.read("/d/sub/y.txt"), Some(&b"x"[..]));
+
+    assert_eq!(
+        run_err("from pathlib import Path\nPath('/d/sub').rmdir()", &mut fs),
+        (ExcType::OSError, "[Errno 39] Directory not empty: '/d/sub'".to_owned())
+    );
+    assert_eq!(
+        run_err("from pathlib import Path\nPath('/d').mkdir()", &mut fs),
+        (ExcType::FileExistsError, "[Errno 17] File exists: '/d'".to_owned())
+    );
+    assert_eq!(
+        run_err(
+            "from pathlib import Path\nPath('/d/sub/y.txt').read_text(); Path('/d/sub').read_text()",
+            &mut fs
+        ),
+        (
+            ExcType::IsADirectoryError,
+            "[Errno 21] Is a directory: '/d/sub'".to_owned()
+        )
+    );
+}
+
+#[test]
+fn stat_reports_mode_size_and_mtime() {
+    let mut fs = MemoryFs::new()
+        .file_with_mode("/f.txt", "abc", 0o600)
+        .dir("/d")
+        .mtime(1_700_000_000.0);
+    let code = "from pathlib import Path
+f = Path('/f.txt').stat()
+d = Path('/d').stat()
+(oct(f.st_mode), f.st_size, f.st_mtime, oct(d.st_mode))";
+    assert_eq!(
+        run(code, &mut fs).unwrap(),
+        MontyObject::Tuple(vec![
+            MontyObject::String("0o100600".to_owned()),
+            MontyObject::Int(3),
+            MontyObject::Float(1_700_000_000.0),
+            MontyObject::String("0o40755".to_owned()),
+        ])
+    );
+}
+
+#[test]
+fn permissions_are_enforced() {
+    let mut fs = MemoryFs::new()
+        .file_with_mode("/readonly.txt", "ro", 0o444)
+        .file_with_mode("/secret.txt", "s", 0o200)
+        .dir_with_mode("/locked", 0o555);
+
+    assert_eq!(
+        run_err(
+            "from pathlib import Path\nPath('/readonly.txt').write_text('x')",
+            &mut fs
+        ),
+        (
+            ExcType::PermissionError,
+            "[Errno 13] Permission denied: '/readonly.txt'".to_owned()
+        )
+    );
+    assert_eq!(
+        run_err("from pathlib import Path\nPath('/secret.txt').read_text()", &mut fs),
+        (
+            ExcType::PermissionError,
+            "[Errno 13] Permission denied: '/secret.txt'".to_owned()
+        )
+    );
+    assert_eq!(
+        run_err("open('/locked/new.txt', 'w')", &mut fs),
+        (
+            ExcType::PermissionError,
+            "[Errno 13] Permission denied: '/locked/new.txt'".to_owned()
+        )
+    );
+    // only the directory's permissions matter for deleting a file
+    let code = "from pathlib import Path
+Path('/readonly.txt').unlink()
+try:
+    Path('/locked/x').mkdir()
+except OSError as e:
+    result = type(e).__name__
+result";
+    assert_eq!(
+        run(code, &mut fs).unwrap(),
+        MontyObject::String("PermissionError".to_owned())
+    );
+    assert!(!fs.exists("/readonly.txt"));
+}
+
+#[test]
+fn quota_limits_total_size() {
+    let mut fs = MemoryFs::new().file("/a.txt", "12345").max_bytes(10);
+    run("from pathlib import Path\nPath('/b.txt').write_text('12345')", &mut fs).unwrap();
+    assert_eq!(
+        run_err("from pathlib import Path\nPath('/c.txt').write_text('x')", &mut fs),
+        (
+            ExcType::OSError,
+            "[Errno 28] No space left on device: '/c.txt'".to_owned()
+        )
+    );
+    // replacing a file with something no bigger always fits
+    run("from pathlib import Path\nPath('/a.txt').write_text('abcde')", &mut fs).unwrap();
+    assert_eq!(
+        run_err("f = open('/a.txt', 'a')\nf.write('more')", &mut fs),
+        (
+            ExcType::OSError,
+            "[Errno 28] No space left on device: '/a.txt'".to_owned()
+        )
+    );
+    assert_eq!(fs.used_bytes(), 10);
+}
+
+#[test]
+fn quota_counts_files_open_for_writing() {
+    let mut fs = MemoryFs::new().max_bytes(10);
+    let code = "a = open('/a.txt', 'w')
+b = open('/b.txt', 'w')
+a.write('123456')
+try:
+    b.write('123456')
+except OSError as e:
+    err = str(e)
+b.write('1234')
+a.close()
+b.close()
+err";
+    assert_eq!(
+        run(code, &mut fs).unwrap(),
+        MontyObject::String("[Errno 28] No space left on device: '/b.txt'".to_owned())
+    );
+    assert_eq!(fs.read("/a.txt"), Some(&b"123456"[..]));
+    assert_eq!(fs.read("/b.txt"), Some(&b"1234"[..]));
+    assert_eq!(fs.used_bytes(), 10);
+}
+
+#[test]
+fn open_files() {
+    let mut fs = MemoryFs::new().file("/log.txt", "one\n");
+    let code = "with open('/log.txt', 'a') as f:
+    f.write('two\\n')
+with open('/log.txt') as f:
+    lines = [line.strip() for line in f]
+with open('/data.bin', 'w+b') as f:
+    f.write(b'abc')
+    f.seek(1)
+    rest = f.read()
+(lines, rest)";
+    assert_eq!(
+        run(code, &mut fs).unwrap(),
+        MontyObject::Tuple(vec![str_list(&["one", "two"]), MontyObject::Bytes(b"bc".to_vec())])
+    );
+    assert_eq!(fs.read("/log.txt"), Some(&b"one\ntwo\n"[..]));
+    assert_eq!(fs.read("/data.bin"), Some(&b"abc"[..]));
+}
+
+#[test]
+fn iter_readline_until_sentinel() {
+    let mut fs = MemoryFs::new().file("/lines.txt", "one\ntwo\nthree\n");
+    let code = "with open('/lines.txt') as f:
+    lines = [line.strip() for line in iter(f.readline, 'three\\n')]
+    rest = f.read()
+(lines, rest)";
+    assert_eq!(
+        run(code, &mut fs).unwrap(),
+        MontyObject::Tuple(vec![str_list(&["one", "two"]), MontyObject::String(String::new())])
+    );
+
+    // Only `for` loops can pause for the host while iterating
+    let code = "with open('/lines.txt') as f:
+    list(iter(f.readline, ''))";
+    assert_eq!(
+        run_err(code, &mut fs),
+        (
+            ExcType::TypeError,
+            "iter(v, w): v can only be an external or OS function when iterated by a for loop".to_owned()
+        )
+    );
+}
+
+#[test]
+fn files_left_open_are_closed_at_exit() {
+    let mut fs = MemoryFs::new();
+    let code = "kept = open('/kept.txt', 'w')
+kept.write('kept')
+def write_and_drop():
+    f = open('/dropped.txt', 'w')
+    f.write('dropped')
+write_and_drop()
+kept.closed";
+    assert_eq!(run(code, &mut fs).unwrap(), MontyObject::Bool(false));
+    assert_eq!(fs.read("/kept.txt"), Some(&b"kept"[..]));
+    assert_eq!(fs.read("/dropped.txt"), Some(&b"dropped"[..]));
+}
+
+#[test]
+fn repl_keeps_files_open_between_snippets() {
+    let mut fs = MemoryFs::new();
+    let repl = MontyRepl::new("repl.py", NoLimitTracker);
+    let repl = feed_repl(repl, "f = open('/kept.txt', 'w')\nf.write('a')", &mut fs);
+    let repl = feed_repl(
+        repl,
+        "f.write('b')\nopen('/dropped.txt', 'w').write('dropped')",
+        &mut fs,
+    );
+    assert_eq!(fs.read("/kept.txt"), Some(&b""[..]));
+    assert_eq!(fs.read("/dropped.txt"), Some(&b"dropped"[..]));
+    feed_repl(repl, "f.close()", &mut fs);
+    assert_eq!(fs.read("/kept.txt"), Some(&b"ab"[..]));
+}
+
+#[test]
+fn glob_walk_and_shutil() {
+    let mut fs = MemoryFs::new()
+        .file("/src/a.py", "a")
+        .file("/src/pkg/b.py", "b")
+        .file("/src/pkg/c.txt", "c");
+    let code = "import os
+import shutil
+from pathlib import Path
+shutil.copytree('/src', '/dst')
+shutil.rmtree('/src/pkg')
+(
+    sorted(str(p) for p in Path('/dst').rglob('*.py')),
+    [d for d, _, _ in os.walk('/dst')],
+    shutil.copy('/dst/pkg/c.txt', '/src'),
+)";
+    assert_eq!(
+        run(code, &mut fs).unwrap(),
+        MontyObject::Tuple(vec![
+            str_list(&["/dst/a.py", "/dst/pkg/b.py"]),
+            str_list(&["/dst", "/dst/pkg"]),
+            MontyObject::String("/src/c.txt".to_owned()),
+        ])
+    );
+    assert!(!fs.exists("/src/pkg"));
+    assert_eq!(fs.read("/src/c.txt"), Some(&b"c"[..]));
+}
+
+#[test]
+fn environment_and_urandom() {
+    let mut fs = MemoryFs::new().env("HOME", "/home/monty").seed(42);
+    let code = "import os
+(os.getenv('HOME'), os.getenv('MISSING', 'default'), os.environ['HOME'], len(os.urandom(20)))";
+    assert_eq!(
+        run(code, &mut fs).unwrap(),
+        MontyObject::Tuple(vec![
+            MontyObject::String("/home/monty".to_owned()),
+            MontyObject::String("default".to_owned()),
+            MontyObject::String("/home/monty".to_owned()),
+            MontyObject::Int(20),
+        ])
+    );
+
+    // the same seed gives the same bytes
+    let bytes = |fs: &mut MemoryFs| fs.handle_os_call(OsFunction::Urandom, &[MontyObject::Int(8)], &[]);
+    let first = format!("{:?}", bytes(&mut MemoryFs::new().seed(7)));
+    assert_eq!(first, format!("{:?}", bytes(&mut MemoryFs::new().seed(7))));
+    assert_ne!(first, format!("{:?}", bytes(&mut MemoryFs::new().seed(8))));
+}
+
+#[test]
+fn dump_and_load_alongside_snapshot() {
+    let mut fs = MemoryFs::new();
+    let code = "f = open('/out.txt', 'w')
+f.write('before')
+f.write(' after')
+f.close()";
+    let runner = MontyRun::new(code.to_owned(), "test.py", vec![]).unwrap();
+    let mut progress = runner.start(vec![], NoLimitTracker, PrintWriter::Stdout).unwrap();
+    // open() and the first write()
+    for _ in 0..2 {
+        let RunProgress::OsCall(call) = progress else {
+            panic!("expected OsCall, got {progress:?}");
+        };
+        progress = call.resume_with(&mut fs, PrintWriter::Stdout).unwrap();
+    }
+
+    // store both with the file still open, then carry on from the stored copies
+    let (progress_bytes, fs_bytes) = (progress.dump().unwrap(), fs.dump().unwrap());
+    let mut progress: RunProgress<NoLimitTracker> = RunProgress::load(&progress_bytes).unwrap();
+    let mut fs = MemoryFs::load(&fs_bytes).unwrap();
+    while let RunProgress::OsCall(call) = progress {
+        progress = call.resume_with(&mut fs, PrintWriter::Stdout).unwrap();
+    }
+    assert_eq!(progress.into_complete(), Some(MontyObject::None));
+    assert_eq!(fs.read("/out.txt"), Some(&b"before after"[..]));
+}
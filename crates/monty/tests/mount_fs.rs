@@ -0,0 +1,318 @@
+//! Tests for `MountFs`, which answers `OsCall`s against real host directories.
+//!
+//! Each test works in its own scratch directory under the system temp directory,
+//! runs Python code with it mounted, then checks the result and the host files.
+
+use std::{fs, path::PathBuf, process};
+
+use monty::{
+    ExcType, MontyException, MontyObject, MontyRun, MountFs, NoLimitTracker, OsFunction, PrintWriter, RunProgress,
+};
+
+/// Creates an empty scratch directory unique to this test process and `name`.
+fn scratch_dir(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("monty-mount-fs-{}-{name}", process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+/// Runs code to completion, resolving every OS call against `fs`.
+fn run(code: &str, fs: &mut MountFs) -> Result<MontyObject, MontyException> {
+    let runner = MontyRun::new(code.to_owned(), "test.py", vec![]).unwrap();
+    let mut progress = runner.start(vec![], NoLimitTracker, PrintWriter::Stdout)?;
+    loop {
+        match progress {
+            RunProgress::Complete(value) => return Ok(value),
+            RunProgress::OsCall(call) => progress = call.resume_with(fs, PrintWriter::Stdout)?,
+            other => panic!("expected OsCall or Complete, got {other:?}"),
+        }
+    }
+}
+
+/// Runs code that should raise, returning the exception type and message.
+fn run_err(code: &str, fs: &mut MountFs) -> (ExcType, String) {
+    let exc = run(code, fs).expect_err("expected an exception");
+    (exc.exc_type(), exc.message().unwrap_or_default().to_owned())
+}
+
+#[test]
+fn read_and_write_host_files() {
+    let dir = scratch_dir("read-write");
+    fs::write(dir.join("in.txt"), "hello").unwrap();
+    let mut mount_fs = MountFs::new().mount(&dir, "/data", false).unwrap();
+    let code = "from pathlib import Path
+Path('/data/sub').mkdir()
+Path('/data/sub/out.txt').write_text(Path('/data/in.txt').read_text() * 2)
+with open('/data/log.txt', 'a') as f:
+    f.write('one\\n')
+(sorted(str(p) for p in Path('/data').iterdir()), Path('/data/sub/out.txt').stat().st_size, str(Path('/data/sub/../in.txt').resolve()))";
+    assert_eq!(
+        run(code, &mut mount_fs).unwrap(),
+        MontyObject::Tuple(vec![
+            MontyObject::List(
+                ["/data/in.txt", "/data/log.txt", "/data/sub"]
+                    .into_iter()
+                    .map(|path| MontyObject::String(path.to_owned()))
+                    .collect()
+            ),
+            MontyObject::Int(10),
+            MontyObject::String("/data/in.txt".to_owned()),
+        ])
+    );
+    assert_eq!(fs::read_to_string(dir.join("sub/out.txt")).unwrap(), "hellohello");
+    assert_eq!(fs::read_to_string(dir.join("log.txt")).unwrap(), "one\n");
+    assert_eq!(mount_fs.bytes_written(), 14);
+
+    assert_eq!(
+        run_err(
+            "from pathlib import Path\nPath('/data/missing.txt').read_text()",
+            &mut mount_fs
+        ),
+        (
+            ExcType::FileNotFoundError,
+            "[Errno 2] No such file or directory: '/data/missing.txt'".to_owned()
+        )
+    );
+    // nothing exists outside the mounts
+    assert_eq!(
+        run_err(
+            "from pathlib import Path\nPath('/etc/passwd').read_text()",
+            &mut mount_fs
+        ),
+        (
+            ExcType::FileNotFoundError,
+            "[Errno 2] No such file or directory: '/etc/passwd'".to_owned()
+        )
+    );
+    fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn open_files_read_and_write_in_place() {
+    let dir = scratch_dir("open-files");
+    let mut mount_fs = MountFs::new().mount(&dir, "/data", false).unwrap();
+    let code = "import os
+with open('/data/lines.txt', 'w') as f:
+    f.write('héllo\\nwörld\\n')
+with open('/data/lines.txt') as f:
+    parts = [f.read(2), f.readline(), f.tell(), f.readline(3), f.read()]
+with open('/data/lines.txt', 'a+') as f:
+    f.write('end')
+    f.seek(0)
+    parts.append(f.read())
+out = open('/data/out.bin', 'wb')
+out.write(b'abc')
+# writes reach the host straight away, before the file is closed
+parts.append(open('/data/out.bin', 'rb').read())
+parts.append(len(os.urandom(16)))
+parts";
+    let string = |s: &str| MontyObject::String(s.to_owned());
+    assert_eq!(
+        run(code, &mut mount_fs).unwrap(),
+        MontyObject::List(vec![
+            string("hé"),
+            string("llo\n"),
+            MontyObject::Int(7),
+            string("wör"),
+            string("ld\n"),
+            string("héllo\nwörld\nend"),
+            MontyObject::Bytes(b"abc".to_vec()),
+            MontyObject::Int(16),
+        ])
+    );
+    assert_eq!(fs::read(dir.join("out.bin")).unwrap(), b"abc");
+    fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn read_only_mounts() {
+    let dir = scratch_dir("read-only");
+    let ro = dir.join("ro");
+    fs::create_dir(&ro).unwrap();
+    fs::write(ro.join("config.txt"), "setting").unwrap();
+    let mut mount_fs = MountFs::new().mount(&ro, "/config", true).unwrap();
+
+    assert_eq!(
+        run(
+            "from pathlib import Path\nPath('/config/config.txt').read_text()",
+            &mut mount_fs
+        )
+        .unwrap(),
+        MontyObject::String("setting".to_owned())
+    );
+    for code in [
+        "from pathlib import Path\nPath('/config/config.txt').write_text('x')",
+        "from pathlib import Path\nPath('/config/config.txt').unlink()",
+        "open('/config/config.txt', 'r+')",
+        "import shutil\nshutil.rmtree('/config')",
+    ] {
+        let (exc_type, message) = run_err(code, &mut mount_fs);
+        assert_eq!(exc_type, ExcType::PermissionError, "{code}");
+        assert!(
+            message.starts_with("[Errno 13] Permission denied: '/config"),
+            "{message}"
+        );
+    }
+    assert_eq!(fs::read_to_string(ro.join("config.txt")).unwrap(), "setting");
+    fs::remove_dir_all(dir).unwrap();
+}
+
+#[cfg(unix)]
+#[test]
+fn symlink_escapes_are_rejected() {
+    use std::os::unix::fs::symlink;
+
+    let dir = scratch_dir("escapes");
+    let (jail, outside) = (dir.join("jail"), dir.join("outside"));
+    fs::create_dir(&jail).unwrap();
+    fs::create_dir(&outside).unwrap();
+    fs::write(outside.join("secret.txt"), "secret").unwrap();
+    fs::write(jail.join("real.txt"), "real").unwrap();
+    symlink(outside.join("secret.txt"), jail.join("secret_link")).unwrap();
+    symlink(&outside, jail.join("outside_dir")).unwrap();
+    symlink(outside.join("new.txt"), jail.join("dangling")).unwrap();
+    symlink("real.txt", jail.join("inside_link")).unwrap();
+    let mut mount_fs = MountFs::new().mount(&jail, "/jail", false).unwrap();
+
+    for (code, path) in [
+        ("Path('/jail/secret_link').read_text()", "/jail/secret_link"),
+        (
+            "Path('/jail/outside_dir/secret.txt').read_text()",
+            "/jail/outside_dir/secret.txt",
+        ),
+        ("Path('/jail/dangling').write_text('x')", "/jail/dangling"),
+        ("Path('/jail/outside_dir/sub').mkdir()", "/jail/outside_dir/sub"),
+        ("Path('/jail/secret_link').resolve()", "/jail/secret_link"),
+    ] {
+        assert_eq!(
+            run_err(&format!("from pathlib import Path\n{code}"), &mut mount_fs),
+            (
+                ExcType::PermissionError,
+                format!("[Errno 13] Permission denied: '{path}'")
+            ),
+            "{code}"
+        );
+    }
+    assert!(!outside.join("new.txt").exists());
+    assert!(!outside.join("sub").exists());
+
+    // `..` is resolved before mapping, so it can't climb out of the mount either
+    assert_eq!(
+        run_err(
+            "from pathlib import Path\nPath('/jail/../outside/secret.txt').read_text()",
+            &mut mount_fs
+        )
+        .0,
+        ExcType::FileNotFoundError
+    );
+
+    // symlinks within the mount work, and unlinking an escaping symlink only removes the link
+    let code = "from pathlib import Path
+Path('/jail/secret_link').unlink()
+(Path('/jail/inside_link').read_text(), str(Path('/jail/inside_link').resolve()), Path('/jail/outside_dir').exists())";
+    assert_eq!(
+        run(code, &mut mount_fs).unwrap(),
+        MontyObject::Tuple(vec![
+            MontyObject::String("real".to_owned()),
+            MontyObject::String("/jail/real.txt".to_owned()),
+            MontyObject::Bool(false),
+        ])
+    );
+    assert!(outside.join("secret.txt").exists());
+    fs::remove_dir_all(dir).unwrap();
+}
+
+#[cfg(unix)]
+#[test]
+fn copytree_does_not_follow_symlinks_in_destination() {
+    use std::os::unix::fs::symlink;
+
+    let dir = scratch_dir("copytree-escape");
+    let (jail, outside) = (dir.join("jail"), dir.join("outside"));
+    fs::create_dir_all(jail.join("src/sub")).unwrap();
+    fs::create_dir_all(jail.join("dst")).unwrap();
+    fs::create_dir(&outside).unwrap();
+    fs::write(jail.join("src/sub/evil.txt"), "evil").unwrap();
+    fs::write(jail.join("src/top.txt"), "top").unwrap();
+    symlink(&outside, jail.join("dst/sub")).unwrap();
+    symlink(outside.join("top.txt"), jail.join("dst/top.txt")).unwrap();
+    let mut mount_fs = MountFs::new().mount(&jail, "/jail", false).unwrap();
+
+    assert_eq!(
+        run_err(
+            "import shutil
+shutil.copytree('/jail/src', '/jail/dst', dirs_exist_ok=True)",
+            &mut mount_fs
+        ),
+        (
+            ExcType::PermissionError,
+            "[Errno 13] Permission denied: '/jail/dst'".to_owned()
+        )
+    );
+    assert!(!outside.join("evil.txt").exists());
+    assert!(!outside.join("top.txt").exists());
+    fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn allow_and_deny_functions() {
+    let dir = scratch_dir("policy");
+    fs::write(dir.join("in.txt"), "hello").unwrap();
+    let mut mount_fs = MountFs::new()
+        .mount(&dir, "/data", false)
+        .unwrap()
+        .allow([OsFunction::ReadText, OsFunction::Exists, OsFunction::Unlink])
+        .deny([OsFunction::Unlink]);
+
+    assert_eq!(
+        run(
+            "from pathlib import Path\nPath('/data/in.txt').read_text()",
+            &mut mount_fs
+        )
+        .unwrap(),
+        MontyObject::String("hello".to_owned())
+    );
+    for code in ["Path('/data/in.txt').unlink()", "Path('/data/in.txt').read_bytes()"] {
+        assert_eq!(
+            run_err(&format!("from pathlib import Path\n{code}"), &mut mount_fs),
+            (
+                ExcType::PermissionError,
+                "[Errno 1] Operation not permitted: '/data/in.txt'".to_owned()
+            ),
+            "{code}"
+        );
+    }
+    assert!(dir.join("in.txt").exists());
+    fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn bytes_written_quota() {
+    let dir = scratch_dir("quota");
+    let mut mount_fs = MountFs::new().mount(&dir, "/data", false).unwrap().max_bytes_written(8);
+
+    run(
+        "from pathlib import Path\nPath('/data/a.txt').write_text('12345')",
+        &mut mount_fs,
+    )
+    .unwrap();
+    assert_eq!(
+        run_err(
+            "from pathlib import Path\nPath('/data/b.txt').write_text('12345')",
+            &mut mount_fs
+        ),
+        (
+            ExcType::OSError,
+            "[Errno 122] Disk quota exceeded: '/data/b.txt'".to_owned()
+        )
+    );
+    // overwriting still counts, since the quota limits bytes written rather than stored
+    assert_eq!(
+        run_err("f = open('/data/a.txt', 'w')\nf.write('abcd')", &mut mount_fs).0,
+        ExcType::OSError
+    );
+    assert!(!dir.join("b.txt").exists());
+    assert_eq!(mount_fs.bytes_written(), 5);
+    fs::remove_dir_all(dir).unwrap();
+}
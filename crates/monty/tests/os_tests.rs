@@ -4,7 +4,10 @@
 //! `RunProgress::OsCall` with the correct `OsFunction` variant and arguments,
 //! and that return values are correctly used by Python code.
 
-use monty::{MontyObject, MontyRun, NoLimitTracker, OsFunction, PrintWriter, RunProgress, file_stat};
+use monty::{
+    ExcType, MontyException, MontyObject, MontyRepl, MontyRun, NoLimitTracker, OsFunction, PrintWriter, ReplProgress,
+    RunProgress, file_stat,
+};
 
 /// Helper to run code and extract the OsCall progress.
 ///
@@ -36,6 +39,16 @@ fn run_to_oscall(code: &str) -> (OsFunction, Vec<MontyObject>) {
                 | OsFunction::Rename => MontyObject::None,
                 OsFunction::Getenv => MontyObject::String("mock_env_value".to_owned()),
                 OsFunction::GetEnviron => MontyObject::Dict(vec![].into()),
+                OsFunction::Urandom => MontyObject::Bytes(vec![0; 16]),
+                OsFunction::Open => MontyObject::Int(3),
+                OsFunction::FileRead | OsFunction::FileReadline => MontyObject::String(String::new()),
+                OsFunction::FileWrite | OsFunction::FileSeek | OsFunction::FileTell => MontyObject::Int(0),
+                OsFunction::FileClose => MontyObject::None,
+                OsFunction::Input => MontyObject::String(String::new()),
+                OsFunction::Glob | OsFunction::Rglob | OsFunction::Walk => MontyObject::List(vec![]),
+                OsFunction::Samefile => MontyObject::Bool(true),
+                OsFunction::Touch | OsFunction::Rmtree => MontyObject::None,
+                OsFunction::Copy | OsFunction::Copytree => MontyObject::String("mock".to_owned()),
             };
             let function = call.function;
             let args = call.args.clone();
@@ -137,6 +150,52 @@ fn path_absolute() {
     assert_eq!(args, vec![MontyObject::Path("./relative".to_owned())]);
 }
 
+#[test]
+fn path_glob() {
+    let (func, args) = run_to_oscall("from pathlib import Path; Path('/tmp').glob('*.txt')");
+    assert_eq!(func, OsFunction::Glob);
+    assert_eq!(
+        args,
+        vec![
+            MontyObject::Path("/tmp".to_owned()),
+            MontyObject::String("*.txt".to_owned())
+        ]
+    );
+}
+
+#[test]
+fn path_rglob() {
+    let (func, args) = run_to_oscall("from pathlib import Path; Path('/tmp').rglob('*.py')");
+    assert_eq!(func, OsFunction::Rglob);
+    assert_eq!(
+        args,
+        vec![
+            MontyObject::Path("/tmp".to_owned()),
+            MontyObject::String("*.py".to_owned())
+        ]
+    );
+}
+
+#[test]
+fn path_touch() {
+    let (func, args) = run_to_oscall("from pathlib import Path; Path('/tmp/new.txt').touch()");
+    assert_eq!(func, OsFunction::Touch);
+    assert_eq!(args, vec![MontyObject::Path("/tmp/new.txt".to_owned())]);
+}
+
+#[test]
+fn path_samefile() {
+    let (func, args) = run_to_oscall("from pathlib import Path; Path('/tmp/a').samefile('/tmp/b')");
+    assert_eq!(func, OsFunction::Samefile);
+    assert_eq!(
+        args,
+        vec![
+            MontyObject::Path("/tmp/a".to_owned()),
+            MontyObject::String("/tmp/b".to_owned())
+        ]
+    );
+}
+
 // =============================================================================
 // Path argument handling (spaces, unicode, concatenation)
 // =============================================================================
@@ -457,3 +516,207 @@ import os
     assert_eq!(func, OsFunction::GetEnviron);
     assert_eq!(result, MontyObject::Bool(true));
 }
+
+// =============================================================================
+// os.walk() and shutil tests
+// =============================================================================
+
+#[test]
+fn os_walk_passes_top_as_path() {
+    let (func, args) = run_to_oscall("import os; os.walk('/tmp')");
+    assert_eq!(func, OsFunction::Walk);
+    assert_eq!(args, vec![MontyObject::Path("/tmp".to_owned())]);
+}
+
+#[test]
+fn os_walk_result_iterated() {
+    let code = r"
+import os
+files = []
+for dirpath, dirnames, filenames in os.walk('/tmp'):
+    files.extend(dirpath + '/' + name for name in filenames)
+files
+";
+    let walk = MontyObject::List(vec![
+        MontyObject::Tuple(vec![
+            MontyObject::String("/tmp".to_owned()),
+            MontyObject::List(vec![MontyObject::String("sub".to_owned())]),
+            MontyObject::List(vec![MontyObject::String("a.txt".to_owned())]),
+        ]),
+        MontyObject::Tuple(vec![
+            MontyObject::String("/tmp/sub".to_owned()),
+            MontyObject::List(vec![]),
+            MontyObject::List(vec![MontyObject::String("b.txt".to_owned())]),
+        ]),
+    ]);
+    let (func, _, result) = run_oscall_with_result(code, walk);
+    assert_eq!(func, OsFunction::Walk);
+    assert_eq!(
+        result,
+        MontyObject::List(vec![
+            MontyObject::String("/tmp/a.txt".to_owned()),
+            MontyObject::String("/tmp/sub/b.txt".to_owned()),
+        ])
+    );
+}
+
+#[test]
+fn shutil_copy_passes_paths() {
+    let (func, args) = run_to_oscall("import shutil; from pathlib import Path; shutil.copy('/tmp/a', Path('/tmp/b'))");
+    assert_eq!(func, OsFunction::Copy);
+    assert_eq!(
+        args,
+        vec![
+            MontyObject::Path("/tmp/a".to_owned()),
+            MontyObject::Path("/tmp/b".to_owned())
+        ]
+    );
+}
+
+#[test]
+fn shutil_copytree() {
+    let (func, args) = run_to_oscall("import shutil; shutil.copytree('/tmp/a', '/tmp/b', dirs_exist_ok=True)");
+    assert_eq!(func, OsFunction::Copytree);
+    assert_eq!(
+        args,
+        vec![
+            MontyObject::Path("/tmp/a".to_owned()),
+            MontyObject::Path("/tmp/b".to_owned())
+        ]
+    );
+}
+
+#[test]
+fn shutil_rmtree() {
+    let (func, args) = run_to_oscall("import shutil; shutil.rmtree('/tmp/dir')");
+    assert_eq!(func, OsFunction::Rmtree);
+    assert_eq!(args, vec![MontyObject::Path("/tmp/dir".to_owned())]);
+}
+
+// =============================================================================
+// open() and file object tests
+// =============================================================================
+
+#[test]
+fn open_yields_oscall() {
+    let (func, args) = run_to_oscall("open('/tmp/file.txt', 'rb+')");
+    assert_eq!(func, OsFunction::Open);
+    // the mode is normalized for the host
+    assert_eq!(
+        args,
+        vec![
+            MontyObject::Path("/tmp/file.txt".to_owned()),
+            MontyObject::String("r+b".to_owned())
+        ]
+    );
+}
+
+#[test]
+fn file_read_uses_handle() {
+    let code = r"
+from pathlib import Path
+f = Path('/tmp/file.txt').open()
+f.read()
+";
+    let runner = MontyRun::new(code.to_owned(), "test.py", vec![]).unwrap();
+    let progress = runner.start(vec![], NoLimitTracker, PrintWriter::Stdout).unwrap();
+    let RunProgress::OsCall(call) = progress else {
+        panic!("expected OsCall, got {progress:?}");
+    };
+    assert_eq!(call.function, OsFunction::Open);
+    let progress = call.resume(MontyObject::Int(7), PrintWriter::Stdout).unwrap();
+    let RunProgress::OsCall(call) = progress else {
+        panic!("expected OsCall, got {progress:?}");
+    };
+    assert_eq!(call.function, OsFunction::FileRead);
+    assert_eq!(call.args, vec![MontyObject::Int(7), MontyObject::Int(-1)]);
+    let progress = call
+        .resume(MontyObject::String("contents".to_owned()), PrintWriter::Stdout)
+        .unwrap();
+    let result = progress.into_complete().expect("expected Complete after resume");
+    assert_eq!(result, MontyObject::String("contents".to_owned()));
+}
+
+// =============================================================================
+// input() tests
+// =============================================================================
+
+#[test]
+fn input_yields_oscall_with_prompt() {
+    let (func, args) = run_to_oscall("input(42)");
+    assert_eq!(func, OsFunction::Input);
+    assert_eq!(args, vec![MontyObject::String("42".to_owned())]);
+    let (_, args) = run_to_oscall("input()");
+    assert_eq!(args, vec![MontyObject::String(String::new())]);
+}
+
+#[test]
+fn input_strips_trailing_newline() {
+    let (_, _, result) = run_oscall_with_result("input('> ')", MontyObject::String("hello\r\n".to_owned()));
+    assert_eq!(result, MontyObject::String("hello".to_owned()));
+    let (_, _, result) = run_oscall_with_result("input('> ')", MontyObject::String("hello".to_owned()));
+    assert_eq!(result, MontyObject::String("hello".to_owned()));
+}
+
+#[test]
+fn input_result_must_be_str() {
+    let runner = MontyRun::new("input()".to_owned(), "test.py", vec![]).unwrap();
+    let progress = runner.start(vec![], NoLimitTracker, PrintWriter::Stdout).unwrap();
+    let call = progress.into_os_call().expect("expected OsCall");
+    let err = call.resume(MontyObject::Int(1), PrintWriter::Stdout).unwrap_err();
+    assert_eq!(err.exc_type(), ExcType::TypeError);
+    assert_eq!(err.message(), Some("input result must be str, not int"));
+}
+
+#[test]
+fn input_eof_error_from_host() {
+    let code = r"
+try:
+    input()
+except EOFError as e:
+    result = str(e)
+result
+";
+    let runner = MontyRun::new(code.to_owned(), "test.py", vec![]).unwrap();
+    let progress = runner.start(vec![], NoLimitTracker, PrintWriter::Stdout).unwrap();
+    let call = progress.into_os_call().expect("expected OsCall");
+    let eof = MontyException::new(ExcType::EOFError, Some("no more input".to_owned()));
+    let result = call.resume(eof, PrintWriter::Stdout).unwrap().into_complete();
+    assert_eq!(result, Some(MontyObject::String("no more input".to_owned())));
+}
+
+#[test]
+fn input_resumes_after_dump_load() {
+    let runner = MontyRun::new("input('a: ') + input('b: ')".to_owned(), "test.py", vec![]).unwrap();
+    let mut progress = runner.start(vec![], NoLimitTracker, PrintWriter::Stdout).unwrap();
+    for line in ["x\n", "y\n"] {
+        let loaded: RunProgress<NoLimitTracker> = RunProgress::load(&progress.dump().unwrap()).unwrap();
+        let call = loaded.into_os_call().expect("expected OsCall");
+        assert_eq!(call.function, OsFunction::Input);
+        progress = call
+            .resume(MontyObject::String(line.to_owned()), PrintWriter::Stdout)
+            .unwrap();
+    }
+    assert_eq!(progress.into_complete(), Some(MontyObject::String("xy".to_owned())));
+}
+
+#[test]
+fn input_in_repl_after_dump_load() {
+    let repl = MontyRepl::new("repl.py", NoLimitTracker);
+    let progress = repl
+        .feed_start("name = input('name? ')", vec![], PrintWriter::Stdout)
+        .unwrap();
+    let loaded: ReplProgress<NoLimitTracker> = ReplProgress::load(&progress.dump().unwrap()).unwrap();
+    let ReplProgress::OsCall(call) = loaded else {
+        panic!("expected OsCall, got {loaded:?}");
+    };
+    assert_eq!(call.function, OsFunction::Input);
+    assert_eq!(call.args, vec![MontyObject::String("name? ".to_owned())]);
+    let progress = call
+        .resume(MontyObject::String("monty\n".to_owned()), PrintWriter::Stdout)
+        .unwrap();
+    let (repl, _) = progress.into_complete().expect("expected Complete");
+    let progress = repl.feed_start("name", vec![], PrintWriter::Stdout).unwrap();
+    let (_, value) = progress.into_complete().expect("expected Complete");
+    assert_eq!(value, MontyObject::String("monty".to_owned()));
+}
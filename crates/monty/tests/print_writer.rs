@@ -1,4 +1,6 @@
-use monty::{MontyRun, NoLimitTracker, PrintWriter};
+use std::borrow::Cow;
+
+use monty::{MontyException, MontyRun, NoLimitTracker, PrintWriter, PrintWriterCallback};
 
 #[test]
 fn print_single_string() {
@@ -236,3 +238,98 @@ fn print_multiline_sep() {
         .unwrap();
     assert_eq!(output, "1\n2\n3\n");
 }
+
+/// Collects stdout and stderr separately.
+#[derive(Default)]
+struct StreamCollector {
+    stdout: String,
+    stderr: String,
+}
+
+impl PrintWriterCallback for StreamCollector {
+    fn stdout_write(&mut self, output: Cow<'_, str>) -> Result<(), MontyException> {
+        self.stdout.push_str(&output);
+        Ok(())
+    }
+
+    fn stdout_push(&mut self, end: char) -> Result<(), MontyException> {
+        self.stdout.push(end);
+        Ok(())
+    }
+
+    fn stderr_write(&mut self, output: Cow<'_, str>) -> Result<(), MontyException> {
+        self.stderr.push_str(&output);
+        Ok(())
+    }
+
+    fn stderr_push(&mut self, end: char) -> Result<(), MontyException> {
+        self.stderr.push(end);
+        Ok(())
+    }
+}
+
+/// Only implements the required stdout methods.
+#[derive(Default)]
+struct StdoutOnly(String);
+
+impl PrintWriterCallback for StdoutOnly {
+    fn stdout_write(&mut self, output: Cow<'_, str>) -> Result<(), MontyException> {
+        self.0.push_str(&output);
+        Ok(())
+    }
+
+    fn stdout_push(&mut self, end: char) -> Result<(), MontyException> {
+        self.0.push(end);
+        Ok(())
+    }
+}
+
+#[test]
+fn print_file_streams_separated() {
+    let code = "
+import sys
+print('out', 1)
+print('err', 2, file=sys.stderr)
+sys.stderr.write('warn\\n')
+sys.stdout.write('done\\n')
+print('explicit', file=sys.stdout)
+";
+    let ex = MontyRun::new(code.to_owned(), "test.py", vec![]).unwrap();
+    let mut collector = StreamCollector::default();
+    ex.run(vec![], NoLimitTracker, PrintWriter::Callback(&mut collector))
+        .unwrap();
+    assert_eq!(collector.stdout, "out 1\ndone\nexplicit\n");
+    assert_eq!(collector.stderr, "err 2\nwarn\n");
+}
+
+#[test]
+fn print_stderr_collect_interleaves() {
+    let code = "import sys\nprint('a')\nprint('b', file=sys.stderr)\nprint('c')";
+    let ex = MontyRun::new(code.to_owned(), "test.py", vec![]).unwrap();
+    let mut output = String::new();
+    ex.run(vec![], NoLimitTracker, PrintWriter::Collect(&mut output))
+        .unwrap();
+    assert_eq!(output, "a\nb\nc\n");
+}
+
+#[test]
+fn stderr_defaults_to_stdout_callback() {
+    let code = "import sys\nprint('a')\nprint('b', file=sys.stderr)";
+    let ex = MontyRun::new(code.to_owned(), "test.py", vec![]).unwrap();
+    let mut collector = StdoutOnly::default();
+    ex.run(vec![], NoLimitTracker, PrintWriter::Callback(&mut collector))
+        .unwrap();
+    assert_eq!(collector.0, "a\nb\n");
+}
+
+#[test]
+fn stderr_traceback() {
+    let ex = MontyRun::new("1 / 0".to_owned(), "test.py", vec![]).unwrap();
+    let mut collector = StreamCollector::default();
+    let mut writer = PrintWriter::Callback(&mut collector);
+    let err = ex.run(vec![], NoLimitTracker, writer.reborrow()).unwrap_err();
+    writer.stderr_traceback(&err).unwrap();
+    assert_eq!(collector.stdout, "");
+    assert_eq!(collector.stderr, format!("{err}\n"));
+    assert!(collector.stderr.starts_with("Traceback (most recent call last):\n"));
+}
@@ -10,7 +10,12 @@
 /// that grow unboundedly — a denial-of-service vector. Monty uses `fancy_regex`
 /// which enforces a default 1M-step backtrack limit, raising `re.PatternError`
 /// when exceeded. This is strictly better behavior for a sandbox.
-use monty::MontyRun;
+///
+/// When the run has a time limit and little time remains, the backtrack limit is lowered
+/// to fit the remaining time, and exceeding it raises an uncatchable `TimeoutError`.
+use std::time::Duration;
+
+use monty::{ExcType, LimitedTracker, MontyObject, MontyRun, PrintWriter, ResourceLimits};
 
 /// Helper to run Python code and return the string result.
 fn run(code: &str) -> String {
@@ -88,3 +93,71 @@ assert m is None, 'no match expected'
 ");
     assert_eq!(result, "ok");
 }
+
+/// Verify that under a time limit, the backtrack limit comes from the remaining time
+/// and exceeding it is a `TimeoutError` that `except` clauses can't swallow.
+///
+/// The pattern is the one from `backtrack_limit_prevents_redos`, which raises
+/// `re.PatternError` when no time limit is configured.
+#[test]
+fn time_limit_replaces_backtrack_limit() {
+    let code = r"
+import re
+try:
+    re.search(r'((a+)\2)+b', 'a' * 60 + 'c')
+except Exception:
+    pass
+'no error'
+";
+    let ex = MontyRun::new(code.to_owned(), "test.py", vec![]).unwrap();
+    let limits = ResourceLimits::new().max_duration(Duration::from_millis(50));
+    let exc = ex
+        .run(vec![], LimitedTracker::new(limits), PrintWriter::Stdout)
+        .unwrap_err();
+    assert_eq!(exc.exc_type(), ExcType::TimeoutError, "got: {exc}");
+}
+
+/// Verify that the classic `(a+)+$` attack runs in linear time under a time limit.
+///
+/// Without fancy features the pattern goes to the `regex` crate, so even a long subject
+/// is rejected well within the limit.
+#[test]
+fn nested_quantifier_attack_under_time_limit() {
+    let code = r"
+import re
+re.search(r'(a+)+$', 'a' * 100000 + '!') is None
+";
+    let ex = MontyRun::new(code.to_owned(), "test.py", vec![]).unwrap();
+    let limits = ResourceLimits::new().max_duration(Duration::from_secs(5));
+    let result = ex
+        .run(vec![], LimitedTracker::new(limits), PrintWriter::Stdout)
+        .unwrap();
+    assert_eq!(result, MontyObject::Bool(true));
+}
+
+/// Verify that a generous time limit doesn't raise the backtrack limit above the default.
+///
+/// The lookbehind forces `(a+)+$` onto the backtracking VM, where it exceeds the default
+/// limit. With plenty of time remaining that limit still applies, so the search fails
+/// quickly with a catchable `re.PatternError`.
+#[test]
+fn time_limit_keeps_default_backtrack_limit() {
+    let code = r"
+import re
+try:
+    re.search(r'(a+)+$(?<!b)', 'a' * 40 + '!')
+    result = 'no error'
+except re.PatternError as e:
+    result = str(e)
+result
+";
+    let ex = MontyRun::new(code.to_owned(), "test.py", vec![]).unwrap();
+    let limits = ResourceLimits::new().max_duration(Duration::from_secs(30));
+    let result = ex
+        .run(vec![], LimitedTracker::new(limits), PrintWriter::Stdout)
+        .unwrap();
+    assert_eq!(
+        result,
+        MontyObject::String("Error executing regex: Max limit for backtracking count exceeded".to_owned())
+    );
+}
@@ -342,3 +342,39 @@ fn repl_start_new_external_function_in_later_block() {
     assert_eq!(feed_run_print(&mut repl, "x").unwrap(), MontyObject::Int(10));
     assert_eq!(feed_run_print(&mut repl, "y").unwrap(), MontyObject::Int(15));
 }
+
+#[test]
+fn repl_sys_argv() {
+    // like CPython's interactive interpreter, `sys.argv` is `['']`
+    let mut repl = MontyRepl::new("repl.py", NoLimitTracker);
+    feed_run_print(&mut repl, "import sys").unwrap();
+    let output = feed_run_print(&mut repl, "sys.argv").unwrap();
+    assert_eq!(output, MontyObject::List(vec![MontyObject::String(String::new())]));
+}
+
+#[test]
+fn repl_module_dunders() {
+    let mut repl = MontyRepl::new("repl.py", NoLimitTracker);
+    let output = feed_run_print(&mut repl, "__name__").unwrap();
+    assert_eq!(output, MontyObject::String("__main__".to_owned()));
+    // as in CPython's interactive interpreter, there's no docstring or file
+    feed_run_print(&mut repl, "'not a docstring'").unwrap();
+    assert_eq!(feed_run_print(&mut repl, "__doc__").unwrap(), MontyObject::None);
+    let err = feed_run_print(&mut repl, "__file__").unwrap_err();
+    assert_eq!(err.exc_type(), monty::ExcType::NameError);
+
+    repl.set_module_name("notebook".to_owned());
+    let output = feed_run_print(&mut repl, "__name__ == '__main__'").unwrap();
+    assert_eq!(output, MontyObject::Bool(false));
+
+    // once assigned, a dunder is an ordinary global in later snippets too
+    feed_run_print(&mut repl, "def get_doc():\n    return __doc__\n__doc__ = 'assigned'").unwrap();
+    assert_eq!(
+        feed_run_print(&mut repl, "get_doc()").unwrap(),
+        MontyObject::String("assigned".to_owned())
+    );
+    assert_eq!(
+        feed_run_print(&mut repl, "__doc__").unwrap(),
+        MontyObject::String("assigned".to_owned())
+    );
+}
@@ -1718,6 +1718,141 @@ re.sub('(a+)+\\1b', 'X', 'a' * 30 + 'c')
     );
 }
 
+/// Runs `code`, under a time limit if `max_duration` is given.
+fn run_with_time_limit(code: &str, max_duration: Option<Duration>) -> Result<MontyObject, monty::MontyException> {
+    let ex = MontyRun::new(code.to_owned(), "test.py", vec![]).unwrap();
+    match max_duration {
+        Some(limit) => ex.run(
+            vec![],
+            LimitedTracker::new(ResourceLimits::new().max_duration(limit)),
+            PrintWriter::Stdout,
+        ),
+        None => ex.run_no_limits(vec![]),
+    }
+}
+
+/// Backreference pattern that needs about 655k backtracking steps to reject `'a' * 32 + 'c'`.
+///
+/// The backreference forces `fancy_regex` onto its backtracking VM, and the step count
+/// is under the engine's default limit of 1M, so without a time limit the search simply
+/// fails to match. A 20ms time limit gives a budget of at most 262144 steps however fast
+/// the host is, so the search can't finish within it.
+const SLOW_BACKREF_PATTERN: &str = r"((a+)\2)+b";
+
+/// Test that a regex within the default backtrack limit is still stopped by a small time limit.
+///
+/// The VM can't check the time limit while the regex runs, so the backtrack budget derived
+/// from `max_duration` must stop it. Like other resource errors, the resulting `TimeoutError`
+/// can't be caught.
+#[test]
+fn re_catastrophic_backtracking_respects_time_limit() {
+    let code = format!(
+        "
+import re
+try:
+    m = re.search(r'{SLOW_BACKREF_PATTERN}', 'a' * 32 + 'c')
+except TimeoutError:
+    m = 'caught'
+m is None
+"
+    );
+
+    let result = run_with_time_limit(&code, None);
+    assert_eq!(
+        result.unwrap(),
+        MontyObject::Bool(true),
+        "the default backtrack limit should not stop this pattern"
+    );
+
+    let exc = run_with_time_limit(&code, Some(Duration::from_millis(20))).unwrap_err();
+    assert_eq!(exc.exc_type(), ExcType::TimeoutError, "got: {exc}");
+    assert!(
+        exc.message().is_some_and(|m| m.starts_with("time limit exceeded:")),
+        "expected time limit error, got: {exc}"
+    );
+}
+
+/// Test that a compiled pattern reused under a time limit is stopped.
+///
+/// The pattern is compiled with a budget for the time remaining then, and a busy loop
+/// uses up some more before it runs, so its backtrack limit has to be recomputed.
+#[test]
+fn re_compiled_pattern_backtracking_respects_time_limit() {
+    let code = format!(
+        "
+import re
+p = re.compile(r'{SLOW_BACKREF_PATTERN}')
+for i in range(100):
+    pass
+p.fullmatch('a' * 32 + 'c') is None
+"
+    );
+
+    let result = run_with_time_limit(&code, None);
+    assert_eq!(result.unwrap(), MontyObject::Bool(true));
+
+    let exc = run_with_time_limit(&code, Some(Duration::from_millis(20))).unwrap_err();
+    assert_eq!(exc.exc_type(), ExcType::TimeoutError, "got: {exc}");
+}
+
+/// Test that a classic `(a+)+$` attack can't run past a time limit.
+///
+/// Each search is stopped by the backtrack limit or the time limit, whichever comes first,
+/// so looping over the attack ends with a `TimeoutError` rather than running forever.
+#[test]
+fn re_nested_quantifier_attack_respects_time_limit() {
+    let code = r"
+import re
+while True:
+    try:
+        re.search(r'(a+)+$(?<!b)', 'a' * 40 + '!')
+    except re.PatternError:
+        pass
+";
+    let exc = run_with_time_limit(code, Some(Duration::from_millis(500))).unwrap_err();
+    assert_eq!(exc.exc_type(), ExcType::TimeoutError, "got: {exc}");
+}
+
+/// Test that a regex with many matches, each within its own budget, stops at the time limit.
+///
+/// Each match of `SLOW_BACKREF_PATTERN|x` against a `'a' * 32 + 'cx'` chunk takes about
+/// 655k steps before matching the `x`, which fits in the default backtrack limit that a
+/// 500ms time limit leaves in place. Only the time check between matches stops `findall`
+/// before it has worked through every chunk.
+#[test]
+fn re_many_matches_respect_time_limit() {
+    let code = |chunks: usize| {
+        format!(
+            "
+import re
+len(re.findall(r'{SLOW_BACKREF_PATTERN}|x', ('a' * 32 + 'cx') * {chunks})) == {chunks}
+"
+        )
+    };
+
+    let result = run_with_time_limit(&code(1), None);
+    assert_eq!(result.unwrap(), MontyObject::Bool(true));
+
+    let exc = run_with_time_limit(&code(1000), Some(Duration::from_millis(500))).unwrap_err();
+    assert_eq!(exc.exc_type(), ExcType::TimeoutError, "got: {exc}");
+}
+
+/// Test that well-behaved regexes needing the backtracking VM still work under a time limit.
+#[test]
+fn re_lookaround_pattern_passes_with_time_limit() {
+    let code = r"
+import re
+s = 'foo1 bar2 baz3 ' * 1000
+matches = re.findall(r'(?<= )\w+(?=\d)', s)
+len(matches) == 2999 and re.sub(r'(\w)\1', 'X', 'aabbc') == 'XXc'
+";
+    let ex = MontyRun::new(code.to_owned(), "test.py", vec![]).unwrap();
+
+    let limits = ResourceLimits::new().max_duration(Duration::from_secs(5));
+    let result = ex.run(vec![], LimitedTracker::new(limits), PrintWriter::Stdout);
+    assert_eq!(result.unwrap(), MontyObject::Bool(true));
+}
+
 // --- Selective patterns: few matches in large text stay within limits ---
 
 /// Test that a selective pattern on large text passes.
@@ -1824,3 +1959,58 @@ re.sub('a', 'b' * 100, 'a' * 1000, count=5) == 'b' * 500 + 'a' * 995
     assert!(result.is_ok(), "re.sub with small count should succeed");
     assert_eq!(result.unwrap(), MontyObject::Bool(true));
 }
+
+// --- zlib tests ---
+
+/// Builds code that decompresses a ~10KB zlib stream expanding to 10MB of zero bytes.
+fn zip_bomb_code(module_call: &str, compressed: &[u8]) -> String {
+    let hex: String = compressed.iter().map(|b| format!("{b:02x}")).collect();
+    format!("{module_call}(bytes.fromhex('{hex}'))")
+}
+
+/// Test that `zlib.decompress` of a small input that expands hugely is stopped by the memory limit.
+#[test]
+fn zlib_decompress_bomb_memory_limit() {
+    let compressed = miniz_oxide::deflate::compress_to_vec_zlib(&vec![0; 10_000_000], 9);
+    let code = format!("import zlib\n{}", zip_bomb_code("zlib.decompress", &compressed));
+    let ex = MontyRun::new(code, "test.py", vec![]).unwrap();
+
+    let limits = ResourceLimits::new().max_memory(1_000_000);
+    let result = ex.run(vec![], LimitedTracker::new(limits), PrintWriter::Stdout);
+
+    let exc = result.expect_err("zip bomb should be rejected");
+    assert_eq!(exc.exc_type(), ExcType::MemoryError);
+}
+
+/// Test that `gzip.decompress` checks the output of every member against the memory limit.
+#[test]
+fn gzip_decompress_bomb_memory_limit() {
+    let deflated = miniz_oxide::deflate::compress_to_vec(&vec![0; 10_000_000], 9);
+    let mut member = vec![0x1f, 0x8b, 8, 0, 0, 0, 0, 0, 2, 255];
+    member.extend_from_slice(&deflated);
+    // the trailer is never reached, so its contents don't matter
+    member.extend_from_slice(&[0; 8]);
+    let code = format!("import gzip\n{}", zip_bomb_code("gzip.decompress", &member));
+    let ex = MontyRun::new(code, "test.py", vec![]).unwrap();
+
+    let limits = ResourceLimits::new().max_memory(1_000_000);
+    let result = ex.run(vec![], LimitedTracker::new(limits), PrintWriter::Stdout);
+
+    let exc = result.expect_err("zip bomb should be rejected");
+    assert_eq!(exc.exc_type(), ExcType::MemoryError);
+}
+
+/// Test that decompressing a small stream works within limits.
+#[test]
+fn zlib_decompress_within_limit() {
+    let code = r"
+import zlib
+zlib.decompress(zlib.compress(b'x' * 10000)) == b'x' * 10000
+";
+    let ex = MontyRun::new(code.to_owned(), "test.py", vec![]).unwrap();
+
+    let limits = ResourceLimits::new().max_memory(500_000);
+    let result = ex.run(vec![], LimitedTracker::new(limits), PrintWriter::Stdout);
+
+    assert_eq!(result.unwrap(), MontyObject::Bool(true));
+}
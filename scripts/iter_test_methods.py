@@ -11,10 +11,12 @@ This module is shared between:
 
 from __future__ import annotations
 
+import io
 import os
+import shutil
 import stat as stat_module
 from dataclasses import dataclass
-from pathlib import Path
+from pathlib import Path, PurePosixPath
 
 
 def add_ints(a: int, b: int) -> int:
@@ -229,6 +231,46 @@ class VirtualPath(type(Path())):
             return False  # No symlinks in virtual fs
         return super().is_symlink()
 
+    def samefile(self, other_path: str | os.PathLike[str]) -> bool:  # pyright: ignore[reportIncompatibleMethodOverride]
+        path_str = str(self)
+        if is_virtual_path(path_str):
+            other_str = os.fspath(other_path)
+            for p in (path_str, other_str):
+                if p not in VIRTUAL_FILES and p not in VIRTUAL_DIRS:
+                    raise FileNotFoundError(2, 'No such file or directory', p)
+            return path_str == other_str
+        return super().samefile(other_path)
+
+    def glob(  # pyright: ignore[reportIncompatibleMethodOverride]
+        self, pattern: str, *, case_sensitive: bool | None = None, recurse_symlinks: bool = False
+    ):
+        path_str = str(self)
+        if is_virtual_path(path_str):
+            for entry in _virtual_descendants(path_str):
+                if PurePosixPath(entry[len(path_str) + 1 :]).full_match(pattern):
+                    yield VirtualPath(entry)
+            return
+        yield from super().glob(pattern, case_sensitive=case_sensitive, recurse_symlinks=recurse_symlinks)
+
+    def rglob(  # pyright: ignore[reportIncompatibleMethodOverride]
+        self, pattern: str, *, case_sensitive: bool | None = None, recurse_symlinks: bool = False
+    ):
+        return self.glob(f'**/{pattern}', case_sensitive=case_sensitive, recurse_symlinks=recurse_symlinks)
+
+    def touch(self, mode: int = 0o666, exist_ok: bool = True) -> None:
+        path_str = str(self)
+        if is_virtual_path(path_str):
+            if path_str in VIRTUAL_FILES or path_str in VIRTUAL_DIRS:
+                if not exist_ok:
+                    raise FileExistsError(17, 'File exists', path_str)
+                return
+            if str(self.parent) not in VIRTUAL_DIRS:
+                raise FileNotFoundError(2, 'No such file or directory', path_str)
+            VIRTUAL_FILES[path_str] = (b'', 0o644)
+            _add_to_parent_dir(path_str)
+            return
+        super().touch(mode=mode, exist_ok=exist_ok)
+
     def read_text(self, encoding: str | None = None, errors: str | None = None, newline: str | None = None) -> str:
         path_str = str(self)
         if is_virtual_path(path_str):
@@ -238,6 +280,16 @@ class VirtualPath(type(Path())):
             raise FileNotFoundError(2, 'No such file or directory', path_str)
         return super().read_text(encoding=encoding, errors=errors, newline=newline)
 
+    def open(  # pyright: ignore[reportIncompatibleMethodOverride]
+        self,
+        mode: str = 'r',
+        buffering: int = -1,
+        encoding: str | None = None,
+        errors: str | None = None,
+        newline: str | None = None,
+    ) -> io.IOBase:
+        return open(self, mode, buffering, encoding, errors, newline)
+
     def read_bytes(self) -> bytes:
         path_str = str(self)
         if is_virtual_path(path_str):
@@ -402,6 +454,16 @@ def _remove_from_parent_dir(path_str: str) -> None:
         VIRTUAL_DIR_CONTENTS[parent].remove(path_str)
 
 
+def _virtual_descendants(path_str: str) -> list[str]:
+    """List every file and directory below a virtual directory, parents before their children."""
+    descendants: list[str] = []
+    for child in sorted(VIRTUAL_DIR_CONTENTS.get(path_str, [])):
+        descendants.append(child)
+        if child in VIRTUAL_DIRS:
+            descendants.extend(_virtual_descendants(child))
+    return descendants
+
+
 # Monkey-patch pathlib.Path to use VirtualPath
 # This is done so tests can use `from pathlib import Path` and get VirtualPath behavior
 _original_path_new = Path.__new__
@@ -424,6 +486,234 @@ def _virtual_path_new(cls: type, *args: object, **kwargs: object) -> Path:
 Path.__new__ = _virtual_path_new
 
 
+class _VirtualRawFile(io.RawIOBase):
+    """Raw file over a virtual file's contents, stored back in `VIRTUAL_FILES` when closed.
+
+    `open()` wraps it in the same buffered and text layers as a real `FileIO`, so file
+    objects have the same types, reprs and behaviour as those for real files.
+    """
+
+    def __init__(self, path_str: str, mode: str):
+        super().__init__()
+        self.name = path_str
+        # set before anything can fail, as close() is still called when the object is collected
+        self._data = bytearray()
+        self._writable = False
+        access = mode.replace('t', '').replace('b', '')
+        if path_str in VIRTUAL_DIRS:
+            raise IsADirectoryError(21, 'Is a directory', path_str)
+        exists = path_str in VIRTUAL_FILES
+        if access[0] == 'r' and not exists:
+            raise FileNotFoundError(2, 'No such file or directory', path_str)
+        if access[0] == 'x' and exists:
+            raise FileExistsError(17, 'File exists', path_str)
+        self._readable = access[0] == 'r' or '+' in access
+        self._writable = access[0] != 'r' or '+' in access
+        self._append = access[0] == 'a'
+        if access[0] in 'ra' and exists:
+            self._data = bytearray(VIRTUAL_FILES[path_str][0])
+        else:
+            # like a real open(), creating or truncating happens straight away
+            VIRTUAL_FILES[path_str] = (b'', 0o644)
+            _add_to_parent_dir(path_str)
+        self._pos = len(self._data) if self._append else 0
+        plus = '+' if '+' in access else ''
+        self.mode = {'r': 'rb', 'w': 'wb', 'a': 'ab', 'x': 'xb'}[access[0]] + plus
+        if plus and access[0] == 'w':
+            self.mode = 'rb+'
+
+    def readable(self) -> bool:
+        return self._readable
+
+    def writable(self) -> bool:
+        return self._writable
+
+    def seekable(self) -> bool:
+        return True
+
+    def readinto(self, buffer: bytearray | memoryview) -> int:  # pyright: ignore[reportIncompatibleMethodOverride]
+        chunk = self._data[self._pos : self._pos + len(buffer)]
+        buffer[: len(chunk)] = chunk
+        self._pos += len(chunk)
+        return len(chunk)
+
+    def write(self, data: bytes) -> int:  # pyright: ignore[reportIncompatibleMethodOverride]
+        if self._append:
+            self._pos = len(self._data)
+        if self._pos > len(self._data):
+            self._data.extend(bytes(self._pos - len(self._data)))
+        self._data[self._pos : self._pos + len(data)] = data
+        self._pos += len(data)
+        return len(data)
+
+    def seek(self, offset: int, whence: int = 0) -> int:
+        base = {0: 0, 1: self._pos, 2: len(self._data)}[whence]
+        if base + offset < 0:
+            raise OSError(22, 'Invalid argument')
+        self._pos = base + offset
+        return self._pos
+
+    def tell(self) -> int:
+        return self._pos
+
+    def close(self) -> None:
+        if not self.closed and self._writable:
+            VIRTUAL_FILES[self.name] = (bytes(self._data), 0o644)
+        super().close()
+
+
+_original_open = io.open
+
+
+def open(  # noqa: A001
+    file: str | os.PathLike[str] | int,
+    mode: str = 'r',
+    buffering: int = -1,
+    encoding: str | None = None,
+    errors: str | None = None,
+    newline: str | None = None,
+    closefd: bool = True,
+    opener: object = None,
+) -> io.IOBase:
+    """`open()` that serves virtual paths from `VIRTUAL_FILES`, shadowing the builtin in iter mode tests."""
+    path_str = os.fspath(file) if isinstance(file, (str, os.PathLike)) else None
+    if path_str is None or not is_virtual_path(str(path_str)):
+        return _original_open(file, mode, buffering, encoding, errors, newline, closefd, opener)  # pyright: ignore[reportArgumentType,reportUnknownVariableType]
+
+    class _Validated(Exception):
+        pass
+
+    def reject(_path: str, _flags: int) -> int:
+        raise _Validated
+
+    # let the real open() check the arguments, it calls the opener once they're valid
+    try:
+        _original_open(path_str, mode, buffering, encoding, errors, newline, opener=reject)
+    except _Validated:
+        pass
+    # the real open() only checks this after opening the file
+    if buffering == 0 and 'b' not in mode:
+        raise ValueError("can't have unbuffered text I/O")
+    raw = _VirtualRawFile(str(path_str), mode)
+    if '+' in mode:
+        buffered = io.BufferedRandom(raw)
+    elif 'r' in mode:
+        buffered = io.BufferedReader(raw)
+    else:
+        buffered = io.BufferedWriter(raw)
+    if 'b' in mode:
+        return buffered
+    text = io.TextIOWrapper(buffered, encoding or 'utf-8', errors, newline)
+    text.mode = mode  # pyright: ignore[reportAttributeAccessIssue]
+    return text
+
+
+def input(prompt: object = '', /) -> str:  # noqa: A001
+    """`input()` that echoes the prompt back as the line read, shadowing the builtin in iter mode tests.
+
+    An empty prompt means the input has run out, matching the datatest runner's `OsFunction::Input` handler.
+    """
+    prompt = str(prompt)
+    if not prompt:
+        raise EOFError('EOF when reading a line')
+    return prompt
+
+
+# =============================================================================
+# Virtual os.walk and shutil Functions
+# =============================================================================
+
+# Check if already patched (happens when module is re-executed in same interpreter)
+if not hasattr(os, '_monty_original_walk'):
+    os._monty_original_walk = os.walk  # pyright: ignore[reportAttributeAccessIssue]
+    shutil._monty_originals = (shutil.copy, shutil.copytree, shutil.rmtree)  # pyright: ignore[reportAttributeAccessIssue]
+
+_original_walk = os._monty_original_walk  # pyright: ignore[reportAttributeAccessIssue,reportUnknownVariableType,reportUnknownMemberType]
+_original_copy, _original_copytree, _original_rmtree = shutil._monty_originals  # pyright: ignore[reportAttributeAccessIssue,reportUnknownVariableType,reportUnknownMemberType]
+
+
+def _virtual_walk(top: str | os.PathLike[str], topdown: bool = True, onerror: object = None, followlinks: bool = False):
+    """`os.walk()` over virtual directories, listing entries in sorted order."""
+    top_str = os.fspath(top)
+    if not is_virtual_path(top_str):
+        yield from _original_walk(top, topdown, onerror, followlinks)  # pyright: ignore[reportUnknownArgumentType]
+        return
+    if top_str not in VIRTUAL_DIRS:
+        return
+    children = sorted(VIRTUAL_DIR_CONTENTS.get(top_str, []))
+    dirnames = [child.rsplit('/', 1)[1] for child in children if child in VIRTUAL_DIRS]
+    filenames = [child.rsplit('/', 1)[1] for child in children if child not in VIRTUAL_DIRS]
+    if topdown:
+        yield top_str, dirnames, filenames
+    for name in dirnames:
+        yield from _virtual_walk(f'{top_str}/{name}', topdown, onerror, followlinks)
+    if not topdown:
+        yield top_str, dirnames, filenames
+
+
+def _virtual_copy(src: str | os.PathLike[str], dst: str | os.PathLike[str], *, follow_symlinks: bool = True) -> object:
+    """`shutil.copy()` between virtual paths."""
+    src_str, dst_str = os.fspath(src), os.fspath(dst)
+    if not is_virtual_path(src_str) and not is_virtual_path(dst_str):
+        return _original_copy(src, dst, follow_symlinks=follow_symlinks)  # pyright: ignore[reportUnknownVariableType]
+    if src_str in VIRTUAL_DIRS:
+        raise IsADirectoryError(21, 'Is a directory', src_str)
+    if src_str not in VIRTUAL_FILES:
+        raise FileNotFoundError(2, 'No such file or directory', src_str)
+    if dst_str in VIRTUAL_DIRS:
+        dst_str = f'{dst_str}/{src_str.rsplit("/", 1)[1]}'
+    if str(Path(dst_str).parent) not in VIRTUAL_DIRS:
+        raise FileNotFoundError(2, 'No such file or directory', dst_str)
+    VIRTUAL_FILES[dst_str] = VIRTUAL_FILES[src_str]
+    _add_to_parent_dir(dst_str)
+    return dst_str
+
+
+def _virtual_copytree(src: str | os.PathLike[str], dst: str | os.PathLike[str], *args: object, **kwargs: object) -> object:
+    """`shutil.copytree()` between virtual paths, only supporting `dirs_exist_ok`."""
+    src_str, dst_str = os.fspath(src), os.fspath(dst)
+    if not is_virtual_path(src_str) and not is_virtual_path(dst_str):
+        return _original_copytree(src, dst, *args, **kwargs)  # pyright: ignore[reportUnknownVariableType]
+    if src_str not in VIRTUAL_DIRS:
+        raise FileNotFoundError(2, 'No such file or directory', src_str)
+    if (dst_str in VIRTUAL_DIRS and not kwargs.get('dirs_exist_ok', False)) or dst_str in VIRTUAL_FILES:
+        raise FileExistsError(17, 'File exists', dst_str)
+    Path(dst_str).mkdir(parents=True, exist_ok=True)
+    for entry in _virtual_descendants(src_str):
+        target = dst_str + entry[len(src_str) :]
+        if entry in VIRTUAL_DIRS:
+            Path(target).mkdir(parents=True, exist_ok=True)
+        else:
+            VIRTUAL_FILES[target] = VIRTUAL_FILES[entry]
+            _add_to_parent_dir(target)
+    return dst
+
+
+def _virtual_rmtree(path: str | os.PathLike[str], ignore_errors: bool = False, *args: object, **kwargs: object) -> None:
+    """`shutil.rmtree()` of a virtual directory."""
+    path_str = os.fspath(path)
+    if not is_virtual_path(path_str):
+        return _original_rmtree(path, ignore_errors, *args, **kwargs)  # pyright: ignore[reportUnknownVariableType]
+    if path_str not in VIRTUAL_DIRS:
+        if ignore_errors:
+            return
+        if path_str in VIRTUAL_FILES:
+            raise NotADirectoryError(20, 'Not a directory', path_str)
+        raise FileNotFoundError(2, 'No such file or directory', path_str)
+    for entry in reversed(_virtual_descendants(path_str)):
+        if entry in VIRTUAL_DIRS:
+            VirtualPath(entry).rmdir()
+        else:
+            VirtualPath(entry).unlink()
+    VirtualPath(path_str).rmdir()
+
+
+os.walk = _virtual_walk
+shutil.copy = _virtual_copy
+shutil.copytree = _virtual_copytree
+shutil.rmtree = _virtual_rmtree
+
+
 # =============================================================================
 # Virtual Environment for os.getenv Tests
 # =============================================================================